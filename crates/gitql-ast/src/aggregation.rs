@@ -1,15 +1,26 @@
+use crate::environment::Environment;
 use crate::object::Group;
+use crate::object::Row;
+use crate::statement::AggregateCall;
+use crate::statement::SortingOrder;
 use crate::types::DataType;
 use crate::value::Value;
 
 use lazy_static::lazy_static;
+use std::any::Any;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 type Aggregation = fn(&str, &[String], &Group) -> Value;
 
+/// Merge the aggregation results of two parts of the same group into the result of the group
+type AggregationMerge = fn(&Value, &Value) -> Value;
+
+#[derive(Clone)]
 pub struct AggregationPrototype {
-    pub parameter: DataType,
+    /// Types of the aggregation arguments, the first one is the aggregated field
+    pub parameters: Vec<DataType>,
     pub result: DataType,
 }
 
@@ -21,120 +32,673 @@ lazy_static! {
         map.insert("sum", aggregation_sum);
         map.insert("avg", aggregation_average);
         map.insert("count", aggregation_count);
+        map.insert("group_concat", aggregation_group_concat);
+        map.insert("string_agg", aggregation_group_concat);
+        map.insert("first", aggregation_first);
+        map.insert("last", aggregation_last);
+        map.insert("any_value", aggregation_any_value);
         map
     };
 }
 
 lazy_static! {
-    pub static ref AGGREGATIONS_PROTOS: HashMap<&'static str, AggregationPrototype> = {
-        let mut map: HashMap<&'static str, AggregationPrototype> = HashMap::new();
+    /// Aggregations that can be calculated from the results of the group parts,
+    /// `avg` is not included because the average of averages is not the group average
+    pub static ref AGGREGATIONS_MERGES: HashMap<&'static str, AggregationMerge> = {
+        let mut map: HashMap<&'static str, AggregationMerge> = HashMap::new();
+        map.insert("max", merge_max);
+        map.insert("min", merge_min);
+        map.insert("sum", merge_sum);
+        map.insert("count", merge_sum);
+        map
+    };
+}
+
+lazy_static! {
+    pub static ref AGGREGATIONS_PROTOS: HashMap<&'static str, Vec<AggregationPrototype>> = {
+        let mut map: HashMap<&'static str, Vec<AggregationPrototype>> = HashMap::new();
+        // `MAX` and `MIN` have overload for each comparable type and return the same type
+        let comparable_types = [
+            DataType::Integer,
+            DataType::Float,
+            DataType::Text,
+            DataType::Date,
+            DataType::Time,
+            DataType::DateTime,
+        ];
+        let comparable_overloads: Vec<AggregationPrototype> = comparable_types
+            .iter()
+            .map(|data_type| AggregationPrototype {
+                parameters: vec![data_type.clone()],
+                result: data_type.clone(),
+            })
+            .collect();
+        map.insert("max", comparable_overloads.clone());
+        map.insert("min", comparable_overloads);
         map.insert(
-            "max",
-            AggregationPrototype {
-                parameter: DataType::Variant(vec![
-                    DataType::Integer,
-                    DataType::Float,
-                    DataType::Text,
-                    DataType::Date,
-                    DataType::Time,
-                    DataType::DateTime,
-                ]),
+            "sum",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Integer],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
-            "min",
-            AggregationPrototype {
-                parameter: DataType::Variant(vec![
-                    DataType::Integer,
-                    DataType::Float,
-                    DataType::Text,
-                    DataType::Date,
-                    DataType::Time,
-                    DataType::DateTime,
-                ]),
+            "avg",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Integer],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
-            "sum",
-            AggregationPrototype {
-                parameter: DataType::Integer,
+            "count",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
-            "avg",
-            AggregationPrototype {
-                parameter: DataType::Integer,
-                result: DataType::Integer,
-            },
+            "group_concat",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any, DataType::Optional(Box::new(DataType::Text))],
+                result: DataType::Text,
+            }],
         );
         map.insert(
-            "count",
-            AggregationPrototype {
-                parameter: DataType::Any,
-                result: DataType::Integer,
-            },
+            "string_agg",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any, DataType::Text],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "first",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "last",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "any_value",
+            vec![AggregationPrototype {
+                parameters: vec![DataType::Any],
+                result: DataType::Any,
+            }],
         );
         map
     };
 }
 
-fn aggregation_max(field_name: &str, titles: &[String], objects: &Group) -> Value {
-    let column_index = titles.iter().position(|r| r.eq(&field_name)).unwrap();
-    let mut max_value = objects.rows[0].values.get(column_index).unwrap();
-    for row in &objects.rows {
-        let field_value = &row.values.get(column_index).unwrap();
-        if max_value.compare(field_value) == Ordering::Greater {
-            max_value = field_value;
-        }
+/// Custom aggregation function that is calculated for each group by accumulating its values one by one
+///
+/// ```ignore
+/// struct Median;
+///
+/// impl AggregateFunction for Median {
+///     fn init(&self) -> Box<dyn Any + Send> {
+///         Box::new(Vec::<i64>::new())
+///     }
+///
+///     fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+///         state.downcast_mut::<Vec<i64>>().unwrap().push(value.as_int());
+///     }
+///
+///     fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+///         let mut values = state.downcast::<Vec<i64>>().unwrap();
+///         values.sort();
+///         Value::Integer(values[values.len() / 2])
+///     }
+/// }
+///
+/// env.register_aggregation(
+///     "median",
+///     AggregationPrototype { parameters: vec![DataType::Integer], result: DataType::Integer },
+///     Box::new(Median),
+/// )?;
+/// ```
+pub trait AggregateFunction: Send + Sync {
+    /// Create the initial state of the aggregation for a new group
+    fn init(&self) -> Box<dyn Any + Send>;
+    /// Update the state with the argument value of one row in the group
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value);
+    /// Calculate the aggregation result from the final state
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value;
+}
+
+/// Custom aggregation registered at runtime in the environment with its prototype
+pub type CustomAggregation = (AggregationPrototype, Arc<dyn AggregateFunction>);
+
+/// Returns true if the name is a builtin or registered custom aggregation function
+pub fn is_aggregation_function(env: &Environment, name: &str) -> bool {
+    AGGREGATIONS.contains_key(name) || env.aggregations.contains_key(name)
+}
+
+/// Returns the first prototype of the builtin or registered custom aggregation function
+pub fn aggregation_prototype(env: &Environment, name: &str) -> Option<AggregationPrototype> {
+    aggregation_prototypes(env, name).into_iter().next()
+}
+
+/// Returns all overloads of the builtin aggregation function or the prototype of the registered
+/// custom aggregation function, returns empty list if there is no aggregation with this name
+pub fn aggregation_prototypes(env: &Environment, name: &str) -> Vec<AggregationPrototype> {
+    if let Some(prototypes) = AGGREGATIONS_PROTOS.get(name) {
+        return prototypes.clone();
     }
-    max_value.clone()
+
+    env.aggregations
+        .get(name)
+        .map(|(prototype, _)| vec![prototype.clone()])
+        .unwrap_or_default()
 }
 
-fn aggregation_min(field_name: &str, titles: &[String], objects: &Group) -> Value {
-    let column_index = titles.iter().position(|r| r.eq(&field_name)).unwrap();
-    let mut min_value = objects.rows[0].values.get(column_index).unwrap();
+/// Calculate the builtin or registered custom aggregation function on the group,
+/// returns None if there is no aggregation function with this name
+pub fn execute_aggregation_function(
+    env: &Environment,
+    name: &str,
+    field_name: &str,
+    titles: &[String],
+    objects: &Group,
+) -> Option<Value> {
+    if let Some(aggregation) = AGGREGATIONS.get(name) {
+        return Some(aggregation(field_name, titles, objects));
+    }
+
+    let (_, aggregation) = env.aggregations.get(name)?;
+    accumulate_column(aggregation.as_ref(), field_name, titles, objects)
+}
+
+/// Calculate the aggregation call on the group after applying its `DISTINCT` and `ORDER BY` modifiers,
+/// returns None if there is no aggregation function with this name
+pub fn execute_aggregate_call(
+    env: &Environment,
+    call: &AggregateCall,
+    titles: &[String],
+    objects: &Group,
+) -> Option<Value> {
+    if !call.is_distinct && call.order_by.is_none() && call.separator.is_none() {
+        return execute_aggregation_function(
+            env,
+            &call.function_name,
+            &call.argument,
+            titles,
+            objects,
+        );
+    }
+
+    let column_index = titles.iter().position(|r| r.eq(&call.argument))?;
+    let mut rows: Vec<&Row> = objects.rows.iter().collect();
+
+    if let Some((field_name, sorting_order)) = &call.order_by {
+        let order_index = titles.iter().position(|r| r.eq(field_name))?;
+        rows.sort_by(|a, b| {
+            // Null values are sorted as the largest values to keep the ordering total
+            let ordering = match (&a.values[order_index], &b.values[order_index]) {
+                (Value::Null, Value::Null) => Ordering::Equal,
+                (Value::Null, _) => Ordering::Greater,
+                (_, Value::Null) => Ordering::Less,
+                (first, second) => second.compare(first),
+            };
+
+            if *sorting_order == SortingOrder::Descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    if call.is_distinct {
+        let mut seen_values: Vec<&Value> = vec![];
+        rows.retain(|row| {
+            let row: &Row = row;
+            let value = &row.values[column_index];
+            if seen_values.iter().any(|seen| seen.equals(value)) {
+                return false;
+            }
+            seen_values.push(value);
+            true
+        });
+    }
+
+    let group = Group {
+        rows: rows
+            .iter()
+            .map(|row| Row {
+                values: row.values.clone(),
+            })
+            .collect(),
+    };
+
+    if let Some(separator) = &call.separator {
+        return Some(group_concat_values(column_index, &group, separator));
+    }
+
+    execute_aggregation_function(env, &call.function_name, &call.argument, titles, &group)
+}
+
+/// Returns the accumulator of the builtin or registered custom aggregation function,
+/// used to calculate the aggregation while streaming the rows without collecting them in groups
+pub fn aggregation_accumulator(
+    env: &Environment,
+    name: &str,
+) -> Option<Arc<dyn AggregateFunction>> {
+    let accumulator: Arc<dyn AggregateFunction> = match name {
+        "max" => Arc::new(MaxAggregation),
+        "min" => Arc::new(MinAggregation),
+        "sum" => Arc::new(SumAggregation),
+        "avg" => Arc::new(AverageAggregation),
+        "count" => Arc::new(CountAggregation),
+        "group_concat" | "string_agg" => Arc::new(GroupConcatAggregation {
+            separator: ",".to_string(),
+        }),
+        "first" => Arc::new(FirstAggregation),
+        "last" => Arc::new(LastAggregation),
+        "any_value" => Arc::new(AnyValueAggregation),
+        _ => {
+            return env
+                .aggregations
+                .get(name)
+                .map(|(_, function)| function.clone())
+        }
+    };
+    Some(accumulator)
+}
+
+/// Calculate the aggregation by accumulating the values of the column in all rows of the group
+fn accumulate_rows(
+    aggregation: &dyn AggregateFunction,
+    column_index: usize,
+    objects: &Group,
+) -> Value {
+    let mut state = aggregation.init();
     for row in &objects.rows {
-        let field_value = &row.values.get(column_index).unwrap();
-        if min_value.compare(field_value) == Ordering::Less {
-            min_value = field_value;
+        if let Some(value) = row.values.get(column_index) {
+            aggregation.accumulate(&mut state, value);
         }
     }
-    min_value.clone()
+    aggregation.finish(state)
+}
+
+/// Calculate the aggregation on the column with this name, returns None if there is no such column
+fn accumulate_column(
+    aggregation: &dyn AggregateFunction,
+    field_name: &str,
+    titles: &[String],
+    objects: &Group,
+) -> Option<Value> {
+    let column_index = titles.iter().position(|r| r.eq(&field_name))?;
+    Some(accumulate_rows(aggregation, column_index, objects))
+}
+
+fn aggregation_max(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    accumulate_column(&MaxAggregation, field_name, titles, objects).unwrap_or(Value::Null)
+}
+
+fn aggregation_min(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    accumulate_column(&MinAggregation, field_name, titles, objects).unwrap_or(Value::Null)
 }
 
 fn aggregation_sum(field_name: &str, titles: &[String], objects: &Group) -> Value {
-    let mut sum: i64 = 0;
-    let column_index = titles.iter().position(|r| r.eq(&field_name)).unwrap();
-    for row in &objects.rows {
-        let field_value = &row.values.get(column_index).unwrap();
-        sum += field_value.as_int();
-    }
-    Value::Integer(sum)
+    accumulate_column(&SumAggregation, field_name, titles, objects).unwrap_or(Value::Null)
 }
 
 fn aggregation_average(field_name: &str, titles: &[String], objects: &Group) -> Value {
-    let mut sum: i64 = 0;
-    let count: i64 = objects.len().try_into().unwrap();
-    let column_index = titles.iter().position(|r| r.eq(&field_name)).unwrap();
-    for row in &objects.rows {
-        let field_value = &row.values.get(column_index).unwrap();
-        sum += field_value.as_int();
-    }
-    let avg = sum / count;
-    Value::Integer(avg)
+    accumulate_column(&AverageAggregation, field_name, titles, objects).unwrap_or(Value::Null)
 }
 
 fn aggregation_count(_field_name: &str, _titles: &[String], objects: &Group) -> Value {
     Value::Integer(objects.len() as i64)
 }
 
+fn aggregation_group_concat(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    let aggregation = GroupConcatAggregation {
+        separator: ",".to_string(),
+    };
+    accumulate_column(&aggregation, field_name, titles, objects).unwrap_or(Value::Null)
+}
+
+fn aggregation_first(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    accumulate_column(&FirstAggregation, field_name, titles, objects).unwrap_or(Value::Null)
+}
+
+fn aggregation_last(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    accumulate_column(&LastAggregation, field_name, titles, objects).unwrap_or(Value::Null)
+}
+
+fn aggregation_any_value(field_name: &str, titles: &[String], objects: &Group) -> Value {
+    accumulate_column(&AnyValueAggregation, field_name, titles, objects).unwrap_or(Value::Null)
+}
+
+/// Returns true if the aggregation result depends on the order of the rows in the group,
+/// so the rows must be sorted by the query `ORDER BY` before aggregation
+pub fn is_order_sensitive_aggregation(name: &str) -> bool {
+    name == "first" || name == "last"
+}
+
+/// Join the non null values of the column with the separator, or Null if all of them are null
+fn group_concat_values(column_index: usize, objects: &Group, separator: &str) -> Value {
+    let aggregation = GroupConcatAggregation {
+        separator: separator.to_string(),
+    };
+    accumulate_rows(&aggregation, column_index, objects)
+}
+
+/// Keep the largest value, the first value of the group is the initial one
+struct MaxAggregation;
+
+impl AggregateFunction for MaxAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(None::<Value>)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        let max_value = state.downcast_mut::<Option<Value>>().unwrap();
+        let is_larger = match max_value {
+            Some(max) => max.compare(value) == Ordering::Greater,
+            None => true,
+        };
+
+        if is_larger {
+            *max_value = Some(value.clone());
+        }
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        state
+            .downcast::<Option<Value>>()
+            .unwrap()
+            .unwrap_or(Value::Null)
+    }
+}
+
+/// Keep the smallest value, the first value of the group is the initial one
+struct MinAggregation;
+
+impl AggregateFunction for MinAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(None::<Value>)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        let min_value = state.downcast_mut::<Option<Value>>().unwrap();
+        let is_smaller = match min_value {
+            Some(min) => min.compare(value) == Ordering::Less,
+            None => true,
+        };
+
+        if is_smaller {
+            *min_value = Some(value.clone());
+        }
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        state
+            .downcast::<Option<Value>>()
+            .unwrap()
+            .unwrap_or(Value::Null)
+    }
+}
+
+struct SumAggregation;
+
+impl AggregateFunction for SumAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(0_i64)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        *state.downcast_mut::<i64>().unwrap() += value.as_int();
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        Value::Integer(*state.downcast::<i64>().unwrap())
+    }
+}
+
+/// Keep the sum and the count of the values, the average is the integer division of them
+struct AverageAggregation;
+
+impl AggregateFunction for AverageAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new((0_i64, 0_i64))
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        let (sum, count) = state.downcast_mut::<(i64, i64)>().unwrap();
+        *sum += value.as_int();
+        *count += 1;
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        let (sum, count) = *state.downcast::<(i64, i64)>().unwrap();
+        if count == 0 {
+            return Value::Null;
+        }
+        Value::Integer(sum / count)
+    }
+}
+
+/// Count all rows of the group including the rows with null values
+struct CountAggregation;
+
+impl AggregateFunction for CountAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(0_i64)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, _value: &Value) {
+        *state.downcast_mut::<i64>().unwrap() += 1;
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        Value::Integer(*state.downcast::<i64>().unwrap())
+    }
+}
+
+/// Join the non null values with the separator, or Null if all of them are null
+struct GroupConcatAggregation {
+    separator: String,
+}
+
+impl AggregateFunction for GroupConcatAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(Vec::<String>::new())
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        if !matches!(value, Value::Null) {
+            let values = state.downcast_mut::<Vec<String>>().unwrap();
+            values.push(value.to_string());
+        }
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        let values = state.downcast::<Vec<String>>().unwrap();
+        if values.is_empty() {
+            return Value::Null;
+        }
+        Value::Text(values.join(&self.separator))
+    }
+}
+
+struct FirstAggregation;
+
+impl AggregateFunction for FirstAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(None::<Value>)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        let first_value = state.downcast_mut::<Option<Value>>().unwrap();
+        if first_value.is_none() {
+            *first_value = Some(value.clone());
+        }
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        state
+            .downcast::<Option<Value>>()
+            .unwrap()
+            .unwrap_or(Value::Null)
+    }
+}
+
+struct LastAggregation;
+
+impl AggregateFunction for LastAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(None::<Value>)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        *state.downcast_mut::<Option<Value>>().unwrap() = Some(value.clone());
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        state
+            .downcast::<Option<Value>>()
+            .unwrap()
+            .unwrap_or(Value::Null)
+    }
+}
+
+/// Keep the first non null value
+struct AnyValueAggregation;
+
+impl AggregateFunction for AnyValueAggregation {
+    fn init(&self) -> Box<dyn Any + Send> {
+        Box::new(None::<Value>)
+    }
+
+    fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+        let any_value = state.downcast_mut::<Option<Value>>().unwrap();
+        if any_value.is_none() && !matches!(value, Value::Null) {
+            *any_value = Some(value.clone());
+        }
+    }
+
+    fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+        state
+            .downcast::<Option<Value>>()
+            .unwrap()
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn merge_max(value: &Value, other: &Value) -> Value {
+    if value.compare(other) == Ordering::Greater {
+        return other.clone();
+    }
+    value.clone()
+}
+
+fn merge_min(value: &Value, other: &Value) -> Value {
+    if value.compare(other) == Ordering::Less {
+        return other.clone();
+    }
+    value.clone()
+}
+
+fn merge_sum(value: &Value, other: &Value) -> Value {
+    Value::Integer(value.as_int() + other.as_int())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::object::Row;
+
+    struct Median;
+
+    impl AggregateFunction for Median {
+        fn init(&self) -> Box<dyn Any + Send> {
+            Box::new(Vec::<i64>::new())
+        }
+
+        fn accumulate(&self, state: &mut Box<dyn Any + Send>, value: &Value) {
+            state
+                .downcast_mut::<Vec<i64>>()
+                .unwrap()
+                .push(value.as_int());
+        }
+
+        fn finish(&self, state: Box<dyn Any + Send>) -> Value {
+            let mut values = state.downcast::<Vec<i64>>().unwrap();
+            values.sort();
+            Value::Integer(values[values.len() / 2])
+        }
+    }
+
+    #[test]
+    fn test_register_aggregation() {
+        let prototype = AggregationPrototype {
+            parameters: vec![DataType::Integer],
+            result: DataType::Integer,
+        };
+
+        let mut env = Environment::default();
+        let ret = env.register_aggregation("MEDIAN", prototype.clone(), Box::new(Median));
+        assert!(ret.is_ok());
+        assert!(is_aggregation_function(&env, "median"));
+        assert!(aggregation_prototype(&env, "median").is_some());
+        assert!(!is_aggregation_function(&Environment::default(), "median"));
+
+        let ret = env.register_aggregation("count", prototype, Box::new(Median));
+        assert!(ret.is_err());
+
+        let titles: Vec<String> = vec!["field1".to_string()];
+        let rows: Vec<Row> = vec![
+            Row {
+                values: vec![Value::Integer(7)],
+            },
+            Row {
+                values: vec![Value::Integer(1)],
+            },
+            Row {
+                values: vec![Value::Integer(3)],
+            },
+        ];
+        let objects = Group { rows };
+
+        let result = execute_aggregation_function(&env, "median", "field1", &titles, &objects);
+        if let Some(Value::Integer(v)) = result {
+            assert_eq!(v, 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_aggregations_merges() {
+        let count = AGGREGATIONS_MERGES.get("count").unwrap();
+        if let Value::Integer(v) = count(&Value::Integer(2), &Value::Integer(3)) {
+            assert_eq!(v, 5);
+        } else {
+            assert!(false);
+        }
+
+        let max = AGGREGATIONS_MERGES.get("max").unwrap();
+        if let Value::Integer(v) = max(&Value::Integer(5), &Value::Integer(7)) {
+            assert_eq!(v, 7);
+        } else {
+            assert!(false);
+        }
+
+        let min = AGGREGATIONS_MERGES.get("min").unwrap();
+        if let Value::Integer(v) = min(&Value::Integer(5), &Value::Integer(7)) {
+            assert_eq!(v, 5);
+        } else {
+            assert!(false);
+        }
+
+        assert!(!AGGREGATIONS_MERGES.contains_key("avg"));
+    }
 
     #[test]
     fn test_aggregation_max() {
@@ -240,4 +804,141 @@ mod tests {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_execute_aggregate_call_with_modifiers() {
+        let env = Environment::default();
+        let titles: Vec<String> = vec!["field1".to_string()];
+        let rows: Vec<Row> = vec![
+            Row {
+                values: vec![Value::Text("b".to_string())],
+            },
+            Row {
+                values: vec![Value::Text("a".to_string())],
+            },
+            Row {
+                values: vec![Value::Null],
+            },
+            Row {
+                values: vec![Value::Text("b".to_string())],
+            },
+        ];
+        let objects = Group { rows };
+
+        let call = AggregateCall::new("group_concat".to_string(), "field1".to_string());
+        if let Some(Value::Text(v)) = execute_aggregate_call(&env, &call, &titles, &objects) {
+            assert_eq!(v, "b,a,b");
+        } else {
+            assert!(false);
+        }
+
+        let call = AggregateCall {
+            function_name: "group_concat".to_string(),
+            argument: "field1".to_string(),
+            is_distinct: true,
+            order_by: Some(("field1".to_string(), SortingOrder::Ascending)),
+            separator: Some(", ".to_string()),
+        };
+        if let Some(Value::Text(v)) = execute_aggregate_call(&env, &call, &titles, &objects) {
+            assert_eq!(v, "a, b");
+        } else {
+            assert!(false);
+        }
+
+        let call = AggregateCall {
+            function_name: "count".to_string(),
+            argument: "field1".to_string(),
+            is_distinct: true,
+            order_by: None,
+            separator: None,
+        };
+        if let Some(Value::Integer(v)) = execute_aggregate_call(&env, &call, &titles, &objects) {
+            assert_eq!(v, 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_string_agg_prototype() {
+        let env = Environment::default();
+        let prototype = aggregation_prototype(&env, "string_agg").unwrap();
+        assert_eq!(prototype.parameters.len(), 2);
+        assert!(prototype.result.is_text());
+
+        let prototype = aggregation_prototype(&env, "group_concat").unwrap();
+        assert!(prototype.parameters[1].is_optional());
+    }
+
+    #[test]
+    fn test_max_min_prototypes() {
+        let env = Environment::default();
+        let prototypes = aggregation_prototypes(&env, "max");
+        assert_eq!(prototypes.len(), 6);
+        assert!(prototypes
+            .iter()
+            .all(|prototype| prototype.parameters[0] == prototype.result));
+
+        assert_eq!(aggregation_prototypes(&env, "min").len(), 6);
+        assert!(aggregation_prototypes(&env, "unknown").is_empty());
+    }
+
+    #[test]
+    fn test_first_last_and_any_value() {
+        let titles: Vec<String> = vec!["field1".to_string()];
+        let rows: Vec<Row> = vec![
+            Row {
+                values: vec![Value::Null],
+            },
+            Row {
+                values: vec![Value::Integer(1)],
+            },
+            Row {
+                values: vec![Value::Integer(2)],
+            },
+        ];
+        let objects = Group { rows };
+
+        let value = aggregation_first("field1", &titles, &objects);
+        assert!(matches!(value, Value::Null));
+
+        let value = aggregation_last("field1", &titles, &objects);
+        assert!(value.equals(&Value::Integer(2)));
+
+        let value = aggregation_any_value("field1", &titles, &objects);
+        assert!(value.equals(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn test_aggregation_accumulator() {
+        let env = Environment::default();
+        let values = [
+            Value::Integer(4),
+            Value::Null,
+            Value::Integer(9),
+            Value::Integer(2),
+        ];
+
+        let expected_results = [
+            ("max", Value::Integer(9)),
+            ("min", Value::Integer(2)),
+            ("count", Value::Integer(4)),
+            ("group_concat", Value::Text("4,9,2".to_string())),
+            ("any_value", Value::Integer(4)),
+            ("last", Value::Integer(2)),
+        ];
+
+        for (name, expected_result) in expected_results {
+            let accumulator = aggregation_accumulator(&env, name).unwrap();
+            let mut state = accumulator.init();
+            for value in &values {
+                accumulator.accumulate(&mut state, value);
+            }
+
+            let result = accumulator.finish(state);
+            assert!(result.equals(&expected_result));
+        }
+
+        assert!(aggregation_accumulator(&env, "not_aggregation").is_none());
+    }
 }
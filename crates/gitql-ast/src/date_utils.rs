@@ -1,6 +1,10 @@
 extern crate chrono;
 
 use chrono::Datelike;
+use chrono::Duration;
+use chrono::FixedOffset;
+use chrono::LocalResult;
+use chrono::Months;
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 use chrono::NaiveTime;
@@ -8,12 +12,27 @@ use chrono::TimeZone;
 use chrono::Timelike;
 use chrono::Utc;
 use chrono::Weekday;
+use chrono_tz::Tz;
 
 static CHRONO_TIME_FORMAT: &str = "%H:%M:%S";
 static CHRONO_DATE_FORMAT: &str = "%Y-%m-%d";
 static CHRONO_DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 static CHRONO_DATE_TIME_FULL_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
 
+/// Time zone of the dates functions and renderers, a fixed offset or an IANA name like
+/// `Europe/Paris` that follows its daylight saving time rules
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateTimeZone {
+    Offset(FixedOffset),
+    Named(Tz),
+}
+
+impl Default for DateTimeZone {
+    fn default() -> Self {
+        DateTimeZone::Offset(FixedOffset::east_opt(0).unwrap())
+    }
+}
+
 pub fn get_unix_timestamp_ms() -> i64 {
     Utc::now().timestamp()
 }
@@ -36,6 +55,14 @@ pub fn time_stamp_to_date_time(time_stamp: i64) -> String {
     datetime.format(CHRONO_DATE_TIME_FULL_FORMAT).to_string()
 }
 
+/// Format the time stamp as date time in the time zone
+pub fn time_stamp_to_date_time_in(time_stamp: i64, time_zone: &DateTimeZone) -> String {
+    match time_stamp_to_local(time_stamp, time_zone) {
+        Some(local) => local.format(CHRONO_DATE_TIME_FULL_FORMAT).to_string(),
+        None => time_stamp_to_date_time(time_stamp),
+    }
+}
+
 pub fn date_to_time_stamp(date: &str) -> i64 {
     let date_time = NaiveDate::parse_from_str(date, CHRONO_DATE_FORMAT).ok();
     if let Some(date) = date_time {
@@ -114,6 +141,179 @@ pub fn time_stamp_from_year_and_day(year: i32, day_of_year: u32) -> i64 {
     Utc.from_utc_datetime(&datetime).timestamp()
 }
 
+/// Parse time zone name or offset like `UTC`, `+02:00`, `-0530` or `+3`
+pub fn parse_time_zone_offset(time_zone: &str) -> Option<FixedOffset> {
+    let time_zone = time_zone.trim();
+    if time_zone.eq_ignore_ascii_case("utc") || time_zone.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, offset) = match time_zone.chars().next()? {
+        '+' => (1, &time_zone[1..]),
+        '-' => (-1, &time_zone[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = if let Some((hours, minutes)) = offset.split_once(':') {
+        (hours, minutes)
+    } else if offset.len() == 4 {
+        offset.split_at(2)
+    } else {
+        (offset, "0")
+    };
+
+    let hours = hours.parse::<i32>().ok()?;
+    let minutes = minutes.parse::<i32>().ok()?;
+    if !(0..=14).contains(&hours) || !(0..60).contains(&minutes) {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Parse time zone offset or IANA time zone name like `Europe/Paris`
+pub fn parse_time_zone(time_zone: &str) -> Option<DateTimeZone> {
+    if let Some(offset) = parse_time_zone_offset(time_zone) {
+        return Some(DateTimeZone::Offset(offset));
+    }
+    time_zone.trim().parse::<Tz>().ok().map(DateTimeZone::Named)
+}
+
+fn time_stamp_to_local(time_stamp: i64, time_zone: &DateTimeZone) -> Option<NaiveDateTime> {
+    let utc = NaiveDateTime::from_timestamp_opt(time_stamp, 0)?;
+    let local = match time_zone {
+        DateTimeZone::Offset(offset) => offset.from_utc_datetime(&utc).naive_local(),
+        DateTimeZone::Named(tz) => tz.from_utc_datetime(&utc).naive_local(),
+    };
+    Some(local)
+}
+
+fn local_to_time_stamp(local: NaiveDateTime, time_zone: &DateTimeZone) -> Option<i64> {
+    match time_zone {
+        DateTimeZone::Offset(offset) => local_in_zone_to_time_stamp(local, offset),
+        DateTimeZone::Named(tz) => local_in_zone_to_time_stamp(local, tz),
+    }
+}
+
+/// Local time repeated by a daylight saving time transition is resolved to the earliest instant,
+/// and local time skipped by the transition is moved forward by the skipped hour
+fn local_in_zone_to_time_stamp<Z: TimeZone>(local: NaiveDateTime, zone: &Z) -> Option<i64> {
+    match zone.from_local_datetime(&local) {
+        LocalResult::Single(date_time) | LocalResult::Ambiguous(date_time, _) => {
+            Some(date_time.timestamp())
+        }
+        LocalResult::None => {
+            let local = local.checked_add_signed(Duration::hours(1))?;
+            Some(zone.from_local_datetime(&local).earliest()?.timestamp())
+        }
+    }
+}
+
+/// Add amount of date part units to time stamp, calendar units are added in the time zone
+pub fn time_stamp_add(
+    time_stamp: i64,
+    unit: &str,
+    amount: i64,
+    time_zone: &DateTimeZone,
+) -> Option<i64> {
+    // Clock units are elapsed time, they are added to the time stamp without the time zone
+    let unit = unit.to_lowercase();
+    let unit_seconds = match unit.as_str() {
+        "second" => Some(1),
+        "minute" => Some(60),
+        "hour" => Some(3600),
+        _ => None,
+    };
+    if let Some(unit_seconds) = unit_seconds {
+        return time_stamp.checked_add(amount.checked_mul(unit_seconds)?);
+    }
+
+    let local = time_stamp_to_local(time_stamp, time_zone)?;
+    let result = match unit.as_str() {
+        "day" => local.checked_add_signed(checked_duration(amount, 86400)?)?,
+        "week" => local.checked_add_signed(checked_duration(amount, 604800)?)?,
+        "month" => add_months(local, amount)?,
+        "quarter" => add_months(local, amount.checked_mul(3)?)?,
+        "year" => add_months(local, amount.checked_mul(12)?)?,
+        _ => return None,
+    };
+    local_to_time_stamp(result, time_zone)
+}
+
+fn checked_duration(amount: i64, unit_seconds: i64) -> Option<Duration> {
+    let seconds = amount.checked_mul(unit_seconds)?;
+    if seconds.unsigned_abs() > Duration::max_value().num_seconds().unsigned_abs() {
+        return None;
+    }
+    Some(Duration::seconds(seconds))
+}
+
+fn add_months(local: NaiveDateTime, amount: i64) -> Option<NaiveDateTime> {
+    let months = Months::new(u32::try_from(amount.unsigned_abs()).ok()?);
+    if amount < 0 {
+        local.checked_sub_months(months)
+    } else {
+        local.checked_add_months(months)
+    }
+}
+
+/// Truncate time stamp to the start of the date part unit in the time zone
+pub fn time_stamp_truncate(time_stamp: i64, unit: &str, time_zone: &DateTimeZone) -> Option<i64> {
+    let local = time_stamp_to_local(time_stamp, time_zone)?;
+    let date = local.date();
+    let result = match unit.to_lowercase().as_str() {
+        "second" => date.and_hms_opt(local.hour(), local.minute(), local.second())?,
+        "minute" => date.and_hms_opt(local.hour(), local.minute(), 0)?,
+        "hour" => date.and_hms_opt(local.hour(), 0, 0)?,
+        "day" => date.and_hms_opt(0, 0, 0)?,
+        "week" => {
+            let days_from_monday = date.weekday().num_days_from_monday() as i64;
+            let monday = date.checked_sub_signed(Duration::days(days_from_monday))?;
+            monday.and_hms_opt(0, 0, 0)?
+        }
+        "month" => date.with_day(1)?.and_hms_opt(0, 0, 0)?,
+        "quarter" => {
+            let quarter_month = (date.month() - 1) / 3 * 3 + 1;
+            NaiveDate::from_ymd_opt(date.year(), quarter_month, 1)?.and_hms_opt(0, 0, 0)?
+        }
+        "year" => NaiveDate::from_ymd_opt(date.year(), 1, 1)?.and_hms_opt(0, 0, 0)?,
+        _ => return None,
+    };
+    local_to_time_stamp(result, time_zone)
+}
+
+/// Return the number of days between the calendar dates of two time stamps in the time zone
+pub fn time_stamp_days_diff(end: i64, start: i64, time_zone: &DateTimeZone) -> Option<i64> {
+    let end_date = time_stamp_to_local(end, time_zone)?.date();
+    let start_date = time_stamp_to_local(start, time_zone)?.date();
+    Some(end_date.signed_duration_since(start_date).num_days())
+}
+
+/// Return the time of the time stamp in the time zone
+pub fn time_stamp_to_time_in(time_stamp: i64, time_zone: &DateTimeZone) -> String {
+    match time_stamp_to_local(time_stamp, time_zone) {
+        Some(local) => local.format(CHRONO_TIME_FORMAT).to_string(),
+        None => time_stamp_to_time(time_stamp),
+    }
+}
+
+/// Return the hour of the time stamp in the time zone
+pub fn date_time_to_hour_in(time_stamp: i64, time_zone: &DateTimeZone) -> Option<i64> {
+    Some(time_stamp_to_local(time_stamp, time_zone)?.hour() as i64)
+}
+
+/// Return the calendar date of the time stamp in the time zone as a Date time stamp
+pub fn time_stamp_to_date_in(time_stamp: i64, time_zone: &DateTimeZone) -> Option<i64> {
+    let date = time_stamp_to_local(time_stamp, time_zone)?.date();
+    Some(date.and_hms_opt(0, 0, 0)?.timestamp())
+}
+
+/// Return the time stamp of the start of the Date day in the time zone
+pub fn date_start_in(date: i64, time_zone: &DateTimeZone) -> Option<i64> {
+    let local = NaiveDateTime::from_timestamp_opt(date, 0)?;
+    local_to_time_stamp(local.date().and_hms_opt(0, 0, 0)?, time_zone)
+}
+
 /// Check if String literal is matching SQL time format: HH:MM:SS or HH:MM:SS.SSS
 pub fn is_valid_time_format(time_str: &str) -> bool {
     // Check length of the string
@@ -227,6 +427,15 @@ mod tests {
         assert_ne!(ret, "");
     }
 
+    #[test]
+    fn test_time_stamp_to_date_time_in() {
+        let time_zone = parse_time_zone("+02:00").unwrap();
+        assert_eq!(
+            time_stamp_to_date_time_in(0, &time_zone),
+            "1970-01-01 02:00:00.000"
+        );
+    }
+
     #[test]
     fn test_date_to_time_stamp() {
         let ret = date_to_time_stamp("2024-01-10 12:36:31");
@@ -288,6 +497,129 @@ mod tests {
         assert_ne!(ret, 0);
     }
 
+    #[test]
+    fn test_parse_time_zone_offset() {
+        let ret = parse_time_zone_offset("UTC").map(|offset| offset.local_minus_utc());
+        assert_eq!(ret, Some(0));
+
+        let ret = parse_time_zone_offset("+02:00").map(|offset| offset.local_minus_utc());
+        assert_eq!(ret, Some(7200));
+
+        let ret = parse_time_zone_offset("-0530").map(|offset| offset.local_minus_utc());
+        assert_eq!(ret, Some(-19800));
+
+        let ret = parse_time_zone_offset("+3").map(|offset| offset.local_minus_utc());
+        assert_eq!(ret, Some(10800));
+
+        let ret = parse_time_zone_offset("Europe/Paris");
+        assert!(ret.is_none());
+    }
+
+    #[test]
+    fn test_parse_time_zone() {
+        let ret = parse_time_zone("+02:00");
+        assert_eq!(
+            ret,
+            Some(DateTimeZone::Offset(FixedOffset::east_opt(7200).unwrap()))
+        );
+
+        let ret = parse_time_zone("Europe/Paris");
+        assert_eq!(ret, Some(DateTimeZone::Named(Tz::Europe__Paris)));
+
+        let ret = parse_time_zone("Mars/Olympus");
+        assert!(ret.is_none());
+    }
+
+    #[test]
+    fn test_time_stamp_in_named_time_zone() {
+        let paris = parse_time_zone("Europe/Paris").unwrap();
+
+        // Paris is at +01:00 in winter and +02:00 in summer
+        let winter = date_time_to_time_stamp("2024-01-15 12:00:00");
+        assert_eq!(date_time_to_hour_in(winter, &paris), Some(13));
+        let summer = date_time_to_time_stamp("2024-07-15 12:00:00");
+        assert_eq!(date_time_to_hour_in(summer, &paris), Some(14));
+
+        // Adding a day across the daylight saving time change keeps the local time
+        let ret = time_stamp_add(
+            date_time_to_time_stamp("2024-03-30 11:00:00"),
+            "day",
+            1,
+            &paris,
+        );
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-03-31 10:00:00")));
+
+        let ret = time_stamp_truncate(summer, "day", &paris);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-07-14 22:00:00")));
+
+        // Hours are elapsed time, 00:30 UTC is 01:30 in Paris and two hours later is 04:30
+        let ret = time_stamp_add(
+            date_time_to_time_stamp("2024-03-31 00:30:00"),
+            "hour",
+            2,
+            &paris,
+        );
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-03-31 02:30:00")));
+
+        // 02:30 doesn't exist on 2024-03-31 in Paris, it's moved after the skipped hour
+        let ret = time_stamp_add(
+            date_time_to_time_stamp("2024-03-30 01:30:00"),
+            "day",
+            1,
+            &paris,
+        );
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-03-31 01:30:00")));
+    }
+
+    #[test]
+    fn test_time_stamp_add() {
+        let utc = DateTimeZone::default();
+
+        // 2024-01-31 00:00:00 UTC
+        let ret = time_stamp_add(1706659200, "month", 1, &utc);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-02-29 00:00:00")));
+
+        let ret = time_stamp_add(1706659200, "day", -31, &utc);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2023-12-31 00:00:00")));
+
+        let ret = time_stamp_add(1706659200, "decade", 1, &utc);
+        assert!(ret.is_none());
+    }
+
+    #[test]
+    fn test_time_stamp_truncate() {
+        let utc = DateTimeZone::default();
+        let plus_two = parse_time_zone("+02:00").unwrap();
+
+        // 2024-01-31 23:30:00 UTC is 2024-02-01 01:30:00 in +02:00
+        let time_stamp = date_time_to_time_stamp("2024-01-31 23:30:00");
+
+        let ret = time_stamp_truncate(time_stamp, "month", &utc);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-01-01 00:00:00")));
+
+        let ret = time_stamp_truncate(time_stamp, "month", &plus_two);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-01-31 22:00:00")));
+
+        let ret = time_stamp_truncate(time_stamp, "week", &utc);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-01-29 00:00:00")));
+
+        let ret = time_stamp_truncate(time_stamp, "quarter", &utc);
+        assert_eq!(ret, Some(date_time_to_time_stamp("2024-01-01 00:00:00")));
+    }
+
+    #[test]
+    fn test_time_stamp_days_diff() {
+        let utc = DateTimeZone::default();
+        let plus_two = parse_time_zone("+02:00").unwrap();
+
+        let end = date_time_to_time_stamp("2024-01-31 23:30:00");
+        let start = date_time_to_time_stamp("2024-01-30 12:00:00");
+
+        assert_eq!(time_stamp_days_diff(end, start, &utc), Some(1));
+        assert_eq!(time_stamp_days_diff(end, start, &plus_two), Some(2));
+        assert_eq!(time_stamp_days_diff(start, end, &utc), Some(-1));
+    }
+
     #[test]
     fn test_is_valid_time_format() {
         let ret = is_valid_time_format("");
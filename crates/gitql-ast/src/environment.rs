@@ -1,28 +1,91 @@
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
 
+use crate::aggregation::AggregateFunction;
+use crate::aggregation::AggregationPrototype;
+use crate::aggregation::CustomAggregation;
+use crate::aggregation::AGGREGATIONS;
+use crate::function::is_standard_function;
+use crate::object::GitQLObject;
+use crate::object::Group;
+use crate::object::Row;
+use crate::provider::DataProvider;
+use crate::rows_cache::RowsCache;
+use crate::session::SessionOptions;
+use crate::spill::SpillOptions;
 use crate::types::DataType;
+use crate::types::TABLES_FIELDS_TYPES;
 use crate::value::Value;
 
+/// Prefix of the generated hidden field that store the result of `TOUCHES(pathspec)` for each commit
+pub const TOUCHES_FIELD_PREFIX: &str = "touches:";
+
+/// Prefix of the generated hidden field of `RANGE(revisions)`, the engine walks only the commits in the range
+pub const RANGE_FIELD_PREFIX: &str = "range:";
+
+/// Prefix of the generated hidden field of `FROM COMMITS_TOUCHING(path)`, the engine skips the commits
+/// that don't change the path
+pub const TOUCHING_FIELD_PREFIX: &str = "touching:";
+
+/// Prefix of the generated hidden field of `path = "path"` in `WHERE` condition of `blame` table,
+/// the engine blames only the file with that path
+pub const BLAME_PATH_FIELD_PREFIX: &str = "blame_path:";
+
+/// Name of the global variable that select the revision used by the `blobs` table, default is `HEAD`
+pub const BLOBS_REVISION_VARIABLE: &str = "@revision";
+
+/// Prefix of the table name of `FROM EXTERNAL(path)`, the file is loaded once per query as read-only table
+pub const EXTERNAL_TABLE_PREFIX: &str = "external:";
+
 lazy_static! {
+    /// Table-valued functions that can be used in `FROM`, mapped to the table of their rows and the prefix
+    /// of the generated hidden field that pass the function argument to the engine
+    pub static ref TABLE_FUNCTIONS: HashMap<&'static str, (&'static str, &'static str)> = {
+        let mut map = HashMap::new();
+        map.insert("commits_touching", ("commits", TOUCHING_FIELD_PREFIX));
+        map
+    };
+
     pub static ref TABLES_FIELDS_NAMES: HashMap<&'static str, Vec<&'static str>> = {
         let mut map = HashMap::new();
-        map.insert("refs", vec!["name", "full_name", "type", "repo"]);
+        map.insert(
+            "refs",
+            vec!["name", "full_name", "short_name", "target", "type", "repo"],
+        );
         map.insert(
             "commits",
             vec![
                 "commit_id",
                 "title",
+                "summary",
                 "message",
+                "body",
+                "trailers",
                 "name",
                 "email",
                 "datetime",
+                "is_lossy_decoded",
+                "is_signed",
+                "signer",
+                "signature_status",
                 "repo",
             ],
         );
         map.insert(
             "branches",
-            vec!["name", "commit_count", "is_head", "is_remote", "repo"],
+            vec![
+                "name",
+                "commit_count",
+                "is_head",
+                "is_remote",
+                "upstream",
+                "ahead",
+                "behind",
+                "repo",
+            ],
         );
         map.insert(
             "diffs",
@@ -36,12 +99,89 @@ lazy_static! {
                 "repo",
             ],
         );
-        map.insert("tags", vec!["name", "repo"]);
+        map.insert(
+            "diff_files",
+            vec![
+                "commit_id",
+                "name",
+                "email",
+                "datetime",
+                "path",
+                "status",
+                "renamed_from",
+                "insertions",
+                "deletions",
+                "repo",
+            ],
+        );
+        map.insert(
+            "files",
+            vec![
+                "path",
+                "extension",
+                "size",
+                "is_binary",
+                "last_modified_commit",
+                "repo",
+            ],
+        );
+        map.insert(
+            "blobs",
+            vec![
+                "path",
+                "size",
+                "is_binary",
+                "lfs_pointer",
+                "revision",
+                "repo",
+            ],
+        );
+        map.insert(
+            "tags",
+            vec![
+                "name",
+                "target_commit_id",
+                "is_annotated",
+                "message",
+                "tagger_name",
+                "tagger_email",
+                "created_datetime",
+                "is_signed",
+                "signer",
+                "signature_status",
+                "repo",
+            ],
+        );
+        map.insert(
+            "worktrees",
+            vec![
+                "name",
+                "path",
+                "branch",
+                "commit_id",
+                "is_main",
+                "is_locked",
+                "repo",
+            ],
+        );
+        map.insert(
+            "blame",
+            vec![
+                "path",
+                "line_number",
+                "line",
+                "commit_id",
+                "author_name",
+                "author_email",
+                "datetime",
+                "repo",
+            ],
+        );
         map
     };
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Environment {
     /// All Global Variables values that can life for this program session
     pub globals: HashMap<String, Value>,
@@ -49,6 +189,26 @@ pub struct Environment {
     pub globals_types: HashMap<String, DataType>,
     /// Local variables types in the current scope, later will be multi layer scopes
     pub scopes: HashMap<String, DataType>,
+    /// Fields names and types of the tables registered at runtime by data providers
+    pub tables: HashMap<String, Vec<(String, DataType)>>,
+    /// Data providers that select the rows of the registered tables
+    pub providers: HashMap<String, Arc<dyn DataProvider>>,
+    /// Aliases of the tables used in the current query, map each alias to the table name
+    pub tables_aliases: HashMap<String, String>,
+    /// Values of the `IN` subqueries in the current query, each set is indexed by the subquery index
+    pub subqueries_values: Vec<HashSet<String>>,
+    /// Rows of the temporary tables created by `CREATE TEMP TABLE`, they live for this program session
+    pub temp_tables: HashMap<String, Arc<GitQLObject>>,
+    /// Names of the read-only tables loaded from external CSV or JSON files, their rows are stored with the temporary tables
+    pub external_tables: HashSet<String>,
+    /// Options of this session selected by the session variables like `@@timezone` and `@@null_text`
+    pub session: SessionOptions,
+    /// Custom aggregation functions registered at runtime with their prototypes
+    pub aggregations: HashMap<String, CustomAggregation>,
+    /// Directory and sizes of the spill files, the selected rows are spilled only if it's set
+    pub spill: Option<SpillOptions>,
+    /// Rows collected from the repositories by the queries of this session, shared with the environments of the threads
+    pub rows_cache: Arc<Mutex<RowsCache>>,
 }
 
 impl Environment {
@@ -75,9 +235,141 @@ impl Environment {
         return self.scopes.get(str);
     }
 
-    /// Clear all locals scopes and only save globals
+    /// Clear all locals scopes and tables aliases and only save globals
     pub fn clear_session(&mut self) {
-        self.scopes.clear()
+        self.scopes.clear();
+        self.tables_aliases.clear();
+    }
+
+    /// Define alias name for table in the current query
+    pub fn define_table_alias(&mut self, alias: &str, table_name: &str) {
+        self.tables_aliases
+            .insert(alias.to_string(), table_name.to_string());
+    }
+
+    /// Resolve the table name of alias, or the name itself if it's not an alias
+    pub fn resolve_table_alias<'a>(&'a self, name: &'a str) -> &'a str {
+        self.tables_aliases
+            .get(name)
+            .map_or(name, |table_name| table_name.as_str())
+    }
+
+    /// Register a custom table with its fields names and types so it can be queried and type checked,
+    /// builtin tables can't be replaced
+    pub fn register_table(
+        &mut self,
+        table_name: &str,
+        fields: Vec<(&str, DataType)>,
+    ) -> Result<(), String> {
+        if TABLES_FIELDS_NAMES.contains_key(table_name) {
+            return Err(format!("Table `{}` is already a builtin table", table_name));
+        }
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, data_type)| (name.to_lowercase(), data_type))
+            .collect();
+        self.tables.insert(table_name.to_lowercase(), fields);
+        Ok(())
+    }
+
+    /// Register custom aggregation function that can be used like the builtin aggregations,
+    /// builtin aggregations and standard functions can't be replaced
+    pub fn register_aggregation(
+        &mut self,
+        name: &str,
+        prototype: AggregationPrototype,
+        aggregation: Box<dyn AggregateFunction>,
+    ) -> Result<(), String> {
+        let name = name.to_lowercase();
+        if AGGREGATIONS.contains_key(name.as_str()) || is_standard_function(name.as_str()) {
+            return Err(format!("Function `{}` is already a builtin function", name));
+        }
+
+        self.aggregations
+            .insert(name, (prototype, Arc::from(aggregation)));
+        Ok(())
+    }
+
+    /// Replace the fields names and types of builtin or registered table, used to type check
+    /// queries against the schema of another GitQL version
+    pub fn override_table(&mut self, table_name: &str, fields: Vec<(String, DataType)>) {
+        let fields = fields
+            .into_iter()
+            .map(|(name, data_type)| (name.to_lowercase(), data_type))
+            .collect();
+        self.tables.insert(table_name.to_lowercase(), fields);
+    }
+
+    /// Store the rows of temporary table and register its fields so it can be queried like other tables
+    pub fn define_temp_table(
+        &mut self,
+        table_name: &str,
+        fields: Vec<(String, DataType)>,
+        object: GitQLObject,
+    ) {
+        self.tables.insert(table_name.to_string(), fields);
+        self.temp_tables
+            .insert(table_name.to_string(), Arc::new(object));
+    }
+
+    /// Append the rows to the temporary table, returns false if the table is not a temporary table
+    pub fn append_temp_table_rows(&mut self, table_name: &str, rows: Vec<Row>) -> bool {
+        let Some(object) = self.temp_tables.get_mut(table_name) else {
+            return false;
+        };
+
+        // Rows are copied only if the table is still shared with another environment
+        let object = Arc::make_mut(object);
+        match object.groups.first_mut() {
+            Some(group) => group.rows.extend(rows),
+            None => object.groups.push(Group { rows }),
+        }
+        true
+    }
+
+    /// Store the rows of read-only table loaded from external file, the table can be selected but can't be
+    /// used as `INSERT INTO` target
+    pub fn define_external_table(
+        &mut self,
+        table_name: &str,
+        fields: Vec<(String, DataType)>,
+        object: GitQLObject,
+    ) {
+        self.define_temp_table(table_name, fields, object);
+        self.external_tables.insert(table_name.to_string());
+    }
+
+    /// Returns true if the table is a temporary table created by `CREATE TEMP TABLE`
+    pub fn is_temp_table(&self, table_name: &str) -> bool {
+        self.temp_tables.contains_key(table_name) && !self.external_tables.contains(table_name)
+    }
+
+    /// Returns true if the rows of the table are stored in memory, temporary or external table
+    pub fn is_in_memory_table(&self, table_name: &str) -> bool {
+        self.temp_tables.contains_key(table_name)
+    }
+
+    /// Returns true if the table is a builtin or registered table
+    pub fn has_table(&self, table_name: &str) -> bool {
+        TABLES_FIELDS_NAMES.contains_key(table_name) || self.tables.contains_key(table_name)
+    }
+
+    /// Returns the fields names and types of builtin or registered table in the table order
+    pub fn table_fields(&self, table_name: &str) -> Vec<(String, DataType)> {
+        if let Some(fields) = self.tables.get(table_name) {
+            return fields.clone();
+        }
+
+        TABLES_FIELDS_NAMES
+            .get(table_name)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .map(|name| (name.to_string(), TABLES_FIELDS_TYPES[name].clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 }
 
@@ -91,6 +383,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.define("field1".to_string(), DataType::Text);
@@ -107,6 +409,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.define_global("field1".to_string(), DataType::Text);
@@ -123,6 +435,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.define("field1".to_string(), DataType::Text);
@@ -144,6 +466,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.define("field1".to_string(), DataType::Text);
@@ -182,11 +514,75 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.define("field1".to_string(), DataType::Text);
+        env.define_table_alias("c", "commits");
 
         env.clear_session();
         assert_eq!(env.scopes.len(), 0);
+        assert_eq!(env.tables_aliases.len(), 0);
+    }
+
+    #[test]
+    fn test_resolve_table_alias() {
+        let mut env = Environment::default();
+        env.define_table_alias("c", "commits");
+        assert_eq!(env.resolve_table_alias("c"), "commits");
+        assert_eq!(env.resolve_table_alias("tags"), "tags");
+    }
+
+    #[test]
+    fn test_define_temp_table() {
+        let mut env = Environment::default();
+        assert!(!env.has_table("hot"));
+
+        let fields = vec![("name".to_string(), DataType::Text)];
+        env.define_temp_table("hot", fields, GitQLObject::default());
+        assert!(env.has_table("hot"));
+        assert!(env.is_temp_table("hot"));
+        assert!(!env.is_temp_table("commits"));
+        assert_eq!(env.table_fields("hot").len(), 1);
+
+        env.clear_session();
+        assert!(env.is_temp_table("hot"));
+
+        let rows = vec![Row {
+            values: vec![Value::Text("gql".to_string())],
+        }];
+        assert!(env.append_temp_table_rows("hot", rows));
+        assert_eq!(env.temp_tables["hot"].groups[0].len(), 1);
+        assert!(!env.append_temp_table_rows("commits", vec![]));
+    }
+
+    #[test]
+    fn test_define_external_table() {
+        let mut env = Environment::default();
+        let fields = vec![("name".to_string(), DataType::Text)];
+        env.define_external_table("authors", fields, GitQLObject::default());
+        assert!(env.has_table("authors"));
+        assert!(env.is_in_memory_table("authors"));
+        assert!(!env.is_temp_table("authors"));
+    }
+
+    #[test]
+    fn test_override_table() {
+        let mut env = Environment::default();
+        assert!(env.table_fields("tags").len() > 1);
+
+        env.override_table("tags", vec![("Name".to_string(), DataType::Text)]);
+        let fields = env.table_fields("tags");
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "name");
     }
 }
@@ -1,7 +1,9 @@
 use std::any::Any;
 
 use crate::environment::Environment;
-use crate::function::PROTOTYPES;
+use crate::function::function_prototype;
+use crate::function::generic_result_argument_index;
+use crate::session::is_session_variable;
 use crate::types::{DataType, TABLES_FIELDS_TYPES};
 use crate::value::Value;
 
@@ -24,11 +26,12 @@ pub enum ExpressionKind {
     Between,
     Case,
     In,
+    InSubquery,
     IsNull,
     Null,
 }
 
-pub trait Expression {
+pub trait Expression: Send + Sync {
     fn kind(&self) -> ExpressionKind;
     fn expr_type(&self, scope: &Environment) -> DataType;
     fn as_any(&self) -> &dyn Any;
@@ -41,6 +44,170 @@ impl dyn Expression {
             ExpressionKind::Number | ExpressionKind::Boolean | ExpressionKind::String
         )
     }
+
+    /// Structural text of the expression, two expressions with the same fingerprint always evaluate
+    /// to the same value on the same row, returns None if the expression can't be compared
+    pub fn fingerprint(&self) -> Option<String> {
+        let any = self.as_any();
+        let fingerprint = match self.kind() {
+            ExpressionKind::Symbol => {
+                let expr = any.downcast_ref::<SymbolExpression>()?;
+                format!("symbol({})", expr.value)
+            }
+            ExpressionKind::GlobalVariable => {
+                let expr = any.downcast_ref::<GlobalVariableExpression>()?;
+                format!("global({})", expr.name)
+            }
+            ExpressionKind::String => {
+                let expr = any.downcast_ref::<StringExpression>()?;
+                let value_type = match expr.value_type {
+                    StringValueType::Text => "text",
+                    StringValueType::Time => "time",
+                    StringValueType::Date => "date",
+                    StringValueType::DateTime => "datetime",
+                };
+                format!("{}({:?})", value_type, expr.value)
+            }
+            ExpressionKind::Number => {
+                let expr = any.downcast_ref::<NumberExpression>()?;
+                format!("number({})", expr.value.hash_key())
+            }
+            ExpressionKind::Boolean => {
+                let expr = any.downcast_ref::<BooleanExpression>()?;
+                format!("boolean({})", expr.is_true)
+            }
+            ExpressionKind::Null => "null".to_string(),
+            ExpressionKind::PrefixUnary => {
+                let expr = any.downcast_ref::<PrefixUnary>()?;
+                let operator = match expr.op {
+                    PrefixUnaryOperator::Minus => "-",
+                    PrefixUnaryOperator::Bang => "!",
+                };
+                format!("({}{})", operator, expr.right.fingerprint()?)
+            }
+            ExpressionKind::Arithmetic => {
+                let expr = any.downcast_ref::<ArithmeticExpression>()?;
+                let operator = match expr.operator {
+                    ArithmeticOperator::Plus => "+",
+                    ArithmeticOperator::Minus => "-",
+                    ArithmeticOperator::Star => "*",
+                    ArithmeticOperator::Slash => "/",
+                    ArithmeticOperator::Modulus => "%",
+                };
+                binary_fingerprint(&expr.left, operator, &expr.right)?
+            }
+            ExpressionKind::Comparison => {
+                let expr = any.downcast_ref::<ComparisonExpression>()?;
+                let operator = match expr.operator {
+                    ComparisonOperator::Greater => ">",
+                    ComparisonOperator::GreaterEqual => ">=",
+                    ComparisonOperator::Less => "<",
+                    ComparisonOperator::LessEqual => "<=",
+                    ComparisonOperator::Equal => "=",
+                    ComparisonOperator::NotEqual => "!=",
+                    ComparisonOperator::NullSafeEqual => "<=>",
+                };
+                binary_fingerprint(&expr.left, operator, &expr.right)?
+            }
+            ExpressionKind::Logical => {
+                let expr = any.downcast_ref::<LogicalExpression>()?;
+                let operator = match expr.operator {
+                    LogicalOperator::Or => "OR",
+                    LogicalOperator::And => "AND",
+                    LogicalOperator::Xor => "XOR",
+                };
+                binary_fingerprint(&expr.left, operator, &expr.right)?
+            }
+            ExpressionKind::Bitwise => {
+                let expr = any.downcast_ref::<BitwiseExpression>()?;
+                let operator = match expr.operator {
+                    BitwiseOperator::Or => "|",
+                    BitwiseOperator::And => "&",
+                    BitwiseOperator::RightShift => ">>",
+                    BitwiseOperator::LeftShift => "<<",
+                };
+                binary_fingerprint(&expr.left, operator, &expr.right)?
+            }
+            ExpressionKind::Like => {
+                let expr = any.downcast_ref::<LikeExpression>()?;
+                binary_fingerprint(&expr.input, "LIKE", &expr.pattern)?
+            }
+            ExpressionKind::Glob => {
+                let expr = any.downcast_ref::<GlobExpression>()?;
+                binary_fingerprint(&expr.input, "GLOB", &expr.pattern)?
+            }
+            ExpressionKind::Call => {
+                let expr = any.downcast_ref::<CallExpression>()?;
+                let arguments = list_fingerprint(&expr.arguments)?;
+                format!("{}({})", expr.function_name, arguments)
+            }
+            ExpressionKind::Between => {
+                let expr = any.downcast_ref::<BetweenExpression>()?;
+                format!(
+                    "between({}, {}, {}, {}, {})",
+                    expr.value.fingerprint()?,
+                    expr.range_start.fingerprint()?,
+                    expr.range_end.fingerprint()?,
+                    expr.is_symmetric,
+                    expr.has_not_keyword
+                )
+            }
+            ExpressionKind::Case => {
+                let expr = any.downcast_ref::<CaseExpression>()?;
+                let default_value = match &expr.default_value {
+                    Some(value) => value.fingerprint()?,
+                    None => "null".to_string(),
+                };
+                format!(
+                    "case([{}], [{}], {})",
+                    list_fingerprint(&expr.conditions)?,
+                    list_fingerprint(&expr.values)?,
+                    default_value
+                )
+            }
+            ExpressionKind::In => {
+                let expr = any.downcast_ref::<InExpression>()?;
+                format!(
+                    "in({}, [{}], {})",
+                    expr.argument.fingerprint()?,
+                    list_fingerprint(&expr.values)?,
+                    expr.has_not_keyword
+                )
+            }
+            ExpressionKind::IsNull => {
+                let expr = any.downcast_ref::<IsNullExpression>()?;
+                format!(
+                    "is_null({}, {})",
+                    expr.argument.fingerprint()?,
+                    expr.has_not
+                )
+            }
+            ExpressionKind::Assignment | ExpressionKind::InSubquery => return None,
+        };
+        Some(fingerprint)
+    }
+}
+
+#[allow(clippy::borrowed_box)]
+fn binary_fingerprint(
+    left: &Box<dyn Expression>,
+    operator: &str,
+    right: &Box<dyn Expression>,
+) -> Option<String> {
+    Some(format!(
+        "({} {} {})",
+        left.fingerprint()?,
+        operator,
+        right.fingerprint()?
+    ))
+}
+
+fn list_fingerprint(expressions: &[Box<dyn Expression>]) -> Option<String> {
+    let fingerprints: Option<Vec<String>> = expressions
+        .iter()
+        .map(|expression| expression.fingerprint())
+        .collect();
+    Some(fingerprints?.join(", "))
 }
 
 pub struct AssignmentExpression {
@@ -134,6 +301,9 @@ impl Expression for GlobalVariableExpression {
         if scope.globals_types.contains_key(&self.name) {
             return scope.globals_types[self.name.as_str()].clone();
         }
+        if is_session_variable(&self.name) {
+            return DataType::Text;
+        }
         DataType::Undefined
     }
 
@@ -228,7 +398,15 @@ impl Expression for ArithmeticExpression {
     }
 
     fn expr_type(&self, scope: &Environment) -> DataType {
-        if self.left.expr_type(scope).is_int() && self.right.expr_type(scope).is_int() {
+        let left_type = self.left.expr_type(scope);
+        let right_type = self.right.expr_type(scope);
+        if left_type.is_null() && right_type.is_null() {
+            return DataType::Null;
+        }
+
+        // NULL operand makes the result NULL, so the type is decided by the other operand
+        let is_int_or_null = |data_type: &DataType| data_type.is_int() || data_type.is_null();
+        if is_int_or_null(&left_type) && is_int_or_null(&right_type) {
             return DataType::Integer;
         }
         DataType::Float
@@ -378,8 +556,23 @@ impl Expression for CallExpression {
         ExpressionKind::Call
     }
 
-    fn expr_type(&self, _scope: &Environment) -> DataType {
-        let prototype = PROTOTYPES.get(&self.function_name.as_str()).unwrap();
+    fn expr_type(&self, scope: &Environment) -> DataType {
+        if let Some(index) = generic_result_argument_index(&self.function_name) {
+            return self
+                .arguments
+                .iter()
+                .skip(index)
+                .map(|argument| argument.expr_type(scope))
+                .find(|data_type| !data_type.is_null())
+                .unwrap_or(DataType::Null);
+        }
+
+        let arguments_types: Vec<DataType> = self
+            .arguments
+            .iter()
+            .map(|argument| argument.expr_type(scope))
+            .collect();
+        let prototype = function_prototype(&self.function_name, &arguments_types).unwrap();
         prototype.result.clone()
     }
 
@@ -392,6 +585,8 @@ pub struct BetweenExpression {
     pub value: Box<dyn Expression>,
     pub range_start: Box<dyn Expression>,
     pub range_end: Box<dyn Expression>,
+    pub is_symmetric: bool,
+    pub has_not_keyword: bool,
 }
 
 impl Expression for BetweenExpression {
@@ -450,6 +645,28 @@ impl Expression for InExpression {
     }
 }
 
+/// Expression `argument [NOT] IN (SELECT ...)` or `argument [NOT] IN table.column`, the values
+/// of the subquery are evaluated into a set before the query that contains it
+pub struct InSubqueryExpression {
+    pub argument: Box<dyn Expression>,
+    pub subquery_index: usize,
+    pub has_not_keyword: bool,
+}
+
+impl Expression for InSubqueryExpression {
+    fn kind(&self) -> ExpressionKind {
+        ExpressionKind::InSubquery
+    }
+
+    fn expr_type(&self, _scope: &Environment) -> DataType {
+        DataType::Boolean
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 pub struct IsNullExpression {
     pub argument: Box<dyn Expression>,
     pub has_not: bool,
@@ -513,6 +730,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -535,6 +762,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -556,6 +793,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         scope.scopes.insert("field1".to_string(), DataType::Text);
@@ -593,6 +840,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         scope
@@ -625,6 +882,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -644,6 +911,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -666,6 +943,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -701,6 +988,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -741,6 +1038,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -780,6 +1087,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -806,6 +1123,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -833,6 +1160,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -860,6 +1197,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -885,6 +1232,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -908,12 +1265,24 @@ mod tests {
             range_end: Box::new(NumberExpression {
                 value: Value::Integer(1),
             }),
+            is_symmetric: false,
+            has_not_keyword: false,
         };
 
         let scope = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -938,6 +1307,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -964,12 +1343,38 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
         assert_eq!(ret.is_text(), true);
     }
 
+    #[test]
+    fn test_insubqueryexpression_expr_type() {
+        let expr = InSubqueryExpression {
+            argument: Box::new(StringExpression {
+                value: "value".to_string(),
+                value_type: StringValueType::Text,
+            }),
+            subquery_index: 0,
+            has_not_keyword: false,
+        };
+
+        let scope = Environment::default();
+        let ret = expr.expr_type(&scope);
+        assert!(ret.is_bool());
+    }
+
     #[test]
     fn test_isnullexpression_kind() {
         assert!(true);
@@ -988,6 +1393,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
@@ -1007,9 +1422,53 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let ret = expr.expr_type(&scope);
         assert_eq!(ret.is_null(), true);
     }
+
+    #[test]
+    fn test_expression_fingerprint() {
+        let lower_name = |name: &str| -> Box<dyn Expression> {
+            Box::new(CallExpression {
+                function_name: "lower".to_string(),
+                arguments: vec![Box::new(SymbolExpression {
+                    value: name.to_string(),
+                })],
+                is_aggregation: false,
+            })
+        };
+
+        let first = lower_name("name");
+        let second = lower_name("name");
+        let other = lower_name("email");
+        assert!(first.fingerprint().is_some());
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_ne!(first.fingerprint(), other.fingerprint());
+
+        let integer: Box<dyn Expression> = Box::new(NumberExpression {
+            value: Value::Integer(1),
+        });
+        let float: Box<dyn Expression> = Box::new(NumberExpression {
+            value: Value::Float(1.0),
+        });
+        assert_ne!(integer.fingerprint(), float.fingerprint());
+
+        let assignment: Box<dyn Expression> = Box::new(AssignmentExpression {
+            symbol: "@name".to_string(),
+            value: lower_name("name"),
+        });
+        assert!(assignment.fingerprint().is_none());
+    }
 }
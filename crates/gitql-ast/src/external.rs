@@ -0,0 +1,248 @@
+use std::path::Path;
+
+use crate::date_utils::date_time_to_time_stamp;
+use crate::date_utils::date_to_time_stamp;
+use crate::date_utils::is_valid_date_format;
+use crate::date_utils::is_valid_datetime_format;
+use crate::object::GitQLObject;
+use crate::object::Group;
+use crate::object::Row;
+use crate::types::DataType;
+use crate::value::Value;
+
+/// Load CSV, TSV, JSON array or JSON lines file as table rows, the format is detected from the file
+/// extension and each column type is inferred from its values, empty values are loaded as `Null`
+pub fn load_external_table(path: &str) -> Result<(Vec<(String, DataType)>, GitQLObject), String> {
+    let content = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase())
+        .unwrap_or_default();
+
+    let (titles, cells) = match extension.as_str() {
+        "csv" => parse_delimited(&content, b',')?,
+        "tsv" => parse_delimited(&content, b'\t')?,
+        "json" => {
+            let value: serde_json::Value =
+                serde_json::from_str(&content).map_err(|error| error.to_string())?;
+            match value {
+                serde_json::Value::Array(objects) => parse_json_objects(objects)?,
+                _ => return Err("JSON file must contain an array of objects".to_string()),
+            }
+        }
+        "jsonl" | "ndjson" => {
+            let mut objects = vec![];
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                objects.push(serde_json::from_str(line).map_err(|error| error.to_string())?);
+            }
+            parse_json_objects(objects)?
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported file extension `{}`, expect csv, tsv, json, jsonl or ndjson",
+                extension
+            ))
+        }
+    };
+
+    let types: Vec<DataType> = (0..titles.len())
+        .map(|index| infer_column_type(cells.iter().map(|row| row[index].as_deref())))
+        .collect();
+
+    let rows = cells
+        .into_iter()
+        .map(|row| Row {
+            values: row
+                .iter()
+                .zip(types.iter())
+                .map(|(cell, data_type)| cell_to_value(cell.as_deref(), data_type))
+                .collect(),
+        })
+        .collect();
+
+    let fields = titles.iter().cloned().zip(types).collect();
+    let object = GitQLObject {
+        titles,
+        groups: vec![Group { rows }],
+    };
+    Ok((fields, object))
+}
+
+type Cells = Vec<Vec<Option<String>>>;
+
+fn parse_delimited(content: &str, delimiter: u8) -> Result<(Vec<String>, Cells), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+
+    let titles = reader
+        .headers()
+        .map_err(|error| error.to_string())?
+        .iter()
+        .map(|title| title.trim().to_lowercase())
+        .collect::<Vec<String>>();
+
+    let mut cells = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|error| error.to_string())?;
+        let row = (0..titles.len())
+            .map(|index| match record.get(index) {
+                Some(cell) if !cell.is_empty() => Some(cell.to_string()),
+                _ => None,
+            })
+            .collect();
+        cells.push(row);
+    }
+    Ok((titles, cells))
+}
+
+fn parse_json_objects(objects: Vec<serde_json::Value>) -> Result<(Vec<String>, Cells), String> {
+    let mut titles: Vec<String> = vec![];
+    let mut maps = Vec::with_capacity(objects.len());
+    for object in objects {
+        let serde_json::Value::Object(map) = object else {
+            return Err("Each JSON row must be an object".to_string());
+        };
+
+        for key in map.keys() {
+            let key = key.to_lowercase();
+            if !titles.contains(&key) {
+                titles.push(key);
+            }
+        }
+
+        let map = map
+            .into_iter()
+            .map(|(key, value)| (key.to_lowercase(), value))
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        maps.push(map);
+    }
+
+    let cells = maps
+        .iter()
+        .map(|map| {
+            titles
+                .iter()
+                .map(|title| match map.get(title) {
+                    None | Some(serde_json::Value::Null) => None,
+                    Some(serde_json::Value::String(text)) => Some(text.to_string()),
+                    Some(value) => Some(value.to_string()),
+                })
+                .collect()
+        })
+        .collect();
+    Ok((titles, cells))
+}
+
+/// Infer the narrowest type that can represent all the non empty values of the column
+fn infer_column_type<'a>(cells: impl Iterator<Item = Option<&'a str>> + Clone) -> DataType {
+    let mut values = cells.flatten().peekable();
+    if values.peek().is_none() {
+        return DataType::Text;
+    }
+
+    if values.clone().all(|value| value.parse::<i64>().is_ok()) {
+        return DataType::Integer;
+    }
+
+    if values.clone().all(|value| value.parse::<f64>().is_ok()) {
+        return DataType::Float;
+    }
+
+    if values
+        .clone()
+        .all(|value| value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false"))
+    {
+        return DataType::Boolean;
+    }
+
+    if values.clone().all(is_valid_date_format) {
+        return DataType::Date;
+    }
+
+    if values.all(is_valid_datetime_format) {
+        return DataType::DateTime;
+    }
+
+    DataType::Text
+}
+
+fn cell_to_value(cell: Option<&str>, data_type: &DataType) -> Value {
+    let Some(cell) = cell else {
+        return Value::Null;
+    };
+
+    match data_type {
+        DataType::Integer => Value::Integer(cell.parse().unwrap_or_default()),
+        DataType::Float => Value::Float(cell.parse().unwrap_or_default()),
+        DataType::Boolean => Value::Boolean(cell.eq_ignore_ascii_case("true")),
+        DataType::Date => Value::Date(date_to_time_stamp(cell)),
+        DataType::DateTime => Value::DateTime(date_time_to_time_stamp(cell)),
+        _ => Value::Text(cell.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("gitql_external_{}", name));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_load_external_csv_table() {
+        let path = write_file(
+            "authors.csv",
+            "Name,Commits,Ratio,Active,Joined\nJane,10,0.5,true,2023-01-01\nJohn,,1,false,2024-02-10\n",
+        );
+        let (fields, object) = load_external_table(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            object.titles,
+            vec!["name", "commits", "ratio", "active", "joined"]
+        );
+        assert!(fields[0].1 == DataType::Text);
+        assert!(fields[1].1 == DataType::Integer);
+        assert!(fields[2].1 == DataType::Float);
+        assert!(fields[3].1 == DataType::Boolean);
+        assert!(fields[4].1 == DataType::Date);
+
+        let rows = &object.groups[0].rows;
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].values[1].equals(&Value::Integer(10)));
+        assert!(rows[1].values[1].data_type().is_null());
+        assert!(rows[1].values[2].equals(&Value::Float(1.0)));
+    }
+
+    #[test]
+    fn test_load_external_json_table() {
+        let path = write_file(
+            "tickets.json",
+            r#"[{"id": 1, "title": "Crash"}, {"id": 2, "title": null, "closed": true}]"#,
+        );
+        let (fields, object) = load_external_table(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(object.titles, vec!["id", "title", "closed"]);
+        assert!(fields[0].1 == DataType::Integer);
+        assert!(fields[1].1 == DataType::Text);
+        assert!(fields[2].1 == DataType::Boolean);
+        assert!(object.groups[0].rows[0].values[2].data_type().is_null());
+
+        let path = write_file("tickets.jsonl", "{\"id\": 1}\n\n{\"id\": 2.5}\n");
+        let (fields, object) = load_external_table(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(fields[0].1 == DataType::Float);
+        assert_eq!(object.groups[0].rows.len(), 2);
+
+        let path = write_file("tickets.txt", "");
+        assert!(load_external_table(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(load_external_table("missing.csv").is_err());
+    }
+}
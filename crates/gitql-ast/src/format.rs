@@ -0,0 +1,146 @@
+use crate::date_utils::time_stamp_to_date;
+use crate::date_utils::time_stamp_to_date_time_in;
+use crate::session::SessionOptions;
+use crate::value::Value;
+
+static TRUNCATION_MARKER: char = '…';
+
+/// Convert the value into text, shared by all renderers and exporters
+/// so the same value is represented identically in every output format,
+/// `NULL` text and date time zone are selected by `@@null_text` and `@@timezone`
+pub fn value_to_text(value: &Value, options: &SessionOptions) -> String {
+    match value {
+        Value::Integer(integer) => integer.to_string(),
+        Value::Float(float) => float_to_text(*float),
+        Value::Text(text) => escape_text(text),
+        Value::Boolean(boolean) => boolean.to_string(),
+        Value::DateTime(date_time) => time_stamp_to_date_time_in(*date_time, &options.time_zone),
+        Value::Date(date) => time_stamp_to_date(*date),
+        Value::Time(time) => time.to_string(),
+        Value::Null => options.null_text().to_string(),
+    }
+}
+
+/// Convert the value into text with at most `max_chars` characters,
+/// long values are cut on a character boundary and end with `…`
+pub fn value_to_truncated_text(
+    value: &Value,
+    max_chars: usize,
+    options: &SessionOptions,
+) -> String {
+    truncate_text(&value_to_text(value, options), max_chars)
+}
+
+/// Cut the text to be at most `max_chars` characters without splitting multi bytes characters
+pub fn truncate_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push(TRUNCATION_MARKER);
+    truncated
+}
+
+/// Float values always have a fraction part to be different from integers
+fn float_to_text(float: f64) -> String {
+    if float.is_nan() {
+        return "NaN".to_string();
+    }
+
+    if float.is_infinite() {
+        return if float.is_sign_positive() {
+            "Infinity".to_string()
+        } else {
+            "-Infinity".to_string()
+        };
+    }
+
+    if float.fract() == 0.0 {
+        return format!("{:.1}", float);
+    }
+
+    float.to_string()
+}
+
+/// Escape control characters except new lines and tabs so they can't break the output layout
+fn escape_text(text: &str) -> String {
+    if !text.chars().any(is_escapable_char) {
+        return text.to_string();
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if is_escapable_char(character) {
+            escaped.push_str(&character.escape_default().to_string());
+        } else {
+            escaped.push(character);
+        }
+    }
+    escaped
+}
+
+#[inline(always)]
+fn is_escapable_char(character: char) -> bool {
+    character.is_control() && character != '\n' && character != '\t'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_utils::parse_time_zone;
+
+    #[test]
+    fn test_value_to_text() {
+        let options = SessionOptions::default();
+        assert_eq!(value_to_text(&Value::Integer(1), &options), "1");
+        assert_eq!(value_to_text(&Value::Float(1.0), &options), "1.0");
+        assert_eq!(value_to_text(&Value::Float(1.5), &options), "1.5");
+        assert_eq!(value_to_text(&Value::Float(f64::NAN), &options), "NaN");
+        assert_eq!(
+            value_to_text(&Value::Float(f64::NEG_INFINITY), &options),
+            "-Infinity"
+        );
+        assert_eq!(value_to_text(&Value::Boolean(true), &options), "true");
+        assert_eq!(value_to_text(&Value::Null, &options), "Null");
+        assert_eq!(value_to_text(&Value::Date(0), &options), "1970-01-01");
+        assert_eq!(
+            value_to_text(&Value::DateTime(0), &options),
+            "1970-01-01 00:00:00.000"
+        );
+        assert_eq!(
+            value_to_text(&Value::Text("a\rb\nc".to_string()), &options),
+            "a\\rb\nc"
+        );
+
+        let options = SessionOptions {
+            null_text: Some("-".to_string()),
+            time_zone: parse_time_zone("+02:00").unwrap(),
+        };
+        assert_eq!(value_to_text(&Value::Null, &options), "-");
+        assert_eq!(
+            value_to_text(&Value::DateTime(0), &options),
+            "1970-01-01 02:00:00.000"
+        );
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("GitQL", 10), "GitQL");
+        assert_eq!(truncate_text("GitQL", 5), "GitQL");
+        assert_eq!(truncate_text("GitQL", 4), "Git…");
+        assert_eq!(truncate_text("GitQL", 0), "");
+        assert_eq!(truncate_text("éééé", 3), "éé…");
+    }
+
+    #[test]
+    fn test_value_to_truncated_text() {
+        let options = SessionOptions::default();
+        let value = Value::Text("Hello World".to_string());
+        assert_eq!(value_to_truncated_text(&value, 6, &options), "Hello…");
+    }
+}
@@ -1,18 +1,85 @@
 use crate::date_utils;
+use crate::date_utils::DateTimeZone;
 use crate::types::DataType;
 use crate::value::Value;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use lazy_static::lazy_static;
+use md5::Digest;
+use md5::Md5;
+use regex::Regex;
+use sha1::Sha1;
+use sha2::Sha256;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
 type Function = fn(&[Value]) -> Value;
 
+/// Standard function that depends on the time zone, called with the `@@timezone` session time zone
+type TimeZoneFunction = fn(&[Value], &DateTimeZone) -> Value;
+
 pub struct Prototype {
     pub parameters: Vec<DataType>,
     pub result: DataType,
 }
 
+impl Prototype {
+    /// Returns true if the arguments types match the parameters without implicit casting
+    pub fn is_accepting(&self, arguments_types: &[DataType]) -> bool {
+        is_parameters_accepting(&self.parameters, arguments_types)
+    }
+}
+
+/// Returns the prototype of the standard function overload that accepts the arguments types,
+/// or the first overload if none of them accept the arguments
+pub fn function_prototype(name: &str, arguments_types: &[DataType]) -> Option<&'static Prototype> {
+    let prototypes = PROTOTYPES.get(name)?;
+    prototypes
+        .iter()
+        .find(|prototype| prototype.is_accepting(arguments_types))
+        .or(prototypes.first())
+}
+
+/// Returns true if the arguments types match the parameters without implicit casting
+pub fn is_parameters_accepting(parameters: &[DataType], arguments_types: &[DataType]) -> bool {
+    match arguments_parameters_types(parameters, arguments_types.len()) {
+        Some(parameters_types) => parameters_types
+            .iter()
+            .zip(arguments_types)
+            .all(|(parameter_type, argument_type)| *parameter_type == argument_type),
+        None => false,
+    }
+}
+
+/// Returns the parameter type of each argument, the optional or varargs parameter is used for
+/// all arguments after the required parameters, or None if the number of arguments is invalid
+pub fn arguments_parameters_types(
+    parameters: &[DataType],
+    arguments_len: usize,
+) -> Option<Vec<&DataType>> {
+    let last_parameter = parameters.last();
+    let has_optional_parameter = last_parameter.is_some_and(|last| last.is_optional());
+    let has_varargs_parameter = last_parameter.is_some_and(|last| last.is_varargs());
+
+    let mut required_parameters_len = parameters.len();
+    if has_optional_parameter || has_varargs_parameter {
+        required_parameters_len -= 1;
+    }
+
+    if arguments_len < required_parameters_len
+        || (!has_varargs_parameter && arguments_len > parameters.len())
+    {
+        return None;
+    }
+
+    let last_index = parameters.len().saturating_sub(1);
+    let parameters_types = (0..arguments_len)
+        .map(|index| &parameters[index.min(last_index)])
+        .collect();
+    Some(parameters_types)
+}
+
 lazy_static! {
     pub static ref FUNCTIONS: HashMap<&'static str, Function> = {
         let mut map: HashMap<&'static str, Function> = HashMap::new();
@@ -42,10 +109,16 @@ lazy_static! {
         map.insert("concat_ws", text_concat_ws);
         map.insert("unicode", text_unicode);
         map.insert("strcmp", text_strcmp);
+        map.insert("split_part", text_split_part);
+        map.insert("regexp_extract", text_regexp_extract);
+        map.insert("regexp_replace", text_regexp_replace);
+        map.insert("md5", text_md5);
+        map.insert("sha1", text_sha1);
+        map.insert("sha256", text_sha256);
+        map.insert("hex", text_hex);
+        map.insert("base64", text_base64);
 
         // Date functions
-        map.insert("current_date", date_current_date);
-        map.insert("current_time", date_current_time);
         map.insert("current_timestamp", date_current_timestamp);
         map.insert("now", date_current_timestamp);
         map.insert("makedate", date_make_date);
@@ -53,7 +126,6 @@ lazy_static! {
         map.insert("day", date_day);
         map.insert("dayname", date_dayname);
         map.insert("monthname", date_monthname);
-        map.insert("hour", date_hour);
         map.insert("isdate", date_is_date);
 
         // Numeric functions
@@ -77,389 +149,581 @@ lazy_static! {
         map.insert("typeof", general_type_of);
         map.insert("greatest", general_greatest);
         map.insert("least", general_least);
+        map.insert("coalesce", general_coalesce);
+        map.insert("ifnull", general_if_null);
+        map.insert("nullif", general_null_if);
+        map.insert("if", general_if);
+        map.insert("choose", general_choose);
         map
     };
 }
 
 lazy_static! {
-    pub static ref PROTOTYPES: HashMap<&'static str, Prototype> = {
-        let mut map: HashMap<&'static str, Prototype> = HashMap::new();
+    /// Dates functions that calculate the calendar fields of the date time values in the `@@timezone`
+    /// session time zone, the optional time zone argument is used instead of the session time zone if passed
+    pub static ref TIME_ZONE_FUNCTIONS: HashMap<&'static str, TimeZoneFunction> = {
+        let mut map: HashMap<&'static str, TimeZoneFunction> = HashMap::new();
+        map.insert("current_date", date_current_date);
+        map.insert("current_time", date_current_time);
+        map.insert("hour", date_hour);
+        map.insert("date_add", date_add);
+        map.insert("date_sub", date_sub);
+        map.insert("datediff", date_diff);
+        map.insert("date_trunc", date_trunc);
+        map
+    };
+}
+
+/// Standard functions that return different values for the same arguments in each evaluation
+pub const NON_DETERMINISTIC_FUNCTIONS: [&str; 4] =
+    ["now", "current_date", "current_time", "current_timestamp"];
+
+/// Returns true if the name is a standard function with or without time zone
+pub fn is_standard_function(name: &str) -> bool {
+    FUNCTIONS.contains_key(name) || TIME_ZONE_FUNCTIONS.contains_key(name)
+}
+
+/// Returns the names of all standard functions with or without time zone
+pub fn standard_functions_names() -> impl Iterator<Item = &'static str> {
+    FUNCTIONS.keys().chain(TIME_ZONE_FUNCTIONS.keys()).copied()
+}
+
+lazy_static! {
+    pub static ref PROTOTYPES: HashMap<&'static str, Vec<Prototype>> = {
+        let mut map: HashMap<&'static str, Vec<Prototype>> = HashMap::new();
         // String functions
         map.insert(
             "lower",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "upper",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "reverse",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "replicate",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "space",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "trim",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "ltrim",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "rtrim",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "len",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "ascii",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "left",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "datalength",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "char",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "nchar",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "charindex",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Text],
                 result: DataType::Integer,
-            }
+            }],
         );
         map.insert(
             "replace",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Text, DataType::Text],
                 result: DataType::Text
-          },
+            }],
         );
         map.insert(
             "substring",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Integer, DataType::Integer],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "stuff",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Integer, DataType::Integer, DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "right",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Integer],
                 result: DataType::Text
-             },
+             }],
         );
         map.insert(
             "translate",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Text, DataType::Text],
                 result: DataType::Text
-             },
+             }],
         );
         map.insert(
             "soundex",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "concat",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any, DataType::Any, DataType::Varargs(Box::new(DataType::Any))],
                 result: DataType::Text
-             },
+             }],
         );
         map.insert(
             "concat_ws",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text, DataType::Any, DataType::Any, DataType::Varargs(Box::new(DataType::Any))],
                 result: DataType::Text
-             },
+             }],
         );
         map.insert(
             "unicode",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Text],
                 result: DataType::Integer
-             },
+             }],
+        );
+        map.insert(
+            "strcmp",
+            vec![Prototype {
+                parameters: vec![DataType::Text, DataType::Text],
+                result: DataType::Integer,
+            }],
+        );
+        map.insert(
+            "split_part",
+            vec![Prototype {
+                parameters: vec![DataType::Text, DataType::Text, DataType::Integer],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "regexp_extract",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Text,
+                    DataType::Text,
+                    DataType::Optional(Box::new(DataType::Integer)),
+                ],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "regexp_replace",
+            vec![Prototype {
+                parameters: vec![DataType::Text, DataType::Text, DataType::Text],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "md5",
+            vec![Prototype {
+                parameters: vec![DataType::Text],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "sha1",
+            vec![Prototype {
+                parameters: vec![DataType::Text],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "sha256",
+            vec![Prototype {
+                parameters: vec![DataType::Text],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "hex",
+            vec![Prototype {
+                parameters: vec![DataType::Variant(vec![DataType::Text, DataType::Integer])],
+                result: DataType::Text,
+            }],
+        );
+        map.insert(
+            "base64",
+            vec![Prototype {
+                parameters: vec![DataType::Text],
+                result: DataType::Text,
+            }],
         );
-        map.insert("strcmp", Prototype { parameters: vec![DataType::Text, DataType::Text], result: DataType::Integer });
 
         // Date functions
         map.insert(
             "current_date",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![],
                 result: DataType::Date,
-            },
+            }],
         );
         map.insert(
             "current_time",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![],
                 result: DataType::Time,
-            },
+            }],
         );
         map.insert(
             "current_timestamp",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![],
                 result: DataType::DateTime,
-            },
+            }],
         );
         map.insert(
             "now",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![],
                 result: DataType::DateTime,
-            },
+            }],
         );
         map.insert(
             "makedate",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer, DataType::Integer],
                 result: DataType::Date,
-            },
+            }],
         );
         map.insert(
             "maketime",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer, DataType::Integer, DataType::Integer],
                 result: DataType::Time,
-            },
+            }],
         );
         map.insert(
             "dayname",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Date],
                 result: DataType::Text,
-            }
+            }],
         );
         map.insert(
             "day",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Date],
                 result: DataType::Integer,
-            }
+            }],
         );
         map.insert(
             "monthname",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Date],
                 result: DataType::Text,
-            }
+            }],
         );
         map.insert(
             "hour",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::DateTime],
                 result: DataType::Integer,
-            }
+            }],
         );
         map.insert(
             "isdate",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any],
                 result: DataType::Boolean,
-            }
+            }],
+        );
+        map.insert(
+            "date_add",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Text,
+                    DataType::Integer,
+                    DataType::Variant(vec![DataType::Date, DataType::DateTime]),
+                    DataType::Optional(Box::new(DataType::Text)),
+                ],
+                result: DataType::DateTime,
+            }],
+        );
+        map.insert(
+            "date_sub",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Text,
+                    DataType::Integer,
+                    DataType::Variant(vec![DataType::Date, DataType::DateTime]),
+                    DataType::Optional(Box::new(DataType::Text)),
+                ],
+                result: DataType::DateTime,
+            }],
+        );
+        map.insert(
+            "datediff",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Variant(vec![DataType::Date, DataType::DateTime]),
+                    DataType::Variant(vec![DataType::Date, DataType::DateTime]),
+                    DataType::Optional(Box::new(DataType::Text)),
+                ],
+                result: DataType::Integer,
+            }],
+        );
+        map.insert(
+            "date_trunc",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Text,
+                    DataType::Variant(vec![DataType::Date, DataType::DateTime]),
+                    DataType::Optional(Box::new(DataType::Text)),
+                ],
+                result: DataType::DateTime,
+            }],
         );
         // Numeric functions
         map.insert(
             "abs",
-            Prototype {
-                parameters: vec![DataType::Integer],
-                result: DataType::Integer,
-            },
+            vec![
+                Prototype {
+                    parameters: vec![DataType::Integer],
+                    result: DataType::Integer,
+                },
+                Prototype {
+                    parameters: vec![DataType::Float],
+                    result: DataType::Float,
+                },
+            ],
         );
         map.insert(
             "pi",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "floor",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "round",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "square",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Integer],
                 result: DataType::Integer,
-            },
+            }],
         );
         map.insert(
             "sin",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "asin",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "cos",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "acos",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "tan",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "atan",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "atn2",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Float, DataType::Float],
                 result: DataType::Float,
-            },
+            }],
         );
         map.insert(
             "sign",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Variant(vec![DataType::Integer, DataType::Float])],
                 result: DataType::Integer,
-            },
+            }],
         );
         // General functions
         map.insert(
             "isnull",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any],
                 result: DataType::Boolean,
-            },
+            }],
         );
         map.insert(
             "isnumeric",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any],
                 result: DataType::Boolean,
-            },
+            }],
         );
         map.insert(
             "typeof",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any],
                 result: DataType::Text,
-            },
+            }],
         );
         map.insert(
             "greatest",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any, DataType::Any, DataType::Varargs(Box::new(DataType::Any))],
                 result: DataType::Any
-             },
+             }],
         );
         map.insert(
             "least",
-            Prototype {
+            vec![Prototype {
                 parameters: vec![DataType::Any, DataType::Any, DataType::Varargs(Box::new(DataType::Any))],
                 result: DataType::Any
-             },
+             }],
+        );
+        map.insert(
+            "coalesce",
+            vec![Prototype {
+                parameters: vec![DataType::Any, DataType::Varargs(Box::new(DataType::Any))],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "ifnull",
+            vec![Prototype {
+                parameters: vec![DataType::Any, DataType::Any],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "nullif",
+            vec![Prototype {
+                parameters: vec![DataType::Any, DataType::Any],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "if",
+            vec![Prototype {
+                parameters: vec![DataType::Boolean, DataType::Any, DataType::Any],
+                result: DataType::Any,
+            }],
+        );
+        map.insert(
+            "choose",
+            vec![Prototype {
+                parameters: vec![
+                    DataType::Integer,
+                    DataType::Any,
+                    DataType::Varargs(Box::new(DataType::Any)),
+                ],
+                result: DataType::Any,
+            }],
         );
         map
     };
@@ -629,20 +893,110 @@ fn text_right(inputs: &[Value]) -> Value {
 }
 
 fn text_translate(inputs: &[Value]) -> Value {
-    let mut text = inputs[0].as_text();
-    let characters = inputs[1].as_text();
-    let translations = inputs[2].as_text();
+    let text = inputs[0].as_text();
+    let characters = inputs[1].as_text().chars().collect::<Vec<_>>();
+    let translations = inputs[2].as_text().chars().collect::<Vec<_>>();
 
-    if translations.len() != characters.len() {
-        return Value::Text("".to_string());
+    // Translate each character once so replaced characters are not translated again,
+    // characters without translation are deleted and the extra translations are ignored
+    let text = text
+        .chars()
+        .filter_map(
+            |letter| match characters.iter().position(|c| *c == letter) {
+                Some(idx) => translations.get(idx).copied(),
+                None => Some(letter),
+            },
+        )
+        .collect();
+
+    Value::Text(text)
+}
+
+fn text_split_part(inputs: &[Value]) -> Value {
+    let text = inputs[0].as_text();
+    let delimiter = inputs[1].as_text();
+    let index = inputs[2].as_int();
+
+    let parts: Vec<&str> = if delimiter.is_empty() {
+        vec![&text]
+    } else {
+        text.split(&delimiter).collect()
+    };
+
+    // Index starts from 1, and negative index count parts from the end
+    let part = match index.cmp(&0) {
+        Ordering::Greater => parts.get(index as usize - 1),
+        Ordering::Less => index
+            .checked_neg()
+            .and_then(|index| parts.len().checked_sub(index as usize))
+            .and_then(|idx| parts.get(idx)),
+        Ordering::Equal => None,
+    };
+
+    Value::Text(part.unwrap_or(&"").to_string())
+}
+
+fn text_regexp_extract(inputs: &[Value]) -> Value {
+    let text = inputs[0].as_text();
+    let regex = match Regex::new(&inputs[1].as_text()) {
+        Ok(regex) => regex,
+        Err(_) => return Value::Null,
+    };
+
+    let group = if inputs.len() == 3 {
+        inputs[2].as_int()
+    } else {
+        0
+    };
+
+    if group < 0 {
+        return Value::Null;
     }
 
-    let translations = translations.chars().collect::<Vec<_>>();
-    for (idx, letter) in characters.char_indices() {
-        text = text.replace(letter, &char::to_string(&translations[idx]));
+    regex
+        .captures(&text)
+        .and_then(|captures| captures.get(group as usize))
+        .map(|matched| Value::Text(matched.as_str().to_string()))
+        .unwrap_or(Value::Null)
+}
+
+fn text_regexp_replace(inputs: &[Value]) -> Value {
+    let text = inputs[0].as_text();
+    let replacement = inputs[2].as_text();
+    match Regex::new(&inputs[1].as_text()) {
+        Ok(regex) => Value::Text(regex.replace_all(&text, replacement.as_str()).to_string()),
+        Err(_) => Value::Null,
     }
+}
 
-    Value::Text(text)
+fn text_md5(inputs: &[Value]) -> Value {
+    let digest = Md5::digest(inputs[0].as_text().as_bytes());
+    Value::Text(bytes_to_hex(&digest))
+}
+
+fn text_sha1(inputs: &[Value]) -> Value {
+    let digest = Sha1::digest(inputs[0].as_text().as_bytes());
+    Value::Text(bytes_to_hex(&digest))
+}
+
+fn text_sha256(inputs: &[Value]) -> Value {
+    let digest = Sha256::digest(inputs[0].as_text().as_bytes());
+    Value::Text(bytes_to_hex(&digest))
+}
+
+fn text_hex(inputs: &[Value]) -> Value {
+    match &inputs[0] {
+        Value::Integer(number) => Value::Text(format!("{:X}", number)),
+        value => Value::Text(bytes_to_hex(value.as_text().as_bytes()).to_uppercase()),
+    }
+}
+
+fn text_base64(inputs: &[Value]) -> Value {
+    Value::Text(BASE64_STANDARD.encode(inputs[0].as_text()))
+}
+
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
 fn text_unicode(inputs: &[Value]) -> Value {
@@ -713,14 +1067,17 @@ fn text_strcmp(inputs: &[Value]) -> Value {
 
 // Date functions
 
-fn date_current_date(_inputs: &[Value]) -> Value {
+fn date_current_date(_inputs: &[Value], time_zone: &DateTimeZone) -> Value {
     let time_stamp = date_utils::get_unix_timestamp_ms();
-    Value::Date(time_stamp)
+    match date_utils::time_stamp_to_date_in(time_stamp, time_zone) {
+        Some(date) => Value::Date(date),
+        None => Value::Null,
+    }
 }
 
-fn date_current_time(_inputs: &[Value]) -> Value {
+fn date_current_time(_inputs: &[Value], time_zone: &DateTimeZone) -> Value {
     let time_stamp = date_utils::get_unix_timestamp_ms();
-    let time = date_utils::time_stamp_to_time(time_stamp);
+    let time = date_utils::time_stamp_to_time_in(time_stamp, time_zone);
     Value::Time(time)
 }
 
@@ -760,19 +1117,111 @@ fn date_monthname(inputs: &[Value]) -> Value {
     Value::Text(month_str)
 }
 
-fn date_hour(inputs: &[Value]) -> Value {
+fn date_hour(inputs: &[Value], time_zone: &DateTimeZone) -> Value {
     let date = inputs[0].as_date_time();
-    let hour = date_utils::date_time_to_hour(date);
-    Value::Integer(hour)
+    match date_utils::date_time_to_hour_in(date, time_zone) {
+        Some(hour) => Value::Integer(hour),
+        None => Value::Null,
+    }
 }
 
 fn date_is_date(inputs: &[Value]) -> Value {
     Value::Boolean(inputs[0].data_type().is_date())
 }
 
+fn date_add(inputs: &[Value], session_time_zone: &DateTimeZone) -> Value {
+    let unit = inputs[0].as_text();
+    let amount = inputs[1].as_int();
+    date_add_amount(&unit, amount, &inputs[2], inputs.get(3), session_time_zone)
+}
+
+fn date_sub(inputs: &[Value], session_time_zone: &DateTimeZone) -> Value {
+    let unit = inputs[0].as_text();
+    let amount = inputs[1].as_int();
+    match amount.checked_neg() {
+        Some(amount) => {
+            date_add_amount(&unit, amount, &inputs[2], inputs.get(3), session_time_zone)
+        }
+        None => Value::Null,
+    }
+}
+
+fn date_add_amount(
+    unit: &str,
+    amount: i64,
+    date: &Value,
+    time_zone: Option<&Value>,
+    session_time_zone: &DateTimeZone,
+) -> Value {
+    let time_zone = match date_time_zone(time_zone, session_time_zone) {
+        Some(time_zone) => time_zone,
+        None => return Value::Null,
+    };
+
+    let time_stamp = date_time_stamp(date, &time_zone);
+    match date_utils::time_stamp_add(time_stamp, unit, amount, &time_zone) {
+        Some(time_stamp) => Value::DateTime(time_stamp),
+        None => Value::Null,
+    }
+}
+
+fn date_diff(inputs: &[Value], session_time_zone: &DateTimeZone) -> Value {
+    let time_zone = match date_time_zone(inputs.get(2), session_time_zone) {
+        Some(time_zone) => time_zone,
+        None => return Value::Null,
+    };
+
+    let end = date_time_stamp(&inputs[0], &time_zone);
+    let start = date_time_stamp(&inputs[1], &time_zone);
+    match date_utils::time_stamp_days_diff(end, start, &time_zone) {
+        Some(days) => Value::Integer(days),
+        None => Value::Null,
+    }
+}
+
+fn date_trunc(inputs: &[Value], session_time_zone: &DateTimeZone) -> Value {
+    let time_zone = match date_time_zone(inputs.get(2), session_time_zone) {
+        Some(time_zone) => time_zone,
+        None => return Value::Null,
+    };
+
+    let unit = inputs[0].as_text();
+    let time_stamp = date_time_stamp(&inputs[1], &time_zone);
+    match date_utils::time_stamp_truncate(time_stamp, &unit, &time_zone) {
+        Some(time_stamp) => Value::DateTime(time_stamp),
+        None => Value::Null,
+    }
+}
+
+/// Return the time stamp of Date or DateTime value, a date is the start of its day in the time zone
+fn date_time_stamp(value: &Value, time_zone: &DateTimeZone) -> i64 {
+    match value {
+        Value::Date(time_stamp) => {
+            date_utils::date_start_in(*time_stamp, time_zone).unwrap_or(*time_stamp)
+        }
+        Value::DateTime(time_stamp) => *time_stamp,
+        _ => 0,
+    }
+}
+
+/// Return the optional time zone argument, or the session time zone if it not passed
+fn date_time_zone(
+    time_zone: Option<&Value>,
+    session_time_zone: &DateTimeZone,
+) -> Option<DateTimeZone> {
+    match time_zone {
+        Some(time_zone) => date_utils::parse_time_zone(&time_zone.as_text()),
+        None => Some(*session_time_zone),
+    }
+}
+
 // Numeric functions
 
 fn numeric_abs(inputs: &[Value]) -> Value {
+    if inputs[0].data_type().is_float() {
+        return Value::Float(inputs[0].as_float().abs());
+    }
+
     let value = inputs[0].as_int();
     Value::Integer(value.abs())
 }
@@ -890,6 +1339,53 @@ fn general_least(inputs: &[Value]) -> Value {
     least.to_owned()
 }
 
+fn general_coalesce(inputs: &[Value]) -> Value {
+    inputs
+        .iter()
+        .find(|value| !matches!(value, Value::Null))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+fn general_if_null(inputs: &[Value]) -> Value {
+    general_coalesce(inputs)
+}
+
+fn general_null_if(inputs: &[Value]) -> Value {
+    if inputs[0].equals(&inputs[1]) {
+        return Value::Null;
+    }
+    inputs[0].to_owned()
+}
+
+fn general_if(inputs: &[Value]) -> Value {
+    if inputs[0].as_bool() {
+        inputs[1].to_owned()
+    } else {
+        inputs[2].to_owned()
+    }
+}
+
+fn general_choose(inputs: &[Value]) -> Value {
+    // Index starts from 1, and out of range index returns null
+    let index = inputs[0].as_int();
+    if index < 1 {
+        return Value::Null;
+    }
+
+    inputs.get(index as usize).cloned().unwrap_or(Value::Null)
+}
+
+/// Return the index of the first argument that function may return, for functions that
+/// return one of their arguments so the result type is inferred from the arguments types
+pub fn generic_result_argument_index(name: &str) -> Option<usize> {
+    match name {
+        "coalesce" | "ifnull" | "nullif" => Some(0),
+        "if" | "choose" => Some(1),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1260,7 +1756,17 @@ mod tests {
         buf.push(Value::Text("he".to_string()));
         buf.push(Value::Text("aaa".to_string()));
         if let Value::Text(v) = text_translate(&buf.to_owned()) {
-            assert_eq!(v, "");
+            assert_eq!(v, "aallo");
+        } else {
+            assert!(false);
+        }
+
+        buf.clear();
+        buf.push(Value::Text("abc".to_string()));
+        buf.push(Value::Text("abc".to_string()));
+        buf.push(Value::Text("x".to_string()));
+        if let Value::Text(v) = text_translate(&buf.to_owned()) {
+            assert_eq!(v, "x");
         } else {
             assert!(false);
         }
@@ -1274,6 +1780,133 @@ mod tests {
         } else {
             assert!(false);
         }
+
+        buf.clear();
+        buf.push(Value::Text("abc".to_string()));
+        buf.push(Value::Text("ab".to_string()));
+        buf.push(Value::Text("bc".to_string()));
+        if let Value::Text(v) = text_translate(&buf.to_owned()) {
+            assert_eq!(v, "bcc");
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_text_split_part() {
+        let mut buf: Vec<Value> = vec![
+            Value::Text("refs/heads/main".to_string()),
+            Value::Text("/".to_string()),
+            Value::Integer(2),
+        ];
+        if let Value::Text(v) = text_split_part(&buf) {
+            assert_eq!(v, "heads");
+        } else {
+            assert!(false);
+        }
+
+        buf[2] = Value::Integer(-1);
+        if let Value::Text(v) = text_split_part(&buf) {
+            assert_eq!(v, "main");
+        } else {
+            assert!(false);
+        }
+
+        buf[2] = Value::Integer(4);
+        if let Value::Text(v) = text_split_part(&buf) {
+            assert_eq!(v, "");
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_text_regexp_extract() {
+        let mut buf: Vec<Value> = Vec::new();
+
+        buf.push(Value::Text("Fix JIRA-123 crash".to_string()));
+        buf.push(Value::Text("JIRA-([0-9]+)".to_string()));
+        if let Value::Text(v) = text_regexp_extract(&buf) {
+            assert_eq!(v, "JIRA-123");
+        } else {
+            assert!(false);
+        }
+
+        buf.push(Value::Integer(1));
+        if let Value::Text(v) = text_regexp_extract(&buf) {
+            assert_eq!(v, "123");
+        } else {
+            assert!(false);
+        }
+
+        buf[0] = Value::Text("Fix crash".to_string());
+        assert!(matches!(text_regexp_extract(&buf), Value::Null));
+    }
+
+    #[test]
+    fn test_text_hash_functions() {
+        let buf = vec![Value::Text("abc".to_string())];
+
+        if let Value::Text(v) = text_md5(&buf) {
+            assert_eq!(v, "900150983cd24fb0d6963f7d28e17f72");
+        } else {
+            assert!(false);
+        }
+
+        if let Value::Text(v) = text_sha1(&buf) {
+            assert_eq!(v, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        } else {
+            assert!(false);
+        }
+
+        if let Value::Text(v) = text_sha256(&buf) {
+            assert_eq!(
+                v,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_text_encoding_functions() {
+        let buf = vec![Value::Text("abc".to_string())];
+
+        if let Value::Text(v) = text_hex(&buf) {
+            assert_eq!(v, "616263");
+        } else {
+            assert!(false);
+        }
+
+        if let Value::Text(v) = text_hex(&[Value::Integer(255)]) {
+            assert_eq!(v, "FF");
+        } else {
+            assert!(false);
+        }
+
+        if let Value::Text(v) = text_base64(&buf) {
+            assert_eq!(v, "YWJj");
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_text_regexp_replace() {
+        let mut buf: Vec<Value> = vec![
+            Value::Text("a1b22c333".to_string()),
+            Value::Text("[0-9]+".to_string()),
+            Value::Text("#".to_string()),
+        ];
+        if let Value::Text(v) = text_regexp_replace(&buf) {
+            assert_eq!(v, "a#b#c#");
+        } else {
+            assert!(false);
+        }
+
+        buf[1] = Value::Text("(".to_string());
+        assert!(matches!(text_regexp_replace(&buf), Value::Null));
     }
 
     #[test]
@@ -1391,8 +2024,9 @@ mod tests {
     #[test]
     fn test_date_current_date() {
         let buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
 
-        if let Value::Date(v) = date_current_date(&buf) {
+        if let Value::Date(v) = date_current_date(&buf, &utc) {
             println!("date_current_date: {}", v);
             assert_ne!(v, 0);
         } else {
@@ -1403,8 +2037,9 @@ mod tests {
     #[test]
     fn test_date_current_time() {
         let buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
 
-        if let Value::Time(v) = date_current_time(&buf) {
+        if let Value::Time(v) = date_current_time(&buf, &utc) {
             println!("date_current_time: {}", v);
             assert_ne!(v, "".to_string());
         } else {
@@ -1500,15 +2135,24 @@ mod tests {
     #[test]
     fn test_date_hour() {
         let mut buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
 
         buf.clear();
         buf.push(Value::DateTime(1705117592));
-        if let Value::Integer(v) = date_hour(&buf) {
+        if let Value::Integer(v) = date_hour(&buf, &utc) {
             println!("date_hour: {}", v);
             assert_ne!(v, 0);
         } else {
             assert!(false);
         }
+
+        // 2024-01-13 03:46:32 UTC is 05:46:32 at +02:00
+        let time_zone = date_utils::parse_time_zone("+02:00").unwrap();
+        if let Value::Integer(v) = date_hour(&buf, &time_zone) {
+            assert_eq!(v, 5);
+        } else {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -1524,6 +2168,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_date_add_and_sub() {
+        let mut buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
+
+        // 2024-01-31 00:00:00 UTC
+        buf.push(Value::Text("month".to_string()));
+        buf.push(Value::Integer(1));
+        buf.push(Value::Date(1706659200));
+        if let Value::DateTime(v) = date_add(&buf, &utc) {
+            assert_eq!(
+                v,
+                date_utils::date_time_to_time_stamp("2024-02-29 00:00:00")
+            );
+        } else {
+            assert!(false);
+        }
+
+        buf.clear();
+        buf.push(Value::Text("hour".to_string()));
+        buf.push(Value::Integer(2));
+        buf.push(Value::DateTime(1706659200));
+        if let Value::DateTime(v) = date_sub(&buf, &utc) {
+            assert_eq!(v, 1706659200 - 7200);
+        } else {
+            assert!(false);
+        }
+
+        buf.push(Value::Text("Mars/Olympus".to_string()));
+        assert!(matches!(date_sub(&buf, &utc), Value::Null));
+    }
+
+    #[test]
+    fn test_date_diff() {
+        let mut buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
+
+        buf.push(Value::DateTime(date_utils::date_time_to_time_stamp(
+            "2024-01-31 23:30:00",
+        )));
+        buf.push(Value::Date(date_utils::date_to_time_stamp("2024-01-30")));
+        if let Value::Integer(v) = date_diff(&buf, &utc) {
+            assert_eq!(v, 1);
+        } else {
+            assert!(false);
+        }
+
+        let time_zone = date_utils::parse_time_zone("+02:00").unwrap();
+        if let Value::Integer(v) = date_diff(&buf, &time_zone) {
+            assert_eq!(v, 2);
+        } else {
+            assert!(false);
+        }
+
+        buf.push(Value::Text("+02:00".to_string()));
+        if let Value::Integer(v) = date_diff(&buf, &utc) {
+            assert_eq!(v, 2);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_date_trunc() {
+        let mut buf: Vec<Value> = Vec::new();
+        let utc = DateTimeZone::default();
+
+        buf.push(Value::Text("month".to_string()));
+        buf.push(Value::DateTime(date_utils::date_time_to_time_stamp(
+            "2024-01-31 23:30:00",
+        )));
+        if let Value::DateTime(v) = date_trunc(&buf, &utc) {
+            assert_eq!(
+                v,
+                date_utils::date_time_to_time_stamp("2024-01-01 00:00:00")
+            );
+        } else {
+            assert!(false);
+        }
+
+        buf.push(Value::Text("+02:00".to_string()));
+        if let Value::DateTime(v) = date_trunc(&buf, &utc) {
+            assert_eq!(
+                v,
+                date_utils::date_time_to_time_stamp("2024-01-31 22:00:00")
+            );
+        } else {
+            assert!(false);
+        }
+
+        buf[2] = Value::Text("Europe/Paris".to_string());
+        if let Value::DateTime(v) = date_trunc(&buf, &utc) {
+            assert_eq!(
+                v,
+                date_utils::date_time_to_time_stamp("2024-01-31 23:00:00")
+            );
+        } else {
+            assert!(false);
+        }
+
+        buf[0] = Value::Text("fortnight".to_string());
+        assert!(matches!(date_trunc(&buf, &utc), Value::Null));
+    }
+
     // Numeric functions
 
     #[test]
@@ -1545,6 +2293,45 @@ mod tests {
         } else {
             assert!(false);
         }
+
+        buf.clear();
+        buf.push(Value::Float(-1.5));
+        if let Value::Float(v) = numeric_abs(&buf.to_owned()) {
+            assert_eq!(v, 1.5);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_function_prototype_overloads() {
+        let prototype = function_prototype("abs", &[DataType::Integer]).unwrap();
+        assert!(prototype.result == DataType::Integer);
+
+        let prototype = function_prototype("abs", &[DataType::Float]).unwrap();
+        assert!(prototype.result == DataType::Float);
+
+        let prototype = function_prototype("abs", &[DataType::Text]).unwrap();
+        assert!(prototype.result == DataType::Integer);
+
+        assert!(function_prototype("unknown", &[]).is_none());
+    }
+
+    #[test]
+    fn test_arguments_parameters_types() {
+        let parameters = vec![DataType::Text, DataType::Optional(Box::new(DataType::Text))];
+        assert!(arguments_parameters_types(&parameters, 0).is_none());
+        assert_eq!(arguments_parameters_types(&parameters, 2).unwrap().len(), 2);
+        assert!(arguments_parameters_types(&parameters, 3).is_none());
+
+        let parameters = vec![DataType::Varargs(Box::new(DataType::Any))];
+        assert_eq!(arguments_parameters_types(&parameters, 3).unwrap().len(), 3);
+
+        assert!(is_parameters_accepting(&[], &[]));
+        assert!(!is_parameters_accepting(
+            &[DataType::Integer],
+            &[DataType::Float]
+        ));
     }
 
     #[test]
@@ -1876,4 +2663,55 @@ mod tests {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_general_if_and_choose() {
+        let buf = vec![Value::Boolean(false), Value::Integer(1), Value::Integer(2)];
+        if let Value::Integer(v) = general_if(&buf) {
+            assert_eq!(v, 2);
+        } else {
+            assert!(false);
+        }
+
+        let mut buf = vec![
+            Value::Integer(2),
+            Value::Text("a".to_string()),
+            Value::Text("b".to_string()),
+        ];
+        if let Value::Text(v) = general_choose(&buf) {
+            assert_eq!(v, "b");
+        } else {
+            assert!(false);
+        }
+
+        buf[0] = Value::Integer(3);
+        assert!(matches!(general_choose(&buf), Value::Null));
+    }
+
+    #[test]
+    fn test_general_null_handling() {
+        let buf = vec![Value::Null, Value::Null, Value::Integer(3)];
+        if let Value::Integer(v) = general_coalesce(&buf) {
+            assert_eq!(v, 3);
+        } else {
+            assert!(false);
+        }
+
+        let buf = vec![Value::Null, Value::Text("gql".to_string())];
+        if let Value::Text(v) = general_if_null(&buf) {
+            assert_eq!(v, "gql");
+        } else {
+            assert!(false);
+        }
+
+        let buf = vec![Value::Integer(1), Value::Integer(1)];
+        assert!(matches!(general_null_if(&buf), Value::Null));
+
+        let buf = vec![Value::Integer(1), Value::Integer(2)];
+        if let Value::Integer(v) = general_null_if(&buf) {
+            assert_eq!(v, 1);
+        } else {
+            assert!(false);
+        }
+    }
 }
@@ -2,8 +2,14 @@ pub mod aggregation;
 pub mod date_utils;
 pub mod environment;
 pub mod expression;
+pub mod external;
+pub mod format;
 pub mod function;
 pub mod object;
+pub mod provider;
+pub mod rows_cache;
+pub mod session;
+pub mod spill;
 pub mod statement;
 pub mod types;
 pub mod value;
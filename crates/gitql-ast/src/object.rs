@@ -1,16 +1,18 @@
 use std::error::Error;
 
+use crate::format::value_to_text;
+use crate::session::SessionOptions;
+use crate::types::DataType;
 use crate::value::Value;
-use csv::Writer;
 
 /// In memory representation of the list of [`Value`] in one Row
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Row {
     pub values: Vec<Value>,
 }
 
 /// In memory representation of the Rows of one [`Group`]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Group {
     pub rows: Vec<Row>,
 }
@@ -27,8 +29,66 @@ impl Group {
     }
 }
 
+/// Name and type of one column of the [`GitQLObject`]
+#[derive(Clone)]
+pub struct Column {
+    pub name: String,
+    pub data_type: DataType,
+}
+
+/// Read only view of one [`Row`] with access to the values by the column name
+pub struct ResultRow<'a> {
+    titles: &'a [String],
+    row: &'a Row,
+}
+
+impl<'a> ResultRow<'a> {
+    /// Returns the value of the column, or `None` if there is no column with this name
+    pub fn get(&self, column: &str) -> Option<&'a Value> {
+        let index = self.titles.iter().position(|title| title == column)?;
+        self.row.values.get(index)
+    }
+
+    /// Returns the integer value of the column, or `None` if it's missing or has another type
+    pub fn get_int(&self, column: &str) -> Option<i64> {
+        match self.get(column)? {
+            Value::Integer(integer) => Some(*integer),
+            _ => None,
+        }
+    }
+
+    /// Returns the float value of the column, or `None` if it's missing or has another type
+    pub fn get_float(&self, column: &str) -> Option<f64> {
+        match self.get(column)? {
+            Value::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean value of the column, or `None` if it's missing or has another type
+    pub fn get_bool(&self, column: &str) -> Option<bool> {
+        match self.get(column)? {
+            Value::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// Returns the text value of the column, or `None` if it's missing or has another type
+    pub fn get_text(&self, column: &str) -> Option<&'a str> {
+        match self.get(column)? {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of the row in the same order of the columns
+    pub fn values(&self) -> &'a [Value] {
+        &self.row.values
+    }
+}
+
 /// In memory representation of the GitQL Object which has titles and groups
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct GitQLObject {
     pub titles: Vec<String>,
     pub groups: Vec<Group>,
@@ -46,6 +106,29 @@ impl GitQLObject {
         self.groups.push(Group { rows })
     }
 
+    /// Remove the columns that are selected only to be used by other parts of the query,
+    /// the groups are flatted first so the exporters that read only the first group see all rows
+    pub fn remove_hidden_selections(&mut self, hidden_selections: &[String]) {
+        if self.len() > 1 {
+            self.flat();
+        }
+
+        let mut index = self.titles.len();
+        while index > 0 {
+            index -= 1;
+            if !hidden_selections.contains(&self.titles[index]) {
+                continue;
+            }
+
+            self.titles.remove(index);
+            for group in &mut self.groups {
+                for row in &mut group.rows {
+                    row.values.remove(index);
+                }
+            }
+        }
+    }
+
     /// Returns true of there is no groups
     pub fn is_empty(&self) -> bool {
         self.groups.is_empty()
@@ -56,8 +139,42 @@ impl GitQLObject {
         self.groups.len()
     }
 
+    /// Returns the columns of the object, the type of each column is the type of its first non null value
+    /// or [`DataType::Null`] if all of its values are null
+    pub fn columns(&self) -> Vec<Column> {
+        self.titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| {
+                let data_type = self
+                    .groups
+                    .iter()
+                    .flat_map(|group| group.rows.iter())
+                    .filter_map(|row| row.values.get(index))
+                    .find(|value| !matches!(value, Value::Null))
+                    .map_or(DataType::Null, |value| value.data_type());
+
+                Column {
+                    name: title.to_string(),
+                    data_type,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the rows of all groups
+    pub fn rows(&self) -> impl Iterator<Item = ResultRow<'_>> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.rows.iter())
+            .map(|row| ResultRow {
+                titles: &self.titles,
+                row,
+            })
+    }
+
     /// Export the GitQLObject as JSON String
-    pub fn as_json(&self) -> serde_json::Result<String> {
+    pub fn as_json(&self, options: &SessionOptions) -> serde_json::Result<String> {
         let mut elements: Vec<serde_json::Value> = vec![];
 
         if let Some(group) = self.groups.first() {
@@ -65,10 +182,7 @@ impl GitQLObject {
             for row in &group.rows {
                 let mut object = serde_json::Map::new();
                 for (i, value) in row.values.iter().enumerate() {
-                    object.insert(
-                        titles[i].to_string(),
-                        serde_json::Value::String(value.to_string()),
-                    );
+                    object.insert(titles[i].to_string(), value_to_json(value, options));
                 }
                 elements.push(serde_json::Value::Object(object));
             }
@@ -77,21 +191,143 @@ impl GitQLObject {
         serde_json::to_string(&serde_json::Value::Array(elements))
     }
 
+    /// Export the GitQLObject as JSON Lines String, each row is JSON object on its own line
+    pub fn as_json_lines(&self, options: &SessionOptions) -> serde_json::Result<String> {
+        let mut lines = String::new();
+        if let Some(group) = self.groups.first() {
+            for row in &group.rows {
+                let mut object = serde_json::Map::new();
+                for (i, value) in row.values.iter().enumerate() {
+                    object.insert(self.titles[i].to_string(), value_to_json(value, options));
+                }
+                lines.push_str(&serde_json::to_string(&serde_json::Value::Object(object))?);
+                lines.push('\n');
+            }
+        }
+        Ok(lines)
+    }
+
     /// Export the GitQLObject as CSV String
-    pub fn as_csv(&self) -> Result<String, Box<dyn Error>> {
-        let mut writer = Writer::from_writer(vec![]);
-        writer.write_record(self.titles.clone())?;
-        let row_len = self.titles.len();
+    pub fn as_csv(&self, options: &SessionOptions) -> Result<String, Box<dyn Error>> {
+        self.as_delimited(b',', true, options)
+    }
+
+    /// Export the GitQLObject as delimiter separated values, for example `,` for CSV and `\t` for TSV,
+    /// records end with CRLF and fields are quoted only if needed as described in RFC 4180,
+    /// `NULL` is written as an empty field and the empty text as `""` so readers can tell them apart
+    pub fn as_delimited(
+        &self,
+        delimiter: u8,
+        include_header: bool,
+        options: &SessionOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let delimiter = char::from(delimiter);
+        let mut content = String::new();
+
+        if include_header {
+            let titles: Vec<Option<String>> = self
+                .titles
+                .iter()
+                .map(|title| Some(title.to_string()))
+                .collect();
+            push_delimited_record(&mut content, &titles, delimiter);
+        }
+
         if let Some(group) = self.groups.first() {
             for row in &group.rows {
-                let mut values_row: Vec<String> = Vec::with_capacity(row_len);
-                for value in &row.values {
-                    values_row.push(value.to_string());
+                let fields: Vec<Option<String>> = row
+                    .values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Null => None,
+                        _ => Some(value_to_text(value, options)),
+                    })
+                    .collect();
+                push_delimited_record(&mut content, &fields, delimiter);
+            }
+        }
+        Ok(content)
+    }
+}
+
+/// Append the fields separated by the delimiter and ended by CRLF, `None` fields are left empty
+fn push_delimited_record(content: &mut String, fields: &[Option<String>], delimiter: char) {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            content.push(delimiter);
+        }
+
+        let Some(field) = field else {
+            continue;
+        };
+
+        let is_quoted = field.is_empty()
+            || field
+                .chars()
+                .any(|character| matches!(character, '"' | '\r' | '\n') || character == delimiter);
+        if is_quoted {
+            content.push('"');
+            content.push_str(&field.replace('"', "\"\""));
+            content.push('"');
+        } else {
+            content.push_str(field);
+        }
+    }
+    content.push_str("\r\n");
+}
+
+/// Convert value to JSON keeping numbers, booleans and nulls typed, other values are formatted as text
+pub fn value_to_json(value: &Value, options: &SessionOptions) -> serde_json::Value {
+    match value {
+        Value::Integer(integer) => serde_json::Value::from(*integer),
+        Value::Float(float) => serde_json::Number::from_f64(*float)
+            .map_or(serde_json::Value::Null, serde_json::Value::Number),
+        Value::Boolean(boolean) => serde_json::Value::Bool(*boolean),
+        Value::Null => serde_json::Value::Null,
+        _ => serde_json::Value::String(value_to_text(value, options)),
+    }
+}
+
+/// Serialize the GitQLObject as a sequence of rows, each row is a map from the column name to its value
+#[cfg(feature = "serde")]
+impl serde::Serialize for GitQLObject {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        use serde::ser::SerializeSeq;
+
+        struct RowEntries<'a>(ResultRow<'a>);
+
+        impl serde::Serialize for RowEntries<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.titles.len()))?;
+                for (title, value) in self.0.titles.iter().zip(self.0.values()) {
+                    map.serialize_entry(title, value)?;
                 }
-                writer.write_record(values_row)?;
+                map.end()
             }
         }
-        Ok(String::from_utf8(writer.into_inner()?)?)
+
+        let rows_count = self.groups.iter().map(|group| group.len()).sum();
+        let mut seq = serializer.serialize_seq(Some(rows_count))?;
+        for row in self.rows() {
+            seq.serialize_element(&RowEntries(row))?;
+        }
+        seq.end()
+    }
+}
+
+/// Serialize the value keeping numbers, booleans and nulls typed, other values are formatted as text
+/// with the default session options
+#[cfg(feature = "serde")]
+impl serde::Serialize for Value {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::Float(float) => serializer.serialize_f64(*float),
+            Value::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            Value::Null => serializer.serialize_none(),
+            _ => serializer.serialize_str(&value_to_text(self, &SessionOptions::default())),
+        }
     }
 }
 
@@ -176,6 +412,51 @@ mod tests {
         assert_eq!(ret, 1);
     }
 
+    #[test]
+    fn test_gitqlobject_typed_access() {
+        let object = GitQLObject {
+            titles: vec![
+                "name".to_string(),
+                "commit_count".to_string(),
+                "email".to_string(),
+            ],
+            groups: vec![
+                Group {
+                    rows: vec![Row {
+                        values: vec![
+                            Value::Text("main".to_string()),
+                            Value::Integer(10),
+                            Value::Null,
+                        ],
+                    }],
+                },
+                Group {
+                    rows: vec![Row {
+                        values: vec![
+                            Value::Text("dev".to_string()),
+                            Value::Integer(3),
+                            Value::Null,
+                        ],
+                    }],
+                },
+            ],
+        };
+
+        let columns = object.columns();
+        assert_eq!(columns.len(), 3);
+        assert!(columns[0].data_type.is_text());
+        assert!(columns[1].data_type.is_int());
+        assert!(columns[2].data_type.is_null());
+
+        let rows: Vec<ResultRow> = object.rows().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_text("name"), Some("main"));
+        assert_eq!(rows[1].get_int("commit_count"), Some(3));
+        assert_eq!(rows[1].get_text("commit_count"), None);
+        assert_eq!(rows[1].get_int("unknown"), None);
+        assert!(matches!(rows[0].get("email"), Some(Value::Null)));
+    }
+
     #[test]
     fn test_gitqlobject_as_json() {
         let object = GitQLObject {
@@ -197,7 +478,7 @@ mod tests {
             ],
         };
 
-        if let Ok(ret) = object.as_json() {
+        if let Ok(ret) = object.as_json(&SessionOptions::default()) {
             println!("{}", ret);
             assert!(true);
         } else {
@@ -205,6 +486,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_gitqlobject_as_typed_json() {
+        let object = GitQLObject {
+            titles: vec![
+                "count".to_string(),
+                "ratio".to_string(),
+                "is_head".to_string(),
+                "name".to_string(),
+                "email".to_string(),
+            ],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        Value::Integer(1),
+                        Value::Float(0.5),
+                        Value::Boolean(true),
+                        Value::Text("gql".to_string()),
+                        Value::Null,
+                    ],
+                }],
+            }],
+        };
+
+        let json = object.as_json(&SessionOptions::default());
+        assert!(json.is_ok());
+        assert_eq!(
+            json.ok().unwrap(),
+            r#"[{"count":1,"email":null,"is_head":true,"name":"gql","ratio":0.5}]"#
+        );
+    }
+
+    #[test]
+    fn test_gitqlobject_as_json_lines() {
+        let object = GitQLObject {
+            titles: vec!["count".to_string(), "name".to_string()],
+            groups: vec![Group {
+                rows: vec![
+                    Row {
+                        values: vec![Value::Integer(1), Value::Text("gql".to_string())],
+                    },
+                    Row {
+                        values: vec![Value::Integer(2), Value::Null],
+                    },
+                ],
+            }],
+        };
+
+        let json_lines = object.as_json_lines(&SessionOptions::default());
+        assert!(json_lines.is_ok());
+        assert_eq!(
+            json_lines.ok().unwrap(),
+            "{\"count\":1,\"name\":\"gql\"}\n{\"count\":2,\"name\":null}\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_gitqlobject_serialize() {
+        let object = GitQLObject {
+            titles: vec!["count".to_string(), "name".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![Value::Integer(1), Value::Null],
+                }],
+            }],
+        };
+
+        let json = serde_json::to_string(&object);
+        assert!(json.is_ok());
+        assert_eq!(json.ok().unwrap(), r#"[{"count":1,"name":null}]"#);
+    }
+
+    #[test]
+    fn test_gitqlobject_as_delimited() {
+        let object = GitQLObject {
+            titles: vec!["title".to_string(), "count".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        Value::Text("Fix \"parser\", tokenizer".to_string()),
+                        Value::Integer(2),
+                    ],
+                }],
+            }],
+        };
+
+        let csv = object.as_delimited(b',', true, &SessionOptions::default());
+        assert!(csv.is_ok());
+        assert_eq!(
+            csv.ok().unwrap(),
+            "title,count\r\n\"Fix \"\"parser\"\", tokenizer\",2\r\n"
+        );
+
+        let tsv = object.as_delimited(b'\t', false, &SessionOptions::default());
+        assert!(tsv.is_ok());
+        assert_eq!(
+            tsv.ok().unwrap(),
+            "\"Fix \"\"parser\"\", tokenizer\"\t2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_gitqlobject_as_delimited_null() {
+        let object = GitQLObject {
+            titles: vec!["name".to_string(), "email".to_string(), "count".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![Value::Text("".to_string()), Value::Null, Value::Integer(1)],
+                }],
+            }],
+        };
+
+        let csv = object.as_delimited(b',', false, &SessionOptions::default());
+        assert!(csv.is_ok());
+        assert_eq!(csv.ok().unwrap(), "\"\",,1\r\n");
+
+        let tsv = object.as_delimited(b'\t', true, &SessionOptions::default());
+        assert!(tsv.is_ok());
+        assert_eq!(tsv.ok().unwrap(), "name\temail\tcount\r\n\"\"\t\t1\r\n");
+    }
+
     #[test]
     fn test_gitqlobject_as_csv() {
         let object = GitQLObject {
@@ -226,7 +628,7 @@ mod tests {
             ],
         };
 
-        if let Ok(ret) = object.as_csv() {
+        if let Ok(ret) = object.as_csv(&SessionOptions::default()) {
             println!("{}", ret);
             assert!(true);
         } else {
@@ -0,0 +1,24 @@
+use crate::object::Row;
+
+/// Source of rows for a custom table, so downstream crates can make their own data queryable
+///
+/// ```ignore
+/// struct IssuesProvider;
+///
+/// impl DataProvider for IssuesProvider {
+///     fn provide(&self, _table_name: &str) -> Result<Vec<Row>, String> {
+///         Ok(vec![Row { values: vec![Value::Integer(1), Value::Text("Crash".to_string())] }])
+///     }
+/// }
+///
+/// register_data_provider(
+///     &mut env,
+///     "issues",
+///     vec![("id", DataType::Integer), ("title", DataType::Text)],
+///     Box::new(IssuesProvider),
+/// )?;
+/// ```
+pub trait DataProvider: Send + Sync {
+    /// Returns all rows of the table, each row has one value for each registered field in the same order
+    fn provide(&self, table_name: &str) -> Result<Vec<Row>, String>;
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::object::Group;
+
+/// Identify the rows of one table collected from one repository at specific HEAD commit and references,
+/// only the selected fields are collected so expensive fields are calculated only if they are used
+#[derive(Clone, PartialEq)]
+pub struct RowsCacheKey {
+    pub repo_path: String,
+    pub table_name: String,
+    pub fields_names: Vec<String>,
+    pub head_id: String,
+    pub refs_fingerprint: u64,
+}
+
+impl RowsCacheKey {
+    /// Returns true if the key is for the same repository and table but different HEAD or references
+    fn is_outdated_by(&self, other: &RowsCacheKey) -> bool {
+        self.repo_path == other.repo_path
+            && self.table_name == other.table_name
+            && (self.head_id != other.head_id || self.refs_fingerprint != other.refs_fingerprint)
+    }
+}
+
+/// Least recently used cache for the collected table rows, used to avoid walking
+/// the whole repository history again for each query in the same session
+///
+/// The rows are shared so the cache lock is held only to find or insert them, not while they are used,
+/// the default cache has zero capacity so it's disabled until the capacity is set
+#[derive(Default)]
+pub struct RowsCache {
+    capacity: usize,
+    entries: VecDeque<(RowsCacheKey, Arc<Group>)>,
+}
+
+impl RowsCache {
+    pub const fn new(capacity: usize) -> Self {
+        RowsCache {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Returns true if the cache can store at least one entry
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Change the maximum number of entries, and remove the least recently used entries if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Returns the cached rows and mark them as the most recently used
+    pub fn get(&mut self, key: &RowsCacheKey) -> Option<Arc<Group>> {
+        let index = self.entries.iter().position(|entry| entry.0 == *key)?;
+        let entry = self.entries.remove(index)?;
+        let group = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(group)
+    }
+
+    /// Insert the rows, entries for the same repository and table with old HEAD or references are removed
+    pub fn insert(&mut self, key: RowsCacheKey, group: Arc<Group>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        self.entries.retain(|entry| !entry.0.is_outdated_by(&key));
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back((key, group));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::environment::Environment;
+
+    fn cache_key(repo_path: &str, head_id: &str) -> RowsCacheKey {
+        RowsCacheKey {
+            repo_path: repo_path.to_string(),
+            table_name: "commits".to_string(),
+            fields_names: vec!["title".to_string()],
+            head_id: head_id.to_string(),
+            refs_fingerprint: 0,
+        }
+    }
+
+    #[test]
+    fn test_rows_cache_lru() {
+        let mut cache = RowsCache::new(2);
+        cache.insert(cache_key("first", "1"), Arc::new(Group { rows: vec![] }));
+        cache.insert(cache_key("second", "1"), Arc::new(Group { rows: vec![] }));
+
+        // Mark first as recently used, so second will be removed
+        assert!(cache.get(&cache_key("first", "1")).is_some());
+        cache.insert(cache_key("third", "1"), Arc::new(Group { rows: vec![] }));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&cache_key("first", "1")).is_some());
+        assert!(cache.get(&cache_key("second", "1")).is_none());
+    }
+
+    #[test]
+    fn test_rows_cache_invalidation() {
+        let mut cache = RowsCache::new(4);
+        cache.insert(cache_key("first", "1"), Arc::new(Group { rows: vec![] }));
+        cache.insert(cache_key("first", "2"), Arc::new(Group { rows: vec![] }));
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&cache_key("first", "1")).is_none());
+        assert!(cache.get(&cache_key("first", "2")).is_some());
+
+        // Moving any reference invalidates the rows, even if HEAD is not changed
+        let mut moved_refs_key = cache_key("first", "2");
+        moved_refs_key.refs_fingerprint = 1;
+        cache.insert(moved_refs_key.clone(), Arc::new(Group { rows: vec![] }));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&moved_refs_key).is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_rows_cache_selected_fields() {
+        let mut cache = RowsCache::new(4);
+        let mut signature_key = cache_key("first", "1");
+        signature_key.fields_names = vec!["title".to_string(), "signature_status".to_string()];
+
+        // Rows with different selected fields are cached together for the same HEAD
+        cache.insert(cache_key("first", "1"), Arc::new(Group { rows: vec![] }));
+        assert!(cache.get(&signature_key).is_none());
+        cache.insert(signature_key.clone(), Arc::new(Group { rows: vec![] }));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&cache_key("first", "1")).is_some());
+        assert!(cache.get(&signature_key).is_some());
+    }
+
+    #[test]
+    fn test_environment_rows_cache() {
+        let mut env = Environment::default();
+        assert!(!env.rows_cache.lock().unwrap().is_enabled());
+
+        // Threads environments are cloned from the session environment and share its cache
+        env.rows_cache = Arc::new(Mutex::new(RowsCache::new(2)));
+        let thread_env = env.clone();
+        let group = Arc::new(Group { rows: vec![] });
+        thread_env
+            .rows_cache
+            .lock()
+            .unwrap()
+            .insert(cache_key("first", "1"), group);
+        assert_eq!(env.rows_cache.lock().unwrap().len(), 1);
+
+        // Each session has its own cache
+        assert!(Environment::default().rows_cache.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disabled_rows_cache() {
+        let mut cache = RowsCache::new(0);
+        cache.insert(cache_key("first", "1"), Arc::new(Group { rows: vec![] }));
+        assert!(cache.is_empty());
+    }
+}
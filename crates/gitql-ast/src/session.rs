@@ -0,0 +1,223 @@
+use crate::date_utils::parse_time_zone;
+use crate::date_utils::DateTimeZone;
+use crate::environment::Environment;
+use crate::types::DataType;
+use crate::value::Value;
+
+/// Name of the session variable that select the default time zone of dates functions and renderers
+pub const TIME_ZONE_VARIABLE: &str = "@@timezone";
+
+/// Name of the session variable that select the output format of the query result
+pub const OUTPUT_FORMAT_VARIABLE: &str = "@@output_format";
+
+/// Name of the session variable that select the text used to represent `NULL` values
+pub const NULL_TEXT_VARIABLE: &str = "@@null_text";
+
+/// Name of the session variable that select the minimum similarity percentage of renamed files in diffs
+pub const RENAME_SIMILARITY_VARIABLE: &str = "@@rename_similarity";
+
+/// Name of the session variable that select the integer seed of the random rows sampling, empty for a random seed
+pub const SEED_VARIABLE: &str = "@@seed";
+
+/// Default minimum similarity percentage of renamed files, same as git default
+pub const DEFAULT_RENAME_SIMILARITY: u8 = 50;
+
+/// Reserved global variables that tweak the engine and renderers behaviour, with their default values
+pub const SESSION_VARIABLES: [(&str, &str); 5] = [
+    (NULL_TEXT_VARIABLE, "Null"),
+    (OUTPUT_FORMAT_VARIABLE, "render"),
+    (RENAME_SIMILARITY_VARIABLE, "50"),
+    (SEED_VARIABLE, ""),
+    (TIME_ZONE_VARIABLE, "UTC"),
+];
+
+/// Names of the supported values of the `@@output_format` session variable
+pub const OUTPUT_FORMATS: [&str; 5] = ["render", "json", "csv", "tsv", "sqlite"];
+
+/// Options consulted by the engine and renderers, updated after each `SET` of a session variable
+#[derive(Clone, Default)]
+pub struct SessionOptions {
+    pub null_text: Option<String>,
+    pub time_zone: DateTimeZone,
+}
+
+impl SessionOptions {
+    /// Returns the text used to represent `NULL` values, default is `Null`
+    pub fn null_text(&self) -> &str {
+        self.null_text.as_deref().unwrap_or("Null")
+    }
+}
+
+/// Returns true if the name is one of the reserved session variables
+pub fn is_session_variable(name: &str) -> bool {
+    session_variable_default(name).is_some()
+}
+
+/// Returns the default value of the session variable if it exists
+pub fn session_variable_default(name: &str) -> Option<&'static str> {
+    SESSION_VARIABLES
+        .iter()
+        .find(|(variable, _)| *variable == name)
+        .map(|(_, default)| *default)
+}
+
+/// Returns the type of the session variable if it exists, `@@seed` is integer and the others are text
+pub fn session_variable_type(name: &str) -> Option<DataType> {
+    match name {
+        SEED_VARIABLE => Some(DataType::Integer),
+        _ => is_session_variable(name).then_some(DataType::Text),
+    }
+}
+
+/// Convert the text value of the session variable from the config to its type, empty integer is `NULL`
+pub fn typed_session_variable_value(name: &str, value: &str) -> Value {
+    match session_variable_type(name) {
+        Some(DataType::Integer) => value
+            .trim()
+            .parse::<i64>()
+            .map_or(Value::Null, Value::Integer),
+        _ => Value::Text(value.to_string()),
+    }
+}
+
+/// Check that the value is valid for the session variable, returns the error message if not
+pub fn check_session_variable_value(name: &str, value: &str) -> Result<(), String> {
+    match name {
+        TIME_ZONE_VARIABLE if parse_time_zone(value).is_none() => Err(format!(
+            "Invalid time zone `{}` for `{}`, expect `UTC`, offset like `+02:00` or name like `Europe/Paris`",
+            value, name
+        )),
+        OUTPUT_FORMAT_VARIABLE if !OUTPUT_FORMATS.contains(&value.to_lowercase().as_str()) => {
+            Err(format!(
+                "Invalid output format `{}` for `{}`, expect one of {}",
+                value,
+                name,
+                OUTPUT_FORMATS.join(", ")
+            ))
+        }
+        RENAME_SIMILARITY_VARIABLE if parse_rename_similarity(value).is_none() => Err(format!(
+            "Invalid rename similarity `{}` for `{}`, expect percentage from 0 to 100",
+            value, name
+        )),
+        SEED_VARIABLE if !value.is_empty() && parse_seed(value).is_none() => Err(format!(
+            "Invalid seed `{}` for `{}`, expect positive integer or empty text",
+            value, name
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Parse the seed of the random rows sampling, returns None if it's empty or invalid
+pub fn parse_seed(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok()
+}
+
+/// Parse the rename similarity percentage, valid values are from 0 to 100
+pub fn parse_rename_similarity(value: &str) -> Option<u8> {
+    value
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|percentage| *percentage <= 100)
+}
+
+/// Returns the value of the session variable as text, or the default value if it's not set
+pub fn session_variable_value(env: &Environment, name: &str) -> Option<String> {
+    match env.globals.get(name) {
+        Some(Value::Null) | None => session_variable_default(name).map(|value| value.to_string()),
+        Some(value) => Some(value.as_text()),
+    }
+}
+
+/// Update the session options of the environment from its session variables
+pub fn apply_session_variables(env: &mut Environment) {
+    let null_text = session_variable_value(env, NULL_TEXT_VARIABLE);
+    let time_zone = session_variable_value(env, TIME_ZONE_VARIABLE)
+        .and_then(|time_zone| parse_time_zone(&time_zone))
+        .unwrap_or_default();
+
+    env.session = SessionOptions {
+        null_text,
+        time_zone,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_variable_default() {
+        assert_eq!(session_variable_default("@@timezone"), Some("UTC"));
+        assert_eq!(session_variable_default("@@null_text"), Some("Null"));
+        assert_eq!(session_variable_default("@@unknown"), None);
+        assert!(!is_session_variable("@timezone"));
+    }
+
+    #[test]
+    fn test_check_session_variable_value() {
+        assert!(check_session_variable_value("@@timezone", "+02:00").is_ok());
+        assert!(check_session_variable_value("@@timezone", "Europe/Paris").is_ok());
+        assert!(check_session_variable_value("@@timezone", "Mars").is_err());
+        assert!(check_session_variable_value("@@output_format", "JSON").is_ok());
+        assert!(check_session_variable_value("@@output_format", "xml").is_err());
+        assert!(check_session_variable_value("@@null_text", "").is_ok());
+        assert!(check_session_variable_value("@@rename_similarity", "75").is_ok());
+        assert!(check_session_variable_value("@@rename_similarity", "101").is_err());
+        assert!(check_session_variable_value("@@rename_similarity", "high").is_err());
+        assert!(check_session_variable_value("@@seed", "42").is_ok());
+        assert!(check_session_variable_value("@@seed", "").is_ok());
+        assert!(check_session_variable_value("@@seed", "-1").is_err());
+    }
+
+    #[test]
+    fn test_session_variable_type() {
+        assert!(session_variable_type("@@seed") == Some(DataType::Integer));
+        assert!(session_variable_type("@@timezone") == Some(DataType::Text));
+        assert!(session_variable_type("@@unknown").is_none());
+
+        assert!(typed_session_variable_value("@@seed", "3").equals(&Value::Integer(3)));
+        assert!(typed_session_variable_value("@@seed", "").equals(&Value::Null));
+        assert!(
+            typed_session_variable_value("@@null_text", "3").equals(&Value::Text("3".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_session_variable_value() {
+        let mut env = Environment::default();
+        assert_eq!(
+            session_variable_value(&env, "@@output_format"),
+            Some("render".to_string())
+        );
+
+        env.globals.insert(
+            "@@output_format".to_string(),
+            Value::Text("csv".to_string()),
+        );
+        assert_eq!(
+            session_variable_value(&env, "@@output_format"),
+            Some("csv".to_string())
+        );
+        assert_eq!(session_variable_value(&env, "@limit"), None);
+    }
+
+    #[test]
+    fn test_apply_session_variables() {
+        let mut env = Environment::default();
+        apply_session_variables(&mut env);
+        assert_eq!(env.session.null_text(), "Null");
+        assert_eq!(env.session.time_zone, DateTimeZone::default());
+
+        env.globals
+            .insert("@@null_text".to_string(), Value::Text("-".to_string()));
+        env.globals
+            .insert("@@timezone".to_string(), Value::Text("+02:00".to_string()));
+        apply_session_variables(&mut env);
+        assert_eq!(env.session.null_text(), "-");
+        assert_eq!(env.session.time_zone, parse_time_zone("+02:00").unwrap());
+
+        // Other environments keep their own session options
+        assert_eq!(Environment::default().session.null_text(), "Null");
+    }
+}
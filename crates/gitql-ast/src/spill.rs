@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+/// Default estimated size of the selected rows in memory before they are written to spill files
+pub const DEFAULT_SPILL_THRESHOLD: usize = 1024 * 1024 * 1024;
+
+/// Directory of the spill files, the estimated size of the selected rows in memory before
+/// they are spilled and the maximum size of the rows spilled by one query
+#[derive(Clone)]
+pub struct SpillOptions {
+    pub directory: PathBuf,
+    pub threshold: usize,
+    pub max_size: Option<usize>,
+}
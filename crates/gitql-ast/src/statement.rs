@@ -2,16 +2,19 @@ use std::any::Any;
 use std::collections::HashMap;
 
 use crate::expression::Expression;
+use crate::types::DataType;
 
 pub enum StatementKind {
     Select,
     Where,
     Having,
     Limit,
+    Sample,
     Offset,
     OrderBy,
     GroupBy,
     AggregateFunction,
+    RowNumber,
     GlobalVariable,
 }
 
@@ -23,13 +26,27 @@ pub trait Statement {
 pub enum Query {
     Select(GQLQuery),
     GlobalVariableDeclaration(GlobalVariableStatement),
+    /// `SHOW VARIABLES` to list the defined global variables with their types and values
+    ShowVariables,
+    /// `EXPLAIN SELECT ...` to show the estimated number of rows the query scans without evaluating it
+    Explain(GQLQuery),
+    /// `CREATE TEMP TABLE name AS SELECT ...` to store the query result for the next queries
+    CreateTempTable(CreateTempTableStatement),
+    /// `INSERT INTO name SELECT ...` or `INSERT INTO FILE "path" SELECT ...` to append the query result
+    Insert(InsertStatement),
 }
 
 pub struct GQLQuery {
     pub statements: HashMap<&'static str, Box<dyn Statement>>,
     pub has_aggregation_function: bool,
     pub has_group_by_statement: bool,
+    /// True if the query calls functions like `NOW()` that return different values in each evaluation
+    pub has_non_deterministic_function: bool,
     pub hidden_selections: Vec<String>,
+    /// Query selected in `FROM (SELECT ...) AS name`, it's evaluated first as the rows source
+    pub subquery: Option<Box<GQLQuery>>,
+    /// Queries used as values in `IN (SELECT ...)`, they are evaluated first into sets of values
+    pub in_subqueries: Vec<GQLQuery>,
 }
 
 pub struct SelectStatement {
@@ -38,6 +55,14 @@ pub struct SelectStatement {
     pub fields_values: Vec<Box<dyn Expression>>,
     pub alias_table: HashMap<String, String>,
     pub is_distinct: bool,
+    /// Inline table selected from `VALUES` instead of repository table
+    pub values_table: Option<ValuesTable>,
+}
+
+/// Derived table from `FROM (VALUES (...), ...) AS name(columns)`, each row has value for each column
+pub struct ValuesTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Box<dyn Expression>>>,
 }
 
 impl Statement for SelectStatement {
@@ -52,6 +77,8 @@ impl Statement for SelectStatement {
 
 pub struct WhereStatement {
     pub condition: Box<dyn Expression>,
+    /// Condition start and end columns in the query, used to report runtime errors
+    pub condition_location: Option<(usize, usize)>,
 }
 
 impl Statement for WhereStatement {
@@ -66,6 +93,8 @@ impl Statement for WhereStatement {
 
 pub struct HavingStatement {
     pub condition: Box<dyn Expression>,
+    /// Condition start and end columns in the query, used to report runtime errors
+    pub condition_location: Option<(usize, usize)>,
 }
 
 impl Statement for HavingStatement {
@@ -78,8 +107,13 @@ impl Statement for HavingStatement {
     }
 }
 
+pub enum LimitCount {
+    Absolute(usize),
+    Ratio(f64),
+}
+
 pub struct LimitStatement {
-    pub count: usize,
+    pub count: LimitCount,
 }
 
 impl Statement for LimitStatement {
@@ -92,6 +126,22 @@ impl Statement for LimitStatement {
     }
 }
 
+/// Random subset of the rows selected by `SAMPLE 1000` or `SAMPLE 10 PERCENT`, the rows keep their order
+/// and the same `@@seed` selects the same rows
+pub struct SampleStatement {
+    pub count: LimitCount,
+}
+
+impl Statement for SampleStatement {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::Sample
+    }
+}
+
 pub struct OffsetStatement {
     pub count: usize,
 }
@@ -106,7 +156,7 @@ impl Statement for OffsetStatement {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum SortingOrder {
     Ascending,
     Descending,
@@ -128,7 +178,8 @@ impl Statement for OrderByStatement {
 }
 
 pub struct GroupByStatement {
-    pub field_name: String,
+    pub field_names: Vec<String>,
+    pub is_rollup: bool,
 }
 
 impl Statement for GroupByStatement {
@@ -141,9 +192,32 @@ impl Statement for GroupByStatement {
     }
 }
 
+/// Aggregation function call with its optional argument modifiers, for example
+/// `GROUP_CONCAT(DISTINCT name ORDER BY name DESC SEPARATOR ', ')`
+pub struct AggregateCall {
+    pub function_name: String,
+    pub argument: String,
+    pub is_distinct: bool,
+    pub order_by: Option<(String, SortingOrder)>,
+    pub separator: Option<String>,
+}
+
+impl AggregateCall {
+    /// Create aggregation call on the argument without any modifiers
+    pub fn new(function_name: String, argument: String) -> Self {
+        AggregateCall {
+            function_name,
+            argument,
+            is_distinct: false,
+            order_by: None,
+            separator: None,
+        }
+    }
+}
+
 pub enum AggregateValue {
     Expression(Box<dyn Expression>),
-    Function(String, String),
+    Function(AggregateCall),
 }
 
 pub struct AggregationsStatement {
@@ -160,6 +234,21 @@ impl Statement for AggregationsStatement {
     }
 }
 
+/// Columns selected by `ROW_NUMBER()`, they are numbered from 1 after `ORDER BY` and before `OFFSET` and `LIMIT`
+pub struct RowNumberStatement {
+    pub titles: Vec<String>,
+}
+
+impl Statement for RowNumberStatement {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::RowNumber
+    }
+}
+
 pub struct GlobalVariableStatement {
     pub name: String,
     pub value: Box<dyn Expression>,
@@ -175,8 +264,58 @@ impl Statement for GlobalVariableStatement {
     }
 }
 
+/// Temporary table materialized from the result of the query, its columns are the query titles
+pub struct CreateTempTableStatement {
+    pub table_name: String,
+    pub columns: Vec<(String, DataType)>,
+    pub query: GQLQuery,
+}
+
+/// Destination of the rows appended by `INSERT INTO`
+pub enum InsertTarget {
+    TempTable(String),
+    File(String, FileFormat),
+}
+
+/// Format of the file selected by its extension in `INSERT INTO FILE "path"`
+#[derive(Clone, Copy, PartialEq)]
+pub enum FileFormat {
+    CSV,
+    TSV,
+    JSONLines,
+}
+
+impl FileFormat {
+    /// Resolve the file format from the extension of the path, for example `out.csv`
+    pub fn from_path(path: &str) -> Option<FileFormat> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "csv" => Some(FileFormat::CSV),
+            "tsv" => Some(FileFormat::TSV),
+            "jsonl" | "ndjson" => Some(FileFormat::JSONLines),
+            _ => None,
+        }
+    }
+}
+
+/// Rows selected by the query are appended to the target, the query columns must match the temporary table
+pub struct InsertStatement {
+    pub target: InsertTarget,
+    pub query: GQLQuery,
+}
+
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn test_file_format_from_path() {
+        use super::FileFormat;
+        assert!(FileFormat::from_path("out.csv") == Some(FileFormat::CSV));
+        assert!(FileFormat::from_path("data/out.TSV") == Some(FileFormat::TSV));
+        assert!(FileFormat::from_path("out.ndjson") == Some(FileFormat::JSONLines));
+        assert!(FileFormat::from_path("out.json").is_none());
+        assert!(FileFormat::from_path("out").is_none());
+    }
+
     #[test]
     fn test_selectstatement_kind() {
         assert!(true);
@@ -8,17 +8,50 @@ lazy_static! {
         map.insert("commit_id", DataType::Text);
         map.insert("title", DataType::Text);
         map.insert("message", DataType::Text);
+        map.insert("summary", DataType::Text);
+        map.insert("body", DataType::Text);
+        map.insert("trailers", DataType::Text);
         map.insert("name", DataType::Text);
         map.insert("full_name", DataType::Text);
+        map.insert("short_name", DataType::Text);
+        map.insert("target", DataType::Text);
         map.insert("insertions", DataType::Integer);
         map.insert("deletions", DataType::Integer);
         map.insert("files_changed", DataType::Integer);
+        map.insert("path", DataType::Text);
+        map.insert("status", DataType::Text);
+        map.insert("renamed_from", DataType::Text);
+        map.insert("extension", DataType::Text);
+        map.insert("size", DataType::Integer);
+        map.insert("is_binary", DataType::Boolean);
+        map.insert("last_modified_commit", DataType::Text);
+        map.insert("lfs_pointer", DataType::Boolean);
+        map.insert("revision", DataType::Text);
         map.insert("email", DataType::Text);
         map.insert("type", DataType::Text);
         map.insert("datetime", DataType::DateTime);
+        map.insert("is_lossy_decoded", DataType::Boolean);
+        map.insert("is_signed", DataType::Boolean);
+        map.insert("target_commit_id", DataType::Text);
+        map.insert("is_annotated", DataType::Boolean);
+        map.insert("tagger_name", DataType::Text);
+        map.insert("tagger_email", DataType::Text);
+        map.insert("created_datetime", DataType::DateTime);
+        map.insert("signer", DataType::Text);
+        map.insert("signature_status", DataType::Text);
         map.insert("is_head", DataType::Boolean);
         map.insert("is_remote", DataType::Boolean);
         map.insert("commit_count", DataType::Integer);
+        map.insert("upstream", DataType::Text);
+        map.insert("ahead", DataType::Integer);
+        map.insert("behind", DataType::Integer);
+        map.insert("branch", DataType::Text);
+        map.insert("is_main", DataType::Boolean);
+        map.insert("is_locked", DataType::Boolean);
+        map.insert("line_number", DataType::Integer);
+        map.insert("line", DataType::Text);
+        map.insert("author_name", DataType::Text);
+        map.insert("author_email", DataType::Text);
         map.insert("repo", DataType::Text);
         map
     };
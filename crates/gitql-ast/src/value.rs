@@ -34,6 +34,11 @@ impl fmt::Display for Value {
 }
 
 impl Value {
+    /// Return a key that is the same for equal values, used to store values in hash set
+    pub fn hash_key(&self) -> String {
+        format!("{}:{}", self.data_type(), self)
+    }
+
     pub fn equals(&self, other: &Self) -> bool {
         if self.data_type() != other.data_type() {
             return false;
@@ -195,7 +200,15 @@ impl Value {
         }
 
         if self_type.is_int() && other_type.is_int() {
-            return Ok(Value::Integer(self.as_int() / other.as_int()));
+            let lhs = self.as_int();
+            let rhs = other.as_int();
+            return match lhs.checked_div(rhs) {
+                Some(result) => Ok(Value::Integer(result)),
+                None => Err(format!(
+                    "Attempt to compute `{} / {}`, which would overflow",
+                    lhs, rhs
+                )),
+            };
         }
 
         if self_type.is_float() && other_type.is_float() {
@@ -228,7 +241,15 @@ impl Value {
         }
 
         if self_type.is_int() && other_type.is_int() {
-            return Ok(Value::Integer(self.as_int() % other.as_int()));
+            let lhs = self.as_int();
+            let rhs = other.as_int();
+            return match lhs.checked_rem(rhs) {
+                Some(result) => Ok(Value::Integer(result)),
+                None => Err(format!(
+                    "Attempt to compute `{} % {}`, which would overflow",
+                    lhs, rhs
+                )),
+            };
         }
 
         if self_type.is_float() && other_type.is_float() {
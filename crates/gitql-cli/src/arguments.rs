@@ -1,4 +1,21 @@
-#[derive(Debug, PartialEq)]
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::session::NULL_TEXT_VARIABLE;
+
+use crate::config::Config;
+use crate::render::parse_theme;
+use crate::render::Theme;
+
+/// Tables exported by the export mode when `--tables` is not passed
+const EXPORT_DEFAULT_TABLES: [&str; 3] = ["commits", "branches", "tags"];
+
+/// Default address of the HTTP server of the serve mode
+const SERVE_DEFAULT_HOST: &str = "127.0.0.1";
+const SERVE_DEFAULT_PORT: u16 = 8080;
+
+/// Default port of the MySQL protocol server, same as the MySQL server default port
+const MYSQL_DEFAULT_PORT: u16 = 3306;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 /// Represent the different type of available formats
 pub enum OutputFormat {
     /// Render the output as table
@@ -7,6 +24,10 @@ pub enum OutputFormat {
     JSON,
     /// Print the output in csv format
     CSV,
+    /// Print the output in tab separated values format
+    TSV,
+    /// Write the output into table of SQLite database file
+    SQLite,
 }
 
 /// Arguments for GitQL
@@ -14,9 +35,33 @@ pub enum OutputFormat {
 pub struct Arguments {
     pub repos: Vec<String>,
     pub analysis: bool,
+    pub stats: bool,
     pub pagination: bool,
+    pub no_pager: bool,
     pub page_size: usize,
     pub output_format: OutputFormat,
+    pub output_path: Option<String>,
+    pub no_overwrite: bool,
+    pub no_header: bool,
+    pub any_value_mode: bool,
+    pub ansi_mode: bool,
+    pub threads: usize,
+    pub memory_limit: Option<usize>,
+    pub spill_dir: Option<String>,
+    pub spill_threshold: Option<usize>,
+    pub spill_limit: Option<usize>,
+    pub schema_path: Option<String>,
+    pub export_tables: Vec<String>,
+    pub serve_host: String,
+    pub serve_port: u16,
+    pub serve_token: Option<String>,
+    pub script_arguments: Vec<(String, String)>,
+    pub external_tables: Vec<(String, String)>,
+    pub no_color: bool,
+    pub session_variables: Vec<(String, String)>,
+    pub theme: Theme,
+    pub max_column_width: Option<usize>,
+    pub vertical: bool,
 }
 
 /// Create a new instance of Arguments with the default settings
@@ -25,9 +70,33 @@ impl Arguments {
         Arguments {
             repos: vec![],
             analysis: false,
+            stats: false,
             pagination: false,
+            no_pager: false,
             page_size: 10,
             output_format: OutputFormat::Render,
+            output_path: None,
+            no_overwrite: false,
+            no_header: false,
+            any_value_mode: false,
+            ansi_mode: false,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            memory_limit: None,
+            spill_dir: None,
+            spill_threshold: None,
+            spill_limit: None,
+            schema_path: None,
+            export_tables: vec![],
+            serve_host: SERVE_DEFAULT_HOST.to_string(),
+            serve_port: SERVE_DEFAULT_PORT,
+            serve_token: None,
+            script_arguments: vec![],
+            external_tables: vec![],
+            no_color: false,
+            session_variables: vec![],
+            theme: Theme::default(),
+            max_column_width: None,
+            vertical: false,
         }
     }
 }
@@ -36,12 +105,25 @@ impl Arguments {
 pub enum Command {
     ReplMode(Arguments),
     QueryMode(String, Arguments),
+    ScriptMode(String, Arguments),
+    TuiMode(Arguments),
+    KernelMode(String, Arguments),
+    ValidateMode(String, Arguments),
+    ExportMode(String, Arguments),
+    ServeMode(Arguments),
+    MySQLMode(Arguments),
     Help,
     Version,
+    SelfUpdate,
     Error(String),
 }
 
-pub fn parse_arguments(args: &Vec<String>) -> Command {
+pub fn parse_arguments(args: &[String]) -> Command {
+    parse_arguments_with_config(args, Config::default())
+}
+
+/// Parse the command line arguments, the options that are not passed are taken from the config
+pub fn parse_arguments_with_config(args: &[String], config: Config) -> Command {
     let args_len = args.len();
 
     if args.iter().any(|i| i == "--help" || i == "-h") {
@@ -52,10 +134,81 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
         return Command::Version;
     }
 
+    if args.iter().any(|i| i == "--self-update") {
+        return Command::SelfUpdate;
+    }
+
     let mut optional_query: Option<String> = None;
+    let mut optional_script: Option<String> = None;
     let mut arguments = Arguments::new();
+    arguments.output_format = config.output_format.unwrap_or(arguments.output_format);
+    arguments.no_pager = config.pager == Some(false);
+    arguments.no_color = config.color == Some(false);
+    arguments.session_variables = config.session_variables;
+    arguments.theme = config.theme.unwrap_or_default();
+    arguments.max_column_width = config.max_column_width;
+
+    // Terminal UI mode is a sub command, for example `gitql tui -r <repo>`
+    let is_tui_mode = args.get(1).is_some_and(|arg| arg == "tui");
 
-    let mut arg_index = 1;
+    // Jupyter kernel mode is a sub command with the connection file, for example `gitql kernel <file> -r <repo>`
+    let is_kernel_mode = args.get(1).is_some_and(|arg| arg == "kernel");
+    let mut connection_file: Option<String> = None;
+    if is_kernel_mode {
+        match args.get(2) {
+            Some(path) if !path.starts_with('-') => connection_file = Some(path.to_string()),
+            _ => {
+                return Command::Error(
+                    "Kernel mode must be followed by connection file".to_string(),
+                )
+            }
+        }
+    }
+
+    // Validation mode is a sub command with the queries path, for example `gitql validate queries/`
+    let is_validate_mode = args.get(1).is_some_and(|arg| arg == "validate");
+    let mut queries_path: Option<String> = None;
+    if is_validate_mode {
+        match args.get(2) {
+            Some(path) if !path.starts_with('-') => queries_path = Some(path.to_string()),
+            _ => {
+                return Command::Error(
+                    "Validate mode must be followed by queries file or directory".to_string(),
+                )
+            }
+        }
+    }
+
+    // Export mode is a sub command with the database path, for example `gitql export repo.db`
+    let is_export_mode = args.get(1).is_some_and(|arg| arg == "export");
+    let mut database_path: Option<String> = None;
+    if is_export_mode {
+        match args.get(2) {
+            Some(path) if !path.starts_with('-') => database_path = Some(path.to_string()),
+            _ => {
+                return Command::Error(
+                    "Export mode must be followed by SQLite database file".to_string(),
+                )
+            }
+        }
+    }
+
+    // Serve mode is a sub command, for example `gitql serve -r <repo> --port 8080`
+    let is_serve_mode = args.get(1).is_some_and(|arg| arg == "serve");
+
+    // MySQL mode is a sub command that serves the MySQL protocol, for example `gitql mysql -r <repo> --port 3306`
+    let is_mysql_mode = args.get(1).is_some_and(|arg| arg == "mysql");
+    if is_mysql_mode {
+        arguments.serve_port = MYSQL_DEFAULT_PORT;
+    }
+
+    let mut arg_index = if is_tui_mode || is_serve_mode || is_mysql_mode {
+        2
+    } else if is_kernel_mode || is_validate_mode || is_export_mode {
+        3
+    } else {
+        1
+    };
     loop {
         if arg_index >= args_len {
             break;
@@ -68,7 +221,7 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
         }
 
         match arg.as_ref() {
-            "--repos" | "-r" => {
+            "--repos" | "--repo" | "-r" => {
                 arg_index += 1;
                 if arg_index >= args_len {
                     let message = format!("Argument {} must be followed by one or more path", arg);
@@ -100,14 +253,139 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
                 optional_query = Some(args[arg_index].to_string());
                 arg_index += 1;
             }
+            "--file" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by script file path", arg);
+                    return Command::Error(message);
+                }
+
+                optional_script = Some(args[arg_index].to_string());
+                arg_index += 1;
+            }
+            "--arg" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by name=value", arg);
+                    return Command::Error(message);
+                }
+
+                match args[arg_index].split_once('=') {
+                    Some((name, value)) if !name.is_empty() => arguments
+                        .script_arguments
+                        .push((name.to_string(), value.to_string())),
+                    _ => {
+                        let message = format!("Invalid script argument {}", args[arg_index]);
+                        return Command::Error(message);
+                    }
+                }
+
+                arg_index += 1;
+            }
+            "--table" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by name=path", arg);
+                    return Command::Error(message);
+                }
+
+                match args[arg_index].split_once('=') {
+                    Some((name, path)) if !name.is_empty() && !path.is_empty() => arguments
+                        .external_tables
+                        .push((name.to_lowercase(), path.to_string())),
+                    _ => {
+                        let message = format!("Invalid external table {}", args[arg_index]);
+                        return Command::Error(message);
+                    }
+                }
+
+                arg_index += 1;
+            }
             "--analysis" | "-a" => {
                 arguments.analysis = true;
                 arg_index += 1;
             }
+            "--stats" => {
+                arguments.stats = true;
+                arg_index += 1;
+            }
+            "--ansi" => {
+                arguments.ansi_mode = true;
+                arg_index += 1;
+            }
+            "--no-header" => {
+                arguments.no_header = true;
+                arg_index += 1;
+            }
+            "--any-value" => {
+                arguments.any_value_mode = true;
+                arg_index += 1;
+            }
             "--pagination" | "-p" => {
                 arguments.pagination = true;
                 arg_index += 1;
             }
+            "--no-pager" => {
+                arguments.no_pager = true;
+                arg_index += 1;
+            }
+            "--theme" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by theme name", arg);
+                    return Command::Error(message);
+                }
+
+                match parse_theme(&args[arg_index]) {
+                    Some(theme) => arguments.theme = theme,
+                    None => return Command::Error("Invalid theme name".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--max-column-width" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by the width", arg);
+                    return Command::Error(message);
+                }
+
+                match args[arg_index].parse::<usize>() {
+                    Ok(width) if width > 0 => arguments.max_column_width = Some(width),
+                    _ => return Command::Error("Invalid max column width".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--null-text" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by the text", arg);
+                    return Command::Error(message);
+                }
+
+                let null_text = args[arg_index].to_string();
+                arguments
+                    .session_variables
+                    .retain(|(name, _)| name != NULL_TEXT_VARIABLE);
+                arguments
+                    .session_variables
+                    .push((NULL_TEXT_VARIABLE.to_string(), null_text));
+
+                arg_index += 1;
+            }
+            "--vertical" | "-E" => {
+                arguments.vertical = true;
+                arg_index += 1;
+            }
+            "--no-color" => {
+                arguments.no_color = true;
+                arg_index += 1;
+            }
+            "--no-config" => {
+                // The config is loaded before parsing the arguments
+                arg_index += 1;
+            }
             "--pagesize" | "-ps" => {
                 arg_index += 1;
                 if arg_index >= args_len {
@@ -124,31 +402,183 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
                 arguments.page_size = page_size;
                 arg_index += 1;
             }
-            "--output" | "-o" => {
+            "--threads" | "-t" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by number of threads", arg);
+                    return Command::Error(message);
+                }
+
+                let threads_result = args[arg_index].parse::<usize>();
+                if threads_result.is_err() || threads_result == Ok(0) {
+                    return Command::Error("Invalid number of threads".to_string());
+                }
+
+                arguments.threads = threads_result.ok().unwrap();
+                arg_index += 1;
+            }
+            "--memory-limit" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by memory size", arg);
+                    return Command::Error(message);
+                }
+
+                match parse_memory_size(&args[arg_index]) {
+                    Some(size) if size > 0 => arguments.memory_limit = Some(size),
+                    _ => return Command::Error("Invalid memory limit".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--spill-dir" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by directory path", arg);
+                    return Command::Error(message);
+                }
+
+                arguments.spill_dir = Some(args[arg_index].to_string());
+                arg_index += 1;
+            }
+            "--spill-threshold" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by memory size", arg);
+                    return Command::Error(message);
+                }
+
+                match parse_memory_size(&args[arg_index]) {
+                    Some(size) if size > 0 => arguments.spill_threshold = Some(size),
+                    _ => return Command::Error("Invalid spill threshold".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--spill-limit" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by spill size", arg);
+                    return Command::Error(message);
+                }
+
+                match parse_memory_size(&args[arg_index]) {
+                    Some(size) if size > 0 => arguments.spill_limit = Some(size),
+                    _ => return Command::Error("Invalid spill limit".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--format" | "-f" => {
                 arg_index += 1;
                 if arg_index >= args_len {
                     let message = format!("Argument {} must be followed by output format", arg);
                     return Command::Error(message);
                 }
 
-                let output_type = &args[arg_index].to_lowercase();
-                if output_type == "csv" {
-                    arguments.output_format = OutputFormat::CSV;
-                } else if output_type == "json" {
-                    arguments.output_format = OutputFormat::JSON;
-                } else if output_type == "render" {
-                    arguments.output_format = OutputFormat::Render;
+                match parse_output_format(&args[arg_index]) {
+                    Some(output_format) => arguments.output_format = output_format,
+                    None => return Command::Error("Invalid output format".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--output" | "-o" => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by output file path", arg);
+                    return Command::Error(message);
+                }
+
+                // Keep `--output <format>` working for the scripts that used it before
+                // the format had its own option
+                let output = &args[arg_index];
+                if let Some(output_format) = parse_output_format(output) {
+                    arguments.output_format = output_format;
                 } else {
-                    return Command::Error("Invalid output format".to_string());
+                    arguments.output_path = Some(output.to_string());
+                }
+
+                arg_index += 1;
+            }
+            "--no-overwrite" => {
+                arguments.no_overwrite = true;
+                arg_index += 1;
+            }
+            "--schema" if is_validate_mode => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by schema file path", arg);
+                    return Command::Error(message);
                 }
 
+                arguments.schema_path = Some(args[arg_index].to_string());
+                arg_index += 1;
+            }
+            "--tables" if is_export_mode => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by tables names", arg);
+                    return Command::Error(message);
+                }
+
+                arguments.export_tables = args[arg_index]
+                    .split(',')
+                    .map(|table| table.trim().to_lowercase())
+                    .filter(|table| !table.is_empty())
+                    .collect();
+                arg_index += 1;
+            }
+            "--host" if is_serve_mode || is_mysql_mode => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by host address", arg);
+                    return Command::Error(message);
+                }
+
+                arguments.serve_host = args[arg_index].to_string();
+                arg_index += 1;
+            }
+            "--port" if is_serve_mode || is_mysql_mode => {
+                arg_index += 1;
+                if arg_index >= args_len {
+                    let message = format!("Argument {} must be followed by port number", arg);
+                    return Command::Error(message);
+                }
+
+                match args[arg_index].parse::<u16>() {
+                    Ok(port) if port > 0 => arguments.serve_port = port,
+                    _ => return Command::Error("Invalid port number".to_string()),
+                }
+
+                arg_index += 1;
+            }
+            "--token" if is_serve_mode || is_mysql_mode => {
+                arg_index += 1;
+                if arg_index >= args_len || args[arg_index].is_empty() {
+                    let message = format!("Argument {} must be followed by the token", arg);
+                    return Command::Error(message);
+                }
+
+                arguments.serve_token = Some(args[arg_index].to_string());
                 arg_index += 1;
             }
             _ => return Command::Error(format!("Unknown command {}", arg)),
         }
     }
 
-    // Add the current directory if no repository is passed
+    // Add the repository from `GIT_DIR` like git does, or the current directory if no repository is passed
+    if arguments.repos.is_empty() {
+        if let Some(git_dir) = std::env::var_os("GIT_DIR").filter(|dir| !dir.is_empty()) {
+            arguments.repos.push(git_dir.to_string_lossy().to_string());
+        }
+    }
+
+    // Default repositories of the config are used instead of the current directory
+    if arguments.repos.is_empty() {
+        arguments.repos = config.repos;
+    }
+
     if arguments.repos.is_empty() {
         let current_dir = std::env::current_dir();
         if current_dir.is_ok() {
@@ -166,6 +596,83 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
         }
     }
 
+    if !arguments.script_arguments.is_empty() && optional_script.is_none() {
+        return Command::Error("Argument --arg can be used only with --file".to_string());
+    }
+
+    if let Some(script_path) = optional_script {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed with the script file".to_string());
+        }
+
+        if is_tui_mode
+            || connection_file.is_some()
+            || queries_path.is_some()
+            || database_path.is_some()
+            || is_serve_mode
+            || is_mysql_mode
+        {
+            return Command::Error("Script file can't be passed to this mode".to_string());
+        }
+
+        return Command::ScriptMode(script_path, arguments);
+    }
+
+    if let Some(connection_file) = connection_file {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the kernel mode".to_string());
+        }
+        return Command::KernelMode(connection_file, arguments);
+    }
+
+    if let Some(database_path) = database_path {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the export mode".to_string());
+        }
+
+        if arguments.export_tables.is_empty() {
+            arguments.export_tables = EXPORT_DEFAULT_TABLES
+                .iter()
+                .map(|table| table.to_string())
+                .collect();
+        }
+
+        for table in &arguments.export_tables {
+            if !TABLES_FIELDS_NAMES.contains_key(table.as_str()) {
+                return Command::Error(format!("Unknown table `{}` can't be exported", table));
+            }
+        }
+        return Command::ExportMode(database_path, arguments);
+    }
+
+    if let Some(queries_path) = queries_path {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the validate mode".to_string());
+        }
+        return Command::ValidateMode(queries_path, arguments);
+    }
+
+    if is_serve_mode {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the serve mode".to_string());
+        }
+        return Command::ServeMode(arguments);
+    }
+
+    if is_mysql_mode {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the MySQL mode".to_string());
+        }
+        return Command::MySQLMode(arguments);
+    }
+
+    if is_tui_mode {
+        if optional_query.is_some() {
+            return Command::Error("Query can't be passed to the terminal UI mode".to_string());
+        }
+        return Command::TuiMode(arguments);
+    }
+
     if let Some(query) = optional_query {
         Command::QueryMode(query, arguments)
     } else {
@@ -173,20 +680,90 @@ pub fn parse_arguments(args: &Vec<String>) -> Command {
     }
 }
 
+/// Resolve the output format from its case-insensitive name
+pub fn parse_output_format(name: &str) -> Option<OutputFormat> {
+    match name.to_lowercase().as_str() {
+        "render" => Some(OutputFormat::Render),
+        "json" => Some(OutputFormat::JSON),
+        "csv" => Some(OutputFormat::CSV),
+        "tsv" => Some(OutputFormat::TSV),
+        "sqlite" => Some(OutputFormat::SQLite),
+        _ => None,
+    }
+}
+
+/// Parse memory size in bytes or with `KB`, `MB` or `GB` unit, for example `512MB`
+fn parse_memory_size(size: &str) -> Option<usize> {
+    let size = size.trim().to_uppercase();
+    let digits_end = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(digits_end);
+    let number = number.parse::<usize>().ok()?;
+    let multiplier = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" => 1024,
+        "M" | "MB" => 1024 * 1024,
+        "G" | "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
+
 pub fn print_help_list() {
     println!("GitQL is a SQL like query language to run on local repositories");
     println!();
     println!("Usage: gitql [OPTIONS]");
+    println!("       gitql tui [OPTIONS]    Start the interactive terminal UI");
+    println!("       gitql kernel <CONNECTION_FILE> [OPTIONS]    Run as Jupyter kernel");
+    println!("       gitql validate <PATH> [--schema <FILE>]    Type check saved queries files");
+    println!(
+        "       gitql export <DATABASE> [--tables <TABLES>]    Export tables into SQLite database"
+    );
+    println!("       gitql serve [--host <HOST>] [--port <PORT>] [--token <TOKEN>]    Serve queries over HTTP JSON API");
+    println!("       gitql mysql [--host <HOST>] [--port <PORT>] [--token <PASSWORD>]    Serve queries over MySQL protocol");
     println!();
     println!("Options:");
     println!("-r,  --repos <REPOS>        Path for local repositories to run query on");
     println!("-q,  --query <GQL Query>    GitQL query to run on selected repositories");
+    println!("     --file <PATH>          Run the `;` separated statements of the script file");
+    println!("     --arg <NAME=VALUE>     Pass script argument as global variable, `NAME:TYPE=VALUE` to set type");
+    println!(
+        "     --table <NAME=PATH>    Load CSV, TSV or JSON file as read-only table with the name"
+    );
     println!("-p,  --pagination           Enable print result with pagination");
     println!("-ps, --pagesize             Set pagination page size [default: 10]");
-    println!("-o,  --output               Set output format [render, json, csv]");
+    println!("     --no-pager             Don't pipe large results to the pager");
+    println!("-E,  --vertical             Print each row as `field: value` lines like ending the query with \\G");
+    println!(
+        "     --theme <NAME>         Set the colors theme of the tables [default, zebra, plain]"
+    );
+    println!("     --max-column-width <N> Truncate the values longer than the width in the tables");
+    println!(
+        "     --null-text <TEXT>     Set the text of NULL values in the tables [default: Null]"
+    );
+    println!("     --no-color             Don't use colors in the results and diagnostics");
+    println!(
+        "     --no-config            Ignore the config file and the GITQL_* environment variables"
+    );
+    println!("-f,  --format               Set output format [render, json, csv, tsv, sqlite]");
+    println!("-o,  --output <PATH>        Write the query result to a file instead of stdout");
+    println!("     --no-overwrite         Don't replace the output file if it already exists");
+    println!("     --no-header            Don't print the header row in csv and tsv formats");
+    println!("-t,  --threads              Set number of threads to collect data from repositories");
+    println!("     --memory-limit <SIZE>  Abort queries that select rows larger than the size, for example 512MB");
+    println!("     --spill-dir <PATH>     Write the selected rows over the spill threshold to files in the directory");
+    println!("     --spill-threshold <SIZE>  Set memory size of the selected rows before they are spilled [default: 1GB]");
+    println!("     --spill-limit <SIZE>   Abort queries that spill rows larger than the size, for example 10GB");
     println!("-a,  --analysis             Print Query analysis");
+    println!(
+        "     --stats                Print rows counts and timing of each query stage to stderr"
+    );
+    println!("     --any-value            Allow selecting fields that are not used in GROUP BY");
+    println!("     --ansi                 Enable ANSI SQL compatibility mode");
     println!("-h,  --help                 Print GitQL help");
     println!("-v,  --version              Print GitQL Current Version");
+    println!("     --self-update          Download and install the latest GitQL release");
 }
 
 #[cfg(test)]
@@ -218,6 +795,235 @@ mod tests {
         assert!(matches!(command, Command::QueryMode { .. }));
     }
 
+    #[test]
+    fn test_export_arguments() {
+        let arguments: Vec<String> = ["gitql", "export", "repo.db", "--tables", "Commits, tags"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        if let Command::ExportMode(path, arguments) = parse_arguments(&arguments) {
+            assert_eq!(path, "repo.db");
+            assert_eq!(arguments.export_tables, vec!["commits", "tags"]);
+        } else {
+            assert!(false);
+        }
+
+        let arguments: Vec<String> = ["gitql", "export", "--tables", "commits"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
+    #[test]
+    fn test_serve_arguments() {
+        let arguments: Vec<String> = [
+            "gitql", "serve", "--repo", ".", "--port", "9000", "--token", "secret",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        if let Command::ServeMode(arguments) = parse_arguments(&arguments) {
+            assert_eq!(arguments.repos, vec!["."]);
+            assert_eq!(arguments.serve_host, "127.0.0.1");
+            assert_eq!(arguments.serve_port, 9000);
+            assert_eq!(arguments.serve_token, Some("secret".to_string()));
+        } else {
+            assert!(false);
+        }
+
+        let arguments: Vec<String> = ["gitql", "--port", "9000"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
+    #[test]
+    fn test_mysql_arguments() {
+        let arguments: Vec<String> = ["gitql", "mysql", "-r", "."]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        if let Command::MySQLMode(arguments) = parse_arguments(&arguments) {
+            assert_eq!(arguments.serve_port, 3306);
+            assert_eq!(arguments.serve_token, None);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("1024"), Some(1024));
+        assert_eq!(parse_memory_size("2kb"), Some(2048));
+        assert_eq!(parse_memory_size("512MB"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_memory_size("1 G"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_memory_size("MB"), None);
+        assert_eq!(parse_memory_size("10TB"), None);
+    }
+
+    #[test]
+    fn test_script_arguments() {
+        let arguments: Vec<String> = ["gitql", "--file", "report.gql", "--arg", "author=Jane"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments(&arguments);
+        if let Command::ScriptMode(path, arguments) = command {
+            assert_eq!(path, "report.gql");
+            assert_eq!(
+                arguments.script_arguments,
+                vec![("author".to_string(), "Jane".to_string())]
+            );
+        } else {
+            assert!(false);
+        }
+
+        let arguments: Vec<String> = ["gitql", "--arg", "author=Jane"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+
+        let arguments: Vec<String> = ["gitql", "--file", "report.gql", "--arg", "=Jane"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
+    #[test]
+    fn test_arguments_with_config() {
+        let config = || Config {
+            repos: vec!["/work/gql".to_string()],
+            output_format: Some(OutputFormat::JSON),
+            pager: Some(false),
+            color: Some(false),
+            session_variables: vec![("@@timezone".to_string(), "+02:00".to_string())],
+            ..Default::default()
+        };
+
+        let arguments: Vec<String> = ["gitql", "--no-config"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments_with_config(&arguments, config());
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.output_format, OutputFormat::JSON);
+            assert!(arguments.no_pager);
+            assert!(arguments.no_color);
+            assert_eq!(arguments.session_variables.len(), 1);
+        } else {
+            assert!(false);
+        }
+
+        // Command line arguments override the config options
+        let arguments: Vec<String> = ["gitql", "-r", "/work/docs", "-f", "csv"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments_with_config(&arguments, config());
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.repos, vec!["/work/docs"]);
+            assert_eq!(arguments.output_format, OutputFormat::CSV);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_render_arguments() {
+        let arguments: Vec<String> = ["gitql", "--theme", "zebra", "--max-column-width", "30"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.theme, parse_theme("zebra").unwrap());
+            assert_eq!(arguments.max_column_width, Some(30));
+        } else {
+            assert!(false);
+        }
+
+        let arguments: Vec<String> = ["gitql", "--theme", "neon"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+
+        let arguments: Vec<String> = ["gitql", "--max-column-width", "0"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
+    #[test]
+    fn test_arguments_with_null_text() {
+        let config = Config {
+            session_variables: vec![("@@null_text".to_string(), "-".to_string())],
+            ..Default::default()
+        };
+
+        let arguments: Vec<String> = ["gitql", "--null-text", "NULL"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments_with_config(&arguments, config);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(
+                arguments.session_variables,
+                vec![("@@null_text".to_string(), "NULL".to_string())]
+            );
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec!["gitql".to_string(), "--null-text".to_string()];
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
+    #[test]
+    fn test_arguments_with_vertical() {
+        let arguments = vec!["gitql".to_string(), "-E".to_string()];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert!(arguments.vertical);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_external_tables_arguments() {
+        let arguments: Vec<String> = ["gitql", "--table", "Authors=team/authors.csv"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(
+                arguments.external_tables,
+                vec![("authors".to_string(), "team/authors.csv".to_string())]
+            );
+        } else {
+            assert!(false);
+        }
+
+        let arguments: Vec<String> = ["gitql", "--table", "authors"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+
+        let arguments: Vec<String> = ["gitql", "--table"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert!(matches!(parse_arguments(&arguments), Command::Error(_)));
+    }
+
     #[test]
     fn test_arguments_with_help() {
         let arguments = vec![
@@ -240,6 +1046,24 @@ mod tests {
         assert_eq!(command, Command::Version);
     }
 
+    #[test]
+    fn test_arguments_with_self_update() {
+        let arguments = vec!["gitql".to_string(), "--self-update".to_string()];
+        let command = parse_arguments(&arguments);
+        assert_eq!(command, Command::SelfUpdate);
+    }
+
+    #[test]
+    fn test_arguments_with_any_value_mode() {
+        let arguments = vec!["gitql".to_string(), "--any-value".to_string()];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert!(arguments.any_value_mode);
+        } else {
+            assert!(false);
+        }
+    }
+
     #[test]
     fn test_arguments_with_valid_page_size() {
         let arguments = vec![
@@ -262,6 +1086,29 @@ mod tests {
         assert!(matches!(command, Command::Error { .. }));
     }
 
+    #[test]
+    fn test_arguments_with_threads() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "--threads".to_string(),
+            "4".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.threads, 4);
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec![
+            "gitql".to_string(),
+            "--threads".to_string(),
+            "0".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
+
     #[test]
     fn test_arguments_with_valid_output_format() {
         let arguments = vec![
@@ -273,14 +1120,182 @@ mod tests {
         assert!(!matches!(command, Command::Error { .. }));
     }
 
+    #[test]
+    fn test_arguments_with_format_alias() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "--format".to_string(),
+            "json".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert!(matches!(arguments.output_format, OutputFormat::JSON));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_arguments_with_tsv_format_without_header() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "--format".to_string(),
+            "tsv".to_string(),
+            "--no-header".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.output_format, OutputFormat::TSV);
+            assert!(arguments.no_header);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_tui_mode_arguments() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "tui".to_string(),
+            "--repos".to_string(),
+            ".".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::TuiMode { .. }));
+
+        let arguments = vec![
+            "gitql".to_string(),
+            "tui".to_string(),
+            "-q".to_string(),
+            "SELECT 1".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
+
+    #[test]
+    fn test_kernel_mode_arguments() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "kernel".to_string(),
+            "connection.json".to_string(),
+            "--repos".to_string(),
+            ".".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::KernelMode(connection_file, _) = command {
+            assert_eq!(connection_file, "connection.json");
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec!["gitql".to_string(), "kernel".to_string()];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
+
+    #[test]
+    fn test_validate_mode_arguments() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "validate".to_string(),
+            "queries".to_string(),
+            "--schema".to_string(),
+            "schema.json".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ValidateMode(path, arguments) = command {
+            assert_eq!(path, "queries");
+            assert_eq!(arguments.schema_path, Some("schema.json".to_string()));
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec!["gitql".to_string(), "validate".to_string()];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+
+        let arguments = vec![
+            "gitql".to_string(),
+            "--schema".to_string(),
+            "schema.json".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
+
     #[test]
     fn test_arguments_with_invalid_output_format() {
         let arguments = vec![
             "gitql".to_string(),
-            "--output".to_string(),
+            "--format".to_string(),
             "text".to_string(),
         ];
         let command = parse_arguments(&arguments);
         assert!(matches!(command, Command::Error { .. }));
     }
+
+    #[test]
+    fn test_arguments_with_spill_dir() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "--spill-dir".to_string(),
+            "/tmp/gitql".to_string(),
+            "--spill-threshold".to_string(),
+            "512MB".to_string(),
+            "--spill-limit".to_string(),
+            "10GB".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.spill_dir, Some("/tmp/gitql".to_string()));
+            assert_eq!(arguments.spill_threshold, Some(512 * 1024 * 1024));
+            assert_eq!(arguments.spill_limit, Some(10 * 1024 * 1024 * 1024));
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec![
+            "gitql".to_string(),
+            "--spill-limit".to_string(),
+            "0".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
+
+    #[test]
+    fn test_arguments_with_no_pager() {
+        let arguments = vec!["gitql".to_string(), "--no-pager".to_string()];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert!(arguments.no_pager);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_arguments_with_output_path() {
+        let arguments = vec![
+            "gitql".to_string(),
+            "--format".to_string(),
+            "csv".to_string(),
+            "--output".to_string(),
+            "result.csv".to_string(),
+            "--no-overwrite".to_string(),
+        ];
+        let command = parse_arguments(&arguments);
+        if let Command::ReplMode(arguments) = command {
+            assert_eq!(arguments.output_format, OutputFormat::CSV);
+            assert_eq!(arguments.output_path, Some("result.csv".to_string()));
+            assert!(arguments.no_overwrite);
+        } else {
+            assert!(false);
+        }
+
+        let arguments = vec!["gitql".to_string(), "--output".to_string()];
+        let command = parse_arguments(&arguments);
+        assert!(matches!(command, Command::Error { .. }));
+    }
 }
@@ -1,5 +1,21 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+/// Colors of the diagnostics and rendered tables, disabled by `--no-color`, the config or `NO_COLOR`
+static COLORS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable the colors of the diagnostics and rendered tables
+pub fn set_colors_enabled(enabled: bool) {
+    COLORS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if the output can be colored
+pub fn is_colors_enabled() -> bool {
+    COLORS_ENABLED.load(Ordering::Relaxed)
+}
+
 pub struct ColoredStream {
     stdout: StandardStream,
 }
@@ -7,7 +23,11 @@ pub struct ColoredStream {
 impl Default for ColoredStream {
     fn default() -> Self {
         Self {
-            stdout: StandardStream::stdout(ColorChoice::Always),
+            stdout: StandardStream::stdout(if is_colors_enabled() {
+                ColorChoice::Always
+            } else {
+                ColorChoice::Never
+            }),
         }
     }
 }
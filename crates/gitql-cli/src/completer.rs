@@ -0,0 +1,195 @@
+use gitql_ast::aggregation::AGGREGATIONS;
+use gitql_ast::environment::Environment;
+use gitql_ast::environment::EXTERNAL_TABLE_PREFIX;
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::function::standard_functions_names;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::tokenizer::tokenize_with_recovery;
+use gitql_parser::tokenizer::TokenKind;
+
+/// Reserved keywords suggested by the completion engine
+const KEYWORDS: [&str; 40] = [
+    "SET",
+    "SHOW",
+    "EXPLAIN",
+    "CREATE",
+    "INSERT",
+    "SELECT",
+    "DISTINCT",
+    "FROM",
+    "GROUP",
+    "WHERE",
+    "HAVING",
+    "LIMIT",
+    "PERCENT",
+    "SAMPLE",
+    "OFFSET",
+    "ORDER",
+    "BY",
+    "ALL",
+    "IN",
+    "IS",
+    "NOT",
+    "LIKE",
+    "GLOB",
+    "CASE",
+    "WHEN",
+    "THEN",
+    "ELSE",
+    "END",
+    "BETWEEN",
+    "SYMMETRIC",
+    "OR",
+    "AND",
+    "XOR",
+    "TRUE",
+    "FALSE",
+    "NULL",
+    "AS",
+    "ASC",
+    "DESC",
+    "VALUES",
+];
+
+/// Completion candidates for the word that ends at the cursor
+pub struct Completion {
+    /// Start position of the completed word in characters
+    pub start: usize,
+    /// Sorted candidates that can replace the completed word
+    pub candidates: Vec<String>,
+}
+
+/// Suggest keywords, table names, fields of the current `FROM` table, functions and global variables
+/// that start with the word before the cursor, the cursor position is in characters
+pub fn complete(input: &str, cursor: usize, env: &Environment) -> Completion {
+    let characters: Vec<char> = input.chars().collect();
+    let cursor = cursor.min(characters.len());
+
+    let mut start = cursor;
+    while start > 0 && is_word_character(characters[start - 1]) {
+        start -= 1;
+    }
+
+    let prefix: String = characters[start..cursor].iter().collect::<String>();
+    let lowercase_prefix = prefix.to_lowercase();
+
+    let mut candidates: Vec<String> = vec![];
+    if prefix.starts_with('@') {
+        candidates.extend(env.globals_types.keys().cloned());
+    } else if is_after_from_keyword(&characters[..start]) {
+        candidates.extend(TABLES_FIELDS_NAMES.keys().map(|name| name.to_string()));
+        candidates.extend(
+            env.tables
+                .keys()
+                .filter(|name| !name.starts_with(EXTERNAL_TABLE_PREFIX))
+                .cloned(),
+        );
+    } else {
+        if let Some(table_name) = current_table_name(input) {
+            candidates.extend(
+                env.table_fields(&table_name)
+                    .into_iter()
+                    .map(|(name, _)| name),
+            );
+        }
+
+        candidates.extend(standard_functions_names().map(|name| name.to_string()));
+        candidates.extend(AGGREGATIONS.keys().map(|name| name.to_string()));
+        candidates.push("row_number".to_string());
+
+        // Keywords follow the case of the word, and they are uppercase by default
+        let is_lowercase = prefix.chars().next().is_some_and(|c| c.is_lowercase());
+        candidates.extend(KEYWORDS.iter().map(|keyword| {
+            if is_lowercase {
+                keyword.to_lowercase()
+            } else {
+                keyword.to_string()
+            }
+        }));
+    }
+
+    candidates.retain(|candidate| candidate.to_lowercase().starts_with(&lowercase_prefix));
+    candidates.sort();
+    candidates.dedup();
+
+    Completion { start, candidates }
+}
+
+/// Returns the longest prefix shared by all the candidates
+pub fn common_prefix(candidates: &[String]) -> String {
+    let mut prefix: Vec<char> = match candidates.first() {
+        Some(first) => first.chars().collect(),
+        None => return String::new(),
+    };
+
+    for candidate in &candidates[1..] {
+        let shared = prefix
+            .iter()
+            .zip(candidate.chars())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        prefix.truncate(shared);
+    }
+
+    prefix.into_iter().collect()
+}
+
+#[inline(always)]
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '@'
+}
+
+/// Check if the last token before the completed word is the `FROM` keyword
+fn is_after_from_keyword(characters: &[char]) -> bool {
+    let input: String = characters.iter().collect();
+    let (tokens, _) = tokenize_with_recovery(&input, ParserOptions::default());
+    tokens
+        .last()
+        .is_some_and(|token| token.kind == TokenKind::From)
+}
+
+/// Resolve the table name after the `FROM` keyword in the input if exists
+fn current_table_name(input: &str) -> Option<String> {
+    let (tokens, _) = tokenize_with_recovery(input, ParserOptions::default());
+    tokens
+        .windows(2)
+        .find(|pair| pair[0].kind == TokenKind::From && pair[1].kind == TokenKind::Symbol)
+        .map(|pair| pair[1].identifier().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_keywords_and_functions() {
+        let env = Environment::default();
+
+        let completion = complete("SEL", 3, &env);
+        assert_eq!(completion.start, 0);
+        assert_eq!(completion.candidates, vec!["SELECT"]);
+
+        let completion = complete("select lo", 9, &env);
+        assert_eq!(completion.start, 7);
+        assert!(completion.candidates.contains(&"lower".to_string()));
+    }
+
+    #[test]
+    fn test_complete_tables_and_fields() {
+        let env = Environment::default();
+
+        let completion = complete("SELECT * FROM com", 17, &env);
+        assert_eq!(completion.candidates, vec!["commits"]);
+
+        let completion = complete("SELECT tit FROM commits", 10, &env);
+        assert_eq!(completion.start, 7);
+        assert_eq!(completion.candidates, vec!["title"]);
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let candidates = vec!["commit_id".to_string(), "commit_count".to_string()];
+        assert_eq!(common_prefix(&candidates), "commit_");
+        assert_eq!(common_prefix(&[]), "");
+    }
+}
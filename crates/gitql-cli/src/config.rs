@@ -0,0 +1,263 @@
+use std::path::PathBuf;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::session::apply_session_variables;
+use gitql_ast::session::check_session_variable_value;
+use gitql_ast::session::is_session_variable;
+use gitql_ast::session::typed_session_variable_value;
+use gitql_ast::session::SESSION_VARIABLES;
+
+use crate::arguments::parse_output_format;
+use crate::arguments::OutputFormat;
+use crate::render::parse_theme;
+use crate::render::Theme;
+
+/// Name of the config file in the user home directory
+const CONFIG_FILE_NAME: &str = ".gitqlrc";
+
+/// Default options loaded from the config file and the environment variables at startup,
+/// the command line arguments override them
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub repos: Vec<String>,
+    pub output_format: Option<OutputFormat>,
+    pub pager: Option<bool>,
+    pub color: Option<bool>,
+    pub session_variables: Vec<(String, String)>,
+    pub theme: Option<Theme>,
+    pub max_column_width: Option<usize>,
+}
+
+/// Load the config file from `GITQL_CONFIG` or `~/.gitqlrc` if it exists, then override
+/// its options by the `GITQL_*` and `NO_COLOR` environment variables
+pub fn load_config() -> Result<Config, String> {
+    let explicit_path = std::env::var_os("GITQL_CONFIG").filter(|path| !path.is_empty());
+    let is_explicit_path = explicit_path.is_some();
+    let path = explicit_path.map(PathBuf::from).or_else(|| {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(CONFIG_FILE_NAME))
+    });
+
+    let mut config = match path {
+        Some(path) if is_explicit_path || path.exists() => {
+            let content = std::fs::read_to_string(&path).map_err(|error| {
+                format!("Can't read config file `{}`: {}", path.display(), error)
+            })?;
+            parse_config(&content)
+                .map_err(|error| format!("Invalid config file `{}`: {}", path.display(), error))?
+        }
+        _ => Config::default(),
+    };
+
+    apply_environment_variables(&mut config, |name| std::env::var(name).ok())?;
+    Ok(config)
+}
+
+/// Parse the TOML content of the config file, unknown options are reported as errors
+pub fn parse_config(content: &str) -> Result<Config, String> {
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|error| error.message().to_string())?;
+
+    let mut config = Config::default();
+    for (key, value) in table {
+        match key.as_str() {
+            "repos" => {
+                let repos = match value {
+                    toml::Value::String(repo) => vec![repo],
+                    toml::Value::Array(repos) => repos
+                        .into_iter()
+                        .map(|repo| repo.as_str().map(|repo| repo.to_string()))
+                        .collect::<Option<Vec<String>>>()
+                        .ok_or("Option `repos` must be list of paths")?,
+                    _ => return Err("Option `repos` must be list of paths".to_string()),
+                };
+                config.repos = repos.iter().map(|repo| expand_home_path(repo)).collect();
+            }
+            "format" => {
+                let format = value.as_str().ok_or("Option `format` must be string")?;
+                let output_format = parse_output_format(format)
+                    .ok_or_else(|| format!("Invalid output format `{}`", format))?;
+                config.output_format = Some(output_format);
+            }
+            "pager" => {
+                config.pager = Some(value.as_bool().ok_or("Option `pager` must be boolean")?);
+            }
+            "color" => {
+                config.color = Some(value.as_bool().ok_or("Option `color` must be boolean")?);
+            }
+            "theme" => {
+                let name = value.as_str().ok_or("Option `theme` must be string")?;
+                let theme =
+                    parse_theme(name).ok_or_else(|| format!("Invalid theme name `{}`", name))?;
+                config.theme = Some(theme);
+            }
+            "max_column_width" => {
+                let width = value
+                    .as_integer()
+                    .filter(|width| *width > 0)
+                    .ok_or("Option `max_column_width` must be positive number")?;
+                config.max_column_width = Some(width as usize);
+            }
+            "session" => {
+                let variables = value.as_table().ok_or("Option `session` must be table")?;
+                for (name, value) in variables {
+                    let value = match value {
+                        toml::Value::String(text) => text.to_string(),
+                        toml::Value::Integer(number) => number.to_string(),
+                        toml::Value::Float(number) => number.to_string(),
+                        toml::Value::Boolean(boolean) => boolean.to_string(),
+                        _ => return Err(format!("Invalid value of session variable `{}`", name)),
+                    };
+                    set_session_variable(&mut config, name, value)?;
+                }
+            }
+            _ => return Err(format!("Unknown option `{}`", key)),
+        }
+    }
+    Ok(config)
+}
+
+/// Override the config options by the environment variables, `GITQL_REPOS` is list of paths separated
+/// like `PATH`, `GITQL_FORMAT` is the output format, any `NO_COLOR` value disables the colors and
+/// each session variable can be set by its name like `GITQL_TIMEZONE` for `@@timezone`
+pub fn apply_environment_variables(
+    config: &mut Config,
+    variable: impl Fn(&str) -> Option<String>,
+) -> Result<(), String> {
+    if let Some(repos) = variable("GITQL_REPOS").filter(|repos| !repos.is_empty()) {
+        config.repos = std::env::split_paths(&repos)
+            .map(|repo| repo.to_string_lossy().to_string())
+            .collect();
+    }
+
+    if let Some(format) = variable("GITQL_FORMAT").filter(|format| !format.is_empty()) {
+        let output_format = parse_output_format(&format)
+            .ok_or_else(|| format!("Invalid output format `{}` of GITQL_FORMAT", format))?;
+        config.output_format = Some(output_format);
+    }
+
+    if variable("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        config.color = Some(false);
+    }
+
+    for (name, _) in SESSION_VARIABLES {
+        let variable_name = format!("GITQL_{}", name.trim_start_matches('@').to_uppercase());
+        if let Some(value) = variable(&variable_name) {
+            set_session_variable(config, name, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set the session variables default values in the environment before the first query
+pub fn define_session_variables(env: &mut Environment, variables: &[(String, String)]) {
+    for (name, value) in variables {
+        let value = typed_session_variable_value(name, value);
+        env.globals.insert(name.to_string(), value);
+    }
+    apply_session_variables(env);
+}
+
+fn set_session_variable(config: &mut Config, name: &str, value: String) -> Result<(), String> {
+    let name = format!("@@{}", name.trim_start_matches('@').to_lowercase());
+    if !is_session_variable(&name) {
+        return Err(format!("Unknown session variable `{}`", name));
+    }
+
+    check_session_variable_value(&name, &value)?;
+    config
+        .session_variables
+        .retain(|(variable, _)| *variable != name);
+    config.session_variables.push((name, value));
+    Ok(())
+}
+
+/// Replace the `~` prefix of the path by the user home directory
+fn expand_home_path(path: &str) -> String {
+    let Some(relative_path) = path.strip_prefix("~/").or(path.strip_prefix("~\\")) else {
+        return path.to_string();
+    };
+
+    match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        Some(home) => PathBuf::from(home)
+            .join(relative_path)
+            .to_string_lossy()
+            .to_string(),
+        None => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let content = r#"
+            repos = ["/work/gql", "/work/docs"]
+            format = "json"
+            pager = false
+            color = false
+            theme = "zebra"
+            max_column_width = 40
+
+            [session]
+            timezone = "+02:00"
+            null_text = ""
+        "#;
+
+        let config = parse_config(content).unwrap();
+        assert_eq!(config.repos, vec!["/work/gql", "/work/docs"]);
+        assert_eq!(config.output_format, Some(OutputFormat::JSON));
+        assert_eq!(config.pager, Some(false));
+        assert_eq!(config.color, Some(false));
+        assert_eq!(config.theme, parse_theme("zebra"));
+        assert_eq!(config.max_column_width, Some(40));
+        assert_eq!(
+            config.session_variables,
+            vec![
+                ("@@null_text".to_string(), "".to_string()),
+                ("@@timezone".to_string(), "+02:00".to_string()),
+            ]
+        );
+
+        assert_eq!(parse_config("").unwrap(), Config::default());
+        assert!(parse_config("format = \"xml\"").is_err());
+        assert!(parse_config("colors = true").is_err());
+        assert!(parse_config("theme = \"neon\"").is_err());
+        assert!(parse_config("max_column_width = 0").is_err());
+        assert!(parse_config("[session]\ntimezone = \"Mars\"").is_err());
+        assert!(parse_config("[session]\nlimit = 10").is_err());
+        assert!(parse_config("repos = [").is_err());
+    }
+
+    #[test]
+    fn test_apply_environment_variables() {
+        let mut config = parse_config("format = \"csv\"\ncolor = true").unwrap();
+        let variables = [
+            ("GITQL_FORMAT", "tsv"),
+            ("NO_COLOR", "1"),
+            ("GITQL_TIMEZONE", "-05:00"),
+        ];
+        let variable = |name: &str| {
+            variables
+                .iter()
+                .find(|(variable, _)| *variable == name)
+                .map(|(_, value)| value.to_string())
+        };
+
+        apply_environment_variables(&mut config, variable).unwrap();
+        assert_eq!(config.output_format, Some(OutputFormat::TSV));
+        assert_eq!(config.color, Some(false));
+        assert_eq!(
+            config.session_variables,
+            vec![("@@timezone".to_string(), "-05:00".to_string())]
+        );
+
+        let mut config = Config::default();
+        let variable = |name: &str| (name == "GITQL_FORMAT").then(|| "xml".to_string());
+        assert!(apply_environment_variables(&mut config, variable).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::tokenizer::line_and_column;
 
 use termcolor::Color;
 
@@ -14,22 +15,17 @@ impl DiagnosticReporter {
         self.stdout.set_color(Some(Color::Red));
         println!("[{}]: {}", diagnostic.label(), diagnostic.message());
 
-        if let Some(location) = diagnostic.location() {
-            println!("=> Line {}, Column {},", location.0, location.1);
-        }
-
         if !query.is_empty() {
-            println!("  |");
-            println!("1 | {}", query);
-            if let Some(location) = diagnostic.location() {
-                print!("  | ");
-                print!("{}", &"-".repeat(location.0));
-                self.stdout.set_color(Some(Color::Yellow));
-                println!("{}", &"^".repeat(usize::max(1, location.1 - location.0)));
-                self.stdout.set_color(Some(Color::Red));
+            match diagnostic.location() {
+                Some(location) => self.report_query_snippet(query, location),
+                None => {
+                    println!("  |");
+                    for (index, line) in query.lines().enumerate() {
+                        println!("{} | {}", index + 1, line);
+                    }
+                    println!("  |");
+                }
             }
-
-            println!("  |");
         }
 
         self.stdout.set_color(Some(Color::Yellow));
@@ -49,6 +45,28 @@ impl DiagnosticReporter {
 
         self.stdout.reset();
     }
+
+    /// Render the query line that contains the start of the location with a caret
+    /// under the span, spans that cross many lines are underlined until the end of the first line
+    fn report_query_snippet(&mut self, query: &str, location: (usize, usize)) {
+        let (line_number, column) = line_and_column(query, location.0);
+        let line = query.lines().nth(line_number - 1).unwrap_or_default();
+        let gutter = " ".repeat(line_number.to_string().len());
+
+        println!("{}--> Line {}, Column {}", gutter, line_number, column);
+        println!("{} |", gutter);
+        println!("{} | {}", line_number, line);
+
+        let remaining_length = line.chars().count().saturating_sub(column - 1);
+        let span_length = location.1.saturating_sub(location.0);
+        let underline_length = span_length.min(remaining_length).max(1);
+
+        print!("{} | {}", gutter, " ".repeat(column - 1));
+        self.stdout.set_color(Some(Color::Yellow));
+        println!("{}", "^".repeat(underline_length));
+        self.stdout.set_color(Some(Color::Red));
+        println!("{} |", gutter);
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +80,9 @@ mod tests {
         };
 
         reporter.report_diagnostic("keyword", Diagnostic::error("error"));
+
+        let diagnostic = Diagnostic::error("error").with_location_span(9, 13);
+        reporter.report_diagnostic("SELECT *\nFROM commits", diagnostic);
         assert!(true);
     }
 }
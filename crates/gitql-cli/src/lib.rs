@@ -1,4 +1,10 @@
 pub mod arguments;
 pub mod colored_stream;
+pub mod completer;
+pub mod config;
 pub mod diagnostic_reporter;
+pub mod pager;
 pub mod render;
+pub mod script;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
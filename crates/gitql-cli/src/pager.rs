@@ -0,0 +1,70 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Default pager command used when neither `GITQL_PAGER` nor `PAGER` is set
+const DEFAULT_PAGER: &str = "less";
+
+/// Default options for `less` to quit if the content fits one screen, keep the ANSI colors
+/// and don't clear the screen on exit, the same defaults used by git
+const DEFAULT_LESS_OPTIONS: &str = "FRX";
+
+/// Resolve the pager command from `GITQL_PAGER` then `PAGER`, returns None if the pager
+/// is explicitly disabled by setting it to an empty value or `cat`
+pub fn pager_command() -> Option<String> {
+    let command = std::env::var("GITQL_PAGER")
+        .or_else(|_| std::env::var("PAGER"))
+        .unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+
+    let command = command.trim();
+    if command.is_empty() || command == "cat" {
+        return None;
+    }
+
+    Some(command.to_string())
+}
+
+/// Check if the content needs a pager to be displayed in terminal with the given height
+pub fn is_content_exceed_height(content: &str, terminal_height: usize) -> bool {
+    content.lines().count() > terminal_height
+}
+
+/// Pipe the content to the pager command and wait until the user quit it,
+/// returns Err if the pager can't be started so the caller can fallback to another output
+pub fn page_content(command: &str, content: &str) -> Result<(), String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("Empty pager command")?;
+
+    let mut pager = Command::new(program);
+    pager.args(parts).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        pager.env("LESS", DEFAULT_LESS_OPTIONS);
+    }
+
+    let mut child = pager.spawn().map_err(|error| error.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may exit before reading all the content, for example if the user press `q`
+        let _ = stdin.write_all(content.as_bytes());
+    }
+
+    child.wait().map_err(|error| error.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_content_exceed_height() {
+        let content = "1\n2\n3\n";
+        assert!(is_content_exceed_height(content, 2));
+        assert!(!is_content_exceed_height(content, 3));
+    }
+
+    #[test]
+    fn test_page_content_with_invalid_command() {
+        assert!(page_content("gitql-pager-that-does-not-exist", "content").is_err());
+        assert!(page_content("", "content").is_err());
+    }
+}
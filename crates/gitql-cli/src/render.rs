@@ -1,5 +1,129 @@
+use std::sync::RwLock;
+
+use comfy_table::Color;
+use gitql_ast::format::truncate_text;
+use gitql_ast::format::value_to_text;
 use gitql_ast::object::GitQLObject;
 use gitql_ast::object::Row;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::value::Value;
+
+use crate::colored_stream::is_colors_enabled;
+
+/// Columns are not shrunk below this width when the table is fitted to the terminal
+const MIN_COLUMN_WIDTH: usize = 4;
+
+/// Printed around the number of the row in the vertical mode, same as MySQL
+const VERTICAL_ROW_SEPARATOR: &str = "***************************";
+
+/// Colors used to render the result tables in the terminal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Color of the columns titles
+    pub header: Option<Color>,
+    /// Color of the `NULL` values, dimmed so they are not confused with text values
+    pub null: Option<Color>,
+    /// Background color of every other row to make wide tables easier to follow
+    pub stripe: Option<Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        THEMES[0].1
+    }
+}
+
+/// Names and colors of the available themes, the first one is the default
+pub const THEMES: [(&str, Theme); 3] = [
+    (
+        "default",
+        Theme {
+            header: Some(Color::Green),
+            null: Some(Color::DarkGrey),
+            stripe: None,
+        },
+    ),
+    (
+        "zebra",
+        Theme {
+            header: Some(Color::Green),
+            null: Some(Color::DarkGrey),
+            stripe: Some(Color::AnsiValue(236)),
+        },
+    ),
+    (
+        "plain",
+        Theme {
+            header: None,
+            null: None,
+            stripe: None,
+        },
+    ),
+];
+
+/// Resolve the theme by its name
+pub fn parse_theme(name: &str) -> Option<Theme> {
+    let name = name.to_lowercase();
+    THEMES
+        .iter()
+        .find(|(theme_name, _)| *theme_name == name)
+        .map(|(_, theme)| *theme)
+}
+
+/// Options of the tables rendering shared by all the queries of the session
+pub struct RenderOptions {
+    pub theme: Theme,
+    /// Maximum number of characters of the column values, longer values are truncated
+    pub max_column_width: Option<usize>,
+}
+
+static RENDER_OPTIONS: RwLock<RenderOptions> = RwLock::new(RenderOptions {
+    theme: THEMES[0].1,
+    max_column_width: None,
+});
+
+/// Set the theme and maximum column width of the tables rendered by this session
+pub fn set_render_options(options: RenderOptions) {
+    if let Ok(mut render_options) = RENDER_OPTIONS.write() {
+        *render_options = options;
+    }
+}
+
+/// Table style resolved from the render options for one output
+struct TableStyle {
+    theme: Option<Theme>,
+    max_column_width: Option<usize>,
+    width: Option<u16>,
+}
+
+impl TableStyle {
+    /// Style of the table printed directly to the terminal, colored and fitted to the terminal width
+    fn terminal() -> Self {
+        let (theme, max_column_width) = RENDER_OPTIONS
+            .read()
+            .map_or((Theme::default(), None), |options| {
+                (options.theme, options.max_column_width)
+            });
+
+        TableStyle {
+            theme: is_colors_enabled().then_some(theme),
+            max_column_width,
+            width: comfy_table::Table::new().width(),
+        }
+    }
+
+    /// Style of the table rendered as plain text, fitted to the width if it's passed
+    fn plain(width: Option<u16>) -> Self {
+        TableStyle {
+            theme: None,
+            max_column_width: RENDER_OPTIONS
+                .read()
+                .ok()
+                .and_then(|options| options.max_column_width),
+            width,
+        }
+    }
+}
 
 enum PaginationInput {
     NextPage,
@@ -12,6 +136,7 @@ pub fn render_objects(
     hidden_selections: &[String],
     pagination: bool,
     page_size: usize,
+    options: &SessionOptions,
 ) {
     if groups.len() > 1 {
         groups.flat()
@@ -31,16 +156,11 @@ pub fn render_objects(
         .map(|k| k.as_ref())
         .collect();
 
-    // Setup table headers
-    let header_color = comfy_table::Color::Green;
-    let mut table_headers = vec![];
-    for key in &titles {
-        table_headers.push(comfy_table::Cell::new(key).fg(header_color));
-    }
+    let style = TableStyle::terminal();
 
     // Print all data without pagination
     if !pagination || page_size >= gql_group_len {
-        print_group_as_table(&titles, table_headers, &gql_group.rows);
+        print_group_as_table(&titles, &gql_group.rows, &style, options);
         return;
     }
 
@@ -54,7 +174,7 @@ pub fn render_objects(
 
         let current_page_groups = &gql_group.rows[start_index..end_index];
         println!("Page {}/{}", current_page, number_of_pages);
-        print_group_as_table(&titles, table_headers.clone(), current_page_groups);
+        print_group_as_table(&titles, current_page_groups, &style, options);
 
         let pagination_input = handle_pagination_input(current_page, number_of_pages);
         match pagination_input {
@@ -65,7 +185,174 @@ pub fn render_objects(
     }
 }
 
-fn print_group_as_table(titles: &Vec<&str>, table_headers: Vec<comfy_table::Cell>, rows: &[Row]) {
+/// Render the selected rows as table lines that fit in the width, used by interactive views
+/// that draw the result themselves instead of printing it
+pub fn render_objects_as_lines(
+    groups: &mut GitQLObject,
+    hidden_selections: &[String],
+    width: u16,
+    options: &SessionOptions,
+) -> Vec<String> {
+    if groups.len() > 1 {
+        groups.flat()
+    }
+
+    if groups.is_empty() || groups.groups[0].is_empty() {
+        return vec![];
+    }
+
+    let titles: Vec<&str> = groups
+        .titles
+        .iter()
+        .filter(|s| !hidden_selections.contains(s))
+        .map(|k| k.as_ref())
+        .collect();
+
+    let style = TableStyle::plain(Some(width));
+    let mut table = build_group_table(&titles, &groups.groups[0].rows, &style, options);
+    table.set_width(width);
+    table.lines().collect()
+}
+
+/// Render the selected rows as a plain table text without colors, used when the result
+/// is written to a file instead of the terminal
+pub fn render_objects_as_text(
+    groups: &mut GitQLObject,
+    hidden_selections: &[String],
+    options: &SessionOptions,
+) -> String {
+    if groups.len() > 1 {
+        groups.flat()
+    }
+
+    if groups.is_empty() || groups.groups[0].is_empty() {
+        return String::new();
+    }
+
+    let titles: Vec<&str> = groups
+        .titles
+        .iter()
+        .filter(|s| !hidden_selections.contains(s))
+        .map(|k| k.as_ref())
+        .collect();
+
+    let style = TableStyle::plain(None);
+    let table = build_group_table(&titles, &groups.groups[0].rows, &style, options);
+    format!("{table}\n")
+}
+
+/// Render each selected row as block of `field: value` lines like MySQL `\G`, the fields names are
+/// aligned and the lines of multi line values are indented under the first line
+pub fn render_objects_vertically(
+    groups: &mut GitQLObject,
+    hidden_selections: &[String],
+    options: &SessionOptions,
+) -> String {
+    if groups.len() > 1 {
+        groups.flat()
+    }
+
+    if groups.is_empty() || groups.groups[0].is_empty() {
+        return String::new();
+    }
+
+    let columns: Vec<(usize, &str)> = groups
+        .titles
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| !hidden_selections.contains(title))
+        .map(|(index, title)| (index, title.as_str()))
+        .collect();
+
+    let titles_width = columns
+        .iter()
+        .map(|(_, title)| title.chars().count())
+        .max()
+        .unwrap_or_default();
+    let indentation = " ".repeat(titles_width + 2);
+
+    let mut content = String::new();
+    for (row_index, row) in groups.groups[0].rows.iter().enumerate() {
+        content.push_str(&format!(
+            "{0} {1}. row {0}\n",
+            VERTICAL_ROW_SEPARATOR,
+            row_index + 1
+        ));
+
+        for (index, title) in &columns {
+            let text = value_to_text(&row.values[*index], options);
+            let mut lines = text.lines();
+            content.push_str(&format!(
+                "{:>width$}: {}\n",
+                title,
+                lines.next().unwrap_or_default(),
+                width = titles_width
+            ));
+
+            for line in lines {
+                content.push_str(&format!("{}{}\n", indentation, line));
+            }
+        }
+    }
+    content
+}
+
+/// Remove the MySQL style `\G` terminator from the end of the query, returns the query and
+/// true if the terminator exists so the result is rendered vertically
+pub fn split_vertical_terminator(query: &str) -> (&str, bool) {
+    match query.trim_end().strip_suffix("\\G") {
+        Some(query) => (query, true),
+        None => (query, false),
+    }
+}
+
+/// Write the rendered content to the file path atomically, the content is written to a
+/// temporary file in the same directory first then renamed to the target path so readers
+/// never see a partially written file
+pub fn write_output_file(path: &str, content: &str, overwrite: bool) -> Result<(), String> {
+    let target = std::path::Path::new(path);
+    if !overwrite && target.exists() {
+        return Err(format!("Output file `{}` already exists", path));
+    }
+
+    let file_name = match target.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => return Err(format!("Invalid output file path `{}`", path)),
+    };
+
+    let temp_name = format!(".{}.{}.tmp", file_name, std::process::id());
+    let temp_path = target.with_file_name(temp_name);
+
+    if let Err(error) = std::fs::write(&temp_path, content) {
+        return Err(format!("Can't write output file `{}`: {}", path, error));
+    }
+
+    if let Err(error) = std::fs::rename(&temp_path, target) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Can't write output file `{}`: {}", path, error));
+    }
+
+    Ok(())
+}
+
+fn print_group_as_table(
+    titles: &[&str],
+    rows: &[Row],
+    style: &TableStyle,
+    options: &SessionOptions,
+) {
+    let table = build_group_table(titles, rows, style, options);
+
+    // Print table
+    println!("{table}");
+}
+
+fn build_group_table(
+    titles: &[&str],
+    rows: &[Row],
+    style: &TableStyle,
+    options: &SessionOptions,
+) -> comfy_table::Table {
     let mut table = comfy_table::Table::new();
 
     // Setup table style
@@ -73,22 +360,110 @@ fn print_group_as_table(titles: &Vec<&str>, table_headers: Vec<comfy_table::Cell
     table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
     table.set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
 
-    table.set_header(table_headers);
-
     let titles_len = titles.len();
+    let values: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.values[..titles_len]
+                .iter()
+                .map(|value| value_to_text(value, options))
+                .collect()
+        })
+        .collect();
+
+    let columns_widths = columns_widths(titles, &values, style);
+    let theme = style.theme.unwrap_or(THEMES[2].1);
+
+    let mut table_headers = vec![];
+    for (index, title) in titles.iter().enumerate() {
+        let header = comfy_table::Cell::new(truncate_lines(title, columns_widths[index]));
+        match theme.header {
+            Some(color) => table_headers.push(header.fg(color)),
+            None => table_headers.push(header),
+        }
+    }
+    table.set_header(table_headers);
 
     // Add rows to the table
-    for row in rows {
+    for (row_index, row) in rows.iter().enumerate() {
         let mut table_row: Vec<comfy_table::Cell> = vec![];
-        for index in 0..titles_len {
-            let value = row.values.get(index).unwrap();
-            table_row.push(comfy_table::Cell::new(value.to_string()));
+        for (index, text) in values[row_index].iter().enumerate() {
+            let mut cell = comfy_table::Cell::new(truncate_lines(text, columns_widths[index]));
+            if let (Value::Null, Some(color)) = (&row.values[index], theme.null) {
+                cell = cell.fg(color);
+            }
+
+            if let (1, Some(color)) = (row_index % 2, theme.stripe) {
+                cell = cell.bg(color);
+            }
+            table_row.push(cell);
         }
         table.add_row(table_row);
     }
 
-    // Print table
-    println!("{table}");
+    table
+}
+
+/// Resolve the maximum width of each column, limited by the maximum column width option and
+/// shrunk to fit the table width if it's known
+fn columns_widths(titles: &[&str], values: &[Vec<String>], style: &TableStyle) -> Vec<usize> {
+    let mut widths: Vec<usize> = titles.iter().map(|title| text_width(title)).collect();
+    for row in values {
+        for (index, text) in row.iter().enumerate() {
+            widths[index] = widths[index].max(text_width(text));
+        }
+    }
+
+    if let Some(max_column_width) = style.max_column_width {
+        for width in widths.iter_mut() {
+            *width = (*width).min(max_column_width);
+        }
+    }
+
+    // Each column has two padding spaces and one border, and the table has one more border
+    match style.width {
+        Some(width) => {
+            let borders_width = titles.len() * 3 + 1;
+            fit_columns_widths(&widths, (width as usize).saturating_sub(borders_width))
+        }
+        None => widths,
+    }
+}
+
+/// Shrink the widest columns until the sum of the widths fits the available width,
+/// columns are not shrunk below the minimum column width
+fn fit_columns_widths(widths: &[usize], available_width: usize) -> Vec<usize> {
+    let mut limit = widths.iter().copied().max().unwrap_or_default();
+    while limit > MIN_COLUMN_WIDTH
+        && widths
+            .iter()
+            .map(|width| (*width).min(limit))
+            .sum::<usize>()
+            > available_width
+    {
+        limit -= 1;
+    }
+    widths.iter().map(|width| (*width).min(limit)).collect()
+}
+
+/// Width of the longest line of the text in characters
+fn text_width(text: &str) -> usize {
+    text.lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Truncate each line of the text that is longer than the width
+fn truncate_lines(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+
+    text.lines()
+        .map(|line| truncate_text(line, width))
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 fn handle_pagination_input(current_page: usize, number_of_pages: usize) -> PaginationInput {
@@ -167,15 +542,19 @@ mod tests {
         let pagination: bool = false;
         let page_size: usize = 1;
 
-        render_objects(&mut object, &hidden_selections, pagination, page_size);
+        render_objects(
+            &mut object,
+            &hidden_selections,
+            pagination,
+            page_size,
+            &SessionOptions::default(),
+        );
         assert!(true);
     }
 
     #[test]
     fn test_print_group_as_table() {
-        let header_color = comfy_table::Color::Green;
         let mut titles: Vec<&str> = vec![];
-        let mut table_headers: Vec<comfy_table::Cell> = vec![];
         let rows: Vec<Row> = vec![Row {
             values: vec![
                 Value::Text("hello".to_string()),
@@ -186,11 +565,169 @@ mod tests {
         titles.push("title1");
         titles.push("title2");
 
-        for key in &titles {
-            table_headers.push(comfy_table::Cell::new(key).fg(header_color));
-        }
+        print_group_as_table(
+            &titles,
+            &rows,
+            &TableStyle::terminal(),
+            &SessionOptions::default(),
+        );
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        assert!(parse_theme("Zebra").unwrap().stripe.is_some());
+        assert_eq!(parse_theme("plain").unwrap().header, None);
+        assert_eq!(parse_theme("default"), Some(Theme::default()));
+        assert_eq!(parse_theme("neon"), None);
+    }
+
+    #[test]
+    fn test_fit_columns_widths() {
+        assert_eq!(fit_columns_widths(&[5, 10], 20), vec![5, 10]);
+        assert_eq!(fit_columns_widths(&[5, 40, 30], 40), vec![5, 17, 17]);
+        assert_eq!(fit_columns_widths(&[10, 10], 2), vec![4, 4]);
+    }
+
+    #[test]
+    fn test_truncate_lines() {
+        assert_eq!(truncate_lines("hello", 5), "hello");
+        assert_eq!(truncate_lines("hello world", 6), "hello…");
+        assert_eq!(truncate_lines("first line\nok", 6), "first…\nok");
+    }
+
+    #[test]
+    fn test_render_objects_with_max_column_width() {
+        let titles = ["title"];
+        let rows = vec![
+            Row {
+                values: vec![Value::Text("a long commit title".to_string())],
+            },
+            Row {
+                values: vec![Value::Null],
+            },
+        ];
+
+        let style = TableStyle {
+            theme: None,
+            max_column_width: Some(8),
+            width: None,
+        };
+        let text =
+            build_group_table(&titles, &rows, &style, &SessionOptions::default()).to_string();
+        assert!(text.contains("a long …"));
+        assert!(!text.contains("commit"));
+    }
+
+    #[test]
+    fn test_render_objects_as_lines() {
+        let mut object = GitQLObject {
+            titles: vec!["title1".to_string(), "title2".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        Value::Text("hello".to_string()),
+                        Value::Text("world".to_string()),
+                    ],
+                }],
+            }],
+        };
+
+        let lines = render_objects_as_lines(&mut object, &[], 40, &SessionOptions::default());
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("title1"));
+        assert!(lines[3].contains("world"));
+    }
+
+    #[test]
+    fn test_render_objects_as_text() {
+        let mut object = GitQLObject {
+            titles: vec!["title1".to_string(), "title2".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        Value::Text("hello".to_string()),
+                        Value::Text("world".to_string()),
+                    ],
+                }],
+            }],
+        };
+
+        let text = render_objects_as_text(
+            &mut object,
+            &["title2".to_string()],
+            &SessionOptions::default(),
+        );
+        assert!(text.contains("title1"));
+        assert!(text.contains("hello"));
+        assert!(!text.contains("title2"));
+    }
+
+    #[test]
+    fn test_render_objects_vertically() {
+        let mut object = GitQLObject {
+            titles: vec![
+                "id".to_string(),
+                "message".to_string(),
+                "hidden".to_string(),
+            ],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        Value::Integer(1),
+                        Value::Text("Fix parser\n\nDetails".to_string()),
+                        Value::Boolean(true),
+                    ],
+                }],
+            }],
+        };
+
+        let text = render_objects_vertically(
+            &mut object,
+            &["hidden".to_string()],
+            &SessionOptions::default(),
+        );
+        let expected = "*************************** 1. row ***************************\n\
+                     \x20    id: 1\n\
+                     message: Fix parser\n\
+                     \x20        \n\
+                     \x20        Details\n";
+        assert_eq!(text, expected);
+        assert_eq!(
+            render_objects_vertically(&mut GitQLObject::default(), &[], &SessionOptions::default()),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_split_vertical_terminator() {
+        assert_eq!(
+            split_vertical_terminator("SELECT * FROM tags\\G "),
+            ("SELECT * FROM tags", true)
+        );
+        assert_eq!(
+            split_vertical_terminator("SELECT * FROM tags"),
+            ("SELECT * FROM tags", false)
+        );
+    }
+
+    #[test]
+    fn test_write_output_file() {
+        let directory = std::env::temp_dir().join(format!("gitql-output-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("result.csv");
+        let path = path.to_str().unwrap();
+
+        assert!(write_output_file(path, "a,b\r\n", false).is_ok());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "a,b\r\n");
+
+        assert!(write_output_file(path, "c,d\r\n", false).is_err());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "a,b\r\n");
+
+        assert!(write_output_file(path, "c,d\r\n", true).is_ok());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "c,d\r\n");
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
 
-        print_group_as_table(&titles, table_headers, &rows);
+        std::fs::remove_dir_all(&directory).unwrap();
     }
 
     #[test]
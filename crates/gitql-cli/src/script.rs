@@ -0,0 +1,187 @@
+use gitql_ast::date_utils;
+use gitql_ast::environment::Environment;
+use gitql_ast::types::DataType;
+use gitql_ast::value::Value;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser::resolve_type_name;
+use gitql_parser::tokenizer;
+use gitql_parser::tokenizer::TokenKind;
+
+/// Split the script content into `;` separated statements, semicolons inside strings
+/// and comments are not separators because the content is tokenized first
+pub fn split_script_statements(
+    content: &str,
+    options: ParserOptions,
+) -> Result<Vec<String>, Box<Diagnostic>> {
+    let tokens = tokenizer::tokenize_with_options(content, options)?;
+    let chars: Vec<char> = content.chars().collect();
+
+    let mut statements: Vec<String> = vec![];
+    let mut start = 0;
+    for token in tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Semicolon)
+    {
+        statements.push(chars[start..token.location.start].iter().collect());
+
+        // Location end is the position of `;` itself, so the next statement starts after it
+        start = token.location.end + 1;
+    }
+    statements.push(chars[start..].iter().collect());
+
+    statements.retain(|statement| !statement.trim().is_empty());
+    Ok(statements)
+}
+
+/// Define the script arguments passed as `name=value` or `name:type=value` as global variables,
+/// the type is inferred from the value if it's not passed
+pub fn define_script_arguments(
+    env: &mut Environment,
+    arguments: &[(String, String)],
+) -> Result<(), String> {
+    for (name, value) in arguments {
+        let (name, data_type, value) = parse_script_argument(name, value)?;
+        env.define_global(name.to_string(), data_type);
+        env.globals.insert(name, value);
+    }
+    Ok(())
+}
+
+/// Resolve the global variable name, type and value of one script argument
+fn parse_script_argument(name: &str, value: &str) -> Result<(String, DataType, Value), String> {
+    let (name, type_name) = match name.split_once(':') {
+        Some((name, type_name)) => (name, Some(type_name)),
+        None => (name, None),
+    };
+
+    let mut chars = name.chars();
+    let is_valid_name = chars.next().is_some_and(|c| c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric());
+    if !is_valid_name {
+        return Err(format!(
+            "Invalid argument name `{}`, name must start with alphabetic character",
+            name
+        ));
+    }
+
+    // Global variables names are case-insensitive like the other identifiers
+    let variable_name = format!("@{}", name.to_lowercase());
+
+    let (data_type, value) = match type_name {
+        Some(type_name) => {
+            let data_type = resolve_type_name(&type_name.to_lowercase()).ok_or_else(|| {
+                format!("Unknown type name `{}` of argument `{}`", type_name, name)
+            })?;
+            let value = typed_argument_value(value, &data_type).ok_or_else(|| {
+                format!(
+                    "Invalid value `{}` of argument `{}` with type {}",
+                    value, name, data_type
+                )
+            })?;
+            (data_type, value)
+        }
+        None => inferred_argument_value(value),
+    };
+
+    Ok((variable_name, data_type, value))
+}
+
+/// Infer the value type, numbers and booleans are typed and other values are text,
+/// double quoted values are always text, for example `id="10"`
+fn inferred_argument_value(value: &str) -> (DataType, Value) {
+    if let Some(text) = unquote_argument_value(value) {
+        return (DataType::Text, Value::Text(text.to_string()));
+    }
+
+    if let Ok(integer) = value.parse::<i64>() {
+        return (DataType::Integer, Value::Integer(integer));
+    }
+
+    // Words like `inf` and `nan` are parsed as float but they are passed as text
+    let has_digits = value.chars().any(|c| c.is_ascii_digit());
+    match value.parse::<f64>() {
+        Ok(float) if has_digits => return (DataType::Float, Value::Float(float)),
+        _ => {}
+    }
+
+    if let Ok(boolean) = value.to_lowercase().parse::<bool>() {
+        return (DataType::Boolean, Value::Boolean(boolean));
+    }
+
+    (DataType::Text, Value::Text(value.to_string()))
+}
+
+/// Convert the value into the passed type, returns None if the value has invalid format
+fn typed_argument_value(value: &str, data_type: &DataType) -> Option<Value> {
+    let value = unquote_argument_value(value).unwrap_or(value);
+    match data_type {
+        DataType::Integer => value.parse::<i64>().ok().map(Value::Integer),
+        DataType::Float => value.parse::<f64>().ok().map(Value::Float),
+        DataType::Boolean => value
+            .to_lowercase()
+            .parse::<bool>()
+            .ok()
+            .map(Value::Boolean),
+        DataType::Date if date_utils::is_valid_date_format(value) => {
+            Some(Value::Date(date_utils::date_to_time_stamp(value)))
+        }
+        DataType::Time if date_utils::is_valid_time_format(value) => {
+            Some(Value::Time(value.to_string()))
+        }
+        DataType::DateTime if date_utils::is_valid_datetime_format(value) => {
+            Some(Value::DateTime(date_utils::date_time_to_time_stamp(value)))
+        }
+        DataType::Text => Some(Value::Text(value.to_string())),
+        _ => None,
+    }
+}
+
+/// Returns the text between the double quotes if the value is double quoted
+fn unquote_argument_value(value: &str) -> Option<&str> {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_script_statements() {
+        let content = "SET @a = \"x;y\";\n-- comment; here\nSELECT @a;\n\n";
+        let statements = split_script_statements(content, ParserOptions::default())
+            .ok()
+            .unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0], "SET @a = \"x;y\"");
+        assert_eq!(statements[1].trim(), "-- comment; here\nSELECT @a");
+    }
+
+    #[test]
+    fn test_define_script_arguments() {
+        let mut env = Environment::default();
+        let arguments = vec![
+            ("Author".to_string(), "Jane".to_string()),
+            ("limit".to_string(), "10".to_string()),
+            ("id".to_string(), "\"10\"".to_string()),
+            ("since:date".to_string(), "2024-01-01".to_string()),
+        ];
+
+        assert!(define_script_arguments(&mut env, &arguments).is_ok());
+        assert!(env.globals["@author"].equals(&Value::Text("Jane".to_string())));
+        assert!(env.globals_types["@limit"].is_int());
+        assert!(env.globals["@id"].equals(&Value::Text("10".to_string())));
+        assert!(env.globals_types["@since"].is_date());
+    }
+
+    #[test]
+    fn test_invalid_script_arguments() {
+        let mut env = Environment::default();
+        for (name, value) in [("1st", "x"), ("since:date", "2024"), ("n:number", "1")] {
+            let arguments = vec![(name.to_string(), value.to_string())];
+            assert!(define_script_arguments(&mut env, &arguments).is_err());
+        }
+    }
+}
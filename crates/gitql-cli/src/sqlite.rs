@@ -0,0 +1,152 @@
+use gitql_ast::format::value_to_text;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::value::Value;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::Connection;
+
+/// Write the rows of the GitQLObject into a table of the SQLite database file, the database is created
+/// if it doesn't exist and the table is replaced if it already exists, so the export can be repeated
+pub fn export_object_to_sqlite(
+    path: &str,
+    table_name: &str,
+    object: &GitQLObject,
+    options: &SessionOptions,
+) -> Result<(), String> {
+    if object.titles.is_empty() {
+        return Err(format!(
+            "Can't export table `{}` without columns",
+            table_name
+        ));
+    }
+
+    let mut connection = Connection::open(path)
+        .map_err(|error| format!("Can't open SQLite database `{}`: {}", path, error))?;
+
+    write_object_table(&mut connection, table_name, object, options).map_err(|error| {
+        format!(
+            "Can't export table `{}` to SQLite database `{}`: {}",
+            table_name, path, error
+        )
+    })
+}
+
+/// Create the table with the object titles as columns and insert all rows in one transaction
+fn write_object_table(
+    connection: &mut Connection,
+    table_name: &str,
+    object: &GitQLObject,
+    options: &SessionOptions,
+) -> rusqlite::Result<()> {
+    let table_name = quote_identifier(table_name);
+    let columns: Vec<String> = object
+        .titles
+        .iter()
+        .enumerate()
+        .map(|(index, title)| format!("{} {}", quote_identifier(title), column_type(object, index)))
+        .collect();
+
+    let transaction = connection.transaction()?;
+    transaction.execute(&format!("DROP TABLE IF EXISTS {}", table_name), [])?;
+    transaction.execute(
+        &format!("CREATE TABLE {} ({})", table_name, columns.join(", ")),
+        [],
+    )?;
+
+    {
+        let placeholders = vec!["?"; object.titles.len()].join(", ");
+        let mut statement = transaction.prepare(&format!(
+            "INSERT INTO {} VALUES ({})",
+            table_name, placeholders
+        ))?;
+
+        for row in object.groups.iter().flat_map(|group| group.rows.iter()) {
+            statement.execute(rusqlite::params_from_iter(
+                row.values.iter().map(|value| value_to_sql(value, options)),
+            ))?;
+        }
+    }
+
+    transaction.commit()
+}
+
+/// SQLite column type of the first non null value in the column, or no type if all values are null
+fn column_type(object: &GitQLObject, index: usize) -> &'static str {
+    let value = object
+        .groups
+        .iter()
+        .flat_map(|group| group.rows.iter())
+        .filter_map(|row| row.values.get(index))
+        .find(|value| !matches!(value, Value::Null));
+
+    match value {
+        Some(Value::Integer(_)) | Some(Value::Boolean(_)) => "INTEGER",
+        Some(Value::Float(_)) => "REAL",
+        Some(_) => "TEXT",
+        None => "",
+    }
+}
+
+/// Convert value to SQLite value, booleans are stored as integers and dates as ISO text
+fn value_to_sql(value: &Value, options: &SessionOptions) -> SqlValue {
+    match value {
+        Value::Integer(integer) => SqlValue::Integer(*integer),
+        Value::Float(float) => SqlValue::Real(*float),
+        Value::Boolean(boolean) => SqlValue::Integer(*boolean as i64),
+        Value::Text(text) => SqlValue::Text(text.to_string()),
+        Value::Null => SqlValue::Null,
+        _ => SqlValue::Text(value_to_text(value, options)),
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::object::Group;
+    use gitql_ast::object::Row;
+
+    #[test]
+    fn test_export_object_to_sqlite() {
+        let path = std::env::temp_dir().join(format!("gitql-export-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let object = GitQLObject {
+            titles: vec!["name".to_string(), "count".to_string()],
+            groups: vec![Group {
+                rows: vec![
+                    Row {
+                        values: vec![Value::Text("Amr".to_string()), Value::Integer(2)],
+                    },
+                    Row {
+                        values: vec![Value::Null, Value::Integer(1)],
+                    },
+                ],
+            }],
+        };
+
+        // Exporting twice replaces the table instead of appending the rows
+        let options = SessionOptions::default();
+        assert!(export_object_to_sqlite(path, "result", &object, &options).is_ok());
+        assert!(export_object_to_sqlite(path, "result", &object, &options).is_ok());
+
+        let connection = Connection::open(path).unwrap();
+        let total: i64 = connection
+            .query_row("SELECT SUM(\"count\") FROM \"result\"", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(total, 3);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("commits"), "\"commits\"");
+        assert_eq!(quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -0,0 +1,142 @@
+use gitql_ast::environment::Environment;
+use gitql_ast::object::Column;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::object::ResultRow;
+use gitql_engine::engine;
+use gitql_engine::engine::EvaluationResult;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+
+pub use gitql_ast;
+pub use gitql_engine;
+pub use gitql_parser;
+
+/// Options to change how the query is parsed and evaluated
+#[derive(Clone, Copy)]
+pub struct ExecuteOptions {
+    /// Options passed to the tokenizer and parser
+    pub parser_options: ParserOptions,
+    /// Number of threads used to collect data from the repositories
+    pub threads: usize,
+}
+
+impl Default for ExecuteOptions {
+    fn default() -> Self {
+        ExecuteOptions {
+            parser_options: ParserOptions::default(),
+            threads: 1,
+        }
+    }
+}
+
+/// Result of one query, statements that select no rows like `SET` return an empty result
+#[derive(Default)]
+pub struct QueryResult {
+    pub object: GitQLObject,
+}
+
+impl QueryResult {
+    /// Returns the name and type of each selected column
+    pub fn columns(&self) -> Vec<Column> {
+        self.object.columns()
+    }
+
+    /// Returns an iterator over the selected rows with typed access by the column name
+    pub fn rows(&self) -> impl Iterator<Item = ResultRow<'_>> {
+        self.object.rows()
+    }
+}
+
+/// Open the repositories and execute the query on them with the default options
+pub fn execute(repo_paths: &[&str], query: &str) -> Result<QueryResult, Box<Diagnostic>> {
+    execute_with_options(repo_paths, query, ExecuteOptions::default())
+}
+
+/// Open the repositories and execute the query on them
+pub fn execute_with_options(
+    repo_paths: &[&str],
+    query: &str,
+    options: ExecuteOptions,
+) -> Result<QueryResult, Box<Diagnostic>> {
+    let repos = open_repositories(repo_paths)?;
+    let mut env = Environment::default();
+    execute_in(&mut env, &repos, query, options)
+}
+
+/// Execute the query on already opened repositories, the environment can be reused
+/// between queries to keep the global variables defined by the previous queries
+pub fn execute_in(
+    env: &mut Environment,
+    repos: &[gix::Repository],
+    query: &str,
+    options: ExecuteOptions,
+) -> Result<QueryResult, Box<Diagnostic>> {
+    let tokens = tokenizer::tokenize_with_options(query, options.parser_options)?;
+    if tokens.is_empty() {
+        return Ok(QueryResult::default());
+    }
+
+    let query_node = parser::parse_gql_with_options(tokens, env, options.parser_options)?;
+    let evaluation_result = engine::evaluate_with_threads(env, repos, query_node, options.threads);
+    env.clear_session();
+
+    match evaluation_result? {
+        EvaluationResult::SelectedGroups(mut object, hidden_selection) => {
+            object.remove_hidden_selections(&hidden_selection);
+            Ok(QueryResult { object })
+        }
+        EvaluationResult::SetGlobalVariable
+        | EvaluationResult::CreateTempTable
+        | EvaluationResult::InsertedRows(_) => Ok(QueryResult::default()),
+    }
+}
+
+/// Open the repositories, returns an error diagnostic with the path of the first invalid repository
+pub fn open_repositories(repo_paths: &[&str]) -> Result<Vec<gix::Repository>, Box<Diagnostic>> {
+    let mut repos = Vec::with_capacity(repo_paths.len());
+    for path in repo_paths {
+        let repo = gix::discover(path).map_err(|error| {
+            Diagnostic::error(&format!("Can't open repository `{}`: {}", path, error)).as_boxed()
+        })?;
+        repos.push(repo);
+    }
+    Ok(repos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execute_values() {
+        let query = "SELECT name FROM (VALUES (1, \"a\"), (2, \"b\")) AS t(id, name) WHERE id > 1";
+        let result = execute(&[], query);
+        assert!(result.is_ok());
+
+        let result = result.ok().unwrap();
+        let rows: Vec<ResultRow> = result.rows().collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_text("name"), Some("b"));
+        assert_eq!(result.columns().len(), 1);
+    }
+
+    #[test]
+    fn test_execute_in_keeps_global_variables() {
+        let mut env = Environment::default();
+        let options = ExecuteOptions::default();
+        assert!(execute_in(&mut env, &[], "SET @limit = 2", options).is_ok());
+
+        let result = execute_in(&mut env, &[], "SELECT @limit AS value", options);
+        assert!(result.is_ok());
+        let result = result.ok().unwrap();
+        assert_eq!(result.rows().next().unwrap().get_int("value"), Some(2));
+    }
+
+    #[test]
+    fn test_execute_invalid_repository() {
+        let result = execute(&["gitql-core-missing-repository"], "SELECT 1");
+        assert!(result.is_err());
+    }
+}
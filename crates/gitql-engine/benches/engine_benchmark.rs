@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::statement::Query;
+use gitql_engine::engine::evaluate;
+use gitql_parser::parser::parse_gql;
+use gitql_parser::tokenizer::tokenize;
+
+/// Number of commits in the synthetic repository, titles are repeated to create groups
+const FIXTURE_COMMITS_COUNT: usize = 2000;
+const FIXTURE_TITLES_COUNT: usize = 100;
+
+/// Number of commits in the large synthetic repository used to compare sorting strategies
+const LARGE_FIXTURE_COMMITS_COUNT: usize = 20000;
+
+/// Create bare repository with linear history of commits that have empty trees
+fn create_fixture_repository(path: &Path, commits_count: usize) {
+    if path.exists() {
+        std::fs::remove_dir_all(path).expect("failed to delete old fixture");
+    }
+
+    let mut repo = gix::init_bare(path).expect("failed to init bare");
+    let mut config = repo.config_snapshot_mut();
+    config
+        .set_raw_value("author", None, "name", "name")
+        .expect("failed to set name");
+    config
+        .set_raw_value("author", None, "email", "name@example.com")
+        .expect("failed to set email");
+
+    let repo = config
+        .commit_auto_rollback()
+        .expect("failed to commit auto rollback");
+
+    let tree = repo
+        .write_object(gix::objs::Tree::empty())
+        .expect("failed to write object")
+        .detach();
+
+    let mut parents: Vec<gix::ObjectId> = vec![];
+    for i in 0..commits_count {
+        let message = format!(
+            "feature {}\n\ncommit number {}",
+            i % FIXTURE_TITLES_COUNT,
+            i
+        );
+        let commit = repo
+            .commit("HEAD", message, tree, parents)
+            .expect("failed to commit")
+            .detach();
+        parents = vec![commit];
+    }
+}
+
+fn engine_benchmark(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("gitql-engine-benchmark-repo");
+    create_fixture_repository(&path, FIXTURE_COMMITS_COUNT);
+    let repos = [gix::open(&path).expect("failed to open fixture")];
+
+    let queries = [
+        (
+            "select commits",
+            "SELECT commit_id, title, name FROM commits",
+        ),
+        (
+            "filter commits",
+            "SELECT title FROM commits WHERE title LIKE \"feature 1%\"",
+        ),
+        (
+            "group commits",
+            "SELECT title, COUNT(title), MAX(datetime) FROM commits GROUP BY title",
+        ),
+        (
+            "order commits with limit",
+            "SELECT title, datetime FROM commits ORDER BY title DESC LIMIT 10",
+        ),
+    ];
+
+    for (name, query) in queries {
+        bench_query(c, name, query, &repos);
+    }
+
+    std::fs::remove_dir_all(&path).expect("failed to delete fixture");
+}
+
+/// Compare the bounded heap of `ORDER BY` with small `LIMIT` against sorting all rows
+fn large_history_benchmark(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("gitql-engine-benchmark-large-repo");
+    create_fixture_repository(&path, LARGE_FIXTURE_COMMITS_COUNT);
+    let repos = [gix::open(&path).expect("failed to open fixture")];
+
+    let queries = [
+        (
+            "order large history with limit",
+            "SELECT title, message FROM commits ORDER BY message DESC LIMIT 10",
+        ),
+        (
+            "order large history without limit",
+            "SELECT title, message FROM commits ORDER BY message DESC",
+        ),
+    ];
+
+    let mut group = c.benchmark_group("large history");
+    group.sample_size(10);
+    for (name, query) in queries {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || parse_query(query),
+                |(mut env, query)| evaluate(&mut env, black_box(&repos), query),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+
+    std::fs::remove_dir_all(&path).expect("failed to delete fixture");
+}
+
+fn bench_query(c: &mut Criterion, name: &str, query: &str, repos: &[gix::Repository]) {
+    c.bench_function(name, |b| {
+        b.iter_batched(
+            || parse_query(query),
+            |(mut env, query)| evaluate(&mut env, black_box(repos), query),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn parse_query(query: &str) -> (Environment, Query) {
+    let mut env = Environment::default();
+    let tokens = tokenize(query).ok().unwrap();
+    let query = parse_gql(tokens, &mut env).ok().unwrap();
+    (env, query)
+}
+
+criterion_group!(benches, engine_benchmark, large_history_benchmark);
+criterion_main!(benches);
@@ -3,63 +3,390 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::time::Instant;
 use std::vec;
 
+use gitql_ast::aggregation::is_order_sensitive_aggregation;
 use gitql_ast::environment::Environment;
 use gitql_ast::object::GitQLObject;
 use gitql_ast::object::Group;
 use gitql_ast::object::Row;
+use gitql_ast::session::session_variable_type;
+use gitql_ast::session::session_variable_value;
+use gitql_ast::session::SESSION_VARIABLES;
+use gitql_ast::statement::AggregateValue;
+use gitql_ast::statement::AggregationsStatement;
+use gitql_ast::statement::CreateTempTableStatement;
 use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::GroupByStatement;
+use gitql_ast::statement::InsertStatement;
+use gitql_ast::statement::InsertTarget;
+use gitql_ast::statement::LimitCount;
+use gitql_ast::statement::LimitStatement;
+use gitql_ast::statement::OffsetStatement;
+use gitql_ast::statement::OrderByStatement;
 use gitql_ast::statement::Query;
 use gitql_ast::statement::SelectStatement;
+use gitql_ast::statement::Statement;
+use gitql_ast::types::DataType;
+use gitql_ast::value::Value;
+use gitql_parser::diagnostic::Diagnostic;
 
+use crate::engine_diagnostic::runtime_exception;
+use crate::engine_estimate::estimate_query_rows;
+use crate::engine_executor::apply_rollup_placeholders;
 use crate::engine_executor::execute_global_variable_statement;
+use crate::engine_executor::execute_group_by_grouping_sets;
+use crate::engine_executor::execute_group_by_with_aggregations;
+use crate::engine_executor::execute_order_by_in_groups;
+use crate::engine_executor::execute_select_statement_in_parallel;
 use crate::engine_executor::execute_statement;
+use crate::engine_executor::execute_subquery_select_statement;
+use crate::engine_executor::execute_top_n_order_by_statement;
+use crate::engine_memory::check_rows_memory;
+use crate::engine_progress::finish_progress;
+use crate::engine_provider::is_data_provider_table;
+use crate::engine_repository::Repository;
+use crate::engine_sink::append_object_to_file;
+use crate::engine_spill::apply_distinct_on_spilled_rows;
+use crate::engine_spill::execute_statement_on_spilled_rows;
+use crate::engine_spill::load_spilled_rows;
+use crate::engine_spill::spill_selected_rows;
+use crate::engine_spill::SpilledRows;
+use crate::engine_stats::QueryStats;
 
-const GQL_COMMANDS_IN_ORDER: [&str; 8] = [
+const GQL_COMMANDS_IN_ORDER: [&str; 10] = [
     "select",
     "where",
+    "sample",
     "group",
     "aggregation",
     "having",
     "order",
+    "row_number",
     "offset",
     "limit",
 ];
 
+/// Statements that flatten the groups into one, so each group of aggregated query must be
+/// merged into one row before the first of them is executed
+const GROUPS_FLATTENING_COMMANDS: [&str; 5] = ["having", "order", "row_number", "offset", "limit"];
+
 pub enum EvaluationResult {
     SelectedGroups(GitQLObject, Vec<std::string::String>),
     SetGlobalVariable,
+    CreateTempTable,
+    InsertedRows(usize),
 }
 
 pub fn evaluate(
     env: &mut Environment,
-    repos: &[gix::Repository],
+    repos: &[Repository],
+    query: Query,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
+    evaluate_with_threads(env, repos, query, 1)
+}
+
+/// Evaluate the query and select data from multiple repositories using up to `threads` threads
+pub fn evaluate_with_threads(
+    env: &mut Environment,
+    repos: &[Repository],
+    query: Query,
+    threads: usize,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
+    evaluate_with_stats(env, repos, query, threads, &mut QueryStats::default())
+}
+
+/// Evaluate the query like `evaluate_with_threads` and collect the rows counts
+/// and the timing of each stage of the query into the statistics
+pub fn evaluate_with_stats(
+    env: &mut Environment,
+    repos: &[Repository],
     query: Query,
-) -> Result<EvaluationResult, String> {
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
+    let start = Instant::now();
+    let result = evaluate_query(env, repos, query, threads, stats);
+    stats.total_time += start.elapsed();
+    finish_progress();
+    result
+}
+
+fn evaluate_query(
+    env: &mut Environment,
+    repos: &[Repository],
+    query: Query,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
     match query {
-        Query::Select(gql_query) => evaluate_select_query(env, repos, gql_query),
+        Query::Select(gql_query) => {
+            evaluate_select_query_with_stats(env, repos, gql_query, threads, stats)
+        }
         Query::GlobalVariableDeclaration(global_variable) => {
-            execute_global_variable_statement(env, &global_variable)?;
+            execute_global_variable_statement(env, &global_variable).map_err(runtime_exception)?;
             Ok(EvaluationResult::SetGlobalVariable)
         }
+        Query::ShowVariables => Ok(EvaluationResult::SelectedGroups(
+            show_global_variables(env),
+            vec![],
+        )),
+        Query::Explain(gql_query) => Ok(EvaluationResult::SelectedGroups(
+            explain_select_query(env, repos, &gql_query),
+            vec![],
+        )),
+        Query::CreateTempTable(statement) => {
+            create_temp_table(env, repos, statement, threads, stats)?;
+            Ok(EvaluationResult::CreateTempTable)
+        }
+        Query::Insert(statement) => {
+            let rows_count = insert_rows(env, repos, statement, threads, stats)?;
+            Ok(EvaluationResult::InsertedRows(rows_count))
+        }
+    }
+}
+
+/// Evaluate the query of the temporary table once and store its rows without the hidden selections,
+/// so the next queries select them without walking the repositories again
+fn create_temp_table(
+    env: &mut Environment,
+    repos: &[Repository],
+    statement: CreateTempTableStatement,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<(), Box<Diagnostic>> {
+    let object = evaluate_select_object(env, repos, statement.query, threads, stats)?;
+    env.define_temp_table(&statement.table_name, statement.columns, object);
+    Ok(())
+}
+
+/// Evaluate the query and append its rows to the temporary table or the file,
+/// returns the number of inserted rows
+fn insert_rows(
+    env: &mut Environment,
+    repos: &[Repository],
+    statement: InsertStatement,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<usize, Box<Diagnostic>> {
+    let object = evaluate_select_object(env, repos, statement.query, threads, stats)?;
+    let rows_count = main_group_rows_count(&object);
+    match statement.target {
+        InsertTarget::TempTable(table_name) => {
+            let rows = object.groups.into_iter().flat_map(|group| group.rows);
+            if !env.append_temp_table_rows(&table_name, rows.collect()) {
+                return Err(runtime_exception(format!(
+                    "Temporary table `{}` doesn't exist",
+                    table_name
+                )));
+            }
+        }
+        InsertTarget::File(path, format) => {
+            append_object_to_file(&path, format, &object, &env.session)
+                .map_err(runtime_exception)?
+        }
+    }
+    Ok(rows_count)
+}
+
+/// Evaluate the select query into one group of rows without the hidden selections
+fn evaluate_select_object(
+    env: &mut Environment,
+    repos: &[Repository],
+    query: GQLQuery,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<GitQLObject, Box<Diagnostic>> {
+    match evaluate_select_query_with_stats(env, repos, query, threads, stats)? {
+        EvaluationResult::SelectedGroups(mut object, _) => {
+            if object.len() > 1 {
+                object.flat();
+            }
+            Ok(object)
+        }
+        EvaluationResult::SetGlobalVariable
+        | EvaluationResult::CreateTempTable
+        | EvaluationResult::InsertedRows(_) => Ok(GitQLObject::default()),
+    }
+}
+
+/// List the defined global variables sorted by name with their types and current values
+fn show_global_variables(env: &Environment) -> GitQLObject {
+    let mut variables: Vec<(&str, String)> = env
+        .globals_types
+        .iter()
+        .map(|(name, data_type)| (name.as_str(), data_type.to_string()))
+        .collect();
+
+    // Session variables are listed with their default values even if they are not set
+    for (name, _) in SESSION_VARIABLES {
+        if !env.globals_types.contains_key(name) {
+            let data_type = session_variable_type(name).unwrap_or(DataType::Text);
+            variables.push((name, data_type.to_string()));
+        }
+    }
+
+    variables.sort_by_key(|(name, _)| *name);
+
+    let rows = variables
+        .into_iter()
+        .map(|(name, data_type)| {
+            let value = match env.globals.get(name) {
+                Some(value) => value.clone(),
+                None => session_variable_value(env, name)
+                    .filter(|value| !value.is_empty())
+                    .map_or(Value::Null, Value::Text),
+            };
+
+            Row {
+                values: vec![Value::Text(name.to_string()), Value::Text(data_type), value],
+            }
+        })
+        .collect();
+
+    GitQLObject {
+        titles: vec!["name".to_string(), "type".to_string(), "value".to_string()],
+        groups: vec![Group { rows }],
+    }
+}
+
+/// One row with the `FROM` table of the query, the estimated number of rows it scans
+/// and if the scan is expensive, the estimate is `NULL` if the table can't be estimated
+fn explain_select_query(env: &Environment, repos: &[Repository], query: &GQLQuery) -> GitQLObject {
+    let values = match estimate_query_rows(env, repos, query) {
+        Some(estimate) => vec![
+            Value::Text(estimate.table_name.to_string()),
+            Value::Integer(estimate.rows_count as i64),
+            Value::Boolean(estimate.is_expensive()),
+        ],
+        None => vec![Value::Null, Value::Null, Value::Null],
+    };
+
+    GitQLObject {
+        titles: vec![
+            "table".to_string(),
+            "estimated_rows".to_string(),
+            "expensive".to_string(),
+        ],
+        groups: vec![Group {
+            rows: vec![Row { values }],
+        }],
     }
 }
 
 pub fn evaluate_select_query(
     env: &mut Environment,
-    repos: &[gix::Repository],
+    repos: &[Repository],
     query: GQLQuery,
-) -> Result<EvaluationResult, String> {
+    threads: usize,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
+    evaluate_select_query_with_stats(env, repos, query, threads, &mut QueryStats::default())
+}
+
+fn evaluate_select_query_with_stats(
+    env: &mut Environment,
+    repos: &[Repository],
+    mut query: GQLQuery,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
+    // Subqueries in `IN` are evaluated first and materialized into sets of values
+    let mut subqueries_values = Vec::with_capacity(query.in_subqueries.len());
+    for subquery in std::mem::take(&mut query.in_subqueries) {
+        match evaluate_select_query_with_stats(env, repos, subquery, threads, stats)? {
+            EvaluationResult::SelectedGroups(object, hidden_selections) => {
+                subqueries_values.push(subquery_values_set(&object, &hidden_selections))
+            }
+            EvaluationResult::SetGlobalVariable
+            | EvaluationResult::CreateTempTable
+            | EvaluationResult::InsertedRows(_) => subqueries_values.push(HashSet::new()),
+        }
+    }
+
+    // The outer query subqueries values are restored after evaluating this query
+    let outer_subqueries_values = std::mem::replace(&mut env.subqueries_values, subqueries_values);
+    let result = execute_select_query(env, repos, query, threads, stats);
+    env.subqueries_values = outer_subqueries_values;
+    result
+}
+
+/// Collect the values of the selected column of `IN` subquery into set of values keys
+fn subquery_values_set(object: &GitQLObject, hidden_selections: &[String]) -> HashSet<String> {
+    let column_index = object
+        .titles
+        .iter()
+        .position(|title| !hidden_selections.contains(title))
+        .unwrap_or(0);
+
+    object
+        .groups
+        .iter()
+        .flat_map(|group| group.rows.iter())
+        .filter_map(|row| row.values.get(column_index))
+        .map(|value| value.hash_key())
+        .collect()
+}
+
+fn execute_select_query(
+    env: &mut Environment,
+    repos: &[Repository],
+    query: GQLQuery,
+    threads: usize,
+    stats: &mut QueryStats,
+) -> Result<EvaluationResult, Box<Diagnostic>> {
     let mut gitql_object = GitQLObject::default();
     let mut alias_table: HashMap<String, String> = HashMap::new();
 
     let hidden_selections = query.hidden_selections;
     let mut statements_map = query.statements;
-    let first_repo = repos.first().unwrap();
+    let first_repo = repos.first();
 
+    // Subquery in `FROM` is evaluated first to be used as the rows source
+    let subquery_object = match query.subquery {
+        Some(subquery) => {
+            match evaluate_select_query_with_stats(env, repos, *subquery, threads, stats)? {
+                EvaluationResult::SelectedGroups(subquery_object, _) => Some(subquery_object),
+                EvaluationResult::SetGlobalVariable
+                | EvaluationResult::CreateTempTable
+                | EvaluationResult::InsertedRows(_) => None,
+            }
+        }
+        None => None,
+    };
+
+    // Selected rows over the spill threshold are written to disk and processed in partitions
+    let mut spilled = SpilledRows::default();
+
+    let mut is_aggregated_while_grouping = false;
+    let mut is_groups_merged = false;
+    let mut groups_rolled_up_fields: Vec<Vec<usize>> = vec![];
     for gql_command in GQL_COMMANDS_IN_ORDER {
         if statements_map.contains_key(gql_command) {
+            if !is_groups_merged && GROUPS_FLATTENING_COMMANDS.contains(&gql_command) {
+                merge_aggregated_groups(
+                    &mut gitql_object,
+                    query.has_group_by_statement,
+                    query.has_aggregation_function,
+                );
+                is_groups_merged = true;
+            }
+
+            if !spilled.is_empty()
+                && execute_statement_on_spilled_rows(
+                    env,
+                    gql_command,
+                    &mut statements_map,
+                    first_repo,
+                    &mut gitql_object,
+                    &mut spilled,
+                    &mut alias_table,
+                    &hidden_selections,
+                )?
+            {
+                continue;
+            }
+
             let statement = statements_map.get_mut(gql_command).unwrap();
 
             match gql_command {
@@ -70,42 +397,100 @@ pub fn evaluate_select_query(
                         .downcast_ref::<SelectStatement>()
                         .unwrap();
 
-                    // If table name is empty no need to perform it on each repository
-                    if select_statement.table_name.is_empty() {
-                        execute_statement(
-                            env,
-                            statement,
-                            &repos[0],
-                            &mut gitql_object,
-                            &mut alias_table,
-                            &hidden_selections,
-                        )?;
+                    let scan_start = Instant::now();
+
+                    // Temporary and external tables rows are selected like the rows of subquery in `FROM`
+                    let temp_table_object =
+                        env.temp_tables.get(&select_statement.table_name).cloned();
+                    let derived_object = subquery_object.as_ref().or(temp_table_object.as_deref());
+
+                    // Select without table or from data provider or derived table is performed once, not on each repository
+                    if select_statement.table_name.is_empty()
+                        || select_statement.values_table.is_some()
+                        || derived_object.is_some()
+                        || is_data_provider_table(env, &select_statement.table_name)
+                    {
+                        match derived_object {
+                            Some(subquery_object) => execute_subquery_select_statement(
+                                env,
+                                select_statement,
+                                subquery_object,
+                                &mut gitql_object,
+                                &mut alias_table,
+                                &hidden_selections,
+                            )
+                            .map_err(runtime_exception)?,
+                            None => execute_statement(
+                                env,
+                                statement,
+                                first_repo,
+                                &mut gitql_object,
+                                &mut alias_table,
+                                &hidden_selections,
+                            )?,
+                        }
+
+                        check_selected_rows_memory(&gitql_object)?;
+                        if !select_statement.table_name.is_empty() {
+                            let rows_count = main_group_rows_count(&gitql_object);
+                            stats.record_scan(&select_statement.table_name, rows_count, scan_start);
+                        }
 
                         // If the main group is empty, no need to perform other statements
                         if gitql_object.is_empty() || gitql_object.groups[0].is_empty() {
+                            gitql_object.remove_hidden_selections(&hidden_selections);
                             return Ok(EvaluationResult::SelectedGroups(
                                 gitql_object,
                                 hidden_selections,
                             ));
                         }
 
+                        if select_statement.is_distinct {
+                            apply_distinct_on_objects_group(&mut gitql_object, &hidden_selections);
+                        }
+
                         continue;
                     }
 
                     // If table name is not empty, must perform it on each repository
-                    for repo in repos {
-                        execute_statement(
+                    if threads > 1 && repos.len() > 1 {
+                        execute_select_statement_in_parallel(
                             env,
-                            statement,
-                            repo,
+                            select_statement,
+                            repos,
+                            threads,
                             &mut gitql_object,
                             &mut alias_table,
                             &hidden_selections,
-                        )?;
+                        )
+                        .map_err(runtime_exception)?;
+                        spill_selected_rows(env, &mut gitql_object, &mut spilled)
+                            .map_err(runtime_exception)?;
+                    } else {
+                        for repo in repos {
+                            execute_statement(
+                                env,
+                                statement,
+                                Some(repo),
+                                &mut gitql_object,
+                                &mut alias_table,
+                                &hidden_selections,
+                            )?;
+                            spill_selected_rows(env, &mut gitql_object, &mut spilled)
+                                .map_err(runtime_exception)?;
+                        }
                     }
 
+                    // Each repository scan has its own budget, so the rows of all of them are checked again
+                    check_selected_rows_memory(&gitql_object)?;
+                    let rows_count = main_group_rows_count(&gitql_object) + spilled.len();
+                    stats.record_scan(&select_statement.table_name, rows_count, scan_start);
+
                     // If the main group is empty, no need to perform other statements
-                    if gitql_object.is_empty() || gitql_object.groups[0].is_empty() {
+                    if spilled.is_empty()
+                        && (gitql_object.is_empty() || gitql_object.groups[0].is_empty())
+                    {
+                        gitql_object.remove_hidden_selections(&hidden_selections);
                         return Ok(EvaluationResult::SelectedGroups(
                             gitql_object,
                             hidden_selections,
@@ -114,11 +499,117 @@ pub fn evaluate_select_query(
 
                     // If Select statement has table name and distinct flag, keep only unique values
                     if !select_statement.table_name.is_empty() && select_statement.is_distinct {
-                        apply_distinct_on_objects_group(&mut gitql_object, &hidden_selections);
+                        if spilled.is_empty() {
+                            apply_distinct_on_objects_group(&mut gitql_object, &hidden_selections);
+                        } else {
+                            apply_distinct_on_spilled_rows(
+                                env,
+                                &mut gitql_object,
+                                &mut spilled,
+                                &hidden_selections,
+                            )
+                            .map_err(runtime_exception)?;
+                        }
+                    }
+                }
+                "aggregation" => {
+                    // Aggregations are already calculated while grouping the rows
+                    if is_aggregated_while_grouping {
+                        continue;
+                    }
+
+                    let aggregation_start = Instant::now();
+
+                    // Order sensitive aggregations use the rows order of the query `ORDER BY`
+                    if let Some(order_statement) = order_statement_for_aggregations(&statements_map)
+                    {
+                        execute_order_by_in_groups(env, order_statement, &mut gitql_object);
+                    }
+
+                    let statement = statements_map.get_mut(gql_command).unwrap();
+                    execute_statement(
+                        env,
+                        statement,
+                        first_repo,
+                        &mut gitql_object,
+                        &mut alias_table,
+                        &hidden_selections,
+                    )?;
+
+                    apply_rollup_placeholders(&mut gitql_object, &groups_rolled_up_fields);
+                    stats.grouping_time += aggregation_start.elapsed();
+                }
+                "group" => {
+                    let group_start = Instant::now();
+                    let group_statement = statements_map
+                        .get(gql_command)
+                        .unwrap()
+                        .as_any()
+                        .downcast_ref::<GroupByStatement>()
+                        .unwrap();
+
+                    if let Some(aggregations_statement) =
+                        group_by_aggregations_statement(&statements_map)
+                    {
+                        execute_group_by_with_aggregations(
+                            env,
+                            group_statement,
+                            aggregations_statement,
+                            &mut gitql_object,
+                            &alias_table,
+                        )
+                        .map_err(runtime_exception)?;
+                        is_aggregated_while_grouping = true;
+                        stats.grouping_time += group_start.elapsed();
+                        continue;
                     }
+
+                    // Rollup fields are set to NULL after calculating the aggregations on their values
+                    if group_statement.is_rollup && statements_map.contains_key("aggregation") {
+                        groups_rolled_up_fields =
+                            execute_group_by_grouping_sets(group_statement, &mut gitql_object)
+                                .map_err(runtime_exception)?;
+                        stats.grouping_time += group_start.elapsed();
+                        continue;
+                    }
+
+                    let statement = statements_map.get_mut(gql_command).unwrap();
+                    execute_statement(
+                        env,
+                        statement,
+                        first_repo,
+                        &mut gitql_object,
+                        &mut alias_table,
+                        &hidden_selections,
+                    )?;
+                    stats.grouping_time += group_start.elapsed();
                 }
                 _ => {
+                    let statement_start = Instant::now();
+
+                    if gql_command == "order" {
+                        // With absolute `LIMIT` only the rows before the limit and offset are sorted
+                        if let Some(count) = top_n_rows_count(&statements_map) {
+                            let order_statement = statements_map
+                                .get(gql_command)
+                                .unwrap()
+                                .as_any()
+                                .downcast_ref::<OrderByStatement>()
+                                .unwrap();
+                            execute_top_n_order_by_statement(
+                                env,
+                                order_statement,
+                                &mut gitql_object,
+                                count,
+                            )
+                            .map_err(runtime_exception)?;
+                            stats.sorting_time += statement_start.elapsed();
+                            continue;
+                        }
+                    }
+
                     // Any other statement can be performed on first or non repository
+                    let statement = statements_map.get_mut(gql_command).unwrap();
                     execute_statement(
                         env,
                         statement,
@@ -127,32 +618,32 @@ pub fn evaluate_select_query(
                         &mut alias_table,
                         &hidden_selections,
                     )?;
+
+                    match gql_command {
+                        "where" => stats
+                            .record_filter(main_group_rows_count(&gitql_object) + spilled.len()),
+                        "order" => stats.sorting_time += statement_start.elapsed(),
+                        _ => {}
+                    }
                 }
             }
         }
     }
 
-    // If there are many groups that mean group by is executed before.
-    // must merge each group into only one element
-    if gitql_object.len() > 1 {
-        for group in gitql_object.groups.iter_mut() {
-            if group.len() > 1 {
-                group.rows.drain(1..);
-            }
-        }
-    }
-    // If it a single group but it select only aggregations function,
-    // should return only first element in the group
-    else if gitql_object.len() == 1
-        && !query.has_group_by_statement
-        && query.has_aggregation_function
-    {
-        let group: &mut Group = &mut gitql_object.groups[0];
-        if group.len() > 1 {
-            group.rows.drain(1..);
-        }
+    // Rows that are still spilled after the last statement are loaded back to be returned
+    load_spilled_rows(&mut gitql_object, &mut spilled).map_err(runtime_exception)?;
+
+    if !is_groups_merged {
+        merge_aggregated_groups(
+            &mut gitql_object,
+            query.has_group_by_statement,
+            query.has_aggregation_function,
+        );
     }
 
+    // Hidden selections are used only while executing the statements, so they are not part of the result
+    gitql_object.remove_hidden_selections(&hidden_selections);
+
     // Return the groups and hidden selections to be used later in GUI or TUI ...etc
     Ok(EvaluationResult::SelectedGroups(
         gitql_object,
@@ -160,6 +651,124 @@ pub fn evaluate_select_query(
     ))
 }
 
+/// Returns the number of rows in the main group before grouping
+fn main_group_rows_count(gitql_object: &GitQLObject) -> usize {
+    gitql_object.groups.first().map_or(0, |group| group.len())
+}
+
+/// Abort the query if the selected rows exceed the engine memory limit
+fn check_selected_rows_memory(gitql_object: &GitQLObject) -> Result<(), Box<Diagnostic>> {
+    match gitql_object.groups.first() {
+        Some(group) => check_rows_memory(&group.rows).map_err(runtime_exception),
+        None => Ok(()),
+    }
+}
+
+/// Returns the `ORDER BY` statement if the query has aggregations that depend on the rows order
+/// without their own `ORDER BY` modifier
+fn order_statement_for_aggregations<'a>(
+    statements_map: &'a HashMap<&'static str, Box<dyn Statement>>,
+) -> Option<&'a OrderByStatement> {
+    let order_statement = statements_map
+        .get("order")?
+        .as_any()
+        .downcast_ref::<OrderByStatement>()?;
+
+    let aggregations_statement = statements_map
+        .get("aggregation")?
+        .as_any()
+        .downcast_ref::<AggregationsStatement>()?;
+
+    let has_order_sensitive_aggregation =
+        aggregations_statement
+            .aggregations
+            .values()
+            .any(|aggregation| match aggregation {
+                AggregateValue::Function(call) => {
+                    call.order_by.is_none() && is_order_sensitive_aggregation(&call.function_name)
+                }
+                AggregateValue::Expression(_) => false,
+            });
+
+    if has_order_sensitive_aggregation {
+        Some(order_statement)
+    } else {
+        None
+    }
+}
+
+/// Returns the aggregations statement if all of its aggregations can be calculated while grouping
+/// the rows, which requires that they don't depend on the order or on all values of the group
+fn group_by_aggregations_statement<'a>(
+    statements_map: &'a HashMap<&'static str, Box<dyn Statement>>,
+) -> Option<&'a AggregationsStatement> {
+    let aggregations_statement = statements_map
+        .get("aggregation")?
+        .as_any()
+        .downcast_ref::<AggregationsStatement>()?;
+
+    if order_statement_for_aggregations(statements_map).is_some() {
+        return None;
+    }
+
+    let has_modifiers = aggregations_statement
+        .aggregations
+        .values()
+        .any(|aggregation| match aggregation {
+            AggregateValue::Function(call) => {
+                call.is_distinct || call.order_by.is_some() || call.separator.is_some()
+            }
+            AggregateValue::Expression(_) => false,
+        });
+
+    if has_modifiers {
+        None
+    } else {
+        Some(aggregations_statement)
+    }
+}
+
+/// Returns the number of rows needed from the ordered rows if the query has absolute `LIMIT`,
+/// which is the limit count after the `OFFSET` rows
+fn top_n_rows_count(statements_map: &HashMap<&'static str, Box<dyn Statement>>) -> Option<usize> {
+    let limit_statement = statements_map
+        .get("limit")?
+        .as_any()
+        .downcast_ref::<LimitStatement>()?;
+
+    let count = match limit_statement.count {
+        LimitCount::Absolute(count) => count,
+        LimitCount::Ratio(_) => return None,
+    };
+
+    let offset = statements_map
+        .get("offset")
+        .and_then(|statement| statement.as_any().downcast_ref::<OffsetStatement>())
+        .map_or(0, |statement| statement.count);
+
+    Some(count.saturating_add(offset))
+}
+
+/// Merge each group into only one row if the query is grouped or select only aggregations,
+/// all rows in the same group have the same group keys and aggregations values
+fn merge_aggregated_groups(
+    gitql_object: &mut GitQLObject,
+    has_group_by_statement: bool,
+    has_aggregation_function: bool,
+) {
+    // If there are many groups that mean group by is executed before
+    let is_grouped = gitql_object.len() > 1 || has_group_by_statement;
+    if !is_grouped && !has_aggregation_function {
+        return;
+    }
+
+    for group in gitql_object.groups.iter_mut() {
+        if group.len() > 1 {
+            group.rows.drain(1..);
+        }
+    }
+}
+
 fn apply_distinct_on_objects_group(gitql_object: &mut GitQLObject, hidden_selections: &[String]) {
     if gitql_object.is_empty() {
         return;
@@ -204,66 +813,29 @@ fn apply_distinct_on_objects_group(gitql_object: &mut GitQLObject, hidden_select
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "git"))]
 mod tests {
     use super::*;
+    use crate::engine_test_repo::*;
     use gitql_ast::value::Value;
     use gitql_parser::{parser, tokenizer};
 
-    fn test_new_repo(path: String) -> Result<(), String> {
-        let mut repo = gix::init_bare(path).expect("failed to init bare");
-        let mut tree = gix::objs::Tree::empty();
-        let object = repo
-            .write_object(&tree)
-            .expect("failed to write object")
-            .detach();
-
-        let mut config = repo.config_snapshot_mut();
-        config
-            .set_raw_value("author", None, "name", "name")
-            .expect("failed to set name");
-        config
-            .set_raw_value("author", None, "email", "name@example.com")
-            .expect("failed to set email");
-
-        let repo = config
-            .commit_auto_rollback()
-            .expect("failed to commit auto rollback");
-        let commit = repo
-            .commit("HEAD", "initial commit", object, gix::commit::NO_PARENT_IDS)
-            .expect("failed to commit");
-
-        let blob = repo
-            .write_blob("hello world")
-            .expect("faile to write blob")
-            .into();
-        let entry = gix::objs::tree::Entry {
-            mode: gix::objs::tree::EntryKind::Blob.into(),
-            oid: blob,
-            filename: "hello.txt".into(),
-        };
-
-        tree.entries.push(entry);
-        let object = repo.write_object(&tree).expect("failed to write object");
-
-        let _ = repo
-            .commit("HEAD", "hello commit", object, [commit])
-            .expect("failed to commit");
-
-        Ok(())
-    }
-
-    fn test_delete_repo(path: String) -> Result<(), String> {
-        std::fs::remove_dir_all(path).expect("failed to remove dir");
-        Ok(())
-    }
-
     #[test]
     fn test_evaluate() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-evaluate";
@@ -273,7 +845,7 @@ mod tests {
         let repos = &vec![buf.ok().unwrap()];
 
         let query = "SELECT * FROM commits";
-        let result = tokenizer::tokenize(query.to_string());
+        let result = tokenizer::tokenize(query);
         let tokens = result.ok().unwrap();
         let result = parser::parse_gql(tokens, &mut env);
         let query = result.ok().unwrap();
@@ -285,7 +857,7 @@ mod tests {
         }
 
         let query = "SET @STRING = \"GitQL\"";
-        let result = tokenizer::tokenize(query.to_string());
+        let result = tokenizer::tokenize(query);
         let tokens = result.ok().unwrap();
         let result = parser::parse_gql(tokens, &mut env);
         let query = result.ok().unwrap();
@@ -299,12 +871,748 @@ mod tests {
         test_delete_repo(path.to_string()).expect("failed to delete repo");
     }
 
+    #[test]
+    fn test_evaluate_show_variables() {
+        let mut env = Environment::default();
+
+        for query in [
+            "SET @name TEXT = \"gql\"",
+            "SET @limit INT = 10",
+            "SHOW VARIABLES",
+        ] {
+            let tokens = tokenizer::tokenize(query).ok().unwrap();
+            let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+            let ret = evaluate(&mut env, &[], query);
+            if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+                assert_eq!(object.titles, vec!["name", "type", "value"]);
+
+                // Session variables are listed first with their default values
+                let rows = &object.groups[0].rows;
+                assert_eq!(rows.len(), 7);
+                assert!(rows[0].values[0].equals(&Value::Text("@@null_text".to_string())));
+                assert!(rows[0].values[2].equals(&Value::Text("Null".to_string())));
+                assert!(rows[5].values[0].equals(&Value::Text("@limit".to_string())));
+                assert!(rows[5].values[1].equals(&Value::Text("Integer".to_string())));
+                assert!(rows[5].values[2].equals(&Value::Integer(10)));
+                assert!(rows[6].values[0].equals(&Value::Text("@name".to_string())));
+                assert!(rows[6].values[2].equals(&Value::Text("gql".to_string())));
+            } else {
+                assert!(matches!(ret, Ok(EvaluationResult::SetGlobalVariable)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_explain() {
+        let mut env = Environment::default();
+        let query = "EXPLAIN SELECT * FROM (VALUES (1), (2), (3)) AS numbers(n) WHERE n > 1";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles, vec!["table", "estimated_rows", "expensive"]);
+            let values = &object.groups[0].rows[0].values;
+            assert!(values[0].equals(&Value::Text("numbers".to_string())));
+            assert!(values[1].equals(&Value::Integer(3)));
+            assert!(values[2].equals(&Value::Boolean(false)));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_session_variables() {
+        let mut env = Environment::default();
+
+        let tokens = tokenizer::tokenize("SET @@output_format = \"JSON\"")
+            .ok()
+            .unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        assert!(evaluate(&mut env, &[], query).is_ok());
+        assert!(env.globals["@@output_format"].equals(&Value::Text("JSON".to_string())));
+
+        // Invalid values are reported at runtime and the previous value is kept
+        let tokens = tokenizer::tokenize("SET @@output_format = \"xml\"")
+            .ok()
+            .unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        assert!(evaluate(&mut env, &[], query).is_err());
+        assert!(env.globals["@@output_format"].equals(&Value::Text("JSON".to_string())));
+
+        // `NULL` reset the session variable to the default value
+        let tokens = tokenizer::tokenize("SET @@output_format = NULL")
+            .ok()
+            .unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        assert!(evaluate(&mut env, &[], query).is_ok());
+        assert!(!env.globals.contains_key("@@output_format"));
+
+        let tokens = tokenizer::tokenize("SELECT @@output_format").ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Text("render".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_select_without_table() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let query = "SELECT 1 + 2, UPPER(\"gql\") AS name";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        // Expressions without table are evaluated without any repository
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles, vec!["1 + 2".to_string(), "name".to_string()]);
+            assert_eq!(object.groups[0].len(), 1);
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Integer(3)));
+            assert!(object.groups[0].rows[0].values[1].equals(&Value::Text("GQL".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_select_from_values_table() {
+        let mut env = Environment::default();
+
+        let query = "SELECT name FROM (VALUES (1, \"a\"), (2, \"b\"), (3, \"c\")) AS t(id, name) WHERE id > 1 ORDER BY id DESC";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Text("c".to_string())));
+            assert!(object.groups[0].rows[1].values[0].equals(&Value::Text("b".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_aggregation_over_derived_table() {
+        let mut env = Environment::default();
+
+        let query = "SELECT COUNT(n) FROM (SELECT name AS n FROM (VALUES (\"a\"), (\"b\")) AS t(name)) AS sub";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 1);
+            assert_eq!(object.groups[0].rows[0].values[0].as_int(), 2);
+        } else {
+            assert!(false);
+        }
+
+        let mut env = Environment::default();
+        let query = "SELECT COUNT(name) FROM (VALUES (\"a\"), (LOWER(\"B\")), (\"c\")) AS t(name)";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 1);
+            assert_eq!(object.groups[0].rows[0].values[0].as_int(), 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_aggregation_over_values_column() {
+        let mut env = Environment::default();
+
+        let query = "SELECT MAX(x) AS top FROM (VALUES (1), (3), (2)) AS t(x)";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles, vec!["top"]);
+            assert_eq!(object.groups[0].rows[0].values[0].as_int(), 3);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_with_stats() {
+        let mut env = Environment::default();
+
+        let query = "SELECT name FROM (VALUES (1, \"a\"), (2, \"b\"), (3, \"c\")) AS t(id, name) WHERE id > 1 ORDER BY id DESC";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let mut stats = QueryStats::default();
+        let ret = evaluate_with_stats(&mut env, &[], query, 1, &mut stats);
+        assert!(ret.is_ok());
+        assert_eq!(stats.tables.len(), 1);
+        assert_eq!(stats.tables[0].rows_scanned, 3);
+        assert_eq!(stats.tables[0].rows_after_filtering, 2);
+        assert!(stats.total_time >= stats.scan_time + stats.sorting_time);
+    }
+
+    #[test]
+    fn test_evaluate_order_by_with_limit_and_offset() {
+        let mut env = Environment::default();
+
+        let query = "SELECT name FROM (VALUES (1, \"a\"), (3, \"b\"), (2, \"c\"), (3, \"d\")) AS t(id, name) ORDER BY id DESC OFFSET 1 LIMIT 2";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Text("d".to_string())));
+            assert!(object.groups[0].rows[1].values[0].equals(&Value::Text("c".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_row_number() {
+        let mut env = Environment::default();
+
+        let query = "SELECT ROW_NUMBER() AS n, name FROM (VALUES (1, \"a\"), (3, \"b\"), (2, \"c\"), (4, \"d\")) AS t(id, name) ORDER BY id DESC OFFSET 1 LIMIT 2";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Integer(2)));
+            assert!(object.groups[0].rows[0].values[1].equals(&Value::Text("b".to_string())));
+            assert!(object.groups[0].rows[1].values[0].equals(&Value::Integer(3)));
+            assert!(object.groups[0].rows[1].values[1].equals(&Value::Text("c".to_string())));
+        } else {
+            assert!(false);
+        }
+
+        let query = "SELECT name, COUNT(id), ROW_NUMBER() FROM (VALUES (1, \"a\"), (2, \"b\"), (3, \"a\")) AS t(id, name) GROUP BY name";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[2].equals(&Value::Integer(1)));
+            assert!(object.groups[0].rows[1].values[2].equals(&Value::Integer(2)));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_sample() {
+        let mut env = Environment::default();
+
+        // `@@seed` is an integer session variable
+        let tokens = tokenizer::tokenize("SET @@seed = 3").ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, &[], query);
+        assert!(matches!(ret, Ok(EvaluationResult::SetGlobalVariable)));
+        assert!(env.globals["@@seed"].equals(&Value::Integer(3)));
+
+        let mut samples = vec![];
+        for _ in 0..2 {
+            let query =
+                "SELECT id FROM (VALUES (1), (2), (3), (4), (5), (6), (7), (8)) AS t(id) SAMPLE 3";
+            let tokens = tokenizer::tokenize(query).ok().unwrap();
+            let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+            let ret = evaluate(&mut env, &[], query);
+            if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+                let ids: Vec<i64> = object.groups[0]
+                    .rows
+                    .iter()
+                    .map(|row| row.values[0].as_int())
+                    .collect();
+                samples.push(ids);
+            } else {
+                assert!(false);
+            }
+        }
+
+        // The same seed selects the same rows, and the rows keep their order
+        assert_eq!(samples[0].len(), 3);
+        assert_eq!(samples[0], samples[1]);
+        assert!(samples[0].windows(2).all(|ids| ids[0] < ids[1]));
+
+        let query = "SELECT id FROM (VALUES (1), (2)) AS t(id) SAMPLE 50 PERCENT";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 1);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_group_by_with_aggregations() {
+        let mut env = Environment::default();
+
+        let query = "SELECT name, COUNT(id) AS n, MAX(id) AS max_id, SUM(id) + 1 AS total FROM (VALUES (1, \"a\"), (2, \"b\"), (3, \"a\"), (5, \"a\")) AS t(id, name) GROUP BY name ORDER BY n DESC";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+
+            let first_row = &object.groups[0].rows[0].values;
+            assert!(first_row[0].equals(&Value::Text("a".to_string())));
+            assert!(first_row[1].equals(&Value::Integer(3)));
+            assert!(first_row[2].equals(&Value::Integer(5)));
+            assert!(first_row[3].equals(&Value::Integer(10)));
+
+            let second_row = &object.groups[0].rows[1].values;
+            assert!(second_row[0].equals(&Value::Text("b".to_string())));
+            assert!(second_row[1].equals(&Value::Integer(1)));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_group_by_rollup() {
+        let mut env = Environment::default();
+
+        let values = "(VALUES (1, \"a\", 2020), (2, \"a\", 2021), (3, \"b\", 2020), (4, \"a\", 2020)) AS t(id, name, y)";
+        let query = format!(
+            "SELECT name, y, COUNT(id) AS n FROM {} GROUP BY ROLLUP(name, y)",
+            values
+        );
+        let tokens = tokenizer::tokenize(&query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            let rows: Vec<&[Value]> = object.rows().map(|row| row.values()).collect();
+            assert_eq!(rows.len(), 6);
+
+            // Detail rows then subtotal rows then the grand total row
+            assert!(rows[0][0].equals(&Value::Text("a".to_string())));
+            assert!(rows[0][1].equals(&Value::Integer(2020)));
+            assert!(rows[0][2].equals(&Value::Integer(2)));
+            assert!(rows[3][0].equals(&Value::Text("a".to_string())));
+            assert!(rows[3][1].equals(&Value::Null));
+            assert!(rows[3][2].equals(&Value::Integer(3)));
+            assert!(rows[4][0].equals(&Value::Text("b".to_string())));
+            assert!(rows[4][2].equals(&Value::Integer(1)));
+            assert!(rows[5][0].equals(&Value::Null));
+            assert!(rows[5][1].equals(&Value::Null));
+            assert!(rows[5][2].equals(&Value::Integer(4)));
+        } else {
+            assert!(false);
+        }
+
+        // Aggregations with modifiers are calculated after grouping with the rolled up values
+        let query = format!(
+            "SELECT name, COUNT(DISTINCT y) AS n FROM {} GROUP BY ROLLUP(name)",
+            values
+        );
+        let tokens = tokenizer::tokenize(&query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            let rows: Vec<&[Value]> = object.rows().map(|row| row.values()).collect();
+            assert_eq!(rows.len(), 3);
+            let total_row = rows[2];
+            assert!(total_row[0].equals(&Value::Null));
+            assert!(total_row[1].equals(&Value::Integer(2)));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_reused_selected_expression() {
+        let mut env = Environment::default();
+
+        let query = "SELECT LOWER(name) AS n FROM (VALUES (\"B\"), (\"a\"), (\"C\")) AS t(name) WHERE LOWER(name) != \"c\" ORDER BY LOWER(name) DESC";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[0].equals(&Value::Text("b".to_string())));
+            assert!(object.groups[0].rows[1].values[0].equals(&Value::Text("a".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_reused_selected_expression_from_commits() {
+        let path = "test-evaluate-reused-selected-expression-from-commits";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+        let repos = &[gix::open(path).ok().unwrap()];
+
+        // The columns of the reused expression are hidden selections of the commits table
+        let queries = [
+            "SELECT LOWER(name), COUNT(name) FROM commits GROUP BY LOWER(name)",
+            "SELECT LOWER(name) AS l, COUNT(name) FROM commits WHERE LOWER(name) = \"name\" GROUP BY l",
+            "SELECT LOWER(name) AS l, COUNT(name) FROM commits GROUP BY l",
+        ];
+
+        let mut results = vec![];
+        for query in queries {
+            let mut env = Environment::default();
+            let tokens = tokenizer::tokenize(query).ok().unwrap();
+            let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+            results.push(evaluate(&mut env, repos, query));
+        }
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        for ret in results {
+            if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+                assert_eq!(object.titles.len(), 2);
+                assert_eq!(object.groups[0].len(), 1);
+                let row = &object.groups[0].rows[0].values;
+                assert!(row[0].equals(&Value::Text("name".to_string())));
+                assert!(row[1].equals(&Value::Integer(2)));
+            } else {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_select_from_subquery() {
+        let mut env = Environment::default();
+
+        let path = "test-evaluate-select-from-subquery";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+        let repos = &[gix::open(path).ok().unwrap()];
+
+        let query = "SELECT sub.t FROM (SELECT title AS t FROM commits c) AS sub WHERE t != \"\"";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, repos, query);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles[0], "t");
+            assert_eq!(object.groups[0].len(), 2);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_create_temp_table() {
+        let mut env = Environment::default();
+
+        let path = "test-evaluate-create-temp-table";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+        let repos = &[gix::open(path).ok().unwrap()];
+
+        let query = "CREATE TEMP TABLE hot AS SELECT title, name FROM commits ORDER BY datetime";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, repos, query);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        assert!(matches!(ret, Ok(EvaluationResult::CreateTempTable)));
+        env.clear_session();
+
+        // Hidden selections are not stored in the temporary table
+        assert_eq!(env.temp_tables["hot"].titles, vec!["title", "name"]);
+
+        // Temporary table is selected without the repositories
+        let query = "SELECT title FROM hot WHERE title = \"hello commit\"";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles, vec!["title"]);
+            assert_eq!(object.groups[0].len(), 1);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_external_table() {
+        let mut env = Environment::default();
+        let path = std::env::temp_dir().join(format!("gitql-external-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, "name,team\nJane,core\nJohn,docs\nAmr,core\n").unwrap();
+
+        let query = format!(
+            "SELECT team, count(name) AS members FROM EXTERNAL(\"{}\") GROUP BY team ORDER BY team",
+            path
+        );
+        let tokens = tokenizer::tokenize(&query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(mut object, _)) = ret {
+            object.flat();
+            assert_eq!(object.titles, vec!["team", "members"]);
+            assert_eq!(object.groups[0].len(), 2);
+            assert_eq!(object.groups[0].rows[0].values[0].to_string(), "core");
+            assert_eq!(object.groups[0].rows[0].values[1].as_int(), 2);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_insert() {
+        let mut env = Environment::default();
+        let path = std::env::temp_dir().join(format!("gitql-insert-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        let queries = [
+            "CREATE TEMP TABLE t AS SELECT id, name FROM (VALUES (1, \"a\")) AS v(id, name)"
+                .to_string(),
+            "INSERT INTO t SELECT id, name FROM (VALUES (2, \"b\"), (3, \"c\")) AS v(id, name)"
+                .to_string(),
+            format!(
+                "INSERT INTO FILE \"{}\" SELECT name FROM t WHERE id > 1",
+                path
+            ),
+        ];
+
+        let mut results = vec![];
+        for query in queries {
+            let tokens = tokenizer::tokenize(&query).ok().unwrap();
+            let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+            results.push(evaluate(&mut env, &[], query));
+            env.clear_session();
+        }
+
+        let content = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(results[1], Ok(EvaluationResult::InsertedRows(2))));
+        assert!(matches!(results[2], Ok(EvaluationResult::InsertedRows(2))));
+        assert_eq!(env.temp_tables["t"].groups[0].len(), 3);
+        assert_eq!(
+            content.ok().unwrap(),
+            "{\"name\":\"b\"}\n{\"name\":\"c\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_in_subquery() {
+        let mut env = Environment::default();
+
+        let query = "SELECT 1 IN (SELECT 1), 2 IN (SELECT 1), 2 NOT IN (SELECT 1)";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+
+        let ret = evaluate(&mut env, &[], query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            let values = &object.groups[0].rows[0].values;
+            assert!(values[0].equals(&Value::Boolean(true)));
+            assert!(values[1].equals(&Value::Boolean(false)));
+            assert!(values[2].equals(&Value::Boolean(true)));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_in_table_column() {
+        let mut env = Environment::default();
+
+        let path = "test-evaluate-in-table-column";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+        let repos = &[gix::open(path).ok().unwrap()];
+
+        let query = "SELECT title FROM commits WHERE title IN (SELECT title FROM commits)";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, repos, query);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.groups[0].len(), 2);
+        } else {
+            test_delete_repo(path.to_string()).expect("failed to delete repo");
+            assert!(false);
+        }
+
+        let query = "SELECT title FROM commits WHERE title NOT IN commits.title";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let ret = evaluate(&mut env, repos, query);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert!(object.is_empty() || object.groups[0].is_empty());
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_with_threads() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let path = "test-evaluate-with-threads";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let repos = &[gix::open(path).ok().unwrap(), gix::open(path).ok().unwrap()];
+
+        let query = "SELECT commit_id FROM commits";
+        let result = tokenizer::tokenize(query);
+        let tokens = result.ok().unwrap();
+        let result = parser::parse_gql(tokens, &mut env);
+        let query = result.ok().unwrap();
+
+        let ret = evaluate_with_threads(&mut env, repos, query, 2);
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.titles.len(), 1);
+            assert_eq!(object.groups[0].len(), 4);
+        } else {
+            test_delete_repo(path.to_string()).expect("failed to delete repo");
+            assert!(false);
+        }
+
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+    }
+
+    #[test]
+    fn test_evaluate_group_by_repo() {
+        let mut env = Environment::default();
+
+        let first_path = "test-evaluate-group-by-repo-first";
+        let second_path = "test-evaluate-group-by-repo-second";
+        test_new_repo(first_path.to_string()).expect("failed to new repo");
+        test_new_repo(second_path.to_string()).expect("failed to new repo");
+
+        let repos = &[
+            gix::open(first_path).ok().unwrap(),
+            gix::open(second_path).ok().unwrap(),
+        ];
+
+        let query = "SELECT repo, COUNT(commit_id) AS commit_num FROM commits GROUP BY repo";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let grouped = evaluate_with_threads(&mut env, repos, query, 2);
+
+        let query = "SELECT commit_id FROM commits WHERE repo LIKE \"%second%\"";
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        let query = parser::parse_gql(tokens, &mut env).ok().unwrap();
+        let filtered = evaluate(&mut env, repos, query);
+
+        test_delete_repo(first_path.to_string()).expect("failed to delete repo");
+        test_delete_repo(second_path.to_string()).expect("failed to delete repo");
+
+        if let Ok(EvaluationResult::SelectedGroups(mut object, _)) = grouped {
+            object.flat();
+            assert_eq!(object.groups[0].len(), 2);
+            for row in &object.groups[0].rows {
+                assert!(row.values[1].equals(&Value::Integer(2)));
+            }
+        } else {
+            assert!(false);
+        }
+
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = filtered {
+            assert_eq!(object.groups[0].len(), 2);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_order_by_aggregated_query() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let path = "test-evaluate-order-by-aggregated-query";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let repos = &[gix::open(path).ok().unwrap(), gix::open(path).ok().unwrap()];
+
+        let query =
+            "SELECT title, COUNT(title) AS commit_num FROM commits GROUP BY title ORDER BY commit_num";
+        let result = tokenizer::tokenize(query);
+        let tokens = result.ok().unwrap();
+        let result = parser::parse_gql(tokens, &mut env);
+        let query = result.ok().unwrap();
+
+        let ret = evaluate(&mut env, repos, query);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        if let Ok(EvaluationResult::SelectedGroups(object, _)) = ret {
+            assert_eq!(object.len(), 1);
+            assert_eq!(object.groups[0].len(), 2);
+            assert!(object.groups[0].rows[0].values[1].equals(&Value::Integer(2)));
+        } else {
+            assert!(false);
+        }
+    }
+
     #[test]
     fn test_evaluate_select_query() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-evaluate-select-query";
@@ -314,14 +1622,14 @@ mod tests {
         let repos = &vec![buf.ok().unwrap()];
 
         let query = "SELECT * FROM commits";
-        let result = tokenizer::tokenize(query.to_string());
+        let result = tokenizer::tokenize(query);
         let tokens = result.ok().unwrap();
         let result = parser::parse_gql(tokens, &mut env);
         let query = result.ok().unwrap();
 
         match query {
             Query::Select(q) => {
-                let ret = evaluate_select_query(&mut env, &repos, q);
+                let ret = evaluate_select_query(&mut env, repos, q, 1);
                 if ret.is_err() {
                     test_delete_repo(path.to_string()).expect("failed to delete repo");
                     assert!(false);
@@ -0,0 +1,107 @@
+use gitql_ast::format::value_to_truncated_text;
+use gitql_ast::object::Row;
+use gitql_ast::session::SessionOptions;
+use gitql_parser::diagnostic::Diagnostic;
+
+/// Fields that identify the row in the runtime error notes if they are selected
+const ROW_KEY_FIELDS: [&str; 3] = ["commit_id", "name", "path"];
+
+/// Maximum number of characters for each value in the row context
+const ROW_VALUE_MAX_CHARS: usize = 40;
+
+/// Create exception diagnostic from runtime error message without row context
+pub fn runtime_exception(message: String) -> Box<Diagnostic> {
+    Diagnostic::exception(&message).as_boxed()
+}
+
+/// Create exception diagnostic for error raised while evaluating statement condition on row,
+/// including the condition location in the query and the key values of the offending row
+pub fn row_evaluation_exception(
+    message: &str,
+    statement_name: &str,
+    location: Option<(usize, usize)>,
+    titles: &[String],
+    row: &Row,
+    options: &SessionOptions,
+) -> Box<Diagnostic> {
+    let mut diagnostic = Diagnostic::exception(message)
+        .add_note(&format!(
+            "Error raised while evaluating `{}` condition",
+            statement_name
+        ))
+        .add_note(&format!(
+            "Offending row: {}",
+            row_context(titles, row, options)
+        ));
+
+    if let Some((start, end)) = location {
+        diagnostic = diagnostic.with_location_span(start, end);
+    }
+
+    diagnostic.as_boxed()
+}
+
+/// Format the key fields of the row, or the first field if no key field is selected
+fn row_context(titles: &[String], row: &Row, options: &SessionOptions) -> String {
+    let mut indexes: Vec<usize> = titles
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| ROW_KEY_FIELDS.contains(&title.as_str()))
+        .map(|(index, _)| index)
+        .collect();
+
+    if indexes.is_empty() && !titles.is_empty() {
+        indexes.push(0);
+    }
+
+    let pairs: Vec<String> = indexes
+        .iter()
+        .filter(|index| **index < row.values.len())
+        .map(|index| {
+            let value = value_to_truncated_text(&row.values[*index], ROW_VALUE_MAX_CHARS, options);
+            format!("{} = {}", titles[*index], value)
+        })
+        .collect();
+
+    pairs.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::value::Value;
+
+    #[test]
+    fn test_row_evaluation_exception() {
+        let titles = vec!["title".to_string(), "commit_id".to_string()];
+        let row = Row {
+            values: vec![
+                Value::Text("Fix parser".to_string()),
+                Value::Text("abc".to_string()),
+            ],
+        };
+
+        let options = SessionOptions::default();
+        let diagnostic = row_evaluation_exception(
+            "Invalid regex",
+            "WHERE",
+            Some((6, 12)),
+            &titles,
+            &row,
+            &options,
+        );
+        assert_eq!(diagnostic.label(), "Exception");
+        assert_eq!(diagnostic.location(), Some((6, 12)));
+        assert_eq!(diagnostic.notes()[1], "Offending row: commit_id = abc");
+    }
+
+    #[test]
+    fn test_row_context_without_key_fields() {
+        let titles = vec!["title".to_string()];
+        let row = Row {
+            values: vec![Value::Text("Fix parser".to_string())],
+        };
+        let options = SessionOptions::default();
+        assert_eq!(row_context(&titles, &row, &options), "title = Fix parser");
+    }
+}
@@ -0,0 +1,200 @@
+use gitql_ast::environment::Environment;
+use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::SelectStatement;
+
+use crate::engine_repository::Repository;
+
+/// Tables that calculate diffs or read blobs content for each row are much slower to scan
+const DIFF_TABLES: [&str; 3] = ["diffs", "diff_files", "blobs"];
+
+/// Scanning more rows than this from a diff table is reported as expensive
+const EXPENSIVE_DIFF_ROWS_COUNT: usize = 50_000;
+
+/// Scanning more rows than this from any other table is reported as expensive
+const EXPENSIVE_ROWS_COUNT: usize = 1_000_000;
+
+/// Estimated number of rows the `FROM` table of the query produces before filtering
+pub struct ScanEstimate {
+    pub table_name: String,
+    pub rows_count: usize,
+}
+
+impl ScanEstimate {
+    /// Returns true if scanning the table is expected to take very long time or memory
+    pub fn is_expensive(&self) -> bool {
+        if DIFF_TABLES.contains(&self.table_name.as_str()) {
+            return self.rows_count > EXPENSIVE_DIFF_ROWS_COUNT;
+        }
+        self.rows_count > EXPENSIVE_ROWS_COUNT
+    }
+}
+
+/// Estimate the number of rows selected from the `FROM` table of the query in all repositories,
+/// the rows are counted without building them, returns None if the table can't be estimated
+pub fn estimate_query_rows(
+    env: &Environment,
+    repos: &[Repository],
+    query: &GQLQuery,
+) -> Option<ScanEstimate> {
+    let statement = query
+        .statements
+        .get("select")?
+        .as_any()
+        .downcast_ref::<SelectStatement>()?;
+
+    let table_name = statement.table_name.to_string();
+    if let Some(subquery) = &query.subquery {
+        let subquery_estimate = estimate_query_rows(env, repos, subquery)?;
+        return Some(ScanEstimate {
+            table_name,
+            rows_count: subquery_estimate.rows_count,
+        });
+    }
+
+    let rows_count = if table_name.is_empty() {
+        1
+    } else if let Some(values_table) = &statement.values_table {
+        values_table.rows.len()
+    } else if let Some(object) = env.temp_tables.get(&table_name) {
+        object.groups.first().map_or(0, |group| group.len())
+    } else {
+        let mut rows_count = 0;
+        for repo in repos {
+            rows_count += estimate_table_rows(repo, &table_name)?;
+        }
+        rows_count
+    };
+
+    Some(ScanEstimate {
+        table_name,
+        rows_count,
+    })
+}
+
+/// Estimate the number of rows of the repository table by counting the commits, references or
+/// files only, `diff_files` has at least one row for each commit so the commits count is used
+#[cfg(feature = "git")]
+pub fn estimate_table_rows(repo: &Repository, table_name: &str) -> Option<usize> {
+    match table_name {
+        "commits" | "diffs" | "diff_files" => count_commits(repo),
+        "refs" => {
+            let platform = repo.references().ok()?;
+            let references = platform.all().ok()?;
+            Some(references.flatten().count())
+        }
+        "branches" => {
+            let platform = repo.references().ok()?;
+            let local_branches = platform.local_branches().ok()?;
+            let remote_branches = platform.remote_branches().ok()?;
+            Some(local_branches.chain(remote_branches).flatten().count())
+        }
+        "tags" => {
+            let platform = repo.references().ok()?;
+            let tags = platform.tags().ok()?;
+            Some(tags.flatten().count())
+        }
+        "files" | "blobs" => count_head_files(repo),
+        "worktrees" => {
+            let linked_worktrees = repo.worktrees().ok()?;
+            Some(linked_worktrees.len() + 1)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "git"))]
+pub fn estimate_table_rows(repo: &Repository, _table_name: &str) -> Option<usize> {
+    match *repo {}
+}
+
+/// Count the commits reachable from the head without loading their diffs or signatures
+#[cfg(feature = "git")]
+fn count_commits(repo: &gix::Repository) -> Option<usize> {
+    let Ok(head_id) = repo.head_id() else {
+        return Some(0);
+    };
+
+    let revwalk = head_id.ancestors().all().ok()?;
+    Some(revwalk.flatten().count())
+}
+
+/// Count the files in the tree of the head commit
+#[cfg(feature = "git")]
+fn count_head_files(repo: &gix::Repository) -> Option<usize> {
+    let Ok(head_commit) = repo.head_commit() else {
+        return Some(0);
+    };
+
+    let head_tree = head_commit.tree().ok()?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    head_tree.traverse().breadthfirst(&mut recorder).ok()?;
+
+    let files_count = recorder
+        .records
+        .iter()
+        .filter(|entry| entry.mode.is_no_tree())
+        .count();
+    Some(files_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::statement::Query;
+    use gitql_parser::{parser, tokenizer};
+
+    fn parse_select_query(env: &mut Environment, query: &str) -> GQLQuery {
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        match parser::parse_gql(tokens, env) {
+            Ok(Query::Select(query)) => query,
+            _ => panic!("Expect select query"),
+        }
+    }
+
+    #[test]
+    fn test_estimate_query_rows() {
+        let mut env = Environment::default();
+
+        let query = parse_select_query(&mut env, "SELECT 1 + 1");
+        let estimate = estimate_query_rows(&env, &[], &query).unwrap();
+        assert_eq!(estimate.rows_count, 1);
+
+        let query = parse_select_query(
+            &mut env,
+            "SELECT id FROM (SELECT id FROM (VALUES (1), (2), (3)) AS t(id)) AS sub WHERE id > 1",
+        );
+        let estimate = estimate_query_rows(&env, &[], &query).unwrap();
+        assert_eq!(estimate.table_name, "sub");
+        assert_eq!(estimate.rows_count, 3);
+        assert!(!estimate.is_expensive());
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn test_estimate_table_rows() {
+        let path = "test-estimate-table-rows";
+        let repo = gix::init_bare(path).expect("failed to init bare");
+
+        assert_eq!(estimate_table_rows(&repo, "commits"), Some(0));
+        assert_eq!(estimate_table_rows(&repo, "files"), Some(0));
+        assert_eq!(estimate_table_rows(&repo, "tags"), Some(0));
+        assert_eq!(estimate_table_rows(&repo, "unknown"), None);
+
+        std::fs::remove_dir_all(path).expect("failed to remove dir");
+    }
+
+    #[test]
+    fn test_scan_estimate_is_expensive() {
+        let estimate = ScanEstimate {
+            table_name: "diff_files".to_string(),
+            rows_count: 100_000,
+        };
+        assert!(estimate.is_expensive());
+
+        let estimate = ScanEstimate {
+            table_name: "commits".to_string(),
+            rows_count: 100_000,
+        };
+        assert!(!estimate.is_expensive());
+    }
+}
@@ -17,6 +17,7 @@ use gitql_ast::expression::ExpressionKind::*;
 use gitql_ast::expression::GlobExpression;
 use gitql_ast::expression::GlobalVariableExpression;
 use gitql_ast::expression::InExpression;
+use gitql_ast::expression::InSubqueryExpression;
 use gitql_ast::expression::IsNullExpression;
 use gitql_ast::expression::LikeExpression;
 use gitql_ast::expression::LogicalExpression;
@@ -28,6 +29,8 @@ use gitql_ast::expression::StringExpression;
 use gitql_ast::expression::StringValueType;
 use gitql_ast::expression::SymbolExpression;
 use gitql_ast::function::FUNCTIONS;
+use gitql_ast::function::TIME_ZONE_FUNCTIONS;
+use gitql_ast::session::session_variable_default;
 use gitql_ast::value::Value;
 
 use regex::Regex;
@@ -154,6 +157,13 @@ pub fn evaluate_expression(
             let expr = expression.as_any().downcast_ref::<InExpression>().unwrap();
             evaluate_in(env, expr, titles, object)
         }
+        InSubquery => {
+            let expr = expression
+                .as_any()
+                .downcast_ref::<InSubqueryExpression>()
+                .unwrap();
+            evaluate_in_subquery(env, expr, titles, object)
+        }
         IsNull => {
             let expr = expression
                 .as_any()
@@ -198,7 +208,10 @@ fn evaluate_symbol(
 ) -> Result<Value, String> {
     for (index, title) in titles.iter().enumerate() {
         if expr.value.eq(title) {
-            return Ok(object[index].clone());
+            return object
+                .get(index)
+                .cloned()
+                .ok_or_else(|| format!("Column `{}` has no value in this row", &expr.value));
         }
     }
     Err(format!("Invalid column name `{}`", &expr.value))
@@ -213,6 +226,10 @@ fn evaluate_global_variable(
         return Ok(env.globals[name].clone());
     }
 
+    if let Some(default_value) = session_variable_default(name) {
+        return Ok(Value::Text(default_value.to_string()));
+    }
+
     Err(format!(
         "The value of `{}` may be not exists or calculated yet",
         name
@@ -234,6 +251,10 @@ fn evaluate_prefix_unary(
     object: &Vec<Value>,
 ) -> Result<Value, String> {
     let rhs = evaluate_expression(env, &expr.right, titles, object)?;
+    if rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
     match expr.op {
         PrefixUnaryOperator::Minus => {
             if rhs.data_type().is_int() {
@@ -255,6 +276,11 @@ fn evaluate_arithmetic(
     let lhs = evaluate_expression(env, &expr.left, titles, object)?;
     let rhs = evaluate_expression(env, &expr.right, titles, object)?;
 
+    // Arithmetic with NULL operand is NULL
+    if lhs.data_type().is_null() || rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
     match expr.operator {
         ArithmeticOperator::Plus => lhs.plus(&rhs),
         ArithmeticOperator::Minus => lhs.minus(&rhs),
@@ -305,6 +331,11 @@ fn evaluate_comparison(
         ));
     }
 
+    // Comparing NULL with any value is unknown, so the result is NULL
+    if left_type.is_null() || rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
     Ok(Value::Boolean(match expr.operator {
         ComparisonOperator::Greater => comparison_result.is_gt(),
         ComparisonOperator::GreaterEqual => comparison_result.is_ge(),
@@ -322,20 +353,27 @@ fn evaluate_like(
     titles: &[String],
     object: &Vec<Value>,
 ) -> Result<Value, String> {
-    let rhs = evaluate_expression(env, &expr.pattern, titles, object)?.as_text();
+    let rhs = evaluate_expression(env, &expr.pattern, titles, object)?;
+    let lhs = evaluate_expression(env, &expr.input, titles, object)?;
+    if lhs.data_type().is_null() || rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
     let pattern = &format!(
         "^{}$",
-        rhs.to_lowercase().replace('%', ".*").replace('_', ".")
+        rhs.as_text()
+            .to_lowercase()
+            .replace('%', ".*")
+            .replace('_', ".")
     );
     let regex_result = Regex::new(pattern);
     if regex_result.is_err() {
         return Err(regex_result.err().unwrap().to_string());
     }
     let regex = regex_result.ok().unwrap();
-    let lhs = evaluate_expression(env, &expr.input, titles, object)?
-        .as_text()
-        .to_lowercase();
-    Ok(Value::Boolean(regex.is_match(&lhs)))
+    Ok(Value::Boolean(
+        regex.is_match(&lhs.as_text().to_lowercase()),
+    ))
 }
 
 fn evaluate_glob(
@@ -344,18 +382,25 @@ fn evaluate_glob(
     titles: &[String],
     object: &Vec<Value>,
 ) -> Result<Value, String> {
-    let rhs = evaluate_expression(env, &expr.pattern, titles, object)?.as_text();
+    let rhs = evaluate_expression(env, &expr.pattern, titles, object)?;
+    let lhs = evaluate_expression(env, &expr.input, titles, object)?;
+    if lhs.data_type().is_null() || rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
     let pattern = &format!(
         "^{}$",
-        rhs.replace('.', "\\.").replace('*', ".*").replace('?', ".")
+        rhs.as_text()
+            .replace('.', "\\.")
+            .replace('*', ".*")
+            .replace('?', ".")
     );
     let regex_result = Regex::new(pattern);
     if regex_result.is_err() {
         return Err(regex_result.err().unwrap().to_string());
     }
     let regex = regex_result.ok().unwrap();
-    let lhs = evaluate_expression(env, &expr.input, titles, object)?.as_text();
-    Ok(Value::Boolean(regex.is_match(&lhs)))
+    Ok(Value::Boolean(regex.is_match(&lhs.as_text())))
 }
 
 fn evaluate_logical(
@@ -364,22 +409,38 @@ fn evaluate_logical(
     titles: &[String],
     object: &Vec<Value>,
 ) -> Result<Value, String> {
-    let lhs = evaluate_expression(env, &expr.left, titles, object)?.as_bool();
-    if expr.operator == LogicalOperator::And && !lhs {
+    // NULL is the unknown value of three-valued logic
+    let lhs = evaluate_expression(env, &expr.left, titles, object)?;
+    let lhs = (!lhs.data_type().is_null()).then(|| lhs.as_bool());
+    if expr.operator == LogicalOperator::And && lhs == Some(false) {
         return Ok(Value::Boolean(false));
     }
 
-    if expr.operator == LogicalOperator::Or && lhs {
+    if expr.operator == LogicalOperator::Or && lhs == Some(true) {
         return Ok(Value::Boolean(true));
     }
 
-    let rhs = evaluate_expression(env, &expr.right, titles, object)?.as_bool();
+    let rhs = evaluate_expression(env, &expr.right, titles, object)?;
+    let rhs = (!rhs.data_type().is_null()).then(|| rhs.as_bool());
+
+    let result = match expr.operator {
+        LogicalOperator::And => match (lhs, rhs) {
+            (_, Some(false)) => Some(false),
+            (Some(lhs), Some(rhs)) => Some(lhs && rhs),
+            _ => None,
+        },
+        LogicalOperator::Or => match (lhs, rhs) {
+            (_, Some(true)) => Some(true),
+            (Some(lhs), Some(rhs)) => Some(lhs || rhs),
+            _ => None,
+        },
+        LogicalOperator::Xor => match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Some(lhs ^ rhs),
+            _ => None,
+        },
+    };
 
-    Ok(Value::Boolean(match expr.operator {
-        LogicalOperator::And => lhs && rhs,
-        LogicalOperator::Or => lhs || rhs,
-        LogicalOperator::Xor => lhs ^ rhs,
-    }))
+    Ok(result.map(Value::Boolean).unwrap_or(Value::Null))
 }
 
 fn evaluate_bitwise(
@@ -388,8 +449,14 @@ fn evaluate_bitwise(
     titles: &[String],
     object: &Vec<Value>,
 ) -> Result<Value, String> {
-    let lhs = evaluate_expression(env, &expr.left, titles, object)?.as_int();
-    let rhs = evaluate_expression(env, &expr.right, titles, object)?.as_int();
+    let lhs = evaluate_expression(env, &expr.left, titles, object)?;
+    let rhs = evaluate_expression(env, &expr.right, titles, object)?;
+    if lhs.data_type().is_null() || rhs.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
+    let lhs = lhs.as_int();
+    let rhs = rhs.as_int();
 
     match expr.operator {
         BitwiseOperator::Or => Ok(Value::Integer(lhs | rhs)),
@@ -418,13 +485,17 @@ fn evaluate_call(
     object: &Vec<Value>,
 ) -> Result<Value, String> {
     let function_name = expr.function_name.as_str();
-    let function = FUNCTIONS.get(function_name).unwrap();
 
     let mut arguments = Vec::with_capacity(expr.arguments.len());
     for arg in expr.arguments.iter() {
         arguments.push(evaluate_expression(env, arg, titles, object)?);
     }
 
+    if let Some(function) = TIME_ZONE_FUNCTIONS.get(function_name) {
+        return Ok(function(&arguments, &env.session.time_zone));
+    }
+
+    let function = FUNCTIONS.get(function_name).unwrap();
     Ok(function(&arguments))
 }
 
@@ -435,11 +506,31 @@ fn evaluate_between(
     object: &Vec<Value>,
 ) -> Result<Value, String> {
     let value = evaluate_expression(env, &expr.value, titles, object)?;
-    let range_start = evaluate_expression(env, &expr.range_start, titles, object)?;
-    let range_end = evaluate_expression(env, &expr.range_end, titles, object)?;
-    Ok(Value::Boolean(
-        value.compare(&range_start).is_le() && value.compare(&range_end).is_ge(),
-    ))
+    let mut range_start = evaluate_expression(env, &expr.range_start, titles, object)?;
+    let mut range_end = evaluate_expression(env, &expr.range_end, titles, object)?;
+    if value.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
+    // Symmetric range accepts the bounds in any order so make sure start is the smaller one
+    if expr.is_symmetric
+        && !range_start.data_type().is_null()
+        && !range_end.data_type().is_null()
+        && range_start.compare(&range_end).is_lt()
+    {
+        std::mem::swap(&mut range_start, &mut range_end);
+    }
+
+    // Each bound compared with NULL is unknown, but the other bound can still make it false
+    let is_after_start =
+        (!range_start.data_type().is_null()).then(|| value.compare(&range_start).is_le());
+    let is_before_end =
+        (!range_end.data_type().is_null()).then(|| value.compare(&range_end).is_ge());
+    Ok(match (is_after_start, is_before_end) {
+        (Some(false), _) | (_, Some(false)) => Value::Boolean(expr.has_not_keyword),
+        (Some(true), Some(true)) => Value::Boolean(!expr.has_not_keyword),
+        _ => Value::Null,
+    })
 }
 
 fn evaluate_case(
@@ -472,13 +563,59 @@ fn evaluate_in(
 ) -> Result<Value, String> {
     let argument = evaluate_expression(env, &expr.argument, titles, object)?;
 
+    // Comparing NULL with any value is unknown, so the result is NULL
+    if argument.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
+    let mut has_null_value = false;
     for value_expr in &expr.values {
         let value = evaluate_expression(env, value_expr, titles, object)?;
+        if value.data_type().is_null() {
+            has_null_value = true;
+            continue;
+        }
+
         if argument.equals(&value) {
             return Ok(Value::Boolean(!expr.has_not_keyword));
         }
     }
 
+    // If no value matched but the list contains NULL, the argument may be equal to it so the result is unknown
+    if has_null_value {
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Boolean(expr.has_not_keyword))
+}
+
+fn evaluate_in_subquery(
+    env: &mut Environment,
+    expr: &InSubqueryExpression,
+    titles: &[String],
+    object: &Vec<Value>,
+) -> Result<Value, String> {
+    let argument = evaluate_expression(env, &expr.argument, titles, object)?;
+
+    // Comparing NULL with any value is unknown, so the result is NULL
+    if argument.data_type().is_null() {
+        return Ok(Value::Null);
+    }
+
+    let values = match env.subqueries_values.get(expr.subquery_index) {
+        Some(values) => values,
+        None => return Err("Values of `IN` subquery are not evaluated".to_string()),
+    };
+
+    if values.contains(&argument.hash_key()) {
+        return Ok(Value::Boolean(!expr.has_not_keyword));
+    }
+
+    // If no value matched but the subquery has NULL, the argument may be equal to it so the result is unknown
+    if values.contains(&Value::Null.hash_key()) {
+        return Ok(Value::Null);
+    }
+
     Ok(Value::Boolean(expr.has_not_keyword))
 }
 
@@ -500,6 +637,7 @@ fn evaluate_is_null(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gitql_ast::date_utils::parse_time_zone;
     use gitql_ast::expression::NullExpression;
     use gitql_ast::types::DataType;
 
@@ -509,6 +647,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression: Box<dyn Expression> = Box::new(AssignmentExpression {
@@ -545,6 +693,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = AssignmentExpression {
@@ -660,6 +818,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         env.globals
@@ -723,6 +891,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = PrefixUnary {
@@ -775,6 +953,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = ArithmeticExpression {
@@ -872,6 +1060,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = ComparisonExpression {
@@ -1065,6 +1263,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = LikeExpression {
@@ -1113,6 +1321,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = GlobExpression {
@@ -1161,6 +1379,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = LogicalExpression {
@@ -1206,12 +1434,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_three_valued_logic() {
+        let mut env = Environment::default();
+        let titles = vec!["title".to_string()];
+        let object = vec![Value::Text("object".to_string())];
+
+        let boolean = |value: Option<bool>| -> Box<dyn Expression> {
+            match value {
+                Some(is_true) => Box::new(BooleanExpression { is_true }),
+                None => Box::new(NullExpression {}),
+            }
+        };
+
+        let cases = [
+            (Some(false), LogicalOperator::And, None, Some(false)),
+            (None, LogicalOperator::And, Some(false), Some(false)),
+            (Some(true), LogicalOperator::And, None, None),
+            (Some(true), LogicalOperator::Or, None, Some(true)),
+            (None, LogicalOperator::Or, Some(true), Some(true)),
+            (Some(false), LogicalOperator::Or, None, None),
+            (Some(true), LogicalOperator::Xor, None, None),
+        ];
+
+        for (lhs, operator, rhs, expected) in cases {
+            let expression = LogicalExpression {
+                left: boolean(lhs),
+                operator,
+                right: boolean(rhs),
+            };
+
+            let ret = evaluate_logical(&mut env, &expression, &titles, &object)
+                .ok()
+                .unwrap();
+            match expected {
+                Some(expected) => assert!(ret.equals(&Value::Boolean(expected))),
+                None => assert!(ret.data_type().is_null()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_operators_with_null() {
+        let mut env = Environment::default();
+        let titles = vec!["title".to_string()];
+        let object = vec![Value::Text("object".to_string())];
+
+        let expression = ArithmeticExpression {
+            left: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            operator: ArithmeticOperator::Plus,
+            right: Box::new(NullExpression {}),
+        };
+        let ret = evaluate_arithmetic(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().data_type().is_null());
+
+        let expression = ComparisonExpression {
+            left: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            operator: ComparisonOperator::Equal,
+            right: Box::new(NullExpression {}),
+        };
+        let ret = evaluate_comparison(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().data_type().is_null());
+
+        let expression = PrefixUnary {
+            right: Box::new(NullExpression {}),
+            op: PrefixUnaryOperator::Bang,
+        };
+        let ret = evaluate_prefix_unary(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().data_type().is_null());
+    }
+
     #[test]
     fn test_evaluate_bitwise() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = BitwiseExpression {
@@ -1292,6 +1604,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = CallExpression {
@@ -1314,12 +1636,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_call_with_session_time_zone() {
+        let mut env = Environment::default();
+        let expression = CallExpression {
+            function_name: "date_trunc".to_string(),
+            arguments: vec![
+                Box::new(StringExpression {
+                    value: "day".to_string(),
+                    value_type: StringValueType::Text,
+                }),
+                Box::new(SymbolExpression {
+                    value: "time".to_string(),
+                }),
+            ],
+            is_aggregation: false,
+        };
+
+        let titles = vec!["time".to_string()];
+        let object = vec![Value::DateTime(3600)];
+
+        let ret = evaluate_call(&mut env, &expression, &titles, &object);
+        assert_eq!(ret.ok().unwrap().as_date_time(), 0);
+
+        // Day starts two hours before UTC midnight in the session time zone
+        env.session.time_zone = parse_time_zone("+02:00").unwrap();
+        let ret = evaluate_call(&mut env, &expression, &titles, &object);
+        assert_eq!(ret.ok().unwrap().as_date_time(), -7200);
+
+        let expression = CallExpression {
+            function_name: "hour".to_string(),
+            arguments: vec![Box::new(SymbolExpression {
+                value: "time".to_string(),
+            })],
+            is_aggregation: false,
+        };
+        let ret = evaluate_call(&mut env, &expression, &titles, &object);
+        assert_eq!(ret.ok().unwrap().as_int(), 3);
+    }
+
     #[test]
     fn test_evaluate_between() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = BetweenExpression {
@@ -1332,6 +1703,8 @@ mod tests {
             range_end: Box::new(NumberExpression {
                 value: Value::Integer(3),
             }),
+            is_symmetric: false,
+            has_not_keyword: false,
         };
 
         let titles = vec!["title".to_string()];
@@ -1354,6 +1727,8 @@ mod tests {
             range_end: Box::new(NumberExpression {
                 value: Value::Integer(3),
             }),
+            is_symmetric: false,
+            has_not_keyword: false,
         };
 
         let ret = evaluate_between(&mut env, &expression, &titles, &object);
@@ -1373,6 +1748,8 @@ mod tests {
             range_end: Box::new(NumberExpression {
                 value: Value::Integer(3),
             }),
+            is_symmetric: false,
+            has_not_keyword: false,
         };
 
         let ret = evaluate_between(&mut env, &expression, &titles, &object);
@@ -1392,6 +1769,8 @@ mod tests {
             range_end: Box::new(NumberExpression {
                 value: Value::Integer(3),
             }),
+            is_symmetric: false,
+            has_not_keyword: false,
         };
 
         let ret = evaluate_between(&mut env, &expression, &titles, &object);
@@ -1402,12 +1781,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_symmetric_and_not_between() {
+        let mut env = Environment::default();
+        let titles = vec!["title".to_string()];
+        let object = vec![Value::Text("object".to_string())];
+
+        // 2 BETWEEN SYMMETRIC 3 AND 1
+        let expression = BetweenExpression {
+            value: Box::new(NumberExpression {
+                value: Value::Integer(2),
+            }),
+            range_start: Box::new(NumberExpression {
+                value: Value::Integer(3),
+            }),
+            range_end: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            is_symmetric: true,
+            has_not_keyword: false,
+        };
+
+        let ret = evaluate_between(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().as_bool());
+
+        // 2 BETWEEN 3 AND 1
+        let expression = BetweenExpression {
+            value: Box::new(NumberExpression {
+                value: Value::Integer(2),
+            }),
+            range_start: Box::new(NumberExpression {
+                value: Value::Integer(3),
+            }),
+            range_end: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            is_symmetric: false,
+            has_not_keyword: false,
+        };
+
+        let ret = evaluate_between(&mut env, &expression, &titles, &object);
+        assert!(!ret.ok().unwrap().as_bool());
+
+        // 4 NOT BETWEEN 1 AND 3
+        let expression = BetweenExpression {
+            value: Box::new(NumberExpression {
+                value: Value::Integer(4),
+            }),
+            range_start: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            range_end: Box::new(NumberExpression {
+                value: Value::Integer(3),
+            }),
+            is_symmetric: false,
+            has_not_keyword: true,
+        };
+
+        let ret = evaluate_between(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().as_bool());
+
+        // 4 NOT BETWEEN 1 AND NULL
+        let expression = BetweenExpression {
+            value: Box::new(NumberExpression {
+                value: Value::Integer(4),
+            }),
+            range_start: Box::new(NumberExpression {
+                value: Value::Integer(1),
+            }),
+            range_end: Box::new(NullExpression {}),
+            is_symmetric: false,
+            has_not_keyword: true,
+        };
+
+        let ret = evaluate_between(&mut env, &expression, &titles, &object);
+        assert!(ret.ok().unwrap().data_type().is_null());
+    }
+
     #[test]
     fn test_evaluate_case() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = CaseExpression {
@@ -1455,6 +1921,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = InExpression {
@@ -1487,12 +1963,101 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_in_with_null_values() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let titles = vec!["title".to_string()];
+        let object = vec![Value::Text("object".to_string())];
+
+        // `"One" IN ("One", NULL)` is true because the argument is matched
+        let expression = InExpression {
+            argument: Box::new(StringExpression {
+                value: "One".to_string(),
+                value_type: StringValueType::Text,
+            }),
+            values: vec![
+                Box::new(StringExpression {
+                    value: "One".to_string(),
+                    value_type: StringValueType::Text,
+                }),
+                Box::new(NullExpression {}),
+            ],
+            values_type: DataType::Text,
+            has_not_keyword: false,
+        };
+
+        let ret = evaluate_in(&mut env, &expression, &titles, &object);
+        assert!(ret.is_ok());
+        assert!(ret.ok().unwrap().as_bool());
+
+        // `"Three" NOT IN ("One", NULL)` is unknown because the argument may be equal to NULL
+        let expression = InExpression {
+            argument: Box::new(StringExpression {
+                value: "Three".to_string(),
+                value_type: StringValueType::Text,
+            }),
+            values: vec![
+                Box::new(StringExpression {
+                    value: "One".to_string(),
+                    value_type: StringValueType::Text,
+                }),
+                Box::new(NullExpression {}),
+            ],
+            values_type: DataType::Text,
+            has_not_keyword: true,
+        };
+
+        let ret = evaluate_in(&mut env, &expression, &titles, &object);
+        assert!(ret.is_ok());
+        assert!(ret.ok().unwrap().data_type().is_null());
+
+        // `NULL IN ("One")` is unknown
+        let expression = InExpression {
+            argument: Box::new(NullExpression {}),
+            values: vec![Box::new(StringExpression {
+                value: "One".to_string(),
+                value_type: StringValueType::Text,
+            })],
+            values_type: DataType::Text,
+            has_not_keyword: false,
+        };
+
+        let ret = evaluate_in(&mut env, &expression, &titles, &object);
+        assert!(ret.is_ok());
+        assert!(ret.ok().unwrap().data_type().is_null());
+    }
+
     #[test]
     fn test_evaluate_is_null() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let expression = IsNullExpression {
@@ -1,40 +1,67 @@
+use std::any::Any;
 use std::cmp;
 use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::sync::Arc;
 
-use gitql_ast::aggregation::AGGREGATIONS;
+use gitql_ast::aggregation::aggregation_accumulator;
+use gitql_ast::aggregation::execute_aggregate_call;
 use gitql_ast::environment::Environment;
 use gitql_ast::object::GitQLObject;
 use gitql_ast::object::Group;
 use gitql_ast::object::Row;
+use gitql_ast::session::apply_session_variables;
+use gitql_ast::session::check_session_variable_value;
+use gitql_ast::session::is_session_variable;
+use gitql_ast::session::SEED_VARIABLE;
 use gitql_ast::statement::AggregateValue;
 use gitql_ast::statement::AggregationsStatement;
 use gitql_ast::statement::GlobalVariableStatement;
 use gitql_ast::statement::GroupByStatement;
 use gitql_ast::statement::HavingStatement;
+use gitql_ast::statement::LimitCount;
 use gitql_ast::statement::LimitStatement;
 use gitql_ast::statement::OffsetStatement;
 use gitql_ast::statement::OrderByStatement;
+use gitql_ast::statement::RowNumberStatement;
+use gitql_ast::statement::SampleStatement;
 use gitql_ast::statement::SelectStatement;
 use gitql_ast::statement::SortingOrder;
 use gitql_ast::statement::Statement;
 use gitql_ast::statement::StatementKind::*;
 use gitql_ast::statement::WhereStatement;
 use gitql_ast::value::Value;
+use gitql_parser::diagnostic::Diagnostic;
 
+use crate::engine_diagnostic::row_evaluation_exception;
+use crate::engine_diagnostic::runtime_exception;
 use crate::engine_evaluator::evaluate_expression;
-use crate::engine_function::get_column_name;
+#[cfg(feature = "git")]
 use crate::engine_function::select_gql_objects;
+#[cfg(feature = "git")]
+use crate::engine_function::select_gql_objects_with_cache;
+use crate::engine_provider::data_provider;
+use crate::engine_provider::select_provided_rows;
+use crate::engine_provider::DataProvider;
+use crate::engine_repository::Repository;
+use crate::engine_rows::get_column_name;
+use crate::engine_rows::select_derived_table_rows;
+use crate::engine_rows::select_values;
+use crate::engine_rows::select_values_table;
 
 #[allow(clippy::borrowed_box)]
 pub fn execute_statement(
     env: &mut Environment,
     statement: &Box<dyn Statement>,
-    repo: &gix::Repository,
+    repo: Option<&Repository>,
     gitql_object: &mut GitQLObject,
     alias_table: &mut HashMap<String, String>,
     hidden_selection: &Vec<String>,
-) -> Result<(), String> {
+) -> Result<(), Box<Diagnostic>> {
     match statement.kind() {
         Select => {
             let statement = statement
@@ -47,7 +74,40 @@ pub fn execute_statement(
                 alias_table.insert(alias.0.to_string(), alias.1.to_string());
             }
 
-            execute_select_statement(env, statement, repo, gitql_object, hidden_selection)
+            // Select without table or from `VALUES` table doesn't need a repository
+            if statement.table_name.is_empty() || statement.values_table.is_some() {
+                return execute_values_select_statement(
+                    env,
+                    statement,
+                    gitql_object,
+                    hidden_selection,
+                )
+                .map_err(runtime_exception);
+            }
+
+            // Data provider selects the table rows once without any repository
+            if let Some(provider) = data_provider(env, &statement.table_name) {
+                return execute_provided_select_statement(
+                    env,
+                    statement,
+                    provider,
+                    gitql_object,
+                    hidden_selection,
+                )
+                .map_err(runtime_exception);
+            }
+
+            match repo {
+                #[cfg(feature = "git")]
+                Some(repo) => {
+                    execute_select_statement(env, statement, repo, gitql_object, hidden_selection)
+                        .map_err(runtime_exception)
+                }
+                _ => Err(runtime_exception(format!(
+                    "Selecting from table `{}` requires a repository",
+                    statement.table_name
+                ))),
+            }
         }
         Where => {
             let statement = statement.as_any().downcast_ref::<WhereStatement>().unwrap();
@@ -62,28 +122,36 @@ pub fn execute_statement(
         }
         Limit => {
             let statement = statement.as_any().downcast_ref::<LimitStatement>().unwrap();
-            execute_limit_statement(statement, gitql_object)
+            execute_limit_statement(statement, gitql_object).map_err(runtime_exception)
+        }
+        Sample => {
+            let statement = statement
+                .as_any()
+                .downcast_ref::<SampleStatement>()
+                .unwrap();
+            execute_sample_statement(env, statement, gitql_object);
+            Ok(())
         }
         Offset => {
             let statement = statement
                 .as_any()
                 .downcast_ref::<OffsetStatement>()
                 .unwrap();
-            execute_offset_statement(statement, gitql_object)
+            execute_offset_statement(statement, gitql_object).map_err(runtime_exception)
         }
         OrderBy => {
             let statement = statement
                 .as_any()
                 .downcast_ref::<OrderByStatement>()
                 .unwrap();
-            execute_order_by_statement(env, statement, gitql_object)
+            execute_order_by_statement(env, statement, gitql_object).map_err(runtime_exception)
         }
         GroupBy => {
             let statement = statement
                 .as_any()
                 .downcast_ref::<GroupByStatement>()
                 .unwrap();
-            execute_group_by_statement(statement, gitql_object)
+            execute_group_by_statement(statement, gitql_object).map_err(runtime_exception)
         }
         AggregateFunction => {
             let statement = statement
@@ -91,43 +159,162 @@ pub fn execute_statement(
                 .downcast_ref::<AggregationsStatement>()
                 .unwrap();
             execute_aggregation_function_statement(env, statement, gitql_object, alias_table)
+                .map_err(runtime_exception)
+        }
+        RowNumber => {
+            let statement = statement
+                .as_any()
+                .downcast_ref::<RowNumberStatement>()
+                .unwrap();
+            execute_row_number_statement(statement, gitql_object);
+            Ok(())
         }
         GlobalVariable => {
             let statement = statement
                 .as_any()
                 .downcast_ref::<GlobalVariableStatement>()
                 .unwrap();
-            execute_global_variable_statement(env, statement)
+            execute_global_variable_statement(env, statement).map_err(runtime_exception)
         }
     }
 }
 
-fn execute_select_statement(
+/// Evaluate the selected expressions of select statement without table as one row of values,
+/// or on each row of the `VALUES` table if it exists
+pub fn execute_values_select_statement(
     env: &mut Environment,
     statement: &SelectStatement,
-    repo: &gix::Repository,
     gitql_object: &mut GitQLObject,
-    hidden_selections: &Vec<String>,
+    hidden_selections: &[String],
 ) -> Result<(), String> {
-    // Append hidden selection to the selected fields names
-    let mut fields_names = statement.fields_names.to_owned();
-    if !statement.table_name.is_empty() {
-        for hidden in hidden_selections {
-            if !fields_names.contains(hidden) {
-                fields_names.push(hidden.to_string());
-            }
-        }
+    let fields_names = select_fields_names(statement, hidden_selections);
+    for field_name in &fields_names {
+        gitql_object
+            .titles
+            .push(get_column_name(&statement.alias_table, field_name));
     }
 
-    // Calculate list of titles once
+    let mut objects = match &statement.values_table {
+        Some(values_table) => select_values_table(
+            env,
+            values_table,
+            &fields_names,
+            &gitql_object.titles,
+            &statement.fields_values,
+        )?,
+        None => select_values(env, &gitql_object.titles, &statement.fields_values)?,
+    };
+    if gitql_object.is_empty() {
+        gitql_object.groups.push(objects);
+    } else {
+        gitql_object.groups[0].rows.append(&mut objects.rows);
+    }
+
+    Ok(())
+}
+
+/// Select the fields and expressions of the select statement from the rows of its subquery
+pub fn execute_subquery_select_statement(
+    env: &mut Environment,
+    statement: &SelectStatement,
+    subquery_object: &GitQLObject,
+    gitql_object: &mut GitQLObject,
+    alias_table: &mut HashMap<String, String>,
+    hidden_selections: &[String],
+) -> Result<(), String> {
+    // Copy alias table to be last later for Aggregations functions
+    for alias in &statement.alias_table {
+        alias_table.insert(alias.0.to_string(), alias.1.to_string());
+    }
+
+    let fields_names = select_fields_names(statement, hidden_selections);
+    for field_name in &fields_names {
+        gitql_object
+            .titles
+            .push(get_column_name(&statement.alias_table, field_name));
+    }
+
+    let subquery_rows: Vec<Row> = subquery_object
+        .groups
+        .iter()
+        .flat_map(|group| group.rows.iter())
+        .map(|row| Row {
+            values: row.values.clone(),
+        })
+        .collect();
+
+    let mut objects = select_derived_table_rows(
+        env,
+        &subquery_object.titles,
+        &subquery_rows,
+        &fields_names,
+        &gitql_object.titles,
+        &statement.fields_values,
+    )?;
+
+    if gitql_object.is_empty() {
+        gitql_object.groups.push(objects);
+    } else {
+        gitql_object.groups[0].rows.append(&mut objects.rows);
+    }
+
+    Ok(())
+}
+
+/// Select the fields and expressions of the select statement from the rows of the table data provider
+fn execute_provided_select_statement(
+    env: &mut Environment,
+    statement: &SelectStatement,
+    provider: Arc<dyn DataProvider>,
+    gitql_object: &mut GitQLObject,
+    hidden_selections: &[String],
+) -> Result<(), String> {
+    let fields_names = select_fields_names(statement, hidden_selections);
     for field_name in &fields_names {
         gitql_object
             .titles
             .push(get_column_name(&statement.alias_table, field_name));
     }
 
+    let mut objects = select_provided_rows(
+        env,
+        provider,
+        &statement.table_name,
+        &fields_names,
+        &gitql_object.titles,
+        &statement.fields_values,
+    )?;
+
+    if gitql_object.is_empty() {
+        gitql_object.groups.push(objects);
+    } else {
+        gitql_object.groups[0].rows.append(&mut objects.rows);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "git")]
+fn execute_select_statement(
+    env: &mut Environment,
+    statement: &SelectStatement,
+    repo: &Repository,
+    gitql_object: &mut GitQLObject,
+    hidden_selections: &Vec<String>,
+) -> Result<(), String> {
+    let fields_names = select_fields_names(statement, hidden_selections);
+
+    // Calculate list of titles once, the statement is executed again for each repository
+    if gitql_object.titles.is_empty() {
+        for field_name in &fields_names {
+            gitql_object
+                .titles
+                .push(get_column_name(&statement.alias_table, field_name));
+        }
+    }
+
     // Select objects from the target table
-    let mut objects = select_gql_objects(
+    let mut objects = select_gql_objects_with_cache(
         env,
         repo,
         statement.table_name.to_string(),
@@ -146,11 +333,145 @@ fn execute_select_statement(
     Ok(())
 }
 
+/// Execute the select statement on each repository using a pool of threads,
+/// the selected rows are merged in the same order of the repositories
+pub fn execute_select_statement_in_parallel(
+    env: &mut Environment,
+    statement: &SelectStatement,
+    repos: &[Repository],
+    threads: usize,
+    gitql_object: &mut GitQLObject,
+    alias_table: &mut HashMap<String, String>,
+    hidden_selections: &[String],
+) -> Result<(), String> {
+    // Copy alias table to be last later for Aggregations functions
+    for alias in &statement.alias_table {
+        alias_table.insert(alias.0.to_string(), alias.1.to_string());
+    }
+
+    let fields_names = select_fields_names(statement, hidden_selections);
+
+    // Calculate list of titles once
+    for field_name in &fields_names {
+        gitql_object
+            .titles
+            .push(get_column_name(&statement.alias_table, field_name));
+    }
+
+    if repos.is_empty() {
+        return Ok(());
+    }
+
+    let selected_groups = select_repositories_in_parallel(
+        env,
+        statement,
+        repos,
+        threads,
+        &gitql_object.titles,
+        &fields_names,
+    );
+
+    // Push the selected elements as a first group
+    for selected_group in selected_groups {
+        let mut objects = selected_group?;
+        if gitql_object.is_empty() {
+            gitql_object.groups.push(objects);
+        } else {
+            gitql_object.groups[0].rows.append(&mut objects.rows);
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the rows of the statement table from each repository using a pool of threads,
+/// returns the rows of each repository in the same order of the repositories
+#[cfg(feature = "git")]
+fn select_repositories_in_parallel(
+    env: &Environment,
+    statement: &SelectStatement,
+    repos: &[Repository],
+    threads: usize,
+    titles: &[String],
+    fields_names: &Vec<String>,
+) -> Vec<Result<Group, String>> {
+    // Repository can't be shared between threads, but it can be converted to thread safe one
+    let sync_repos: Vec<gix::ThreadSafeRepository> =
+        repos.iter().map(|repo| repo.clone().into_sync()).collect();
+
+    let threads = threads.clamp(1, sync_repos.len());
+    let chunk_size = sync_repos.len().div_ceil(threads);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = sync_repos
+            .chunks(chunk_size)
+            .map(|chunk| {
+                // Each thread has its own copy of the environment
+                let mut thread_env = env.clone();
+                scope.spawn(move || {
+                    let mut groups = Vec::with_capacity(chunk.len());
+                    for sync_repo in chunk {
+                        let repo = sync_repo.to_thread_local();
+                        groups.push(select_gql_objects(
+                            &mut thread_env,
+                            &repo,
+                            statement.table_name.to_string(),
+                            fields_names,
+                            titles,
+                            &statement.fields_values,
+                        ));
+                    }
+                    groups
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle.join().unwrap_or_else(|_| {
+                    vec![Err("Failed to select data from repository".to_string())]
+                })
+            })
+            .collect()
+    })
+}
+
+#[cfg(not(feature = "git"))]
+fn select_repositories_in_parallel(
+    _env: &Environment,
+    _statement: &SelectStatement,
+    repos: &[Repository],
+    _threads: usize,
+    _titles: &[String],
+    _fields_names: &Vec<String>,
+) -> Vec<Result<Group, String>> {
+    repos.iter().map(|repo| match *repo {}).collect()
+}
+
+/// Prepend hidden selection to the selected fields names, the rows are selected in this order,
+/// so the hidden fields used by the selected expressions are evaluated before the expressions
+pub fn select_fields_names(
+    statement: &SelectStatement,
+    hidden_selections: &[String],
+) -> Vec<String> {
+    let mut fields_names: Vec<String> = vec![];
+    if !statement.table_name.is_empty() {
+        for hidden in hidden_selections {
+            if !fields_names.contains(hidden) && !statement.fields_names.contains(hidden) {
+                fields_names.push(hidden.to_string());
+            }
+        }
+    }
+    fields_names.extend(statement.fields_names.iter().cloned());
+    fields_names
+}
+
 fn execute_where_statement(
     env: &mut Environment,
     statement: &WhereStatement,
     gitql_object: &mut GitQLObject,
-) -> Result<(), String> {
+) -> Result<(), Box<Diagnostic>> {
     if gitql_object.is_empty() {
         return Ok(());
     }
@@ -167,7 +488,14 @@ fn execute_where_statement(
             &object.values,
         );
         if eval_result.is_err() {
-            return Err(eval_result.err().unwrap());
+            return Err(row_evaluation_exception(
+                &eval_result.err().unwrap(),
+                "WHERE",
+                statement.condition_location,
+                &gitql_object.titles,
+                object,
+                &env.session,
+            ));
         }
 
         if eval_result.ok().unwrap().as_bool() {
@@ -188,7 +516,7 @@ fn execute_having_statement(
     env: &mut Environment,
     statement: &HavingStatement,
     gitql_object: &mut GitQLObject,
-) -> Result<(), String> {
+) -> Result<(), Box<Diagnostic>> {
     if gitql_object.is_empty() {
         return Ok(());
     }
@@ -209,7 +537,14 @@ fn execute_having_statement(
             &object.values,
         );
         if eval_result.is_err() {
-            return Err(eval_result.err().unwrap());
+            return Err(row_evaluation_exception(
+                &eval_result.err().unwrap(),
+                "HAVING",
+                statement.condition_location,
+                &gitql_object.titles,
+                object,
+                &env.session,
+            ));
         }
 
         if eval_result.ok().unwrap().as_bool() {
@@ -239,13 +574,89 @@ fn execute_limit_statement(
     }
 
     let main_group: &mut Group = &mut gitql_object.groups[0];
-    if statement.count <= main_group.len() {
-        main_group.rows.drain(statement.count..main_group.len());
+    let count = match statement.count {
+        LimitCount::Absolute(count) => count,
+        LimitCount::Ratio(ratio) => (main_group.len() as f64 * ratio).ceil() as usize,
+    };
+
+    if count <= main_group.len() {
+        main_group.rows.drain(count..main_group.len());
     }
 
     Ok(())
 }
 
+/// Keep a random subset of the rows in their original order, the rows are selected by partial
+/// Fisher-Yates shuffle of their indexes so the same `@@seed` and rows always select the same subset
+fn execute_sample_statement(
+    env: &mut Environment,
+    statement: &SampleStatement,
+    gitql_object: &mut GitQLObject,
+) {
+    if gitql_object.is_empty() {
+        return;
+    }
+
+    if gitql_object.len() > 1 {
+        gitql_object.flat()
+    }
+
+    let main_group: &mut Group = &mut gitql_object.groups[0];
+    let rows_count = main_group.len();
+    let count = match statement.count {
+        LimitCount::Absolute(count) => count,
+        LimitCount::Ratio(ratio) => (rows_count as f64 * ratio).ceil() as usize,
+    };
+
+    if count >= rows_count {
+        return;
+    }
+
+    // Without `@@seed` each sampling selects different rows
+    let seed = match env.globals.get(SEED_VARIABLE) {
+        Some(Value::Integer(seed)) => *seed as u64,
+        _ => RandomState::new().build_hasher().finish(),
+    };
+    let mut random = SampleRandom::new(seed);
+
+    let mut indexes: Vec<usize> = (0..rows_count).collect();
+    for index in 0..count {
+        let remaining = (rows_count - index) as u64;
+        let selected = index + (random.next_u64() % remaining) as usize;
+        indexes.swap(index, selected);
+    }
+
+    let mut is_selected = vec![false; rows_count];
+    for index in &indexes[..count] {
+        is_selected[*index] = true;
+    }
+
+    let mut row_index = 0;
+    main_group.rows.retain(|_| {
+        row_index += 1;
+        is_selected[row_index - 1]
+    });
+}
+
+/// SplitMix64 generator, fast and good enough to select uniform random rows from a seed
+struct SampleRandom {
+    state: u64,
+}
+
+impl SampleRandom {
+    fn new(seed: u64) -> Self {
+        SampleRandom { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+}
+
 fn execute_offset_statement(
     statement: &OffsetStatement,
     gitql_object: &mut GitQLObject,
@@ -266,6 +677,29 @@ fn execute_offset_statement(
     Ok(())
 }
 
+/// Set the values of the `ROW_NUMBER()` columns to the position of each row starting from 1
+fn execute_row_number_statement(statement: &RowNumberStatement, gitql_object: &mut GitQLObject) {
+    if gitql_object.is_empty() {
+        return;
+    }
+
+    if gitql_object.len() > 1 {
+        gitql_object.flat();
+    }
+
+    let indexes: Vec<usize> = statement
+        .titles
+        .iter()
+        .filter_map(|title| gitql_object.titles.iter().position(|name| name == title))
+        .collect();
+
+    for (row_index, row) in gitql_object.groups[0].rows.iter_mut().enumerate() {
+        for index in &indexes {
+            row.values[*index] = Value::Integer(row_index as i64 + 1);
+        }
+    }
+}
+
 fn execute_order_by_statement(
     env: &mut Environment,
     statement: &OrderByStatement,
@@ -284,89 +718,417 @@ fn execute_order_by_statement(
         return Ok(());
     }
 
-    main_group.rows.sort_by(|a, b| {
-        // The default ordering
-        let mut ordering = Ordering::Equal;
+    let titles = &gitql_object.titles;
+    main_group
+        .rows
+        .sort_by(|a, b| compare_rows_by_order(env, statement, titles, a, b));
 
-        for i in 0..statement.arguments.len() {
-            let argument = &statement.arguments[i];
-            // No need to compare if the ordering argument is constants
-            if argument.is_const() {
-                continue;
+    Ok(())
+}
+
+/// Sort the rows inside each group by the `ORDER BY` arguments without changing the groups order,
+/// used before calculating aggregations that depend on the rows order like `FIRST` and `LAST`
+pub fn execute_order_by_in_groups(
+    env: &mut Environment,
+    statement: &OrderByStatement,
+    gitql_object: &mut GitQLObject,
+) {
+    let titles = &gitql_object.titles;
+    for group in gitql_object.groups.iter_mut() {
+        group
+            .rows
+            .sort_by(|a, b| compare_rows_by_order(env, statement, titles, a, b));
+    }
+}
+
+fn compare_rows_by_order(
+    env: &mut Environment,
+    statement: &OrderByStatement,
+    titles: &[String],
+    a: &Row,
+    b: &Row,
+) -> Ordering {
+    for i in 0..statement.arguments.len() {
+        let argument = &statement.arguments[i];
+        // No need to compare if the ordering argument is constants
+        if argument.is_const() {
+            continue;
+        }
+
+        // Compare the two set of attributes using the current argument
+        let first = &evaluate_expression(env, argument, titles, &a.values).unwrap_or(Value::Null);
+        let other = &evaluate_expression(env, argument, titles, &b.values).unwrap_or(Value::Null);
+
+        let current_ordering = first.compare(other);
+
+        // If comparing result still equal, check the next argument
+        if current_ordering == Ordering::Equal {
+            continue;
+        }
+
+        // Reverse the order if its not ASC order
+        return if statement.sorting_orders[i] == SortingOrder::Descending {
+            current_ordering
+        } else {
+            current_ordering.reverse()
+        };
+    }
+
+    // The default ordering
+    Ordering::Equal
+}
+
+/// Sort only the first `count` rows by the `ORDER BY` arguments using a bounded heap and drop
+/// the others, used when the query has `LIMIT` so the rows after it are never sorted
+pub fn execute_top_n_order_by_statement(
+    env: &mut Environment,
+    statement: &OrderByStatement,
+    gitql_object: &mut GitQLObject,
+    count: usize,
+) -> Result<(), String> {
+    if gitql_object.is_empty() {
+        return Ok(());
+    }
+
+    if gitql_object.len() > 1 {
+        gitql_object.flat();
+    }
+
+    let main_group: &mut Group = &mut gitql_object.groups[0];
+    if count >= main_group.len() {
+        return execute_order_by_statement(env, statement, gitql_object);
+    }
+
+    let titles = &gitql_object.titles;
+    let rows = std::mem::take(&mut main_group.rows);
+    let mut heap: BinaryHeap<OrderedRow> = BinaryHeap::with_capacity(count + 1);
+    for (index, row) in rows.into_iter().enumerate() {
+        let keys = statement
+            .arguments
+            .iter()
+            .map(|argument| {
+                // Constants ordering arguments are equal in all rows
+                if argument.is_const() {
+                    return Ok(Value::Null);
+                }
+                evaluate_expression(env, argument, titles, &row.values)
+            })
+            .collect::<Result<Vec<Value>, String>>()?;
+
+        let ordered_row = OrderedRow {
+            keys,
+            index,
+            sorting_orders: &statement.sorting_orders,
+            row,
+        };
+
+        // The top of the heap is the last row in the order, replaced if the new row comes before it
+        if heap.len() < count {
+            heap.push(ordered_row);
+        } else if let Some(mut last) = heap.peek_mut() {
+            if ordered_row < *last {
+                *last = ordered_row;
             }
+        }
+    }
 
-            // Compare the two set of attributes using the current argument
-            let first = &evaluate_expression(env, argument, &gitql_object.titles, &a.values)
-                .unwrap_or(Value::Null);
-            let other = &evaluate_expression(env, argument, &gitql_object.titles, &b.values)
-                .unwrap_or(Value::Null);
+    main_group.rows = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|ordered_row| ordered_row.row)
+        .collect();
 
-            let current_ordering = first.compare(other);
+    Ok(())
+}
+
+/// Row with its evaluated `ORDER BY` values, ordered like [`compare_rows_by_order`] and
+/// by the original position for equal rows to keep the sort stable
+struct OrderedRow<'a> {
+    keys: Vec<Value>,
+    index: usize,
+    sorting_orders: &'a [SortingOrder],
+    row: Row,
+}
 
-            // If comparing result still equal, check the next argument
+impl Ord for OrderedRow<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (i, key) in self.keys.iter().enumerate() {
+            let current_ordering = key.compare(&other.keys[i]);
             if current_ordering == Ordering::Equal {
                 continue;
             }
 
-            // Reverse the order if its not ASC order
-            ordering = if statement.sorting_orders[i] == SortingOrder::Descending {
+            return if self.sorting_orders[i] == SortingOrder::Descending {
                 current_ordering
             } else {
                 current_ordering.reverse()
             };
-            break;
         }
 
-        ordering
-    });
+        self.index.cmp(&other.index)
+    }
+}
 
-    Ok(())
+impl PartialOrd for OrderedRow<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OrderedRow<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
+impl Eq for OrderedRow<'_> {}
+
 fn execute_group_by_statement(
     statement: &GroupByStatement,
     gitql_object: &mut GitQLObject,
 ) -> Result<(), String> {
+    let groups_rolled_up_fields = execute_group_by_grouping_sets(statement, gitql_object)?;
+    apply_rollup_placeholders(gitql_object, &groups_rolled_up_fields);
+    Ok(())
+}
+
+/// Group the rows by each grouping set of the `GROUP BY` statement, returns the indexes of the
+/// rolled up fields of each group, which must be set to NULL after calculating the aggregations
+pub fn execute_group_by_grouping_sets(
+    statement: &GroupByStatement,
+    gitql_object: &mut GitQLObject,
+) -> Result<Vec<Vec<usize>>, String> {
     if gitql_object.is_empty() {
-        return Ok(());
+        return Ok(vec![]);
     }
 
     let main_group: Group = gitql_object.groups.remove(0);
     if main_group.is_empty() {
-        return Ok(());
+        return Ok(vec![]);
     }
 
-    // Mapping each unique value to it group index
-    let mut groups_map: HashMap<String, usize> = HashMap::new();
-
-    // Track current group index
-    let mut next_group_index = 0;
+    // Grouping by no fields, keep all rows in one group
+    if statement.field_names.is_empty() {
+        gitql_object.groups.push(main_group);
+        return Ok(vec![]);
+    }
 
-    for object in main_group.rows.into_iter() {
+    // Resolve the index of each grouping field once
+    let mut fields_indexes: Vec<usize> = Vec::with_capacity(statement.field_names.len());
+    for field_name in &statement.field_names {
         let field_index = gitql_object
             .titles
             .iter()
-            .position(|r| r.eq(&statement.field_name))
+            .position(|r| r.eq(field_name))
             .unwrap();
+        fields_indexes.push(field_index);
+    }
 
-        let field_value = &object.values[field_index];
+    // Without rollup there is only one grouping set, so the rows are moved to the groups
+    if !statement.is_rollup {
+        group_rows_by_fields(main_group.rows, &fields_indexes, gitql_object);
+        return Ok(vec![]);
+    }
 
-        // If there is an existing group for this value, append current object to it
-        if let std::collections::hash_map::Entry::Vacant(e) =
-            groups_map.entry(field_value.as_text())
-        {
-            e.insert(next_group_index);
-            next_group_index += 1;
-            gitql_object.groups.push(Group { rows: vec![object] });
+    // Each grouping set is grouped in a separate pass, so each row is copied to one group per set
+    let mut groups_rolled_up_fields: Vec<Vec<usize>> = vec![];
+    for grouping_set_len in grouping_sets_lengths(statement) {
+        let (grouping_fields, rolled_up_fields) = fields_indexes.split_at(grouping_set_len);
+        let rows = main_group.rows.iter().map(|row| Row {
+            values: row.values.clone(),
+        });
+
+        let groups_count = gitql_object.groups.len();
+        group_rows_by_fields(rows, grouping_fields, gitql_object);
+        for _ in groups_count..gitql_object.groups.len() {
+            groups_rolled_up_fields.push(rolled_up_fields.to_vec());
         }
-        // Push a new group for this unique value and update the next index
-        else {
-            let index = *groups_map.get(&field_value.as_text()).unwrap();
-            let target_group = &mut gitql_object.groups[index];
+    }
+
+    Ok(groups_rolled_up_fields)
+}
+
+/// Append the rows to new groups by the values of the fields, rows with the same values are in the same group
+fn group_rows_by_fields(
+    rows: impl IntoIterator<Item = Row>,
+    fields_indexes: &[usize],
+    gitql_object: &mut GitQLObject,
+) {
+    // Mapping each unique values to it group index
+    let mut groups_map: HashMap<Vec<String>, usize> = HashMap::new();
+
+    for object in rows.into_iter() {
+        let fields_values: Vec<String> = fields_indexes
+            .iter()
+            .map(|index| object.values[*index].to_string())
+            .collect();
+
+        // If there is an existing group for this values, append current object to it
+        if let Some(index) = groups_map.get(&fields_values) {
+            let target_group = &mut gitql_object.groups[*index];
             target_group.rows.push(object);
         }
+        // Push a new group for this unique values
+        else {
+            groups_map.insert(fields_values, gitql_object.groups.len());
+            gitql_object.groups.push(Group { rows: vec![object] });
+        }
+    }
+}
+
+/// Returns the number of grouping fields in each grouping set, `GROUP BY ROLLUP(a, b)` groups by `(a, b)`
+/// then `(a)` for the subtotal rows then `()` for the grand total row, otherwise it groups by all fields once
+fn grouping_sets_lengths(statement: &GroupByStatement) -> Vec<usize> {
+    let fields_count = statement.field_names.len();
+    if statement.is_rollup {
+        (0..=fields_count).rev().collect()
+    } else {
+        vec![fields_count]
+    }
+}
+
+/// Set the rolled up fields of the subtotal and grand total groups to NULL
+pub fn apply_rollup_placeholders(
+    gitql_object: &mut GitQLObject,
+    groups_rolled_up_fields: &[Vec<usize>],
+) {
+    for (group, rolled_up_fields) in gitql_object.groups.iter_mut().zip(groups_rolled_up_fields) {
+        for object in group.rows.iter_mut() {
+            for index in rolled_up_fields {
+                if let Some(value) = object.values.get_mut(*index) {
+                    *value = Value::Null;
+                }
+            }
+        }
+    }
+}
+
+/// Group the rows by the `GROUP BY` fields values and calculate the aggregations functions while
+/// streaming the rows, so only the first row and the aggregations states are kept for each group
+pub fn execute_group_by_with_aggregations(
+    env: &mut Environment,
+    group_statement: &GroupByStatement,
+    aggregations_statement: &AggregationsStatement,
+    gitql_object: &mut GitQLObject,
+    alias_table: &HashMap<String, String>,
+) -> Result<(), String> {
+    if gitql_object.is_empty() {
+        return Ok(());
+    }
+
+    let main_group: Group = gitql_object.groups.remove(0);
+    if main_group.is_empty() {
+        return Ok(());
+    }
+
+    let titles = &gitql_object.titles;
+
+    // Resolve the index of each grouping field once
+    let mut fields_indexes: Vec<usize> = Vec::with_capacity(group_statement.field_names.len());
+    for field_name in &group_statement.field_names {
+        let field_index = titles.iter().position(|r| r.eq(field_name)).unwrap();
+        fields_indexes.push(field_index);
+    }
+
+    // Resolve the accumulator of each aggregation function with its argument and result columns
+    let mut accumulators = vec![];
+    for (result_column_name, aggregation) in &aggregations_statement.aggregations {
+        if let AggregateValue::Function(call) = aggregation {
+            let no_such_function =
+                || format!("No such aggregation function `{}`", call.function_name);
+            let accumulator =
+                aggregation_accumulator(env, &call.function_name).ok_or_else(no_such_function)?;
+            let argument_index = titles
+                .iter()
+                .position(|r| r.eq(&call.argument))
+                .ok_or_else(no_such_function)?;
+
+            let column_name = get_column_name(alias_table, result_column_name);
+            let column_index = titles.iter().position(|r| r.eq(&column_name)).unwrap();
+            accumulators.push((accumulator, argument_index, column_index));
+        }
+    }
+
+    let mut groups_states: Vec<Vec<Box<dyn Any + Send>>> = vec![];
+    let mut groups_rolled_up_fields: Vec<Vec<usize>> = vec![];
+
+    // Each grouping set is aggregated in a separate pass over the rows
+    for grouping_set_len in grouping_sets_lengths(group_statement) {
+        let (grouping_fields, rolled_up_fields) = fields_indexes.split_at(grouping_set_len);
+
+        // Mapping each unique values to it group index
+        let mut groups_map: HashMap<Vec<String>, usize> = HashMap::new();
+
+        for object in main_group.rows.iter() {
+            let fields_values: Vec<String> = grouping_fields
+                .iter()
+                .map(|index| object.values[*index].to_string())
+                .collect();
+
+            let group_index = match groups_map.get(&fields_values) {
+                Some(index) => *index,
+                None => {
+                    let index = groups_states.len();
+                    groups_map.insert(fields_values, index);
+                    groups_states.push(
+                        accumulators
+                            .iter()
+                            .map(|(accumulator, _, _)| accumulator.init())
+                            .collect(),
+                    );
+
+                    // Only the first row is kept, all rows in the same group have the same group keys
+                    gitql_object.groups.push(Group {
+                        rows: vec![Row {
+                            values: object.values.clone(),
+                        }],
+                    });
+                    groups_rolled_up_fields.push(rolled_up_fields.to_vec());
+                    index
+                }
+            };
+
+            let states = &mut groups_states[group_index];
+            for (state, (accumulator, argument_index, _)) in states.iter_mut().zip(&accumulators) {
+                if let Some(value) = object.values.get(*argument_index) {
+                    accumulator.accumulate(state, value);
+                }
+            }
+        }
+    }
+
+    for (group, states) in gitql_object.groups.iter_mut().zip(groups_states) {
+        let object = &mut group.rows[0];
+        for (state, (accumulator, _, column_index)) in states.into_iter().zip(&accumulators) {
+            let result = accumulator.finish(state);
+            if *column_index < object.values.len() {
+                object.values[*column_index] = result;
+            } else {
+                object.values.push(result);
+            }
+        }
+
+        // Resolve aggregations expressions after the aggregations functions results
+        for (result_column_name, aggregation) in &aggregations_statement.aggregations {
+            if let AggregateValue::Expression(expr) = aggregation {
+                let column_name = get_column_name(alias_table, result_column_name);
+                let column_index = gitql_object
+                    .titles
+                    .iter()
+                    .position(|r| r.eq(&column_name))
+                    .unwrap();
+
+                let result = evaluate_expression(env, expr, &gitql_object.titles, &object.values)?;
+                if column_index < object.values.len() {
+                    object.values[column_index] = result;
+                } else {
+                    object.values.push(result);
+                }
+            }
+        }
     }
 
+    apply_rollup_placeholders(gitql_object, &groups_rolled_up_fields);
     Ok(())
 }
 
@@ -394,7 +1156,7 @@ fn execute_aggregation_function_statement(
 
         // Resolve all aggregations functions first
         for aggregation in aggregations_map {
-            if let AggregateValue::Function(function, argument) = aggregation.1 {
+            if let AggregateValue::Function(call) = aggregation.1 {
                 // Get alias name if exists or column name by default
 
                 let result_column_name = aggregation.0;
@@ -406,10 +1168,11 @@ fn execute_aggregation_function_statement(
                     .position(|r| r.eq(&column_name))
                     .unwrap();
 
-                // Get the target aggregation function
-                let aggregation_function = AGGREGATIONS.get(function.as_str()).unwrap();
-                let result =
-                    &aggregation_function(&argument.to_string(), &gitql_object.titles, group);
+                // Execute the target builtin or custom aggregation function
+                let result = &execute_aggregate_call(env, call, &gitql_object.titles, group)
+                    .ok_or_else(|| {
+                        format!("No such aggregation function `{}`", call.function_name)
+                    })?;
 
                 // Insert the calculated value in the group objects
                 for object in &mut group.rows {
@@ -463,62 +1226,30 @@ pub fn execute_global_variable_statement(
     statement: &GlobalVariableStatement,
 ) -> Result<(), String> {
     let value = evaluate_expression(env, &statement.value, &[], &vec![])?;
+
+    // Session variables are validated before they are applied, `NULL` reset them to default
+    if is_session_variable(&statement.name) {
+        if matches!(value, Value::Null) {
+            env.globals.remove(&statement.name);
+        } else {
+            check_session_variable_value(&statement.name, &value.as_text())?;
+            env.globals.insert(statement.name.to_string(), value);
+        }
+        apply_session_variables(env);
+        return Ok(());
+    }
+
     env.globals.insert(statement.name.to_string(), value);
     Ok(())
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "git"))]
 mod tests {
     use super::*;
+    use crate::engine_test_repo::*;
     use gitql_ast::expression::NumberExpression;
-
-    fn test_new_repo(path: String) -> Result<(), String> {
-        let mut repo = gix::init_bare(path).expect("failed to init bare");
-        let mut tree = gix::objs::Tree::empty();
-        let object = repo
-            .write_object(&tree)
-            .expect("failed to write object")
-            .detach();
-
-        let mut config = repo.config_snapshot_mut();
-        config
-            .set_raw_value("author", None, "name", "name")
-            .expect("failed to set name");
-        config
-            .set_raw_value("author", None, "email", "name@example.com")
-            .expect("failed to set email");
-
-        let repo = config
-            .commit_auto_rollback()
-            .expect("failed to commit auto rollback");
-        let commit = repo
-            .commit("HEAD", "initial commit", object, gix::commit::NO_PARENT_IDS)
-            .expect("failed to commit");
-
-        let blob = repo
-            .write_blob("hello world")
-            .expect("faile to write blob")
-            .into();
-        let entry = gix::objs::tree::Entry {
-            mode: gix::objs::tree::EntryKind::Blob.into(),
-            oid: blob,
-            filename: "hello.txt".into(),
-        };
-
-        tree.entries.push(entry);
-        let object = repo.write_object(&tree).expect("failed to write object");
-
-        let _ = repo
-            .commit("HEAD", "hello commit", object, [commit])
-            .expect("failed to commit");
-
-        Ok(())
-    }
-
-    fn test_delete_repo(path: String) -> Result<(), String> {
-        std::fs::remove_dir_all(path).expect("failed to remove dir");
-        Ok(())
-    }
+    use gitql_ast::expression::SymbolExpression;
+    use gitql_ast::statement::AggregateCall;
 
     #[test]
     fn test_execute_statement() {
@@ -526,6 +1257,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement: Box<dyn Statement> = Box::new(SelectStatement {
@@ -542,6 +1283,7 @@ mod tests {
             fields_values: vec![],
             alias_table: Default::default(),
             is_distinct: false,
+            values_table: None,
         });
 
         let path = "test-execute-statement";
@@ -557,7 +1299,7 @@ mod tests {
         let ret = execute_statement(
             &mut env,
             &statement,
-            &repo,
+            Some(&repo),
             &mut object,
             &mut table,
             &selection,
@@ -577,6 +1319,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement = SelectStatement {
@@ -593,6 +1345,7 @@ mod tests {
             fields_values: vec![],
             alias_table: Default::default(),
             is_distinct: false,
+            values_table: None,
         };
 
         let path = "test-execute-select-statement";
@@ -620,12 +1373,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement = WhereStatement {
             condition: Box::new(NumberExpression {
                 value: Value::Integer(1),
             }),
+            condition_location: None,
         };
 
         let mut object = GitQLObject {
@@ -656,12 +1420,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement = HavingStatement {
             condition: Box::new(NumberExpression {
                 value: Value::Integer(1),
             }),
+            condition_location: None,
         };
 
         let mut object = GitQLObject {
@@ -688,7 +1463,9 @@ mod tests {
 
     #[test]
     fn test_execute_limit_statement() {
-        let statement = LimitStatement { count: 0 };
+        let statement = LimitStatement {
+            count: LimitCount::Absolute(0),
+        };
 
         let mut object = GitQLObject {
             titles: vec!["title1".to_string(), "title2".to_string()],
@@ -710,6 +1487,34 @@ mod tests {
         } else {
             assert!(false);
         }
+
+        let statement = LimitStatement {
+            count: LimitCount::Ratio(0.5),
+        };
+
+        let mut object = GitQLObject {
+            titles: vec!["title1".to_string(), "title2".to_string()],
+            groups: vec![Group {
+                rows: vec![
+                    Row {
+                        values: vec![Value::Integer(1), Value::Integer(2)],
+                    },
+                    Row {
+                        values: vec![Value::Integer(3), Value::Integer(4)],
+                    },
+                    Row {
+                        values: vec![Value::Integer(5), Value::Integer(6)],
+                    },
+                ],
+            }],
+        };
+
+        let ret = execute_limit_statement(&statement, &mut object);
+        if ret.is_ok() {
+            assert_eq!(object.groups[0].len(), 2);
+        } else {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -744,6 +1549,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement = OrderByStatement {
@@ -775,10 +1590,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_execute_top_n_order_by_statement() {
+        let mut env = Environment::default();
+
+        let statement = OrderByStatement {
+            arguments: vec![Box::new(SymbolExpression {
+                value: "title1".to_string(),
+            })],
+            sorting_orders: vec![SortingOrder::Ascending],
+        };
+
+        let values = [5, 1, 4, 1, 3, 2, 5, 0];
+        let new_object = || GitQLObject {
+            titles: vec!["title1".to_string(), "title2".to_string()],
+            groups: vec![Group {
+                rows: values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| Row {
+                        values: vec![Value::Integer(*value), Value::Integer(index as i64)],
+                    })
+                    .collect(),
+            }],
+        };
+
+        let mut sorted_object = new_object();
+        let ret = execute_order_by_statement(&mut env, &statement, &mut sorted_object);
+        assert!(ret.is_ok());
+
+        for count in [0, 1, 3, 5, values.len(), values.len() + 1] {
+            let mut object = new_object();
+            let ret = execute_top_n_order_by_statement(&mut env, &statement, &mut object, count);
+            assert!(ret.is_ok());
+
+            let expected_len = cmp::min(count, values.len());
+            assert_eq!(object.groups[0].len(), expected_len);
+            for i in 0..expected_len {
+                let row = &object.groups[0].rows[i];
+                let expected_row = &sorted_object.groups[0].rows[i];
+                assert!(row.values[0].equals(&expected_row.values[0]));
+                assert!(row.values[1].equals(&expected_row.values[1]));
+            }
+        }
+    }
+
     #[test]
     fn test_execute_group_by_statement() {
         let statement = GroupByStatement {
-            field_name: "title1".to_string(),
+            field_names: vec!["title1".to_string()],
+            is_rollup: false,
         };
 
         let mut object = GitQLObject {
@@ -801,6 +1662,33 @@ mod tests {
         } else {
             assert!(false);
         }
+
+        let statement = GroupByStatement {
+            field_names: vec!["title1".to_string(), "title2".to_string()],
+            is_rollup: false,
+        };
+
+        let mut object = GitQLObject {
+            titles: vec!["title1".to_string(), "title2".to_string()],
+            groups: vec![Group {
+                rows: vec![
+                    Row {
+                        values: vec![Value::Integer(1), Value::Integer(2)],
+                    },
+                    Row {
+                        values: vec![Value::Integer(1), Value::Integer(3)],
+                    },
+                    Row {
+                        values: vec![Value::Integer(1), Value::Integer(2)],
+                    },
+                ],
+            }],
+        };
+
+        let ret = execute_group_by_statement(&statement, &mut object);
+        assert!(ret.is_ok());
+        assert_eq!(object.len(), 2);
+        assert_eq!(object.groups[0].len(), 2);
     }
 
     #[test]
@@ -809,6 +1697,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let mut statement = AggregationsStatement {
@@ -817,7 +1715,7 @@ mod tests {
 
         statement.aggregations.insert(
             "title".to_string(),
-            AggregateValue::Function("max".to_string(), "title1".to_string()),
+            AggregateValue::Function(AggregateCall::new("max".to_string(), "title1".to_string())),
         );
         statement.aggregations.insert(
             "title".to_string(),
@@ -857,6 +1755,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let statement = GlobalVariableStatement {
@@ -1,14 +1,44 @@
 use gitql_ast::environment::Environment;
+use gitql_ast::environment::BLAME_PATH_FIELD_PREFIX;
+use gitql_ast::environment::BLOBS_REVISION_VARIABLE;
+use gitql_ast::environment::RANGE_FIELD_PREFIX;
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::environment::TOUCHES_FIELD_PREFIX;
+use gitql_ast::environment::TOUCHING_FIELD_PREFIX;
 use gitql_ast::object::Group;
 use gitql_ast::object::Row;
+use gitql_ast::rows_cache::RowsCacheKey;
+use gitql_ast::session::parse_rename_similarity;
+use gitql_ast::session::session_variable_value;
+use gitql_ast::session::DEFAULT_RENAME_SIMILARITY;
+use gitql_ast::session::RENAME_SIMILARITY_VARIABLE;
+use gix::bstr::ByteSlice;
 use gix::refs::Category;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::ops::Range;
+use std::sync::Arc;
 
 use gitql_ast::expression::Expression;
 use gitql_ast::expression::SymbolExpression;
 use gitql_ast::value::Value;
 
 use crate::engine_evaluator::evaluate_expression;
+use crate::engine_memory::MemoryBudget;
+use crate::engine_progress::ScanProgressTracker;
+use crate::engine_provider::data_provider;
+use crate::engine_provider::project_table_rows;
+use crate::engine_provider::select_provided_rows;
+use crate::engine_rows::select_values;
+use crate::engine_signature::extract_commit_signature;
+use crate::engine_signature::extract_tag_signature;
+use crate::engine_signature::verify_signature;
+use crate::engine_signature::SignatureVerification;
+use crate::engine_signature::SignedData;
 
 pub fn select_gql_objects(
     env: &mut Environment,
@@ -23,9 +53,125 @@ pub fn select_gql_objects(
         "commits" => select_commits(env, repo, fields_names, titles, fields_values),
         "branches" => select_branches(env, repo, fields_names, titles, fields_values),
         "diffs" => select_diffs(env, repo, fields_names, titles, fields_values),
+        "diff_files" => select_diff_files(env, repo, fields_names, titles, fields_values),
         "tags" => select_tags(env, repo, fields_names, titles, fields_values),
-        _ => select_values(env, titles, fields_values),
+        "files" => select_files(env, repo, fields_names, titles, fields_values),
+        "blobs" => select_blobs(env, repo, fields_names, titles, fields_values),
+        "worktrees" => select_worktrees(env, repo, fields_names, titles, fields_values),
+        "blame" => select_blame(env, repo, fields_names, titles, fields_values),
+        _ => match data_provider(env, &table) {
+            Some(provider) => {
+                select_provided_rows(env, provider, &table, fields_names, titles, fields_values)
+            }
+            None => select_values(env, titles, fields_values),
+        },
+    }
+}
+
+/// Select objects using the rows cache if it's enabled, the selected fields of the table are collected once
+/// then each query with the same fields only evaluate the selected expressions from the cached rows
+pub fn select_gql_objects_with_cache(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    table: String,
+    fields_names: &Vec<String>,
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    // Diffs are expensive to calculate for fields that may not be selected, so they are not cached
+    // Commits touched paths, revision ranges, blamed path and table-valued functions are calculated
+    // per query and not stored in the cached rows
+    // Blobs depend on the `@revision` variable not on the repository head
+    // Worktrees can be added or removed without changing the repository head
+    // Diff files renames depend on the `@@rename_similarity` variable so only the default is cached
+    let has_touches_field = fields_names.iter().any(|name| {
+        name.starts_with(TOUCHES_FIELD_PREFIX)
+            || name.starts_with(RANGE_FIELD_PREFIX)
+            || name.starts_with(TOUCHING_FIELD_PREFIX)
+            || name.starts_with(BLAME_PATH_FIELD_PREFIX)
+    });
+    let head_id = repo.head_id();
+    if table == "diffs"
+        || (table == "diff_files" && !is_default_rename_similarity(env))
+        || table == "blobs"
+        || table == "worktrees"
+        || has_touches_field
+        || head_id.is_err()
+        || !TABLES_FIELDS_NAMES.contains_key(table.as_str())
+    {
+        return select_gql_objects(env, repo, table, fields_names, titles, fields_values);
+    }
+
+    if !env.rows_cache.lock().is_ok_and(|cache| cache.is_enabled()) {
+        return select_gql_objects(env, repo, table, fields_names, titles, fields_values);
+    }
+
+    // Fields like signatures status or upstream distance are expensive, so only the selected are collected
+    let table_fields: Vec<String> = TABLES_FIELDS_NAMES[table.as_str()]
+        .iter()
+        .filter(|field| fields_names.iter().any(|name| name == *field))
+        .map(|field| field.to_string())
+        .collect();
+
+    // References can be moved or deleted without changing the repository head
+    let refs_fingerprint = match table.as_str() {
+        "refs" | "branches" | "tags" => references_fingerprint(repo),
+        _ => 0,
+    };
+
+    let key = RowsCacheKey {
+        repo_path: repo.path().to_str().unwrap_or("").to_string(),
+        table_name: table.to_string(),
+        fields_names: table_fields.clone(),
+        head_id: head_id.unwrap().to_string(),
+        refs_fingerprint,
+    };
+
+    // Rows are collected without holding the cache lock, so the threads of other repositories are not blocked
+    let cached_group = env
+        .rows_cache
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(&key));
+    let cached_group = match cached_group {
+        Some(group) => group,
+        None => {
+            let group = select_gql_objects(env, repo, table, &table_fields, &table_fields, &[])?;
+            let group = Arc::new(group);
+            if let Ok(mut cache) = env.rows_cache.lock() {
+                cache.insert(key, group.clone());
+            }
+            group
+        }
+    };
+
+    project_table_rows(
+        env,
+        &table_fields,
+        &cached_group.rows,
+        fields_names,
+        titles,
+        fields_values,
+    )
+}
+
+/// Hash the names and targets of all references, used to invalidate the cached rows of references tables
+fn references_fingerprint(repo: &gix::Repository) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Ok(platform) = repo.references() {
+        if let Ok(references) = platform.all() {
+            for reference in references.flatten() {
+                reference.name().as_bstr().hash(&mut hasher);
+                let target = reference.target();
+                target.try_id().map(|id| id.to_string()).hash(&mut hasher);
+                target
+                    .try_name()
+                    .map(|name| name.as_bstr())
+                    .hash(&mut hasher);
+            }
+        }
     }
+    hasher.finish()
 }
 
 fn select_references(
@@ -80,6 +226,26 @@ fn select_references(
                 continue;
             }
 
+            if field_name == "short_name" {
+                let short_name = reference.name().shorten().to_string();
+                values.push(Value::Text(short_name));
+                continue;
+            }
+
+            if field_name == "target" {
+                // Symbolic references are peeled to the object they finally point to
+                let target = match reference.try_id() {
+                    Some(id) => Some(id.to_string()),
+                    None => reference
+                        .clone()
+                        .peel_to_id_in_place()
+                        .ok()
+                        .map(|id| id.to_string()),
+                };
+                values.push(target.map_or(Value::Null, Value::Text));
+                continue;
+            }
+
             if field_name == "type" {
                 let category = reference.name().category();
                 if category.map_or(false, |cat| cat == Category::LocalBranch) {
@@ -118,90 +284,357 @@ fn select_commits(
     titles: &[String],
     fields_values: &[Box<dyn Expression>],
 ) -> Result<Group, String> {
-    let repo_path = repo.path().to_str().unwrap().to_string();
-
     let mut rows: Vec<Row> = vec![];
-    let head_id = repo.head_id();
-    if head_id.is_err() {
+    let mut memory = MemoryBudget::default();
+    let Some(revwalk) = commits_revwalk(repo, fields_names)? else {
         return Ok(Group { rows });
+    };
+
+    let mut progress = scan_progress_tracker("commits", repo);
+    for commit_info in revwalk {
+        let commit_info = commit_info.map_err(|error| error.to_string())?;
+        if !is_commit_selected(repo, &commit_info, fields_names)? {
+            continue;
+        }
+
+        let row = select_commit_row(env, repo, &commit_info, fields_names, titles, fields_values)?;
+        memory.add_row(&row)?;
+        rows.push(row);
+        progress.update(rows.len());
     }
 
-    let revwalk = head_id.unwrap().ancestors().all().unwrap();
+    Ok(Group { rows })
+}
+
+/// Walk the commits from the head, or only the commits of the revision range selected by `RANGE`,
+/// returns None if the repository has no commits
+pub fn commits_revwalk<'repo>(
+    repo: &'repo gix::Repository,
+    fields_names: &[String],
+) -> Result<Option<gix::revision::Walk<'repo>>, String> {
+    let range = fields_names
+        .iter()
+        .find_map(|name| name.strip_prefix(RANGE_FIELD_PREFIX));
+
+    let Some(range) = range else {
+        let Ok(head_id) = repo.head_id() else {
+            return Ok(None);
+        };
+        let revwalk = head_id.ancestors().all();
+        return revwalk.map(Some).map_err(|error| error.to_string());
+    };
+
+    let spec = repo.rev_parse(range).map_err(|error| {
+        format!(
+            "Can't resolve revision range `{}` in repository `{}`: {}",
+            range,
+            repo.path().display(),
+            error
+        )
+    })?;
+
+    let (tip, hidden) = match spec.detach() {
+        gix::revision::plumbing::Spec::Include(id) => (id, None),
+        gix::revision::plumbing::Spec::Range { from, to } => (to, Some(from)),
+        _ => {
+            return Err(format!(
+                "Unsupported revision range `{}`, expect `<from>..<to>` or one revision",
+                range
+            ))
+        }
+    };
+
+    // Commits reachable from the start of the range are excluded with all their ancestors
+    let hidden_commits = match hidden {
+        Some(from) => collect_ancestors(repo, peel_to_commit_id(repo, from)?)?,
+        None => HashSet::new(),
+    };
+
+    let revwalk = repo
+        .rev_walk([peel_to_commit_id(repo, tip)?])
+        .selected(move |id| !hidden_commits.contains(id));
+    revwalk.map(Some).map_err(|error| error.to_string())
+}
+
+/// Returns false if the commit doesn't change the path prefix of the table-valued function
+/// like `COMMITS_TOUCHING(path)`, so it's skipped before its row is created
+pub fn is_commit_selected(
+    repo: &gix::Repository,
+    commit_info: &gix::revision::walk::Info,
+    fields_names: &[String],
+) -> Result<bool, String> {
+    for field_name in fields_names {
+        if let Some(prefix) = field_name.strip_prefix(TOUCHING_FIELD_PREFIX) {
+            let is_touched = is_commit_touches(repo, commit_info, |location| {
+                is_path_under_prefix(location, prefix)
+            })?;
+            if !is_touched {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Resolve annotated tags in revision ranges to the tagged commits
+fn peel_to_commit_id(repo: &gix::Repository, id: gix::ObjectId) -> Result<gix::ObjectId, String> {
+    let object = repo.find_object(id).map_err(|error| error.to_string())?;
+    let commit = object
+        .peel_to_kind(gix::object::Kind::Commit)
+        .map_err(|error| error.to_string())?;
+    Ok(commit.id)
+}
+
+/// Create progress tracker for the scan of the table in the repository on its current branch
+fn scan_progress_tracker(table_name: &str, repo: &gix::Repository) -> ScanProgressTracker {
+    let repo_path = repo.path().to_str().unwrap_or("");
+    let branch = match repo.head_name() {
+        Ok(Some(name)) => name.shorten().to_string(),
+        _ => "HEAD".to_string(),
+    };
+    ScanProgressTracker::new(table_name, repo_path, &branch)
+}
+
+pub fn select_commit_row(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    commit_info: &gix::revision::walk::Info,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Row, String> {
+    let repo_path = repo.path().to_str().unwrap().to_string();
+    let commit_object = repo
+        .find_object(commit_info.id)
+        .map_err(|error| error.to_string())?
+        .into_commit();
+    let commit = commit_object.decode().map_err(|error| error.to_string())?;
+
+    // Commit text is stored in the encoding declared by the commit header, UTF-8 if missing
+    let encoding = commit
+        .encoding
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_ref()));
+    let (author_name, is_lossy_name) = decode_commit_text(commit.author().name, encoding);
+    let (author_email, is_lossy_email) = decode_commit_text(commit.author().email, encoding);
+    let (message, is_lossy_message) = decode_commit_text(commit.message, encoding);
+    let is_lossy_decoded = is_lossy_name || is_lossy_email || is_lossy_message;
 
     let names_len = fields_names.len() as i64;
     let values_len = fields_values.len() as i64;
     let padding = names_len - values_len;
 
-    for commit_info in revwalk {
-        let commit_info = commit_info.unwrap();
-        let commit = repo.find_object(commit_info.id).unwrap().into_commit();
-        let commit = commit.decode().unwrap();
-
-        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+    let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
 
-        for index in 0..names_len {
-            let field_name = &fields_names[index as usize];
+    // Signature is verified only once per row and only if its status or signer is selected
+    let signed = extract_commit_signature(&commit_object.data);
+    let mut verification: Option<SignatureVerification> = None;
 
-            if (index - padding) >= 0 {
-                let value = &fields_values[(index - padding) as usize];
-                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
-                    let evaluated = evaluate_expression(env, value, titles, &values)?;
-                    values.push(evaluated);
-                    continue;
-                }
-            }
+    for index in 0..names_len {
+        let field_name = &fields_names[index as usize];
 
-            if field_name == "commit_id" {
-                let commit_id = Value::Text(commit_info.id.to_string());
-                values.push(commit_id);
+        if (index - padding) >= 0 {
+            let value = &fields_values[(index - padding) as usize];
+            if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                let evaluated = evaluate_expression(env, value, titles, &values)?;
+                values.push(evaluated);
                 continue;
             }
+        }
 
-            if field_name == "name" {
-                let name = commit.author().name.to_string();
-                values.push(Value::Text(name));
-                continue;
-            }
+        if field_name == "is_signed" {
+            values.push(Value::Boolean(signed.is_some()));
+            continue;
+        }
 
-            if field_name == "email" {
-                let email = commit.author().email.to_string();
-                values.push(Value::Text(email));
-                continue;
-            }
+        if field_name == "signer" || field_name == "signature_status" {
+            let verification =
+                verification.get_or_insert_with(|| verify_signature(signed.as_ref()));
+            values.push(signature_verification_value(verification, field_name));
+            continue;
+        }
 
-            if field_name == "title" {
-                let summary = Value::Text(commit.message().summary().to_string());
-                values.push(summary);
-                continue;
-            }
+        if field_name == "commit_id" {
+            let commit_id = Value::Text(commit_info.id.to_string());
+            values.push(commit_id);
+            continue;
+        }
 
-            if field_name == "message" {
-                let message = Value::Text(commit.message.to_string());
-                values.push(message);
-                continue;
-            }
+        if field_name == "name" {
+            values.push(Value::Text(author_name.to_string()));
+            continue;
+        }
 
-            if field_name == "datetime" {
-                let time_stamp = commit_info
-                    .commit_time
-                    .unwrap_or_else(|| commit.time().seconds);
-                values.push(Value::DateTime(time_stamp));
-                continue;
-            }
+        if field_name == "email" {
+            values.push(Value::Text(author_email.to_string()));
+            continue;
+        }
 
-            if field_name == "repo" {
-                values.push(Value::Text(repo_path.to_string()));
-                continue;
-            }
+        if field_name == "title" {
+            let summary = gix::objs::commit::MessageRef::from_bytes(message.as_bytes()).summary();
+            values.push(Value::Text(summary.to_string()));
+            continue;
+        }
 
-            values.push(Value::Null);
+        if field_name == "summary" {
+            let first_line = message.lines().next().unwrap_or_default();
+            values.push(Value::Text(first_line.trim().to_string()));
+            continue;
         }
 
-        let row = Row { values };
-        rows.push(row);
+        if field_name == "message" {
+            values.push(Value::Text(message.to_string()));
+            continue;
+        }
+
+        if field_name == "body" {
+            let (body, _) = split_commit_body_trailers(&message);
+            values.push(Value::Text(body));
+            continue;
+        }
+
+        if field_name == "trailers" {
+            let (_, trailers) = split_commit_body_trailers(&message);
+            values.push(Value::Text(trailers));
+            continue;
+        }
+
+        if field_name == "datetime" {
+            let time_stamp = commit_info
+                .commit_time
+                .unwrap_or_else(|| commit.time().seconds);
+            values.push(Value::DateTime(time_stamp));
+            continue;
+        }
+
+        if field_name == "is_lossy_decoded" {
+            values.push(Value::Boolean(is_lossy_decoded));
+            continue;
+        }
+
+        if field_name == "repo" {
+            values.push(Value::Text(repo_path.to_string()));
+            continue;
+        }
+
+        // Only the commits in the range or touching the path are selected
+        if field_name.starts_with(RANGE_FIELD_PREFIX)
+            || field_name.starts_with(TOUCHING_FIELD_PREFIX)
+        {
+            values.push(Value::Boolean(true));
+            continue;
+        }
+
+        if let Some(pathspec) = field_name.strip_prefix(TOUCHES_FIELD_PREFIX) {
+            let is_touched = is_commit_touches_pathspec(repo, commit_info, pathspec)?;
+            values.push(Value::Boolean(is_touched));
+            continue;
+        }
+
+        values.push(Value::Null);
     }
 
-    Ok(Group { rows })
+    Ok(Row { values })
+}
+
+/// Decode commit text using the commit encoding, invalid sequences are replaced
+/// and reported so rows are never dropped because of a badly encoded commit
+fn decode_commit_text(
+    text: &gix::bstr::BStr,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> (String, bool) {
+    match encoding {
+        Some(encoding) if encoding != encoding_rs::UTF_8 => {
+            let (decoded, had_errors) = encoding.decode_without_bom_handling(text.as_ref());
+            (decoded.to_string(), had_errors)
+        }
+        _ => match std::str::from_utf8(text.as_ref()) {
+            Ok(decoded) => (decoded.to_string(), false),
+            Err(_) => (String::from_utf8_lossy(text.as_ref()).to_string(), true),
+        },
+    }
+}
+
+/// Split the commit message after the title into the body text and the trailers,
+/// trailers like `Signed-off-by: Name <email>` are joined by new lines
+fn split_commit_body_trailers(message: &str) -> (String, String) {
+    let message = gix::objs::commit::MessageRef::from_bytes(message.as_bytes());
+    let Some(body) = message.body() else {
+        return (String::new(), String::new());
+    };
+
+    let trailers: Vec<String> = body
+        .trailers()
+        .map(|trailer| format!("{}: {}", trailer.token, trailer.value.trim().as_bstr()))
+        .collect();
+    let body_text = body.without_trailer().to_string();
+    (body_text.trim().to_string(), trailers.join("\n"))
+}
+
+/// Returns true if the commit changed any path that match the pathspec glob
+fn is_commit_touches_pathspec(
+    repo: &gix::Repository,
+    commit_info: &gix::revision::walk::Info,
+    pathspec: &str,
+) -> Result<bool, String> {
+    let mode = gix::glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL;
+    is_commit_touches(repo, commit_info, |location| {
+        gix::glob::wildmatch(pathspec.into(), location, mode)
+    })
+}
+
+/// Returns true if the path is the prefix itself or inside it, the prefix matches only whole path components
+fn is_path_under_prefix(path: &gix::bstr::BStr, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() {
+        return true;
+    }
+
+    match path.strip_prefix(prefix.as_bytes()) {
+        Some(rest) => rest.is_empty() || rest.starts_with(b"/"),
+        None => false,
+    }
+}
+
+/// Returns true if the commit changed any path that is matched by the predicate,
+/// the tree diff stops at the first matched path
+fn is_commit_touches(
+    repo: &gix::Repository,
+    commit_info: &gix::revision::walk::Info,
+    mut is_matched: impl FnMut(&gix::bstr::BStr) -> bool,
+) -> Result<bool, String> {
+    let commit = commit_info
+        .id()
+        .object()
+        .map_err(|error| error.to_string())?
+        .into_commit();
+    let current = commit.tree().map_err(|error| error.to_string())?;
+    let previous = commit_info
+        .parent_ids()
+        .next()
+        .map(|id| id.object().unwrap().into_commit().tree().unwrap())
+        .unwrap_or_else(|| repo.empty_tree());
+
+    let mut is_touched = false;
+    let diff_result = previous
+        .changes()
+        .map_err(|error| error.to_string())?
+        .track_path()
+        .track_rewrites(None)
+        .for_each_to_obtain_tree(&current, |change| {
+            if is_matched(change.location) {
+                is_touched = true;
+                return Ok::<_, std::convert::Infallible>(gix::object::tree::diff::Action::Cancel);
+            }
+            Ok(gix::object::tree::diff::Action::Continue)
+        });
+
+    // Cancelling the diff after the first match is reported as error
+    if is_touched {
+        return Ok(true);
+    }
+
+    diff_result.map_err(|error| error.to_string())?;
+    Ok(false)
 }
 
 fn select_branches(
@@ -237,6 +670,9 @@ fn select_branches(
     for branch in local_and_remote_branches.flatten() {
         let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
 
+        // Upstream and the ahead, behind counts are resolved only once per row and only if selected
+        let mut upstream: Option<Option<BranchUpstream>> = None;
+
         for index in 0..names_len {
             let field_name = &fields_names[index as usize];
 
@@ -283,6 +719,20 @@ fn select_branches(
                 continue;
             }
 
+            if field_name == "upstream" || field_name == "ahead" || field_name == "behind" {
+                let upstream =
+                    upstream.get_or_insert_with(|| BranchUpstream::resolve(repo, &branch));
+                let value = match upstream {
+                    Some(upstream) if field_name == "upstream" => {
+                        Value::Text(upstream.name.to_string())
+                    }
+                    Some(upstream) => upstream.ahead_or_behind(repo, field_name == "ahead")?,
+                    None => Value::Null,
+                };
+                values.push(value);
+                continue;
+            }
+
             if field_name == "repo" {
                 values.push(Value::Text(repo_path.to_string()));
                 continue;
@@ -298,6 +748,160 @@ fn select_branches(
     Ok(Group { rows })
 }
 
+/// Remote tracking branch of local branch configured by `branch.<name>.remote` and `branch.<name>.merge`
+struct BranchUpstream {
+    name: String,
+    branch_id: Option<gix::ObjectId>,
+    upstream_id: Option<gix::ObjectId>,
+    ahead_behind: Option<(usize, usize)>,
+}
+
+impl BranchUpstream {
+    /// Returns None for remote branches and local branches without upstream
+    fn resolve(repo: &gix::Repository, branch: &gix::Reference) -> Option<BranchUpstream> {
+        let (category, short_name) = branch.name().category_and_short_name()?;
+        if category != Category::LocalBranch {
+            return None;
+        }
+
+        let config = repo.config_snapshot();
+        let remote = config
+            .string(format!("branch.{}.remote", short_name).as_str())?
+            .to_string();
+        let merge = config
+            .string(format!("branch.{}.merge", short_name).as_str())?
+            .to_string();
+        let merge_branch = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+
+        // Remote `.` means that the upstream is another local branch
+        let (name, full_name) = if remote == "." {
+            (
+                merge_branch.to_string(),
+                format!("refs/heads/{}", merge_branch),
+            )
+        } else {
+            let name = format!("{}/{}", remote, merge_branch);
+            let full_name = format!("refs/remotes/{}", name);
+            (name, full_name)
+        };
+
+        let upstream_id = repo
+            .try_find_reference(full_name.as_str())
+            .ok()
+            .flatten()
+            .and_then(|mut reference| reference.peel_to_id_in_place().ok())
+            .map(|id| id.detach());
+
+        Some(BranchUpstream {
+            name,
+            branch_id: branch.try_id().map(|id| id.detach()),
+            upstream_id,
+            ahead_behind: None,
+        })
+    }
+
+    /// Returns the number of commits that are only in the branch, or only in the upstream if not ahead,
+    /// the value is Null if the upstream branch is not fetched
+    fn ahead_or_behind(&mut self, repo: &gix::Repository, is_ahead: bool) -> Result<Value, String> {
+        let (Some(branch_id), Some(upstream_id)) = (self.branch_id, self.upstream_id) else {
+            return Ok(Value::Null);
+        };
+
+        if self.ahead_behind.is_none() {
+            self.ahead_behind = Some(count_ahead_behind(repo, branch_id, upstream_id)?);
+        }
+
+        let (ahead, behind) = self.ahead_behind.unwrap_or_default();
+        let count = if is_ahead { ahead } else { behind };
+        Ok(Value::Integer(count as i64))
+    }
+}
+
+fn collect_ancestors(
+    repo: &gix::Repository,
+    id: gix::ObjectId,
+) -> Result<HashSet<gix::ObjectId>, String> {
+    let revwalk = repo
+        .rev_walk([id])
+        .all()
+        .map_err(|error| error.to_string())?;
+
+    let mut ancestors = HashSet::new();
+    for info in revwalk {
+        let info = info.map_err(|error| error.to_string())?;
+        ancestors.insert(info.id);
+    }
+    Ok(ancestors)
+}
+
+/// Mark the commits that are reachable from the branch, the upstream or both of them
+const REACHABLE_FROM_BRANCH: u8 = 1;
+const REACHABLE_FROM_UPSTREAM: u8 = 2;
+const REACHABLE_FROM_BOTH: u8 = REACHABLE_FROM_BRANCH | REACHABLE_FROM_UPSTREAM;
+
+/// Count the commits that are only reachable from the branch and only reachable from the upstream,
+/// the commits are walked from the newest to the oldest and the walk stops at the merge base
+/// when all the remaining commits are reachable from both of them
+fn count_ahead_behind(
+    repo: &gix::Repository,
+    branch_id: gix::ObjectId,
+    upstream_id: gix::ObjectId,
+) -> Result<(usize, usize), String> {
+    let mut marks: HashMap<gix::ObjectId, u8> = HashMap::new();
+    let mut queue: BinaryHeap<(i64, gix::ObjectId)> = BinaryHeap::new();
+
+    for (id, mark) in [
+        (branch_id, REACHABLE_FROM_BRANCH),
+        (upstream_id, REACHABLE_FROM_UPSTREAM),
+    ] {
+        *marks.entry(id).or_default() |= mark;
+        queue.push((commit_seconds(repo, id)?, id));
+    }
+
+    while queue
+        .iter()
+        .any(|(_, id)| marks.get(id) != Some(&REACHABLE_FROM_BOTH))
+    {
+        let Some((_, id)) = queue.pop() else {
+            break;
+        };
+
+        let mark = marks.get(&id).copied().unwrap_or_default();
+        let commit = find_commit(repo, id)?;
+        for parent_id in commit.parent_ids() {
+            let parent_id = parent_id.detach();
+            let parent_mark = marks.entry(parent_id).or_default();
+            if *parent_mark | mark != *parent_mark {
+                *parent_mark |= mark;
+                queue.push((commit_seconds(repo, parent_id)?, parent_id));
+            }
+        }
+    }
+
+    let ahead = marks
+        .values()
+        .filter(|mark| **mark == REACHABLE_FROM_BRANCH)
+        .count();
+    let behind = marks
+        .values()
+        .filter(|mark| **mark == REACHABLE_FROM_UPSTREAM)
+        .count();
+    Ok((ahead, behind))
+}
+
+fn find_commit(repo: &gix::Repository, id: gix::ObjectId) -> Result<gix::Commit<'_>, String> {
+    repo.find_object(id)
+        .map_err(|error| error.to_string())?
+        .try_into_commit()
+        .map_err(|error| error.to_string())
+}
+
+fn commit_seconds(repo: &gix::Repository, id: gix::ObjectId) -> Result<i64, String> {
+    let commit = find_commit(repo, id)?;
+    let time = commit.time().map_err(|error| error.to_string())?;
+    Ok(time.seconds)
+}
+
 fn select_diffs(
     env: &mut Environment,
     repo: &gix::Repository,
@@ -312,6 +916,7 @@ fn select_diffs(
     };
 
     let mut rows: Vec<Row> = vec![];
+    let mut memory = MemoryBudget::default();
     let revwalk = repo.head_id().unwrap().ancestors().all().unwrap();
     let repo_path = repo.path().to_str().unwrap().to_string();
 
@@ -319,14 +924,17 @@ fn select_diffs(
         .diff_resource_cache(gix::diff::blob::pipeline::Mode::ToGit, Default::default())
         .unwrap();
     let mut diff_cache = rewrite_cache.clone();
+    let rewrites = diff_rewrites(env);
 
     let names_len = fields_names.len() as i64;
     let values_len = fields_values.len() as i64;
     let padding = names_len - values_len;
 
+    let mut progress = scan_progress_tracker("diffs", &repo);
     for commit_info in revwalk {
         let commit_info = commit_info.unwrap();
         let commit = commit_info.id().object().unwrap().into_commit();
+        progress.update(rows.len());
 
         let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
 
@@ -386,6 +994,7 @@ fn select_diffs(
                 previous
                     .changes()
                     .unwrap()
+                    .track_rewrites(rewrites)
                     .for_each_to_obtain_tree_with_cache(
                         &current,
                         &mut rewrite_cache,
@@ -424,153 +1033,1282 @@ fn select_diffs(
         }
 
         let row = Row { values };
+        memory.add_row(&row)?;
         rows.push(row);
     }
 
     Ok(Group { rows })
 }
 
-fn select_tags(
+fn select_diff_files(
     env: &mut Environment,
     repo: &gix::Repository,
-    fields_names: &Vec<String>,
+    fields_names: &[String],
     titles: &[String],
     fields_values: &[Box<dyn Expression>],
 ) -> Result<Group, String> {
-    let platform = repo.references().unwrap();
-    let tag_names = platform.tags().unwrap();
+    let repo = {
+        let mut repo = repo.clone();
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+        repo
+    };
+
+    let mut rows: Vec<Row> = vec![];
+    let mut memory = MemoryBudget::default();
+    let head_id = repo.head_id();
+    if head_id.is_err() {
+        return Ok(Group { rows });
+    }
+
+    let revwalk = head_id.unwrap().ancestors().all().unwrap();
     let repo_path = repo.path().to_str().unwrap().to_string();
 
+    let mut rewrite_cache = repo
+        .diff_resource_cache(gix::diff::blob::pipeline::Mode::ToGit, Default::default())
+        .unwrap();
+    let mut diff_cache = rewrite_cache.clone();
+
+    let select_insertions_or_deletions = fields_names
+        .iter()
+        .any(|name| name == "insertions" || name == "deletions");
+    let rewrites = diff_rewrites(env);
+
     let names_len = fields_names.len() as i64;
     let values_len = fields_values.len() as i64;
     let padding = names_len - values_len;
 
-    let mut rows: Vec<Row> = vec![];
+    let mut progress = scan_progress_tracker("diff_files", &repo);
+    for commit_info in revwalk {
+        let commit_info = commit_info.unwrap();
+        let commit = commit_info.id().object().unwrap().into_commit();
+        progress.update(rows.len());
+
+        let current = commit.tree().unwrap();
+        let previous = commit_info
+            .parent_ids()
+            .next()
+            .map(|id| id.object().unwrap().into_commit().tree().unwrap())
+            .unwrap_or_else(|| repo.empty_tree());
+
+        rewrite_cache.clear_resource_cache();
+        diff_cache.clear_resource_cache();
+
+        // Collect path, status, renamed from path, insertions and deletions for each changed file
+        let mut changed_files: Vec<(String, &str, Option<String>, usize, usize)> = vec![];
+
+        previous
+            .changes()
+            .unwrap()
+            .track_path()
+            .track_rewrites(rewrites)
+            .for_each_to_obtain_tree_with_cache(
+                &current,
+                &mut rewrite_cache,
+                |change| -> Result<_, std::convert::Infallible> {
+                    if !change.event.entry_mode().is_no_tree() {
+                        return Ok(gix::object::tree::diff::Action::Continue);
+                    }
 
-    for tag_ref in tag_names.flatten() {
-        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+                    let (status, renamed_from) = match change.event {
+                        gix::object::tree::diff::change::Event::Addition { .. } => ("added", None),
+                        gix::object::tree::diff::change::Event::Deletion { .. } => {
+                            ("deleted", None)
+                        }
+                        gix::object::tree::diff::change::Event::Modification { .. } => {
+                            ("modified", None)
+                        }
+                        gix::object::tree::diff::change::Event::Rewrite {
+                            source_location, ..
+                        } => ("renamed", Some(source_location.to_string())),
+                    };
+
+                    let (mut insertions, mut deletions) = (0, 0);
+                    if select_insertions_or_deletions {
+                        if let Ok(mut platform) = change.diff(&mut diff_cache) {
+                            if let Ok(Some(counts)) = platform.line_counts() {
+                                insertions = counts.insertions as usize;
+                                deletions = counts.removals as usize;
+                            }
+                        }
+                    }
 
-        for index in 0..names_len {
-            let field_name = &fields_names[index as usize];
-            if (index - padding) >= 0 {
-                let value = &fields_values[(index - padding) as usize];
+                    changed_files.push((
+                        change.location.to_string(),
+                        status,
+                        renamed_from,
+                        insertions,
+                        deletions,
+                    ));
+                    Ok(gix::object::tree::diff::Action::Continue)
+                },
+            )
+            .unwrap();
+
+        for (path, status, renamed_from, insertions, deletions) in changed_files {
+            let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+            for index in 0..names_len {
+                let field_name = &fields_names[index as usize];
+
+                if (index - padding) >= 0 {
+                    let value = &fields_values[(index - padding) as usize];
+                    if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                        let evaluated = evaluate_expression(env, value, titles, &values)?;
+                        values.push(evaluated);
+                        continue;
+                    }
+                }
 
-                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
-                    let evaluated = evaluate_expression(env, value, titles, &values)?;
-                    values.push(evaluated);
+                if field_name == "commit_id" {
+                    values.push(Value::Text(commit_info.id.to_string()));
                     continue;
                 }
-            }
 
-            if field_name == "name" {
-                let tag_name = tag_ref
-                    .name()
-                    .category_and_short_name()
-                    .map_or_else(String::default, |(_, short_name)| short_name.to_string());
-                values.push(Value::Text(tag_name.to_string()));
-                continue;
-            }
+                if field_name == "name" {
+                    let name = commit.author().unwrap().name.to_string();
+                    values.push(Value::Text(name));
+                    continue;
+                }
 
-            if field_name == "repo" {
-                values.push(Value::Text(repo_path.to_string()));
-                continue;
+                if field_name == "email" {
+                    let email = commit.author().unwrap().email.to_string();
+                    values.push(Value::Text(email));
+                    continue;
+                }
+
+                if field_name == "datetime" {
+                    let time_stamp = commit_info
+                        .commit_time
+                        .unwrap_or_else(|| commit.time().unwrap().seconds);
+                    values.push(Value::DateTime(time_stamp));
+                    continue;
+                }
+
+                if field_name == "path" {
+                    values.push(Value::Text(path.to_string()));
+                    continue;
+                }
+
+                if field_name == "status" {
+                    values.push(Value::Text(status.to_string()));
+                    continue;
+                }
+
+                if field_name == "renamed_from" {
+                    match &renamed_from {
+                        Some(source_path) => values.push(Value::Text(source_path.to_string())),
+                        None => values.push(Value::Null),
+                    }
+                    continue;
+                }
+
+                if field_name == "insertions" {
+                    values.push(Value::Integer(insertions as i64));
+                    continue;
+                }
+
+                if field_name == "deletions" {
+                    values.push(Value::Integer(deletions as i64));
+                    continue;
+                }
+
+                if field_name == "repo" {
+                    values.push(Value::Text(repo_path.to_string()));
+                    continue;
+                }
+
+                values.push(Value::Null);
+            }
+
+            let row = Row { values };
+            memory.add_row(&row)?;
+            rows.push(row);
+        }
+    }
+
+    Ok(Group { rows })
+}
+
+fn select_tags(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    fields_names: &Vec<String>,
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let platform = repo.references().unwrap();
+    let tag_names = platform.tags().unwrap();
+    let repo_path = repo.path().to_str().unwrap().to_string();
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    let mut rows: Vec<Row> = vec![];
+
+    for tag_ref in tag_names.flatten() {
+        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+        let tag_info = TagInfo::new(&tag_ref);
+        let mut verification: Option<SignatureVerification> = None;
+
+        for index in 0..names_len {
+            let field_name = &fields_names[index as usize];
+            if (index - padding) >= 0 {
+                let value = &fields_values[(index - padding) as usize];
+
+                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                    let evaluated = evaluate_expression(env, value, titles, &values)?;
+                    values.push(evaluated);
+                    continue;
+                }
+            }
+
+            if field_name == "name" {
+                let tag_name = tag_ref
+                    .name()
+                    .category_and_short_name()
+                    .map_or_else(String::default, |(_, short_name)| short_name.to_string());
+                values.push(Value::Text(tag_name.to_string()));
+                continue;
+            }
+
+            if field_name == "target_commit_id" {
+                values.push(
+                    tag_info
+                        .target_commit_id
+                        .clone()
+                        .map_or(Value::Null, Value::Text),
+                );
+                continue;
+            }
+
+            if field_name == "is_annotated" {
+                values.push(Value::Boolean(tag_info.is_annotated));
+                continue;
+            }
+
+            if field_name == "message" {
+                values.push(tag_info.message.clone().map_or(Value::Null, Value::Text));
+                continue;
+            }
+
+            if field_name == "tagger_name" {
+                values.push(
+                    tag_info
+                        .tagger_name
+                        .clone()
+                        .map_or(Value::Null, Value::Text),
+                );
+                continue;
+            }
+
+            if field_name == "tagger_email" {
+                values.push(
+                    tag_info
+                        .tagger_email
+                        .clone()
+                        .map_or(Value::Null, Value::Text),
+                );
+                continue;
+            }
+
+            if field_name == "created_datetime" {
+                let created_datetime = tag_info
+                    .created_datetime
+                    .map_or(Value::Null, Value::DateTime);
+                values.push(created_datetime);
+                continue;
+            }
+
+            if field_name == "is_signed" {
+                values.push(Value::Boolean(tag_info.signed.is_some()));
+                continue;
+            }
+
+            if field_name == "signer" || field_name == "signature_status" {
+                let verification =
+                    verification.get_or_insert_with(|| verify_signature(tag_info.signed.as_ref()));
+                values.push(signature_verification_value(verification, field_name));
+                continue;
+            }
+
+            if field_name == "repo" {
+                values.push(Value::Text(repo_path.to_string()));
+                continue;
+            }
+
+            values.push(Value::Null);
+        }
+
+        let row = Row { values };
+        rows.push(row);
+    }
+
+    Ok(Group { rows })
+}
+
+/// Target and annotation of the tag with the values of the `tags` table,
+/// lightweight tags point directly to the commit so they have no message, tagger or signature
+struct TagInfo {
+    target_commit_id: Option<String>,
+    is_annotated: bool,
+    message: Option<String>,
+    tagger_name: Option<String>,
+    tagger_email: Option<String>,
+    created_datetime: Option<i64>,
+    signed: Option<SignedData>,
+}
+
+impl TagInfo {
+    fn new(tag_ref: &gix::Reference) -> Self {
+        let mut tag_info = TagInfo {
+            target_commit_id: None,
+            is_annotated: false,
+            message: None,
+            tagger_name: None,
+            tagger_email: None,
+            created_datetime: None,
+            signed: None,
+        };
+
+        let Some(object) = tag_ref.try_id().and_then(|id| id.object().ok()) else {
+            return tag_info;
+        };
+
+        if object.kind == gix::object::Kind::Tag {
+            tag_info.is_annotated = true;
+            tag_info.signed = extract_tag_signature(&object.data);
+            if let Ok(tag) = object.try_to_tag_ref() {
+                tag_info.message = Some(tag.message.to_string().trim_end().to_string());
+                if let Some(tagger) = tag.tagger {
+                    tag_info.tagger_name = Some(tagger.name.to_string());
+                    tag_info.tagger_email = Some(tagger.email.to_string());
+                    tag_info.created_datetime = Some(tagger.time.seconds);
+                }
+            }
+        }
+
+        // Annotated tags can point to other tags, trees or blobs, only commits targets are reported
+        tag_info.target_commit_id = object
+            .peel_to_kind(gix::object::Kind::Commit)
+            .ok()
+            .map(|commit| commit.id.to_string());
+        tag_info
+    }
+}
+
+/// Rename detection options of the diffs from `@@rename_similarity` session variable,
+/// 0 disables the detection and 100 detects only renamed files with identical content
+fn diff_rewrites(env: &Environment) -> Option<gix::diff::Rewrites> {
+    match rename_similarity(env) {
+        0 => None,
+        100 => Some(gix::diff::Rewrites {
+            percentage: None,
+            ..Default::default()
+        }),
+        similarity => Some(gix::diff::Rewrites {
+            percentage: Some(similarity as f32 / 100.0),
+            ..Default::default()
+        }),
+    }
+}
+
+fn rename_similarity(env: &Environment) -> u8 {
+    session_variable_value(env, RENAME_SIMILARITY_VARIABLE)
+        .and_then(|value| parse_rename_similarity(&value))
+        .unwrap_or(DEFAULT_RENAME_SIMILARITY)
+}
+
+fn is_default_rename_similarity(env: &Environment) -> bool {
+    rename_similarity(env) == DEFAULT_RENAME_SIMILARITY
+}
+
+/// Returns the value of `signer` or `signature_status` field from the signature verification
+fn signature_verification_value(verification: &SignatureVerification, field_name: &str) -> Value {
+    if field_name == "signature_status" {
+        return Value::Text(verification.status.to_string());
+    }
+
+    match &verification.signer {
+        Some(signer) => Value::Text(signer.to_string()),
+        None => Value::Null,
+    }
+}
+
+/// Worktree of the repository with the values of the `worktrees` table
+struct WorktreeInfo {
+    name: String,
+    path: Option<String>,
+    branch: Option<String>,
+    commit_id: Option<String>,
+    is_main: bool,
+    is_locked: bool,
+}
+
+impl WorktreeInfo {
+    fn new(repo: &gix::Repository, name: String, path: Option<String>, is_main: bool) -> Self {
+        WorktreeInfo {
+            name,
+            path,
+            branch: repo
+                .head_name()
+                .ok()
+                .flatten()
+                .map(|name| name.shorten().to_string()),
+            commit_id: repo.head_id().ok().map(|id| id.to_string()),
+            is_main,
+            is_locked: false,
+        }
+    }
+}
+
+/// Collect the main worktree and the linked worktrees, the repository may be opened from any of them
+/// so they are resolved from the main repository, bare repositories have only linked worktrees
+fn collect_worktrees(repo: &gix::Repository) -> Result<Vec<WorktreeInfo>, String> {
+    let main_repo = repo.main_repo().map_err(|error| error.to_string())?;
+    let mut worktrees = vec![];
+
+    if let Some(work_dir) = main_repo.work_dir() {
+        let name = work_dir
+            .file_name()
+            .map_or_else(String::default, |name| name.to_string_lossy().to_string());
+        let path = Some(work_dir.to_string_lossy().to_string());
+        worktrees.push(WorktreeInfo::new(&main_repo, name, path, true));
+    }
+
+    for proxy in main_repo.worktrees().map_err(|error| error.to_string())? {
+        let name = proxy.id().to_string();
+        let path = proxy
+            .base()
+            .ok()
+            .map(|path| path.to_string_lossy().to_string());
+        let is_locked = proxy.is_locked();
+
+        // Worktree directory may be deleted without pruning, its HEAD is still readable from the git dir
+        let mut worktree = match proxy.into_repo_with_possibly_inaccessible_worktree() {
+            Ok(linked_repo) => WorktreeInfo::new(&linked_repo, name, path, false),
+            Err(_) => WorktreeInfo {
+                name,
+                path,
+                branch: None,
+                commit_id: None,
+                is_main: false,
+                is_locked: false,
+            },
+        };
+        worktree.is_locked = is_locked;
+        worktrees.push(worktree);
+    }
+
+    Ok(worktrees)
+}
+
+fn select_worktrees(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let repo_path = repo.path().to_str().unwrap_or("").to_string();
+    let worktrees = collect_worktrees(repo)?;
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    let mut rows: Vec<Row> = Vec::with_capacity(worktrees.len());
+    for worktree in worktrees {
+        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+        for index in 0..names_len {
+            let field_name = &fields_names[index as usize];
+            if (index - padding) >= 0 {
+                let value = &fields_values[(index - padding) as usize];
+
+                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                    let evaluated = evaluate_expression(env, value, titles, &values)?;
+                    values.push(evaluated);
+                    continue;
+                }
+            }
+
+            let value = match field_name.as_str() {
+                "name" => Value::Text(worktree.name.to_string()),
+                "path" => worktree.path.clone().map_or(Value::Null, Value::Text),
+                "branch" => worktree.branch.clone().map_or(Value::Null, Value::Text),
+                "commit_id" => worktree.commit_id.clone().map_or(Value::Null, Value::Text),
+                "is_main" => Value::Boolean(worktree.is_main),
+                "is_locked" => Value::Boolean(worktree.is_locked),
+                "repo" => Value::Text(repo_path.to_string()),
+                _ => Value::Null,
+            };
+            values.push(value);
+        }
+
+        rows.push(Row { values });
+    }
+
+    Ok(Group { rows })
+}
+
+fn select_files(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let mut rows: Vec<Row> = vec![];
+    let mut memory = MemoryBudget::default();
+
+    let head_commit = repo.head_commit();
+    if head_commit.is_err() {
+        return Ok(Group { rows });
+    }
+
+    let head_tree = head_commit
+        .unwrap()
+        .tree()
+        .map_err(|error| error.to_string())?;
+
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    head_tree
+        .traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|error| error.to_string())?;
+
+    let repo_path = repo.path().to_str().unwrap().to_string();
+
+    // Blob content and history are only loaded when their fields are selected
+    let select_blob_content = fields_names
+        .iter()
+        .any(|name| name == "size" || name == "is_binary");
+
+    let last_modified_commits = if fields_names.contains(&"last_modified_commit".to_string()) {
+        let paths = recorder
+            .records
+            .iter()
+            .filter(|entry| entry.mode.is_no_tree())
+            .map(|entry| entry.filepath.to_string())
+            .collect();
+        select_last_modified_commits(repo, paths)?
+    } else {
+        HashMap::new()
+    };
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    for entry in recorder.records.iter() {
+        if !entry.mode.is_no_tree() {
+            continue;
+        }
+
+        let path = entry.filepath.to_string();
+        let content = if select_blob_content {
+            repo.find_object(entry.oid)
+                .ok()
+                .map(|object| object.detach().data)
+        } else {
+            None
+        };
+
+        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+        for index in 0..names_len {
+            let field_name = &fields_names[index as usize];
+
+            if (index - padding) >= 0 {
+                let value = &fields_values[(index - padding) as usize];
+                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                    let evaluated = evaluate_expression(env, value, titles, &values)?;
+                    values.push(evaluated);
+                    continue;
+                }
+            }
+
+            if field_name == "path" {
+                values.push(Value::Text(path.to_string()));
+                continue;
+            }
+
+            if field_name == "extension" {
+                let extension = std::path::Path::new(&path)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_string());
+                values.push(extension.map_or(Value::Null, Value::Text));
+                continue;
+            }
+
+            if field_name == "size" {
+                let size = content.as_ref().map(|data| data.len() as i64);
+                values.push(size.map_or(Value::Null, Value::Integer));
+                continue;
+            }
+
+            if field_name == "is_binary" {
+                let is_binary = content.as_ref().map(|data| is_binary_content(data));
+                values.push(is_binary.map_or(Value::Null, Value::Boolean));
+                continue;
+            }
+
+            if field_name == "last_modified_commit" {
+                let commit_id = last_modified_commits.get(&path).cloned();
+                values.push(commit_id.map_or(Value::Null, Value::Text));
+                continue;
+            }
+
+            if field_name == "repo" {
+                values.push(Value::Text(repo_path.to_string()));
+                continue;
+            }
+
+            values.push(Value::Null);
+        }
+
+        let row = Row { values };
+        memory.add_row(&row)?;
+        rows.push(row);
+    }
+
+    Ok(Group { rows })
+}
+
+/// Walk the history from HEAD and map each path to the latest commit that changed it,
+/// stop walking once all paths are resolved
+fn select_last_modified_commits(
+    repo: &gix::Repository,
+    mut paths: HashSet<String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut last_modified_commits: HashMap<String, String> = HashMap::new();
+    let revwalk = repo
+        .head_id()
+        .map_err(|error| error.to_string())?
+        .ancestors()
+        .all()
+        .map_err(|error| error.to_string())?;
+
+    for commit_info in revwalk {
+        if paths.is_empty() {
+            break;
+        }
+
+        let commit_info = commit_info.map_err(|error| error.to_string())?;
+        let commit = commit_info.id().object().unwrap().into_commit();
+        let current = commit.tree().map_err(|error| error.to_string())?;
+        let previous = commit_info
+            .parent_ids()
+            .next()
+            .map(|id| id.object().unwrap().into_commit().tree().unwrap())
+            .unwrap_or_else(|| repo.empty_tree());
+
+        let commit_id = commit_info.id.to_string();
+        previous
+            .changes()
+            .map_err(|error| error.to_string())?
+            .track_path()
+            .track_rewrites(None)
+            .for_each_to_obtain_tree(&current, |change| {
+                let path = change.location.to_string();
+                if paths.remove(&path) {
+                    last_modified_commits.insert(path, commit_id.to_string());
+                }
+                Ok::<_, std::convert::Infallible>(gix::object::tree::diff::Action::Continue)
+            })
+            .map_err(|error| error.to_string())?;
+    }
+
+    Ok(last_modified_commits)
+}
+
+/// Same heuristic as git, content is binary if it has a null byte in the first 8000 bytes
+fn is_binary_content(data: &[u8]) -> bool {
+    data.iter().take(8000).any(|byte| *byte == 0)
+}
+
+/// Git LFS pointer files are small text files that start with the LFS spec version line
+fn is_lfs_pointer_content(data: &[u8]) -> bool {
+    data.len() < 1024 && data.starts_with(b"version https://git-lfs.github.com/spec/v1")
+}
+
+fn select_blobs(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let mut rows: Vec<Row> = vec![];
+    let mut memory = MemoryBudget::default();
+
+    let revision = match env.globals.get(BLOBS_REVISION_VARIABLE) {
+        Some(Value::Text(revision)) => revision.to_string(),
+        Some(_) => {
+            return Err(format!(
+                "Global variable `{}` must be a revision text",
+                BLOBS_REVISION_VARIABLE
+            ))
+        }
+        None => "HEAD".to_string(),
+    };
+
+    let revision_id = repo.rev_parse_single(revision.as_str());
+    if revision_id.is_err() {
+        // Repository without commits has no blobs at `HEAD`
+        if revision == "HEAD" {
+            return Ok(Group { rows });
+        }
+
+        return Err(format!(
+            "Can't resolve revision `{}` in repository `{}`",
+            revision,
+            repo.path().display()
+        ));
+    }
+
+    let tree = revision_id
+        .unwrap()
+        .object()
+        .map_err(|error| error.to_string())?
+        .peel_to_tree()
+        .map_err(|error| error.to_string())?;
+
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    tree.traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|error| error.to_string())?;
+
+    let repo_path = repo.path().to_str().unwrap().to_string();
+
+    // Blob content is only loaded when one of its fields is selected
+    let select_blob_content = fields_names
+        .iter()
+        .any(|name| name == "size" || name == "is_binary" || name == "lfs_pointer");
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    for entry in recorder.records.iter() {
+        if !entry.mode.is_blob() {
+            continue;
+        }
+
+        let content = if select_blob_content {
+            repo.find_object(entry.oid)
+                .ok()
+                .map(|object| object.detach().data)
+        } else {
+            None
+        };
+
+        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+        for index in 0..names_len {
+            let field_name = &fields_names[index as usize];
+
+            if (index - padding) >= 0 {
+                let value = &fields_values[(index - padding) as usize];
+                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                    let evaluated = evaluate_expression(env, value, titles, &values)?;
+                    values.push(evaluated);
+                    continue;
+                }
+            }
+
+            if field_name == "path" {
+                values.push(Value::Text(entry.filepath.to_string()));
+                continue;
+            }
+
+            if field_name == "size" {
+                let size = content.as_ref().map(|data| data.len() as i64);
+                values.push(size.map_or(Value::Null, Value::Integer));
+                continue;
+            }
+
+            if field_name == "is_binary" {
+                let is_binary = content.as_ref().map(|data| is_binary_content(data));
+                values.push(is_binary.map_or(Value::Null, Value::Boolean));
+                continue;
+            }
+
+            if field_name == "lfs_pointer" {
+                let is_lfs_pointer = content.as_ref().map(|data| is_lfs_pointer_content(data));
+                values.push(is_lfs_pointer.map_or(Value::Null, Value::Boolean));
+                continue;
+            }
+
+            if field_name == "revision" {
+                values.push(Value::Text(revision.to_string()));
+                continue;
+            }
+
+            if field_name == "repo" {
+                values.push(Value::Text(repo_path.to_string()));
+                continue;
+            }
+
+            values.push(Value::Null);
+        }
+
+        let row = Row { values };
+        memory.add_row(&row)?;
+        rows.push(row);
+    }
+
+    Ok(Group { rows })
+}
+
+fn select_blame(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let mut rows: Vec<Row> = vec![];
+
+    let path = fields_names
+        .iter()
+        .find_map(|name| name.strip_prefix(BLAME_PATH_FIELD_PREFIX));
+    let blamed_files = blame_head_files(repo, path)?;
+    let repo_path = repo.path().to_str().unwrap().to_string();
+
+    // Author and date of each blamed commit are loaded once
+    let mut commits_authors: HashMap<gix::ObjectId, (String, String, i64)> = HashMap::new();
+    let select_author = fields_names
+        .iter()
+        .any(|name| name == "author_name" || name == "author_email" || name == "datetime");
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    for blamed_file in blamed_files {
+        let lines = blamed_file
+            .content
+            .split_inclusive(|byte| *byte == b'\n')
+            .zip(&blamed_file.lines_commits);
+
+        for (line_index, (line, commit_id)) in lines.enumerate() {
+            let author = match commit_id {
+                Some(commit_id) if select_author => {
+                    if !commits_authors.contains_key(commit_id) {
+                        let commit = repo
+                            .find_object(*commit_id)
+                            .map_err(|error| error.to_string())?
+                            .into_commit();
+                        let signature = commit.author().map_err(|error| error.to_string())?;
+                        let time = commit.time().map_err(|error| error.to_string())?;
+                        commits_authors.insert(
+                            *commit_id,
+                            (
+                                signature.name.to_string(),
+                                signature.email.to_string(),
+                                time.seconds,
+                            ),
+                        );
+                    }
+                    commits_authors.get(commit_id)
+                }
+                _ => None,
+            };
+
+            let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+            for index in 0..names_len {
+                let field_name = &fields_names[index as usize];
+
+                if (index - padding) >= 0 {
+                    let value = &fields_values[(index - padding) as usize];
+                    if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                        let evaluated = evaluate_expression(env, value, titles, &values)?;
+                        values.push(evaluated);
+                        continue;
+                    }
+                }
+
+                if field_name == "path" {
+                    values.push(Value::Text(blamed_file.path.to_string()));
+                    continue;
+                }
+
+                if field_name == "line_number" {
+                    values.push(Value::Integer(line_index as i64 + 1));
+                    continue;
+                }
+
+                if field_name == "line" {
+                    let line = String::from_utf8_lossy(line);
+                    let line = line.trim_end_matches(['\n', '\r']);
+                    values.push(Value::Text(line.to_string()));
+                    continue;
+                }
+
+                if field_name == "commit_id" {
+                    let commit_id = commit_id.map(|commit_id| commit_id.to_string());
+                    values.push(commit_id.map_or(Value::Null, Value::Text));
+                    continue;
+                }
+
+                if field_name == "author_name" {
+                    let name = author.map(|(name, _, _)| name.to_string());
+                    values.push(name.map_or(Value::Null, Value::Text));
+                    continue;
+                }
+
+                if field_name == "author_email" {
+                    let email = author.map(|(_, email, _)| email.to_string());
+                    values.push(email.map_or(Value::Null, Value::Text));
+                    continue;
+                }
+
+                if field_name == "datetime" {
+                    let time_stamp = author.map(|(_, _, time_stamp)| *time_stamp);
+                    values.push(time_stamp.map_or(Value::Null, Value::DateTime));
+                    continue;
+                }
+
+                if field_name == "repo" {
+                    values.push(Value::Text(repo_path.to_string()));
+                    continue;
+                }
+
+                // Only the lines of the file with the required path are selected
+                if field_name.starts_with(BLAME_PATH_FIELD_PREFIX) {
+                    values.push(Value::Boolean(true));
+                    continue;
+                }
+
+                values.push(Value::Null);
+            }
+
+            rows.push(Row { values });
+        }
+    }
+
+    Ok(Group { rows })
+}
+
+/// Text file at `HEAD` with the commit that last changed each of its lines
+struct BlamedFile {
+    path: String,
+    content: Vec<u8>,
+    lines_commits: Vec<Option<gix::ObjectId>>,
+}
+
+/// Lines of a file at `HEAD`, each line is a pair of its position in the version of the file
+/// in the current commit and its position at `HEAD`
+type LinesPositions = Vec<(u32, u32)>;
+
+/// Unblamed lines of a file at `HEAD` with the index of the file
+type UnblamedLines = (usize, LinesPositions);
+
+/// Changed file path with its path and blob in the parent if it's not added, and its blob in the commit
+type ChangedBlob = (String, Option<(String, gix::ObjectId)>, gix::ObjectId);
+
+/// Blame the lines of all text files at `HEAD`, or only the file with the given path, in one walk over
+/// the commits ordered by their time, the lines of a commit that are unchanged from one of its parents
+/// are passed to the first of them and the lines that are changed from all parents are blamed on it,
+/// following the detected renames
+fn blame_head_files(repo: &gix::Repository, path: Option<&str>) -> Result<Vec<BlamedFile>, String> {
+    let Ok(head_commit) = repo.head_commit() else {
+        return Ok(vec![]);
+    };
+
+    let head_tree = head_commit.tree().map_err(|error| error.to_string())?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    head_tree
+        .traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|error| error.to_string())?;
+
+    // Unblamed lines of the files mapped by their path in the current commit, many files at `HEAD`
+    // can have the same path in older commits if they are copied from the same file
+    let mut files: Vec<BlamedFile> = vec![];
+    let mut unblamed: HashMap<String, Vec<UnblamedLines>> = HashMap::new();
+    for entry in recorder.records.iter() {
+        if !entry.mode.is_blob() || path.is_some_and(|path| entry.filepath != path) {
+            continue;
+        }
+
+        let content = repo
+            .find_object(entry.oid)
+            .map_err(|error| error.to_string())?
+            .detach()
+            .data;
+        if is_binary_content(&content) {
+            continue;
+        }
+
+        let lines_count = content.split_inclusive(|byte| *byte == b'\n').count() as u32;
+        if lines_count == 0 {
+            continue;
+        }
+
+        let path = entry.filepath.to_string();
+        let lines = (0..lines_count)
+            .map(|position| (position, position))
+            .collect();
+        unblamed.insert(path.to_string(), vec![(files.len(), lines)]);
+        files.push(BlamedFile {
+            path,
+            content,
+            lines_commits: vec![None; lines_count as usize],
+        });
+    }
+
+    if unblamed.is_empty() {
+        return Ok(files);
+    }
+
+    let mut rewrite_cache = repo
+        .diff_resource_cache(gix::diff::blob::pipeline::Mode::ToGit, Default::default())
+        .map_err(|error| error.to_string())?;
+
+    // Commits are visited from the newest, so a commit reached from many children (for example the base
+    // of a merged branch) gets the unblamed lines from all of them before it's visited
+    let head_time = head_commit
+        .time()
+        .map_err(|error| error.to_string())?
+        .seconds;
+    let mut pending: HashMap<gix::ObjectId, HashMap<String, Vec<UnblamedLines>>> = HashMap::new();
+    let mut queue: BinaryHeap<(i64, gix::ObjectId)> = BinaryHeap::new();
+    pending.insert(head_commit.id, unblamed);
+    queue.push((head_time, head_commit.id));
+
+    while let Some((_, commit_id)) = queue.pop() {
+        let Some(mut unblamed) = pending.remove(&commit_id) else {
+            continue;
+        };
+
+        let commit = repo
+            .find_object(commit_id)
+            .map_err(|error| error.to_string())?
+            .into_commit();
+
+        // Lines of the root commit or the boundary of shallow clone are blamed on it
+        let parents: Vec<gix::Commit<'_>> = commit
+            .parent_ids()
+            .filter_map(|parent_id| parent_id.object().ok())
+            .map(|parent| parent.into_commit())
+            .collect();
+
+        let tree = commit.tree().map_err(|error| error.to_string())?;
+        for parent in parents {
+            if unblamed.is_empty() {
+                break;
+            }
+
+            let parent_tree = parent.tree().map_err(|error| error.to_string())?;
+            rewrite_cache.clear_resource_cache();
+
+            let mut changes: Vec<ChangedBlob> = vec![];
+            parent_tree
+                .changes()
+                .map_err(|error| error.to_string())?
+                .track_path()
+                .for_each_to_obtain_tree_with_cache(&tree, &mut rewrite_cache, |change| {
+                    let location = change.location.to_string();
+                    if !change.event.entry_mode().is_blob() || !unblamed.contains_key(&location) {
+                        return Ok::<_, std::convert::Infallible>(
+                            gix::object::tree::diff::Action::Continue,
+                        );
+                    }
+
+                    match change.event {
+                        gix::object::tree::diff::change::Event::Addition { id, .. } => {
+                            changes.push((location, None, id.detach()));
+                        }
+                        gix::object::tree::diff::change::Event::Modification {
+                            previous_entry_mode,
+                            previous_id,
+                            id,
+                            ..
+                        } => {
+                            let source = previous_entry_mode
+                                .is_blob()
+                                .then(|| (location.to_string(), previous_id.detach()));
+                            changes.push((location, source, id.detach()));
+                        }
+                        gix::object::tree::diff::change::Event::Rewrite {
+                            source_location,
+                            source_entry_mode,
+                            source_id,
+                            id,
+                            ..
+                        } => {
+                            let source = source_entry_mode
+                                .is_blob()
+                                .then(|| (source_location.to_string(), source_id.detach()));
+                            changes.push((location, source, id.detach()));
+                        }
+                        gix::object::tree::diff::change::Event::Deletion { .. } => {}
+                    }
+                    Ok(gix::object::tree::diff::Action::Continue)
+                })
+                .map_err(|error| error.to_string())?;
+
+            // Files that are not changed from the parent are passed to it with all their lines
+            let mut passed_lines: HashMap<String, Vec<UnblamedLines>> = HashMap::new();
+            let changed_locations: HashSet<&String> =
+                changes.iter().map(|(location, _, _)| location).collect();
+            let unchanged_locations: Vec<String> = unblamed
+                .keys()
+                .filter(|location| !changed_locations.contains(location))
+                .cloned()
+                .collect();
+            for location in unchanged_locations {
+                let files_lines = unblamed.remove(&location).unwrap_or_default();
+                passed_lines
+                    .entry(location)
+                    .or_default()
+                    .extend(files_lines);
+            }
+
+            // Unchanged lines of changed files are moved after all changes, a renamed file can take
+            // the path of another one, and the changed lines are kept for the next parents
+            for (location, source, id) in changes {
+                let Some(files_lines) = unblamed.remove(&location) else {
+                    continue;
+                };
+
+                let Some((source_location, source_id)) = source else {
+                    unblamed.insert(location, files_lines);
+                    continue;
+                };
+
+                let content = repo
+                    .find_object(id)
+                    .map_err(|error| error.to_string())?
+                    .detach()
+                    .data;
+                let source_content = repo
+                    .find_object(source_id)
+                    .map_err(|error| error.to_string())?
+                    .detach()
+                    .data;
+
+                let mut changed_lines: Vec<UnblamedLines> = vec![];
+                for (file_index, lines) in files_lines {
+                    let (unchanged, changed) =
+                        blame_changed_lines(&source_content, &content, lines);
+                    if !changed.is_empty() {
+                        changed_lines.push((file_index, changed));
+                    }
+
+                    if !unchanged.is_empty() {
+                        passed_lines
+                            .entry(source_location.to_string())
+                            .or_default()
+                            .push((file_index, unchanged));
+                    }
+                }
+
+                if !changed_lines.is_empty() {
+                    unblamed.insert(location, changed_lines);
+                }
             }
 
-            values.push(Value::Null);
+            if passed_lines.is_empty() {
+                continue;
+            }
+
+            if !pending.contains_key(&parent.id) {
+                let time = parent.time().map_err(|error| error.to_string())?.seconds;
+                queue.push((time, parent.id));
+            }
+
+            let parent_unblamed = pending.entry(parent.id).or_default();
+            for (location, files_lines) in passed_lines {
+                parent_unblamed
+                    .entry(location)
+                    .or_default()
+                    .extend(files_lines);
+            }
         }
 
-        let row = Row { values };
-        rows.push(row);
+        // Lines that are changed from all parents are blamed on the commit
+        for (file_index, lines) in unblamed.into_values().flatten() {
+            for (_, head_position) in lines {
+                files[file_index].lines_commits[head_position as usize] = Some(commit_id);
+            }
+        }
     }
 
-    Ok(Group { rows })
+    Ok(files)
 }
 
-fn select_values(
-    env: &mut Environment,
-    titles: &[String],
-    fields_values: &[Box<dyn Expression>],
-) -> Result<Group, String> {
-    let mut group = Group { rows: vec![] };
-    let mut values = Vec::with_capacity(fields_values.len());
+/// Diff the source content with the content of the file and split the lines into the unchanged lines
+/// with their position in the source content and the changed lines with their position in the content
+fn blame_changed_lines(
+    source_content: &[u8],
+    content: &[u8],
+    lines: LinesPositions,
+) -> (LinesPositions, LinesPositions) {
+    let input = gix::diff::blob::intern::InternedInput::new(
+        gix::diff::blob::sources::byte_lines_with_terminator(source_content),
+        gix::diff::blob::sources::byte_lines_with_terminator(content),
+    );
+
+    let mut hunks: Vec<(Range<u32>, Range<u32>)> = vec![];
+    gix::diff::blob::diff(
+        gix::diff::blob::Algorithm::Histogram,
+        &input,
+        |before: Range<u32>, after: Range<u32>| hunks.push((before, after)),
+    );
+
+    // Lines are sorted by their position, so the hunks before each line are passed once
+    let mut unchanged = Vec::with_capacity(lines.len());
+    let mut changed = vec![];
+    let mut hunks = hunks.into_iter().peekable();
+    let mut offset: i64 = 0;
+    for (position, head_position) in lines {
+        while let Some((before, after)) = hunks.peek() {
+            if after.end > position {
+                break;
+            }
 
-    for value in fields_values.iter() {
-        let evaluated = evaluate_expression(env, value, titles, &values)?;
-        values.push(evaluated);
-    }
+            offset += before.len() as i64 - after.len() as i64;
+            hunks.next();
+        }
 
-    group.rows.push(Row { values });
-    Ok(group)
-}
+        match hunks.peek() {
+            Some((_, after)) if after.start <= position => changed.push((position, head_position)),
+            _ => unchanged.push(((position as i64 + offset) as u32, head_position)),
+        }
+    }
 
-#[inline(always)]
-pub fn get_column_name(alias_table: &HashMap<String, String>, name: &str) -> String {
-    alias_table
-        .get(name)
-        .unwrap_or(&name.to_string())
-        .to_string()
+    (unchanged, changed)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine_test_repo::*;
     use gitql_ast::expression::StringExpression;
     use gitql_ast::expression::StringValueType;
 
-    fn test_new_repo(path: String) -> Result<(), String> {
-        let mut repo = gix::init_bare(path).expect("failed to init bare");
-        let mut tree = gix::objs::Tree::empty();
-        let object = repo
-            .write_object(&tree)
-            .expect("failed to write object")
-            .detach();
-
-        let mut config = repo.config_snapshot_mut();
-        config
-            .set_raw_value("author", None, "name", "name")
-            .expect("failed to set name");
-        config
-            .set_raw_value("author", None, "email", "name@example.com")
-            .expect("failed to set email");
-
-        let repo = config
-            .commit_auto_rollback()
-            .expect("failed to commit auto rollback");
-        let commit = repo
-            .commit("HEAD", "initial commit", object, gix::commit::NO_PARENT_IDS)
-            .expect("failed to commit");
-
-        let blob = repo
-            .write_blob("hello world")
-            .expect("faile to write blob")
-            .into();
-        let entry = gix::objs::tree::Entry {
-            mode: gix::objs::tree::EntryKind::Blob.into(),
-            oid: blob,
-            filename: "hello.txt".into(),
-        };
-
-        tree.entries.push(entry);
-        let object = repo.write_object(&tree).expect("failed to write object");
-
-        let _ = repo
-            .commit("HEAD", "hello commit", object, [commit])
-            .expect("failed to commit");
-
-        Ok(())
-    }
-
-    fn test_delete_repo(path: String) -> Result<(), String> {
-        std::fs::remove_dir_all(path).expect("failed to remove dir");
-        Ok(())
-    }
-
     #[test]
     fn test_select_gql_objects() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-gql-objects";
@@ -583,6 +2321,8 @@ mod tests {
         let fields_names = vec![
             "name".to_string(),
             "full_name".to_string(),
+            "short_name".to_string(),
+            "target".to_string(),
             "type".to_string(),
             "repo".to_string(),
         ];
@@ -617,6 +2357,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-references";
@@ -628,6 +2378,8 @@ mod tests {
         let fields_names = vec![
             "name".to_string(),
             "full_name".to_string(),
+            "short_name".to_string(),
+            "target".to_string(),
             "type".to_string(),
             "repo".to_string(),
         ];
@@ -654,6 +2406,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-commits";
@@ -667,7 +2429,10 @@ mod tests {
             "name".to_string(),
             "email".to_string(),
             "title".to_string(),
+            "summary".to_string(),
             "message".to_string(),
+            "body".to_string(),
+            "trailers".to_string(),
             "datetime".to_string(),
             "repo".to_string(),
         ];
@@ -688,12 +2453,54 @@ mod tests {
         test_delete_repo(path.to_string()).expect("failed to delete repo");
     }
 
+    #[test]
+    fn test_split_commit_body_trailers() {
+        let message = "Fix parser\n\nHandle empty input\n\nSigned-off-by: Name <name@example.com>\nCo-authored-by: Other <other@example.com>\n";
+        let (body, trailers) = split_commit_body_trailers(message);
+        assert_eq!(body, "Handle empty input");
+        assert_eq!(
+            trailers,
+            "Signed-off-by: Name <name@example.com>\nCo-authored-by: Other <other@example.com>"
+        );
+
+        let (body, trailers) = split_commit_body_trailers("Fix parser");
+        assert!(body.is_empty());
+        assert!(trailers.is_empty());
+    }
+
+    #[test]
+    fn test_decode_commit_text() {
+        let (text, is_lossy) = decode_commit_text("Fix parser".into(), None);
+        assert_eq!(text, "Fix parser");
+        assert!(!is_lossy);
+
+        let latin1: &[u8] = b"Caf\xe9";
+        let (text, is_lossy) = decode_commit_text(latin1.into(), None);
+        assert_eq!(text, "Caf\u{FFFD}");
+        assert!(is_lossy);
+
+        let encoding = encoding_rs::Encoding::for_label(b"ISO-8859-1");
+        let (text, is_lossy) = decode_commit_text(latin1.into(), encoding);
+        assert_eq!(text, "Café");
+        assert!(!is_lossy);
+    }
+
     #[test]
     fn test_select_branches() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-branches";
@@ -726,12 +2533,185 @@ mod tests {
         test_delete_repo(path.to_string()).expect("failed to delete repo");
     }
 
+    #[test]
+    fn test_select_commits_range() {
+        let path = "test-select-commits-range";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let repo = gix::open(path).ok().unwrap();
+        let head_id = repo.head_id().expect("failed to read head").detach();
+        let fields_names = vec![
+            "commit_id".to_string(),
+            format!("{}HEAD~1..HEAD", RANGE_FIELD_PREFIX),
+        ];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        let mut env = Environment::default();
+        let ret = select_commits(
+            &mut env,
+            &repo,
+            &fields_names,
+            &fields_names,
+            &fields_values,
+        );
+
+        let invalid_range = vec![format!("{}HEAD...HEAD~1", RANGE_FIELD_PREFIX)];
+        let invalid_ret = select_commits(
+            &mut env,
+            &repo,
+            &invalid_range,
+            &invalid_range,
+            &fields_values,
+        );
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        let group = ret.ok().unwrap();
+        assert_eq!(group.len(), 1);
+        assert!(group.rows[0].values[0].equals(&Value::Text(head_id.to_string())));
+        assert!(group.rows[0].values[1].equals(&Value::Boolean(true)));
+        assert!(invalid_ret.is_err());
+    }
+
+    #[test]
+    fn test_select_commits_touching() {
+        let path = "test-select-commits-touching";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let repo = gix::open(path).ok().unwrap();
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+        let mut env = Environment::default();
+
+        let touching_file = vec![
+            "title".to_string(),
+            format!("{}hello.txt", TOUCHING_FIELD_PREFIX),
+        ];
+        let ret = select_commits(
+            &mut env,
+            &repo,
+            &touching_file,
+            &touching_file,
+            &fields_values,
+        );
+
+        let touching_directory = vec![format!("{}src/", TOUCHING_FIELD_PREFIX)];
+        let directory_ret = select_commits(
+            &mut env,
+            &repo,
+            &touching_directory,
+            &touching_directory,
+            &fields_values,
+        );
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        let group = ret.ok().unwrap();
+        assert_eq!(group.len(), 1);
+        assert!(group.rows[0].values[0].equals(&Value::Text("hello commit".to_string())));
+        assert_eq!(directory_ret.ok().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_is_path_under_prefix() {
+        assert!(is_path_under_prefix("src/main.rs".into(), "src/"));
+        assert!(is_path_under_prefix("src/main.rs".into(), "src"));
+        assert!(is_path_under_prefix("src".into(), "src/"));
+        assert!(is_path_under_prefix("README.md".into(), ""));
+        assert!(!is_path_under_prefix("srcs/main.rs".into(), "src"));
+        assert!(!is_path_under_prefix("docs/src/main.rs".into(), "src/"));
+    }
+
+    #[test]
+    fn test_select_branches_upstream() {
+        let path = "test-select-branches-upstream";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        // Track `base` branch that points to the first commit from the head branch
+        let mut repo = gix::open(path).ok().unwrap();
+        let first_commit_id = repo
+            .head_commit()
+            .expect("failed to read head commit")
+            .parent_ids()
+            .next()
+            .unwrap()
+            .detach();
+        repo.reference(
+            "refs/heads/base",
+            first_commit_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "create base branch",
+        )
+        .expect("failed to create branch");
+
+        let head_name = repo.head_name().ok().flatten().unwrap();
+        let head_branch = head_name.shorten().to_string();
+        let mut config = repo.config_snapshot_mut();
+        config
+            .set_raw_value("branch", Some(head_branch.as_str().into()), "remote", ".")
+            .expect("failed to set remote");
+        config
+            .set_raw_value(
+                "branch",
+                Some(head_branch.as_str().into()),
+                "merge",
+                "refs/heads/base",
+            )
+            .expect("failed to set merge");
+        let repo = config
+            .commit_auto_rollback()
+            .expect("failed to commit auto rollback");
+
+        let fields_names = vec![
+            "name".to_string(),
+            "upstream".to_string(),
+            "ahead".to_string(),
+            "behind".to_string(),
+        ];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        let mut env = Environment::default();
+        let ret = select_branches(
+            &mut env,
+            &repo,
+            &fields_names,
+            &fields_names,
+            &fields_values,
+        );
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        let group = ret.ok().unwrap();
+        let head_row = group
+            .rows
+            .iter()
+            .find(|row| row.values[0].equals(&Value::Text(head_name.as_bstr().to_string())))
+            .unwrap();
+        assert!(head_row.values[1].equals(&Value::Text("base".to_string())));
+        assert!(head_row.values[2].equals(&Value::Integer(1)));
+        assert!(head_row.values[3].equals(&Value::Integer(0)));
+
+        let base_row = group
+            .rows
+            .iter()
+            .find(|row| row.values[0].equals(&Value::Text("refs/heads/base".to_string())))
+            .unwrap();
+        assert!(base_row.values[1].equals(&Value::Null));
+        assert!(base_row.values[2].equals(&Value::Null));
+    }
+
     #[test]
     fn test_select_diffs() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-diffs";
@@ -772,6 +2752,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         let path = "test-select-tags";
@@ -799,39 +2789,340 @@ mod tests {
     }
 
     #[test]
-    fn test_select_values() {
+    fn test_select_tags_annotation() {
+        let path = "test-select-tags-annotation";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let repo = gix::open(path).ok().unwrap();
+        let head_id = repo.head_id().expect("failed to read head").detach();
+        repo.tag_reference("v1", head_id, gix::refs::transaction::PreviousValue::Any)
+            .expect("failed to create lightweight tag");
+
+        let tagger = gix::actor::SignatureRef {
+            name: "tagger".into(),
+            email: "tagger@example.com".into(),
+            time: gix::date::Time::new(1700000000, 0),
+        };
+        repo.tag(
+            "v2",
+            head_id,
+            gix::object::Kind::Commit,
+            Some(tagger),
+            "Release v2",
+            gix::refs::transaction::PreviousValue::Any,
+        )
+        .expect("failed to create annotated tag");
+
+        let fields_names = vec![
+            "name".to_string(),
+            "target_commit_id".to_string(),
+            "is_annotated".to_string(),
+            "message".to_string(),
+            "tagger_name".to_string(),
+            "created_datetime".to_string(),
+        ];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        let mut env = Environment::default();
+        let ret = select_tags(
+            &mut env,
+            &repo,
+            &fields_names,
+            &fields_names,
+            &fields_values,
+        );
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        let group = ret.ok().unwrap();
+        let find_row = |name: &str| {
+            group
+                .rows
+                .iter()
+                .find(|row| row.values[0].equals(&Value::Text(name.to_string())))
+                .unwrap()
+        };
+
+        let head_id = Value::Text(head_id.to_string());
+        let lightweight = find_row("v1");
+        assert!(lightweight.values[1].equals(&head_id));
+        assert!(lightweight.values[2].equals(&Value::Boolean(false)));
+        assert!(lightweight.values[3].equals(&Value::Null));
+
+        let annotated = find_row("v2");
+        assert!(annotated.values[1].equals(&head_id));
+        assert!(annotated.values[2].equals(&Value::Boolean(true)));
+        assert!(annotated.values[3].equals(&Value::Text("Release v2".to_string())));
+        assert!(annotated.values[4].equals(&Value::Text("tagger".to_string())));
+        assert!(annotated.values[5].equals(&Value::DateTime(1700000000)));
+    }
+
+    #[test]
+    fn test_select_diff_files_renames() {
+        let path = "test-select-diff-files-renames";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        // Move `hello.txt` to `world.txt` without changing its content
+        let mut repo = gix::open(path).ok().unwrap();
+        let mut config = repo.config_snapshot_mut();
+        config
+            .set_raw_value("author", None, "name", "name")
+            .expect("failed to set name");
+        config
+            .set_raw_value("author", None, "email", "name@example.com")
+            .expect("failed to set email");
+        let repo = config
+            .commit_auto_rollback()
+            .expect("failed to commit auto rollback");
+
+        let head_id = repo.head_id().expect("failed to read head").detach();
+        let blob = repo
+            .write_blob("hello world")
+            .expect("failed to write blob")
+            .into();
+        let mut tree = gix::objs::Tree::empty();
+        tree.entries.push(gix::objs::tree::Entry {
+            mode: gix::objs::tree::EntryKind::Blob.into(),
+            oid: blob,
+            filename: "world.txt".into(),
+        });
+        let object = repo.write_object(&tree).expect("failed to write object");
+        repo.commit("HEAD", "rename commit", object, [head_id])
+            .expect("failed to commit");
+
+        let fields_names = vec![
+            "path".to_string(),
+            "status".to_string(),
+            "renamed_from".to_string(),
+        ];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        let mut env = Environment::default();
+        let ret = select_diff_files(
+            &mut env,
+            &repo,
+            &fields_names,
+            &fields_names,
+            &fields_values,
+        );
+
+        // Disable the renames detection so the rename is reported as deletion and addition
+        env.globals.insert(
+            RENAME_SIMILARITY_VARIABLE.to_string(),
+            Value::Text("0".to_string()),
+        );
+        let ret_without_renames = select_diff_files(
+            &mut env,
+            &repo,
+            &fields_names,
+            &fields_names,
+            &fields_values,
+        );
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        let group = ret.ok().unwrap();
+        assert!(group.rows[0].values[0].equals(&Value::Text("world.txt".to_string())));
+        assert!(group.rows[0].values[1].equals(&Value::Text("renamed".to_string())));
+        assert!(group.rows[0].values[2].equals(&Value::Text("hello.txt".to_string())));
+
+        let group = ret_without_renames.ok().unwrap();
+        let renamed_rows = group
+            .rows
+            .iter()
+            .filter(|row| row.values[1].equals(&Value::Text("renamed".to_string())))
+            .count();
+        assert_eq!(renamed_rows, 0);
+        assert!(group.rows[0].values[2].equals(&Value::Null));
+    }
+
+    #[test]
+    fn test_select_worktrees() {
+        let mut env = Environment::default();
+        let fields_names = vec!["name".to_string(), "is_main".to_string()];
+        let titles = vec!["name".to_string(), "is_main".to_string()];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        // Bare repository has no main worktree
+        let bare_path = "test-select-worktrees-bare";
+        test_new_repo(bare_path.to_string()).expect("failed to new repo");
+        let repo = gix::open(bare_path).ok().unwrap();
+        let bare_ret = select_worktrees(&mut env, &repo, &fields_names, &titles, &fields_values);
+        test_delete_repo(bare_path.to_string()).expect("failed to delete repo");
+
+        let path = "test-select-worktrees";
+        let repo = gix::init(path).expect("failed to init");
+        let ret = select_worktrees(&mut env, &repo, &fields_names, &titles, &fields_values);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+
+        assert_eq!(bare_ret.ok().unwrap().len(), 0);
+
+        let group = ret.ok().unwrap();
+        assert_eq!(group.len(), 1);
+        assert!(group.rows[0].values[0].equals(&Value::Text(path.to_string())));
+        assert!(group.rows[0].values[1].equals(&Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_select_blobs() {
         let mut env = Environment {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
-        let titles = vec!["title".to_string()];
+        let path = "test-select-blobs";
+        test_new_repo(path.to_string()).expect("failed to new repo");
 
-        let fields_values: Vec<Box<dyn Expression>> = vec![Box::new(StringExpression {
-            value: "value".to_string(),
-            value_type: StringValueType::Text,
-        })];
+        let buf = gix::open(path);
+        let repo = buf.ok().unwrap();
 
-        let ret = select_values(&mut env, &titles, &fields_values);
-        if ret.is_ok() {
-            assert!(true);
-        } else {
+        let fields_names = vec![
+            "path".to_string(),
+            "size".to_string(),
+            "is_binary".to_string(),
+            "lfs_pointer".to_string(),
+        ];
+        let titles = vec!["path".to_string()];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        env.globals.insert(
+            BLOBS_REVISION_VARIABLE.to_string(),
+            Value::Text("HEAD~1".to_string()),
+        );
+        let ret = select_blobs(&mut env, &repo, &fields_names, &titles, &fields_values);
+        if ret.is_err() || !ret.ok().unwrap().rows.is_empty() {
+            test_delete_repo(path.to_string()).expect("failed to delete repo");
+            assert!(false);
+        }
+
+        env.globals.remove(BLOBS_REVISION_VARIABLE);
+        let ret = select_blobs(&mut env, &repo, &fields_names, &titles, &fields_values);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        if ret.is_err() {
+            assert!(false);
+        }
+
+        let rows = ret.ok().unwrap().rows;
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].values[1].equals(&Value::Integer(11)));
+        assert!(rows[0].values[2].equals(&Value::Boolean(false)));
+        assert!(rows[0].values[3].equals(&Value::Boolean(false)));
+    }
+
+    #[test]
+    fn test_is_lfs_pointer_content() {
+        let pointer = b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 12345\n";
+        assert!(is_lfs_pointer_content(pointer));
+        assert!(!is_lfs_pointer_content(b"hello world"));
+    }
+
+    #[test]
+    fn test_select_blame() {
+        let mut env = Environment::default();
+
+        let path = "test-select-blame";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let buf = gix::open(path);
+        let repo = buf.ok().unwrap();
+        let head_id = repo.head_id().ok().unwrap().to_string();
+
+        let fields_names = vec![
+            "path".to_string(),
+            "line_number".to_string(),
+            "line".to_string(),
+            "commit_id".to_string(),
+            "author_name".to_string(),
+        ];
+        let titles = vec!["path".to_string()];
+        let fields_values: Vec<Box<dyn Expression>> = vec![];
+
+        let ret = select_blame(&mut env, &repo, &fields_names, &titles, &fields_values);
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+        if ret.is_err() {
             assert!(false);
         }
+
+        let rows = ret.ok().unwrap().rows;
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].values[0].equals(&Value::Text("hello.txt".to_string())));
+        assert!(rows[0].values[1].equals(&Value::Integer(1)));
+        assert!(rows[0].values[2].equals(&Value::Text("hello world".to_string())));
+        assert!(rows[0].values[3].equals(&Value::Text(head_id)));
+        assert!(rows[0].values[4].equals(&Value::Text("name".to_string())));
     }
 
     #[test]
-    fn test_get_column_name() {
-        let mut table: HashMap<String, String> = HashMap::new();
-        table.insert("key".to_string(), "value".to_string());
+    fn test_blame_head_files_with_merge() {
+        let path = "test-blame-head-files-with-merge";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+        let repo = gix::open(path).expect("failed to open repo");
+
+        let write_tree = |files: &[(&str, &str)]| {
+            let mut tree = gix::objs::Tree::empty();
+            for (name, content) in files {
+                let blob = repo.write_blob(content).expect("failed to write blob");
+                tree.entries.push(gix::objs::tree::Entry {
+                    mode: gix::objs::tree::EntryKind::Blob.into(),
+                    oid: blob.detach(),
+                    filename: (*name).into(),
+                });
+            }
+            repo.write_object(&tree)
+                .expect("failed to write tree")
+                .detach()
+        };
+
+        // Each branch changes one line and the merge takes both changes
+        let head_id = repo.head_id().expect("failed to get head").detach();
+        let tree = write_tree(&[("a.txt", "a\nb\n")]);
+        let base = repo.commit("HEAD", "base", tree, [head_id]).unwrap();
+        let tree = write_tree(&[("a.txt", "a\nc\n")]);
+        let side = repo
+            .commit("refs/heads/side", "side", tree, [base])
+            .unwrap();
+        let tree = write_tree(&[("a.txt", "x\nb\n")]);
+        let main = repo.commit("HEAD", "main", tree, [base]).unwrap();
+        let tree = write_tree(&[("a.txt", "x\nc\n")]);
+        let _ = repo.commit("HEAD", "merge", tree, [main, side]).unwrap();
+
+        let ret = blame_head_files(&repo, None);
+        let other_path_ret = blame_head_files(&repo, Some("b.txt"));
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
 
-        let name = "key";
-        let ret = get_column_name(&table, name);
-        assert_eq!(ret, "value".to_string());
+        let files = ret.expect("failed to blame");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "a.txt");
+        assert_eq!(
+            files[0].lines_commits,
+            vec![Some(main.detach()), Some(side.detach())]
+        );
+
+        // Only the file with the required path is blamed
+        let files = other_path_ret.expect("failed to blame");
+        assert!(files.is_empty());
+    }
 
-        let name = "invalid";
-        let ret = get_column_name(&table, name);
-        assert_eq!(ret, "invalid".to_string());
+    #[test]
+    fn test_blame_changed_lines() {
+        let lines = (0..5).map(|position| (position, position)).collect();
+        let (unchanged, changed) =
+            blame_changed_lines(b"a\nb\nc\nd\ne\n", b"a\nx\nc\ne\nf\n", lines);
+        assert_eq!(unchanged, vec![(0, 0), (2, 2), (4, 3)]);
+        assert_eq!(changed, vec![(1, 1), (4, 4)]);
+
+        let lines = vec![(0, 2), (2, 7)];
+        let (unchanged, changed) = blame_changed_lines(b"a\nb\n", b"z\na\nb\n", lines);
+        assert_eq!(unchanged, vec![(1, 7)]);
+        assert_eq!(changed, vec![(0, 2)]);
     }
 }
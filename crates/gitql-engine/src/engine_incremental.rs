@@ -0,0 +1,746 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use gitql_ast::aggregation::AGGREGATIONS;
+use gitql_ast::aggregation::AGGREGATIONS_MERGES;
+use gitql_ast::environment::Environment;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+use gitql_ast::statement::AggregateValue;
+use gitql_ast::statement::AggregationsStatement;
+use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::GroupByStatement;
+use gitql_ast::statement::Query;
+use gitql_ast::statement::SelectStatement;
+use gitql_ast::value::Value;
+use gitql_parser::diagnostic::Diagnostic;
+
+use crate::engine::evaluate_with_threads;
+use crate::engine::EvaluationResult;
+use crate::engine_diagnostic::runtime_exception;
+use crate::engine_evaluator::evaluate_expression;
+use crate::engine_executor::execute_statement;
+use crate::engine_executor::select_fields_names;
+use crate::engine_function::select_commit_row;
+use crate::engine_rows::get_column_name;
+
+/// Statements that can be executed on the aggregated groups of the incremental query
+const INCREMENTAL_COMMANDS: [&str; 8] = [
+    "select",
+    "where",
+    "group",
+    "aggregation",
+    "having",
+    "order",
+    "offset",
+    "limit",
+];
+
+/// Statements that are executed after the aggregations, in the same order of the engine
+const AGGREGATED_COMMANDS_IN_ORDER: [&str; 4] = ["having", "order", "offset", "limit"];
+
+/// Number of queries that their states are kept, the least recently evaluated is removed first
+const MAX_INCREMENTAL_QUERIES: usize = 16;
+
+/// Aggregation function name with its argument column name and its result column index
+type MergeableAggregation<'a> = (&'a str, &'a str, usize);
+
+/// Rows of the new commits with the ids of the visited commits
+type NewCommitsRows = (Vec<Row>, Vec<gix::ObjectId>);
+
+/// Repository with its head commit and the state of the previous evaluation
+type NewCommitsSelection<'a> = (&'a gix::Repository, gix::ObjectId, &'a RepositoryState);
+
+/// Aggregated groups of the query collected from one repository at specific head commit,
+/// each group is its first row with the aggregations results of the group
+struct RepositoryState {
+    head_id: gix::ObjectId,
+    selected_commits: Arc<HashSet<gix::ObjectId>>,
+    groups_map: HashMap<Vec<String>, usize>,
+    rows: Vec<Row>,
+}
+
+/// Persisted execution states of the aggregated queries on the commits table, used by long running
+/// modes to accumulate only the new commits when the repository head moves forward instead of
+/// walking the whole history again for each evaluation of the same query
+///
+/// The query text identifies its states, so the environment must be prepared the same way each time
+#[derive(Default)]
+pub struct IncrementalStates {
+    queries: VecDeque<(String, HashMap<String, RepositoryState>)>,
+}
+
+impl IncrementalStates {
+    /// Evaluate the query like [`evaluate_with_threads`], if the query can be updated incrementally
+    /// its states are stored and used by the next evaluation of the same query text
+    pub fn evaluate(
+        &mut self,
+        env: &mut Environment,
+        repos: &[gix::Repository],
+        query_text: &str,
+        query: Query,
+        threads: usize,
+    ) -> Result<EvaluationResult, Box<Diagnostic>> {
+        match query {
+            Query::Select(query) if is_incremental_query(&query) => {
+                self.evaluate_incremental_query(env, repos, query_text, query, threads)
+            }
+            query => evaluate_with_threads(env, repos, query, threads),
+        }
+    }
+
+    /// Returns the number of queries that have stored states
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queries.clear();
+    }
+
+    fn evaluate_incremental_query(
+        &mut self,
+        env: &mut Environment,
+        repos: &[gix::Repository],
+        query_text: &str,
+        query: GQLQuery,
+        threads: usize,
+    ) -> Result<EvaluationResult, Box<Diagnostic>> {
+        let hidden_selections = query.hidden_selections;
+        let statements_map = query.statements;
+
+        let select_statement = statements_map
+            .get("select")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+
+        let fields_names = select_fields_names(select_statement, &hidden_selections);
+        let titles: Vec<String> = fields_names
+            .iter()
+            .map(|field_name| get_column_name(&select_statement.alias_table, field_name))
+            .collect();
+
+        let aggregations_statement = statements_map
+            .get("aggregation")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<AggregationsStatement>()
+            .unwrap();
+
+        let aggregations = resolve_aggregations(
+            aggregations_statement,
+            &select_statement.alias_table,
+            &titles,
+        );
+
+        // Without `GROUP BY` all rows are aggregated into one group
+        let mut fields_indexes: Vec<usize> = vec![];
+        if let Some(statement) = statements_map.get("group") {
+            let group_statement = statement
+                .as_any()
+                .downcast_ref::<GroupByStatement>()
+                .unwrap();
+            for field_name in &group_statement.field_names {
+                let field_index = titles.iter().position(|r| r.eq(field_name)).unwrap();
+                fields_indexes.push(field_index);
+            }
+        }
+
+        let mut previous_states = self.take_query_states(query_text);
+        let mut repos_states: Vec<(String, RepositoryState)> = Vec::with_capacity(repos.len());
+        let mut updated_repos: Vec<(&gix::Repository, gix::ObjectId, usize)> = vec![];
+        for repo in repos {
+            let repo_path = repo.path().to_str().unwrap_or("").to_string();
+            let Ok(head_id) = repo.head_id() else {
+                continue;
+            };
+            let head_id = head_id.detach();
+
+            let state = previous_states
+                .remove(&repo_path)
+                .unwrap_or_else(|| RepositoryState::new(head_id));
+
+            if state.selected_commits.is_empty() || state.head_id != head_id {
+                updated_repos.push((repo, head_id, repos_states.len()));
+            }
+
+            repos_states.push((repo_path, state));
+        }
+
+        // Only the new commits of the repositories that moved forward are selected
+        let selections: Vec<NewCommitsSelection> = updated_repos
+            .iter()
+            .map(|(repo, head_id, index)| (*repo, *head_id, &repos_states[*index].1))
+            .collect();
+        let selected_rows = select_new_commits_rows_in_parallel(
+            env,
+            &selections,
+            threads,
+            &fields_names,
+            &titles,
+            select_statement,
+        );
+
+        for ((repo, head_id, index), selected) in updated_repos.into_iter().zip(selected_rows) {
+            let mut new_commits = selected.map_err(runtime_exception)?;
+
+            // History is rewritten, the stored groups contain commits that are not reachable
+            if new_commits.is_none() {
+                repos_states[index].1 = RepositoryState::new(head_id);
+                new_commits = select_new_commits_rows(
+                    env,
+                    repo,
+                    head_id,
+                    &repos_states[index].1,
+                    &fields_names,
+                    &titles,
+                    select_statement,
+                )
+                .map_err(runtime_exception)?;
+            }
+
+            let (rows, commits) = new_commits.unwrap_or_default();
+            let mut gitql_object = GitQLObject {
+                titles: titles.clone(),
+                groups: vec![Group { rows }],
+            };
+
+            if let Some(statement) = statements_map.get("where") {
+                execute_statement(
+                    env,
+                    statement,
+                    Some(repo),
+                    &mut gitql_object,
+                    &mut HashMap::new(),
+                    &hidden_selections,
+                )?;
+            }
+
+            let state = &mut repos_states[index].1;
+            let rows = gitql_object.groups.remove(0).rows;
+            accumulate_rows(state, &aggregations, &fields_indexes, &titles, rows);
+            Arc::make_mut(&mut state.selected_commits).extend(commits);
+            state.head_id = head_id;
+        }
+
+        let mut gitql_object = GitQLObject {
+            titles,
+            groups: vec![],
+        };
+
+        finish_aggregations(
+            env,
+            &mut gitql_object,
+            &repos_states,
+            aggregations_statement,
+            &select_statement.alias_table,
+            &aggregations,
+        )
+        .map_err(runtime_exception)?;
+
+        self.insert_query_states(query_text, repos_states.into_iter().collect());
+
+        // Without any selected rows there are no groups to execute other statements on
+        if gitql_object.groups.is_empty() {
+            gitql_object.groups.push(Group { rows: vec![] });
+            gitql_object.remove_hidden_selections(&hidden_selections);
+            return Ok(EvaluationResult::SelectedGroups(
+                gitql_object,
+                hidden_selections,
+            ));
+        }
+
+        let first_repo = repos.first();
+        let mut alias_table = select_statement.alias_table.clone();
+        for gql_command in AGGREGATED_COMMANDS_IN_ORDER {
+            if let Some(statement) = statements_map.get(gql_command) {
+                execute_statement(
+                    env,
+                    statement,
+                    first_repo,
+                    &mut gitql_object,
+                    &mut alias_table,
+                    &hidden_selections,
+                )?;
+            }
+        }
+
+        // Same result columns as the full evaluation, without the hidden selections
+        gitql_object.remove_hidden_selections(&hidden_selections);
+        Ok(EvaluationResult::SelectedGroups(
+            gitql_object,
+            hidden_selections,
+        ))
+    }
+
+    fn take_query_states(&mut self, query_text: &str) -> HashMap<String, RepositoryState> {
+        match self.queries.iter().position(|(text, _)| text == query_text) {
+            Some(index) => self.queries.remove(index).unwrap().1,
+            None => HashMap::new(),
+        }
+    }
+
+    fn insert_query_states(&mut self, query_text: &str, states: HashMap<String, RepositoryState>) {
+        if self.queries.len() >= MAX_INCREMENTAL_QUERIES {
+            self.queries.pop_front();
+        }
+        self.queries.push_back((query_text.to_string(), states));
+    }
+}
+
+impl RepositoryState {
+    fn new(head_id: gix::ObjectId) -> Self {
+        RepositoryState {
+            head_id,
+            selected_commits: Arc::new(HashSet::new()),
+            groups_map: HashMap::new(),
+            rows: vec![],
+        }
+    }
+}
+
+/// Returns true if the query aggregates the commits table with aggregations that can be merged,
+/// and all of its statements can be executed after the aggregations without the rows of the groups,
+/// queries that call functions like `NOW()` are evaluated again because the old rows are outdated
+fn is_incremental_query(query: &GQLQuery) -> bool {
+    if !query.has_aggregation_function
+        || query.has_non_deterministic_function
+        || query.subquery.is_some()
+        || !query.in_subqueries.is_empty()
+    {
+        return false;
+    }
+
+    for command in query.statements.keys() {
+        if !INCREMENTAL_COMMANDS.contains(command) {
+            return false;
+        }
+    }
+
+    let Some(statement) = query.statements.get("select") else {
+        return false;
+    };
+
+    let select_statement = statement
+        .as_any()
+        .downcast_ref::<SelectStatement>()
+        .unwrap();
+
+    if select_statement.table_name != "commits" || select_statement.is_distinct {
+        return false;
+    }
+
+    // `ROLLUP` subtotals are computed from all the rows of the group, not from the merged groups
+    if let Some(statement) = query.statements.get("group") {
+        let group_statement = statement
+            .as_any()
+            .downcast_ref::<GroupByStatement>()
+            .unwrap();
+        if group_statement.is_rollup {
+            return false;
+        }
+    }
+
+    let Some(statement) = query.statements.get("aggregation") else {
+        return false;
+    };
+
+    let aggregations_statement = statement
+        .as_any()
+        .downcast_ref::<AggregationsStatement>()
+        .unwrap();
+
+    aggregations_statement
+        .aggregations
+        .values()
+        .all(|aggregation| match aggregation {
+            // `DISTINCT` and ordered calls depend on the rows of other parts of the group
+            AggregateValue::Function(call) => {
+                !call.is_distinct
+                    && call.order_by.is_none()
+                    && call.separator.is_none()
+                    && AGGREGATIONS_MERGES.contains_key(call.function_name.as_str())
+            }
+            AggregateValue::Expression(_) => true,
+        })
+}
+
+/// Resolve the argument and the result column of each aggregation function
+fn resolve_aggregations<'a>(
+    aggregations_statement: &'a AggregationsStatement,
+    alias_table: &HashMap<String, String>,
+    titles: &[String],
+) -> Vec<MergeableAggregation<'a>> {
+    let mut aggregations = vec![];
+    for (result_column_name, aggregation) in &aggregations_statement.aggregations {
+        if let AggregateValue::Function(call) = aggregation {
+            let column_name = get_column_name(alias_table, result_column_name);
+            let column_index = titles.iter().position(|r| r.eq(&column_name)).unwrap();
+            let function = call.function_name.as_str();
+            aggregations.push((function, call.argument.as_str(), column_index));
+        }
+    }
+    aggregations
+}
+
+/// Select the rows of the commits reachable from the head that are not selected before, the walk
+/// stops at the selected commits so only the new commits are visited, returns the rows and the
+/// ids of the visited commits, or `None` if the stored head is not an ancestor of the head
+fn select_new_commits_rows(
+    env: &mut Environment,
+    repo: &gix::Repository,
+    head_id: gix::ObjectId,
+    state: &RepositoryState,
+    fields_names: &[String],
+    titles: &[String],
+    select_statement: &SelectStatement,
+) -> Result<Option<NewCommitsRows>, String> {
+    let selected = state.selected_commits.clone();
+    let revwalk = repo
+        .find_object(head_id)
+        .map_err(|error| error.to_string())?
+        .into_commit()
+        .ancestors()
+        .selected(move |id| !selected.contains(id))
+        .map_err(|error| error.to_string())?;
+
+    let mut is_head_reached = state.selected_commits.is_empty();
+    let mut rows: Vec<Row> = vec![];
+    let mut commits: Vec<gix::ObjectId> = vec![];
+    for commit_info in revwalk {
+        let commit_info = commit_info.map_err(|error| error.to_string())?;
+        if state.selected_commits.contains(&commit_info.id) {
+            continue;
+        }
+
+        if !is_head_reached {
+            let commit = repo
+                .find_object(commit_info.id)
+                .map_err(|error| error.to_string())?
+                .into_commit();
+            is_head_reached = commit.parent_ids().any(|id| id == state.head_id);
+        }
+
+        commits.push(commit_info.id);
+        let row = select_commit_row(
+            env,
+            repo,
+            &commit_info,
+            fields_names,
+            titles,
+            &select_statement.fields_values,
+        )?;
+        rows.push(row);
+    }
+
+    if !is_head_reached {
+        return Ok(None);
+    }
+
+    Ok(Some((rows, commits)))
+}
+
+/// Select the rows of the new commits of each repository using a pool of threads like
+/// [`crate::engine_executor::execute_select_statement_in_parallel`], returns the rows of each
+/// repository in the same order of the repositories
+fn select_new_commits_rows_in_parallel(
+    env: &mut Environment,
+    selections: &[NewCommitsSelection],
+    threads: usize,
+    fields_names: &[String],
+    titles: &[String],
+    select_statement: &SelectStatement,
+) -> Vec<Result<Option<NewCommitsRows>, String>> {
+    if threads <= 1 || selections.len() <= 1 {
+        return selections
+            .iter()
+            .map(|(repo, head_id, state)| {
+                select_new_commits_rows(
+                    env,
+                    repo,
+                    *head_id,
+                    state,
+                    fields_names,
+                    titles,
+                    select_statement,
+                )
+            })
+            .collect();
+    }
+
+    // Repository can't be shared between threads, but it can be converted to thread safe one
+    let sync_selections: Vec<(gix::ThreadSafeRepository, gix::ObjectId, &RepositoryState)> =
+        selections
+            .iter()
+            .map(|(repo, head_id, state)| ((*repo).clone().into_sync(), *head_id, *state))
+            .collect();
+
+    let threads = threads.clamp(1, sync_selections.len());
+    let chunk_size = sync_selections.len().div_ceil(threads);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = sync_selections
+            .chunks(chunk_size)
+            .map(|chunk| {
+                // Each thread has its own copy of the environment
+                let mut thread_env = env.clone();
+                let handle = scope.spawn(move || {
+                    let mut selected = Vec::with_capacity(chunk.len());
+                    for (sync_repo, head_id, state) in chunk {
+                        let repo = sync_repo.to_thread_local();
+                        selected.push(select_new_commits_rows(
+                            &mut thread_env,
+                            &repo,
+                            *head_id,
+                            state,
+                            fields_names,
+                            titles,
+                            select_statement,
+                        ));
+                    }
+                    selected
+                });
+                (handle, chunk.len())
+            })
+            .collect();
+
+        // Panicked thread fails each repository of its chunk, so the results keep the repositories order
+        handles
+            .into_iter()
+            .flat_map(|(handle, chunk_len)| {
+                handle.join().unwrap_or_else(|_| {
+                    (0..chunk_len)
+                        .map(|_| Err("Failed to select data from repository".to_string()))
+                        .collect()
+                })
+            })
+            .collect()
+    })
+}
+
+/// Aggregate the new rows of each group and merge the results into the stored group results,
+/// new groups keep their first row
+fn accumulate_rows(
+    repo_state: &mut RepositoryState,
+    aggregations: &[MergeableAggregation],
+    fields_indexes: &[usize],
+    titles: &[String],
+    rows: Vec<Row>,
+) {
+    let mut groups_map: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut groups: Vec<(Vec<String>, Group)> = vec![];
+    for row in rows {
+        let fields_values: Vec<String> = fields_indexes
+            .iter()
+            .map(|index| row.values[*index].to_string())
+            .collect();
+
+        match groups_map.get(&fields_values) {
+            Some(index) => groups[*index].1.rows.push(row),
+            None => {
+                groups_map.insert(fields_values.clone(), groups.len());
+                groups.push((fields_values, Group { rows: vec![row] }));
+            }
+        }
+    }
+
+    for (fields_values, group) in groups {
+        let mut object = Row {
+            values: group.rows[0].values.clone(),
+        };
+
+        for (function, argument, column_index) in aggregations {
+            let aggregation_function = AGGREGATIONS.get(function).unwrap();
+            let result = aggregation_function(argument, titles, &group);
+            set_column_value(&mut object, *column_index, result);
+        }
+
+        match repo_state.groups_map.get(&fields_values) {
+            Some(index) => merge_aggregations(&mut repo_state.rows[*index], &object, aggregations),
+            None => {
+                repo_state
+                    .groups_map
+                    .insert(fields_values, repo_state.rows.len());
+                repo_state.rows.push(object);
+            }
+        }
+    }
+}
+
+/// Merge the results of the same group from all repositories into one row for each group,
+/// then calculate the aggregations expressions of each group
+fn finish_aggregations(
+    env: &mut Environment,
+    gitql_object: &mut GitQLObject,
+    repos_states: &[(String, RepositoryState)],
+    aggregations_statement: &AggregationsStatement,
+    alias_table: &HashMap<String, String>,
+    aggregations: &[MergeableAggregation],
+) -> Result<(), String> {
+    let mut groups_map: HashMap<&Vec<String>, usize> = HashMap::new();
+
+    for (_, repo_state) in repos_states {
+        // Groups are merged in the order of their first rows
+        let mut groups: Vec<(&Vec<String>, &usize)> = repo_state.groups_map.iter().collect();
+        groups.sort_by_key(|(_, index)| **index);
+
+        for (fields_values, index) in groups {
+            let object = &repo_state.rows[*index];
+            match groups_map.get(fields_values) {
+                Some(group_index) => {
+                    let merged = &mut gitql_object.groups[*group_index].rows[0];
+                    merge_aggregations(merged, object, aggregations);
+                }
+                None => {
+                    groups_map.insert(fields_values, gitql_object.groups.len());
+                    gitql_object.groups.push(Group {
+                        rows: vec![Row {
+                            values: object.values.clone(),
+                        }],
+                    });
+                }
+            }
+        }
+    }
+
+    // Resolve aggregations expressions after the aggregations functions results
+    for group in gitql_object.groups.iter_mut() {
+        let object = &mut group.rows[0];
+        for (result_column_name, aggregation) in &aggregations_statement.aggregations {
+            if let AggregateValue::Expression(expr) = aggregation {
+                let column_name = get_column_name(alias_table, result_column_name);
+                let column_index = gitql_object
+                    .titles
+                    .iter()
+                    .position(|r| r.eq(&column_name))
+                    .unwrap();
+
+                let result = evaluate_expression(env, expr, &gitql_object.titles, &object.values)?;
+                set_column_value(object, column_index, result);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Merge the aggregations results of the other part of the group into the group row
+fn merge_aggregations(object: &mut Row, other: &Row, aggregations: &[MergeableAggregation]) {
+    for (function, _, column_index) in aggregations {
+        let merge = AGGREGATIONS_MERGES.get(function).unwrap();
+        let result = merge(&object.values[*column_index], &other.values[*column_index]);
+        object.values[*column_index] = result;
+    }
+}
+
+fn set_column_value(object: &mut Row, column_index: usize, value: Value) {
+    if column_index < object.values.len() {
+        object.values[column_index] = value;
+    } else {
+        object.values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_test_repo::*;
+    use gitql_parser::{parser, tokenizer};
+
+    fn commits_count(
+        states: &mut IncrementalStates,
+        paths: &[&str],
+        query: &str,
+        threads: usize,
+    ) -> Option<i64> {
+        let mut env = Environment::default();
+        let mut repos = vec![];
+        for path in paths {
+            repos.push(gix::open(path).ok()?);
+        }
+        let tokens = tokenizer::tokenize(query).ok()?;
+        let query_node = parser::parse_gql(tokens, &mut env).ok()?;
+        match states
+            .evaluate(&mut env, &repos, query, query_node, threads)
+            .ok()?
+        {
+            EvaluationResult::SelectedGroups(object, _) => Some(
+                object
+                    .groups
+                    .first()?
+                    .rows
+                    .first()?
+                    .values
+                    .first()?
+                    .as_int(),
+            ),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_incremental_states_evaluate() {
+        let path = "test-incremental-states-evaluate";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let mut states = IncrementalStates::default();
+        let query = "SELECT COUNT(title) FROM commits";
+
+        let count = commits_count(&mut states, &[path], query, 1);
+        assert_eq!(count, Some(2));
+        assert_eq!(states.len(), 1);
+
+        test_add_commit(path.to_string()).expect("failed to add commit");
+        test_add_commit(path.to_string()).expect("failed to add commit");
+
+        let count = commits_count(&mut states, &[path], query, 1);
+        assert_eq!(count, Some(4));
+        assert_eq!(states.len(), 1);
+
+        // Queries on other tables are evaluated without states
+        let query = "SELECT COUNT(name) FROM branches";
+        let count = commits_count(&mut states, &[path], query, 1);
+        assert!(count.is_some());
+        assert_eq!(states.len(), 1);
+
+        // Queries that call non deterministic functions are evaluated without states
+        let query = "SELECT COUNT(title) FROM commits WHERE datetime <= NOW()";
+        let count = commits_count(&mut states, &[path], query, 1);
+        assert_eq!(count, Some(4));
+        assert_eq!(states.len(), 1);
+
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+    }
+
+    #[test]
+    fn test_incremental_states_evaluate_with_threads() {
+        let first_path = "test-incremental-states-threads-first";
+        let second_path = "test-incremental-states-threads-second";
+        test_new_repo(first_path.to_string()).expect("failed to new repo");
+        test_new_repo(second_path.to_string()).expect("failed to new repo");
+
+        let mut states = IncrementalStates::default();
+        let paths = [first_path, second_path];
+        let query = "SELECT COUNT(title) FROM commits";
+
+        let count = commits_count(&mut states, &paths, query, 2);
+        assert_eq!(count, Some(4));
+
+        test_add_commit(second_path.to_string()).expect("failed to add commit");
+        let count = commits_count(&mut states, &paths, query, 2);
+        assert_eq!(count, Some(5));
+        assert_eq!(states.len(), 1);
+
+        test_delete_repo(first_path.to_string()).expect("failed to delete repo");
+        test_delete_repo(second_path.to_string()).expect("failed to delete repo");
+    }
+}
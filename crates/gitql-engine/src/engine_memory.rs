@@ -0,0 +1,143 @@
+use std::mem::size_of;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use gitql_ast::object::Row;
+use gitql_ast::value::Value;
+
+/// Maximum estimated size in bytes of the rows materialized by one query, zero means unlimited
+static MEMORY_LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+/// Set the maximum estimated size in bytes of the materialized rows, `None` removes the limit
+pub fn set_memory_limit(limit: Option<usize>) {
+    MEMORY_LIMIT.store(limit.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Returns the memory limit in bytes if it's set
+pub fn memory_limit() -> Option<usize> {
+    match MEMORY_LIMIT.load(Ordering::Relaxed) {
+        0 => None,
+        limit => Some(limit),
+    }
+}
+
+/// Estimate the memory used by the value including the text stored on the heap
+pub fn estimate_value_size(value: &Value) -> usize {
+    let heap_size = match value {
+        Value::Text(text) | Value::Time(text) => text.capacity(),
+        _ => 0,
+    };
+    size_of::<Value>() + heap_size
+}
+
+/// Estimate the memory used by the row and its values
+pub fn estimate_row_size(row: &Row) -> usize {
+    size_of::<Row>() + row.values.iter().map(estimate_value_size).sum::<usize>()
+}
+
+/// Returns an error if the estimated size of the rows exceeds the memory limit
+pub fn check_rows_memory(rows: &[Row]) -> Result<(), String> {
+    let Some(limit) = memory_limit() else {
+        return Ok(());
+    };
+
+    let mut used = 0;
+    for row in rows {
+        used += estimate_row_size(row);
+        if used > limit {
+            return Err(memory_limit_exceeded_message(limit));
+        }
+    }
+    Ok(())
+}
+
+/// Track the estimated size of the rows while they are collected, so the scan is aborted
+/// as soon as the rows exceed the memory limit instead of after collecting all of them
+pub struct MemoryBudget {
+    limit: Option<usize>,
+    used: usize,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        MemoryBudget {
+            limit: memory_limit(),
+            used: 0,
+        }
+    }
+}
+
+impl MemoryBudget {
+    /// Add the row size to the used memory, returns an error if the limit is exceeded
+    pub fn add_row(&mut self, row: &Row) -> Result<(), String> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+
+        self.used += estimate_row_size(row);
+        if self.used > limit {
+            return Err(memory_limit_exceeded_message(limit));
+        }
+        Ok(())
+    }
+}
+
+fn memory_limit_exceeded_message(limit: usize) -> String {
+    format!(
+        "Selected rows exceed the memory limit of {}, select fewer fields or repositories, \
+         or increase the limit using `--memory-limit`",
+        format_memory_size(limit)
+    )
+}
+
+/// Format the size in bytes using the largest unit that keeps the value at least one
+pub fn format_memory_size(size: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", size, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_budget() {
+        let row = Row {
+            values: vec![Value::Text("GitQL".to_string()), Value::Integer(1)],
+        };
+        let row_size = estimate_row_size(&row);
+        assert!(row_size >= size_of::<Row>() + 2 * size_of::<Value>() + 5);
+
+        let mut budget = MemoryBudget {
+            limit: Some(row_size * 2),
+            used: 0,
+        };
+        assert!(budget.add_row(&row).is_ok());
+        assert!(budget.add_row(&row).is_ok());
+        assert!(budget.add_row(&row).is_err());
+
+        let mut unlimited = MemoryBudget {
+            limit: None,
+            used: 0,
+        };
+        assert!(unlimited.add_row(&row).is_ok());
+    }
+
+    #[test]
+    fn test_format_memory_size() {
+        assert_eq!(format_memory_size(512), "512 B");
+        assert_eq!(format_memory_size(1536), "1.5 KB");
+        assert_eq!(format_memory_size(512 * 1024 * 1024), "512.0 MB");
+    }
+}
@@ -0,0 +1,329 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::statement::OrderByStatement;
+use gitql_ast::statement::SortingOrder;
+use gitql_ast::value::Value;
+use gitql_parser::diagnostic::Diagnostic;
+
+use crate::engine_evaluator::evaluate_expression;
+
+/// Number of paginated results kept in the cache, the oldest result is removed first
+pub const MAX_CACHED_RESULTS: usize = 16;
+
+/// Position of the next page, the `ORDER BY` keys of the last row of the previous page and the number
+/// of rows with the same keys in the previous pages, so new rows don't shift the next pages
+pub type PageCursor = (Vec<Value>, usize);
+
+/// `ORDER BY` keys of each selected row with the sorting orders of the query,
+/// queries without `ORDER BY` have empty keys so the cursor is only the rows offset
+#[derive(Default)]
+pub struct OrderKeys {
+    pub keys: Vec<Vec<Value>>,
+    pub sorting_orders: Vec<SortingOrder>,
+}
+
+impl OrderKeys {
+    /// Evaluate the `ORDER BY` arguments of each row, they must be selected to be used as page cursor
+    pub fn select(
+        env: &mut Environment,
+        statement: Option<&OrderByStatement>,
+        object: &GitQLObject,
+    ) -> Result<OrderKeys, Box<Diagnostic>> {
+        let rows = object.groups.iter().flat_map(|group| group.rows.iter());
+        let Some(statement) = statement else {
+            return Ok(OrderKeys {
+                keys: rows.map(|_| vec![]).collect(),
+                sorting_orders: vec![],
+            });
+        };
+
+        let mut keys = vec![];
+        for row in rows {
+            let mut row_keys = Vec::with_capacity(statement.arguments.len());
+            for argument in &statement.arguments {
+                let key = evaluate_expression(env, argument, &object.titles, &row.values);
+                row_keys.push(key.map_err(|_| {
+                    Diagnostic::error("Paginated query must select the fields used in `ORDER BY`")
+                        .add_help("Add the `ORDER BY` fields to the selected fields")
+                        .add_note("The page cursor has the `ORDER BY` values of the last row")
+                        .as_boxed()
+                })?);
+            }
+            keys.push(row_keys);
+        }
+
+        Ok(OrderKeys {
+            keys,
+            sorting_orders: statement.sorting_orders.clone(),
+        })
+    }
+
+    /// Returns the number of rows in the result
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the result has no rows
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the index of the first row after the cursor, or None if the cursor has different keys
+    pub fn cursor_offset(&self, cursor: &PageCursor) -> Option<usize> {
+        let (keys, equal_rows) = cursor;
+        if keys.len() != self.sorting_orders.len() {
+            return None;
+        }
+
+        let first_equal = self
+            .keys
+            .iter()
+            .position(|row_keys| self.compare(row_keys, keys) != Ordering::Less)
+            .unwrap_or(self.keys.len());
+
+        let skipped_equal = self.keys[first_equal..]
+            .iter()
+            .take(*equal_rows)
+            .take_while(|row_keys| self.compare(row_keys, keys) == Ordering::Equal)
+            .count();
+        Some(first_equal + skipped_equal)
+    }
+
+    /// Returns the cursor of the page that starts at the row index
+    pub fn next_cursor(&self, start: usize) -> PageCursor {
+        let keys = &self.keys[start - 1];
+        let equal_rows = self.keys[..start]
+            .iter()
+            .rev()
+            .take_while(|row_keys| self.compare(row_keys, keys) == Ordering::Equal)
+            .count();
+        (keys.clone(), equal_rows)
+    }
+
+    /// Compare the `ORDER BY` keys of two rows in the order of the query
+    fn compare(&self, keys: &[Value], other: &[Value]) -> Ordering {
+        for ((key, other), sorting_order) in keys.iter().zip(other).zip(&self.sorting_orders) {
+            // Value compare returns the reversed ordering
+            let ordering = other.compare(key);
+            if ordering != Ordering::Equal {
+                return match sorting_order {
+                    SortingOrder::Ascending => ordering,
+                    SortingOrder::Descending => ordering.reverse(),
+                };
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+/// Results of the paginated queries by the query text, so the next pages are served
+/// without evaluating the query again
+pub struct ResultsCache<T> {
+    results: VecDeque<(String, T)>,
+}
+
+impl<T> Default for ResultsCache<T> {
+    fn default() -> Self {
+        Self {
+            results: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> ResultsCache<T> {
+    pub fn get(&self, query: &str) -> Option<&T> {
+        self.results
+            .iter()
+            .find(|(key, _)| key == query)
+            .map(|(_, result)| result)
+    }
+
+    pub fn insert(&mut self, query: &str, result: T) -> &T {
+        self.results.retain(|(key, _)| key != query);
+        if self.results.len() >= MAX_CACHED_RESULTS {
+            self.results.pop_front();
+        }
+        self.results.push_back((query.to_string(), result));
+        &self.results.back().expect("Result is just inserted").1
+    }
+}
+
+const INTEGER_TAG: u8 = b'i';
+const FLOAT_TAG: u8 = b'f';
+const TEXT_TAG: u8 = b's';
+const BOOLEAN_TAG: u8 = b'b';
+const DATE_TIME_TAG: u8 = b't';
+const DATE_TAG: u8 = b'd';
+const TIME_TAG: u8 = b'h';
+const NULL_TAG: u8 = b'n';
+
+/// Cursor is opaque for clients, it's the hex of the equal rows count and the keys tagged with
+/// their types, so the keys are compared the same way after decoding
+pub fn encode_cursor(cursor: &PageCursor) -> String {
+    let (keys, equal_rows) = cursor;
+    let mut bytes = (*equal_rows as u64).to_le_bytes().to_vec();
+    for key in keys {
+        match key {
+            Value::Integer(integer) => encode_number(&mut bytes, INTEGER_TAG, *integer as u64),
+            Value::Float(float) => encode_number(&mut bytes, FLOAT_TAG, float.to_bits()),
+            Value::DateTime(time_stamp) => {
+                encode_number(&mut bytes, DATE_TIME_TAG, *time_stamp as u64)
+            }
+            Value::Date(time_stamp) => encode_number(&mut bytes, DATE_TAG, *time_stamp as u64),
+            Value::Text(text) => encode_text(&mut bytes, TEXT_TAG, text),
+            Value::Time(time) => encode_text(&mut bytes, TIME_TAG, time),
+            Value::Boolean(boolean) => bytes.extend([BOOLEAN_TAG, *boolean as u8]),
+            Value::Null => bytes.push(NULL_TAG),
+        }
+    }
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Returns None if the cursor is not encoded by `encode_cursor`
+pub fn decode_cursor(cursor: &str) -> Option<PageCursor> {
+    if !cursor.is_ascii() || !cursor.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&cursor[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    let mut bytes = bytes.as_slice();
+    let equal_rows = usize::try_from(decode_number(&mut bytes)?).ok()?;
+    let mut keys = vec![];
+    while let Some((tag, rest)) = bytes.split_first() {
+        bytes = rest;
+        let key = match *tag {
+            INTEGER_TAG => Value::Integer(decode_number(&mut bytes)? as i64),
+            FLOAT_TAG => Value::Float(f64::from_bits(decode_number(&mut bytes)?)),
+            DATE_TIME_TAG => Value::DateTime(decode_number(&mut bytes)? as i64),
+            DATE_TAG => Value::Date(decode_number(&mut bytes)? as i64),
+            TEXT_TAG => Value::Text(decode_text(&mut bytes)?),
+            TIME_TAG => Value::Time(decode_text(&mut bytes)?),
+            BOOLEAN_TAG => {
+                let (boolean, rest) = bytes.split_first()?;
+                bytes = rest;
+                Value::Boolean(*boolean != 0)
+            }
+            NULL_TAG => Value::Null,
+            _ => return None,
+        };
+        keys.push(key);
+    }
+    Some((keys, equal_rows))
+}
+
+fn encode_number(bytes: &mut Vec<u8>, tag: u8, number: u64) {
+    bytes.push(tag);
+    bytes.extend(number.to_le_bytes());
+}
+
+fn encode_text(bytes: &mut Vec<u8>, tag: u8, text: &str) {
+    bytes.push(tag);
+    bytes.extend((text.len() as u64).to_le_bytes());
+    bytes.extend(text.as_bytes());
+}
+
+fn decode_number(bytes: &mut &[u8]) -> Option<u64> {
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let (number, rest) = bytes.split_at(8);
+    *bytes = rest;
+    Some(u64::from_le_bytes(number.try_into().ok()?))
+}
+
+fn decode_text(bytes: &mut &[u8]) -> Option<String> {
+    let len = usize::try_from(decode_number(bytes)?).ok()?;
+    if bytes.len() < len {
+        return None;
+    }
+
+    let (text, rest) = bytes.split_at(len);
+    *bytes = rest;
+    String::from_utf8(text.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_cursor() {
+        let cursor = (
+            vec![
+                Value::Integer(-5),
+                Value::Float(1.5),
+                Value::Text("GitQL".to_string()),
+                Value::Boolean(true),
+                Value::DateTime(1704067200),
+                Value::Date(1704067200),
+                Value::Time("12:30:00".to_string()),
+                Value::Null,
+            ],
+            3,
+        );
+
+        let encoded = encode_cursor(&cursor);
+        let (keys, equal_rows) = decode_cursor(&encoded).unwrap();
+        assert_eq!(equal_rows, 3);
+        assert_eq!(keys.len(), cursor.0.len());
+        for (key, expected) in keys.iter().zip(&cursor.0) {
+            assert!(key.equals(expected));
+        }
+
+        assert!(decode_cursor("").is_none());
+        assert!(decode_cursor("xyz").is_none());
+        assert!(decode_cursor(&encoded[..20]).is_none());
+    }
+
+    #[test]
+    fn test_order_keys_cursor() {
+        let keys: Vec<Vec<Value>> = [3, 2, 2, 2, 1]
+            .iter()
+            .map(|key| vec![Value::Integer(*key)])
+            .collect();
+        let order_keys = OrderKeys {
+            keys,
+            sorting_orders: vec![SortingOrder::Descending],
+        };
+
+        // Page of two rows ends in the middle of the rows with key 2
+        let cursor = order_keys.next_cursor(2);
+        assert!(cursor.0[0].equals(&Value::Integer(2)));
+        assert_eq!(cursor.1, 1);
+        assert_eq!(order_keys.cursor_offset(&cursor), Some(2));
+
+        let cursor = order_keys.next_cursor(4);
+        assert_eq!(cursor.1, 3);
+        assert_eq!(order_keys.cursor_offset(&cursor), Some(4));
+
+        // New row before the cursor keys doesn't shift the next page
+        let mut order_keys = order_keys;
+        order_keys.keys.insert(0, vec![Value::Integer(4)]);
+        assert_eq!(order_keys.cursor_offset(&cursor), Some(5));
+
+        let invalid_cursor = (vec![], 0);
+        assert_eq!(order_keys.cursor_offset(&invalid_cursor), None);
+    }
+
+    #[test]
+    fn test_results_cache() {
+        let mut cache: ResultsCache<usize> = ResultsCache::default();
+        for index in 0..=MAX_CACHED_RESULTS {
+            cache.insert(&index.to_string(), index);
+        }
+
+        assert!(cache.get("0").is_none());
+        assert_eq!(cache.get("1"), Some(&1));
+
+        cache.insert("1", 100);
+        assert_eq!(cache.get("1"), Some(&100));
+    }
+}
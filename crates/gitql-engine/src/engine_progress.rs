@@ -0,0 +1,96 @@
+use std::sync::RwLock;
+
+/// Minimum number of collected rows between two progress reports of the same scan
+const PROGRESS_REPORT_INTERVAL: usize = 1000;
+
+/// Progress of one long running table scan, for example collecting the commits of large repository
+pub struct ScanProgress<'a> {
+    pub table_name: &'a str,
+    pub repo_path: &'a str,
+    pub branch: &'a str,
+    pub rows_count: usize,
+}
+
+/// Receive the progress of the long running scans, implemented by the front ends to render
+/// progress bar, reports may be sent from many threads when repositories are scanned in parallel
+pub trait ProgressReporter: Send + Sync {
+    /// Called periodically while collecting the rows of the table
+    fn report_progress(&self, progress: &ScanProgress);
+
+    /// Called once the query is evaluated so the progress can be cleared before printing the result
+    fn finish(&self);
+}
+
+/// Progress reporter shared by all queries, no progress is reported until it is set
+static PROGRESS_REPORTER: RwLock<Option<Box<dyn ProgressReporter>>> = RwLock::new(None);
+
+/// Set the progress reporter, or remove it if `None` is passed
+pub fn set_progress_reporter(reporter: Option<Box<dyn ProgressReporter>>) {
+    if let Ok(mut current) = PROGRESS_REPORTER.write() {
+        *current = reporter;
+    }
+}
+
+/// Notify the progress reporter that the query evaluation is finished
+pub fn finish_progress() {
+    if let Ok(reporter) = PROGRESS_REPORTER.read() {
+        if let Some(reporter) = reporter.as_ref() {
+            reporter.finish();
+        }
+    }
+}
+
+/// Track the number of collected rows of one scan and report it every `PROGRESS_REPORT_INTERVAL` rows
+pub struct ScanProgressTracker {
+    table_name: String,
+    repo_path: String,
+    branch: String,
+    last_reported_count: usize,
+}
+
+impl ScanProgressTracker {
+    pub fn new(table_name: &str, repo_path: &str, branch: &str) -> Self {
+        ScanProgressTracker {
+            table_name: table_name.to_string(),
+            repo_path: repo_path.to_string(),
+            branch: branch.to_string(),
+            last_reported_count: 0,
+        }
+    }
+
+    /// Update the number of collected rows and report it if enough rows are collected since the last report,
+    /// returns true if the progress is reported
+    pub fn update(&mut self, rows_count: usize) -> bool {
+        if rows_count < self.last_reported_count + PROGRESS_REPORT_INTERVAL {
+            return false;
+        }
+
+        self.last_reported_count = rows_count;
+        if let Ok(reporter) = PROGRESS_REPORTER.read() {
+            if let Some(reporter) = reporter.as_ref() {
+                reporter.report_progress(&ScanProgress {
+                    table_name: &self.table_name,
+                    repo_path: &self.repo_path,
+                    branch: &self.branch,
+                    rows_count,
+                });
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_progress_tracker_interval() {
+        let mut tracker = ScanProgressTracker::new("commits", "repo", "main");
+        assert!(!tracker.update(1));
+        assert!(!tracker.update(PROGRESS_REPORT_INTERVAL - 1));
+        assert!(tracker.update(PROGRESS_REPORT_INTERVAL));
+        assert!(!tracker.update(PROGRESS_REPORT_INTERVAL + 1));
+        assert!(tracker.update(PROGRESS_REPORT_INTERVAL * 2 + 5));
+    }
+}
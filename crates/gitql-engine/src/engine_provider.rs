@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::expression::Expression;
+use gitql_ast::expression::SymbolExpression;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+pub use gitql_ast::provider::DataProvider;
+use gitql_ast::types::DataType;
+use gitql_ast::value::Value;
+
+use crate::engine_evaluator::evaluate_expression;
+
+/// Register the table fields in the environment to be type checked, and the provider that select its rows,
+/// registering the same table again replaces its provider
+///
+/// ```ignore
+/// register_data_provider(
+///     &mut env,
+///     "issues",
+///     vec![("id", DataType::Integer), ("title", DataType::Text)],
+///     Box::new(IssuesProvider),
+/// )?;
+/// ```
+pub fn register_data_provider(
+    env: &mut Environment,
+    table_name: &str,
+    fields: Vec<(&str, DataType)>,
+    provider: Box<dyn DataProvider>,
+) -> Result<(), String> {
+    env.register_table(table_name, fields)?;
+
+    env.providers
+        .insert(table_name.to_lowercase(), Arc::from(provider));
+    Ok(())
+}
+
+/// Returns the provider registered for the table in the environment if exists
+pub fn data_provider(env: &Environment, table_name: &str) -> Option<Arc<dyn DataProvider>> {
+    env.providers.get(table_name).cloned()
+}
+
+/// Returns true if the table rows are selected from a provider registered in the environment
+pub fn is_data_provider_table(env: &Environment, table_name: &str) -> bool {
+    env.providers.contains_key(table_name)
+}
+
+/// Select the table rows from the provider then build the selected fields and expressions
+pub fn select_provided_rows(
+    env: &mut Environment,
+    provider: Arc<dyn DataProvider>,
+    table_name: &str,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let table_fields: Vec<String> = env
+        .table_fields(table_name)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    let table_rows = provider.provide(table_name)?;
+    project_table_rows(
+        env,
+        &table_fields,
+        &table_rows,
+        fields_names,
+        titles,
+        fields_values,
+    )
+}
+
+/// Build the selected fields and expressions from rows that contains all table fields in the table order
+pub fn project_table_rows(
+    env: &mut Environment,
+    table_fields: &[String],
+    table_rows: &[Row],
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    // Calculate the index of each field in the table row once
+    let fields_indexes: Vec<Option<usize>> = fields_names
+        .iter()
+        .map(|name| table_fields.iter().position(|field| field == name))
+        .collect();
+
+    let names_len = fields_names.len() as i64;
+    let values_len = fields_values.len() as i64;
+    let padding = names_len - values_len;
+
+    let mut rows: Vec<Row> = Vec::with_capacity(table_rows.len());
+    for table_row in table_rows {
+        let mut values: Vec<Value> = Vec::with_capacity(fields_names.len());
+
+        for index in 0..names_len {
+            if (index - padding) >= 0 {
+                let value = &fields_values[(index - padding) as usize];
+                if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                    let evaluated = evaluate_expression(env, value, titles, &values)?;
+                    values.push(evaluated);
+                    continue;
+                }
+            }
+
+            let value = match fields_indexes[index as usize] {
+                Some(field_index) => table_row
+                    .values
+                    .get(field_index)
+                    .cloned()
+                    .unwrap_or(Value::Null),
+                None => Value::Null,
+            };
+            values.push(value);
+        }
+
+        rows.push(Row { values });
+    }
+
+    Ok(Group { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct IssuesProvider;
+
+    impl DataProvider for IssuesProvider {
+        fn provide(&self, _table_name: &str) -> Result<Vec<Row>, String> {
+            Ok(vec![Row {
+                values: vec![Value::Integer(1), Value::Text("Crash".to_string())],
+            }])
+        }
+    }
+
+    #[test]
+    fn test_register_data_provider() {
+        let mut env = Environment::default();
+        let fields = vec![("id", DataType::Integer), ("title", DataType::Text)];
+        let ret = register_data_provider(&mut env, "issues", fields, Box::new(IssuesProvider));
+        assert!(ret.is_ok());
+        assert!(env.has_table("issues"));
+        assert!(is_data_provider_table(&env, "issues"));
+        assert!(!is_data_provider_table(&Environment::default(), "issues"));
+
+        let fields_names = vec!["title".to_string()];
+        let provider = data_provider(&env, "issues").unwrap();
+        let group = select_provided_rows(
+            &mut env,
+            provider,
+            "issues",
+            &fields_names,
+            &fields_names,
+            &[],
+        );
+        if let Ok(group) = group {
+            assert!(group.rows[0].values[0].equals(&Value::Text("Crash".to_string())));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_register_builtin_table() {
+        let mut env = Environment::default();
+        let fields = vec![("id", DataType::Integer)];
+        let ret = register_data_provider(&mut env, "commits", fields, Box::new(IssuesProvider));
+        assert!(ret.is_err());
+    }
+}
@@ -0,0 +1,8 @@
+/// Repository that the rows of the builtin tables like `commits` and `branches` are selected from
+#[cfg(feature = "git")]
+pub type Repository = gix::Repository;
+
+/// Without the `git` feature there is no repository to open, so queries can select only from
+/// the data providers, temporary, external and `VALUES` tables
+#[cfg(not(feature = "git"))]
+pub enum Repository {}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::expression::Expression;
+use gitql_ast::expression::SymbolExpression;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+use gitql_ast::statement::ValuesTable;
+use gitql_ast::value::Value;
+
+use crate::engine_evaluator::evaluate_expression;
+
+/// Evaluate the selected expressions once without any table and returns them as one row
+pub fn select_values(
+    env: &mut Environment,
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let mut group = Group { rows: vec![] };
+    let mut values = Vec::with_capacity(fields_values.len());
+
+    for value in fields_values.iter() {
+        let evaluated = evaluate_expression(env, value, titles, &values)?;
+        values.push(evaluated);
+    }
+
+    group.rows.push(Row { values });
+    Ok(group)
+}
+
+/// Evaluate the rows of `VALUES` table then select the fields and expressions from them
+pub fn select_values_table(
+    env: &mut Environment,
+    values_table: &ValuesTable,
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    let mut table_rows: Vec<Row> = Vec::with_capacity(values_table.rows.len());
+    for row in &values_table.rows {
+        let mut values: Vec<Value> = Vec::with_capacity(row.len());
+        for value in row {
+            values.push(evaluate_expression(env, value, &[], &vec![])?);
+        }
+        table_rows.push(Row { values });
+    }
+
+    select_derived_table_rows(
+        env,
+        &values_table.columns,
+        &table_rows,
+        fields_names,
+        titles,
+        fields_values,
+    )
+}
+
+/// Select the fields and expressions from each row of derived table like `VALUES` or subquery,
+/// the table fields are resolved first so expressions can use any of them
+pub fn select_derived_table_rows(
+    env: &mut Environment,
+    columns: &[String],
+    table_rows: &[Row],
+    fields_names: &[String],
+    titles: &[String],
+    fields_values: &[Box<dyn Expression>],
+) -> Result<Group, String> {
+    // Calculate the index of each field in the table row once
+    let fields_indexes: Vec<Option<usize>> = fields_names
+        .iter()
+        .map(|name| columns.iter().position(|column| column == name))
+        .collect();
+
+    let mut group = Group { rows: vec![] };
+    for table_row in table_rows {
+        let mut values: Vec<Value> = fields_indexes
+            .iter()
+            .map(|index| index.map_or(Value::Null, |index| table_row.values[index].clone()))
+            .collect();
+
+        // Hidden fields are before the selected fields that have the expressions
+        let padding = fields_names.len() - fields_values.len();
+        for (index, value) in fields_values.iter().enumerate() {
+            if value.as_any().downcast_ref::<SymbolExpression>().is_none() {
+                values[padding + index] = evaluate_expression(env, value, titles, &values)?;
+            }
+        }
+
+        group.rows.push(Row { values });
+    }
+
+    Ok(group)
+}
+
+#[inline(always)]
+pub fn get_column_name(alias_table: &HashMap<String, String>, name: &str) -> String {
+    alias_table
+        .get(name)
+        .unwrap_or(&name.to_string())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::expression::StringExpression;
+    use gitql_ast::expression::StringValueType;
+
+    #[test]
+    fn test_select_values() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let titles = vec!["title".to_string()];
+
+        let fields_values: Vec<Box<dyn Expression>> = vec![Box::new(StringExpression {
+            value: "value".to_string(),
+            value_type: StringValueType::Text,
+        })];
+
+        let ret = select_values(&mut env, &titles, &fields_values);
+        if ret.is_ok() {
+            assert!(true);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_get_column_name() {
+        let mut table: HashMap<String, String> = HashMap::new();
+        table.insert("key".to_string(), "value".to_string());
+
+        let name = "key";
+        let ret = get_column_name(&table, name);
+        assert_eq!(ret, "value".to_string());
+
+        let name = "invalid";
+        let ret = get_column_name(&table, name);
+        assert_eq!(ret, "invalid".to_string());
+    }
+}
@@ -0,0 +1,262 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Commit headers that hold the signature of the commit for SHA-1 and SHA-256 repositories
+const COMMIT_SIGNATURE_HEADERS: [&[u8]; 2] = [b"gpgsig ", b"gpgsig-sha256 "];
+
+/// First line of the signatures appended to the end of the tag message
+const TAG_SIGNATURE_BEGINS: [&[u8]; 4] = [
+    b"-----BEGIN PGP SIGNATURE-----",
+    b"-----BEGIN PGP MESSAGE-----",
+    b"-----BEGIN SSH SIGNATURE-----",
+    b"-----BEGIN SIGNED MESSAGE-----",
+];
+
+/// Only OpenPGP signatures can be verified by gpg, SSH and X509 signatures are reported as unverified
+const PGP_SIGNATURE_BEGIN: &[u8] = b"-----BEGIN PGP";
+
+/// Program used to verify the signatures, it's resolved from `PATH` and `gpg.program` is ignored because
+/// the repository local config is controlled by the repository author and can run any program
+const GPG_PROGRAM: &str = "gpg";
+
+pub const SIGNATURE_STATUS_UNSIGNED: &str = "unsigned";
+pub const SIGNATURE_STATUS_GOOD: &str = "good";
+pub const SIGNATURE_STATUS_BAD: &str = "bad";
+pub const SIGNATURE_STATUS_EXPIRED: &str = "expired";
+pub const SIGNATURE_STATUS_EXPIRED_KEY: &str = "expired_key";
+pub const SIGNATURE_STATUS_REVOKED_KEY: &str = "revoked_key";
+pub const SIGNATURE_STATUS_MISSING_KEY: &str = "missing_key";
+pub const SIGNATURE_STATUS_UNVERIFIED: &str = "unverified";
+
+/// Used to create unique names for the signature files passed to gpg from many threads
+static SIGNATURE_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Signature of commit or tag object with the content that is signed by it
+pub struct SignedData {
+    pub payload: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// Result of the signature verification, signer is the user id of the key or the key id if the key is missing
+pub struct SignatureVerification {
+    pub status: &'static str,
+    pub signer: Option<String>,
+}
+
+/// Extract the signature from the headers of the raw commit object, the payload is the commit without them
+pub fn extract_commit_signature(data: &[u8]) -> Option<SignedData> {
+    let mut payload = Vec::with_capacity(data.len());
+    let mut signature = Vec::new();
+    let mut is_header = true;
+    let mut is_signature_header = false;
+
+    for line in data.split_inclusive(|byte| *byte == b'\n') {
+        if !is_header || line == b"\n" {
+            is_header = false;
+            payload.extend_from_slice(line);
+            continue;
+        }
+
+        // Multi lines header values are continued on the lines that start with space
+        if is_signature_header {
+            if let Some(value) = line.strip_prefix(b" ") {
+                signature.extend_from_slice(value);
+                continue;
+            }
+        }
+
+        let header_value = COMMIT_SIGNATURE_HEADERS
+            .iter()
+            .find_map(|header| line.strip_prefix(*header));
+
+        is_signature_header = header_value.is_some();
+        match header_value {
+            Some(value) if signature.is_empty() => signature.extend_from_slice(value),
+            Some(_) => {}
+            None => payload.extend_from_slice(line),
+        }
+    }
+
+    if signature.is_empty() {
+        return None;
+    }
+
+    Some(SignedData { payload, signature })
+}
+
+/// Extract the signature appended to the message of the raw tag object, the payload is the tag before it
+pub fn extract_tag_signature(data: &[u8]) -> Option<SignedData> {
+    let mut offset = 0;
+    for line in data.split_inclusive(|byte| *byte == b'\n') {
+        if TAG_SIGNATURE_BEGINS
+            .iter()
+            .any(|begin| line.starts_with(begin))
+        {
+            return Some(SignedData {
+                payload: data[..offset].to_vec(),
+                signature: data[offset..].to_vec(),
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Verify the signature using gpg, the status is `unverified` if gpg is not available
+/// or the signature is not OpenPGP signature
+pub fn verify_signature(signed: Option<&SignedData>) -> SignatureVerification {
+    let Some(signed) = signed else {
+        return SignatureVerification {
+            status: SIGNATURE_STATUS_UNSIGNED,
+            signer: None,
+        };
+    };
+
+    let unverified = SignatureVerification {
+        status: SIGNATURE_STATUS_UNVERIFIED,
+        signer: None,
+    };
+
+    if !signed.signature.starts_with(PGP_SIGNATURE_BEGIN) {
+        return unverified;
+    }
+
+    run_gpg_verify(GPG_PROGRAM, signed).unwrap_or(unverified)
+}
+
+/// Run `gpg --verify` with the signature in temporary file and the payload in the standard input,
+/// returns None if gpg can't be executed
+fn run_gpg_verify(program: &str, signed: &SignedData) -> Option<SignatureVerification> {
+    let file_name = format!(
+        "gitql-signature-{}-{}.asc",
+        std::process::id(),
+        SIGNATURE_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let signature_path = std::env::temp_dir().join(file_name);
+    std::fs::write(&signature_path, &signed.signature).ok()?;
+
+    let output = Command::new(program)
+        .arg("--status-fd=1")
+        .arg("--verify")
+        .arg(&signature_path)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(&signed.payload)?;
+            }
+            child.wait_with_output()
+        });
+
+    let _ = std::fs::remove_file(&signature_path);
+    let output = output.ok()?;
+    Some(parse_gpg_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the machine readable status lines printed by gpg to the status file descriptor
+fn parse_gpg_status(status: &str) -> SignatureVerification {
+    for line in status.lines() {
+        let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+
+        let mut parts = line.splitn(3, ' ');
+        let keyword = parts.next().unwrap_or_default();
+        let key_id = parts.next().map(|key_id| key_id.to_string());
+        let user_id = parts.next().map(|user_id| user_id.to_string());
+
+        let status = match keyword {
+            "GOODSIG" => SIGNATURE_STATUS_GOOD,
+            "BADSIG" => SIGNATURE_STATUS_BAD,
+            "EXPSIG" => SIGNATURE_STATUS_EXPIRED,
+            "EXPKEYSIG" => SIGNATURE_STATUS_EXPIRED_KEY,
+            "REVKEYSIG" => SIGNATURE_STATUS_REVOKED_KEY,
+            "ERRSIG" if is_missing_key_error(line) => SIGNATURE_STATUS_MISSING_KEY,
+            "ERRSIG" => SIGNATURE_STATUS_UNVERIFIED,
+            _ => continue,
+        };
+
+        // ERRSIG has no user id because the key is not known, so the key id is used as signer
+        let signer = if keyword == "ERRSIG" { key_id } else { user_id };
+        return SignatureVerification { status, signer };
+    }
+
+    SignatureVerification {
+        status: SIGNATURE_STATUS_UNVERIFIED,
+        signer: None,
+    }
+}
+
+/// ERRSIG line is `ERRSIG <keyid> <pkalgo> <hashalgo> <sig_class> <time> <rc> [<fpr>]`, return code 9 means missing key
+fn is_missing_key_error(line: &str) -> bool {
+    line.split(' ').nth(6) == Some("9")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_commit_signature() {
+        let commit = b"tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n\
+author name <name@example.com> 1700000000 +0000\n\
+committer name <name@example.com> 1700000000 +0000\n\
+gpgsig -----BEGIN PGP SIGNATURE-----\n \n abc\n -----END PGP SIGNATURE-----\n\
+\n\
+Signed commit\n";
+
+        let signed = extract_commit_signature(commit).unwrap();
+        assert_eq!(
+            signed.signature,
+            b"-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----\n"
+        );
+        assert_eq!(
+            signed.payload,
+            b"tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n\
+author name <name@example.com> 1700000000 +0000\n\
+committer name <name@example.com> 1700000000 +0000\n\
+\n\
+Signed commit\n"
+        );
+
+        let unsigned = b"tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n\ngpgsig in message\n";
+        assert!(extract_commit_signature(unsigned).is_none());
+    }
+
+    #[test]
+    fn test_extract_tag_signature() {
+        let tag = b"object 4b825dc642cb6eb9a060e54bf8d69288fbee4904\ntype commit\ntag v1\n\n\
+Release\n-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n";
+
+        let signed = extract_tag_signature(tag).unwrap();
+        assert!(signed.payload.ends_with(b"Release\n"));
+        assert!(signed
+            .signature
+            .starts_with(b"-----BEGIN SSH SIGNATURE-----"));
+        assert!(extract_tag_signature(b"object 4b825dc6\ntype commit\n\nRelease\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG 0123456789ABCDEF Name <name@example.com>\n";
+        let verification = parse_gpg_status(good);
+        assert_eq!(verification.status, SIGNATURE_STATUS_GOOD);
+        assert_eq!(
+            verification.signer,
+            Some("Name <name@example.com>".to_string())
+        );
+
+        let missing = "[GNUPG:] ERRSIG 0123456789ABCDEF 1 8 00 1700000000 9 -\n";
+        let verification = parse_gpg_status(missing);
+        assert_eq!(verification.status, SIGNATURE_STATUS_MISSING_KEY);
+        assert_eq!(verification.signer, Some("0123456789ABCDEF".to_string()));
+
+        assert_eq!(parse_gpg_status("").status, SIGNATURE_STATUS_UNVERIFIED);
+    }
+}
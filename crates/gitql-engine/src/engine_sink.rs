@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use gitql_ast::object::GitQLObject;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::statement::FileFormat;
+
+/// Append the rows of the object to the file, the header of CSV and TSV files is written only
+/// if the file is new or empty, so the same file can be filled by many `INSERT INTO FILE` queries
+pub fn append_object_to_file(
+    path: &str,
+    format: FileFormat,
+    object: &GitQLObject,
+    options: &SessionOptions,
+) -> Result<(), String> {
+    let is_empty_file = std::fs::metadata(path).map_or(true, |metadata| metadata.len() == 0);
+    let content = match format {
+        FileFormat::CSV => object
+            .as_delimited(b',', is_empty_file, options)
+            .map_err(|error| error.to_string()),
+        FileFormat::TSV => object
+            .as_delimited(b'\t', is_empty_file, options)
+            .map_err(|error| error.to_string()),
+        FileFormat::JSONLines => object
+            .as_json_lines(options)
+            .map_err(|error| error.to_string()),
+    }
+    .map_err(|error| format!("Can't export rows to file `{}`: {}", path, error))?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| format!("Can't open file `{}`: {}", path, error))?;
+
+    file.write_all(content.as_bytes())
+        .map_err(|error| format!("Can't write to file `{}`: {}", path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::object::Group;
+    use gitql_ast::object::Row;
+    use gitql_ast::value::Value;
+
+    #[test]
+    fn test_append_object_to_file() {
+        let path = std::env::temp_dir().join(format!("gitql-sink-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        let object = GitQLObject {
+            titles: vec!["name".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![Value::Text("gql".to_string())],
+                }],
+            }],
+        };
+
+        let options = SessionOptions::default();
+        assert!(append_object_to_file(path, FileFormat::CSV, &object, &options).is_ok());
+        assert!(append_object_to_file(path, FileFormat::CSV, &object, &options).is_ok());
+        let content = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        // Header is written only once
+        assert_eq!(content, "name\r\ngql\r\ngql\r\n");
+    }
+}
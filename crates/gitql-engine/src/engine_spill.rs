@@ -0,0 +1,1188 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::File;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+
+use gitql_ast::aggregation::AGGREGATIONS_MERGES;
+use gitql_ast::environment::Environment;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+use gitql_ast::spill::SpillOptions;
+use gitql_ast::statement::AggregateValue;
+use gitql_ast::statement::AggregationsStatement;
+use gitql_ast::statement::GroupByStatement;
+use gitql_ast::statement::LimitCount;
+use gitql_ast::statement::LimitStatement;
+use gitql_ast::statement::OffsetStatement;
+use gitql_ast::statement::OrderByStatement;
+use gitql_ast::statement::SortingOrder;
+use gitql_ast::statement::Statement;
+use gitql_ast::value::Value;
+use gitql_parser::diagnostic::Diagnostic;
+
+use crate::engine_diagnostic::runtime_exception;
+use crate::engine_evaluator::evaluate_expression;
+use crate::engine_executor::execute_statement;
+use crate::engine_memory::estimate_row_size;
+use crate::engine_memory::format_memory_size;
+use crate::engine_repository::Repository;
+use crate::engine_rows::get_column_name;
+
+/// Maximum number of spill files that are written or merged at the same time
+const MAX_OPEN_PARTITIONS: usize = 64;
+
+/// Maximum number of times a partition over the spill threshold is split again with another hash seed,
+/// the rows with the same values are never split so one group must still fit in memory
+const MAX_PARTITIONING_ROUNDS: u64 = 4;
+
+/// Number of created spill files, used to give each file a unique name
+static SPILL_FILES_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary file that stores one partition of rows, the file is removed when it's dropped
+pub struct SpillFile {
+    path: PathBuf,
+    rows_count: usize,
+    size: usize,
+}
+
+impl SpillFile {
+    /// Write the rows to new file in the directory
+    pub fn create(directory: &Path, rows: &[Row]) -> Result<SpillFile, String> {
+        let mut writer = SpillWriter::create(directory)?;
+        for row in rows {
+            writer.write_row(row)?;
+        }
+        writer.finish()
+    }
+
+    /// Read all rows of the partition in the same order they are written
+    pub fn read_rows(&self) -> Result<Vec<Row>, String> {
+        let mut reader = self.reader()?;
+        let mut rows = Vec::with_capacity(self.rows_count);
+        while let Some(row) = reader.next_row()? {
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    /// Read the rows of the partition one by one
+    pub fn reader(&self) -> Result<SpillReader, String> {
+        let file = File::open(&self.path).map_err(|error| spill_error(&self.path, error))?;
+        Ok(SpillReader {
+            path: self.path.clone(),
+            reader: BufReader::new(file),
+            remaining: self.rows_count,
+        })
+    }
+
+    /// Returns the number of rows in the partition
+    pub fn len(&self) -> usize {
+        self.rows_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows_count == 0
+    }
+
+    /// Returns the size of the file in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Write rows one by one to a new spill file
+pub struct SpillWriter {
+    file: SpillFile,
+    writer: BufWriter<File>,
+}
+
+impl SpillWriter {
+    /// Create new empty file in the directory
+    pub fn create(directory: &Path) -> Result<SpillWriter, String> {
+        let file_index = SPILL_FILES_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let file_name = format!("gitql-spill-{}-{}.bin", std::process::id(), file_index);
+        let path = directory.join(file_name);
+
+        let file = File::create(&path).map_err(|error| spill_error(&path, error))?;
+
+        // File is removed on drop even if writing the rows fails
+        Ok(SpillWriter {
+            file: SpillFile {
+                path,
+                rows_count: 0,
+                size: 0,
+            },
+            writer: BufWriter::new(file),
+        })
+    }
+
+    pub fn write_row(&mut self, row: &Row) -> Result<(), String> {
+        self.file.size += write_row(&mut self.writer, row)
+            .map_err(|error| spill_error(&self.file.path, error))?;
+        self.file.rows_count += 1;
+        Ok(())
+    }
+
+    /// Flush the written rows and returns the file to be read
+    pub fn finish(mut self) -> Result<SpillFile, String> {
+        self.writer
+            .flush()
+            .map_err(|error| spill_error(&self.file.path, error))?;
+        Ok(self.file)
+    }
+}
+
+/// Read the rows of a spill file one by one in the same order they are written
+pub struct SpillReader {
+    path: PathBuf,
+    reader: BufReader<File>,
+    remaining: usize,
+}
+
+impl SpillReader {
+    /// Returns the next row or `None` after the last row
+    pub fn next_row(&mut self) -> Result<Option<Row>, String> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        let row = read_row(&mut self.reader).map_err(|error| spill_error(&self.path, error))?;
+        Ok(Some(row))
+    }
+}
+
+/// Rows of the main group written to spill files in order, the rows kept in memory come after them
+#[derive(Default)]
+pub struct SpilledRows {
+    partitions: Vec<SpillFile>,
+    size: usize,
+}
+
+impl SpilledRows {
+    /// Write the rows to a new partition after the current partitions, returns an error
+    /// if the spilled rows exceed the spill size limit
+    pub fn spill(&mut self, options: &SpillOptions, rows: &[Row]) -> Result<(), String> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        self.push(options, SpillFile::create(&options.directory, rows)?)
+    }
+
+    /// Push the partition after the current partitions
+    pub fn push(&mut self, options: &SpillOptions, partition: SpillFile) -> Result<(), String> {
+        self.size += partition.size();
+        self.partitions.push(partition);
+        check_spill_size(self.size, options)
+    }
+
+    pub fn partitions(&self) -> &[SpillFile] {
+        &self.partitions
+    }
+
+    /// Read the rows of all partitions in order
+    pub fn read_rows(&self) -> Result<Vec<Row>, String> {
+        let mut rows = Vec::with_capacity(self.len());
+        for partition in &self.partitions {
+            rows.append(&mut partition.read_rows()?);
+        }
+        Ok(rows)
+    }
+
+    /// Returns the number of spilled rows in all partitions
+    pub fn len(&self) -> usize {
+        self.partitions
+            .iter()
+            .map(|partition| partition.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+
+    /// Returns the size of the spill files in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// Spill the rows of the main group if their estimated size exceeds the spill threshold,
+/// so the rows selected from the next repositories come after them
+pub fn spill_selected_rows(
+    env: &Environment,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+) -> Result<(), String> {
+    let Some(options) = &env.spill else {
+        return Ok(());
+    };
+
+    let Some(group) = gitql_object.groups.first_mut() else {
+        return Ok(());
+    };
+
+    let size: usize = group.rows.iter().map(estimate_row_size).sum();
+    if size <= options.threshold {
+        return Ok(());
+    }
+
+    spilled.spill(options, &group.rows)?;
+    group.rows.clear();
+    Ok(())
+}
+
+/// Keep only the unique rows of the spilled rows and the main group like `DISTINCT`, the rows are split
+/// into partitions by the hash of their selected values so each partition is deduplicated on its own,
+/// then the unique rows are merged back in their original order
+pub fn apply_distinct_on_spilled_rows(
+    env: &Environment,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+    hidden_selections: &[String],
+) -> Result<(), String> {
+    let options = env
+        .spill
+        .as_ref()
+        .ok_or("Spilling rows to disk is disabled")?;
+    let values_count = gitql_object
+        .titles
+        .iter()
+        .filter(|title| !hidden_selections.contains(title))
+        .count();
+    let fields_indexes: Vec<usize> = (0..values_count).collect();
+
+    // Each row is written with its index after its values, so the partitions are merged back in order
+    spill_main_group_rows(options, gitql_object, spilled)?;
+    let partitions = partition_rows(options, spilled.partitions(), &fields_indexes, 0, true)?;
+    let partitions = split_large_partitions(options, partitions, &fields_indexes, 0)?;
+    *spilled = SpilledRows::default();
+
+    let mut runs: Vec<SpillFile> = vec![];
+    for partition in partitions {
+        let mut values_set: HashSet<Vec<String>> = HashSet::new();
+        let mut writer = SpillWriter::create(&options.directory)?;
+        let mut reader = partition.reader()?;
+        while let Some(row) = reader.next_row()? {
+            let values = fields_indexes
+                .iter()
+                .map(|index| row.values[*index].to_string())
+                .collect();
+            if values_set.insert(values) {
+                writer.write_row(&row)?;
+            }
+        }
+        runs.push(writer.finish()?);
+    }
+
+    while runs.len() > MAX_OPEN_PARTITIONS {
+        let mut merged_runs = vec![];
+        for chunk in runs.chunks(MAX_OPEN_PARTITIONS) {
+            merged_runs.push(merge_sorted_runs(
+                chunk,
+                &options.directory,
+                &mut row_index,
+                &Ord::cmp,
+                false,
+            )?);
+        }
+        runs = merged_runs;
+    }
+
+    let unique = merge_sorted_runs(&runs, &options.directory, &mut row_index, &Ord::cmp, true)?;
+    spilled.push(options, unique)
+}
+
+/// Returns the index of the row that is written after its values while partitioning it
+fn row_index(row: &Row) -> i64 {
+    match row.values.last() {
+        Some(Value::Integer(index)) => *index,
+        _ => 0,
+    }
+}
+
+/// Load the spilled rows back before the rows of the main group for the statements that need
+/// all rows in memory
+pub fn load_spilled_rows(
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+) -> Result<(), String> {
+    if spilled.is_empty() {
+        return Ok(());
+    }
+
+    let mut rows = std::mem::take(spilled).read_rows()?;
+    match gitql_object.groups.first_mut() {
+        Some(group) => {
+            rows.append(&mut group.rows);
+            group.rows = rows;
+        }
+        None => gitql_object.groups.push(Group { rows }),
+    }
+    Ok(())
+}
+
+/// Execute the statement on the spilled rows before it's executed on the main group, returns true
+/// if the statement is executed on all rows
+///
+/// `WHERE` filters each partition, `GROUP BY` splits the rows into partitions by the grouping
+/// fields and groups each partition on its own, `ORDER BY` sorts each partition then merges them,
+/// `OFFSET` and `LIMIT` keep the range of the spilled rows, aggregations without `GROUP BY` merge
+/// the results of the partitions, and any other statement needs the spilled rows loaded back
+#[allow(clippy::too_many_arguments)]
+pub fn execute_statement_on_spilled_rows(
+    env: &mut Environment,
+    gql_command: &str,
+    statements_map: &mut HashMap<&'static str, Box<dyn Statement>>,
+    repo: Option<&Repository>,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+    alias_table: &mut HashMap<String, String>,
+    hidden_selections: &Vec<String>,
+) -> Result<bool, Box<Diagnostic>> {
+    let Some(options) = env.spill.clone() else {
+        load_spilled_rows(gitql_object, spilled).map_err(runtime_exception)?;
+        return Ok(false);
+    };
+
+    let statement = statements_map.get(gql_command).unwrap();
+    match gql_command {
+        "where" => {
+            let mut filtered = SpilledRows::default();
+            for partition in spilled.partitions() {
+                let mut partition_object = GitQLObject {
+                    titles: gitql_object.titles.clone(),
+                    groups: vec![Group {
+                        rows: partition.read_rows().map_err(runtime_exception)?,
+                    }],
+                };
+
+                execute_statement(
+                    env,
+                    statement,
+                    repo,
+                    &mut partition_object,
+                    alias_table,
+                    hidden_selections,
+                )?;
+
+                filtered
+                    .spill(&options, &partition_object.groups[0].rows)
+                    .map_err(runtime_exception)?;
+            }
+            *spilled = filtered;
+            Ok(false)
+        }
+        "group" => {
+            let group_statement = statement
+                .as_any()
+                .downcast_ref::<GroupByStatement>()
+                .unwrap();
+
+            // Grouping by no fields keeps all rows in one group, and rollup needs the rows of all groups
+            if group_statement.field_names.is_empty() || group_statement.is_rollup {
+                load_spilled_rows(gitql_object, spilled).map_err(runtime_exception)?;
+                return Ok(false);
+            }
+
+            execute_group_by_on_spilled_rows(
+                env,
+                &options,
+                statements_map,
+                repo,
+                gitql_object,
+                spilled,
+                alias_table,
+                hidden_selections,
+            )?;
+
+            // Aggregations are executed on each partition after grouping it
+            statements_map.remove("aggregation");
+            Ok(true)
+        }
+        "aggregation" => {
+            let aggregations_statement = statement
+                .as_any()
+                .downcast_ref::<AggregationsStatement>()
+                .unwrap();
+
+            if !is_mergeable_aggregations(aggregations_statement) {
+                load_spilled_rows(gitql_object, spilled).map_err(runtime_exception)?;
+                return Ok(false);
+            }
+
+            execute_aggregations_on_spilled_rows(
+                env,
+                &options,
+                statement,
+                aggregations_statement,
+                repo,
+                gitql_object,
+                spilled,
+                alias_table,
+                hidden_selections,
+            )?;
+            Ok(true)
+        }
+        "order" => {
+            let order_statement = statement
+                .as_any()
+                .downcast_ref::<OrderByStatement>()
+                .unwrap();
+            spill_main_group_rows(&options, gitql_object, spilled).map_err(runtime_exception)?;
+            execute_order_by_on_spilled_rows(
+                env,
+                &options,
+                order_statement,
+                &gitql_object.titles,
+                spilled,
+            )
+            .map_err(runtime_exception)?;
+            Ok(true)
+        }
+        "offset" => {
+            let offset_statement = statement
+                .as_any()
+                .downcast_ref::<OffsetStatement>()
+                .unwrap();
+            spill_main_group_rows(&options, gitql_object, spilled).map_err(runtime_exception)?;
+            retain_spilled_rows_range(&options, spilled, offset_statement.count, usize::MAX)
+                .map_err(runtime_exception)?;
+            Ok(true)
+        }
+        "limit" => {
+            let limit_statement = statement.as_any().downcast_ref::<LimitStatement>().unwrap();
+            spill_main_group_rows(&options, gitql_object, spilled).map_err(runtime_exception)?;
+            let count = match limit_statement.count {
+                LimitCount::Absolute(count) => count,
+                LimitCount::Ratio(ratio) => (spilled.len() as f64 * ratio).ceil() as usize,
+            };
+            retain_spilled_rows_range(&options, spilled, 0, count).map_err(runtime_exception)?;
+            Ok(true)
+        }
+        _ => {
+            load_spilled_rows(gitql_object, spilled).map_err(runtime_exception)?;
+            Ok(false)
+        }
+    }
+}
+
+/// Spill the rows of the main group after the spilled rows, so all rows are in the partitions
+fn spill_main_group_rows(
+    options: &SpillOptions,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+) -> Result<(), String> {
+    if let Some(group) = gitql_object.groups.first_mut() {
+        spilled.spill(options, &group.rows)?;
+        group.rows.clear();
+    }
+    Ok(())
+}
+
+/// Split the rows into partitions by the hash of the grouping fields, so all rows of each group are
+/// in the same partition, then group and aggregate each partition on its own
+#[allow(clippy::too_many_arguments)]
+fn execute_group_by_on_spilled_rows(
+    env: &mut Environment,
+    options: &SpillOptions,
+    statements_map: &HashMap<&'static str, Box<dyn Statement>>,
+    repo: Option<&Repository>,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+    alias_table: &mut HashMap<String, String>,
+    hidden_selections: &Vec<String>,
+) -> Result<(), Box<Diagnostic>> {
+    let group_statement = statements_map.get("group").unwrap();
+    let aggregations_statement = statements_map.get("aggregation");
+
+    let group_by_statement = group_statement
+        .as_any()
+        .downcast_ref::<GroupByStatement>()
+        .unwrap();
+
+    let mut fields_indexes: Vec<usize> = Vec::with_capacity(group_by_statement.field_names.len());
+    for field_name in &group_by_statement.field_names {
+        let field_index = gitql_object
+            .titles
+            .iter()
+            .position(|r| r.eq(field_name))
+            .unwrap();
+        fields_indexes.push(field_index);
+    }
+
+    spill_main_group_rows(options, gitql_object, spilled).map_err(runtime_exception)?;
+    let partitions = partition_rows(options, spilled.partitions(), &fields_indexes, 0, false)
+        .and_then(|partitions| split_large_partitions(options, partitions, &fields_indexes, 0))
+        .map_err(runtime_exception)?;
+    *spilled = SpilledRows::default();
+
+    let mut groups: Vec<Group> = vec![];
+    for partition in partitions {
+        if partition.is_empty() {
+            continue;
+        }
+
+        let mut partition_object = GitQLObject {
+            titles: gitql_object.titles.clone(),
+            groups: vec![Group {
+                rows: partition.read_rows().map_err(runtime_exception)?,
+            }],
+        };
+
+        execute_statement(
+            env,
+            group_statement,
+            repo,
+            &mut partition_object,
+            alias_table,
+            hidden_selections,
+        )?;
+
+        if let Some(statement) = aggregations_statement {
+            execute_statement(
+                env,
+                statement,
+                repo,
+                &mut partition_object,
+                alias_table,
+                hidden_selections,
+            )?;
+
+            // Aggregated groups are merged into their first rows, so the next partitions fit in memory
+            for group in partition_object.groups.iter_mut() {
+                group.rows.drain(1..);
+            }
+        }
+
+        groups.append(&mut partition_object.groups);
+    }
+
+    gitql_object.groups = groups;
+    Ok(())
+}
+
+/// Write the rows of the partitions to new partitions by the hash of the fields values with the seed,
+/// the number of partitions is chosen so each one is expected to be under the spill threshold,
+/// if `is_indexed` the index of each row is written after its values
+fn partition_rows(
+    options: &SpillOptions,
+    partitions: &[SpillFile],
+    fields_indexes: &[usize],
+    seed: u64,
+    is_indexed: bool,
+) -> Result<Vec<SpillFile>, String> {
+    let size: usize = partitions.iter().map(|partition| partition.size()).sum();
+    let partitions_count = (size * 2 / options.threshold.max(1) + 1).clamp(2, MAX_OPEN_PARTITIONS);
+
+    let mut writers = Vec::with_capacity(partitions_count);
+    for _ in 0..partitions_count {
+        writers.push(SpillWriter::create(&options.directory)?);
+    }
+
+    let mut row_index = 0;
+    for partition in partitions {
+        let mut reader = partition.reader()?;
+        while let Some(mut row) = reader.next_row()? {
+            let fields_values: Vec<String> = fields_indexes
+                .iter()
+                .map(|index| row.values[*index].to_string())
+                .collect();
+
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            fields_values.hash(&mut hasher);
+            let index = (hasher.finish() % partitions_count as u64) as usize;
+
+            if is_indexed {
+                row.values.push(Value::Integer(row_index));
+                row_index += 1;
+            }
+            writers[index].write_row(&row)?;
+        }
+    }
+
+    writers.into_iter().map(|writer| writer.finish()).collect()
+}
+
+/// Split the partitions that are expected to exceed the spill threshold in memory again with
+/// another hash seed, and remove the empty partitions, the order of the rows in each partition is kept
+fn split_large_partitions(
+    options: &SpillOptions,
+    partitions: Vec<SpillFile>,
+    fields_indexes: &[usize],
+    seed: u64,
+) -> Result<Vec<SpillFile>, String> {
+    let mut split_partitions = Vec::with_capacity(partitions.len());
+    for partition in partitions {
+        if partition.is_empty() {
+            continue;
+        }
+
+        // Rows in memory are about twice their size in the spill files
+        if partition.size() * 2 <= options.threshold || seed + 1 >= MAX_PARTITIONING_ROUNDS {
+            split_partitions.push(partition);
+            continue;
+        }
+
+        let parts = partition_rows(
+            options,
+            std::slice::from_ref(&partition),
+            fields_indexes,
+            seed + 1,
+            false,
+        )?;
+
+        // All rows have the same values if they are in the same partition with another seed
+        if parts.iter().filter(|part| !part.is_empty()).count() <= 1 {
+            split_partitions.push(partition);
+            continue;
+        }
+
+        let mut parts = split_large_partitions(options, parts, fields_indexes, seed + 1)?;
+        split_partitions.append(&mut parts);
+    }
+    Ok(split_partitions)
+}
+
+/// Returns true if the results of all aggregations functions can be merged
+fn is_mergeable_aggregations(aggregations_statement: &AggregationsStatement) -> bool {
+    aggregations_statement
+        .aggregations
+        .values()
+        .all(|aggregation| match aggregation {
+            // `DISTINCT` and ordered calls depend on the rows of other partitions
+            AggregateValue::Function(call) => {
+                !call.is_distinct
+                    && call.order_by.is_none()
+                    && call.separator.is_none()
+                    && AGGREGATIONS_MERGES.contains_key(call.function_name.as_str())
+            }
+            AggregateValue::Expression(_) => true,
+        })
+}
+
+/// Aggregate each partition as one group then merge the results of the partitions into one row,
+/// and calculate the aggregations expressions from the merged results
+#[allow(clippy::borrowed_box)]
+#[allow(clippy::too_many_arguments)]
+fn execute_aggregations_on_spilled_rows(
+    env: &mut Environment,
+    options: &SpillOptions,
+    statement: &Box<dyn Statement>,
+    aggregations_statement: &AggregationsStatement,
+    repo: Option<&Repository>,
+    gitql_object: &mut GitQLObject,
+    spilled: &mut SpilledRows,
+    alias_table: &mut HashMap<String, String>,
+    hidden_selections: &Vec<String>,
+) -> Result<(), Box<Diagnostic>> {
+    spill_main_group_rows(options, gitql_object, spilled).map_err(runtime_exception)?;
+
+    let mut functions_columns: Vec<(&str, usize)> = vec![];
+    let mut expressions_columns = vec![];
+    for (result_column_name, aggregation) in &aggregations_statement.aggregations {
+        let column_name = get_column_name(alias_table, result_column_name);
+        let column_index = gitql_object
+            .titles
+            .iter()
+            .position(|r| r.eq(&column_name))
+            .unwrap();
+        match aggregation {
+            AggregateValue::Function(call) => {
+                functions_columns.push((call.function_name.as_str(), column_index))
+            }
+            AggregateValue::Expression(expr) => expressions_columns.push((expr, column_index)),
+        }
+    }
+
+    let mut merged: Option<Row> = None;
+    for partition in std::mem::take(spilled).partitions() {
+        if partition.is_empty() {
+            continue;
+        }
+
+        let mut partition_object = GitQLObject {
+            titles: gitql_object.titles.clone(),
+            groups: vec![Group {
+                rows: partition.read_rows().map_err(runtime_exception)?,
+            }],
+        };
+
+        execute_statement(
+            env,
+            statement,
+            repo,
+            &mut partition_object,
+            alias_table,
+            hidden_selections,
+        )?;
+
+        let row = partition_object.groups[0].rows.swap_remove(0);
+        merged = match merged {
+            Some(mut merged) => {
+                for (function, column_index) in &functions_columns {
+                    let merge = AGGREGATIONS_MERGES.get(function).unwrap();
+                    merged.values[*column_index] =
+                        merge(&merged.values[*column_index], &row.values[*column_index]);
+                }
+                Some(merged)
+            }
+            None => Some(row),
+        };
+    }
+
+    // Aggregations expressions are calculated again from the merged results
+    if let Some(merged) = merged.as_mut() {
+        for (expr, column_index) in expressions_columns {
+            let result = evaluate_expression(env, expr, &gitql_object.titles, &merged.values)
+                .map_err(runtime_exception)?;
+            merged.values[column_index] = result;
+        }
+    }
+
+    gitql_object.groups = vec![Group {
+        rows: merged.into_iter().collect(),
+    }];
+    Ok(())
+}
+
+/// Sort each partition on its own into a sorted run, then merge the sorted runs into one
+fn execute_order_by_on_spilled_rows(
+    env: &mut Environment,
+    options: &SpillOptions,
+    statement: &OrderByStatement,
+    titles: &[String],
+    spilled: &mut SpilledRows,
+) -> Result<(), String> {
+    let mut runs: Vec<SpillFile> = vec![];
+    for partition in spilled.partitions() {
+        let mut rows_keys = vec![];
+        for row in partition.read_rows()? {
+            rows_keys.push((order_keys(env, statement, titles, &row), row));
+        }
+
+        rows_keys.sort_by(|(a, _), (b, _)| compare_order_keys(statement, a, b));
+        let rows: Vec<Row> = rows_keys.into_iter().map(|(_, row)| row).collect();
+        runs.push(SpillFile::create(&options.directory, &rows)?);
+    }
+
+    let mut row_keys = |row: &Row| order_keys(env, statement, titles, row);
+    let compare = |a: &Vec<Value>, b: &Vec<Value>| compare_order_keys(statement, a, b);
+    while runs.len() > 1 {
+        let mut merged_runs = vec![];
+        for chunk in runs.chunks(MAX_OPEN_PARTITIONS) {
+            merged_runs.push(merge_sorted_runs(
+                chunk,
+                &options.directory,
+                &mut row_keys,
+                &compare,
+                false,
+            )?);
+        }
+        runs = merged_runs;
+    }
+
+    let mut sorted = SpilledRows::default();
+    for run in runs {
+        sorted.push(options, run)?;
+    }
+    *spilled = sorted;
+    Ok(())
+}
+
+/// Merge the runs that are sorted by the keys of their rows into one sorted file, equal rows keep
+/// the order of their runs, if `is_indexed` the index after the values of each row is removed
+fn merge_sorted_runs<K>(
+    runs: &[SpillFile],
+    directory: &Path,
+    row_keys: &mut impl FnMut(&Row) -> K,
+    compare: &impl Fn(&K, &K) -> Ordering,
+    is_indexed: bool,
+) -> Result<SpillFile, String> {
+    let mut readers = Vec::with_capacity(runs.len());
+    let mut heads: Vec<Option<(K, Row)>> = Vec::with_capacity(runs.len());
+    for run in runs {
+        let mut reader = run.reader()?;
+        let head = reader.next_row()?.map(|row| (row_keys(&row), row));
+        readers.push(reader);
+        heads.push(head);
+    }
+
+    let mut writer = SpillWriter::create(directory)?;
+    loop {
+        let mut first: Option<usize> = None;
+        for (index, head) in heads.iter().enumerate() {
+            let Some((keys, _)) = head else {
+                continue;
+            };
+
+            first = match first {
+                Some(first_index) => {
+                    let first_keys = &heads[first_index].as_ref().unwrap().0;
+                    if compare(keys, first_keys) == Ordering::Less {
+                        Some(index)
+                    } else {
+                        Some(first_index)
+                    }
+                }
+                None => Some(index),
+            };
+        }
+
+        let Some(index) = first else {
+            break;
+        };
+
+        let (_, mut row) = heads[index].take().unwrap();
+        if is_indexed {
+            row.values.pop();
+        }
+        writer.write_row(&row)?;
+        heads[index] = readers[index].next_row()?.map(|row| (row_keys(&row), row));
+    }
+
+    writer.finish()
+}
+
+/// Evaluate the ordering arguments of the row
+fn order_keys(
+    env: &mut Environment,
+    statement: &OrderByStatement,
+    titles: &[String],
+    row: &Row,
+) -> Vec<Value> {
+    statement
+        .arguments
+        .iter()
+        .map(|argument| {
+            evaluate_expression(env, argument, titles, &row.values).unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
+/// Compare the ordering arguments of two rows like `ORDER BY` statement
+fn compare_order_keys(statement: &OrderByStatement, a: &[Value], b: &[Value]) -> Ordering {
+    for i in 0..statement.arguments.len() {
+        // No need to compare if the ordering argument is constants
+        if statement.arguments[i].is_const() {
+            continue;
+        }
+
+        let ordering = a[i].compare(&b[i]);
+        if ordering == Ordering::Equal {
+            continue;
+        }
+
+        return if statement.sorting_orders[i] == SortingOrder::Descending {
+            ordering
+        } else {
+            ordering.reverse()
+        };
+    }
+    Ordering::Equal
+}
+
+/// Keep only the spilled rows from the start index until before the end index
+fn retain_spilled_rows_range(
+    options: &SpillOptions,
+    spilled: &mut SpilledRows,
+    start: usize,
+    end: usize,
+) -> Result<(), String> {
+    let mut writer = SpillWriter::create(&options.directory)?;
+
+    let mut index = 0;
+    'partitions: for partition in spilled.partitions() {
+        // Partitions before the start are skipped without reading them
+        if index + partition.len() <= start {
+            index += partition.len();
+            continue;
+        }
+
+        let mut reader = partition.reader()?;
+        while let Some(row) = reader.next_row()? {
+            if index >= end {
+                break 'partitions;
+            }
+
+            if index >= start {
+                writer.write_row(&row)?;
+            }
+            index += 1;
+        }
+    }
+
+    let mut retained = SpilledRows::default();
+    retained.push(options, writer.finish()?)?;
+    *spilled = retained;
+    Ok(())
+}
+
+fn check_spill_size(size: usize, options: &SpillOptions) -> Result<(), String> {
+    match options.max_size {
+        Some(max_size) if size > max_size => Err(format!(
+            "Spilled rows exceed the spill limit of {}, select fewer fields or repositories, \
+             or increase the limit using `--spill-limit`",
+            format_memory_size(max_size)
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn spill_error(path: &Path, error: std::io::Error) -> String {
+    format!("Failed to spill rows to `{}`: {}", path.display(), error)
+}
+
+const INTEGER_TAG: u8 = 0;
+const FLOAT_TAG: u8 = 1;
+const TEXT_TAG: u8 = 2;
+const BOOLEAN_TAG: u8 = 3;
+const DATE_TIME_TAG: u8 = 4;
+const DATE_TAG: u8 = 5;
+const TIME_TAG: u8 = 6;
+const NULL_TAG: u8 = 7;
+
+/// Write the number of values then each value as a tag followed by its content,
+/// returns the number of written bytes
+fn write_row(writer: &mut impl Write, row: &Row) -> std::io::Result<usize> {
+    writer.write_all(&(row.values.len() as u32).to_le_bytes())?;
+    let mut size = 4;
+    for value in &row.values {
+        size += match value {
+            Value::Integer(integer) => write_number(writer, INTEGER_TAG, *integer as u64)?,
+            Value::Float(float) => write_number(writer, FLOAT_TAG, float.to_bits())?,
+            Value::DateTime(date_time) => write_number(writer, DATE_TIME_TAG, *date_time as u64)?,
+            Value::Date(date) => write_number(writer, DATE_TAG, *date as u64)?,
+            Value::Text(text) => write_text(writer, TEXT_TAG, text)?,
+            Value::Time(time) => write_text(writer, TIME_TAG, time)?,
+            Value::Boolean(boolean) => {
+                writer.write_all(&[BOOLEAN_TAG, *boolean as u8])?;
+                2
+            }
+            Value::Null => {
+                writer.write_all(&[NULL_TAG])?;
+                1
+            }
+        };
+    }
+    Ok(size)
+}
+
+fn write_number(writer: &mut impl Write, tag: u8, number: u64) -> std::io::Result<usize> {
+    writer.write_all(&[tag])?;
+    writer.write_all(&number.to_le_bytes())?;
+    Ok(9)
+}
+
+fn write_text(writer: &mut impl Write, tag: u8, text: &str) -> std::io::Result<usize> {
+    writer.write_all(&[tag])?;
+    writer.write_all(&(text.len() as u32).to_le_bytes())?;
+    writer.write_all(text.as_bytes())?;
+    Ok(5 + text.len())
+}
+
+fn read_row(reader: &mut impl Read) -> std::io::Result<Row> {
+    let values_count = read_u32(reader)? as usize;
+    let mut values = Vec::with_capacity(values_count);
+    for _ in 0..values_count {
+        let mut tag = [0; 1];
+        reader.read_exact(&mut tag)?;
+        let value = match tag[0] {
+            INTEGER_TAG => Value::Integer(read_u64(reader)? as i64),
+            FLOAT_TAG => Value::Float(f64::from_bits(read_u64(reader)?)),
+            DATE_TIME_TAG => Value::DateTime(read_u64(reader)? as i64),
+            DATE_TAG => Value::Date(read_u64(reader)? as i64),
+            TEXT_TAG => Value::Text(read_text(reader)?),
+            TIME_TAG => Value::Time(read_text(reader)?),
+            BOOLEAN_TAG => {
+                let mut boolean = [0; 1];
+                reader.read_exact(&mut boolean)?;
+                Value::Boolean(boolean[0] != 0)
+            }
+            NULL_TAG => Value::Null,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Invalid value in spill file",
+                ))
+            }
+        };
+        values.push(value);
+    }
+    Ok(Row { values })
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_text(reader: &mut impl Read) -> std::io::Result<String> {
+    let len = read_u32(reader)? as usize;
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_ast::spill::DEFAULT_SPILL_THRESHOLD;
+
+    fn integer_rows(values: &[i64]) -> Vec<Row> {
+        values
+            .iter()
+            .map(|value| Row {
+                values: vec![Value::Integer(*value)],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spill_file() {
+        let rows = vec![
+            Row {
+                values: vec![
+                    Value::Integer(-1),
+                    Value::Float(1.5),
+                    Value::Text("GitQL".to_string()),
+                    Value::Boolean(true),
+                ],
+            },
+            Row {
+                values: vec![
+                    Value::DateTime(1704067200),
+                    Value::Date(1704067200),
+                    Value::Time("12:30:00".to_string()),
+                    Value::Null,
+                ],
+            },
+        ];
+
+        let directory = std::env::temp_dir();
+        let spill_file = SpillFile::create(&directory, &rows).ok().unwrap();
+        assert_eq!(spill_file.len(), 2);
+        assert!(spill_file.size() > 0);
+
+        let read_rows = spill_file.read_rows().ok().unwrap();
+        assert_eq!(read_rows.len(), rows.len());
+        for (read_row, row) in read_rows.iter().zip(&rows) {
+            assert_eq!(read_row.values.len(), row.values.len());
+            for (read_value, value) in read_row.values.iter().zip(&row.values) {
+                assert_eq!(read_value.to_string(), value.to_string());
+            }
+        }
+
+        let path = spill_file.path.clone();
+        drop(spill_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_check_spill_size() {
+        let options = SpillOptions {
+            directory: std::env::temp_dir(),
+            threshold: DEFAULT_SPILL_THRESHOLD,
+            max_size: Some(1024),
+        };
+        assert!(check_spill_size(1024, &options).is_ok());
+        assert!(check_spill_size(1025, &options).is_err());
+
+        let unlimited = SpillOptions {
+            directory: std::env::temp_dir(),
+            threshold: DEFAULT_SPILL_THRESHOLD,
+            max_size: None,
+        };
+        assert!(check_spill_size(usize::MAX, &unlimited).is_ok());
+    }
+
+    #[test]
+    fn test_apply_distinct_on_spilled_rows() {
+        // Every partition is over the threshold, so the partitions are split again
+        let options = SpillOptions {
+            directory: std::env::temp_dir(),
+            threshold: 1,
+            max_size: None,
+        };
+
+        let mut spilled = SpilledRows::default();
+        spilled.spill(&options, &integer_rows(&[3, 1, 3])).unwrap();
+        spilled.spill(&options, &integer_rows(&[2, 1, 4])).unwrap();
+
+        let mut gitql_object = GitQLObject {
+            titles: vec!["number".to_string()],
+            groups: vec![Group {
+                rows: integer_rows(&[2, 5]),
+            }],
+        };
+
+        let env = Environment {
+            spill: Some(options),
+            ..Default::default()
+        };
+        apply_distinct_on_spilled_rows(&env, &mut gitql_object, &mut spilled, &[]).unwrap();
+        load_spilled_rows(&mut gitql_object, &mut spilled).unwrap();
+
+        let values: Vec<i64> = gitql_object.groups[0]
+            .rows
+            .iter()
+            .map(|row| row.values[0].as_int())
+            .collect();
+        assert_eq!(values, vec![3, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_split_large_partitions() {
+        let options = SpillOptions {
+            directory: std::env::temp_dir(),
+            threshold: 64,
+            max_size: None,
+        };
+
+        let values: Vec<i64> = (0..200).map(|value| value % 40).collect();
+        let spilled = [SpillFile::create(&options.directory, &integer_rows(&values)).unwrap()];
+        let partitions = partition_rows(&options, &spilled, &[0], 0, false).unwrap();
+        let partitions = split_large_partitions(&options, partitions, &[0], 0).unwrap();
+        assert!(partitions.len() > 2);
+
+        // Rows with the same values are in the same partition
+        let mut partitions_of_values: HashMap<i64, usize> = HashMap::new();
+        let mut rows_count = 0;
+        for (index, partition) in partitions.iter().enumerate() {
+            for row in partition.read_rows().unwrap() {
+                let value = row.values[0].as_int();
+                assert_eq!(*partitions_of_values.entry(value).or_insert(index), index);
+                rows_count += 1;
+            }
+        }
+        assert_eq!(rows_count, values.len());
+        assert_eq!(partitions_of_values.len(), 40);
+    }
+}
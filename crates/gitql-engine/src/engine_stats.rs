@@ -0,0 +1,83 @@
+use std::time::Duration;
+use std::time::Instant;
+
+/// Number of rows selected from one table and how many of them passed the `WHERE` filter
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableStats {
+    pub table_name: String,
+    pub rows_scanned: usize,
+    pub rows_after_filtering: usize,
+}
+
+/// Statistics and timing breakdown collected while executing one query,
+/// subqueries add their tables and times to the same statistics
+#[derive(Clone, Debug, Default)]
+pub struct QueryStats {
+    pub tables: Vec<TableStats>,
+    pub scan_time: Duration,
+    pub grouping_time: Duration,
+    pub sorting_time: Duration,
+    pub total_time: Duration,
+}
+
+impl QueryStats {
+    /// Record the rows selected from the table, the filtered rows are the same until `WHERE` is executed
+    pub fn record_scan(&mut self, table_name: &str, rows_count: usize, start: Instant) {
+        self.scan_time += start.elapsed();
+        self.tables.push(TableStats {
+            table_name: table_name.to_string(),
+            rows_scanned: rows_count,
+            rows_after_filtering: rows_count,
+        });
+    }
+
+    /// Record the number of rows of the last scanned table after executing `WHERE`
+    pub fn record_filter(&mut self, rows_count: usize) {
+        if let Some(table) = self.tables.last_mut() {
+            table.rows_after_filtering = rows_count;
+        }
+    }
+
+    /// Format the statistics as lines of text with aligned labels
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(self.tables.len() + 4);
+        for table in &self.tables {
+            lines.push(format!(
+                "Table {:<12}: {} rows scanned, {} rows after filtering",
+                table.table_name, table.rows_scanned, table.rows_after_filtering
+            ));
+        }
+        lines.push(format!("Scan time         : {:?}", self.scan_time));
+        lines.push(format!("Grouping time     : {:?}", self.grouping_time));
+        lines.push(format!("Sorting time      : {:?}", self.sorting_time));
+        lines.push(format!("Total time        : {:?}", self.total_time));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_stats() {
+        let mut stats = QueryStats::default();
+        stats.record_filter(5);
+        assert!(stats.tables.is_empty());
+
+        stats.record_scan("commits", 10, Instant::now());
+        stats.record_filter(4);
+        assert_eq!(
+            stats.tables,
+            vec![TableStats {
+                table_name: "commits".to_string(),
+                rows_scanned: 10,
+                rows_after_filtering: 4,
+            }]
+        );
+
+        let lines = stats.to_lines();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains("10 rows scanned, 4 rows after filtering"));
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::object::Row;
+use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::LimitCount;
+use gitql_ast::statement::LimitStatement;
+use gitql_ast::statement::OffsetStatement;
+use gitql_ast::statement::SelectStatement;
+use gitql_ast::statement::Statement;
+use gitql_ast::statement::WhereStatement;
+use gitql_parser::diagnostic::Diagnostic;
+
+use crate::engine::evaluate_select_query;
+use crate::engine::EvaluationResult;
+use crate::engine_diagnostic::row_evaluation_exception;
+use crate::engine_diagnostic::runtime_exception;
+use crate::engine_evaluator::evaluate_expression;
+#[cfg(feature = "git")]
+use crate::engine_function::commits_revwalk;
+#[cfg(feature = "git")]
+use crate::engine_function::is_commit_selected;
+#[cfg(feature = "git")]
+use crate::engine_function::select_commit_row;
+#[cfg(feature = "git")]
+use crate::engine_function::select_gql_objects;
+use crate::engine_provider::data_provider;
+use crate::engine_provider::select_provided_rows;
+use crate::engine_repository::Repository;
+use crate::engine_rows::get_column_name;
+use crate::engine_rows::select_values;
+
+/// Statements that can be executed on each row without waiting for the other rows
+const STREAMABLE_COMMANDS: [&str; 4] = ["select", "where", "offset", "limit"];
+
+enum RowsSource<'a> {
+    #[cfg(feature = "git")]
+    Commits(&'a Repository, gix::revision::Walk<'a>),
+    #[cfg(not(feature = "git"))]
+    Commits(&'a Repository),
+    Rows(std::vec::IntoIter<Row>),
+}
+
+/// Lazy iterator over the result rows of a select query
+///
+/// Queries that contains only select, where, offset and limit statements are executed row by row,
+/// so commits are walked only until enough rows are produced, any other query is executed
+/// first then its rows are yielded one by one
+pub struct RowsStream<'a> {
+    env: &'a mut Environment,
+    repos: &'a [Repository],
+    statements: HashMap<&'static str, Box<dyn Statement>>,
+    titles: Vec<String>,
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
+    fields_names: Vec<String>,
+    hidden_selections: Vec<String>,
+    source: Option<RowsSource<'a>>,
+    repo_index: usize,
+    offset: usize,
+    limit: Option<usize>,
+    is_done: bool,
+}
+
+pub fn execute_streaming<'a>(
+    env: &'a mut Environment,
+    repos: &'a [Repository],
+    query: GQLQuery,
+) -> Result<RowsStream<'a>, Box<Diagnostic>> {
+    if !is_streamable_query(env, &query) {
+        let evaluation_result = evaluate_select_query(env, repos, query, 1)?;
+        let (gitql_object, hidden_selections) = match evaluation_result {
+            EvaluationResult::SelectedGroups(gitql_object, hidden_selections) => {
+                (gitql_object, hidden_selections)
+            }
+            EvaluationResult::SetGlobalVariable
+            | EvaluationResult::CreateTempTable
+            | EvaluationResult::InsertedRows(_) => (Default::default(), vec![]),
+        };
+
+        let rows: Vec<Row> = gitql_object
+            .groups
+            .into_iter()
+            .flat_map(|group| group.rows)
+            .collect();
+
+        return Ok(RowsStream {
+            env,
+            repos: &[],
+            statements: HashMap::new(),
+            titles: gitql_object.titles,
+            fields_names: vec![],
+            hidden_selections,
+            source: Some(RowsSource::Rows(rows.into_iter())),
+            repo_index: 0,
+            offset: 0,
+            limit: None,
+            is_done: false,
+        });
+    }
+
+    let statements = query.statements;
+    let hidden_selections = query.hidden_selections;
+
+    let select_statement = statements
+        .get("select")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<SelectStatement>()
+        .unwrap();
+
+    // Append hidden selection to the selected fields names
+    let mut fields_names = select_statement.fields_names.to_owned();
+    if !select_statement.table_name.is_empty() {
+        for hidden in &hidden_selections {
+            if !fields_names.contains(hidden) {
+                fields_names.push(hidden.to_string());
+            }
+        }
+    }
+
+    let titles: Vec<String> = fields_names
+        .iter()
+        .map(|field_name| get_column_name(&select_statement.alias_table, field_name))
+        .collect();
+
+    // If table name is empty the expressions are evaluated once without any repository,
+    // and data provider tables rows are selected once without any repository too
+    let source = if select_statement.table_name.is_empty() {
+        let group = select_values(env, &titles, &select_statement.fields_values)
+            .map_err(runtime_exception)?;
+        Some(RowsSource::Rows(group.rows.into_iter()))
+    } else if let Some(provider) = data_provider(env, &select_statement.table_name) {
+        let group = select_provided_rows(
+            env,
+            provider,
+            &select_statement.table_name,
+            &fields_names,
+            &titles,
+            &select_statement.fields_values,
+        )
+        .map_err(runtime_exception)?;
+        Some(RowsSource::Rows(group.rows.into_iter()))
+    } else {
+        None
+    };
+
+    // If the rows are already selected no need to perform it on each repository
+    let repos: &[Repository] = if source.is_some() { &[] } else { repos };
+
+    let offset = match statements.get("offset") {
+        Some(statement) => {
+            let statement = statement
+                .as_any()
+                .downcast_ref::<OffsetStatement>()
+                .unwrap();
+            statement.count
+        }
+        None => 0,
+    };
+
+    let limit = match statements.get("limit") {
+        Some(statement) => {
+            let statement = statement.as_any().downcast_ref::<LimitStatement>().unwrap();
+            match statement.count {
+                LimitCount::Absolute(count) => Some(count),
+                LimitCount::Ratio(_) => None,
+            }
+        }
+        None => None,
+    };
+
+    Ok(RowsStream {
+        env,
+        repos,
+        statements,
+        titles,
+        fields_names,
+        hidden_selections,
+        source,
+        repo_index: 0,
+        offset,
+        limit,
+        is_done: false,
+    })
+}
+
+impl<'a> RowsStream<'a> {
+    pub fn titles(&self) -> &[String] {
+        &self.titles
+    }
+
+    pub fn hidden_selections(&self) -> &[String] {
+        &self.hidden_selections
+    }
+
+    fn next_selected_row(&mut self) -> Result<Option<Row>, String> {
+        loop {
+            if let Some(source) = self.source.as_mut() {
+                match source {
+                    RowsSource::Rows(rows) => {
+                        if let Some(row) = rows.next() {
+                            return Ok(Some(row));
+                        }
+                    }
+                    #[cfg(feature = "git")]
+                    RowsSource::Commits(repo, revwalk) => {
+                        let repo = *repo;
+                        if let Some(commit_info) = revwalk.next() {
+                            let commit_info = commit_info.map_err(|error| error.to_string())?;
+                            if !is_commit_selected(repo, &commit_info, &self.fields_names)? {
+                                continue;
+                            }
+
+                            let select_statement = self
+                                .statements
+                                .get("select")
+                                .unwrap()
+                                .as_any()
+                                .downcast_ref::<SelectStatement>()
+                                .unwrap();
+
+                            let row = select_commit_row(
+                                self.env,
+                                repo,
+                                &commit_info,
+                                &self.fields_names,
+                                &self.titles,
+                                &select_statement.fields_values,
+                            )?;
+                            return Ok(Some(row));
+                        }
+                    }
+                    #[cfg(not(feature = "git"))]
+                    RowsSource::Commits(repo) => match **repo {},
+                }
+            }
+
+            // Current source is consumed, move to the next repository
+            let repos = self.repos;
+            if self.repo_index >= repos.len() {
+                return Ok(None);
+            }
+
+            let repo = &repos[self.repo_index];
+            self.repo_index += 1;
+            self.source = Some(self.select_rows_source(repo)?);
+        }
+    }
+
+    #[cfg(feature = "git")]
+    fn select_rows_source(&mut self, repo: &'a Repository) -> Result<RowsSource<'a>, String> {
+        let select_statement = self
+            .statements
+            .get("select")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+
+        // Commits are walked lazily because it's the largest table in most repositories
+        if select_statement.table_name == "commits" {
+            let Some(revwalk) = commits_revwalk(repo, &self.fields_names)? else {
+                return Ok(RowsSource::Rows(vec![].into_iter()));
+            };
+            return Ok(RowsSource::Commits(repo, revwalk));
+        }
+
+        let group = select_gql_objects(
+            self.env,
+            repo,
+            select_statement.table_name.to_string(),
+            &self.fields_names,
+            &self.titles,
+            &select_statement.fields_values,
+        )?;
+
+        Ok(RowsSource::Rows(group.rows.into_iter()))
+    }
+
+    #[cfg(not(feature = "git"))]
+    fn select_rows_source(&mut self, repo: &'a Repository) -> Result<RowsSource<'a>, String> {
+        Ok(RowsSource::Commits(repo))
+    }
+}
+
+impl Iterator for RowsStream<'_> {
+    type Item = Result<Row, Box<Diagnostic>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.is_done || self.limit == Some(0) {
+                return None;
+            }
+
+            let row = match self.next_selected_row() {
+                Ok(Some(row)) => row,
+                Ok(None) => {
+                    self.is_done = true;
+                    return None;
+                }
+                Err(error) => {
+                    self.is_done = true;
+                    return Some(Err(runtime_exception(error)));
+                }
+            };
+
+            if let Some(statement) = self.statements.get("where") {
+                let statement = statement.as_any().downcast_ref::<WhereStatement>().unwrap();
+                match evaluate_expression(self.env, &statement.condition, &self.titles, &row.values)
+                {
+                    Ok(value) => {
+                        if !value.as_bool() {
+                            continue;
+                        }
+                    }
+                    Err(error) => {
+                        self.is_done = true;
+                        return Some(Err(row_evaluation_exception(
+                            &error,
+                            "WHERE",
+                            statement.condition_location,
+                            &self.titles,
+                            &row,
+                            &self.env.session,
+                        )));
+                    }
+                }
+            }
+
+            if self.offset > 0 {
+                self.offset -= 1;
+                continue;
+            }
+
+            if let Some(limit) = self.limit.as_mut() {
+                *limit -= 1;
+            }
+
+            return Some(Ok(row));
+        }
+    }
+}
+
+fn is_streamable_query(env: &Environment, query: &GQLQuery) -> bool {
+    if query.has_aggregation_function
+        || query.has_group_by_statement
+        || query.subquery.is_some()
+        || !query.in_subqueries.is_empty()
+    {
+        return false;
+    }
+
+    for command in query.statements.keys() {
+        if !STREAMABLE_COMMANDS.contains(command) {
+            return false;
+        }
+    }
+
+    if let Some(statement) = query.statements.get("select") {
+        let statement = statement
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+        // `VALUES`, temporary and external tables are already materialized in memory
+        if statement.is_distinct
+            || statement.values_table.is_some()
+            || env.is_in_memory_table(&statement.table_name)
+        {
+            return false;
+        }
+    } else {
+        return false;
+    }
+
+    if let Some(statement) = query.statements.get("limit") {
+        let statement = statement.as_any().downcast_ref::<LimitStatement>().unwrap();
+        if let LimitCount::Ratio(_) = statement.count {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(all(test, feature = "git"))]
+mod tests {
+    use super::*;
+    use crate::engine_test_repo::*;
+    use gitql_ast::statement::Query;
+    use gitql_parser::{parser, tokenizer};
+
+    fn parse_select_query(env: &mut Environment, query: &str) -> GQLQuery {
+        let tokens = tokenizer::tokenize(query).ok().unwrap();
+        match parser::parse_gql(tokens, env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        }
+    }
+
+    #[test]
+    fn test_execute_streaming() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let path = "test-execute-streaming";
+        test_new_repo(path.to_string()).expect("failed to new repo");
+
+        let buf = gix::open(path);
+        let repos = &[buf.ok().unwrap()];
+
+        let query = parse_select_query(&mut env, "SELECT title FROM commits LIMIT 1");
+        let rows: Option<Vec<Result<Row, Box<Diagnostic>>>> =
+            execute_streaming(&mut env, repos, query)
+                .ok()
+                .map(|stream| stream.collect());
+        if let Some(rows) = rows {
+            assert_eq!(rows.len(), 1);
+        } else {
+            test_delete_repo(path.to_string()).expect("failed to delete repo");
+            assert!(false);
+        }
+
+        let query = parse_select_query(&mut env, "SELECT title FROM commits ORDER BY title");
+        let rows: Option<Vec<Result<Row, Box<Diagnostic>>>> =
+            execute_streaming(&mut env, repos, query)
+                .ok()
+                .map(|stream| stream.collect());
+        if let Some(rows) = rows {
+            assert_eq!(rows.len(), 2);
+        } else {
+            test_delete_repo(path.to_string()).expect("failed to delete repo");
+            assert!(false);
+        }
+
+        test_delete_repo(path.to_string()).expect("failed to delete repo");
+    }
+}
@@ -0,0 +1,66 @@
+//! Test repositories shared by the engine modules tests
+
+/// Create bare repository with an empty initial commit and a commit that adds `hello.txt`
+pub fn test_new_repo(path: String) -> Result<(), String> {
+    let mut repo = gix::init_bare(path).expect("failed to init bare");
+    let mut tree = gix::objs::Tree::empty();
+    let object = repo
+        .write_object(&tree)
+        .expect("failed to write object")
+        .detach();
+
+    let mut config = repo.config_snapshot_mut();
+    config
+        .set_raw_value("author", None, "name", "name")
+        .expect("failed to set name");
+    config
+        .set_raw_value("author", None, "email", "name@example.com")
+        .expect("failed to set email");
+
+    let repo = config
+        .commit_auto_rollback()
+        .expect("failed to commit auto rollback");
+    let commit = repo
+        .commit("HEAD", "initial commit", object, gix::commit::NO_PARENT_IDS)
+        .expect("failed to commit");
+
+    let blob = repo
+        .write_blob("hello world")
+        .expect("faile to write blob")
+        .into();
+    let entry = gix::objs::tree::Entry {
+        mode: gix::objs::tree::EntryKind::Blob.into(),
+        oid: blob,
+        filename: "hello.txt".into(),
+    };
+
+    tree.entries.push(entry);
+    let object = repo.write_object(&tree).expect("failed to write object");
+
+    let _ = repo
+        .commit("HEAD", "hello commit", object, [commit])
+        .expect("failed to commit");
+
+    Ok(())
+}
+
+/// Add an empty commit on top of the repository head
+pub fn test_add_commit(path: String) -> Result<(), String> {
+    let repo = gix::open(path).expect("failed to open repo");
+    let head_id = repo.head_id().expect("failed to get head").detach();
+    let tree = gix::objs::Tree::empty();
+    let object = repo
+        .write_object(&tree)
+        .expect("failed to write object")
+        .detach();
+
+    let _ = repo
+        .commit("HEAD", "next commit", object, [head_id])
+        .expect("failed to commit");
+    Ok(())
+}
+
+pub fn test_delete_repo(path: String) -> Result<(), String> {
+    std::fs::remove_dir_all(path).expect("failed to remove dir");
+    Ok(())
+}
@@ -1,4 +1,22 @@
 pub mod engine;
+pub mod engine_diagnostic;
+pub mod engine_estimate;
 pub mod engine_evaluator;
 pub mod engine_executor;
+#[cfg(feature = "git")]
 pub mod engine_function;
+#[cfg(feature = "git")]
+pub mod engine_incremental;
+pub mod engine_memory;
+pub mod engine_pagination;
+pub mod engine_progress;
+pub mod engine_provider;
+pub mod engine_repository;
+pub mod engine_rows;
+pub mod engine_signature;
+pub mod engine_sink;
+pub mod engine_spill;
+pub mod engine_stats;
+pub mod engine_streaming;
+#[cfg(all(test, feature = "git"))]
+mod engine_test_repo;
@@ -0,0 +1,37 @@
+#ifndef GITQL_H
+#define GITQL_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle that owns the environment, the repositories and the last query result */
+typedef struct GitQLSession GitQLSession;
+
+/* Strings returned by the functions are owned by the session and valid until the next call that changes it */
+
+GitQLSession *gitql_session_new(void);
+void gitql_session_free(GitQLSession *session);
+
+/* Returns 0 on success or -1 on failure, the error is returned by gitql_session_error */
+int gitql_session_add_repository(GitQLSession *session, const char *path);
+int gitql_session_execute(GitQLSession *session, const char *query);
+const char *gitql_session_error(const GitQLSession *session);
+
+size_t gitql_result_columns_count(const GitQLSession *session);
+const char *gitql_result_column_name(const GitQLSession *session, size_t index);
+const char *gitql_result_column_type(const GitQLSession *session, size_t index);
+
+/* Returns 1 if moved to the next row or 0 at the end of the result */
+int gitql_result_next_row(GitQLSession *session);
+
+/* Returns the value as text, or NULL if the value is NULL */
+const char *gitql_result_value(const GitQLSession *session, size_t index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GITQL_H */
@@ -0,0 +1,399 @@
+use std::ffi::c_char;
+use std::ffi::c_int;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+
+use gitql_core::gitql_ast::environment::Environment;
+use gitql_core::gitql_ast::format::value_to_text;
+use gitql_core::gitql_ast::object::GitQLObject;
+use gitql_core::gitql_ast::session::SessionOptions;
+use gitql_core::gitql_ast::value::Value;
+use gitql_core::gitql_parser::diagnostic::Diagnostic;
+use gitql_core::ExecuteOptions;
+
+const GITQL_OK: c_int = 0;
+const GITQL_ERROR: c_int = -1;
+
+/// Opaque handle that owns the environment, the opened repositories and the result of the last query,
+/// strings returned by the functions are owned by the session and valid until the next call that changes it
+pub struct GitQLSession {
+    env: Environment,
+    repos: Vec<gix::Repository>,
+    object: GitQLObject,
+    next_row: usize,
+    columns_names: Vec<CString>,
+    columns_types: Vec<CString>,
+    row_values: Vec<Option<CString>>,
+    error: Option<CString>,
+}
+
+impl GitQLSession {
+    fn set_result(&mut self, mut object: GitQLObject) {
+        if object.len() > 1 {
+            object.flat();
+        }
+
+        let columns = object.columns();
+        self.columns_names = columns
+            .iter()
+            .map(|column| to_c_string(&column.name))
+            .collect();
+        self.columns_types = columns
+            .iter()
+            .map(|column| to_c_string(&column.data_type.to_string()))
+            .collect();
+        self.object = object;
+        self.next_row = 0;
+        self.row_values.clear();
+    }
+
+    fn set_error(&mut self, message: &str) -> c_int {
+        self.error = Some(to_c_string(message));
+        GITQL_ERROR
+    }
+}
+
+/// Create a new session without repositories, must be released using `gitql_session_free`
+#[no_mangle]
+pub extern "C" fn gitql_session_new() -> *mut GitQLSession {
+    catch_panic(ptr::null_mut(), || {
+        let session = GitQLSession {
+            env: Environment::default(),
+            repos: vec![],
+            object: GitQLObject::default(),
+            next_row: 0,
+            columns_names: vec![],
+            columns_types: vec![],
+            row_values: vec![],
+            error: None,
+        };
+        Box::into_raw(Box::new(session))
+    })
+}
+
+/// Release the session and all strings returned from it
+///
+/// # Safety
+///
+/// The session must be created by `gitql_session_new` and not used after this call
+#[no_mangle]
+pub unsafe extern "C" fn gitql_session_free(session: *mut GitQLSession) {
+    catch_panic((), || {
+        if !session.is_null() {
+            drop(Box::from_raw(session));
+        }
+    })
+}
+
+/// Open the repository and add it to the repositories that the queries run on,
+/// returns 0 on success or -1 and sets the session error on failure
+///
+/// # Safety
+///
+/// The session must be valid and the path must be a null terminated string
+#[no_mangle]
+pub unsafe extern "C" fn gitql_session_add_repository(
+    session: *mut GitQLSession,
+    path: *const c_char,
+) -> c_int {
+    with_session(session, |session| {
+        let Some(path) = from_c_string(path) else {
+            return session.set_error("Repository path must be valid UTF-8 string");
+        };
+
+        match gitql_core::open_repositories(&[path]) {
+            Ok(mut repos) => {
+                session.repos.append(&mut repos);
+                GITQL_OK
+            }
+            Err(diagnostic) => session.set_error(&diagnostic_to_string(&diagnostic)),
+        }
+    })
+}
+
+/// Execute the query on the session repositories and keep its result to be iterated,
+/// returns 0 on success or -1 and sets the session error to the rendered diagnostic on failure
+///
+/// # Safety
+///
+/// The session must be valid and the query must be a null terminated string
+#[no_mangle]
+pub unsafe extern "C" fn gitql_session_execute(
+    session: *mut GitQLSession,
+    query: *const c_char,
+) -> c_int {
+    with_session(session, |session| {
+        session.set_result(GitQLObject::default());
+        let Some(query) = from_c_string(query) else {
+            return session.set_error("Query must be valid UTF-8 string");
+        };
+
+        let options = ExecuteOptions::default();
+        match gitql_core::execute_in(&mut session.env, &session.repos, query, options) {
+            Ok(result) => {
+                session.set_result(result.object);
+                GITQL_OK
+            }
+            Err(diagnostic) => session.set_error(&diagnostic_to_string(&diagnostic)),
+        }
+    })
+}
+
+/// Returns the error of the last call, or null if it succeeded
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_session_error(session: *const GitQLSession) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        match session.as_ref().and_then(|session| session.error.as_ref()) {
+            Some(error) => error.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// Returns the number of columns in the result of the last query
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_result_columns_count(session: *const GitQLSession) -> usize {
+    catch_panic(0, || {
+        session
+            .as_ref()
+            .map_or(0, |session| session.columns_names.len())
+    })
+}
+
+/// Returns the name of the column, or null if the index is out of range
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_result_column_name(
+    session: *const GitQLSession,
+    index: usize,
+) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        session
+            .as_ref()
+            .and_then(|session| session.columns_names.get(index))
+            .map_or(ptr::null(), |name| name.as_ptr())
+    })
+}
+
+/// Returns the type name of the column, for example `Integer` or `Text`, or null if the index is out of range
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_result_column_type(
+    session: *const GitQLSession,
+    index: usize,
+) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        session
+            .as_ref()
+            .and_then(|session| session.columns_types.get(index))
+            .map_or(ptr::null(), |data_type| data_type.as_ptr())
+    })
+}
+
+/// Move to the next row of the result, returns 1 if there is a row to read or 0 at the end of the result
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_result_next_row(session: *mut GitQLSession) -> c_int {
+    catch_panic(0, || {
+        let Some(session) = session.as_mut() else {
+            return 0;
+        };
+
+        let row = session
+            .object
+            .groups
+            .first()
+            .and_then(|group| group.rows.get(session.next_row));
+
+        let Some(row) = row else {
+            session.row_values.clear();
+            return 0;
+        };
+
+        let options = &session.env.session;
+        session.row_values = row
+            .values
+            .iter()
+            .map(|value| value_to_c_string(value, options))
+            .collect();
+        session.next_row += 1;
+        1
+    })
+}
+
+/// Returns the value of the column in the current row as text, or null if the value is `NULL`
+/// or the index is out of range
+///
+/// # Safety
+///
+/// The session must be valid
+#[no_mangle]
+pub unsafe extern "C" fn gitql_result_value(
+    session: *const GitQLSession,
+    index: usize,
+) -> *const c_char {
+    catch_panic(ptr::null(), || {
+        session
+            .as_ref()
+            .and_then(|session| session.row_values.get(index))
+            .and_then(|value| value.as_ref())
+            .map_or(ptr::null(), |value| value.as_ptr())
+    })
+}
+
+/// Run the body of the exported function and return the fallback value if it panics,
+/// because unwinding across `extern "C"` aborts the host process
+fn catch_panic<T>(fallback: T, body: impl FnOnce() -> T) -> T {
+    std::panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(fallback)
+}
+
+/// Run the body on the session after clearing its error, a panic is returned as `GITQL_ERROR`
+/// with the panic message as the session error and the result of the last query is cleared
+unsafe fn with_session(
+    session: *mut GitQLSession,
+    body: impl FnOnce(&mut GitQLSession) -> c_int,
+) -> c_int {
+    let Some(session) = session.as_mut() else {
+        return GITQL_ERROR;
+    };
+
+    session.error = None;
+    match std::panic::catch_unwind(AssertUnwindSafe(|| body(session))) {
+        Ok(status) => status,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Unknown error".to_string());
+            session.set_result(GitQLObject::default());
+            session.set_error(&format!("[Error]: Query engine panicked: {}", message))
+        }
+    }
+}
+
+/// Render the diagnostic as plain text with its notes and helps on separate lines
+fn diagnostic_to_string(diagnostic: &Diagnostic) -> String {
+    let mut message = format!("[{}]: {}", diagnostic.label(), diagnostic.message());
+    for note in diagnostic.notes() {
+        message.push_str(&format!("\n = Note: {}", note));
+    }
+
+    for help in diagnostic.helps() {
+        message.push_str(&format!("\n = Help: {}", help));
+    }
+
+    if let Some(docs) = diagnostic.docs() {
+        message.push_str(&format!("\n = Docs: {}", docs));
+    }
+    message
+}
+
+fn value_to_c_string(value: &Value, options: &SessionOptions) -> Option<CString> {
+    match value {
+        Value::Null => None,
+        _ => Some(to_c_string(&value_to_text(value, options))),
+    }
+}
+
+/// Convert text to C string, null characters are removed because C strings can't contain them
+fn to_c_string(text: &str) -> CString {
+    CString::new(text.replace('\0', "")).unwrap_or_default()
+}
+
+unsafe fn from_c_string<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    CStr::from_ptr(text).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn read_c_string(text: *const c_char) -> String {
+        CStr::from_ptr(text).to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_session_execute_and_iterate_rows() {
+        unsafe {
+            let session = gitql_session_new();
+            let query = CString::new(
+                "SELECT id, name FROM (VALUES (1, \"a\"), (2, NULL)) AS t(id, name) ORDER BY id",
+            )
+            .unwrap();
+
+            assert_eq!(gitql_session_execute(session, query.as_ptr()), GITQL_OK);
+            assert!(gitql_session_error(session).is_null());
+            assert_eq!(gitql_result_columns_count(session), 2);
+            assert_eq!(read_c_string(gitql_result_column_name(session, 1)), "name");
+            assert!(gitql_result_column_name(session, 2).is_null());
+
+            assert_eq!(gitql_result_next_row(session), 1);
+            assert_eq!(read_c_string(gitql_result_value(session, 0)), "1");
+            assert_eq!(read_c_string(gitql_result_value(session, 1)), "a");
+
+            assert_eq!(gitql_result_next_row(session), 1);
+            assert!(gitql_result_value(session, 1).is_null());
+            assert_eq!(gitql_result_next_row(session), 0);
+
+            gitql_session_free(session);
+        }
+    }
+
+    #[test]
+    fn test_session_error() {
+        unsafe {
+            let session = gitql_session_new();
+            let query = CString::new("SELECT FROM").unwrap();
+            assert_eq!(gitql_session_execute(session, query.as_ptr()), GITQL_ERROR);
+
+            let error = gitql_session_error(session);
+            assert!(!error.is_null());
+            assert!(read_c_string(error).starts_with("[Error]"));
+
+            let path = CString::new("gitql-ffi-missing-repository").unwrap();
+            assert_eq!(
+                gitql_session_add_repository(session, path.as_ptr()),
+                GITQL_ERROR
+            );
+            gitql_session_free(session);
+        }
+    }
+
+    #[test]
+    fn test_session_panic_returns_error() {
+        unsafe {
+            let session = gitql_session_new();
+            let status = with_session(session, |_| panic!("invalid row index"));
+            assert_eq!(status, GITQL_ERROR);
+
+            let error = read_c_string(gitql_session_error(session));
+            assert_eq!(error, "[Error]: Query engine panicked: invalid row index");
+            assert_eq!(gitql_result_columns_count(session), 0);
+            assert_eq!(gitql_result_next_row(session), 0);
+            gitql_session_free(session);
+        }
+    }
+}
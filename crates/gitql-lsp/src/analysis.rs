@@ -0,0 +1,307 @@
+use gitql_ast::aggregation::aggregation_prototypes;
+use gitql_ast::aggregation::is_aggregation_function;
+use gitql_ast::environment::Environment;
+use gitql_ast::function::PROTOTYPES;
+use gitql_ast::types::DataType;
+use gitql_ast::types::TABLES_FIELDS_TYPES;
+use gitql_cli::completer;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::parser;
+use gitql_parser::tokenizer::line_and_column;
+use gitql_parser::tokenizer::tokenize_with_recovery;
+use gitql_parser::tokenizer::Token;
+use gitql_parser::tokenizer::TokenKind;
+use lsp_types::CompletionItem;
+use lsp_types::CompletionItemKind;
+use lsp_types::Diagnostic;
+use lsp_types::DiagnosticSeverity;
+use lsp_types::Hover;
+use lsp_types::HoverContents;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::Position;
+use lsp_types::Range;
+
+const DIAGNOSTIC_SOURCE: &str = "gitql";
+
+/// Tokenize and parse every `;` separated query in the document and convert the tokenizer
+/// and parser diagnostics to editor diagnostics
+pub fn document_diagnostics(text: &str) -> Vec<Diagnostic> {
+    let options = ParserOptions::default();
+    let (tokens, tokenizer_diagnostics) = tokenize_with_recovery(text, options);
+
+    // The queries can't be parsed correctly with missing tokens, so report only the lexical errors
+    if !tokenizer_diagnostics.is_empty() {
+        return tokenizer_diagnostics
+            .iter()
+            .map(|diagnostic| to_lsp_diagnostic(text, diagnostic))
+            .collect();
+    }
+
+    let mut env = Environment::default();
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    for query in split_queries(tokens) {
+        if let Err(diagnostic) = parser::parse_gql_with_options(query, &mut env, options) {
+            diagnostics.push(to_lsp_diagnostic(text, &diagnostic));
+        }
+        env.clear_session();
+    }
+
+    diagnostics
+}
+
+/// Suggest keywords, tables, fields of the current query table and functions at the position
+pub fn completion_items(text: &str, position: Position) -> Vec<CompletionItem> {
+    let env = Environment::default();
+    let offset = position_to_offset(text, position);
+    let (query_start, query) = query_at_offset(text, offset);
+    let completion = completer::complete(&query, offset - query_start, &env);
+
+    completion
+        .candidates
+        .into_iter()
+        .map(|candidate| {
+            let kind = completion_item_kind(&candidate, &env);
+            CompletionItem {
+                label: candidate,
+                kind: Some(kind),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Show the signature of the function or the type of the field under the position
+pub fn hover(text: &str, position: Position) -> Option<Hover> {
+    let offset = position_to_offset(text, position);
+    let characters: Vec<char> = text.chars().collect();
+
+    let mut start = offset.min(characters.len());
+    while start > 0 && is_word_character(characters[start - 1]) {
+        start -= 1;
+    }
+
+    let mut end = offset.min(characters.len());
+    while end < characters.len() && is_word_character(characters[end]) {
+        end += 1;
+    }
+
+    if start == end {
+        return None;
+    }
+
+    let word = characters[start..end]
+        .iter()
+        .collect::<String>()
+        .to_lowercase();
+
+    let documentation = word_documentation(&word)?;
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: documentation,
+        }),
+        range: Some(Range {
+            start: offset_to_position(text, start),
+            end: offset_to_position(text, end),
+        }),
+    })
+}
+
+fn word_documentation(word: &str) -> Option<String> {
+    if let Some(prototypes) = PROTOTYPES.get(word) {
+        let signatures: Vec<String> = prototypes
+            .iter()
+            .map(|prototype| {
+                signature_documentation(word, &prototype.parameters, &prototype.result)
+            })
+            .collect();
+        return Some(format!(
+            "```gitql\n{}\n```\nStandard function",
+            signatures.join("\n")
+        ));
+    }
+
+    let env = Environment::default();
+    if is_aggregation_function(&env, word) {
+        let signatures: Vec<String> = aggregation_prototypes(&env, word)
+            .iter()
+            .map(|prototype| {
+                signature_documentation(word, &prototype.parameters, &prototype.result)
+            })
+            .collect();
+        return Some(format!(
+            "```gitql\n{}\n```\nAggregation function",
+            signatures.join("\n")
+        ));
+    }
+
+    if env.has_table(word) {
+        let fields: Vec<String> = env
+            .table_fields(word)
+            .iter()
+            .map(|(name, data_type)| format!("- `{}`: {}", name, data_type))
+            .collect();
+        return Some(format!("Table `{}`\n\n{}", word, fields.join("\n")));
+    }
+
+    TABLES_FIELDS_TYPES
+        .get(word)
+        .map(|data_type| format!("```gitql\n{}: {}\n```\nField", word, data_type))
+}
+
+fn signature_documentation(name: &str, parameters: &[DataType], result: &DataType) -> String {
+    let parameters: Vec<String> = parameters
+        .iter()
+        .map(|parameter| parameter.to_string())
+        .collect();
+    format!("{}({}) -> {}", name, parameters.join(", "), result)
+}
+
+fn completion_item_kind(candidate: &str, env: &Environment) -> CompletionItemKind {
+    let name = candidate.to_lowercase();
+    if name.starts_with('@') {
+        CompletionItemKind::VARIABLE
+    } else if PROTOTYPES.contains_key(name.as_str()) || is_aggregation_function(env, &name) {
+        CompletionItemKind::FUNCTION
+    } else if env.has_table(&name) {
+        CompletionItemKind::STRUCT
+    } else if TABLES_FIELDS_TYPES.contains_key(name.as_str()) {
+        CompletionItemKind::FIELD
+    } else {
+        CompletionItemKind::KEYWORD
+    }
+}
+
+fn to_lsp_diagnostic(text: &str, diagnostic: &gitql_parser::diagnostic::Diagnostic) -> Diagnostic {
+    let (start, end) = diagnostic.location().unwrap_or((0, 0));
+
+    let mut message = diagnostic.message().to_string();
+    for note in diagnostic.notes() {
+        message.push_str(&format!("\nNote: {}", note));
+    }
+    for help in diagnostic.helps() {
+        message.push_str(&format!("\nHelp: {}", help));
+    }
+
+    Diagnostic {
+        range: Range {
+            start: offset_to_position(text, start),
+            end: offset_to_position(text, end.max(start)),
+        },
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+fn split_queries(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut queries: Vec<Vec<Token>> = vec![vec![]];
+    for token in tokens {
+        if token.kind == TokenKind::Semicolon {
+            queries.push(vec![]);
+        } else if let Some(query) = queries.last_mut() {
+            query.push(token);
+        }
+    }
+    queries.retain(|query| !query.is_empty());
+    queries
+}
+
+/// Returns the start offset and the text of the `;` separated query that contains the offset
+fn query_at_offset(text: &str, offset: usize) -> (usize, String) {
+    let characters: Vec<char> = text.chars().collect();
+    let offset = offset.min(characters.len());
+
+    let start = characters[..offset]
+        .iter()
+        .rposition(|c| *c == ';')
+        .map_or(0, |index| index + 1);
+
+    let end = characters[offset..]
+        .iter()
+        .position(|c| *c == ';')
+        .map_or(characters.len(), |index| offset + index);
+
+    (start, characters[start..end].iter().collect())
+}
+
+/// Convert the editor position to character offset, the position character is treated as
+/// characters count in the line
+fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (line_index, line) in text.split('\n').enumerate() {
+        let line_length = line.chars().count();
+        if line_index == position.line as usize {
+            return offset + line_length.min(position.character as usize);
+        }
+        offset += line_length + 1;
+    }
+    text.chars().count()
+}
+
+fn offset_to_position(text: &str, offset: usize) -> Position {
+    let (line, column) = line_and_column(text, offset);
+    Position {
+        line: (line - 1) as u32,
+        character: (column - 1) as u32,
+    }
+}
+
+#[inline(always)]
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '@'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_diagnostics() {
+        let diagnostics = document_diagnostics("SELECT name FROM commits;\nSELECT * FROM unknown");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 1);
+
+        let diagnostics = document_diagnostics("SELECT title FROM commits");
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_completion_items() {
+        let text = "SELECT 1 FROM tags;\nSELECT tit FROM commits";
+        let items = completion_items(
+            text,
+            Position {
+                line: 1,
+                character: 10,
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "title");
+        assert_eq!(items[0].kind, Some(CompletionItemKind::FIELD));
+    }
+
+    #[test]
+    fn test_hover() {
+        let text = "SELECT lower(title) FROM commits";
+        let function_hover = hover(
+            text,
+            Position {
+                line: 0,
+                character: 8,
+            },
+        );
+        assert!(function_hover.is_some());
+
+        let keyword_hover = hover(
+            text,
+            Position {
+                line: 0,
+                character: 0,
+            },
+        );
+        assert!(keyword_hover.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use lsp_server::Connection;
+use lsp_server::ErrorCode;
+use lsp_server::Message;
+use lsp_server::Notification;
+use lsp_server::Request;
+use lsp_server::RequestId;
+use lsp_server::Response;
+use lsp_types::notification::DidChangeTextDocument;
+use lsp_types::notification::DidCloseTextDocument;
+use lsp_types::notification::DidOpenTextDocument;
+use lsp_types::notification::Notification as _;
+use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::Completion;
+use lsp_types::request::HoverRequest;
+use lsp_types::request::Request as _;
+use lsp_types::CompletionOptions;
+use lsp_types::CompletionParams;
+use lsp_types::CompletionResponse;
+use lsp_types::DidChangeTextDocumentParams;
+use lsp_types::DidCloseTextDocumentParams;
+use lsp_types::DidOpenTextDocumentParams;
+use lsp_types::HoverParams;
+use lsp_types::HoverProviderCapability;
+use lsp_types::PublishDiagnosticsParams;
+use lsp_types::ServerCapabilities;
+use lsp_types::TextDocumentSyncCapability;
+use lsp_types::TextDocumentSyncKind;
+use lsp_types::Url;
+
+mod analysis;
+
+type LspResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+fn main() -> LspResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions::default()),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    })?;
+
+    connection.initialize(capabilities)?;
+    main_loop(&connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+fn main_loop(connection: &Connection) -> LspResult<()> {
+    // Content of the opened documents, the full content is sent on each change
+    let mut documents: HashMap<Url, String> = HashMap::new();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+
+                let response = handle_request(&documents, request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                handle_notification(connection, &mut documents, notification)?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_request(documents: &HashMap<Url, String>, request: Request) -> Response {
+    match request.method.as_str() {
+        Completion::METHOD => match serde_json::from_value::<CompletionParams>(request.params) {
+            Ok(params) => {
+                let position = params.text_document_position;
+                let items = documents
+                    .get(&position.text_document.uri)
+                    .map(|text| analysis::completion_items(text, position.position))
+                    .unwrap_or_default();
+                Response::new_ok(request.id, CompletionResponse::Array(items))
+            }
+            Err(error) => invalid_params_response(request.id, error),
+        },
+        HoverRequest::METHOD => match serde_json::from_value::<HoverParams>(request.params) {
+            Ok(params) => {
+                let position = params.text_document_position_params;
+                let hover = documents
+                    .get(&position.text_document.uri)
+                    .and_then(|text| analysis::hover(text, position.position));
+                Response::new_ok(request.id, hover)
+            }
+            Err(error) => invalid_params_response(request.id, error),
+        },
+        _ => Response::new_err(
+            request.id,
+            ErrorCode::MethodNotFound as i32,
+            format!("Unsupported request `{}`", request.method),
+        ),
+    }
+}
+
+fn handle_notification(
+    connection: &Connection,
+    documents: &mut HashMap<Url, String>,
+    notification: Notification,
+) -> LspResult<()> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+            let document = params.text_document;
+            publish_diagnostics(connection, &document.uri, &document.text)?;
+            documents.insert(document.uri, document.text);
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+            if let Some(change) = params.content_changes.into_iter().last() {
+                let uri = params.text_document.uri;
+                publish_diagnostics(connection, &uri, &change.text)?;
+                documents.insert(uri, change.text);
+            }
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+            let uri = params.text_document.uri;
+            documents.remove(&uri);
+
+            // Clear the diagnostics of the closed document
+            let params = PublishDiagnosticsParams::new(uri, vec![], None);
+            let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+            connection
+                .sender
+                .send(Message::Notification(notification))?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn publish_diagnostics(connection: &Connection, uri: &Url, text: &str) -> LspResult<()> {
+    let diagnostics = analysis::document_diagnostics(text);
+    let params = PublishDiagnosticsParams::new(uri.clone(), diagnostics, None);
+    let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+    connection
+        .sender
+        .send(Message::Notification(notification))?;
+    Ok(())
+}
+
+fn invalid_params_response(id: RequestId, error: serde_json::Error) -> Response {
+    Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string())
+}
@@ -0,0 +1,57 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+
+use gitql_ast::environment::Environment;
+use gitql_parser::parser::parse_gql;
+use gitql_parser::tokenizer::tokenize;
+
+/// Query that select many expressions and filter by long chain of conditions
+fn large_query(conditions_count: usize) -> String {
+    let conditions: Vec<String> = (0..conditions_count)
+        .map(|i| format!("(LEN(title) > {} AND name LIKE \"%user_{}%\")", i, i))
+        .collect();
+
+    format!(
+        "SELECT commit_id, title, LOWER(name), UPPER(email), datetime FROM commits WHERE {} ORDER BY datetime DESC LIMIT 10",
+        conditions.join(" OR ")
+    )
+}
+
+/// Query with one expression that pass through all levels of the operators precedence
+fn deep_expression_query(terms_count: usize) -> String {
+    let terms: Vec<String> = (0..terms_count)
+        .map(|i| format!("{} * 2 + {} | 1 & {} << 1", i, i, i))
+        .collect();
+
+    format!("SELECT {} > 1", terms.join(" - "))
+}
+
+fn tokenizer_benchmark(c: &mut Criterion) {
+    let query = large_query(200);
+    c.bench_function("tokenize large query", |b| {
+        b.iter(|| tokenize(black_box(&query)))
+    });
+}
+
+fn parser_benchmark(c: &mut Criterion) {
+    let queries = [
+        ("parse large query", large_query(200)),
+        ("parse deep expression", deep_expression_query(200)),
+    ];
+
+    for (name, query) in queries {
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                || (tokenize(&query).ok().unwrap(), Environment::default()),
+                |(tokens, mut env)| parse_gql(black_box(tokens), &mut env),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+criterion_group!(benches, tokenizer_benchmark, parser_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+#![no_main]
+
+use gitql_ast::environment::Environment;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::parser::parse_gql;
+use gitql_parser::tokenizer::tokenize_with_recovery;
+use libfuzzer_sys::fuzz_target;
+
+// Tokenizer and parser must report diagnostics for any malformed input instead of panicking,
+// the valid tokens of broken input are parsed too like the editors do
+fuzz_target!(|data: &[u8]| {
+    let script = String::from_utf8_lossy(data);
+    let (tokens, _) = tokenize_with_recovery(&script, ParserOptions::default());
+    let mut env = Environment::default();
+    let _ = parse_gql(tokens, &mut env);
+});
@@ -1,17 +1,83 @@
 use std::collections::HashMap;
 
 use gitql_ast::statement::AggregateValue;
+use gitql_ast::statement::GQLQuery;
+
+use crate::tokenizer::Location;
+
+/// Options to change the parser behaviour
+#[derive(Clone, Copy)]
+pub struct ParserOptions {
+    /// Allow selecting fields that are not used in `GROUP BY`, the value of any row in the group is used
+    pub any_value_mode: bool,
+    /// Follow ANSI SQL rules, for example single quoted strings and `||` as string concatenation
+    pub ansi_mode: bool,
+    /// Maximum number of characters in the query
+    pub max_query_length: usize,
+    /// Maximum number of characters in identifier or global variable name
+    pub max_identifier_length: usize,
+    /// Maximum nesting depth of expressions, protects the recursive descent parser from stack overflow
+    pub max_expression_depth: usize,
+    /// Maximum number of selected fields and expressions in one `SELECT` statement
+    pub max_select_items: usize,
+    /// Allow loading local files as tables using `EXTERNAL(path)`, disabled for queries received by servers
+    pub allow_external_files: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            any_value_mode: false,
+            ansi_mode: false,
+            max_query_length: 65536,
+            max_identifier_length: 256,
+            max_expression_depth: 128,
+            max_select_items: 1024,
+            allow_external_files: true,
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct ParserContext {
+    pub options: ParserOptions,
+    pub table_name: String,
+
     pub aggregations: HashMap<String, AggregateValue>,
 
     pub selected_fields: Vec<String>,
     pub hidden_selections: Vec<String>,
+    pub non_aggregated_selections: Vec<String>,
+    pub non_aggregated_fields: Vec<String>,
+    pub selected_titles: Vec<String>,
+    pub group_by_fields: Vec<String>,
 
     pub generated_field_count: i32,
     pub is_single_value_query: bool,
     pub has_group_by_statement: bool,
+    pub has_non_deterministic_function: bool,
+
+    /// Current nesting depth of the expression being parsed
+    pub expression_depth: usize,
+
+    /// True while parsing the condition of `WHERE` statement
+    pub is_where_condition: bool,
+    /// True while parsing one of the selected expressions of `SELECT` statement
+    pub is_select_item: bool,
+    /// Titles of the columns selected by `ROW_NUMBER()`
+    pub row_number_titles: Vec<String>,
+
+    /// Table names or aliases used to qualify fields like `c.title`, checked after `FROM` is parsed
+    pub qualified_references: Vec<(String, Location)>,
+    /// Fields names used in the expressions, queries without `FROM` can use only the selected aliases
+    pub column_references: Vec<(String, Location)>,
+    /// Query selected in `FROM (SELECT ...) AS name`
+    pub subquery: Option<Box<GQLQuery>>,
+    /// Queries used as values in `IN (SELECT ...)`, referenced by index from the expressions
+    pub in_subqueries: Vec<GQLQuery>,
+    /// Fingerprints of the selected expressions mapped to their columns titles, so the same
+    /// expression used after `SELECT` reads the selected column instead of evaluating it again
+    pub selected_expressions: HashMap<String, String>,
 }
 
 impl ParserContext {
@@ -28,12 +94,28 @@ mod tests {
     #[test]
     fn test_generate_column_name() {
         let mut ctx = ParserContext {
+            options: ParserOptions::default(),
+            table_name: String::new(),
             aggregations: Default::default(),
             selected_fields: vec![],
             hidden_selections: vec![],
+            non_aggregated_selections: vec![],
+            non_aggregated_fields: vec![],
+            selected_titles: vec![],
+            group_by_fields: vec![],
             generated_field_count: 0,
             is_single_value_query: false,
             has_group_by_statement: false,
+            has_non_deterministic_function: false,
+            expression_depth: 0,
+            is_where_condition: false,
+            is_select_item: false,
+            row_number_titles: vec![],
+            qualified_references: vec![],
+            column_references: vec![],
+            subquery: None,
+            in_subqueries: vec![],
+            selected_expressions: Default::default(),
         };
 
         let ret = ctx.generate_column_name();
@@ -1,5 +1,8 @@
 pub mod context;
 pub mod diagnostic;
 pub mod parser;
+pub mod query_builder;
+pub mod suggestion;
+pub mod token_stream;
 pub mod tokenizer;
 pub mod type_checker;
@@ -1,5 +1,10 @@
 use gitql_ast::environment::Environment;
+use gitql_ast::environment::BLAME_PATH_FIELD_PREFIX;
+use gitql_ast::environment::EXTERNAL_TABLE_PREFIX;
+use gitql_ast::environment::RANGE_FIELD_PREFIX;
 use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::environment::TABLE_FUNCTIONS;
+use gitql_ast::environment::TOUCHES_FIELD_PREFIX;
 use gitql_ast::value::Value;
 use std::collections::HashMap;
 use std::num::IntErrorKind;
@@ -7,30 +12,64 @@ use std::num::ParseIntError;
 use std::vec;
 
 use crate::context::ParserContext;
+use crate::context::ParserOptions;
 use crate::diagnostic::Diagnostic;
+use crate::query_builder::QueryBuilder;
+use crate::suggestion::add_did_you_mean_help;
+use crate::token_stream::TokenStream;
 use crate::tokenizer::Location;
 use crate::tokenizer::Token;
 use crate::tokenizer::TokenKind;
 use crate::type_checker::are_types_equals;
-use crate::type_checker::check_all_values_are_same_type;
+use crate::type_checker::check_all_values_are_same_type_or_null;
 use crate::type_checker::is_expression_type_equals;
 use crate::type_checker::TypeCheckResult;
 
+use gitql_ast::aggregation::aggregation_prototypes;
+use gitql_ast::aggregation::is_aggregation_function;
+use gitql_ast::aggregation::is_order_sensitive_aggregation;
 use gitql_ast::aggregation::AGGREGATIONS;
-use gitql_ast::aggregation::AGGREGATIONS_PROTOS;
 use gitql_ast::expression::*;
-use gitql_ast::function::FUNCTIONS;
+use gitql_ast::external::load_external_table;
+use gitql_ast::function::arguments_parameters_types;
+use gitql_ast::function::generic_result_argument_index;
+use gitql_ast::function::is_parameters_accepting;
+use gitql_ast::function::is_standard_function;
+use gitql_ast::function::standard_functions_names;
+use gitql_ast::function::NON_DETERMINISTIC_FUNCTIONS;
 use gitql_ast::function::PROTOTYPES;
+use gitql_ast::session::is_session_variable;
+use gitql_ast::session::session_variable_type;
+use gitql_ast::session::SESSION_VARIABLES;
 use gitql_ast::statement::*;
 use gitql_ast::types::DataType;
 use gitql_ast::types::TABLES_FIELDS_TYPES;
 
 pub fn parse_gql(tokens: Vec<Token>, env: &mut Environment) -> Result<Query, Box<Diagnostic>> {
+    parse_gql_with_options(tokens, env, ParserOptions::default())
+}
+
+pub fn parse_gql_with_options(
+    tokens: Vec<Token>,
+    env: &mut Environment,
+    options: ParserOptions,
+) -> Result<Query, Box<Diagnostic>> {
     let mut position = 0;
-    let first_token = &tokens[position];
+    let first_token = match tokens.first() {
+        Some(first_token) => first_token,
+        None => {
+            return Err(Diagnostic::error("Expect query but got empty input")
+                .add_help("Expect query to start with `SELECT`, `SET`, `SHOW`, `EXPLAIN`, `CREATE` or `INSERT` keyword")
+                .as_boxed());
+        }
+    };
     let query_result = match &first_token.kind {
         TokenKind::Set => parse_set_query(env, &tokens, &mut position),
-        TokenKind::Select => parse_select_query(env, &tokens, &mut position),
+        TokenKind::Show => parse_show_query(&tokens, &mut position),
+        TokenKind::Explain => parse_explain_query(env, &tokens, &mut position, options),
+        TokenKind::Create => parse_create_query(env, &tokens, &mut position, options),
+        TokenKind::Insert => parse_insert_query(env, &tokens, &mut position, options),
+        TokenKind::Select => parse_select_query(env, &tokens, &mut position, options),
         _ => Err(un_expected_statement_error(&tokens, &mut position)),
     };
 
@@ -42,7 +81,7 @@ pub fn parse_gql(tokens: Vec<Token>, env: &mut Environment) -> Result<Query, Box
     }
 
     // Check for un expected content after valid statement
-    if query_result.is_ok() && position < tokens.len() {
+    if query_result.is_ok() && !tokens.is_at_end(position) {
         return Err(un_expected_content_after_correct_statement(
             &first_token.literal,
             &tokens,
@@ -58,26 +97,56 @@ fn parse_set_query(
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Query, Box<Diagnostic>> {
-    let len = tokens.len();
     let mut context = ParserContext::default();
 
     // Consume Set keyword
     *position += 1;
 
-    if *position >= len || tokens[*position].kind != TokenKind::GlobalVariable {
-        return Err(Diagnostic::error(
-            "Expect Global variable name start with `@` after `SET` keyword",
-        )
-        .with_location(get_safe_location(tokens, *position - 1))
-        .as_boxed());
-    }
+    let name = match consume_kind(tokens, *position, TokenKind::GlobalVariable) {
+        Ok(name_token) => name_token.identifier(),
+        Err(_) => {
+            return Err(Diagnostic::error(
+                "Expect Global variable name start with `@` after `SET` keyword",
+            )
+            .with_location(get_safe_location(tokens, *position - 1))
+            .as_boxed());
+        }
+    };
 
-    let name = &tokens[*position].literal;
+    // Names that start with `@@` are reserved for the session variables
+    if name.starts_with("@@") && !is_session_variable(&name) {
+        let diagnostic = Diagnostic::error(&format!("Unknown session variable `{}`", name))
+            .add_note(
+                "Global variables names that start with `@@` are reserved for session variables",
+            )
+            .with_location(get_safe_location(tokens, *position));
+        let names = SESSION_VARIABLES.iter().map(|(name, _)| *name);
+        return Err(add_did_you_mean_help(diagnostic, &name, names).as_boxed());
+    }
 
     // Consume variable name
     *position += 1;
 
-    if *position >= len || !is_assignment_operator(&tokens[*position]) {
+    // Parse optional type of the variable, for example `SET @limit INT = 10`
+    let mut declared_type: Option<DataType> = None;
+    let type_location = get_safe_location(tokens, *position);
+    if let Ok(type_token) = consume_kind(tokens, *position, TokenKind::Symbol) {
+        let type_name = type_token.identifier();
+        match resolve_type_name(&type_name) {
+            Some(data_type) => declared_type = Some(data_type),
+            None => {
+                return Err(Diagnostic::error(&format!("Unknown type name `{}`", type_token.literal))
+                    .add_help("Expect one of `INT`, `FLOAT`, `TEXT`, `BOOL`, `DATE`, `TIME` or `DATETIME` types")
+                    .with_location(type_token.location)
+                    .as_boxed());
+            }
+        }
+
+        // Consume type name
+        *position += 1;
+    }
+
+    if !tokens.is_token(*position, is_assignment_operator) {
         return Err(
             Diagnostic::error("Expect `=` or `:=` and Value after Variable name")
                 .with_location(get_safe_location(tokens, *position - 1))
@@ -88,8 +157,9 @@ fn parse_set_query(
     // Consume `=` or `:=` token
     *position += 1;
 
+    let value_location = get_safe_location(tokens, *position);
     let aggregations_count_before = context.aggregations.len();
-    let value = parse_expression(&mut context, env, tokens, position)?;
+    let mut value = parse_expression(&mut context, env, tokens, position)?;
     let has_aggregations = context.aggregations.len() != aggregations_count_before;
 
     // Until supports sub queries, aggregation value can't be stored in variables
@@ -101,7 +171,37 @@ fn parse_set_query(
         );
     }
 
-    env.define_global(name.to_string(), value.expr_type(env));
+    // Variable type can't be changed after the declaration, the value must be of the same type
+    let defined_type = env
+        .globals_types
+        .get(name.as_ref())
+        .cloned()
+        .or_else(|| session_variable_type(&name));
+    if let (Some(declared_type), Some(defined_type)) = (&declared_type, &defined_type) {
+        if declared_type != defined_type {
+            return Err(Diagnostic::error(&format!(
+                "Global variable `{}` is already defined with type {}",
+                name, defined_type
+            ))
+            .add_help(&format!(
+                "Try to declare it with type {} or use a different name",
+                defined_type
+            ))
+            .add_note("Global variable type can't be changed after its declaration")
+            .with_location(type_location)
+            .as_boxed());
+        }
+    }
+
+    let variable_type = match declared_type.or(defined_type) {
+        Some(variable_type) => {
+            value = check_global_variable_value(env, &name, value, &variable_type, value_location)?;
+            variable_type
+        }
+        None => value.expr_type(env),
+    };
+
+    env.define_global(name.to_string(), variable_type);
 
     Ok(Query::GlobalVariableDeclaration(GlobalVariableStatement {
         name: name.to_string(),
@@ -109,19 +209,394 @@ fn parse_set_query(
     }))
 }
 
+/// Check that the value can be assigned to global variable with this type, Text literals are
+/// implicitly casted to Date, Time and DateTime and `NULL` can be assigned to any variable
+fn check_global_variable_value(
+    env: &Environment,
+    name: &str,
+    value: Box<dyn Expression>,
+    variable_type: &DataType,
+    location: Location,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let value_type = value.expr_type(env);
+    if value_type.is_null() {
+        return Ok(value);
+    }
+
+    match is_expression_type_equals(env, &value, variable_type) {
+        TypeCheckResult::Equals => Ok(value),
+        TypeCheckResult::RightSideCasted(casted_value) => Ok(casted_value),
+        TypeCheckResult::Error(diagnostic) => Err(diagnostic.with_location(location).as_boxed()),
+        _ => Err(Diagnostic::error(&format!(
+            "Can't assign value of type {} to global variable `{}` of type {}",
+            value_type, name, variable_type
+        ))
+        .add_help(&format!(
+            "Try to assign value of type {} or use a different variable name",
+            variable_type
+        ))
+        .with_location(location)
+        .as_boxed()),
+    }
+}
+
+/// Resolve the data type from the lowercase type name in the declaration
+pub fn resolve_type_name(name: &str) -> Option<DataType> {
+    match name {
+        "int" | "integer" => Some(DataType::Integer),
+        "float" | "real" => Some(DataType::Float),
+        "text" => Some(DataType::Text),
+        "bool" | "boolean" => Some(DataType::Boolean),
+        "date" => Some(DataType::Date),
+        "time" => Some(DataType::Time),
+        "datetime" => Some(DataType::DateTime),
+        _ => None,
+    }
+}
+
+/// Parse `SHOW VARIABLES` query to list the defined global variables
+fn parse_show_query(tokens: &[Token], position: &mut usize) -> Result<Query, Box<Diagnostic>> {
+    // Consume `SHOW` keyword
+    *position += 1;
+
+    let is_variables_keyword = tokens.peek(*position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("variables")
+    });
+
+    if !is_variables_keyword {
+        return Err(Diagnostic::error("Expect `VARIABLES` after `SHOW` keyword")
+            .add_help("Try `SHOW VARIABLES` to list the global variables")
+            .with_location(tokens.location_at(*position - 1))
+            .as_boxed());
+    }
+
+    // Consume `VARIABLES` keyword
+    *position += 1;
+    Ok(Query::ShowVariables)
+}
+
+/// Parse `EXPLAIN SELECT ...` query to estimate the rows of the select query without evaluating it
+fn parse_explain_query(
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    options: ParserOptions,
+) -> Result<Query, Box<Diagnostic>> {
+    // Consume `EXPLAIN` keyword
+    *position += 1;
+
+    if !tokens.is_kind(*position, TokenKind::Select) {
+        return Err(
+            Diagnostic::error("Expect `SELECT` query after `EXPLAIN` keyword")
+                .add_help("Try `EXPLAIN SELECT * FROM commits`")
+                .with_location(get_safe_location(tokens, *position - 1))
+                .as_boxed(),
+        );
+    }
+
+    match parse_select_query(env, tokens, position, options)? {
+        Query::Select(query) => Ok(Query::Explain(query)),
+        Query::GlobalVariableDeclaration(_)
+        | Query::ShowVariables
+        | Query::Explain(_)
+        | Query::CreateTempTable(_)
+        | Query::Insert(_) => unreachable!(),
+    }
+}
+
+/// Parse `CREATE TEMP TABLE name AS SELECT ...`, the query result is materialized by the engine
+/// into temporary table that can be selected by the next queries in the same session
+fn parse_create_query(
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    options: ParserOptions,
+) -> Result<Query, Box<Diagnostic>> {
+    // Consume `CREATE` keyword
+    *position += 1;
+
+    let is_temp_keyword = tokens.peek(*position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol
+            && (token.literal.eq_ignore_ascii_case("temp")
+                || token.literal.eq_ignore_ascii_case("temporary"))
+    });
+
+    if !is_temp_keyword {
+        return Err(Diagnostic::error("Expect `TEMP` after `CREATE` keyword")
+            .add_note("Only temporary tables can be created")
+            .add_help("Try `CREATE TEMP TABLE name AS SELECT ...`")
+            .with_location(tokens.location_at(*position - 1))
+            .as_boxed());
+    }
+
+    // Consume `TEMP` keyword
+    *position += 1;
+
+    let is_table_keyword = tokens.peek(*position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("table")
+    });
+
+    if !is_table_keyword {
+        return Err(Diagnostic::error("Expect `TABLE` after `TEMP` keyword")
+            .add_help("Try `CREATE TEMP TABLE name AS SELECT ...`")
+            .with_location(tokens.location_at(*position - 1))
+            .as_boxed());
+    }
+
+    // Consume `TABLE` keyword
+    *position += 1;
+
+    let table_name = match consume_kind(tokens, *position, TokenKind::Symbol) {
+        Ok(table_name_token) => table_name_token.identifier().to_string(),
+        Err(_) => {
+            return Err(Diagnostic::error("Expect `identifier` as a table name")
+                .add_note("Table name must be an identifier")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed());
+        }
+    };
+
+    if env.has_table(&table_name) {
+        return Err(
+            Diagnostic::error(&format!("Table `{}` already exists", table_name))
+                .add_help("Try to use a new unique name for the temporary table")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed(),
+        );
+    }
+
+    // Consume table name
+    *position += 1;
+
+    if consume_kind(tokens, *position, TokenKind::As).is_err()
+        || !tokens.is_kind(*position + 1, TokenKind::Select)
+    {
+        return Err(
+            Diagnostic::error("Expect `AS` and `SELECT` query after temporary table name")
+                .add_help("Try `CREATE TEMP TABLE name AS SELECT ...`")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed(),
+        );
+    }
+
+    // Consume `AS` keyword
+    *position += 1;
+
+    let query = match parse_select_query(env, tokens, position, options)? {
+        Query::Select(query) => query,
+        Query::GlobalVariableDeclaration(_)
+        | Query::ShowVariables
+        | Query::Explain(_)
+        | Query::CreateTempTable(_)
+        | Query::Insert(_) => {
+            unreachable!()
+        }
+    };
+
+    let select_statement = query
+        .statements
+        .get("select")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<SelectStatement>()
+        .unwrap();
+    let columns = select_statement_columns(env, select_statement);
+
+    Ok(Query::CreateTempTable(CreateTempTableStatement {
+        table_name,
+        columns,
+        query,
+    }))
+}
+
+/// Parse `INSERT INTO name SELECT ...` to append the query rows to temporary table,
+/// or `INSERT INTO FILE "path" SELECT ...` to append them to CSV, TSV or JSON Lines file
+fn parse_insert_query(
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    options: ParserOptions,
+) -> Result<Query, Box<Diagnostic>> {
+    // Consume `INSERT` keyword
+    *position += 1;
+
+    let is_into_keyword = tokens.peek(*position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("into")
+    });
+
+    if !is_into_keyword {
+        return Err(Diagnostic::error("Expect `INTO` after `INSERT` keyword")
+            .add_help(
+                "Try `INSERT INTO name SELECT ...` or `INSERT INTO FILE \"out.csv\" SELECT ...`",
+            )
+            .with_location(tokens.location_at(*position - 1))
+            .as_boxed());
+    }
+
+    // Consume `INTO` keyword
+    *position += 1;
+
+    let is_file_target = tokens.peek(*position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("file")
+    }) && tokens.is_kind(*position + 1, TokenKind::String);
+
+    let target_location = get_safe_location(tokens, *position);
+    let target = if is_file_target {
+        // Consume `FILE` keyword
+        *position += 1;
+
+        let path = tokens
+            .peek(*position)
+            .map_or(String::new(), |token| token.literal.to_string());
+        let Some(format) = FileFormat::from_path(&path) else {
+            return Err(
+                Diagnostic::error(&format!("Unsupported file format of `{}`", path))
+                    .add_help("File path must end with `.csv`, `.tsv`, `.jsonl` or `.ndjson`")
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed(),
+            );
+        };
+
+        // Consume file path
+        *position += 1;
+        InsertTarget::File(path, format)
+    } else {
+        let table_name = match consume_kind(tokens, *position, TokenKind::Symbol) {
+            Ok(table_name_token) => table_name_token.identifier().to_string(),
+            Err(_) => {
+                return Err(Diagnostic::error(
+                    "Expect temporary table name or `FILE` after `INTO` keyword",
+                )
+                .with_location(target_location)
+                .as_boxed());
+            }
+        };
+
+        if !env.is_temp_table(&table_name) {
+            let diagnostic = Diagnostic::error(&format!(
+                "Can't insert into `{}` because it's not a temporary table",
+                table_name
+            ))
+            .add_help("Create the table first using `CREATE TEMP TABLE name AS SELECT ...`")
+            .with_location(target_location);
+            let names = env
+                .temp_tables
+                .keys()
+                .filter(|name| env.is_temp_table(name))
+                .map(|name| name.as_str());
+            return Err(add_did_you_mean_help(diagnostic, &table_name, names).as_boxed());
+        }
+
+        // Consume table name
+        *position += 1;
+        InsertTarget::TempTable(table_name)
+    };
+
+    if !tokens.is_kind(*position, TokenKind::Select) {
+        return Err(
+            Diagnostic::error("Expect `SELECT` query after `INSERT INTO` target")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed(),
+        );
+    }
+
+    let query = match parse_select_query(env, tokens, position, options)? {
+        Query::Select(query) => query,
+        Query::GlobalVariableDeclaration(_)
+        | Query::ShowVariables
+        | Query::Explain(_)
+        | Query::CreateTempTable(_)
+        | Query::Insert(_) => unreachable!(),
+    };
+
+    // Rows appended to temporary table must have the same columns types of the table
+    if let InsertTarget::TempTable(table_name) = &target {
+        let select_statement = query
+            .statements
+            .get("select")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+        let columns = select_statement_columns(env, select_statement);
+        let table_fields = env.table_fields(table_name);
+        if columns.len() != table_fields.len() {
+            return Err(Diagnostic::error(&format!(
+                "Temporary table `{}` has {} columns but the query selects {} columns",
+                table_name,
+                table_fields.len(),
+                columns.len()
+            ))
+            .with_location(target_location)
+            .as_boxed());
+        }
+
+        for ((_, column_type), (field_name, field_type)) in columns.iter().zip(&table_fields) {
+            if column_type.is_null() || column_type == field_type {
+                continue;
+            }
+
+            return Err(Diagnostic::error(&format!(
+                "Can't insert value of type `{}` into column `{}` of type `{}`",
+                column_type, field_name, field_type
+            ))
+            .with_location(target_location)
+            .as_boxed());
+        }
+    }
+
+    Ok(Query::Insert(InsertStatement { target, query }))
+}
+
 fn parse_select_query(
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
+    options: ParserOptions,
 ) -> Result<Query, Box<Diagnostic>> {
-    let len = tokens.len();
+    let context = ParserContext {
+        options,
+        ..Default::default()
+    };
+    parse_select_query_with_context(context, env, tokens, position)
+}
 
-    let mut context = ParserContext::default();
-    let mut statements: HashMap<&'static str, Box<dyn Statement>> = HashMap::new();
+/// Parse the query of subquery or derived table, each nested query is one more level of the
+/// expression depth, so deep nesting is reported before overflowing the stack
+fn parse_nested_select_query(
+    context: &ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Query, Box<Diagnostic>> {
+    if context.expression_depth >= context.options.max_expression_depth {
+        return Err(Diagnostic::error("Query is nested too deeply")
+            .add_note(&format!(
+                "Maximum expression nesting depth is {}",
+                context.options.max_expression_depth
+            ))
+            .add_help("Try to simplify the query or increase the maximum depth option")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
+    }
+
+    let nested_context = ParserContext {
+        options: context.options,
+        expression_depth: context.expression_depth + 1,
+        ..Default::default()
+    };
+    parse_select_query_with_context(nested_context, env, tokens, position)
+}
 
-    while *position < len {
-        let token = &tokens[*position];
+fn parse_select_query_with_context(
+    mut context: ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Query, Box<Diagnostic>> {
+    let mut statements: HashMap<&'static str, Box<dyn Statement>> = HashMap::new();
 
+    while let Some(token) = tokens.peek(*position) {
         match &token.kind {
             TokenKind::Select => {
                 if statements.contains_key("select") {
@@ -186,11 +661,11 @@ fn parse_select_query(
                         .as_boxed());
                 }
 
-                let statement = parse_limit_statement(tokens, position)?;
+                let statement = parse_limit_statement(&mut context, env, tokens, position)?;
                 statements.insert("limit", statement);
 
                 // Check for Limit and Offset shortcut
-                if *position < len && tokens[*position].kind == TokenKind::Comma {
+                if tokens.is_kind(*position, TokenKind::Comma) {
                     // Prevent user from using offset statement more than one time
                     if statements.contains_key("offset") {
                         return Err(Diagnostic::error("You already used `OFFSET` statement")
@@ -204,18 +679,22 @@ fn parse_select_query(
                     // Consume Comma
                     *position += 1;
 
-                    if *position >= len || tokens[*position].kind != TokenKind::Integer {
-                        return Err(Diagnostic::error(
-                            "Expects `OFFSET` amount as Integer value after `,`",
-                        )
-                        .add_help("Try to add constant Integer after comma")
-                        .add_note("`OFFSET` value must be a constant Integer")
-                        .with_location(token.location)
-                        .as_boxed());
-                    }
+                    check_negative_offset_count(tokens, *position)?;
+
+                    let count_token = match consume_kind(tokens, *position, TokenKind::Integer) {
+                        Ok(count_token) => count_token,
+                        Err(_) => {
+                            return Err(Diagnostic::error(
+                                "Expects `OFFSET` amount as Integer value after `,`",
+                            )
+                            .add_help("Try to add constant Integer after comma")
+                            .add_note("`OFFSET` value must be a constant Integer")
+                            .with_location(token.location)
+                            .as_boxed());
+                        }
+                    };
 
-                    let count_result: Result<usize, ParseIntError> =
-                        tokens[*position].literal.parse();
+                    let count_result: Result<usize, ParseIntError> = count_token.literal.parse();
 
                     // Report clear error for Integer parsing
                     if let Err(error) = &count_result {
@@ -246,6 +725,17 @@ fn parse_select_query(
                     statements.insert("offset", Box::new(OffsetStatement { count }));
                 }
             }
+            TokenKind::Sample => {
+                if statements.contains_key("sample") {
+                    return Err(Diagnostic::error("You already used `SAMPLE` statement")
+                        .add_note("Can't use more than one `SAMPLE` statement in the same query")
+                        .with_location(token.location)
+                        .as_boxed());
+                }
+
+                let statement = parse_sample_statement(&mut context, env, tokens, position)?;
+                statements.insert("sample", statement);
+            }
             TokenKind::Offset => {
                 if statements.contains_key("offset") {
                     return Err(Diagnostic::error("You already used `OFFSET` statement")
@@ -280,6 +770,61 @@ fn parse_select_query(
         statements.insert("aggregation", Box::new(aggregation_functions));
     }
 
+    // If `ROW_NUMBER()` is selected, number the rows after sorting them
+    if !context.row_number_titles.is_empty() {
+        let row_number_statement = RowNumberStatement {
+            titles: context.row_number_titles.clone(),
+        };
+        statements.insert("row_number", Box::new(row_number_statement));
+    }
+
+    // Query without `FROM` has one row without columns, so only the selected aliases can be used
+    if context.table_name.is_empty() {
+        let select_statement = statements
+            .get("select")
+            .and_then(|statement| statement.as_any().downcast_ref::<SelectStatement>());
+        let is_alias = |name: &str| {
+            select_statement.is_some_and(|select| {
+                select
+                    .fields_names
+                    .iter()
+                    .zip(select.fields_values.iter())
+                    .any(|(field_name, value)| {
+                        let title = select.alias_table.get(field_name).unwrap_or(field_name);
+                        title == name && !is_symbol(value.as_ref(), name)
+                    })
+            })
+        };
+
+        let field = context
+            .column_references
+            .iter()
+            .find(|(name, _)| !is_alias(name));
+        if let Some((name, location)) = field {
+            return Err(Diagnostic::error(&format!(
+                "Can't use field `{}` in query without `FROM` table",
+                name
+            ))
+            .add_help("Select the field from a table using `FROM`, or use a literal value")
+            .with_location(*location)
+            .as_boxed());
+        }
+    }
+
+    // Qualified fields must use the current table name or its alias
+    for (qualifier, location) in &context.qualified_references {
+        if env.resolve_table_alias(qualifier) != context.table_name.as_str() {
+            let names = [context.table_name.as_str()]
+                .into_iter()
+                .chain(env.tables_aliases.keys().map(|alias| alias.as_str()));
+            let diagnostic =
+                Diagnostic::error(&format!("Unresolved table name or alias `{}`", qualifier))
+                    .add_help("Qualify the fields with the table name or alias used in `FROM`")
+                    .with_location(*location);
+            return Err(add_did_you_mean_help(diagnostic, qualifier, names).as_boxed());
+        }
+    }
+
     // Remove all selected fields from hidden selection
     let hidden_selections: Vec<String> = context
         .hidden_selections
@@ -292,10 +837,21 @@ fn parse_select_query(
         statements,
         has_aggregation_function: context.is_single_value_query,
         has_group_by_statement: context.has_group_by_statement,
+        has_non_deterministic_function: context.has_non_deterministic_function,
         hidden_selections,
+        subquery: context.subquery,
+        in_subqueries: context.in_subqueries,
     }))
 }
 
+/// Returns true if the expression is a reference to the field with this name
+fn is_symbol(expression: &dyn Expression, name: &str) -> bool {
+    expression
+        .as_any()
+        .downcast_ref::<SymbolExpression>()
+        .is_some_and(|symbol| symbol.value == name)
+}
+
 fn parse_select_statement(
     context: &mut ParserContext,
     env: &mut Environment,
@@ -305,7 +861,7 @@ fn parse_select_statement(
     // Consume select keyword
     *position += 1;
 
-    if *position >= tokens.len() {
+    if tokens.is_at_end(*position) {
         return Err(Diagnostic::error("Incomplete input for select statement")
             .add_help("Try select one or more values in the `SELECT` statement")
             .add_note("Select statements requires at least selecting one value")
@@ -313,35 +869,92 @@ fn parse_select_statement(
             .as_boxed());
     }
 
-    let mut table_name = "";
+    let mut table_name = String::new();
     let mut fields_names: Vec<String> = Vec::new();
     let mut fields_values: Vec<Box<dyn Expression>> = Vec::new();
     let mut alias_table: HashMap<String, String> = HashMap::new();
     let mut is_select_all = false;
     let mut is_distinct = false;
+    let mut generated_names: Vec<(String, usize, usize)> = Vec::new();
+    let mut values_table: Option<ValuesTable> = None;
+    let mut row_number_location: Option<Location> = None;
 
     // Check if select has distinct keyword after it
-    if tokens[*position].kind == TokenKind::Distinct {
+    if tokens.is_kind(*position, TokenKind::Distinct) {
         is_distinct = true;
         *position += 1;
     }
 
+    // Derived table is parsed before the selected fields, so they are resolved with its columns types
+    let mut derived_table: Option<(DerivedTable, usize)> = None;
+    if let Some(from_position) = find_from_keyword_position(tokens, *position) {
+        let mut table_position = from_position + 1;
+        if tokens.is_kind(table_position, TokenKind::LeftParen) {
+            let table = parse_derived_table(context, env, tokens, &mut table_position)?;
+            derived_table = Some((table, table_position));
+        }
+    }
+
     // Select all option
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::Star {
+    if tokens.is_kind(*position, TokenKind::Star) {
         // Consume `*`
         *position += 1;
         is_select_all = true;
     } else {
-        while *position < tokens.len() && tokens[*position].kind != TokenKind::From {
-            let expression = parse_expression(context, env, tokens, position)?;
+        while !tokens.is_at_end(*position) && !tokens.is_kind(*position, TokenKind::From) {
+            if fields_names.len() >= context.options.max_select_items {
+                return Err(Diagnostic::error("Too many selected fields")
+                    .add_note(&format!(
+                        "Maximum number of selected fields is {}",
+                        context.options.max_select_items
+                    ))
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed());
+            }
+
+            let expression_start = *position;
+            let row_numbers_count = context.row_number_titles.len();
+            context.is_select_item = true;
+            let expression = parse_expression(context, env, tokens, position);
+            context.is_select_item = false;
+            let expression = expression?;
+
+            // Row numbers are assigned after sorting the result, so they can't be part of other expressions
+            let is_row_number = context.row_number_titles.len() > row_numbers_count;
+            if is_row_number {
+                let location = get_safe_location(tokens, expression_start);
+                let is_row_number_call = context.row_number_titles.len() == row_numbers_count + 1
+                    && expression
+                        .as_any()
+                        .downcast_ref::<SymbolExpression>()
+                        .is_some_and(|symbol| {
+                            context.row_number_titles.last() == Some(&symbol.value)
+                        });
+                if !is_row_number_call {
+                    return Err(Diagnostic::error(
+                        "`ROW_NUMBER` can't be used inside other expressions",
+                    )
+                    .add_help("Try to select `ROW_NUMBER()` as a separate column")
+                    .with_location(location)
+                    .as_boxed());
+                }
+                row_number_location.get_or_insert(location);
+            }
+
             let expr_type = expression.expr_type(env).clone();
             let expression_name = get_expression_name(&expression);
+            let is_generated_name = expression_name.is_err();
             let field_name = if expression_name.is_ok() {
                 expression_name.ok().unwrap()
             } else {
                 context.generate_column_name()
             };
 
+            // Keep the tokens range of expressions with generated names to be used as titles later
+            if is_generated_name {
+                generated_names.push((field_name.to_string(), expression_start, *position));
+            }
+
             // Assert that each selected field is unique
             if fields_names.contains(&field_name) {
                 return Err(Diagnostic::error("Can't select the same field twice")
@@ -350,7 +963,7 @@ fn parse_select_statement(
             }
 
             // Check for Field name alias
-            if *position < tokens.len() && tokens[*position].kind == TokenKind::As {
+            if tokens.is_kind(*position, TokenKind::As) {
                 // Consume `as` keyword
                 *position += 1;
                 let alias_name_token = consume_kind(tokens, *position, TokenKind::Symbol);
@@ -361,7 +974,7 @@ fn parse_select_statement(
                 }
 
                 // Register alias name
-                let alias_name = alias_name_token.ok().unwrap().literal.to_string();
+                let alias_name = alias_name_token.ok().unwrap().identifier().to_string();
                 if context.selected_fields.contains(&alias_name)
                     || alias_table.contains_key(&alias_name)
                 {
@@ -383,6 +996,22 @@ fn parse_select_statement(
                 alias_table.insert(field_name.to_string(), alias_name);
             }
 
+            // Track selected titles to be used later by `GROUP BY ALL`, `ORDER BY ALL` and `ORDER BY` positions
+            let title = alias_table.get(&field_name).unwrap_or(&field_name);
+            if is_row_number {
+                if let Some(row_number_title) = context.row_number_titles.last_mut() {
+                    *row_number_title = title.to_string();
+                }
+            } else if !context.aggregations.contains_key(&field_name) {
+                context.non_aggregated_selections.push(title.to_string());
+
+                // Track selected table fields to check that they are used in `GROUP BY`
+                if expression.kind() == ExpressionKind::Symbol {
+                    context.non_aggregated_fields.push(field_name.to_string());
+                }
+            }
+            context.selected_titles.push(title.to_string());
+
             // Register field type
             env.define(field_name.to_string(), expr_type);
 
@@ -391,7 +1020,7 @@ fn parse_select_statement(
             fields_values.push(expression);
 
             // Consume `,` or break
-            if *position < tokens.len() && tokens[*position].kind == TokenKind::Comma {
+            if tokens.is_kind(*position, TokenKind::Comma) {
                 *position += 1;
             } else {
                 break;
@@ -400,30 +1029,119 @@ fn parse_select_statement(
     }
 
     // Parse optional Form statement
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::From {
+    if tokens.is_kind(*position, TokenKind::From) {
         // Consume `from` keyword
         *position += 1;
 
-        let table_name_token = consume_kind(tokens, *position, TokenKind::Symbol);
-        if table_name_token.is_err() {
-            return Err(Diagnostic::error("Expect `identifier` as a table name")
-                .add_note("Table name must be an identifier")
-                .with_location(get_safe_location(tokens, *position))
-                .as_boxed());
-        }
-
-        // Consume table name
-        *position += 1;
+        if let Some((table, table_end_position)) = derived_table {
+            *position = table_end_position;
+            let (name, columns) = match &table {
+                DerivedTable::Subquery(name, columns) => (name, columns),
+                DerivedTable::Values(name, table) => (name, &table.columns),
+            };
+            check_derived_table_fields(
+                context,
+                name,
+                columns,
+                &fields_names,
+                &fields_values,
+                &alias_table,
+                get_safe_location(tokens, *position - 1),
+            )?;
+            table_name = name.to_string();
+            context.table_name = table_name.to_string();
+            if let DerivedTable::Values(_, table) = table {
+                values_table = Some(table);
+            }
+        } else {
+            let table_name_token = consume_kind(tokens, *position, TokenKind::Symbol);
+            if table_name_token.is_err() {
+                return Err(Diagnostic::error("Expect `identifier` as a table name")
+                    .add_note("Table name must be an identifier")
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed());
+            }
 
-        table_name = &table_name_token.ok().unwrap().literal;
-        if !TABLES_FIELDS_NAMES.contains_key(table_name) {
-            return Err(Diagnostic::error("Unresolved table name")
-                .add_help("Check the documentations to see available tables")
-                .with_location(get_safe_location(tokens, *position))
-                .as_boxed());
+            // Consume table name
+            *position += 1;
+
+            table_name = table_name_token.ok().unwrap().identifier().to_string();
+
+            // Select from table-valued function like `COMMITS_TOUCHING("src/")`
+            if tokens.is_kind(*position, TokenKind::LeftParen) {
+                table_name = parse_table_function(context, env, tokens, position, &table_name)?;
+            }
+
+            if !env.has_table(&table_name) {
+                let tables_names = TABLES_FIELDS_NAMES
+                    .keys()
+                    .copied()
+                    .chain(env.tables.keys().map(|name| name.as_str()));
+                let diagnostic = Diagnostic::error("Unresolved table name")
+                    .add_help("Check the documentations to see available tables")
+                    .with_location(get_safe_location(tokens, *position));
+                return Err(add_did_you_mean_help(diagnostic, &table_name, tables_names).as_boxed());
+            }
+
+            register_current_table_fields_types(&table_name, env);
+            context.table_name = table_name.to_string();
+
+            // Parse optional table alias `FROM commits c` or `FROM commits AS c`
+            let has_as_keyword = consume_kind(tokens, *position, TokenKind::As).is_ok();
+            let alias_position = if has_as_keyword {
+                *position + 1
+            } else {
+                *position
+            };
+
+            if let Ok(alias_token) = consume_kind(tokens, alias_position, TokenKind::Symbol) {
+                env.define_table_alias(&alias_token.identifier(), &table_name);
+                *position = alias_position + 1;
+            } else if has_as_keyword {
+                return Err(Diagnostic::error("Expect `identifier` as table alias name")
+                    .with_location(get_safe_location(tokens, alias_position))
+                    .as_boxed());
+            }
         }
+    }
+
+    // Select without table has only one row, so it has nothing to number
+    if let Some(location) = row_number_location.filter(|_| table_name.is_empty()) {
+        return Err(Diagnostic::error("`ROW_NUMBER` can't be used without table")
+            .add_help("Try to select from a table, for example `SELECT ROW_NUMBER(), title FROM commits`")
+            .with_location(location)
+            .as_boxed());
+    }
+
+    // Select without table has no fields to name the columns, so expressions text is used as title
+    if table_name.is_empty() {
+        for (field_name, start, end) in generated_names {
+            if alias_table.contains_key(&field_name) {
+                continue;
+            }
+
+            let title = tokens_as_text(tokens.get(start..end).unwrap_or_default());
+            if context.selected_titles.contains(&title) || fields_names.contains(&title) {
+                continue;
+            }
+
+            for selected_title in context
+                .selected_titles
+                .iter_mut()
+                .chain(context.non_aggregated_selections.iter_mut())
+            {
+                if *selected_title == field_name {
+                    *selected_title = title.to_string();
+                }
+            }
 
-        register_current_table_fields_types(table_name, env);
+            if let Some(data_type) = env.resolve_type(&field_name).cloned() {
+                env.define(title.to_string(), data_type);
+            }
+
+            context.selected_fields.push(title.to_string());
+            alias_table.insert(field_name, title);
+        }
     }
 
     // Make sure `SELECT *` used with specific table
@@ -447,26 +1165,436 @@ fn parse_select_statement(
 
     // If it `select *` make all table fields selectable
     if is_select_all {
-        select_all_table_fields(
-            table_name,
-            &mut context.selected_fields,
-            &mut fields_names,
-            &mut fields_values,
-        );
+        if let Some(values_table) = &values_table {
+            for column in &values_table.columns {
+                fields_names.push(column.to_string());
+                context.selected_fields.push(column.to_string());
+                fields_values.push(Box::new(SymbolExpression {
+                    value: column.to_string(),
+                }));
+            }
+        } else {
+            select_all_table_fields(
+                env,
+                &table_name,
+                &mut context.selected_fields,
+                &mut fields_names,
+                &mut fields_values,
+            );
+        }
+
+        context
+            .non_aggregated_selections
+            .extend(fields_names.iter().cloned());
+        context.selected_titles.extend(fields_names.iter().cloned());
+        context
+            .non_aggregated_fields
+            .extend(fields_names.iter().cloned());
     }
 
     // Type check all selected fields has type registered in type table
-    type_check_selected_fields(env, table_name, &fields_names, tokens, *position)?;
+    type_check_selected_fields(env, &table_name, &fields_names, tokens, *position)?;
+
+    // Register selected expressions to be reused by `WHERE`, `GROUP BY`, `HAVING` and `ORDER BY`
+    for (field_name, expression) in fields_names.iter().zip(fields_values.iter()) {
+        if expression.kind() == ExpressionKind::Symbol {
+            continue;
+        }
+
+        if let Some(fingerprint) = expression.fingerprint() {
+            let title = alias_table
+                .get(field_name)
+                .unwrap_or(field_name)
+                .to_string();
+            context
+                .selected_expressions
+                .entry(fingerprint)
+                .or_insert(title);
+        }
+    }
 
     Ok(Box::new(SelectStatement {
-        table_name: table_name.to_string(),
+        table_name,
         fields_names,
         fields_values,
         alias_table,
         is_distinct,
+        values_table,
     }))
 }
 
+/// Table that is the result of subquery `(SELECT ...) AS name` or inline table literal
+/// `(VALUES ...) AS name(columns)`, with the table name and the columns names
+enum DerivedTable {
+    Subquery(String, Vec<String>),
+    Values(String, ValuesTable),
+}
+
+/// Returns the position of the `FROM` keyword of the current select statement, keywords inside
+/// parenthesis belong to subqueries or function calls so they are skipped
+fn find_from_keyword_position(tokens: &[Token], position: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(position) {
+        match token.kind {
+            TokenKind::LeftParen => depth += 1,
+            TokenKind::RightParen if depth == 0 => return None,
+            TokenKind::RightParen => depth -= 1,
+            TokenKind::From if depth == 0 => return Some(index),
+            TokenKind::Semicolon if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse derived table `FROM (SELECT ...) AS name` or `FROM (VALUES ...) AS name(columns)`
+fn parse_derived_table(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<DerivedTable, Box<Diagnostic>> {
+    if tokens.is_kind(*position + 1, TokenKind::Select) {
+        let (name, columns) = parse_subquery_table(context, env, tokens, position)?;
+        return Ok(DerivedTable::Subquery(name, columns));
+    }
+
+    let (name, table) = parse_values_table(context, env, tokens, position)?;
+    Ok(DerivedTable::Values(name, table))
+}
+
+/// Parse the query in `FROM (SELECT ...) AS name` and define its selected columns types,
+/// returns the table name and the columns names in the selection order
+fn parse_subquery_table(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<(String, Vec<String>), Box<Diagnostic>> {
+    // Consume `(` token
+    *position += 1;
+
+    // Subquery has its own fields, so the outer query scope is restored after parsing it
+    let outer_scopes = env.scopes.clone();
+    let subquery = match parse_nested_select_query(context, env, tokens, position)? {
+        Query::Select(subquery) => subquery,
+        Query::GlobalVariableDeclaration(_)
+        | Query::ShowVariables
+        | Query::Explain(_)
+        | Query::CreateTempTable(_)
+        | Query::Insert(_) => {
+            unreachable!()
+        }
+    };
+
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(Diagnostic::error("Expect `)` after subquery")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
+    }
+
+    // Consume `)` token
+    *position += 1;
+
+    let table_name = parse_derived_table_name(tokens, position, "subquery")?;
+
+    let select_statement = subquery
+        .statements
+        .get("select")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<SelectStatement>()
+        .unwrap();
+
+    let columns = select_statement_columns(env, select_statement);
+
+    env.scopes = outer_scopes;
+    for (column, data_type) in &columns {
+        env.define(column.to_string(), data_type.clone());
+    }
+
+    context.subquery = Some(Box::new(subquery));
+    let columns = columns.into_iter().map(|(column, _)| column).collect();
+    Ok((table_name, columns))
+}
+
+/// Returns the titles of the selected fields with their types, used as the columns of derived tables
+fn select_statement_columns(
+    env: &Environment,
+    select_statement: &SelectStatement,
+) -> Vec<(String, DataType)> {
+    let mut columns: Vec<(String, DataType)> = vec![];
+    for field_name in &select_statement.fields_names {
+        let title = select_statement
+            .alias_table
+            .get(field_name)
+            .unwrap_or(field_name);
+        let data_type = env
+            .resolve_type(field_name)
+            .cloned()
+            .unwrap_or(DataType::Any);
+        columns.push((title.to_string(), data_type));
+    }
+    columns
+}
+
+/// Parse the required name of derived table `AS name`
+fn parse_derived_table_name(
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    table_kind: &str,
+) -> Result<String, Box<Diagnostic>> {
+    let table_name_token = match consume_kind(tokens, *position, TokenKind::As)
+        .and_then(|_| consume_kind(tokens, *position + 1, TokenKind::Symbol))
+    {
+        Ok(table_name_token) => table_name_token,
+        Err(_) => {
+            return Err(Diagnostic::error(&format!(
+                "Expect `AS` and table name after `{}`",
+                table_kind
+            ))
+            .add_note("Derived table must have a name")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
+        }
+    };
+
+    let table_name = table_name_token.identifier().to_string();
+
+    // Consume `AS` keyword and table name
+    *position += 2;
+    Ok(table_name)
+}
+
+/// Check that the fields referenced in the selection are columns of the derived table, function
+/// names, aggregations and generated row numbers are not table fields so they are skipped
+fn check_derived_table_fields(
+    context: &ParserContext,
+    table_name: &str,
+    columns: &[String],
+    fields_names: &[String],
+    fields_values: &[Box<dyn Expression>],
+    alias_table: &HashMap<String, String>,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let selected_symbols = fields_names
+        .iter()
+        .zip(fields_values)
+        .filter(|(_, value)| value.kind() == ExpressionKind::Symbol)
+        .map(|(name, _)| name);
+
+    let referenced_fields = selected_symbols
+        .chain(context.hidden_selections.iter())
+        .filter(|name| !context.aggregations.contains_key(*name))
+        .filter(|name| {
+            let title = alias_table.get(*name).unwrap_or(name);
+            !context.row_number_titles.contains(title)
+        });
+
+    for field_name in referenced_fields {
+        if columns.contains(field_name) || alias_table.values().any(|alias| alias == field_name) {
+            continue;
+        }
+
+        let diagnostic = Diagnostic::error(&format!(
+            "Table `{}` has no field with name `{}`",
+            table_name, field_name
+        ))
+        .with_location(location);
+        let columns = columns.iter().map(|column| column.as_str());
+        return Err(add_did_you_mean_help(diagnostic, field_name, columns).as_boxed());
+    }
+
+    Ok(())
+}
+
+/// Parse inline table literal `(VALUES (...), (...)) AS name(columns)` and define its columns types,
+/// all values must be constant expressions and the values of the same column must have the same type
+fn parse_values_table(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<(String, ValuesTable), Box<Diagnostic>> {
+    // Consume `(` token
+    *position += 1;
+
+    if consume_kind(tokens, *position, TokenKind::Values).is_err() {
+        return Err(
+            Diagnostic::error("Expect `VALUES` after `(` in `FROM` statement")
+                .add_help("Try to use `FROM (VALUES (1, \"a\"), (2, \"b\")) AS name(id, title)`")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed(),
+        );
+    }
+
+    // Consume `VALUES` keyword
+    *position += 1;
+
+    let mut rows: Vec<Vec<Box<dyn Expression>>> = vec![];
+    let mut columns_types: Vec<DataType> = vec![];
+    loop {
+        let row_location = get_safe_location(tokens, *position);
+        if consume_kind(tokens, *position, TokenKind::LeftParen).is_err() {
+            return Err(Diagnostic::error("Expect `(` before `VALUES` row")
+                .with_location(row_location)
+                .as_boxed());
+        }
+
+        // Consume `(` token
+        *position += 1;
+
+        let mut row: Vec<Box<dyn Expression>> = vec![];
+        loop {
+            let value_location = get_safe_location(tokens, *position);
+            let hidden_selections_count = context.hidden_selections.len();
+            let aggregations_count = context.aggregations.len();
+            let value = parse_expression(context, env, tokens, position)?;
+
+            // Values can't reference table fields or aggregations
+            if context.hidden_selections.len() != hidden_selections_count
+                || context.aggregations.len() != aggregations_count
+            {
+                return Err(
+                    Diagnostic::error("`VALUES` can contains only constant expressions")
+                        .add_help("Try to use literals, global variables or functions of them")
+                        .with_location(value_location)
+                        .as_boxed(),
+                );
+            }
+
+            // Values of each column must have the same type, `NULL` is allowed in any column
+            let value_type = value.expr_type(env);
+            let column_index = row.len();
+            if column_index >= columns_types.len() {
+                columns_types.push(value_type);
+            } else if columns_types[column_index].is_null() {
+                columns_types[column_index] = value_type;
+            } else if !value_type.is_null() && value_type != columns_types[column_index] {
+                return Err(type_mismatch_error(
+                    value_location,
+                    columns_types[column_index].clone(),
+                    value_type,
+                ));
+            }
+
+            row.push(value);
+
+            if tokens.is_kind(*position, TokenKind::Comma) {
+                // Consume `,` token
+                *position += 1;
+            } else {
+                break;
+            }
+        }
+
+        if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+            return Err(Diagnostic::error("Expect `)` after `VALUES` row")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed());
+        }
+
+        // Consume `)` token
+        *position += 1;
+
+        if !rows.is_empty() && rows[0].len() != row.len() {
+            return Err(Diagnostic::error(&format!(
+                "All `VALUES` rows must have {} values, got {}",
+                rows[0].len(),
+                row.len()
+            ))
+            .with_location(row_location)
+            .as_boxed());
+        }
+
+        rows.push(row);
+
+        if tokens.is_kind(*position, TokenKind::Comma) {
+            // Consume `,` token
+            *position += 1;
+        } else {
+            break;
+        }
+    }
+
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(Diagnostic::error("Expect `)` after `VALUES` rows")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
+    }
+
+    // Consume `)` token
+    *position += 1;
+
+    let table_name = parse_derived_table_name(tokens, position, "VALUES")?;
+
+    // Parse optional columns names, by default they are `column1`, `column2`, ...etc
+    let columns_count = columns_types.len();
+    let mut columns: Vec<String> = vec![];
+    if tokens.is_kind(*position, TokenKind::LeftParen) {
+        // Consume `(` token
+        *position += 1;
+
+        loop {
+            let column_token = consume_kind(tokens, *position, TokenKind::Symbol);
+            if column_token.is_err() {
+                return Err(Diagnostic::error("Expect `identifier` as column name")
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed());
+            }
+
+            let column = column_token.ok().unwrap().identifier().to_string();
+            if columns.contains(&column) {
+                return Err(
+                    Diagnostic::error(&format!("Column `{}` is defined twice", column))
+                        .with_location(get_safe_location(tokens, *position))
+                        .as_boxed(),
+                );
+            }
+
+            // Consume column name
+            *position += 1;
+            columns.push(column);
+
+            if tokens.is_kind(*position, TokenKind::Comma) {
+                // Consume `,` token
+                *position += 1;
+            } else {
+                break;
+            }
+        }
+
+        if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+            return Err(Diagnostic::error("Expect `)` after columns names")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed());
+        }
+
+        // Consume `)` token
+        *position += 1;
+
+        if columns.len() != columns_count {
+            return Err(Diagnostic::error(&format!(
+                "Expect {} columns names, got {}",
+                columns_count,
+                columns.len()
+            ))
+            .with_location(get_safe_location(tokens, *position - 1))
+            .as_boxed());
+        }
+    } else {
+        columns = (1..=columns_count)
+            .map(|index| format!("column{}", index))
+            .collect();
+    }
+
+    for (column, data_type) in columns.iter().zip(columns_types) {
+        env.define(column.to_string(), data_type);
+    }
+
+    Ok((table_name, ValuesTable { columns, rows }))
+}
+
 fn parse_where_statement(
     context: &mut ParserContext,
     env: &mut Environment,
@@ -474,7 +1602,7 @@ fn parse_where_statement(
     position: &mut usize,
 ) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
     *position += 1;
-    if *position >= tokens.len() {
+    if tokens.is_at_end(*position) {
         return Err(Diagnostic::error("Expect expression after `WHERE` keyword")
             .add_help("Try to add boolean expression after `WHERE` keyword")
             .add_note("`WHERE` statement expects expression as condition")
@@ -485,8 +1613,11 @@ fn parse_where_statement(
     let aggregations_count_before = context.aggregations.len();
 
     // Make sure WHERE condition expression has boolean type
-    let condition_location = tokens[*position].location;
-    let condition = parse_expression(context, env, tokens, position)?;
+    let condition_location = get_safe_location(tokens, *position);
+    context.is_where_condition = true;
+    let condition = parse_expression(context, env, tokens, position);
+    context.is_where_condition = false;
+    let condition = condition?;
     let condition_type = condition.expr_type(env);
     if condition_type != DataType::Boolean {
         return Err(Diagnostic::error(&format!(
@@ -510,7 +1641,39 @@ fn parse_where_statement(
         );
     }
 
-    Ok(Box::new(WhereStatement { condition }))
+    // Commits outside the range are never walked, so the range must filter all the rows
+    let range_fields = context
+        .hidden_selections
+        .iter()
+        .filter(|name| name.starts_with(RANGE_FIELD_PREFIX));
+    for range_field in range_fields {
+        if !is_required_condition(condition.as_ref(), range_field) {
+            return Err(Diagnostic::error(
+                "`RANGE` can't be negated or combined with other conditions using `OR` or `XOR`",
+            )
+            .add_help("Try to use `RANGE` as the `WHERE` condition or join it using `AND`")
+            .add_note("`RANGE` restricts the commits walk so it must apply to all rows")
+            .with_location(condition_location)
+            .as_boxed());
+        }
+    }
+
+    // Only the file with the required path is blamed instead of all the files at `HEAD`
+    if context.table_name == "blame" {
+        if let Some(path) = required_equal_string(condition.as_ref(), "path") {
+            let field_name = format!("{}{}", BLAME_PATH_FIELD_PREFIX, path);
+            if !context.hidden_selections.contains(&field_name) {
+                context.hidden_selections.push(field_name.to_string());
+            }
+            env.define(field_name, DataType::Boolean);
+        }
+    }
+
+    let condition_end = get_safe_location(tokens, *position - 1).end;
+    Ok(Box::new(WhereStatement {
+        condition,
+        condition_location: Some((condition_location.start, condition_end)),
+    }))
 }
 
 fn parse_group_by_statement(
@@ -520,7 +1683,7 @@ fn parse_group_by_statement(
     position: &mut usize,
 ) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
     *position += 1;
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::By {
+    if !tokens.is_kind(*position, TokenKind::By) {
         return Err(
             Diagnostic::error("Expect keyword `by` after keyword `group`")
                 .add_help("Try to use `BY` keyword after `GROUP")
@@ -529,36 +1692,258 @@ fn parse_group_by_statement(
         );
     }
     *position += 1;
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::Symbol {
-        return Err(Diagnostic::error("Expect field name after `group by`")
-            .with_location(get_safe_location(tokens, *position - 1))
-            .as_boxed());
-    }
 
-    let field_name = tokens[*position].literal.to_string();
-    *position += 1;
+    // Group by all non aggregated selected fields, for example `GROUP BY ALL`
+    if tokens.is_kind(*position, TokenKind::All) {
+        // Consume `ALL` keyword
+        *position += 1;
 
-    if !env.contains(&field_name) {
-        return Err(
-            Diagnostic::error("Current table not contains field with this name")
-                .add_help("Check the documentations to see available fields for each tables")
-                .with_location(get_safe_location(tokens, *position - 1))
-                .as_boxed(),
-        );
+        let field_names = context.non_aggregated_selections.clone();
+
+        // If all selected fields are aggregations, the result is one group
+        context.has_group_by_statement = !field_names.is_empty();
+        context.group_by_fields = field_names.clone();
+        return Ok(Box::new(GroupByStatement {
+            field_names,
+            is_rollup: false,
+        }));
     }
 
-    context.has_group_by_statement = true;
-    Ok(Box::new(GroupByStatement { field_name }))
-}
+    // Group by the fields then by each prefix of them, for example `GROUP BY ROLLUP(name, email)`
+    if is_rollup_keyword(tokens, *position) {
+        return parse_group_by_rollup(context, env, tokens, position);
+    }
 
-fn parse_having_statement(
+    let current_token = match tokens.peek(*position) {
+        Some(current_token) => current_token,
+        None => {
+            return Err(Diagnostic::error("Expect field name after `group by`")
+                .with_location(get_safe_location(tokens, *position - 1))
+                .as_boxed());
+        }
+    };
+
+    // Group by selected expression, for example `SELECT LOWER(name) ... GROUP BY LOWER(name)`
+    let is_field_name = current_token.kind == TokenKind::Symbol
+        && consume_kind(tokens, *position + 1, TokenKind::LeftParen).is_err();
+    if !is_field_name {
+        return parse_group_by_selected_expression(context, env, tokens, position);
+    }
+
+    let mut field_name = current_token.identifier().to_string();
+    *position += 1;
+
+    // Qualified field name `table.field` or `alias.field`, the qualifier is checked after `FROM`
+    if let Ok(field_token) = consume_kind(tokens, *position, TokenKind::Dot)
+        .and_then(|_| consume_kind(tokens, *position + 1, TokenKind::Symbol))
+    {
+        context
+            .qualified_references
+            .push((field_name, current_token.location));
+        field_name = field_token.identifier().to_string();
+
+        // Consume `.` and field name
+        *position += 2;
+    }
+
+    if !env.contains(&field_name) {
+        let diagnostic = Diagnostic::error("Current table not contains field with this name")
+            .add_help("Check the documentations to see available fields for each tables")
+            .with_location(get_safe_location(tokens, *position - 1));
+        let fields = env.table_fields(&context.table_name);
+        let fields_names = fields.iter().map(|(name, _)| name.as_str());
+        return Err(add_did_you_mean_help(diagnostic, &field_name, fields_names).as_boxed());
+    }
+
+    // Make sure each non aggregated selected field is used in `GROUP BY`, unless any value mode is enabled
+    if !context.options.any_value_mode {
+        for selected_field in context.non_aggregated_fields.iter() {
+            if *selected_field != field_name {
+                return Err(Diagnostic::error(&format!(
+                    "Field `{}` must appear in the `GROUP BY` statement or be used in an aggregation function",
+                    selected_field
+                ))
+                .add_help("Try to add the field to `GROUP BY` or use `GROUP BY ALL`")
+                .add_note("Enable any value mode to select a value from any row in the group")
+                .with_location(get_safe_location(tokens, *position - 1))
+                .as_boxed());
+            }
+        }
+    }
+
+    context.has_group_by_statement = true;
+    context.group_by_fields = vec![field_name.to_string()];
+    Ok(Box::new(GroupByStatement {
+        field_names: vec![field_name],
+        is_rollup: false,
+    }))
+}
+
+/// Group by expression that is already selected, the rows are grouped by the selected column value
+fn parse_group_by_selected_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
+    let expression_location = get_safe_location(tokens, *position);
+    let expression = parse_expression(context, env, tokens, position)?;
+    let selected_column = expression
+        .as_any()
+        .downcast_ref::<SymbolExpression>()
+        .map(|symbol| symbol.value.to_string())
+        .filter(|column| context.selected_titles.contains(column));
+
+    let field_name = match selected_column {
+        Some(field_name) => field_name,
+        None => {
+            return Err(Diagnostic::error(
+                "Expect field name or selected expression after `group by`",
+            )
+            .add_help("Try to select the expression first then group by it")
+            .add_note("`GROUP BY` expression must be the same as one of the selected expressions")
+            .with_location(expression_location)
+            .as_boxed());
+        }
+    };
+
+    // Make sure each non aggregated selected field is used in `GROUP BY`, unless any value mode is enabled
+    if !context.options.any_value_mode {
+        if let Some(selected_field) = context.non_aggregated_fields.first() {
+            return Err(Diagnostic::error(&format!(
+                "Field `{}` must appear in the `GROUP BY` statement or be used in an aggregation function",
+                selected_field
+            ))
+            .add_help("Try to add the field to `GROUP BY` or use `GROUP BY ALL`")
+            .add_note("Enable any value mode to select a value from any row in the group")
+            .with_location(expression_location)
+            .as_boxed());
+        }
+    }
+
+    context.has_group_by_statement = true;
+    context.group_by_fields = vec![field_name.to_string()];
+    Ok(Box::new(GroupByStatement {
+        field_names: vec![field_name],
+        is_rollup: false,
+    }))
+}
+
+/// Parse `ROLLUP(field, ...)` after `GROUP BY`, the rows are grouped by all the fields, then by each prefix
+/// of them for the subtotal rows and by no fields for the grand total row
+fn parse_group_by_rollup(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
+    let rollup_location = get_safe_location(tokens, *position);
+
+    // Consume `ROLLUP` keyword and `(`
+    *position += 2;
+
+    let mut field_names: Vec<String> = vec![];
+    loop {
+        let field_token = match consume_kind(tokens, *position, TokenKind::Symbol) {
+            Ok(field_token) => field_token,
+            Err(_) => {
+                return Err(Diagnostic::error("Expect field name in `ROLLUP` fields")
+                    .add_help(
+                        "Try to use `ROLLUP` with fields names, for example `ROLLUP(name, email)`",
+                    )
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed());
+            }
+        };
+
+        let field_name = field_token.identifier().to_string();
+        if !env.contains(&field_name) {
+            let diagnostic = Diagnostic::error("Current table not contains field with this name")
+                .add_help("Check the documentations to see available fields for each tables")
+                .with_location(field_token.location);
+            let fields = env.table_fields(&context.table_name);
+            let fields_names = fields.iter().map(|(name, _)| name.as_str());
+            return Err(add_did_you_mean_help(diagnostic, &field_name, fields_names).as_boxed());
+        }
+
+        if field_names.contains(&field_name) {
+            return Err(Diagnostic::error(&format!(
+                "Field `{}` is used twice in `ROLLUP` fields",
+                field_name
+            ))
+            .with_location(field_token.location)
+            .as_boxed());
+        }
+
+        // Consume field name
+        *position += 1;
+
+        // Field that is not selected must be selected as hidden field to group the rows by it
+        if !context.selected_fields.contains(&field_name)
+            && !context.hidden_selections.contains(&field_name)
+        {
+            context.hidden_selections.push(field_name.to_string());
+        }
+
+        field_names.push(field_name);
+
+        if tokens.is_kind(*position, TokenKind::Comma) {
+            // Consume `,` token
+            *position += 1;
+        } else {
+            break;
+        }
+    }
+
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(Diagnostic::error("Expect `)` after `ROLLUP` fields")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
+    }
+
+    // Consume `)` token
+    *position += 1;
+
+    // Make sure each non aggregated selected field is used in `ROLLUP`, unless any value mode is enabled
+    if !context.options.any_value_mode {
+        for selected_field in context.non_aggregated_fields.iter() {
+            if !field_names.contains(selected_field) {
+                return Err(Diagnostic::error(&format!(
+                    "Field `{}` must appear in the `GROUP BY` statement or be used in an aggregation function",
+                    selected_field
+                ))
+                .add_help("Try to add the field to `ROLLUP` fields")
+                .add_note("Enable any value mode to select a value from any row in the group")
+                .with_location(rollup_location)
+                .as_boxed());
+            }
+        }
+    }
+
+    context.has_group_by_statement = true;
+    context.group_by_fields = field_names.clone();
+    Ok(Box::new(GroupByStatement {
+        field_names,
+        is_rollup: true,
+    }))
+}
+
+/// `ROLLUP` is not reserved keyword, it's only a keyword after `GROUP BY` when followed by `(`
+#[inline(always)]
+fn is_rollup_keyword(tokens: &[Token], position: usize) -> bool {
+    tokens.peek(position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("rollup")
+    }) && tokens.is_kind(position + 1, TokenKind::LeftParen)
+}
+
+fn parse_having_statement(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
     *position += 1;
-    if *position >= tokens.len() {
+    if tokens.is_at_end(*position) {
         return Err(
             Diagnostic::error("Expect expression after `HAVING` keyword")
                 .add_help("Try to add boolean expression after `HAVING` keyword")
@@ -569,7 +1954,7 @@ fn parse_having_statement(
     }
 
     // Make sure HAVING condition expression has boolean type
-    let condition_location = tokens[*position].location;
+    let condition_location = get_safe_location(tokens, *position);
     let condition = parse_expression(context, env, tokens, position)?;
     let condition_type = condition.expr_type(env);
     if condition_type != DataType::Boolean {
@@ -583,49 +1968,123 @@ fn parse_having_statement(
         .as_boxed());
     }
 
-    Ok(Box::new(HavingStatement { condition }))
+    let condition_end = get_safe_location(tokens, *position - 1).end;
+    Ok(Box::new(HavingStatement {
+        condition,
+        condition_location: Some((condition_location.start, condition_end)),
+    }))
 }
 
 fn parse_limit_statement(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
+    let count = parse_limit_count(context, env, tokens, position, "LIMIT")?;
+    Ok(Box::new(LimitStatement { count }))
+}
+
+fn parse_sample_statement(
+    context: &mut ParserContext,
+    env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
+    let count = parse_limit_count(context, env, tokens, position, "SAMPLE")?;
+    Ok(Box::new(SampleStatement { count }))
+}
+
+/// Parse the rows count after `LIMIT` or `SAMPLE` keyword, it's a constant number expression
+/// or a percentage of the rows, for example `LIMIT 10` or `SAMPLE 2.5 PERCENT`
+fn parse_limit_count(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    keyword: &str,
+) -> Result<LimitCount, Box<Diagnostic>> {
     *position += 1;
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::Integer {
-        return Err(Diagnostic::error("Expect number after `LIMIT` keyword")
-            .with_location(get_safe_location(tokens, *position - 1))
+    if tokens.is_at_end(*position) {
+        return Err(
+            Diagnostic::error(&format!("Expect number after `{}` keyword", keyword))
+                .with_location(get_safe_location(tokens, *position - 1))
+                .as_boxed(),
+        );
+    }
+
+    // Make sure the count is a constant number expression
+    let count_location = get_safe_location(tokens, *position);
+    let count_expression = parse_expression(context, env, tokens, position)?;
+    let count_result = evaluate_constant_number(&count_expression);
+    if let Err(error) = count_result {
+        return Err(Diagnostic::error(&error)
+            .add_note(&format!(
+                "`{}` value must be a constant number expression",
+                keyword
+            ))
+            .with_location(count_location)
             .as_boxed());
     }
 
-    let count_result: Result<usize, ParseIntError> = tokens[*position].literal.parse();
+    let count_value = count_result.ok().unwrap();
 
-    // Report clear error for Integer parsing
-    if let Err(error) = &count_result {
-        if error.kind().eq(&IntErrorKind::PosOverflow) {
-            return Err(Diagnostic::error("`LIMIT` integer value is too large")
-                .add_help("Try to use smaller value")
-                .add_note(&format!(
-                    "`LIMIT` value must be between 0 and {}",
-                    usize::MAX
-                ))
-                .with_location(get_safe_location(tokens, *position))
-                .as_boxed());
+    // Check for percentage count, for example `LIMIT 10 PERCENT`
+    if tokens.is_kind(*position, TokenKind::Percent) {
+        // Consume `PERCENT` keyword
+        *position += 1;
+
+        let percentage = if count_value.data_type().is_int() {
+            count_value.as_int() as f64
+        } else {
+            count_value.as_float()
+        };
+
+        if !(0.0..=100.0).contains(&percentage) {
+            return Err(
+                Diagnostic::error(&format!("`{}` percentage value is invalid", keyword))
+                    .add_help(&format!(
+                        "`{}` percentage value must be between 0 and 100",
+                        keyword
+                    ))
+                    .with_location(count_location)
+                    .as_boxed(),
+            );
         }
 
-        return Err(Diagnostic::error("`LIMIT` integer value is invalid")
-            .add_help(&format!(
-                "`LIMIT` value must be between 0 and {}",
-                usize::MAX
-            ))
-            .with_location(get_safe_location(tokens, *position))
-            .as_boxed());
+        return Ok(LimitCount::Ratio(percentage / 100.0));
     }
 
-    // Consume Integer value
-    *position += 1;
+    if !count_value.data_type().is_int() {
+        return Err(Diagnostic::error(&format!(
+            "Expect `{}` value to be type {} but got {}",
+            keyword,
+            DataType::Integer,
+            count_value.data_type()
+        ))
+        .add_help(&format!(
+            "Try to use `PERCENT` keyword after Float value to {} by percentage",
+            keyword.to_lowercase()
+        ))
+        .with_location(count_location)
+        .as_boxed());
+    }
 
-    let count = count_result.unwrap();
-    Ok(Box::new(LimitStatement { count }))
+    let count = count_value.as_int();
+    if count < 0 {
+        return Err(
+            Diagnostic::error(&format!("`{}` integer value is invalid", keyword))
+                .add_help(&format!(
+                    "`{}` value must be between 0 and {}",
+                    keyword,
+                    i64::MAX
+                ))
+                .with_location(count_location)
+                .as_boxed(),
+        );
+    }
+
+    Ok(LimitCount::Absolute(count as usize))
 }
 
 fn parse_offset_statement(
@@ -633,13 +2092,18 @@ fn parse_offset_statement(
     position: &mut usize,
 ) -> Result<Box<dyn Statement>, Box<Diagnostic>> {
     *position += 1;
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::Integer {
-        return Err(Diagnostic::error("Expect number after `OFFSET` keyword")
-            .with_location(get_safe_location(tokens, *position - 1))
-            .as_boxed());
-    }
+    check_negative_offset_count(tokens, *position)?;
+
+    let count_token = match consume_kind(tokens, *position, TokenKind::Integer) {
+        Ok(count_token) => count_token,
+        Err(_) => {
+            return Err(Diagnostic::error("Expect number after `OFFSET` keyword")
+                .with_location(get_safe_location(tokens, *position - 1))
+                .as_boxed());
+        }
+    };
 
-    let count_result: Result<usize, ParseIntError> = tokens[*position].literal.parse();
+    let count_result: Result<usize, ParseIntError> = count_token.literal.parse();
 
     // Report clear error for Integer parsing
     if let Err(error) = &count_result {
@@ -678,7 +2142,7 @@ fn parse_order_by_statement(
     // Consume `ORDER` keyword
     *position += 1;
 
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::By {
+    if !tokens.is_kind(*position, TokenKind::By) {
         return Err(
             Diagnostic::error("Expect keyword `BY` after keyword `ORDER")
                 .add_help("Try to use `BY` keyword after `ORDER")
@@ -693,13 +2157,60 @@ fn parse_order_by_statement(
     let mut arguments: Vec<Box<dyn Expression>> = vec![];
     let mut sorting_orders: Vec<SortingOrder> = vec![];
 
+    // Check for `ORDER BY ALL` to order by all selected fields from left to right
+    if tokens.is_kind(*position, TokenKind::All) {
+        // Consume `ALL` keyword
+        *position += 1;
+
+        let mut order = SortingOrder::Ascending;
+        if tokens.is_token(*position, is_asc_or_desc) {
+            if tokens.is_kind(*position, TokenKind::Descending) {
+                order = SortingOrder::Descending;
+            }
+
+            // Consume `ASC or DESC` keyword
+            *position += 1;
+        }
+
+        for title in context.selected_titles.iter() {
+            // Row numbers are not assigned yet while sorting
+            if context.row_number_titles.contains(title) {
+                continue;
+            }
+
+            arguments.push(Box::new(SymbolExpression {
+                value: title.to_string(),
+            }));
+            sorting_orders.push(order.clone());
+        }
+
+        return Ok(Box::new(OrderByStatement {
+            arguments,
+            sorting_orders,
+        }));
+    }
+
     loop {
-        let argument = parse_expression(context, env, tokens, position)?;
+        let argument_location = get_safe_location(tokens, *position);
+        let argument_start = *position;
+        let mut argument = parse_expression(context, env, tokens, position)?;
+
+        // Integer literal alone is the position of selected field, for example `ORDER BY 1`
+        if *position == argument_start + 1 {
+            if let Some(token) =
+                tokens.peek_if(argument_start, |token| token.kind == TokenKind::Integer)
+            {
+                argument = resolve_order_by_ordinal(context, token)?;
+            }
+        }
+
+        check_order_by_argument_after_aggregation(context, argument.as_ref(), argument_location)?;
+        check_order_by_argument_is_not_row_number(context, argument.as_ref(), argument_location)?;
         arguments.push(argument);
 
         let mut order = SortingOrder::Ascending;
-        if *position < tokens.len() && is_asc_or_desc(&tokens[*position]) {
-            if tokens[*position].kind == TokenKind::Descending {
+        if tokens.is_token(*position, is_asc_or_desc) {
+            if tokens.is_kind(*position, TokenKind::Descending) {
                 order = SortingOrder::Descending;
             }
 
@@ -708,7 +2219,7 @@ fn parse_order_by_statement(
         }
 
         sorting_orders.push(order);
-        if *position < tokens.len() && tokens[*position].kind == TokenKind::Comma {
+        if tokens.is_kind(*position, TokenKind::Comma) {
             // Consume `,` keyword
             *position += 1;
         } else {
@@ -722,14 +2233,123 @@ fn parse_order_by_statement(
     }))
 }
 
+/// Resolve the ordinal position in `ORDER BY` to the selected field title, positions start from 1
+fn resolve_order_by_ordinal(
+    context: &ParserContext,
+    token: &Token,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let title = token
+        .literal
+        .parse::<usize>()
+        .ok()
+        .and_then(|ordinal| ordinal.checked_sub(1))
+        .and_then(|index| context.selected_titles.get(index));
+
+    match title {
+        Some(title) => Ok(Box::new(SymbolExpression {
+            value: title.to_string(),
+        })),
+        None => Err(Diagnostic::error(&format!(
+            "`ORDER BY` position {} is not in select list",
+            token.literal
+        ))
+        .add_help(&format!(
+            "`ORDER BY` position must be between 1 and {}",
+            context.selected_titles.len()
+        ))
+        .add_note("Integer in `ORDER BY` is the position of selected field starting from 1")
+        .with_location(token.location)
+        .as_boxed()),
+    }
+}
+
+/// In aggregated query each group is merged into one row before ordering, so the ordering field must be
+/// a group key, a selected value or an aggregation, unless any value mode is enabled
+fn check_order_by_argument_after_aggregation(
+    context: &ParserContext,
+    argument: &dyn Expression,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let is_aggregated_query = context.has_group_by_statement || context.is_single_value_query;
+    if !is_aggregated_query || context.options.any_value_mode {
+        return Ok(());
+    }
+
+    // `FIRST` and `LAST` use the `ORDER BY` fields to order rows inside each group
+    let has_order_sensitive_aggregation =
+        context
+            .aggregations
+            .values()
+            .any(|aggregation| match aggregation {
+                AggregateValue::Function(call) => {
+                    call.order_by.is_none() && is_order_sensitive_aggregation(&call.function_name)
+                }
+                AggregateValue::Expression(_) => false,
+            });
+
+    if has_order_sensitive_aggregation {
+        return Ok(());
+    }
+
+    if let Some(symbol) = argument.as_any().downcast_ref::<SymbolExpression>() {
+        let name = &symbol.value;
+        let is_available = context.group_by_fields.contains(name)
+            || context.selected_fields.contains(name)
+            || context.selected_titles.contains(name)
+            || context.aggregations.contains_key(name);
+
+        if !is_available {
+            return Err(Diagnostic::error(&format!(
+                "Field `{}` can't be used in `ORDER BY` of aggregated query",
+                name
+            ))
+            .add_help("Try to add the field to `GROUP BY` or order by an aggregation of it")
+            .add_note("Aggregated query is ordered after merging each group into one row")
+            .add_note("Enable any value mode to order by the value of any row in the group")
+            .with_location(location)
+            .as_boxed());
+        }
+    }
+
+    Ok(())
+}
+
+/// Row numbers are assigned after sorting the rows, so they can't be used to sort them
+fn check_order_by_argument_is_not_row_number(
+    context: &ParserContext,
+    argument: &dyn Expression,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let Some(symbol) = argument.as_any().downcast_ref::<SymbolExpression>() else {
+        return Ok(());
+    };
+
+    if context.row_number_titles.contains(&symbol.value) {
+        return Err(
+            Diagnostic::error("`ROW_NUMBER` column can't be used in `ORDER BY`")
+                .add_note(
+                    "Row numbers are assigned after `ORDER BY` and before `OFFSET` and `LIMIT`",
+                )
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
+
+    Ok(())
+}
+
 fn parse_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    // Operators of the expression count toward the depth until the expression ends
+    let expression_depth = context.expression_depth;
     let aggregations_count_before = context.aggregations.len();
-    let expression = parse_assignment_expression(context, env, tokens, position)?;
+    let expression = parse_assignment_expression(context, env, tokens, position);
+    context.expression_depth = expression_depth;
+    let expression = expression?;
     let has_aggregations = context.aggregations.len() != aggregations_count_before;
 
     if has_aggregations {
@@ -748,7 +2368,29 @@ fn parse_expression(
         return Ok(Box::new(SymbolExpression { value: column_name }));
     }
 
-    Ok(expression)
+    Ok(reuse_selected_expression(context, expression))
+}
+
+/// Replace the expression with its selected column if the same expression is already selected,
+/// for example `SELECT LOWER(name) FROM commits ORDER BY LOWER(name)` evaluates `LOWER(name)` once per row
+fn reuse_selected_expression(
+    context: &ParserContext,
+    expression: Box<dyn Expression>,
+) -> Box<dyn Expression> {
+    if context.selected_expressions.is_empty() || expression.kind() == ExpressionKind::Symbol {
+        return expression;
+    }
+
+    let title = expression
+        .fingerprint()
+        .and_then(|fingerprint| context.selected_expressions.get(&fingerprint));
+
+    match title {
+        Some(title) => Box::new(SymbolExpression {
+            value: title.to_string(),
+        }),
+        None => expression,
+    }
 }
 
 fn parse_assignment_expression(
@@ -758,12 +2400,12 @@ fn parse_assignment_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_is_null_expression(context, env, tokens, position)?;
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::ColonEqual {
+    if tokens.is_kind(*position, TokenKind::ColonEqual) {
         if expression.kind() != ExpressionKind::GlobalVariable {
             return Err(Diagnostic::error(
                 "Assignment expressions expect global variable name before `:=`",
             )
-            .with_location(tokens[*position].location)
+            .with_location(get_safe_location(tokens, *position))
             .as_boxed());
         }
 
@@ -795,22 +2437,21 @@ fn parse_is_null_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_in_expression(context, env, tokens, position)?;
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::Is {
-        let is_location = tokens[*position].location;
+    if tokens.is_kind(*position, TokenKind::Is) {
+        let is_location = get_safe_location(tokens, *position);
 
         // Consume `IS` keyword
         *position += 1;
 
-        let has_not_keyword =
-            if *position < tokens.len() && tokens[*position].kind == TokenKind::Not {
-                // Consume `NOT` keyword
-                *position += 1;
-                true
-            } else {
-                false
-            };
+        let has_not_keyword = if tokens.is_kind(*position, TokenKind::Not) {
+            // Consume `NOT` keyword
+            *position += 1;
+            true
+        } else {
+            false
+        };
 
-        if *position < tokens.len() && tokens[*position].kind == TokenKind::Null {
+        if tokens.is_kind(*position, TokenKind::Null) {
             // Consume `Null` keyword
             *position += 1;
 
@@ -835,23 +2476,57 @@ fn parse_in_expression(
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_between_expression(context, env, tokens, position)?;
+    let expression = parse_logical_or_expression(context, env, tokens, position)?;
 
     // Consume `NOT` keyword if IN Expression prefixed with `NOT` for example `expr NOT IN (...values)`
-    let has_not_keyword = if *position < tokens.len() && tokens[*position].kind == TokenKind::Not {
+    let has_not_keyword = if tokens.is_kind(*position, TokenKind::Not) {
         *position += 1;
         true
     } else {
         false
     };
 
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::In {
-        let in_location = tokens[*position].location;
+    if tokens.is_kind(*position, TokenKind::In) {
+        let in_location = get_safe_location(tokens, *position);
 
         // Consume `IN` keyword
         *position += 1;
 
-        if consume_kind(tokens, *position, TokenKind::LeftParen).is_err() {
+        // Values from table column for example `expr IN table.column`
+        let is_table_column = consume_kind(tokens, *position, TokenKind::Symbol).is_ok()
+            && consume_kind(tokens, *position + 1, TokenKind::Dot).is_ok()
+            && consume_kind(tokens, *position + 2, TokenKind::Symbol).is_ok();
+
+        // Values from subquery for example `expr IN (SELECT ...)`
+        let is_subquery = consume_kind(tokens, *position, TokenKind::LeftParen).is_ok()
+            && consume_kind(tokens, *position + 1, TokenKind::Select).is_ok();
+
+        if is_table_column || is_subquery {
+            let (subquery, values_type) = if is_table_column {
+                parse_in_table_column(tokens, position)?
+            } else {
+                parse_in_subquery(context, env, tokens, position)?
+            };
+
+            let argument_type = expression.expr_type(env);
+            if !values_type.is_any() && !argument_type.is_null() && argument_type != values_type {
+                return Err(Diagnostic::error(&format!(
+                    "Argument and values of In Expression must have the same type, expected `{}`, got `{}`",
+                    values_type, argument_type
+                ))
+                .with_location(in_location)
+                .as_boxed());
+            }
+
+            context.in_subqueries.push(subquery);
+            return Ok(Box::new(InSubqueryExpression {
+                argument: expression,
+                subquery_index: context.in_subqueries.len() - 1,
+                has_not_keyword,
+            }));
+        }
+
+        if consume_kind(tokens, *position, TokenKind::LeftParen).is_err() {
             return Err(
                 Diagnostic::error("Expects values between `(` and `)` after `IN` keyword")
                     .with_location(in_location)
@@ -861,14 +2536,25 @@ fn parse_in_expression(
 
         let values = parse_arguments_expressions(context, env, tokens, position)?;
 
-        // Optimize the Expression if the number of values in the list is 0
         if values.is_empty() {
+            // ANSI SQL requires at least one value in the `IN` list
+            if context.options.ansi_mode {
+                return Err(Diagnostic::error(
+                    "Expects at least one value between `(` and `)` in ANSI mode",
+                )
+                .add_help("Try to add one or more values to the `IN` list")
+                .with_location(in_location)
+                .as_boxed());
+            }
+
+            // Optimize the Expression if the number of values in the list is 0, nothing can be
+            // in the empty list even NULL so the result is constant
             return Ok(Box::new(BooleanExpression {
                 is_true: has_not_keyword,
             }));
         }
 
-        let values_type_result = check_all_values_are_same_type(env, &values);
+        let values_type_result = check_all_values_are_same_type_or_null(env, &values);
         if values_type_result.is_none() {
             return Err(Diagnostic::error(
                 "Expects values between `(` and `)` to have the same type",
@@ -879,7 +2565,12 @@ fn parse_in_expression(
 
         // Check that argument and values has the same type
         let values_type = values_type_result.unwrap();
-        if values_type != DataType::Any && expression.expr_type(env) != values_type {
+        let argument_type = expression.expr_type(env);
+        if !values_type.is_any()
+            && !values_type.is_null()
+            && !argument_type.is_null()
+            && argument_type != values_type
+        {
             return Err(Diagnostic::error(
                 "Argument and Values of In Expression must have the same type",
             )
@@ -910,63 +2601,116 @@ fn parse_in_expression(
     Ok(expression)
 }
 
-fn parse_between_expression(
-    context: &mut ParserContext,
-    env: &mut Environment,
+/// Parse `table.column` after `IN` keyword as subquery that select the column from the table
+fn parse_in_table_column(
     tokens: &Vec<Token>,
     position: &mut usize,
-) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_logical_or_expression(context, env, tokens, position)?;
-
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::Between {
-        let between_location = tokens[*position].location;
-
-        // Consume `BETWEEN` keyword
-        *position += 1;
-
-        if *position >= tokens.len() {
+) -> Result<(GQLQuery, DataType), Box<Diagnostic>> {
+    let location = get_safe_location(tokens, *position);
+    let (table_name, column_name) = match (
+        consume_kind(tokens, *position, TokenKind::Symbol),
+        consume_kind(tokens, *position + 2, TokenKind::Symbol),
+    ) {
+        (Ok(table_token), Ok(column_token)) => {
+            (table_token.identifier(), column_token.identifier())
+        }
+        _ => {
             return Err(
-                Diagnostic::error("`BETWEEN` keyword expects two range after it")
-                    .with_location(between_location)
+                Diagnostic::error("Expect `table.column` after `IN` keyword")
+                    .with_location(location)
                     .as_boxed(),
-            );
+            )
         }
+    };
 
-        let argument_type = expression.expr_type(env);
-        let range_start = parse_logical_or_expression(context, env, tokens, position)?;
-
-        if *position >= tokens.len() || tokens[*position].kind != TokenKind::DotDot {
-            return Err(Diagnostic::error("Expect `..` after `BETWEEN` range start")
-                .with_location(between_location)
-                .as_boxed());
+    let subquery = match QueryBuilder::select([&column_name])
+        .from(&table_name)
+        .build()
+    {
+        Ok(Query::Select(subquery)) => subquery,
+        Ok(
+            Query::GlobalVariableDeclaration(_)
+            | Query::ShowVariables
+            | Query::Explain(_)
+            | Query::CreateTempTable(_)
+            | Query::Insert(_),
+        ) => unreachable!(),
+        Err(message) => {
+            return Err(Diagnostic::error(&message)
+                .add_help("Values after `IN` can be a table column like `table.column`")
+                .with_location(location)
+                .as_boxed())
         }
+    };
 
-        // Consume `..` token
-        *position += 1;
+    // Consume table name, `.` and column name
+    *position += 3;
 
-        let range_end = parse_logical_or_expression(context, env, tokens, position)?;
+    let column_type = TABLES_FIELDS_TYPES
+        .get(column_name.to_lowercase().as_str())
+        .cloned()
+        .unwrap_or(DataType::Any);
+    Ok((subquery, column_type))
+}
 
-        if argument_type != range_start.expr_type(env) || argument_type != range_end.expr_type(env)
-        {
-            return Err(Diagnostic::error(&format!(
-                "Expect `BETWEEN` argument, range start and end to has same type but got {}, {} and {}",
-                argument_type,
-                range_start.expr_type(env),
-                range_end.expr_type(env)
-            ))
-            .add_help("Try to make sure all of them has same type")
-            .with_location(between_location)
-            .as_boxed());
+/// Parse `(SELECT ...)` after `IN` keyword, the subquery must select exactly one column
+fn parse_in_subquery(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<(GQLQuery, DataType), Box<Diagnostic>> {
+    let location = get_safe_location(tokens, *position);
+
+    // Consume `(` token
+    *position += 1;
+
+    // Subquery has its own fields, so the outer query scope is restored after parsing it
+    let outer_scopes = env.scopes.clone();
+    let subquery = match parse_nested_select_query(context, env, tokens, position)? {
+        Query::Select(subquery) => subquery,
+        Query::GlobalVariableDeclaration(_)
+        | Query::ShowVariables
+        | Query::Explain(_)
+        | Query::CreateTempTable(_)
+        | Query::Insert(_) => {
+            unreachable!()
         }
+    };
 
-        return Ok(Box::new(BetweenExpression {
-            value: expression,
-            range_start,
-            range_end,
-        }));
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(Diagnostic::error("Expect `)` after subquery")
+            .with_location(get_safe_location(tokens, *position))
+            .as_boxed());
     }
 
-    Ok(expression)
+    // Consume `)` token
+    *position += 1;
+
+    let select_statement = subquery
+        .statements
+        .get("select")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<SelectStatement>()
+        .unwrap();
+
+    if select_statement.fields_names.len() != 1 {
+        return Err(Diagnostic::error(&format!(
+            "Subquery after `IN` must select one column but got `{}`",
+            select_statement.fields_names.len()
+        ))
+        .with_location(location)
+        .as_boxed());
+    }
+
+    let column_type = env
+        .resolve_type(&select_statement.fields_names[0])
+        .cloned()
+        .unwrap_or(DataType::Any);
+
+    env.scopes = outer_scopes;
+    Ok((subquery, column_type))
 }
 
 fn parse_logical_or_expression(
@@ -975,30 +2719,32 @@ fn parse_logical_or_expression(
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_logical_and_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    let expression = parse_logical_xor_expression(context, env, tokens, position);
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let mut lhs = expression.ok().unwrap();
-    while *position < tokens.len() && tokens[*position].kind == TokenKind::LogicalOr {
+    while tokens.is_kind(*position, TokenKind::LogicalOr) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
         *position += 1;
 
-        if lhs.expr_type(env) != DataType::Boolean {
+        if !is_boolean_or_null(&lhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position - 2].location,
+                get_safe_location(tokens, *position - 2),
                 DataType::Boolean,
                 lhs.expr_type(env),
             )
             .as_boxed());
         }
 
-        let rhs = parse_logical_and_expression(context, env, tokens, position)?;
-        if rhs.expr_type(env) != DataType::Boolean {
+        let rhs = parse_logical_xor_expression(context, env, tokens, position)?;
+        if !is_boolean_or_null(&rhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position].location,
+                get_safe_location(tokens, *position),
                 DataType::Boolean,
-                lhs.expr_type(env),
+                rhs.expr_type(env),
             )
             .as_boxed());
         }
@@ -1013,43 +2759,43 @@ fn parse_logical_or_expression(
     Ok(lhs)
 }
 
-fn parse_logical_and_expression(
+fn parse_logical_xor_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_bitwise_or_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    let expression = parse_logical_and_expression(context, env, tokens, position);
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let mut lhs = expression.ok().unwrap();
-    while *position < tokens.len() && tokens[*position].kind == TokenKind::LogicalAnd {
+    while tokens.is_kind(*position, TokenKind::LogicalXor) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
         *position += 1;
 
-        if lhs.expr_type(env) != DataType::Boolean {
+        if !is_boolean_or_null(&lhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position - 2].location,
+                get_safe_location(tokens, *position - 2),
                 DataType::Boolean,
                 lhs.expr_type(env),
-            )
-            .as_boxed());
+            ));
         }
 
-        let rhs = parse_bitwise_or_expression(context, env, tokens, position)?;
-        if rhs.expr_type(env) != DataType::Boolean {
+        let rhs = parse_logical_and_expression(context, env, tokens, position)?;
+        if !is_boolean_or_null(&rhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position].location,
+                get_safe_location(tokens, *position),
                 DataType::Boolean,
-                lhs.expr_type(env),
-            )
-            .as_boxed());
+                rhs.expr_type(env),
+            ));
         }
 
         lhs = Box::new(LogicalExpression {
             left: lhs,
-            operator: LogicalOperator::And,
+            operator: LogicalOperator::Xor,
             right: rhs,
         });
     }
@@ -1057,132 +2803,166 @@ fn parse_logical_and_expression(
     Ok(lhs)
 }
 
-fn parse_bitwise_or_expression(
+fn parse_logical_and_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_logical_xor_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    let expression = parse_logical_not_expression(context, env, tokens, position);
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
-    let lhs = expression.ok().unwrap();
-    if tokens[*position].kind == TokenKind::BitwiseOr {
+    let mut lhs = expression.ok().unwrap();
+    while tokens.is_kind(*position, TokenKind::LogicalAnd) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
         *position += 1;
 
-        if lhs.expr_type(env) != DataType::Boolean {
+        if !is_boolean_or_null(&lhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position - 2].location,
+                get_safe_location(tokens, *position - 2),
                 DataType::Boolean,
                 lhs.expr_type(env),
             )
             .as_boxed());
         }
 
-        let rhs = parse_logical_xor_expression(context, env, tokens, position)?;
-        if rhs.expr_type(env) != DataType::Boolean {
+        let rhs = parse_logical_not_expression(context, env, tokens, position)?;
+        if !is_boolean_or_null(&rhs.expr_type(env)) {
             return Err(type_mismatch_error(
-                tokens[*position].location,
+                get_safe_location(tokens, *position),
                 DataType::Boolean,
-                lhs.expr_type(env),
+                rhs.expr_type(env),
             )
             .as_boxed());
         }
 
-        return Ok(Box::new(BitwiseExpression {
+        lhs = Box::new(LogicalExpression {
             left: lhs,
-            operator: BitwiseOperator::Or,
+            operator: LogicalOperator::And,
             right: rhs,
-        }));
+        });
     }
 
     Ok(lhs)
 }
 
-fn parse_logical_xor_expression(
+/// `NOT condition` negates the boolean condition like `!`, but it has lower precedence than
+/// the comparisons so `NOT title = "x"` is parsed as `!(title = "x")`
+fn parse_logical_not_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_bitwise_and_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
-        return expression;
+    if !tokens.is_kind(*position, TokenKind::Not) {
+        return parse_between_expression(context, env, tokens, position);
     }
 
-    let mut lhs = expression.ok().unwrap();
-    while *position < tokens.len() && tokens[*position].kind == TokenKind::LogicalXor {
-        *position += 1;
-
-        if lhs.expr_type(env) != DataType::Boolean {
-            return Err(type_mismatch_error(
-                tokens[*position - 2].location,
-                DataType::Boolean,
-                lhs.expr_type(env),
-            ));
-        }
+    check_expression_depth(context, tokens, *position)?;
 
-        let rhs = parse_bitwise_and_expression(context, env, tokens, position)?;
-        if rhs.expr_type(env) != DataType::Boolean {
-            return Err(type_mismatch_error(
-                tokens[*position].location,
-                DataType::Boolean,
-                lhs.expr_type(env),
-            ));
-        }
+    // Consume `NOT` keyword
+    *position += 1;
 
-        lhs = Box::new(LogicalExpression {
-            left: lhs,
-            operator: LogicalOperator::Xor,
-            right: rhs,
-        });
+    context.expression_depth += 1;
+    let rhs = parse_logical_not_expression(context, env, tokens, position);
+    context.expression_depth -= 1;
+    let rhs = rhs?;
+    let rhs_type = rhs.expr_type(env);
+    if !is_boolean_or_null(&rhs_type) {
+        return Err(type_mismatch_error(
+            get_safe_location(tokens, *position - 1),
+            DataType::Boolean,
+            rhs_type,
+        ));
     }
 
-    Ok(lhs)
+    Ok(Box::new(PrefixUnary {
+        right: rhs,
+        op: PrefixUnaryOperator::Bang,
+    }))
 }
 
-fn parse_bitwise_and_expression(
+fn parse_between_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_equality_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
-        return expression;
+    let expression = parse_equality_expression(context, env, tokens, position)?;
+
+    // Consume `NOT` keyword if Between Expression prefixed with `NOT` for example `expr NOT BETWEEN a AND b`
+    let has_not_keyword = tokens.is_kind(*position, TokenKind::Not)
+        && tokens.is_kind(*position + 1, TokenKind::Between);
+    if has_not_keyword {
+        *position += 1;
     }
 
-    let mut lhs = expression.ok().unwrap();
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::BitwiseAnd {
+    if tokens.is_kind(*position, TokenKind::Between) {
+        let between_location = get_safe_location(tokens, *position);
+
+        // Consume `BETWEEN` keyword
         *position += 1;
 
-        if lhs.expr_type(env) != DataType::Boolean {
-            return Err(type_mismatch_error(
-                tokens[*position - 2].location,
-                DataType::Boolean,
-                lhs.expr_type(env),
-            ));
+        // Consume `SYMMETRIC` keyword if exists, for example `expr BETWEEN SYMMETRIC a AND b`
+        let is_symmetric = tokens.is_kind(*position, TokenKind::Symmetric);
+        if is_symmetric {
+            *position += 1;
         }
 
-        let rhs = parse_equality_expression(context, env, tokens, position)?;
-        if rhs.expr_type(env) != DataType::Boolean {
-            return Err(type_mismatch_error(
-                tokens[*position].location,
-                DataType::Boolean,
-                lhs.expr_type(env),
-            ));
+        if tokens.is_at_end(*position) {
+            return Err(
+                Diagnostic::error("`BETWEEN` keyword expects two range after it")
+                    .with_location(between_location)
+                    .as_boxed(),
+            );
         }
 
-        lhs = Box::new(BitwiseExpression {
-            left: lhs,
-            operator: BitwiseOperator::And,
-            right: rhs,
-        });
+        let argument_type = expression.expr_type(env);
+        let range_start = parse_equality_expression(context, env, tokens, position)?;
+
+        // Range start and end can be separated by `AND` keyword or by `..` token
+        if !tokens.is_kind(*position, TokenKind::LogicalAnd)
+            && !tokens.is_kind(*position, TokenKind::DotDot)
+        {
+            return Err(
+                Diagnostic::error("Expect `AND` or `..` after `BETWEEN` range start")
+                    .add_help("Try to use `BETWEEN start AND end` or `BETWEEN start .. end`")
+                    .with_location(between_location)
+                    .as_boxed(),
+            );
+        }
+
+        // Consume `AND` or `..` token
+        *position += 1;
+
+        let range_end = parse_equality_expression(context, env, tokens, position)?;
+
+        if argument_type != range_start.expr_type(env) || argument_type != range_end.expr_type(env)
+        {
+            return Err(Diagnostic::error(&format!(
+                "Expect `BETWEEN` argument, range start and end to has same type but got {}, {} and {}",
+                argument_type,
+                range_start.expr_type(env),
+                range_end.expr_type(env)
+            ))
+            .add_help("Try to make sure all of them has same type")
+            .with_location(between_location)
+            .as_boxed());
+        }
+
+        return Ok(Box::new(BetweenExpression {
+            value: expression,
+            range_start,
+            range_end,
+            is_symmetric,
+            has_not_keyword,
+        }));
     }
 
-    Ok(lhs)
+    Ok(expression)
 }
 
 fn parse_equality_expression(
@@ -1192,13 +2972,13 @@ fn parse_equality_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_comparison_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
-        return expression;
-    }
+    let operator = match tokens.peek(*position) {
+        Some(operator) if expression.is_ok() => operator,
+        _ => return expression,
+    };
 
     let mut lhs = expression.ok().unwrap();
 
-    let operator = &tokens[*position];
     if operator.kind == TokenKind::Equal || operator.kind == TokenKind::BangEqual {
         *position += 1;
         let comparison_operator = if operator.kind == TokenKind::Equal {
@@ -1209,7 +2989,11 @@ fn parse_equality_expression(
 
         let mut rhs = parse_comparison_expression(context, env, tokens, position)?;
 
+        // Comparing with NULL is valid for any type and the result is NULL
+        let has_null_side = lhs.expr_type(env).is_null() || rhs.expr_type(env).is_null();
+
         match are_types_equals(env, &lhs, &rhs) {
+            _ if has_null_side => {}
             TypeCheckResult::Equals => {}
             TypeCheckResult::RightSideCasted(expr) => rhs = expr,
             TypeCheckResult::LeftSideCasted(expr) => lhs = expr,
@@ -1222,14 +3006,6 @@ fn parse_equality_expression(
                 ))
                 .with_location(get_safe_location(tokens, *position - 2));
 
-                // Provides help messages if use compare null to non null value
-                if lhs_type.is_null() || rhs_type.is_null() {
-                    return Err(diagnostic
-                        .add_help("Try to use `IS NULL expr` expression")
-                        .add_help("Try to use `ISNULL(expr)` function")
-                        .as_boxed());
-                }
-
                 return Err(diagnostic.as_boxed());
             }
             TypeCheckResult::Error(diagnostic) => {
@@ -1255,14 +3031,13 @@ fn parse_comparison_expression(
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let expression = parse_bitwise_shift_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    let expression = parse_bitwise_or_expression(context, env, tokens, position);
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let mut lhs = expression.ok().unwrap();
-    if is_comparison_operator(&tokens[*position]) {
-        let operator = &tokens[*position];
+    if let Some(operator) = tokens.peek_if(*position, is_comparison_operator) {
         *position += 1;
         let comparison_operator = match operator.kind {
             TokenKind::Greater => ComparisonOperator::Greater,
@@ -1272,9 +3047,13 @@ fn parse_comparison_expression(
             _ => ComparisonOperator::NullSafeEqual,
         };
 
-        let mut rhs = parse_bitwise_shift_expression(context, env, tokens, position)?;
+        let mut rhs = parse_bitwise_or_expression(context, env, tokens, position)?;
+
+        // Comparing with NULL is valid for any type and the result is NULL
+        let has_null_side = lhs.expr_type(env).is_null() || rhs.expr_type(env).is_null();
 
         match are_types_equals(env, &lhs, &rhs) {
+            _ if has_null_side => {}
             TypeCheckResult::Equals => {}
             TypeCheckResult::RightSideCasted(expr) => rhs = expr,
             TypeCheckResult::LeftSideCasted(expr) => lhs = expr,
@@ -1287,14 +3066,6 @@ fn parse_comparison_expression(
                 ))
                 .with_location(get_safe_location(tokens, *position - 2));
 
-                // Provides help messages if use compare null to non null value
-                if lhs_type.is_null() || rhs_type.is_null() {
-                    return Err(diagnostic
-                        .add_help("Try to use `IS NULL expr` expression")
-                        .add_help("Try to use `ISNULL(expr)` function")
-                        .as_boxed());
-                }
-
                 return Err(diagnostic.as_boxed());
             }
             TypeCheckResult::Error(diagnostic) => {
@@ -1304,6 +3075,15 @@ fn parse_comparison_expression(
             }
         };
 
+        // Report error if user chain comparisons for example `a < b < c`
+        if tokens.is_token(*position, is_comparison_operator) {
+            return Err(Diagnostic::error("Comparison operators can't be chained")
+                .add_help("Try to split it into two comparisons, for example `a < b AND b < c`")
+                .add_help("Try to use `BETWEEN` expression to check if value is in a range")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed());
+        }
+
         return Ok(Box::new(ComparisonExpression {
             left: lhs,
             operator: comparison_operator,
@@ -1314,39 +3094,49 @@ fn parse_comparison_expression(
     Ok(lhs)
 }
 
-fn parse_bitwise_shift_expression(
+fn parse_bitwise_or_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let mut lhs = parse_term_expression(context, env, tokens, position)?;
+    let mut lhs = parse_bitwise_and_expression(context, env, tokens, position)?;
 
-    while *position < tokens.len() && is_bitwise_shift_operator(&tokens[*position]) {
-        let operator = &tokens[*position];
+    while tokens.is_kind(*position, TokenKind::BitwiseOr) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
+        let operator_location = get_safe_location(tokens, *position);
         *position += 1;
-        let bitwise_operator = if operator.kind == TokenKind::BitwiseRightShift {
-            BitwiseOperator::RightShift
-        } else {
-            BitwiseOperator::LeftShift
-        };
+        let rhs = parse_bitwise_and_expression(context, env, tokens, position)?;
+        check_bitwise_operands_types(env, &lhs, &rhs, operator_location)?;
+        lhs = Box::new(BitwiseExpression {
+            left: lhs,
+            operator: BitwiseOperator::Or,
+            right: rhs,
+        });
+    }
 
-        let rhs = parse_term_expression(context, env, tokens, position)?;
+    Ok(lhs)
+}
 
-        // Make sure right and left hand side types are numbers
-        if rhs.expr_type(env).is_int() && rhs.expr_type(env) != lhs.expr_type(env) {
-            return Err(Diagnostic::error(&format!(
-                "Bitwise operators require number types but got `{}` and `{}`",
-                lhs.expr_type(env),
-                rhs.expr_type(env)
-            ))
-            .with_location(get_safe_location(tokens, *position - 2))
-            .as_boxed());
-        }
+fn parse_bitwise_and_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let mut lhs = parse_bitwise_shift_expression(context, env, tokens, position)?;
 
+    while tokens.is_kind(*position, TokenKind::BitwiseAnd) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
+        let operator_location = get_safe_location(tokens, *position);
+        *position += 1;
+        let rhs = parse_bitwise_shift_expression(context, env, tokens, position)?;
+        check_bitwise_operands_types(env, &lhs, &rhs, operator_location)?;
         lhs = Box::new(BitwiseExpression {
             left: lhs,
-            operator: bitwise_operator,
+            operator: BitwiseOperator::And,
             right: rhs,
         });
     }
@@ -1354,18 +3144,63 @@ fn parse_bitwise_shift_expression(
     Ok(lhs)
 }
 
-fn parse_term_expression(
+fn parse_bitwise_shift_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let mut lhs = parse_factor_expression(context, env, tokens, position)?;
+    let mut lhs = parse_term_expression(context, env, tokens, position)?;
 
-    while *position < tokens.len() && is_term_operator(&tokens[*position]) {
-        let operator = &tokens[*position];
+    while let Some(operator) = tokens.peek_if(*position, is_bitwise_shift_operator) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
+        let operator_location = operator.location;
         *position += 1;
-        let math_operator = if operator.kind == TokenKind::Plus {
+        let bitwise_operator = if operator.kind == TokenKind::BitwiseRightShift {
+            BitwiseOperator::RightShift
+        } else {
+            BitwiseOperator::LeftShift
+        };
+
+        let rhs = parse_term_expression(context, env, tokens, position)?;
+        check_bitwise_operands_types(env, &lhs, &rhs, operator_location)?;
+
+        lhs = Box::new(BitwiseExpression {
+            left: lhs,
+            operator: bitwise_operator,
+            right: rhs,
+        });
+    }
+
+    Ok(lhs)
+}
+
+fn parse_term_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let mut lhs = parse_factor_expression(context, env, tokens, position)?;
+
+    while let Some(operator) = tokens.peek_if(*position, is_term_operator) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
+        *position += 1;
+
+        // In ANSI mode `||` concatenate the two sides as text
+        if operator.kind == TokenKind::Concat {
+            let rhs = parse_factor_expression(context, env, tokens, position)?;
+            lhs = Box::new(CallExpression {
+                function_name: "concat".to_string(),
+                arguments: vec![lhs, rhs],
+                is_aggregation: false,
+            });
+            continue;
+        }
+
+        let math_operator = if operator.kind == TokenKind::Plus {
             ArithmeticOperator::Plus
         } else {
             ArithmeticOperator::Minus
@@ -1376,8 +3211,8 @@ fn parse_term_expression(
         let lhs_type = lhs.expr_type(env);
         let rhs_type = rhs.expr_type(env);
 
-        // Make sure right and left hand side types are numbers
-        if lhs_type.is_number() && rhs_type.is_number() {
+        // Make sure right and left hand side types are numbers, NULL operand makes the result NULL
+        if is_number_or_null(&lhs_type) && is_number_or_null(&rhs_type) {
             lhs = Box::new(ArithmeticExpression {
                 left: lhs,
                 operator: math_operator,
@@ -1418,13 +3253,14 @@ fn parse_factor_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_like_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let mut lhs = expression.ok().unwrap();
-    while *position < tokens.len() && is_factor_operator(&tokens[*position]) {
-        let operator = &tokens[*position];
+    while let Some(operator) = tokens.peek_if(*position, is_factor_operator) {
+        check_expression_depth(context, tokens, *position)?;
+        context.expression_depth += 1;
         *position += 1;
 
         let factor_operator = match operator.kind {
@@ -1438,8 +3274,8 @@ fn parse_factor_expression(
         let lhs_type = lhs.expr_type(env);
         let rhs_type = rhs.expr_type(env);
 
-        // Make sure right and left hand side types are numbers
-        if lhs_type.is_number() && rhs_type.is_number() {
+        // Make sure right and left hand side types are numbers, NULL operand makes the result NULL
+        if is_number_or_null(&lhs_type) && is_number_or_null(&rhs_type) {
             lhs = Box::new(ArithmeticExpression {
                 left: lhs,
                 operator: factor_operator,
@@ -1466,16 +3302,16 @@ fn parse_like_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_glob_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let lhs = expression.ok().unwrap();
-    if tokens[*position].kind == TokenKind::Like {
-        let location = tokens[*position].location;
+    if tokens.is_kind(*position, TokenKind::Like) {
+        let location = get_safe_location(tokens, *position);
         *position += 1;
 
-        if !lhs.expr_type(env).is_text() {
+        if !is_text_or_null(&lhs.expr_type(env)) {
             return Err(Diagnostic::error(&format!(
                 "Expect `LIKE` left hand side to be `TEXT` but got {}",
                 lhs.expr_type(env)
@@ -1485,7 +3321,7 @@ fn parse_like_expression(
         }
 
         let pattern = parse_glob_expression(context, env, tokens, position)?;
-        if !pattern.expr_type(env).is_text() {
+        if !is_text_or_null(&pattern.expr_type(env)) {
             return Err(Diagnostic::error(&format!(
                 "Expect `LIKE` right hand side to be `TEXT` but got {}",
                 pattern.expr_type(env)
@@ -1510,16 +3346,16 @@ fn parse_glob_expression(
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
     let expression = parse_unary_expression(context, env, tokens, position);
-    if expression.is_err() || *position >= tokens.len() {
+    if expression.is_err() || tokens.is_at_end(*position) {
         return expression;
     }
 
     let lhs = expression.ok().unwrap();
-    if tokens[*position].kind == TokenKind::Glob {
-        let location = tokens[*position].location;
+    if tokens.is_kind(*position, TokenKind::Glob) {
+        let location = get_safe_location(tokens, *position);
         *position += 1;
 
-        if !lhs.expr_type(env).is_text() {
+        if !is_text_or_null(&lhs.expr_type(env)) {
             return Err(Diagnostic::error(&format!(
                 "Expect `GLOB` left hand side to be `TEXT` but got {}",
                 lhs.expr_type(env)
@@ -1529,7 +3365,7 @@ fn parse_glob_expression(
         }
 
         let pattern = parse_unary_expression(context, env, tokens, position)?;
-        if !pattern.expr_type(env).is_text() {
+        if !is_text_or_null(&pattern.expr_type(env)) {
             return Err(Diagnostic::error(&format!(
                 "Expect `GLOB` right hand side to be `TEXT` but got {}",
                 pattern.expr_type(env)
@@ -1547,14 +3383,49 @@ fn parse_glob_expression(
     Ok(lhs)
 }
 
+/// Every nested expression like group, function argument or unary operand is parsed through this function,
+/// so the expression depth is tracked here to report deep nesting before overflowing the stack
 fn parse_unary_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    if *position < tokens.len() && is_prefix_unary_operator(&tokens[*position]) {
-        let op = if tokens[*position].kind == TokenKind::Bang {
+    check_expression_depth(context, tokens, *position)?;
+    context.expression_depth += 1;
+    let expression = parse_prefix_unary_expression(context, env, tokens, position);
+    context.expression_depth -= 1;
+    expression
+}
+
+/// Returns an error if one more level of nesting exceeds the maximum expression depth, the operators of
+/// left associative chains like `1 + 1 + 1` are levels too because each one wraps the previous operands
+fn check_expression_depth(
+    context: &ParserContext,
+    tokens: &Vec<Token>,
+    position: usize,
+) -> Result<(), Box<Diagnostic>> {
+    if context.expression_depth >= context.options.max_expression_depth {
+        return Err(Diagnostic::error("Expression is nested too deeply")
+            .add_note(&format!(
+                "Maximum expression nesting depth is {}",
+                context.options.max_expression_depth
+            ))
+            .add_help("Try to simplify the expression or increase the maximum depth option")
+            .with_location(get_safe_location(tokens, position))
+            .as_boxed());
+    }
+    Ok(())
+}
+
+fn parse_prefix_unary_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    if tokens.is_token(*position, is_prefix_unary_operator) {
+        let op = if tokens.is_kind(*position, TokenKind::Bang) {
             PrefixUnaryOperator::Bang
         } else {
             PrefixUnaryOperator::Minus
@@ -1562,9 +3433,14 @@ fn parse_unary_expression(
 
         *position += 1;
 
+        // Fold minus with number literal into negative number so range checks see the true value
+        if op == PrefixUnaryOperator::Minus && is_number_literal(tokens, *position) {
+            return parse_number_literal(tokens, position, true);
+        }
+
         let rhs = parse_unary_expression(context, env, tokens, position)?;
         let rhs_type = rhs.expr_type(env);
-        if op == PrefixUnaryOperator::Bang && rhs_type != DataType::Boolean {
+        if op == PrefixUnaryOperator::Bang && !is_boolean_or_null(&rhs_type) {
             return Err(type_mismatch_error(
                 get_safe_location(tokens, *position - 1),
                 DataType::Boolean,
@@ -1572,7 +3448,7 @@ fn parse_unary_expression(
             ));
         }
 
-        if op == PrefixUnaryOperator::Minus && rhs_type != DataType::Integer {
+        if op == PrefixUnaryOperator::Minus && !is_number_or_null(&rhs_type) {
             return Err(type_mismatch_error(
                 get_safe_location(tokens, *position - 1),
                 DataType::Integer,
@@ -1592,8 +3468,14 @@ fn parse_function_call_expression(
     tokens: &Vec<Token>,
     position: &mut usize,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let hidden_selections_count = context.hidden_selections.len();
+    let column_references_count = context.column_references.len();
     let expression = parse_primary_expression(context, env, tokens, position)?;
-    if *position < tokens.len() && tokens[*position].kind == TokenKind::LeftParen {
+    if tokens.is_kind(*position, TokenKind::LeftParen) {
+        // Function name is not a field, so it's not selected as hidden field
+        context.hidden_selections.truncate(hidden_selections_count);
+        context.column_references.truncate(column_references_count);
+
         let symbol_expression = expression.as_any().downcast_ref::<SymbolExpression>();
         let function_name_location = get_safe_location(tokens, *position);
 
@@ -1606,47 +3488,129 @@ fn parse_function_call_expression(
 
         let function_name = &symbol_expression.unwrap().value;
 
+        // Check if this function is the commits revision range predicate
+        if function_name == "range" {
+            return parse_range_expression(context, env, tokens, position, function_name_location);
+        }
+
+        // Check if this function is the result rows numbering
+        if function_name == "row_number" {
+            return parse_row_number_expression(
+                context,
+                env,
+                tokens,
+                position,
+                function_name_location,
+            );
+        }
+
+        // Check if this function is the commits pathspec predicate
+        if function_name == "touches" {
+            return parse_touches_expression(
+                context,
+                env,
+                tokens,
+                position,
+                function_name_location,
+            );
+        }
+
         // Check if this function is a Standard library functions
-        if FUNCTIONS.contains_key(function_name.as_str()) {
+        if is_standard_function(function_name.as_str()) {
+            if NON_DETERMINISTIC_FUNCTIONS.contains(&function_name.as_str()) {
+                context.has_non_deterministic_function = true;
+            }
+
             let mut arguments = parse_arguments_expressions(context, env, tokens, position)?;
-            let prototype = PROTOTYPES.get(function_name.as_str()).unwrap();
-            let parameters = &prototype.parameters;
-            let return_type = prototype.result.clone();
+            let prototypes = PROTOTYPES.get(function_name.as_str()).unwrap();
+            let overloads: Vec<(&Vec<DataType>, &DataType)> = prototypes
+                .iter()
+                .map(|prototype| (&prototype.parameters, &prototype.result))
+                .collect();
 
-            check_function_call_arguments(
+            let overload_index = check_function_call_overloads(
                 env,
                 &mut arguments,
-                parameters,
-                function_name.to_string(),
+                &overloads,
+                function_name,
                 function_name_location,
             )?;
+            let return_type = prototypes[overload_index].result.clone();
 
-            // Register function name with return type
-            env.define(function_name.to_string(), return_type);
+            let generic_result_index = generic_result_argument_index(function_name);
+            if let Some(index) = generic_result_index {
+                check_generic_result_arguments(
+                    env,
+                    &arguments[index.min(arguments.len())..],
+                    function_name,
+                    function_name_location,
+                )?;
+            }
 
-            return Ok(Box::new(CallExpression {
+            let call = CallExpression {
                 function_name: function_name.to_string(),
                 arguments,
                 is_aggregation: false,
-            }));
+            };
+
+            // Register function name with return type
+            let return_type = if generic_result_index.is_some() {
+                call.expr_type(env)
+            } else {
+                return_type
+            };
+            env.define(function_name.to_string(), return_type);
+
+            return Ok(reuse_selected_expression(context, Box::new(call)));
         }
 
         // Check if this function is an Aggregation functions
-        if AGGREGATIONS.contains_key(function_name.as_str()) {
-            let mut arguments = parse_arguments_expressions(context, env, tokens, position)?;
-            let prototype = AGGREGATIONS_PROTOS.get(function_name.as_str()).unwrap();
-            let parameters = &vec![prototype.parameter.clone()];
-            let return_type = prototype.result.clone();
-
-            check_function_call_arguments(
+        if is_aggregation_function(env, function_name.as_str()) {
+            let mut call = AggregateCall::new(function_name.to_string(), String::new());
+            let mut arguments =
+                parse_aggregation_arguments(context, env, tokens, position, &mut call)?;
+            let prototypes = aggregation_prototypes(env, function_name.as_str());
+            let overloads: Vec<(&Vec<DataType>, &DataType)> = prototypes
+                .iter()
+                .map(|prototype| (&prototype.parameters, &prototype.result))
+                .collect();
+
+            let overload_index = check_function_call_overloads(
                 env,
                 &mut arguments,
-                parameters,
-                function_name.to_string(),
+                &overloads,
+                function_name,
                 function_name_location,
             )?;
+            let return_type = prototypes[overload_index].result.clone();
+
+            // The separator of `GROUP_CONCAT` and `STRING_AGG` can be passed as second argument
+            let has_separator_parameter =
+                call.function_name == "group_concat" || call.function_name == "string_agg";
+            if let Some(separator) = arguments.get(1).filter(|_| has_separator_parameter) {
+                if call.separator.is_some() {
+                    return Err(Diagnostic::error(
+                        "Separator can't be passed as argument and `SEPARATOR` in the same call",
+                    )
+                    .with_location(function_name_location)
+                    .as_boxed());
+                }
+
+                match separator.as_any().downcast_ref::<StringExpression>() {
+                    Some(separator) => call.separator = Some(separator.value.to_string()),
+                    None => {
+                        return Err(Diagnostic::error(&format!(
+                            "Aggregation function `{}` separator must be a string literal",
+                            function_name
+                        ))
+                        .add_help("Try to pass the separator as string, for example `\", \"`")
+                        .with_location(function_name_location)
+                        .as_boxed());
+                    }
+                }
+            }
 
-            let argument_result = get_expression_name(&arguments[0]);
+            let argument_result = arguments.first().ok_or(()).and_then(get_expression_name);
             if argument_result.is_err() {
                 return Err(Diagnostic::error("Invalid Aggregation function argument")
                     .add_help("Try to use field name as Aggregation function argument")
@@ -1655,7 +3619,7 @@ fn parse_function_call_expression(
                     .as_boxed());
             }
 
-            let argument = argument_result.ok().unwrap();
+            call.argument = argument_result.ok().unwrap();
             let column_name = context.generate_column_name();
 
             context.hidden_selections.push(column_name.to_string());
@@ -1663,193 +3627,610 @@ fn parse_function_call_expression(
             // Register aggregation generated name with return type
             env.define(column_name.to_string(), return_type);
 
-            context.aggregations.insert(
-                column_name.clone(),
-                AggregateValue::Function(function_name.to_string(), argument),
-            );
+            context
+                .aggregations
+                .insert(column_name.clone(), AggregateValue::Function(call));
 
             return Ok(Box::new(SymbolExpression { value: column_name }));
         }
 
         // Report that this function name is not standard or aggregation
-        return Err(Diagnostic::error("No such function name")
+        let diagnostic = Diagnostic::error("No such function name")
             .add_help(&format!(
                 "Function `{}` is not an Aggregation or Standard library function name",
                 function_name,
             ))
-            .with_location(function_name_location)
-            .as_boxed());
+            .with_location(function_name_location);
+        let functions_names = standard_functions_names().chain(AGGREGATIONS.keys().copied());
+        return Err(add_did_you_mean_help(diagnostic, function_name, functions_names).as_boxed());
     }
     Ok(expression)
 }
 
-fn parse_arguments_expressions(
+/// `ROW_NUMBER()` is parsed as a generated hidden field that the engine fills with the position of
+/// each row in the result after `ORDER BY` and before `OFFSET` and `LIMIT`
+fn parse_row_number_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
-) -> Result<Vec<Box<dyn Expression>>, Box<Diagnostic>> {
-    let mut arguments: Vec<Box<dyn Expression>> = vec![];
-    if consume_kind(tokens, *position, TokenKind::LeftParen).is_ok() {
-        *position += 1;
+    location: Location,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    if !context.is_select_item {
+        return Err(
+            Diagnostic::error("`ROW_NUMBER` can only be used in `SELECT` statement")
+                .add_note(
+                    "Row numbers are assigned after `ORDER BY` and before `OFFSET` and `LIMIT`",
+                )
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
 
-        while tokens[*position].kind != TokenKind::RightParen {
-            let argument = parse_expression(context, env, tokens, position)?;
-            let argument_literal = get_expression_name(&argument);
-            if argument_literal.is_ok() {
-                let literal = argument_literal.ok().unwrap();
-                context.hidden_selections.push(literal);
-            }
+    let arguments = parse_arguments_expressions(context, env, tokens, position)?;
+    if !arguments.is_empty() {
+        return Err(Diagnostic::error("`ROW_NUMBER` doesn't accept arguments")
+            .add_help("Try to use `ROW_NUMBER()` and `ORDER BY` to select the rows order")
+            .with_location(location)
+            .as_boxed());
+    }
 
-            arguments.push(argument);
+    let column_name = context.generate_column_name();
+    context.hidden_selections.push(column_name.to_string());
+    context.row_number_titles.push(column_name.to_string());
 
-            if tokens[*position].kind == TokenKind::Comma {
-                *position += 1;
-            } else {
-                break;
-            }
-        }
+    env.define(column_name.to_string(), DataType::Integer);
+    Ok(Box::new(SymbolExpression { value: column_name }))
+}
 
-        if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
-            return Err(
-                Diagnostic::error("Expect `)` after function call arguments")
-                    .add_help("Try to add ')' at the end of function call, after arguments")
-                    .with_location(get_safe_location(tokens, *position))
-                    .as_boxed(),
-            );
-        }
+/// `TOUCHES(pathspec)` is evaluated while walking the commits, so it's parsed as a generated hidden field
+/// and the engine only diff the commits trees when it's used
+fn parse_touches_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    location: Location,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    if context.table_name != "commits" {
+        return Err(
+            Diagnostic::error("`TOUCHES` can only be used with `commits` table")
+                .add_help(
+                    "Try to select from `commits` table or use `path` field of `diff_files` table",
+                )
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
 
-        *position += 1;
+    let arguments = parse_arguments_expressions(context, env, tokens, position)?;
+    let pathspec = match arguments.first() {
+        Some(argument) if arguments.len() == 1 => argument
+            .as_any()
+            .downcast_ref::<StringExpression>()
+            .map(|string| string.value.to_string()),
+        _ => None,
+    };
+
+    if pathspec.is_none() {
+        return Err(
+            Diagnostic::error("`TOUCHES` expects one string pathspec argument")
+                .add_help(
+                    "Try to pass the pathspec as string, for example `TOUCHES(\"src/**/*.rs\")`",
+                )
+                .with_location(location)
+                .as_boxed(),
+        );
     }
-    Ok(arguments)
+
+    let field_name = format!("{}{}", TOUCHES_FIELD_PREFIX, pathspec.unwrap());
+    if !context.hidden_selections.contains(&field_name) {
+        context.hidden_selections.push(field_name.to_string());
+    }
+
+    env.define(field_name.to_string(), DataType::Boolean);
+    Ok(Box::new(SymbolExpression { value: field_name }))
 }
 
-fn parse_primary_expression(
+/// Parse the arguments of table-valued function in `FROM` and returns the name of the table of its rows,
+/// the argument is passed to the engine as a generated hidden field like `TOUCHES` predicate, except
+/// `EXTERNAL(path)` that loads the file rows as read-only table
+fn parse_table_function(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
-) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    if *position >= tokens.len() {
-        return Err(un_expected_expression_error(tokens, position));
+    function_name: &str,
+) -> Result<String, Box<Diagnostic>> {
+    let location = get_safe_location(tokens, *position - 1);
+    let function_name = function_name.to_lowercase();
+    if function_name == "external" {
+        if !context.options.allow_external_files {
+            return Err(Diagnostic::error("`EXTERNAL` tables are disabled")
+                .add_note("Loading local files is disabled for the queries received by the server")
+                .with_location(location)
+                .as_boxed());
+        }
+
+        let path = parse_table_function_argument(
+            context,
+            env,
+            tokens,
+            position,
+            &function_name,
+            "authors.csv",
+            location,
+        )?;
+
+        let (fields, object) = load_external_table(&path).map_err(|error| {
+            Diagnostic::error(&format!("Can't load external table `{}`", path))
+                .add_note(&error)
+                .add_help("External table can be CSV, TSV, JSON array or JSON lines file")
+                .with_location(location)
+                .as_boxed()
+        })?;
+
+        let table_name = format!("{}{}", EXTERNAL_TABLE_PREFIX, path);
+        env.define_external_table(&table_name, fields, object);
+        return Ok(table_name);
     }
 
-    match tokens[*position].kind {
-        TokenKind::String => {
-            *position += 1;
-            Ok(Box::new(StringExpression {
-                value: tokens[*position - 1].literal.to_string(),
-                value_type: StringValueType::Text,
-            }))
-        }
-        TokenKind::Symbol => {
-            let value = tokens[*position].literal.to_string();
-            *position += 1;
-            if !context.selected_fields.contains(&value) {
-                context.hidden_selections.push(value.to_string());
-            }
-            Ok(Box::new(SymbolExpression { value }))
-        }
-        TokenKind::GlobalVariable => {
-            let name = tokens[*position].literal.to_string();
-            *position += 1;
-            Ok(Box::new(GlobalVariableExpression { name }))
-        }
-        TokenKind::Integer => {
-            if let Ok(integer) = tokens[*position].literal.parse::<i64>() {
-                *position += 1;
-                let value = Value::Integer(integer);
-                return Ok(Box::new(NumberExpression { value }));
-            }
+    let Some((table_name, field_prefix)) = TABLE_FUNCTIONS.get(function_name.as_str()) else {
+        let diagnostic = Diagnostic::error(&format!(
+            "Unresolved table-valued function `{}`",
+            function_name
+        ))
+        .add_help("Check the documentations to see available table-valued functions")
+        .with_location(location);
+        let names = TABLE_FUNCTIONS
+            .keys()
+            .copied()
+            .chain(std::iter::once("external"));
+        return Err(add_did_you_mean_help(diagnostic, &function_name, names).as_boxed());
+    };
 
-            Err(Diagnostic::error("Too big Integer value")
-                .add_help("Try to use smaller value")
-                .add_note(&format!(
-                    "Integer value must be between {} and {}",
-                    i64::MIN,
-                    i64::MAX
-                ))
-                .with_location(tokens[*position].location)
-                .as_boxed())
-        }
-        TokenKind::Float => {
-            if let Ok(float) = tokens[*position].literal.parse::<f64>() {
-                *position += 1;
-                let value = Value::Float(float);
-                return Ok(Box::new(NumberExpression { value }));
-            }
+    let argument = parse_table_function_argument(
+        context,
+        env,
+        tokens,
+        position,
+        &function_name,
+        "src/",
+        location,
+    )?;
 
-            Err(Diagnostic::error("Too big Float value")
-                .add_help("Try to use smaller value")
-                .add_note(&format!(
-                    "Float value must be between {} and {}",
-                    f64::MIN,
-                    f64::MAX
-                ))
-                .with_location(tokens[*position].location)
-                .as_boxed())
-        }
-        TokenKind::True => {
-            *position += 1;
-            Ok(Box::new(BooleanExpression { is_true: true }))
-        }
-        TokenKind::False => {
-            *position += 1;
-            Ok(Box::new(BooleanExpression { is_true: false }))
-        }
-        TokenKind::Null => {
-            *position += 1;
-            Ok(Box::new(NullExpression {}))
-        }
-        TokenKind::LeftParen => parse_group_expression(context, env, tokens, position),
-        TokenKind::Case => parse_case_expression(context, env, tokens, position),
-        _ => Err(un_expected_expression_error(tokens, position)),
+    let field_name = format!("{}{}", field_prefix, argument);
+    if !context.hidden_selections.contains(&field_name) {
+        context.hidden_selections.push(field_name.to_string());
     }
+
+    env.define(field_name, DataType::Boolean);
+    Ok(table_name.to_string())
 }
 
-fn parse_group_expression(
+/// Parse the one string argument of table-valued function
+fn parse_table_function_argument(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
-) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    *position += 1;
-    let expression = parse_expression(context, env, tokens, position)?;
-    if tokens[*position].kind != TokenKind::RightParen {
-        return Err(Diagnostic::error("Expect `)` to end group expression")
-            .with_location(get_safe_location(tokens, *position))
-            .add_help("Try to add ')' at the end of group expression")
-            .as_boxed());
-    }
-    *position += 1;
-    Ok(expression)
+    function_name: &str,
+    example: &str,
+    location: Location,
+) -> Result<String, Box<Diagnostic>> {
+    let arguments = parse_arguments_expressions(context, env, tokens, position)?;
+    let argument = match arguments.first() {
+        Some(argument) if arguments.len() == 1 => argument
+            .as_any()
+            .downcast_ref::<StringExpression>()
+            .map(|string| string.value.to_string()),
+        _ => None,
+    };
+
+    argument.ok_or_else(|| {
+        Diagnostic::error(&format!(
+            "`{}` expects one string argument",
+            function_name.to_uppercase()
+        ))
+        .add_help(&format!(
+            "Try to pass the path as string, for example `{}(\"{}\")`",
+            function_name.to_uppercase(),
+            example
+        ))
+        .with_location(location)
+        .as_boxed()
+    })
 }
 
-fn parse_case_expression(
+/// `RANGE(revisions)` like `RANGE("v1.0..v2.0")` select only the commits in the range, it's parsed
+/// as a generated hidden field and the engine walks only the range instead of filtering all commits
+fn parse_range_expression(
     context: &mut ParserContext,
     env: &mut Environment,
     tokens: &Vec<Token>,
     position: &mut usize,
+    location: Location,
 ) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
-    let mut conditions: Vec<Box<dyn Expression>> = vec![];
-    let mut values: Vec<Box<dyn Expression>> = vec![];
-    let mut default_value: Option<Box<dyn Expression>> = None;
+    if !context.is_where_condition {
+        return Err(
+            Diagnostic::error("`RANGE` can only be used in `WHERE` condition")
+                .add_help("Try `WHERE RANGE(\"v1.0..v2.0\")` to select the commits in the range")
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
 
-    // Consume `case` keyword
-    let case_location = tokens[*position].location;
-    *position += 1;
+    if context.table_name != "commits" {
+        return Err(
+            Diagnostic::error("`RANGE` can only be used with `commits` table")
+                .add_help("Try to select from `commits` table")
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
 
-    let mut has_else_branch = false;
+    let arguments = parse_arguments_expressions(context, env, tokens, position)?;
+    let revisions = match arguments.first() {
+        Some(argument) if arguments.len() == 1 => argument
+            .as_any()
+            .downcast_ref::<StringExpression>()
+            .map(|string| string.value.to_string()),
+        _ => None,
+    };
 
-    while *position < tokens.len() && tokens[*position].kind != TokenKind::End {
-        // Else branch
-        if tokens[*position].kind == TokenKind::Else {
-            if has_else_branch {
-                return Err(
-                    Diagnostic::error("This `CASE` expression already has else branch")
-                        .add_note("`CASE` expression can has only one `ELSE` branch")
-                        .with_location(get_safe_location(tokens, *position))
-                        .as_boxed(),
+    if revisions.is_none() {
+        return Err(
+            Diagnostic::error("`RANGE` expects one string revision range argument")
+                .add_help("Try to pass the range as string, for example `RANGE(\"v1.0..v2.0\")`")
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
+
+    let field_name = format!("{}{}", RANGE_FIELD_PREFIX, revisions.unwrap());
+    if context
+        .hidden_selections
+        .iter()
+        .any(|name| name.starts_with(RANGE_FIELD_PREFIX) && *name != field_name)
+    {
+        return Err(
+            Diagnostic::error("Can't use more than one `RANGE` in the same query")
+                .with_location(location)
+                .as_boxed(),
+        );
+    }
+
+    if !context.hidden_selections.contains(&field_name) {
+        context.hidden_selections.push(field_name.to_string());
+    }
+
+    env.define(field_name.to_string(), DataType::Boolean);
+    Ok(Box::new(SymbolExpression { value: field_name }))
+}
+
+/// Returns true if the condition is the field or the field joined with other conditions using `AND`
+fn is_required_condition(condition: &dyn Expression, field_name: &str) -> bool {
+    if let Some(symbol) = condition.as_any().downcast_ref::<SymbolExpression>() {
+        return symbol.value == field_name;
+    }
+
+    if let Some(logical) = condition.as_any().downcast_ref::<LogicalExpression>() {
+        return logical.operator == LogicalOperator::And
+            && (is_required_condition(logical.left.as_ref(), field_name)
+                || is_required_condition(logical.right.as_ref(), field_name));
+    }
+
+    false
+}
+
+/// Returns the string that the field must be equal to if the condition is `field = "string"`
+/// or it's joined with other conditions using `AND`
+fn required_equal_string(condition: &dyn Expression, field_name: &str) -> Option<String> {
+    if let Some(comparison) = condition.as_any().downcast_ref::<ComparisonExpression>() {
+        if comparison.operator != ComparisonOperator::Equal {
+            return None;
+        }
+
+        let (left, right) = (comparison.left.as_any(), comparison.right.as_any());
+        let (symbol, string) = match left.downcast_ref::<SymbolExpression>() {
+            Some(symbol) => (symbol, right.downcast_ref::<StringExpression>()?),
+            None => (
+                right.downcast_ref::<SymbolExpression>()?,
+                left.downcast_ref::<StringExpression>()?,
+            ),
+        };
+        return (symbol.value == field_name).then(|| string.value.to_string());
+    }
+
+    if let Some(logical) = condition.as_any().downcast_ref::<LogicalExpression>() {
+        if logical.operator != LogicalOperator::And {
+            return None;
+        }
+
+        return required_equal_string(logical.left.as_ref(), field_name)
+            .or_else(|| required_equal_string(logical.right.as_ref(), field_name));
+    }
+
+    None
+}
+
+/// Parse the aggregation function arguments with the optional modifiers
+/// `(DISTINCT argument ORDER BY field ASC|DESC SEPARATOR 'text')` into the aggregation call
+fn parse_aggregation_arguments(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+    call: &mut AggregateCall,
+) -> Result<Vec<Box<dyn Expression>>, Box<Diagnostic>> {
+    // Consume `(` token
+    *position += 1;
+
+    if consume_kind(tokens, *position, TokenKind::Distinct).is_ok() {
+        // Consume `DISTINCT` keyword
+        *position += 1;
+        call.is_distinct = true;
+    }
+
+    let mut arguments: Vec<Box<dyn Expression>> = vec![];
+    while !tokens.is_at_end(*position)
+        && !tokens.is_kind(*position, TokenKind::RightParen)
+        && !tokens.is_kind(*position, TokenKind::Order)
+        && !is_separator_keyword(tokens, *position)
+    {
+        let argument = parse_expression(context, env, tokens, position)?;
+        if let Ok(literal) = get_expression_name(&argument) {
+            context.hidden_selections.push(literal);
+        }
+
+        arguments.push(argument);
+
+        if tokens.is_kind(*position, TokenKind::Comma) {
+            *position += 1;
+        } else {
+            break;
+        }
+    }
+
+    if consume_kind(tokens, *position, TokenKind::Order).is_ok() {
+        // Consume `ORDER` keyword
+        *position += 1;
+
+        if consume_kind(tokens, *position, TokenKind::By).is_err() {
+            return Err(
+                Diagnostic::error("Expect keyword `BY` after keyword `ORDER")
+                    .add_help("Try to use `BY` keyword after `ORDER")
+                    .with_location(get_safe_location(tokens, *position - 1))
+                    .as_boxed(),
+            );
+        }
+
+        // Consume `BY` keyword
+        *position += 1;
+
+        let field_location = get_safe_location(tokens, *position);
+        let field = parse_expression(context, env, tokens, position)?;
+        let field_name = get_expression_name(&field).map_err(|_| {
+            Diagnostic::error("Invalid Aggregation function `ORDER BY` argument")
+                .add_help("Try to use field name as Aggregation function `ORDER BY` argument")
+                .with_location(field_location)
+                .as_boxed()
+        })?;
+
+        context.hidden_selections.push(field_name.to_string());
+
+        let mut order = SortingOrder::Ascending;
+        if tokens.is_token(*position, is_asc_or_desc) {
+            if tokens.is_kind(*position, TokenKind::Descending) {
+                order = SortingOrder::Descending;
+            }
+
+            // Consume `ASC or DESC` keyword
+            *position += 1;
+        }
+
+        call.order_by = Some((field_name, order));
+    }
+
+    if is_separator_keyword(tokens, *position) {
+        let separator_location = get_safe_location(tokens, *position);
+        if call.function_name != "group_concat" {
+            return Err(Diagnostic::error(
+                "`SEPARATOR` can only be used with `GROUP_CONCAT` Aggregation function",
+            )
+            .with_location(separator_location)
+            .as_boxed());
+        }
+
+        // Consume `SEPARATOR` keyword
+        *position += 1;
+
+        match consume_kind(tokens, *position, TokenKind::String) {
+            Ok(token) => call.separator = Some(token.literal.to_string()),
+            Err(_) => {
+                return Err(Diagnostic::error("Expect string literal after `SEPARATOR`")
+                    .add_help("Try to pass the separator as string, for example `SEPARATOR ', '`")
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed());
+            }
+        }
+
+        // Consume separator string
+        *position += 1;
+    }
+
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(
+            Diagnostic::error("Expect `)` after function call arguments")
+                .add_help("Try to add ')' at the end of function call, after arguments")
+                .with_location(get_safe_location(tokens, *position))
+                .as_boxed(),
+        );
+    }
+
+    // Consume `)` token
+    *position += 1;
+    Ok(arguments)
+}
+
+/// `SEPARATOR` is not reserved keyword, it's only a keyword inside `GROUP_CONCAT` arguments
+#[inline(always)]
+fn is_separator_keyword(tokens: &[Token], position: usize) -> bool {
+    tokens.peek(position).is_some_and(|token| {
+        token.kind == TokenKind::Symbol && token.literal.eq_ignore_ascii_case("separator")
+    })
+}
+
+fn parse_arguments_expressions(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Vec<Box<dyn Expression>>, Box<Diagnostic>> {
+    let mut arguments: Vec<Box<dyn Expression>> = vec![];
+    if consume_kind(tokens, *position, TokenKind::LeftParen).is_ok() {
+        *position += 1;
+
+        while !tokens.is_at_end(*position) && !tokens.is_kind(*position, TokenKind::RightParen) {
+            let argument = parse_expression(context, env, tokens, position)?;
+            let argument_literal = get_expression_name(&argument);
+            if argument_literal.is_ok() {
+                let literal = argument_literal.ok().unwrap();
+                context.hidden_selections.push(literal);
+            }
+
+            arguments.push(argument);
+
+            if tokens.is_kind(*position, TokenKind::Comma) {
+                *position += 1;
+            } else {
+                break;
+            }
+        }
+
+        if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+            return Err(
+                Diagnostic::error("Expect `)` after function call arguments")
+                    .add_help("Try to add ')' at the end of function call, after arguments")
+                    .with_location(get_safe_location(tokens, *position))
+                    .as_boxed(),
+            );
+        }
+
+        *position += 1;
+    }
+    Ok(arguments)
+}
+
+fn parse_primary_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let token = match tokens.peek(*position) {
+        Some(token) => token,
+        None => return Err(un_expected_expression_error(tokens, position)),
+    };
+
+    match token.kind {
+        TokenKind::String => {
+            *position += 1;
+            Ok(Box::new(StringExpression {
+                value: token.literal.to_string(),
+                value_type: StringValueType::Text,
+            }))
+        }
+        TokenKind::Symbol => {
+            let mut value = token.identifier().to_string();
+            *position += 1;
+
+            // Qualified field name `table.field` or `alias.field`, the qualifier is checked after `FROM`
+            if let Ok(field_token) = consume_kind(tokens, *position, TokenKind::Dot)
+                .and_then(|_| consume_kind(tokens, *position + 1, TokenKind::Symbol))
+            {
+                context.qualified_references.push((value, token.location));
+                value = field_token.identifier().to_string();
+
+                // Consume `.` and field name
+                *position += 2;
+            }
+
+            context
+                .column_references
+                .push((value.to_string(), token.location));
+            if !context.selected_fields.contains(&value) {
+                context.hidden_selections.push(value.to_string());
+            }
+            Ok(Box::new(SymbolExpression { value }))
+        }
+        TokenKind::GlobalVariable => {
+            let name = token.identifier().to_string();
+            *position += 1;
+            Ok(Box::new(GlobalVariableExpression { name }))
+        }
+        TokenKind::Integer | TokenKind::Float => parse_number_literal(tokens, position, false),
+        TokenKind::True => {
+            *position += 1;
+            Ok(Box::new(BooleanExpression { is_true: true }))
+        }
+        TokenKind::False => {
+            *position += 1;
+            Ok(Box::new(BooleanExpression { is_true: false }))
+        }
+        TokenKind::Null => {
+            *position += 1;
+            Ok(Box::new(NullExpression {}))
+        }
+        TokenKind::LeftParen => parse_group_expression(context, env, tokens, position),
+        TokenKind::Case => parse_case_expression(context, env, tokens, position),
+        _ => Err(un_expected_expression_error(tokens, position)),
+    }
+}
+
+fn parse_group_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    *position += 1;
+    let expression = parse_expression(context, env, tokens, position)?;
+    if consume_kind(tokens, *position, TokenKind::RightParen).is_err() {
+        return Err(Diagnostic::error("Expect `)` to end group expression")
+            .with_location(get_safe_location(tokens, *position))
+            .add_help("Try to add ')' at the end of group expression")
+            .as_boxed());
+    }
+    *position += 1;
+    Ok(expression)
+}
+
+fn parse_case_expression(
+    context: &mut ParserContext,
+    env: &mut Environment,
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let mut conditions: Vec<Box<dyn Expression>> = vec![];
+    let mut values: Vec<Box<dyn Expression>> = vec![];
+    let mut default_value: Option<Box<dyn Expression>> = None;
+
+    // Consume `case` keyword
+    let case_location = get_safe_location(tokens, *position);
+    *position += 1;
+
+    let mut has_else_branch = false;
+
+    while !tokens.is_at_end(*position) && !tokens.is_kind(*position, TokenKind::End) {
+        // Else branch
+        if tokens.is_kind(*position, TokenKind::Else) {
+            if has_else_branch {
+                return Err(
+                    Diagnostic::error("This `CASE` expression already has else branch")
+                        .add_note("`CASE` expression can has only one `ELSE` branch")
+                        .with_location(get_safe_location(tokens, *position))
+                        .as_boxed(),
                 );
             }
 
@@ -1907,7 +4288,7 @@ fn parse_case_expression(
     }
 
     // Make sure case expression end with END keyword
-    if *position >= tokens.len() || tokens[*position].kind != TokenKind::End {
+    if !tokens.is_kind(*position, TokenKind::End) {
         return Err(Diagnostic::error("Expect `END` after case branches")
             .with_location(get_safe_location(tokens, *position))
             .as_boxed());
@@ -1923,8 +4304,11 @@ fn parse_case_expression(
             .as_boxed());
     }
 
-    // Assert that all values has the same type
-    let values_type: DataType = values[0].expr_type(env);
+    // Assert that all values has the same type, `CASE` with only else branch has the else value type
+    let values_type: DataType = match values.first() {
+        Some(value) => value.expr_type(env),
+        None => default_value.as_ref().unwrap().expr_type(env),
+    };
     for (i, value) in values.iter().enumerate().skip(1) {
         if values_type != value.expr_type(env) {
             return Err(Diagnostic::error(&format!(
@@ -1945,28 +4329,128 @@ fn parse_case_expression(
     }))
 }
 
-fn check_function_call_arguments(
+/// Select the overload of the function that accepts the arguments and check the arguments against it,
+/// the overload that match the arguments types without implicit casting is preferred, returns the
+/// index of the selected overload
+fn check_function_call_overloads(
     env: &mut Environment,
     arguments: &mut Vec<Box<dyn Expression>>,
-    parameters: &Vec<DataType>,
-    function_name: String,
+    overloads: &[(&Vec<DataType>, &DataType)],
+    function_name: &str,
     location: Location,
-) -> Result<(), Box<Diagnostic>> {
-    let parameters_len = parameters.len();
-    let arguments_len = arguments.len();
+) -> Result<usize, Box<Diagnostic>> {
+    // Function with a single signature reports the mismatch of the exact argument
+    if overloads.len() == 1 {
+        let (parameters, _) = overloads[0];
+        check_function_call_arguments(
+            env,
+            arguments,
+            parameters,
+            function_name.to_string(),
+            location,
+        )?;
+        return Ok(0);
+    }
 
-    let mut has_optional_parameter = false;
-    let mut has_varargs_parameter = false;
-    if !parameters.is_empty() {
-        let last_parameter = parameters.last().unwrap();
-        has_optional_parameter = last_parameter.is_optional();
-        has_varargs_parameter = last_parameter.is_varargs();
+    let arguments_types: Vec<DataType> = arguments
+        .iter()
+        .map(|argument| argument.expr_type(env))
+        .collect();
+
+    let selected_index = overloads
+        .iter()
+        .position(|(parameters, _)| is_parameters_accepting(parameters, &arguments_types))
+        .or_else(|| {
+            overloads.iter().position(|(parameters, _)| {
+                is_arguments_implicitly_castable(env, arguments, parameters)
+            })
+        });
+
+    if let Some(index) = selected_index {
+        let (parameters, _) = overloads[index];
+        check_function_call_arguments(
+            env,
+            arguments,
+            parameters,
+            function_name.to_string(),
+            location,
+        )?;
+        return Ok(index);
     }
 
-    // Has Optional parameter type at the end
-    if has_optional_parameter {
-        // If function last parameter is optional make sure it at least has
-        if arguments_len < parameters_len - 1 {
+    let arguments_types: Vec<String> = arguments_types
+        .iter()
+        .map(|data_type| data_type.to_string())
+        .collect();
+
+    let mut diagnostic = Diagnostic::error(&format!(
+        "Function `{}` has no overload that accepts arguments `({})`",
+        function_name,
+        arguments_types.join(", ")
+    ));
+
+    for (parameters, result) in overloads {
+        let parameters: Vec<String> = parameters
+            .iter()
+            .map(|parameter| parameter.to_string())
+            .collect();
+        diagnostic = diagnostic.add_note(&format!(
+            "Candidate `{}({}) -> {}`",
+            function_name,
+            parameters.join(", "),
+            result
+        ));
+    }
+
+    Err(diagnostic
+        .add_help("Try to pass arguments that match the types of one of the candidates")
+        .with_location(location)
+        .as_boxed())
+}
+
+/// Returns true if each argument has the type of its parameter or can be implicitly casted to it
+fn is_arguments_implicitly_castable(
+    env: &Environment,
+    arguments: &[Box<dyn Expression>],
+    parameters: &[DataType],
+) -> bool {
+    let Some(parameters_types) = arguments_parameters_types(parameters, arguments.len()) else {
+        return false;
+    };
+
+    arguments
+        .iter()
+        .zip(parameters_types)
+        .all(|(argument, parameter_type)| {
+            !matches!(
+                is_expression_type_equals(env, argument, parameter_type),
+                TypeCheckResult::NotEqualAndCantImplicitCast | TypeCheckResult::Error(_)
+            )
+        })
+}
+
+fn check_function_call_arguments(
+    env: &mut Environment,
+    arguments: &mut Vec<Box<dyn Expression>>,
+    parameters: &Vec<DataType>,
+    function_name: String,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let parameters_len = parameters.len();
+    let arguments_len = arguments.len();
+
+    let mut has_optional_parameter = false;
+    let mut has_varargs_parameter = false;
+    if !parameters.is_empty() {
+        let last_parameter = parameters.last().unwrap();
+        has_optional_parameter = last_parameter.is_optional();
+        has_varargs_parameter = last_parameter.is_varargs();
+    }
+
+    // Has Optional parameter type at the end
+    if has_optional_parameter {
+        // If function last parameter is optional make sure it at least has
+        if arguments_len < parameters_len - 1 {
             return Err(Box::new(
                 Diagnostic::error(&format!(
                     "Function `{}` expects at least `{}` arguments but got `{}`",
@@ -2094,25 +4578,28 @@ fn type_check_selected_fields(
             continue;
         }
 
-        return Err(Diagnostic::error(&format!(
+        let diagnostic = Diagnostic::error(&format!(
             "Table `{}` has no field with name `{}`",
             table_name, field_name
         ))
         .add_help("Check the documentations to see available fields for each tables")
-        .with_location(get_safe_location(tokens, position))
-        .as_boxed());
+        .with_location(get_safe_location(tokens, position));
+        let fields = env.table_fields(table_name);
+        let fields_names = fields.iter().map(|(name, _)| name.as_str());
+        return Err(add_did_you_mean_help(diagnostic, field_name, fields_names).as_boxed());
     }
     Ok(())
 }
 
 fn un_expected_statement_error(tokens: &[Token], position: &mut usize) -> Box<Diagnostic> {
-    let token: &Token = &tokens[*position];
-    let location = token.location;
+    let location = tokens.location_at(*position);
 
     // Query starts with invalid statement
     if location.start == 0 {
         return Diagnostic::error("Unexpected statement")
-            .add_help("Expect query to start with `SELECT` or `SET` keyword")
+            .add_help(
+                "Expect query to start with `SELECT`, `SET`, `SHOW`, `EXPLAIN`, `CREATE` or `INSERT` keyword",
+            )
             .with_location(location)
             .as_boxed();
     }
@@ -2126,14 +4613,14 @@ fn un_expected_statement_error(tokens: &[Token], position: &mut usize) -> Box<Di
 fn un_expected_expression_error(tokens: &Vec<Token>, position: &usize) -> Box<Diagnostic> {
     let location = get_safe_location(tokens, *position);
 
-    if *position == 0 || *position >= tokens.len() {
-        return Diagnostic::error("Can't complete parsing this expression")
-            .with_location(location)
-            .as_boxed();
-    }
-
-    let current = &tokens[*position];
-    let previous = &tokens[*position - 1];
+    let (current, previous) = match (tokens.peek(*position), tokens.previous(*position)) {
+        (Some(current), Some(previous)) => (current, previous),
+        _ => {
+            return Diagnostic::error("Can't complete parsing this expression")
+                .with_location(location)
+                .as_boxed();
+        }
+    };
 
     // Make sure `ASC` and `DESC` are used in ORDER BY statement
     if current.kind == TokenKind::Ascending || current.kind == TokenKind::Descending {
@@ -2209,8 +4696,8 @@ fn un_expected_content_after_correct_statement(
 
     // The range of extra content
     let location_of_extra_content = Location {
-        start: tokens[*position].location.start,
-        end: tokens[tokens.len() - 1].location.end,
+        start: get_safe_location(tokens, *position).start,
+        end: get_safe_location(tokens, tokens.len()).end,
     };
 
     Diagnostic::error(error_message)
@@ -2236,26 +4723,156 @@ fn get_expression_name(expression: &Box<dyn Expression>) -> Result<String, ()> {
     Err(())
 }
 
+/// Evaluate constant number expression at parse time, or return error if it's not constant
+#[allow(clippy::borrowed_box)]
+fn evaluate_constant_number(expression: &Box<dyn Expression>) -> Result<Value, String> {
+    match expression.kind() {
+        ExpressionKind::Number => {
+            let number = expression
+                .as_any()
+                .downcast_ref::<NumberExpression>()
+                .unwrap();
+            Ok(number.value.clone())
+        }
+        ExpressionKind::PrefixUnary => {
+            let unary = expression.as_any().downcast_ref::<PrefixUnary>().unwrap();
+            if unary.op != PrefixUnaryOperator::Minus {
+                return Err("Expect constant number expression".to_string());
+            }
+
+            let value = evaluate_constant_number(&unary.right)?;
+            if value.data_type().is_int() {
+                return Value::Integer(0).minus(&value);
+            }
+            Ok(Value::Float(-value.as_float()))
+        }
+        ExpressionKind::Arithmetic => {
+            let arithmetic = expression
+                .as_any()
+                .downcast_ref::<ArithmeticExpression>()
+                .unwrap();
+            let lhs = evaluate_constant_number(&arithmetic.left)?;
+            let rhs = evaluate_constant_number(&arithmetic.right)?;
+            match arithmetic.operator {
+                ArithmeticOperator::Plus => lhs.plus(&rhs),
+                ArithmeticOperator::Minus => lhs.minus(&rhs),
+                ArithmeticOperator::Star => lhs.mul(&rhs),
+                ArithmeticOperator::Slash => lhs.div(&rhs),
+                ArithmeticOperator::Modulus => lhs.modulus(&rhs),
+            }
+        }
+        _ => Err("Expect constant number expression".to_string()),
+    }
+}
+
+/// Report clear error if `OFFSET` value is a negative number literal, for example `OFFSET -1`
+fn check_negative_offset_count(tokens: &[Token], position: usize) -> Result<(), Box<Diagnostic>> {
+    if let (Some(minus_token), Some(number_token)) = (
+        tokens.peek_if(position, |token| token.kind == TokenKind::Minus),
+        tokens.peek_if(position + 1, is_number_token),
+    ) {
+        return Err(Diagnostic::error("`OFFSET` integer value is invalid")
+            .add_help(&format!(
+                "`OFFSET` value must be between 0 and {}",
+                usize::MAX
+            ))
+            .add_note(&format!(
+                "`OFFSET` value is negative `-{}`",
+                number_token.literal
+            ))
+            .with_location_span(minus_token.location.start, number_token.location.end)
+            .as_boxed());
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+fn is_number_literal(tokens: &[Token], position: usize) -> bool {
+    tokens.is_token(position, is_number_token)
+}
+
+#[inline(always)]
+fn is_number_token(token: &Token) -> bool {
+    token.kind == TokenKind::Integer || token.kind == TokenKind::Float
+}
+
+fn parse_number_literal(
+    tokens: &[Token],
+    position: &mut usize,
+    is_negative: bool,
+) -> Result<Box<dyn Expression>, Box<Diagnostic>> {
+    let token = match tokens.peek_if(*position, is_number_token) {
+        Some(token) => token,
+        None => {
+            return Err(Diagnostic::error("Expect number literal")
+                .with_location(tokens.location_at(*position))
+                .as_boxed())
+        }
+    };
+
+    let literal = if is_negative {
+        format!("-{}", token.literal)
+    } else {
+        token.literal.to_string()
+    };
+
+    if token.kind == TokenKind::Integer {
+        if let Ok(integer) = literal.parse::<i64>() {
+            *position += 1;
+            let value = Value::Integer(integer);
+            return Ok(Box::new(NumberExpression { value }));
+        }
+
+        return Err(Diagnostic::error("Too big Integer value")
+            .add_help("Try to use smaller value")
+            .add_note(&format!(
+                "Integer value must be between {} and {}",
+                i64::MIN,
+                i64::MAX
+            ))
+            .with_location(token.location)
+            .as_boxed());
+    }
+
+    if let Ok(float) = literal.parse::<f64>() {
+        if float.is_finite() {
+            *position += 1;
+            let value = Value::Float(float);
+            return Ok(Box::new(NumberExpression { value }));
+        }
+    }
+
+    Err(Diagnostic::error("Too big Float value")
+        .add_help("Try to use smaller value")
+        .add_note(&format!(
+            "Float value must be between {} and {}",
+            f64::MIN,
+            f64::MAX
+        ))
+        .with_location(token.location)
+        .as_boxed())
+}
+
 #[inline(always)]
 fn register_current_table_fields_types(table_name: &str, symbol_table: &mut Environment) {
-    let table_fields_names = &TABLES_FIELDS_NAMES[table_name];
-    for field_name in table_fields_names {
-        let field_type = TABLES_FIELDS_TYPES[field_name].clone();
-        symbol_table.define(field_name.to_string(), field_type);
+    for (field_name, field_type) in symbol_table.table_fields(table_name) {
+        symbol_table.define(field_name, field_type);
     }
 }
 
 #[inline(always)]
 fn select_all_table_fields(
+    env: &Environment,
     table_name: &str,
     selected_fields: &mut Vec<String>,
     fields_names: &mut Vec<String>,
     fields_values: &mut Vec<Box<dyn Expression>>,
 ) {
-    if TABLES_FIELDS_NAMES.contains_key(table_name) {
-        let table_fields = &TABLES_FIELDS_NAMES[table_name];
+    if env.has_table(table_name) {
+        let table_fields = env.table_fields(table_name);
 
-        for field in table_fields {
+        for (field, _) in table_fields {
             if !fields_names.contains(&field.to_string()) {
                 fields_names.push(field.to_string());
                 selected_fields.push(field.to_string());
@@ -2270,20 +4887,67 @@ fn select_all_table_fields(
     }
 }
 
+/// Rebuild the text of expression from its tokens, used as a column title
+fn tokens_as_text(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    let mut previous_kind: Option<&TokenKind> = None;
+    let mut is_after_unary = false;
+    for token in tokens {
+        let literal = match token.kind {
+            TokenKind::String => format!("\"{}\"", token.literal),
+            TokenKind::Symbol | TokenKind::GlobalVariable => token.identifier().to_string(),
+            TokenKind::Integer | TokenKind::Float => token.literal.to_string(),
+            // Keywords are case-insensitive, so the title is the same regardless of the query case
+            _ => token.literal.to_lowercase(),
+        };
+
+        let has_space = match previous_kind {
+            None => false,
+            Some(TokenKind::LeftParen) | Some(TokenKind::Dot) => false,
+            _ => {
+                !is_after_unary
+                    && token.kind != TokenKind::RightParen
+                    && token.kind != TokenKind::Comma
+                    && token.kind != TokenKind::Dot
+                    && !(token.kind == TokenKind::LeftParen
+                        && previous_kind == Some(&TokenKind::Symbol))
+            }
+        };
+
+        // Prefix unary operators are attached to their operand
+        is_after_unary = is_prefix_unary_operator(token)
+            && previous_kind.is_none_or(|kind| {
+                *kind != TokenKind::RightParen
+                    && *kind != TokenKind::Symbol
+                    && *kind != TokenKind::Integer
+                    && *kind != TokenKind::Float
+                    && *kind != TokenKind::String
+            });
+
+        if has_space {
+            text.push(' ');
+        }
+        text.push_str(&literal);
+        previous_kind = Some(&token.kind);
+    }
+    text
+}
+
 #[inline(always)]
-fn consume_kind(tokens: &Vec<Token>, position: usize, kind: TokenKind) -> Result<&Token, ()> {
-    if position < tokens.len() && tokens[position].kind == kind {
-        return Ok(&tokens[position]);
+fn consume_kind<'a>(
+    tokens: &'a Vec<Token>,
+    position: usize,
+    kind: TokenKind,
+) -> Result<&'a Token<'a>, ()> {
+    match tokens.peek(position) {
+        Some(token) if token.kind == kind => Ok(token),
+        _ => Err(()),
     }
-    Err(())
 }
 
 #[inline(always)]
 fn get_safe_location(tokens: &Vec<Token>, position: usize) -> Location {
-    if position < tokens.len() {
-        return tokens[position].location;
-    }
-    tokens[tokens.len() - 1].location
+    tokens.location_at(position)
 }
 
 #[inline(always)]
@@ -2293,7 +4957,9 @@ fn is_assignment_operator(token: &Token) -> bool {
 
 #[inline(always)]
 fn is_term_operator(token: &Token) -> bool {
-    token.kind == TokenKind::Plus || token.kind == TokenKind::Minus
+    token.kind == TokenKind::Plus
+        || token.kind == TokenKind::Minus
+        || token.kind == TokenKind::Concat
 }
 
 #[inline(always)]
@@ -2327,6 +4993,75 @@ fn is_asc_or_desc(token: &Token) -> bool {
     token.kind == TokenKind::Ascending || token.kind == TokenKind::Descending
 }
 
+/// Check that the non null arguments which function may return has the same type
+fn check_generic_result_arguments(
+    env: &Environment,
+    arguments: &[Box<dyn Expression>],
+    function_name: &str,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let mut expected_type: Option<DataType> = None;
+    for argument in arguments {
+        let argument_type = argument.expr_type(env);
+        if argument_type.is_null() {
+            continue;
+        }
+
+        match &expected_type {
+            Some(expected) if *expected != argument_type => {
+                return Err(Diagnostic::error(&format!(
+                    "Function `{}` result arguments must have the same type, expected `{}`, got `{}`",
+                    function_name, expected, argument_type
+                ))
+                .add_help("Make sure all arguments has the same type or are `NULL`")
+                .with_location(location)
+                .as_boxed());
+            }
+            Some(_) => {}
+            None => expected_type = Some(argument_type),
+        }
+    }
+    Ok(())
+}
+
+/// Make sure right and left hand side of bitwise operator are integers or NULL
+#[allow(clippy::borrowed_box)]
+fn check_bitwise_operands_types(
+    env: &Environment,
+    lhs: &Box<dyn Expression>,
+    rhs: &Box<dyn Expression>,
+    location: Location,
+) -> Result<(), Box<Diagnostic>> {
+    let lhs_type = lhs.expr_type(env);
+    let rhs_type = rhs.expr_type(env);
+    let is_int_or_null = |data_type: &DataType| data_type.is_int() || data_type.is_null();
+    if is_int_or_null(&lhs_type) && is_int_or_null(&rhs_type) {
+        return Ok(());
+    }
+
+    Err(Diagnostic::error(&format!(
+        "Bitwise operators require integer types but got `{}` and `{}`",
+        lhs_type, rhs_type
+    ))
+    .with_location(location)
+    .as_boxed())
+}
+
+#[inline(always)]
+fn is_boolean_or_null(data_type: &DataType) -> bool {
+    data_type.is_bool() || data_type.is_null()
+}
+
+#[inline(always)]
+fn is_number_or_null(data_type: &DataType) -> bool {
+    data_type.is_number() || data_type.is_null()
+}
+
+#[inline(always)]
+fn is_text_or_null(data_type: &DataType) -> bool {
+    data_type.is_text() || data_type.is_null()
+}
+
 #[inline(always)]
 fn type_mismatch_error(
     location: Location,
@@ -2344,6 +5079,7 @@ fn type_mismatch_error(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gitql_ast::environment::TABLES_FIELDS_NAMES;
 
     #[test]
     fn test_parse_gql() {
@@ -2351,6 +5087,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // Test: SET @name = value
@@ -2358,22 +5104,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@name".to_string(),
+                literal: "@name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::String,
-                literal: "value".to_string(),
+                literal: "value".into(),
             },
         ];
 
@@ -2387,12 +5133,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@name".to_string(),
+                literal: "@name".into(),
             },
         ];
 
@@ -2405,7 +5151,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Distinct,
-            literal: "DISTINCT".to_string(),
+            literal: "DISTINCT".into(),
         }];
 
         let ret = parse_gql(tokens, &mut env);
@@ -2418,17 +5164,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@name".to_string(),
+                literal: "@name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@invalid".to_string(),
+                literal: "@invalid".into(),
             },
         ];
 
@@ -2444,6 +5190,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // Test: SET @invalid
@@ -2451,12 +5207,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Set,
-                literal: "@invalid".to_string(),
+                literal: "@invalid".into(),
             },
         ];
 
@@ -2472,12 +5228,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@name".to_string(),
+                literal: "@name".into(),
             },
         ];
 
@@ -2493,17 +5249,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@name".to_string(),
+                literal: "@name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
         ];
 
@@ -2519,22 +5275,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@one".to_string(),
+                literal: "@one".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -2550,22 +5306,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Set,
-                literal: "SET".to_string(),
+                literal: "SET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GlobalVariable,
-                literal: "@STRING".to_string(),
+                literal: "@STRING".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::String,
-                literal: "GitQL".to_string(),
+                literal: "GitQL".into(),
             },
         ];
 
@@ -2583,6 +5339,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // Test: SELECT SELECT
@@ -2590,18 +5356,18 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_ok() {
             assert!(false);
         }
@@ -2611,43 +5377,43 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "count".to_string(),
+                literal: "count".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_err() {
             assert!(false);
         }
@@ -2657,48 +5423,48 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "branches".to_string(),
+                literal: "branches".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Where,
-                literal: "WHERE".to_string(),
+                literal: "WHERE".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "is_head".to_string(),
+                literal: "is_head".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::True,
-                literal: "true".to_string(),
+                literal: "true".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_err() {
             assert!(false);
         }
@@ -2708,43 +5474,48 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Group,
-                literal: "GROUP".to_string(),
+                literal: "GROUP".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        // Selecting fields that are not grouped is allowed only with any value mode
+        let options = ParserOptions {
+            any_value_mode: true,
+            ..Default::default()
+        };
+        let ret = parse_select_query(&mut env, &tokens, &mut position, options);
         if ret.is_err() {
             assert!(false);
         }
@@ -2754,63 +5525,68 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "branches".to_string(),
+                literal: "branches".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Group,
-                literal: "GROUP".to_string(),
+                literal: "GROUP".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 8, end: 9 },
                 kind: TokenKind::Having,
-                literal: "HAVING".to_string(),
+                literal: "HAVING".into(),
             },
             Token {
                 location: Location { start: 9, end: 10 },
                 kind: TokenKind::Symbol,
-                literal: "is_head".to_string(),
+                literal: "is_head".into(),
             },
             Token {
                 location: Location { start: 10, end: 11 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 11, end: 12 },
                 kind: TokenKind::True,
-                literal: "true".to_string(),
+                literal: "true".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        // Selecting fields that are not grouped is allowed only with any value mode
+        let options = ParserOptions {
+            any_value_mode: true,
+            ..Default::default()
+        };
+        let ret = parse_select_query(&mut env, &tokens, &mut position, options);
         if ret.is_err() {
             assert!(false);
         }
@@ -2820,38 +5596,38 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Limit,
-                literal: "LIMIT".to_string(),
+                literal: "LIMIT".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Integer,
-                literal: "10".to_string(),
+                literal: "10".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_err() {
             assert!(false);
         }
@@ -2861,38 +5637,38 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Offset,
-                literal: "OFFSET".to_string(),
+                literal: "OFFSET".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Integer,
-                literal: "10".to_string(),
+                literal: "10".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_err() {
             assert!(false);
         }
@@ -2902,53 +5678,53 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "email".to_string(),
+                literal: "email".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Order,
-                literal: "ORDER".to_string(),
+                literal: "ORDER".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
         ];
 
         let mut position = 0;
 
-        let ret = parse_select_query(&mut env, &tokens, &mut position);
+        let ret = parse_select_query(&mut env, &tokens, &mut position, ParserOptions::default());
         if ret.is_err() {
             assert!(false);
         }
@@ -2961,13 +5737,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // SELECT
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Select,
-            literal: "SELECT".to_string(),
+            literal: "SELECT".into(),
         }];
 
         let mut position = 1;
@@ -2982,32 +5768,32 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
@@ -3023,32 +5809,32 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "title".to_string(),
+                literal: "title".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::As,
-                literal: "AS".to_string(),
+                literal: "AS".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::As,
-                literal: "AS".to_string(),
+                literal: "AS".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
@@ -3064,52 +5850,52 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "title".to_string(),
+                literal: "title".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::As,
-                literal: "AS".to_string(),
+                literal: "AS".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "title".to_string(),
+                literal: "title".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Symbol,
-                literal: "message".to_string(),
+                literal: "message".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::As,
-                literal: "AS".to_string(),
+                literal: "AS".into(),
             },
             Token {
                 location: Location { start: 8, end: 9 },
                 kind: TokenKind::Symbol,
-                literal: "title".to_string(),
+                literal: "title".into(),
             },
             Token {
                 location: Location { start: 9, end: 10 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 10, end: 10 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
@@ -3125,22 +5911,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
         ];
 
@@ -3156,22 +5942,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "invalid".to_string(),
+                literal: "invalid".into(),
             },
         ];
 
@@ -3187,12 +5973,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
         ];
 
@@ -3208,17 +5994,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
@@ -3234,22 +6020,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Select,
-                literal: "SELECT".to_string(),
+                literal: "SELECT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::From,
-                literal: "FROM".to_string(),
+                literal: "FROM".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "commits".to_string(),
+                literal: "commits".into(),
             },
         ];
 
@@ -3268,13 +6054,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // WHERE
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Where,
-            literal: "WHERE".to_string(),
+            literal: "WHERE".into(),
         }];
 
         let mut position = 0;
@@ -3289,12 +6085,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Where,
-                literal: "WHERE".to_string(),
+                literal: "WHERE".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "head".to_string(),
+                literal: "head".into(),
             },
         ];
 
@@ -3310,12 +6106,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Where,
-                literal: "WHERE".to_string(),
+                literal: "WHERE".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "is_head".to_string(),
+                literal: "is_head".into(),
             },
         ];
 
@@ -3334,13 +6130,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // GROUP
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Group,
-            literal: "GROUP".to_string(),
+            literal: "GROUP".into(),
         }];
 
         let mut position = 0;
@@ -3355,12 +6161,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Group,
-                literal: "GROUP".to_string(),
+                literal: "GROUP".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
         ];
 
@@ -3376,17 +6182,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Group,
-                literal: "GROUP".to_string(),
+                literal: "GROUP".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
         ];
 
@@ -3397,6 +6203,74 @@ mod tests {
         if statement.is_err() {
             assert!(false);
         }
+
+        // GROUP BY ALL
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::Group,
+                literal: "GROUP".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::By,
+                literal: "BY".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::All,
+                literal: "all".into(),
+            },
+        ];
+
+        context.non_aggregated_selections = vec!["name".to_string(), "email".to_string()];
+        let mut position = 0;
+
+        let statement = parse_group_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if let Ok(statement) = statement {
+            let group_by = statement
+                .as_any()
+                .downcast_ref::<GroupByStatement>()
+                .unwrap();
+            assert_eq!(group_by.field_names, vec!["name", "email"]);
+        } else {
+            assert!(false);
+        }
+
+        // SELECT name, email ... GROUP BY name
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::Group,
+                literal: "GROUP".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::By,
+                literal: "BY".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::Symbol,
+                literal: "name".into(),
+            },
+        ];
+
+        context.non_aggregated_fields = vec!["name".to_string(), "email".to_string()];
+        let mut position = 0;
+
+        let statement = parse_group_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_ok() {
+            assert!(false);
+        }
+
+        context.options.any_value_mode = true;
+        let mut position = 0;
+
+        let statement = parse_group_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_err() {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -3406,13 +6280,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // HAVING
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Having,
-            literal: "HAVING".to_string(),
+            literal: "HAVING".into(),
         }];
 
         let mut position = 0;
@@ -3427,22 +6311,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Having,
-                literal: "HAVING".to_string(),
+                literal: "HAVING".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "is_head".to_string(),
+                literal: "is_head".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::True,
-                literal: "true".to_string(),
+                literal: "true".into(),
             },
         ];
 
@@ -3456,16 +6340,33 @@ mod tests {
 
     #[test]
     fn test_parse_limit_statement() {
+        let mut context = ParserContext::default();
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
         // LIMIT
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Limit,
-            literal: "LIMIT".to_string(),
+            literal: "LIMIT".into(),
         }];
 
         let mut position = 0;
 
-        let statement = parse_limit_statement(&tokens, &mut position);
+        let statement = parse_limit_statement(&mut context, &mut env, &tokens, &mut position);
         if statement.is_ok() {
             assert!(false);
         }
@@ -3475,18 +6376,18 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Limit,
-                literal: "LIMIT".to_string(),
+                literal: "LIMIT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "-1".to_string(),
+                literal: "-1".into(),
             },
         ];
 
         let mut position = 0;
 
-        let statement = parse_limit_statement(&tokens, &mut position);
+        let statement = parse_limit_statement(&mut context, &mut env, &tokens, &mut position);
         if statement.is_ok() {
             assert!(false);
         }
@@ -3496,21 +6397,80 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Limit,
-                literal: "LIMIT".to_string(),
+                literal: "LIMIT".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
         let mut position = 0;
 
-        let statement = parse_limit_statement(&tokens, &mut position);
+        let statement = parse_limit_statement(&mut context, &mut env, &tokens, &mut position);
         if statement.is_err() {
             assert!(false);
         }
+
+        // LIMIT 10 PERCENT
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::Limit,
+                literal: "LIMIT".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::Integer,
+                literal: "10".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::Percent,
+                literal: "PERCENT".into(),
+            },
+        ];
+
+        let mut position = 0;
+
+        let statement = parse_limit_statement(&mut context, &mut env, &tokens, &mut position);
+        if let Ok(statement) = statement {
+            let limit = statement.as_any().downcast_ref::<LimitStatement>().unwrap();
+            if let LimitCount::Ratio(ratio) = limit.count {
+                assert_eq!(ratio, 0.1);
+            } else {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+
+        // LIMIT 200 PERCENT
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::Limit,
+                literal: "LIMIT".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::Integer,
+                literal: "200".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::Percent,
+                literal: "PERCENT".into(),
+            },
+        ];
+
+        let mut position = 0;
+
+        let statement = parse_limit_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_ok() {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -3519,7 +6479,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Offset,
-            literal: "OFFSET".to_string(),
+            literal: "OFFSET".into(),
         }];
 
         let mut position = 0;
@@ -3534,12 +6494,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Offset,
-                literal: "OFFSET".to_string(),
+                literal: "OFFSET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "-1".to_string(),
+                literal: "-1".into(),
             },
         ];
 
@@ -3555,12 +6515,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Offset,
-                literal: "OFFSET".to_string(),
+                literal: "OFFSET".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -3579,13 +6539,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // ORDER
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Order,
-            literal: "ORDER".to_string(),
+            literal: "ORDER".into(),
         }];
 
         let mut position = 0;
@@ -3600,17 +6570,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Order,
-                literal: "ORDER".to_string(),
+                literal: "ORDER".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::By,
-                literal: "BY".to_string(),
+                literal: "BY".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
         ];
 
@@ -3620,33 +6590,201 @@ mod tests {
         if statement.is_err() {
             assert!(false);
         }
-    }
-
-    #[test]
-    fn test_parse_expression() {
-        let mut context = ParserContext::default();
-        let mut env = Environment {
-            globals: Default::default(),
-            globals_types: Default::default(),
-            scopes: Default::default(),
-        };
 
-        // commit_count > -1
+        // ORDER BY ALL DESC
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::Order,
+                literal: "ORDER".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::By,
+                literal: "BY".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::All,
+                literal: "ALL".into(),
+            },
+            Token {
+                location: Location { start: 4, end: 5 },
+                kind: TokenKind::Descending,
+                literal: "DESC".into(),
+            },
+        ];
+
+        let mut position = 0;
+        context.selected_titles = vec!["name".to_string(), "email".to_string()];
+
+        let statement = parse_order_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if let Ok(statement) = statement {
+            let order_by = statement
+                .as_any()
+                .downcast_ref::<OrderByStatement>()
+                .unwrap();
+            assert_eq!(order_by.arguments.len(), 2);
+            assert!(order_by
+                .sorting_orders
+                .iter()
+                .all(|order| *order == SortingOrder::Descending));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_order_by_ordinal_positions() {
+        let mut env = Environment::default();
+        let query = "SELECT name, email AS mail FROM commits ORDER BY 2 DESC, 1, 1 + 1";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+
+        let statement = query.statements.get("order").unwrap();
+        let order_by = statement
+            .as_any()
+            .downcast_ref::<OrderByStatement>()
+            .unwrap();
+        assert_eq!(order_by.arguments.len(), 3);
+        assert!(order_by.sorting_orders[0] == SortingOrder::Descending);
+
+        let symbol = |index: usize| {
+            order_by.arguments[index]
+                .as_any()
+                .downcast_ref::<SymbolExpression>()
+                .map(|symbol| symbol.value.to_string())
+        };
+        assert_eq!(symbol(0), Some("mail".to_string()));
+        assert_eq!(symbol(1), Some("name".to_string()));
+
+        // Integer inside expression is a value not a position
+        assert!(symbol(2).is_none());
+
+        // Positions out of the select list range
+        for query in [
+            "SELECT name FROM commits ORDER BY 0",
+            "SELECT name FROM commits ORDER BY 2",
+            "SELECT name, email FROM commits ORDER BY name, 3 DESC",
+        ] {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            let result = parse_gql(tokens, &mut env);
+            assert!(result.is_err());
+            assert!(result
+                .err()
+                .unwrap()
+                .message()
+                .contains("is not in select list"));
+        }
+    }
+
+    #[test]
+    fn test_parse_order_by_statement_in_aggregated_query() {
+        let mut context = ParserContext {
+            has_group_by_statement: true,
+            group_by_fields: vec!["name".to_string()],
+            ..Default::default()
+        };
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let order_by_tokens = |field: &str| {
+            vec![
+                Token {
+                    location: Location { start: 1, end: 2 },
+                    kind: TokenKind::Order,
+                    literal: "ORDER".into(),
+                },
+                Token {
+                    location: Location { start: 2, end: 3 },
+                    kind: TokenKind::By,
+                    literal: "BY".into(),
+                },
+                Token {
+                    location: Location { start: 3, end: 4 },
+                    kind: TokenKind::Symbol,
+                    literal: field.to_string().into(),
+                },
+            ]
+        };
+
+        // ORDER BY name
+        let tokens = order_by_tokens("name");
+        let mut position = 0;
+        let statement = parse_order_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_err() {
+            assert!(false);
+        }
+
+        // ORDER BY email
+        let tokens = order_by_tokens("email");
+        let mut position = 0;
+        let statement = parse_order_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_ok() {
+            assert!(false);
+        }
+
+        // ORDER BY email in any value mode
+        context.options.any_value_mode = true;
+        let tokens = order_by_tokens("email");
+        let mut position = 0;
+        let statement = parse_order_by_statement(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_err() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let mut context = ParserContext::default();
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        // commit_count > -1
         let tokens = vec![
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "-1".to_string(),
+                literal: "-1".into(),
             },
         ];
 
@@ -3665,6 +6803,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count := 1
@@ -3672,17 +6820,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::GlobalVariable,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::ColonEqual,
-                literal: ":=".to_string(),
+                literal: ":=".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -3701,6 +6849,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // 1 IS
@@ -3708,12 +6866,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Is,
-                literal: "IS".to_string(),
+                literal: "IS".into(),
             },
         ];
 
@@ -3729,17 +6887,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Is,
-                literal: "IS".to_string(),
+                literal: "IS".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Null,
-                literal: "NULL".to_string(),
+                literal: "NULL".into(),
             },
         ];
 
@@ -3755,22 +6913,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Is,
-                literal: "IS".to_string(),
+                literal: "IS".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Not,
-                literal: "NOT".to_string(),
+                literal: "NOT".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Null,
-                literal: "NULL".to_string(),
+                literal: "NULL".into(),
             },
         ];
 
@@ -3789,6 +6947,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // "One" IN
@@ -3796,12 +6964,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::In,
-                literal: "IN".to_string(),
+                literal: "IN".into(),
             },
         ];
 
@@ -3817,37 +6985,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::In,
-                literal: "IN".to_string(),
+                literal: "IN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -3863,37 +7031,103 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::In,
-                literal: "IN".to_string(),
+                literal: "IN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::String,
-                literal: "Two".to_string(),
+                literal: "Two".into(),
+            },
+            Token {
+                location: Location { start: 7, end: 8 },
+                kind: TokenKind::RightParen,
+                literal: ")".into(),
+            },
+        ];
+
+        let mut position = 0;
+
+        let statement = parse_in_expression(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_err() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_in_expression_with_null_and_empty_list() {
+        let mut context = ParserContext::default();
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        // "One" IN ("One", NULL)
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::String,
+                literal: "One".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::In,
+                literal: "IN".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::LeftParen,
+                literal: "(".into(),
+            },
+            Token {
+                location: Location { start: 4, end: 5 },
+                kind: TokenKind::String,
+                literal: "One".into(),
+            },
+            Token {
+                location: Location { start: 5, end: 6 },
+                kind: TokenKind::Comma,
+                literal: ",".into(),
+            },
+            Token {
+                location: Location { start: 6, end: 7 },
+                kind: TokenKind::Null,
+                literal: "NULL".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -3903,6 +7137,48 @@ mod tests {
         if statement.is_err() {
             assert!(false);
         }
+
+        // "One" IN ()
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::String,
+                literal: "One".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::In,
+                literal: "IN".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::LeftParen,
+                literal: "(".into(),
+            },
+            Token {
+                location: Location { start: 4, end: 5 },
+                kind: TokenKind::RightParen,
+                literal: ")".into(),
+            },
+        ];
+
+        let mut position = 0;
+
+        let statement = parse_in_expression(&mut context, &mut env, &tokens, &mut position);
+        match statement {
+            Ok(expression) => assert!(expression.kind() == ExpressionKind::Boolean),
+            Err(_) => assert!(false),
+        }
+
+        // Empty list is not allowed in ANSI mode
+        context.options.ansi_mode = true;
+
+        let mut position = 0;
+
+        let statement = parse_in_expression(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_ok() {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -3912,6 +7188,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count BETWEEN
@@ -3919,12 +7205,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Between,
-                literal: "BETWEEN".to_string(),
+                literal: "BETWEEN".into(),
             },
         ];
 
@@ -3940,17 +7226,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Between,
-                literal: "BETWEEN".to_string(),
+                literal: "BETWEEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
         ];
 
@@ -3966,27 +7252,27 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Between,
-                literal: "BETWEEN".to_string(),
+                literal: "BETWEEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::DotDot,
-                literal: "..".to_string(),
+                literal: "..".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::String,
-                literal: "invalid".to_string(),
+                literal: "invalid".into(),
             },
         ];
 
@@ -4002,27 +7288,27 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Between,
-                literal: "BETWEEN".to_string(),
+                literal: "BETWEEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::DotDot,
-                literal: "..".to_string(),
+                literal: "..".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Integer,
-                literal: "30000".to_string(),
+                literal: "30000".into(),
             },
         ];
 
@@ -4041,6 +7327,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count > 0 || commit_count < 0
@@ -4048,37 +7344,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalOr,
-                literal: "||".to_string(),
+                literal: "||".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4094,37 +7390,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalOr,
-                literal: "OR".to_string(),
+                literal: "OR".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4143,6 +7439,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count > 0 && commit_count < 0
@@ -4150,37 +7456,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalAnd,
-                literal: "&&".to_string(),
+                literal: "&&".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4196,37 +7502,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalAnd,
-                literal: "AND".to_string(),
+                literal: "AND".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4246,44 +7552,34 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
-        // commit_count > 0 | commit_count < 0
+        // commit_count | 1
         let tokens = vec![
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
-                kind: TokenKind::Greater,
-                literal: ">".to_string(),
-            },
-            Token {
-                location: Location { start: 3, end: 4 },
-                kind: TokenKind::Integer,
-                literal: "0".to_string(),
-            },
-            Token {
-                location: Location { start: 4, end: 5 },
                 kind: TokenKind::BitwiseOr,
-                literal: "|".to_string(),
-            },
-            Token {
-                location: Location { start: 5, end: 6 },
-                kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "|".into(),
             },
             Token {
-                location: Location { start: 6, end: 7 },
-                kind: TokenKind::Less,
-                literal: "<".to_string(),
-            },
-            Token {
-                location: Location { start: 7, end: 8 },
+                location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4302,6 +7598,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count > 0 ^ commit_count < 0
@@ -4309,37 +7615,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalXor,
-                literal: "^".to_string(),
+                literal: "^".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4356,37 +7662,37 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::LogicalXor,
-                literal: "XOR".to_string(),
+                literal: "XOR".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4406,44 +7712,34 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
-        // commit_count > 0 & commit_count < 0
+        // commit_count & 1
         let tokens = vec![
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
-                kind: TokenKind::Greater,
-                literal: ">".to_string(),
-            },
-            Token {
-                location: Location { start: 3, end: 4 },
-                kind: TokenKind::Integer,
-                literal: "0".to_string(),
-            },
-            Token {
-                location: Location { start: 4, end: 5 },
                 kind: TokenKind::BitwiseAnd,
-                literal: "&".to_string(),
-            },
-            Token {
-                location: Location { start: 5, end: 6 },
-                kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
-            },
-            Token {
-                location: Location { start: 6, end: 7 },
-                kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "&".into(),
             },
             Token {
-                location: Location { start: 7, end: 8 },
+                location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4463,6 +7759,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count = 0
@@ -4470,17 +7776,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4496,17 +7802,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::BangEqual,
-                literal: "!=".to_string(),
+                literal: "!=".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4522,17 +7828,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::BangEqual,
-                literal: "<>".to_string(),
+                literal: "<>".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4551,6 +7857,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count > 0
@@ -4558,17 +7874,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4584,17 +7900,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::GreaterEqual,
-                literal: ">=".to_string(),
+                literal: ">=".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4610,17 +7926,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4636,17 +7952,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::LessEqual,
-                literal: "<=".to_string(),
+                literal: "<=".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4662,17 +7978,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::NullSafeEqual,
-                literal: "<=>".to_string(),
+                literal: "<=>".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
         ];
 
@@ -4691,6 +8007,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // commit_count << 1
@@ -4698,17 +8024,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::BitwiseLeftShift,
-                literal: "<<".to_string(),
+                literal: "<<".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4725,17 +8051,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::BitwiseRightShift,
-                literal: ">>".to_string(),
+                literal: ">>".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4755,6 +8081,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // 1 + 1
@@ -4762,17 +8098,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Plus,
-                literal: "+".to_string(),
+                literal: "+".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4788,17 +8124,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Minus,
-                literal: "-".to_string(),
+                literal: "-".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4808,6 +8144,34 @@ mod tests {
         if statement.is_err() {
             assert!(false);
         }
+
+        // "a" || "b"
+        let tokens = vec![
+            Token {
+                location: Location { start: 1, end: 2 },
+                kind: TokenKind::String,
+                literal: "a".into(),
+            },
+            Token {
+                location: Location { start: 2, end: 3 },
+                kind: TokenKind::Concat,
+                literal: "||".into(),
+            },
+            Token {
+                location: Location { start: 3, end: 4 },
+                kind: TokenKind::String,
+                literal: "b".into(),
+            },
+        ];
+
+        let mut position = 0;
+
+        let statement = parse_term_expression(&mut context, &mut env, &tokens, &mut position);
+        if let Ok(expression) = statement {
+            assert!(expression.kind() == ExpressionKind::Call);
+        } else {
+            assert!(false);
+        }
     }
 
     #[test]
@@ -4817,6 +8181,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // 1 * 2
@@ -4824,17 +8198,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Star,
-                literal: "*".to_string(),
+                literal: "*".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
         ];
 
@@ -4850,17 +8224,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Slash,
-                literal: "/".to_string(),
+                literal: "/".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
         ];
 
@@ -4876,17 +8250,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Percentage,
-                literal: "%".to_string(),
+                literal: "%".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "2".to_string(),
+                literal: "2".into(),
             },
         ];
 
@@ -4905,6 +8279,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // "10 usd" LIKE 1
@@ -4912,17 +8296,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "10 usd".to_string(),
+                literal: "10 usd".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Like,
-                literal: "LIKE".to_string(),
+                literal: "LIKE".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -4938,17 +8322,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "10 usd".to_string(),
+                literal: "10 usd".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Like,
-                literal: "LIKE".to_string(),
+                literal: "LIKE".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::String,
-                literal: "[0-9]* usd".to_string(),
+                literal: "[0-9]* usd".into(),
             },
         ];
 
@@ -4967,6 +8351,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // "Git Query Language" GLOB 1
@@ -4974,17 +8368,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "Git Query Language".to_string(),
+                literal: "Git Query Language".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Glob,
-                literal: "GLOB".to_string(),
+                literal: "GLOB".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -5000,17 +8394,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::String,
-                literal: "Git Query Language".to_string(),
+                literal: "Git Query Language".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Glob,
-                literal: "GLOB".to_string(),
+                literal: "GLOB".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::String,
-                literal: "Git*".to_string(),
+                literal: "Git*".into(),
             },
         ];
 
@@ -5022,6 +8416,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_unary_expression_depth_limit() {
+        let mut context = ParserContext {
+            options: ParserOptions {
+                max_expression_depth: 4,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let nested_bang_tokens = |count: usize| {
+            let mut tokens: Vec<Token> = (0..count)
+                .map(|index| Token {
+                    location: Location {
+                        start: index,
+                        end: index + 1,
+                    },
+                    kind: TokenKind::Bang,
+                    literal: "!".into(),
+                })
+                .collect();
+            tokens.push(Token {
+                location: Location {
+                    start: count,
+                    end: count + 4,
+                },
+                kind: TokenKind::True,
+                literal: "true".into(),
+            });
+            tokens
+        };
+
+        // !!!true
+        let tokens = nested_bang_tokens(3);
+        let mut position = 0;
+        let statement = parse_unary_expression(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_err() {
+            assert!(false);
+        }
+        assert_eq!(context.expression_depth, 0);
+
+        // !!!!!true
+        let tokens = nested_bang_tokens(5);
+        let mut position = 0;
+        let statement = parse_unary_expression(&mut context, &mut env, &tokens, &mut position);
+        if statement.is_ok() {
+            assert!(false);
+        }
+    }
+
     #[test]
     fn test_parse_unary_expression() {
         let mut context = ParserContext::default();
@@ -5029,6 +8488,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // !1
@@ -5036,12 +8505,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Bang,
-                literal: "!".to_string(),
+                literal: "!".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -5057,12 +8526,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Minus,
-                literal: "-".to_string(),
+                literal: "-".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "is_remote".to_string(),
+                literal: "is_remote".into(),
             },
         ];
 
@@ -5078,12 +8547,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Bang,
-                literal: "!".to_string(),
+                literal: "!".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "is_remote".to_string(),
+                literal: "is_remote".into(),
             },
         ];
 
@@ -5099,12 +8568,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Minus,
-                literal: "-".to_string(),
+                literal: "-".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
         ];
 
@@ -5123,6 +8592,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // invalid(name)
@@ -5130,22 +8609,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "invalid".to_string(),
+                literal: "invalid".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5162,22 +8641,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "lower".to_string(),
+                literal: "lower".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5194,22 +8673,22 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Symbol,
-                literal: "max".to_string(),
+                literal: "max".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Symbol,
-                literal: "commit_count".to_string(),
+                literal: "commit_count".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5229,6 +8708,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // (name]
@@ -5236,17 +8725,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::String,
-                literal: "]".to_string(),
+                literal: "]".into(),
             },
         ];
 
@@ -5262,17 +8751,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "name".to_string(),
+                literal: "name".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5288,27 +8777,27 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Symbol,
-                literal: "name1".to_string(),
+                literal: "name1".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::Comma,
-                literal: ",".to_string(),
+                literal: ",".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Symbol,
-                literal: "name2".to_string(),
+                literal: "name2".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5327,13 +8816,23 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // name
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::String,
-            literal: "name".to_string(),
+            literal: "name".into(),
         }];
 
         let mut position = 0;
@@ -5347,7 +8846,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "name".to_string(),
+            literal: "name".into(),
         }];
 
         let mut position = 0;
@@ -5361,7 +8860,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::GlobalVariable,
-            literal: "name".to_string(),
+            literal: "name".into(),
         }];
 
         let mut position = 0;
@@ -5375,7 +8874,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Integer,
-            literal: "1".to_string(),
+            literal: "1".into(),
         }];
 
         let mut position = 0;
@@ -5389,7 +8888,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Float,
-            literal: "1.0".to_string(),
+            literal: "1.0".into(),
         }];
 
         let mut position = 0;
@@ -5403,7 +8902,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::True,
-            literal: "TRUE".to_string(),
+            literal: "TRUE".into(),
         }];
 
         let mut position = 0;
@@ -5417,7 +8916,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::False,
-            literal: "FALSE".to_string(),
+            literal: "FALSE".into(),
         }];
 
         let mut position = 0;
@@ -5431,7 +8930,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Null,
-            literal: "NULL".to_string(),
+            literal: "NULL".into(),
         }];
 
         let mut position = 0;
@@ -5446,17 +8945,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5472,42 +8971,42 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Case,
-                literal: "CASE".to_string(),
+                literal: "CASE".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::When,
-                literal: "WHEN".to_string(),
+                literal: "WHEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::True,
-                literal: "isRemote".to_string(),
+                literal: "isRemote".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Then,
-                literal: "THEN".to_string(),
+                literal: "THEN".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Else,
-                literal: "ELSE".to_string(),
+                literal: "ELSE".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 8, end: 9 },
                 kind: TokenKind::End,
-                literal: "END".to_string(),
+                literal: "END".into(),
             },
         ];
 
@@ -5522,7 +9021,7 @@ mod tests {
         let tokens = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Star,
-            literal: "*".to_string(),
+            literal: "*".into(),
         }];
 
         let mut position = 0;
@@ -5540,6 +9039,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // ("One"(
@@ -5547,17 +9056,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
         ];
 
@@ -5573,17 +9082,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::String,
-                literal: "One".to_string(),
+                literal: "One".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
 
@@ -5602,6 +9111,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // CASE WHEN isRemote
@@ -5609,17 +9128,17 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Case,
-                literal: "CASE".to_string(),
+                literal: "CASE".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::When,
-                literal: "WHEN".to_string(),
+                literal: "WHEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::True,
-                literal: "isRemote".to_string(),
+                literal: "isRemote".into(),
             },
         ];
 
@@ -5635,42 +9154,42 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Case,
-                literal: "CASE".to_string(),
+                literal: "CASE".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::When,
-                literal: "WHEN".to_string(),
+                literal: "WHEN".into(),
             },
             Token {
                 location: Location { start: 3, end: 4 },
                 kind: TokenKind::True,
-                literal: "isRemote".to_string(),
+                literal: "isRemote".into(),
             },
             Token {
                 location: Location { start: 4, end: 5 },
                 kind: TokenKind::Then,
-                literal: "THEN".to_string(),
+                literal: "THEN".into(),
             },
             Token {
                 location: Location { start: 5, end: 6 },
                 kind: TokenKind::Integer,
-                literal: "1".to_string(),
+                literal: "1".into(),
             },
             Token {
                 location: Location { start: 6, end: 7 },
                 kind: TokenKind::Else,
-                literal: "ELSE".to_string(),
+                literal: "ELSE".into(),
             },
             Token {
                 location: Location { start: 7, end: 8 },
                 kind: TokenKind::Integer,
-                literal: "0".to_string(),
+                literal: "0".into(),
             },
             Token {
                 location: Location { start: 8, end: 9 },
                 kind: TokenKind::End,
-                literal: "END".to_string(),
+                literal: "END".into(),
             },
         ];
 
@@ -5688,6 +9207,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // lower(invalid)
@@ -5860,6 +9389,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         // invalid
@@ -5868,7 +9407,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "invalid".to_string(),
+            literal: "invalid".into(),
         }];
         let position = 0;
 
@@ -5888,7 +9427,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "invalid".to_string(),
+            literal: "invalid".into(),
         }];
         let position = 0;
 
@@ -5907,7 +9446,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "commits".to_string(),
+            literal: "commits".into(),
         }];
         let position = 0;
 
@@ -5927,7 +9466,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 0, end: 0 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         }];
         let mut position = 0;
 
@@ -5938,7 +9477,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         }];
         let mut position = 0;
 
@@ -5952,7 +9491,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 0, end: 0 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         }];
         let mut position = 0;
 
@@ -5967,12 +9506,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Descending,
-                literal: "DESC".to_string(),
+                literal: "DESC".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Ascending,
-                literal: "ASC".to_string(),
+                literal: "ASC".into(),
             },
         ];
         let mut position = 1;
@@ -5988,12 +9527,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Equal,
-                literal: "==".to_string(),
+                literal: "==".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Equal,
-                literal: "==".to_string(),
+                literal: "==".into(),
             },
         ];
         let mut position = 1;
@@ -6009,12 +9548,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Equal,
-                literal: "=".to_string(),
+                literal: "=".into(),
             },
         ];
         let mut position = 1;
@@ -6027,12 +9566,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
         ];
         let mut position = 1;
@@ -6045,12 +9584,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::Less,
-                literal: "<".to_string(),
+                literal: "<".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::Greater,
-                literal: ">".to_string(),
+                literal: ">".into(),
             },
         ];
         let mut position = 1;
@@ -6063,12 +9602,12 @@ mod tests {
             Token {
                 location: Location { start: 1, end: 2 },
                 kind: TokenKind::LeftParen,
-                literal: "(".to_string(),
+                literal: "(".into(),
             },
             Token {
                 location: Location { start: 2, end: 3 },
                 kind: TokenKind::RightParen,
-                literal: ")".to_string(),
+                literal: ")".into(),
             },
         ];
         let mut position = 1;
@@ -6087,7 +9626,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "invalid".to_string(),
+            literal: "invalid".into(),
         }];
         let mut position = 0;
 
@@ -6141,6 +9680,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
 
         register_current_table_fields_types(&table_name, &mut env);
@@ -6158,7 +9707,9 @@ mod tests {
         let mut fields_names: Vec<String> = vec![];
         let mut fields_values: Vec<Box<dyn Expression>> = vec![];
 
+        let env = Environment::default();
         select_all_table_fields(
+            &env,
             &table_name,
             &mut selected_fields,
             &mut fields_names,
@@ -6177,7 +9728,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         }];
 
         // position = 1
@@ -6213,7 +9764,7 @@ mod tests {
         let tokens: Vec<Token> = vec![Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         }];
 
         // position = 0
@@ -6237,7 +9788,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_assignment_operator(&tokens);
@@ -6247,7 +9798,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Equal,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_assignment_operator(&tokens);
@@ -6257,7 +9808,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::ColonEqual,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_assignment_operator(&tokens);
@@ -6270,7 +9821,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_term_operator(&tokens);
@@ -6280,7 +9831,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Plus,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_term_operator(&tokens);
@@ -6290,7 +9841,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Minus,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_term_operator(&tokens);
@@ -6303,7 +9854,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_bitwise_shift_operator(&tokens);
@@ -6313,7 +9864,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::BitwiseLeftShift,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_bitwise_shift_operator(&tokens);
@@ -6323,7 +9874,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::BitwiseRightShift,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_bitwise_shift_operator(&tokens);
@@ -6336,7 +9887,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_prefix_unary_operator(&tokens);
@@ -6346,7 +9897,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Bang,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_prefix_unary_operator(&tokens);
@@ -6356,7 +9907,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Minus,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_prefix_unary_operator(&tokens);
@@ -6369,7 +9920,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6379,7 +9930,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Greater,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6389,7 +9940,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::GreaterEqual,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6399,7 +9950,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Less,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6409,7 +9960,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::LessEqual,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6419,7 +9970,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::NullSafeEqual,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_comparison_operator(&tokens);
@@ -6432,7 +9983,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_factor_operator(&tokens);
@@ -6442,7 +9993,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Star,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_factor_operator(&tokens);
@@ -6452,7 +10003,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Slash,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_factor_operator(&tokens);
@@ -6462,7 +10013,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Percentage,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_factor_operator(&tokens);
@@ -6475,7 +10026,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Symbol,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_asc_or_desc(&tokens);
@@ -6485,7 +10036,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Ascending,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_asc_or_desc(&tokens);
@@ -6495,7 +10046,7 @@ mod tests {
         let tokens = Token {
             location: Location { start: 1, end: 2 },
             kind: TokenKind::Descending,
-            literal: "select".to_string(),
+            literal: "select".into(),
         };
 
         let status = is_asc_or_desc(&tokens);
@@ -6503,15 +10054,1073 @@ mod tests {
     }
 
     #[test]
-    fn test_type_mismatch_error() {
-        let location = Location { start: 1, end: 2 };
-        let expected = DataType::Text;
-        let actual = DataType::Integer;
+    fn test_select_without_table_titles() {
+        let mut env = Environment::default();
+        let query = "SELECT 1 + 2, -1, upper(\"gql\"), 4 AS four";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = parse_gql(tokens, &mut env).ok().unwrap();
+        let query = match query {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
 
-        let status = type_mismatch_error(location, expected, actual);
-        assert_eq!(
-            "Type mismatch expected `Text`, got `Integer`",
-            status.message()
-        );
+        let statement = query.statements.get("select").unwrap();
+        let statement = statement
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+
+        let titles: Vec<&String> = statement
+            .fields_names
+            .iter()
+            .map(|name| statement.alias_table.get(name).unwrap_or(name))
+            .collect();
+        assert_eq!(titles, vec!["1 + 2", "-1", "upper(\"gql\")", "four"]);
+    }
+
+    #[test]
+    fn test_select_without_table_fields() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        // Selected aliases can be used by other statements
+        assert!(parse(&mut env, "SELECT 1 AS a ORDER BY a").is_ok());
+        assert!(parse(&mut env, "SELECT upper(\"gql\") AS name ORDER BY name").is_ok());
+
+        let errors = [
+            (
+                "SELECT name",
+                "Can't use field `name` in query without `FROM` table",
+            ),
+            (
+                "SELECT 1 + commit_count",
+                "Can't use field `commit_count` in query without `FROM` table",
+            ),
+            (
+                "SELECT 1 AS a ORDER BY title",
+                "Can't use field `title` in query without `FROM` table",
+            ),
+        ];
+
+        for (query, message) in errors {
+            let error = parse(&mut env, query).err().unwrap();
+            assert_eq!(error.message(), message);
+        }
+    }
+
+    #[test]
+    fn test_parse_typed_set_query() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        assert!(parse(&mut env, "SET @limit INT = 10").is_ok());
+        assert!(env.globals_types.get("@limit") == Some(&DataType::Integer));
+
+        // Text literal is implicitly casted to the declared type
+        assert!(parse(&mut env, "SET @since Date = \"2024-01-01\"").is_ok());
+        assert!(env.globals_types.get("@since").is_some_and(|t| t.is_date()));
+
+        // Redefine with the same type or NULL value
+        assert!(parse(&mut env, "SET @limit = 20").is_ok());
+        assert!(parse(&mut env, "SET @limit INTEGER = NULL").is_ok());
+
+        let error = |env: &mut Environment, query: &str| {
+            parse(env, query).err().unwrap().message().to_string()
+        };
+
+        assert_eq!(
+            error(&mut env, "SET @x NUMBER = 1"),
+            "Unknown type name `NUMBER`"
+        );
+        assert_eq!(
+            error(&mut env, "SET @x INT = \"gql\""),
+            "Can't assign value of type Text to global variable `@x` of type Integer"
+        );
+        assert_eq!(
+            error(&mut env, "SET @limit TEXT = \"gql\""),
+            "Global variable `@limit` is already defined with type Integer"
+        );
+        assert_eq!(
+            error(&mut env, "SET @limit = \"gql\""),
+            "Can't assign value of type Text to global variable `@limit` of type Integer"
+        );
+    }
+
+    #[test]
+    fn test_parse_range_expression() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let query = "SELECT title FROM commits WHERE RANGE(\"v1.0..v2.0\") AND name = \"amr\"";
+        assert!(parse(&mut env, query).is_ok());
+
+        let errors = [
+            (
+                "SELECT name FROM branches WHERE RANGE(\"v1.0..v2.0\")",
+                "`RANGE` can only be used with `commits` table",
+            ),
+            (
+                "SELECT RANGE(\"v1.0..v2.0\") FROM commits",
+                "`RANGE` can only be used in `WHERE` condition",
+            ),
+            (
+                "SELECT title FROM commits WHERE RANGE(\"v1.0..v2.0\") OR name = \"amr\"",
+                "`RANGE` can't be negated or combined with other conditions using `OR` or `XOR`",
+            ),
+            (
+                "SELECT title FROM commits WHERE NOT RANGE(\"v1.0..v2.0\")",
+                "`RANGE` can't be negated or combined with other conditions using `OR` or `XOR`",
+            ),
+            (
+                "SELECT title FROM commits WHERE RANGE(1)",
+                "`RANGE` expects one string revision range argument",
+            ),
+        ];
+
+        for (query, message) in errors {
+            let error = parse(&mut env, query).err().unwrap();
+            assert_eq!(error.message(), message);
+        }
+    }
+
+    #[test]
+    fn test_parse_blame_path_condition() {
+        let hidden_selections = |query: &str| {
+            let mut env = Environment::default();
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            match parse_gql(tokens, &mut env).ok().unwrap() {
+                Query::Select(query) => query.hidden_selections,
+                _ => panic!("Expect select query"),
+            }
+        };
+
+        let blamed_path = format!("{}src/main.rs", BLAME_PATH_FIELD_PREFIX);
+        let query = "SELECT line FROM blame WHERE line_number > 1 AND \"src/main.rs\" = path";
+        assert!(hidden_selections(query).contains(&blamed_path));
+
+        // Path can't be pushed down if other rows can match the condition
+        for query in [
+            "SELECT line FROM blame WHERE path = \"src/main.rs\" OR line_number = 1",
+            "SELECT line FROM blame WHERE path != \"src/main.rs\"",
+            "SELECT name FROM branches WHERE name = \"src/main.rs\"",
+        ] {
+            let hidden_selections = hidden_selections(query);
+            assert!(!hidden_selections
+                .iter()
+                .any(|name| name.starts_with(BLAME_PATH_FIELD_PREFIX)));
+        }
+    }
+
+    #[test]
+    fn test_parse_logical_not_expression() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let query =
+            "SELECT title FROM commits WHERE NOT title = \"x\" AND NOT NOT name LIKE \"a%\"";
+        assert!(parse(&mut env, query).is_ok());
+
+        let query = "SELECT title FROM commits WHERE NOT title";
+        assert!(parse(&mut env, query).is_err());
+    }
+
+    #[test]
+    fn test_parse_create_temp_table() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let query = "CREATE TEMP TABLE hot AS SELECT name, COUNT(name) AS changes FROM commits GROUP BY name";
+        match parse(&mut env, query) {
+            Ok(Query::CreateTempTable(statement)) => {
+                assert_eq!(statement.table_name, "hot");
+                assert_eq!(statement.columns.len(), 2);
+                assert_eq!(statement.columns[0].0, "name");
+                assert!(statement.columns[0].1 == DataType::Text);
+                assert_eq!(statement.columns[1].0, "changes");
+                assert!(statement.columns[1].1 == DataType::Integer);
+            }
+            _ => panic!("Expect create temp table query"),
+        }
+
+        let error = parse(&mut env, "CREATE TABLE hot AS SELECT 1")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Expect `TEMP` after `CREATE` keyword");
+
+        let error = parse(&mut env, "CREATE TEMPORARY TABLE commits AS SELECT 1")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Table `commits` already exists");
+
+        let error = parse(&mut env, "CREATE TEMP TABLE hot SELECT 1")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Expect `AS` and `SELECT` query after temporary table name"
+        );
+    }
+
+    #[test]
+    fn test_parse_insert() {
+        let mut env = Environment::default();
+        let fields = vec![("name".to_string(), DataType::Text)];
+        env.define_temp_table("hot", fields, Default::default());
+
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        match parse(&mut env, "INSERT INTO hot SELECT name FROM commits") {
+            Ok(Query::Insert(statement)) => {
+                assert!(matches!(statement.target, InsertTarget::TempTable(name) if name == "hot"));
+            }
+            _ => panic!("Expect insert query"),
+        }
+
+        match parse(
+            &mut env,
+            "INSERT INTO FILE \"out.csv\" SELECT name FROM commits",
+        ) {
+            Ok(Query::Insert(statement)) => {
+                assert!(matches!(
+                    statement.target,
+                    InsertTarget::File(path, FileFormat::CSV) if path == "out.csv"
+                ));
+            }
+            _ => panic!("Expect insert query"),
+        }
+
+        let error = parse(&mut env, "INSERT INTO commits SELECT name FROM commits")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Can't insert into `commits` because it's not a temporary table"
+        );
+
+        let error = parse(&mut env, "INSERT INTO hot SELECT name, email FROM commits")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Temporary table `hot` has 1 columns but the query selects 2 columns"
+        );
+
+        let error = parse(&mut env, "INSERT INTO hot SELECT datetime FROM commits")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Can't insert value of type `DateTime` into column `name` of type `Text`"
+        );
+
+        let error = parse(
+            &mut env,
+            "INSERT INTO FILE \"out.xml\" SELECT name FROM commits",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.message(), "Unsupported file format of `out.xml`");
+    }
+
+    #[test]
+    fn test_parse_table_function() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let query = "SELECT title FROM COMMITS_TOUCHING(\"src/\")";
+        match parse(&mut env, query) {
+            Ok(Query::Select(query)) => {
+                assert!(query
+                    .hidden_selections
+                    .contains(&"touching:src/".to_string()));
+                let select = query.statements.get("select").unwrap();
+                let select = select.as_any().downcast_ref::<SelectStatement>().unwrap();
+                assert_eq!(select.table_name, "commits");
+            }
+            _ => panic!("Expect select query"),
+        }
+
+        let error = parse(&mut env, "SELECT * FROM commits_touch(\"src/\")")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Unresolved table-valued function `commits_touch`"
+        );
+
+        let error = parse(&mut env, "SELECT * FROM commits_touching(1)")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`COMMITS_TOUCHING` expects one string argument"
+        );
+    }
+
+    #[test]
+    fn test_parse_external_table() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let path = std::env::temp_dir().join("gitql_parser_authors.csv");
+        std::fs::write(&path, "name,team\nJane,core\n").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let query = format!(
+            "SELECT a.team, count(name) FROM EXTERNAL(\"{}\") a GROUP BY a.team",
+            path
+        );
+        let result = parse(&mut env, &query);
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Ok(Query::Select(query)) => {
+                let select = query.statements.get("select").unwrap();
+                let select = select.as_any().downcast_ref::<SelectStatement>().unwrap();
+                assert_eq!(select.table_name, format!("external:{}", path));
+            }
+            _ => panic!("Expect select query"),
+        }
+
+        let error = parse(&mut env, "SELECT * FROM EXTERNAL(\"missing.csv\")")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Can't load external table `missing.csv`");
+
+        let options = ParserOptions {
+            allow_external_files: false,
+            ..Default::default()
+        };
+        let tokens = crate::tokenizer::tokenize("SELECT * FROM EXTERNAL(\"a.csv\")")
+            .ok()
+            .unwrap();
+        let error = parse_gql_with_options(tokens, &mut env, options)
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "`EXTERNAL` tables are disabled");
+
+        let fields = vec![("name".to_string(), DataType::Text)];
+        env.define_external_table("authors", fields, Default::default());
+        let error = parse(&mut env, "INSERT INTO authors SELECT name FROM authors")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Can't insert into `authors` because it's not a temporary table"
+        );
+    }
+
+    #[test]
+    fn test_parse_row_number() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let result = parse(
+            &mut env,
+            "SELECT ROW_NUMBER() AS rank, name FROM branches ORDER BY commit_count DESC LIMIT 3",
+        );
+        match result {
+            Ok(Query::Select(query)) => {
+                let statement = query.statements.get("row_number").unwrap();
+                let statement = statement
+                    .as_any()
+                    .downcast_ref::<RowNumberStatement>()
+                    .unwrap();
+                assert_eq!(statement.titles, vec!["rank"]);
+                assert_eq!(query.hidden_selections, vec!["commit_count"]);
+            }
+            _ => panic!("Expect select query"),
+        }
+
+        assert!(parse(
+            &mut env,
+            "SELECT name, ROW_NUMBER() FROM branches GROUP BY name"
+        )
+        .is_ok());
+
+        let error = parse(&mut env, "SELECT name FROM branches WHERE ROW_NUMBER() > 1")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`ROW_NUMBER` can only be used in `SELECT` statement"
+        );
+
+        let error = parse(&mut env, "SELECT ROW_NUMBER() + 1 FROM branches")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "`ROW_NUMBER` can't be used inside other expressions"
+        );
+
+        let error = parse(&mut env, "SELECT ROW_NUMBER(name) FROM branches")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "`ROW_NUMBER` doesn't accept arguments");
+
+        let error = parse(&mut env, "SELECT ROW_NUMBER()").err().unwrap();
+        assert_eq!(error.message(), "`ROW_NUMBER` can't be used without table");
+
+        let error = parse(
+            &mut env,
+            "SELECT ROW_NUMBER() AS n, name FROM branches ORDER BY n",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.message(),
+            "`ROW_NUMBER` column can't be used in `ORDER BY`"
+        );
+    }
+
+    #[test]
+    fn test_parse_sample_statement() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        let result = parse(
+            &mut env,
+            "SELECT title FROM commits SAMPLE 2.5 PERCENT LIMIT 10",
+        );
+        match result {
+            Ok(Query::Select(query)) => {
+                let statement = query.statements.get("sample").unwrap();
+                let statement = statement
+                    .as_any()
+                    .downcast_ref::<SampleStatement>()
+                    .unwrap();
+                assert!(matches!(statement.count, LimitCount::Ratio(ratio) if ratio == 0.025));
+            }
+            _ => panic!("Expect select query"),
+        }
+
+        assert!(parse(&mut env, "SELECT title FROM commits SAMPLE 1000").is_ok());
+
+        let error = parse(&mut env, "SELECT title FROM commits SAMPLE 1.5")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message(),
+            "Expect `SAMPLE` value to be type Integer but got Float"
+        );
+
+        let error = parse(&mut env, "SELECT title FROM commits SAMPLE 10 SAMPLE 20")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "You already used `SAMPLE` statement");
+    }
+
+    #[test]
+    fn test_parse_function_call_overloads() {
+        let mut env = Environment::default();
+        let parse_select = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            match parse_gql(tokens, env) {
+                Ok(Query::Select(query)) => query,
+                _ => panic!("Expect select query"),
+            }
+        };
+        let first_field_type = |env: &Environment, query: &GQLQuery| {
+            let statement = query.statements.get("select").unwrap();
+            let statement = statement
+                .as_any()
+                .downcast_ref::<SelectStatement>()
+                .unwrap();
+            statement.fields_values[0].expr_type(env)
+        };
+
+        let query = parse_select(&mut env, "SELECT ABS(-1)");
+        assert!(first_field_type(&env, &query).is_int());
+
+        let query = parse_select(&mut env, "SELECT ABS(1.5)");
+        assert!(first_field_type(&env, &query).is_float());
+
+        let query = parse_select(&mut env, "SELECT MAX(datetime) FROM commits");
+        assert!(first_field_type(&env, &query).is_datetime());
+
+        let query = parse_select(&mut env, "SELECT MIN(commit_count) FROM branches");
+        assert!(first_field_type(&env, &query).is_int());
+
+        let tokens = crate::tokenizer::tokenize("SELECT ABS(\"text\")")
+            .ok()
+            .unwrap();
+        let error = parse_gql(tokens, &mut env).err().unwrap();
+        assert_eq!(
+            error.message(),
+            "Function `abs` has no overload that accepts arguments `(Text)`"
+        );
+        assert_eq!(error.notes().len(), 2);
+        assert_eq!(error.notes()[1], "Candidate `abs(Float) -> Float`");
+    }
+
+    #[test]
+    fn test_parse_set_session_variable() {
+        let mut env = Environment::default();
+        let parse = |env: &mut Environment, query: &str| {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            parse_gql(tokens, env)
+        };
+
+        assert!(parse(&mut env, "SET @@timezone = \"+02:00\"").is_ok());
+        assert!(env.globals_types.get("@@timezone") == Some(&DataType::Text));
+        assert!(parse(&mut env, "SELECT UPPER(@@null_text)").is_ok());
+
+        let error = parse(&mut env, "SET @@timezon = \"UTC\"").err().unwrap();
+        assert_eq!(error.message(), "Unknown session variable `@@timezon`");
+        assert!(error
+            .helps()
+            .iter()
+            .any(|help| help.contains("`@@timezone`")));
+
+        assert_eq!(
+            parse(&mut env, "SET @@null_text = 1")
+                .err()
+                .unwrap()
+                .message(),
+            "Can't assign value of type Integer to global variable `@@null_text` of type Text"
+        );
+
+        assert!(parse(&mut env, "SET @@seed = 3").is_ok());
+        assert!(env.globals_types.get("@@seed") == Some(&DataType::Integer));
+        assert_eq!(
+            parse(&mut env, "SET @@seed = \"3\"")
+                .err()
+                .unwrap()
+                .message(),
+            "Can't assign value of type Text to global variable `@@seed` of type Integer"
+        );
+    }
+
+    #[test]
+    fn test_parse_explain_query() {
+        let mut env = Environment::default();
+        let tokens = crate::tokenizer::tokenize("EXPLAIN SELECT title FROM commits;")
+            .ok()
+            .unwrap();
+        assert!(matches!(parse_gql(tokens, &mut env), Ok(Query::Explain(_))));
+
+        let tokens = crate::tokenizer::tokenize("EXPLAIN SHOW VARIABLES")
+            .ok()
+            .unwrap();
+        let result = parse_gql(tokens, &mut env);
+        assert_eq!(
+            result.err().unwrap().message(),
+            "Expect `SELECT` query after `EXPLAIN` keyword"
+        );
+    }
+
+    #[test]
+    fn test_parse_show_query() {
+        let mut env = Environment::default();
+        let tokens = crate::tokenizer::tokenize("show Variables;").ok().unwrap();
+        assert!(matches!(
+            parse_gql(tokens, &mut env),
+            Ok(Query::ShowVariables)
+        ));
+
+        let tokens = crate::tokenizer::tokenize("SHOW TABLES").ok().unwrap();
+        let result = parse_gql(tokens, &mut env);
+        assert_eq!(
+            result.err().unwrap().message(),
+            "Expect `VARIABLES` after `SHOW` keyword"
+        );
+    }
+
+    #[test]
+    fn test_parse_group_by_rollup() {
+        let mut env = Environment::default();
+        let query = "SELECT name, COUNT(name) FROM commits GROUP BY Rollup(name, email)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+
+        let statement = query.statements.get("group").unwrap();
+        let group_by = statement
+            .as_any()
+            .downcast_ref::<GroupByStatement>()
+            .unwrap();
+        assert!(group_by.is_rollup);
+        assert_eq!(group_by.field_names, vec!["name", "email"]);
+
+        // Rollup field that is not selected is selected as hidden field
+        assert!(query.hidden_selections.contains(&"email".to_string()));
+
+        for query in [
+            // Selected field is not one of the rollup fields
+            "SELECT name, email FROM commits GROUP BY ROLLUP(name)",
+            // Field is used twice
+            "SELECT name FROM commits GROUP BY ROLLUP(name, name)",
+            // Unknown field
+            "SELECT name FROM commits GROUP BY ROLLUP(name, invalid)",
+            // Missing `)`
+            "SELECT name FROM commits GROUP BY ROLLUP(name",
+        ] {
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            assert!(parse_gql(tokens, &mut env).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_values_table() {
+        let mut env = Environment::default();
+        let query = "SELECT * FROM (VALUES (1, \"a\"), (2, NULL)) AS t(id, name) WHERE id > 1";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+
+        let statement = query.statements.get("select").unwrap();
+        let statement = statement
+            .as_any()
+            .downcast_ref::<SelectStatement>()
+            .unwrap();
+        assert_eq!(statement.table_name, "t");
+        assert_eq!(statement.fields_names, vec!["id", "name"]);
+
+        let values_table = statement.values_table.as_ref().unwrap();
+        assert_eq!(values_table.columns, vec!["id", "name"]);
+        assert_eq!(values_table.rows.len(), 2);
+
+        // Rows with different number of values
+        let query = "SELECT * FROM (VALUES (1, 2), (3)) AS t";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        // Column with different types
+        let query = "SELECT * FROM (VALUES (1), (\"a\")) AS t";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        // Missing table name
+        let query = "SELECT * FROM (VALUES (1))";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_table_alias() {
+        let mut env = Environment::default();
+        let query = "SELECT c.title FROM commits c WHERE c.name = \"gql\"";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+        assert_eq!(env.resolve_table_alias("c"), "commits");
+
+        let mut env = Environment::default();
+        let query = "SELECT commits.title FROM commits AS c";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        // Unknown qualifier
+        let mut env = Environment::default();
+        let query = "SELECT x.title FROM commits c";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_subquery_table() {
+        let mut env = Environment::default();
+        let query = "SELECT sub.n FROM (SELECT name AS n FROM branches WHERE is_head) AS sub";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+        assert!(query.subquery.is_some());
+
+        // Field that is not selected in the subquery
+        let mut env = Environment::default();
+        let query = "SELECT title FROM (SELECT name FROM branches) AS sub";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        // Missing subquery name
+        let mut env = Environment::default();
+        let query = "SELECT name FROM (SELECT name FROM branches)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_nested_subquery_depth_limit() {
+        let mut env = Environment::default();
+        let nested_query = |depth: usize| {
+            format!(
+                "SELECT * FROM {}commits{}",
+                "(SELECT * FROM ".repeat(depth),
+                ") AS s".repeat(depth)
+            )
+        };
+
+        let query = nested_query(3);
+        let tokens = crate::tokenizer::tokenize(&query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        // Deep nesting is reported instead of overflowing the stack
+        let options = ParserOptions {
+            max_query_length: usize::MAX,
+            max_expression_depth: 16,
+            ..Default::default()
+        };
+        let query = nested_query(3000);
+        let tokens = crate::tokenizer::tokenize_with_options(&query, options)
+            .ok()
+            .unwrap();
+        let error = parse_gql_with_options(tokens, &mut env, options)
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "Query is nested too deeply");
+    }
+
+    #[test]
+    fn test_parse_operators_chain_depth_limit() {
+        let mut env = Environment::default();
+        let options = ParserOptions {
+            max_query_length: usize::MAX,
+            ..Default::default()
+        };
+        let parse = |query: &str, env: &mut Environment| {
+            let tokens = crate::tokenizer::tokenize_with_options(query, options)
+                .ok()
+                .unwrap();
+            parse_gql_with_options(tokens, env, options)
+        };
+
+        let query = format!("SELECT {}TRUE", "NOT ".repeat(16));
+        assert!(parse(&query, &mut env).is_ok());
+
+        let query = format!("SELECT 1{}", " + 1".repeat(16));
+        assert!(parse(&query, &mut env).is_ok());
+
+        // Long chains are reported instead of overflowing the stack
+        let query = format!("SELECT {}TRUE", "NOT ".repeat(3000));
+        let error = parse(&query, &mut env).err().unwrap();
+        assert_eq!(error.message(), "Expression is nested too deeply");
+
+        let query = format!("SELECT 1{}", " + 1".repeat(2000));
+        let error = parse(&query, &mut env).err().unwrap();
+        assert_eq!(error.message(), "Expression is nested too deeply");
+
+        let query = format!("SELECT 1 WHERE TRUE{}", " OR 1 * 2 = 2".repeat(2000));
+        let error = parse(&query, &mut env).err().unwrap();
+        assert_eq!(error.message(), "Expression is nested too deeply");
+    }
+
+    #[test]
+    fn test_parse_aggregation_separator_argument() {
+        let mut env = Environment::default();
+        let query = "SELECT name, GROUP_CONCAT(title, \" | \") FROM commits GROUP BY name";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+
+        let statement = query.statements.get("aggregation").unwrap();
+        let statement = statement
+            .as_any()
+            .downcast_ref::<AggregationsStatement>()
+            .unwrap();
+        let call = statement
+            .aggregations
+            .values()
+            .find_map(|aggregation| match aggregation {
+                AggregateValue::Function(call) => Some(call),
+                _ => None,
+            })
+            .expect("Expect aggregation function");
+        assert_eq!(call.separator, Some(" | ".to_string()));
+
+        // `STRING_AGG` requires the separator
+        let mut env = Environment::default();
+        let query = "SELECT STRING_AGG(title) FROM commits";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        // Separator must be a string literal
+        let mut env = Environment::default();
+        let query = "SELECT STRING_AGG(title, name) FROM commits";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_type_mismatch_error() {
+        let location = Location { start: 1, end: 2 };
+        let expected = DataType::Text;
+        let actual = DataType::Integer;
+
+        let status = type_mismatch_error(location, expected, actual);
+        assert_eq!(
+            "Type mismatch expected `Text`, got `Integer`",
+            status.message()
+        );
+    }
+
+    #[test]
+    fn test_null_handling_functions_type_check() {
+        let mut env = Environment::default();
+        let query = "SELECT coalesce(NULL, name, \"unknown\"), nullif(1, 2) > 0 FROM commits";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        let mut env = Environment::default();
+        let query = "SELECT ifnull(name, 1) FROM commits";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_if_and_choose_result_type() {
+        let mut env = Environment::default();
+        let query = "SELECT IF(insertions > 10, \"big\", \"small\") = \"big\" FROM diffs";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        let mut env = Environment::default();
+        let query = "SELECT CHOOSE(2, \"a\", \"b\", 3)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        let mut env = Environment::default();
+        let query = "SELECT IF(true, 1, \"one\")";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_in_subquery() {
+        let mut env = Environment::default();
+        let query = "SELECT title FROM commits WHERE commit_id IN (SELECT commit_id FROM diffs)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+        assert_eq!(query.in_subqueries.len(), 1);
+
+        let mut env = Environment::default();
+        let query =
+            "SELECT title FROM commits WHERE commit_id IN (SELECT commit_id, name FROM diffs)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+
+        let mut env = Environment::default();
+        let query = "SELECT title FROM commits WHERE title IN diffs.insertions";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_operators_with_null_operand() {
+        let mut env = Environment::default();
+        let query = "SELECT 1 = NULL, NULL > 2, 1 + NULL, NULL AND true, !NULL, NULL LIKE \"%a\"";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        let mut env = Environment::default();
+        let query = "SELECT 1 AND NULL";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_bitwise_operators_precedence() {
+        // 1 | 2 = 3 must be parsed as (1 | 2) = 3
+        let mut context = ParserContext::default();
+        let mut env = Environment::default();
+        let tokens = crate::tokenizer::tokenize("1 | 2 = 3").ok().unwrap();
+        let mut position = 0;
+        let expression = parse_expression(&mut context, &mut env, &tokens, &mut position);
+        let expression = expression.ok().unwrap();
+        assert!(expression.kind() == ExpressionKind::Comparison);
+        let comparison = expression.as_any().downcast_ref::<ComparisonExpression>();
+        assert!(comparison.unwrap().left.kind() == ExpressionKind::Bitwise);
+
+        // 1 | 6 & 3 must be parsed as 1 | (6 & 3)
+        let mut position = 0;
+        let tokens = crate::tokenizer::tokenize("1 | 6 & 3").ok().unwrap();
+        let expression = parse_expression(&mut context, &mut env, &tokens, &mut position);
+        let expression = expression.ok().unwrap();
+        let bitwise = expression.as_any().downcast_ref::<BitwiseExpression>();
+        let bitwise = bitwise.unwrap();
+        assert!(bitwise.operator == BitwiseOperator::Or);
+        assert!(bitwise.right.kind() == ExpressionKind::Bitwise);
+
+        let mut env = Environment::default();
+        let query = "SELECT 6 & 3 > 1 AND 1 << 2 | 1 = 5, insertions & 1 = 0 FROM diffs";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+
+        let mut env = Environment::default();
+        let query = "SELECT true | false";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_between_and_not_between() {
+        let queries = [
+            "SELECT * FROM diffs WHERE insertions BETWEEN 1 AND 10 AND deletions > 1",
+            "SELECT * FROM diffs WHERE insertions NOT BETWEEN 1 AND 10",
+            "SELECT * FROM diffs WHERE insertions BETWEEN SYMMETRIC 10 AND 1",
+            "SELECT * FROM diffs WHERE insertions NOT BETWEEN SYMMETRIC 1 .. 10",
+        ];
+
+        for query in queries {
+            let mut env = Environment::default();
+            let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+            assert!(parse_gql(tokens, &mut env).is_ok());
+        }
+
+        let mut env = Environment::default();
+        let query = "SELECT * FROM diffs WHERE insertions BETWEEN 1 OR 10";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
+    }
+
+    #[test]
+    fn test_parse_malformed_input_without_panic() {
+        let queries = [
+            "",
+            "SELECT LOWER(",
+            "SELECT LOWER(name,",
+            "SELECT (1",
+            "SELECT 1 IN (",
+            "SELECT 1 IN (1,",
+            "SELECT TRUE OR 1",
+            "SELECT TRUE AND 1",
+            "SELECT TRUE XOR 1",
+            "SELECT CASE ELSE 1 END",
+            "SELECT * FROM commits LIMIT -9223372036854775808 / -1",
+        ];
+
+        for query in queries {
+            let mut env = Environment::default();
+            let (tokens, _) =
+                crate::tokenizer::tokenize_with_recovery(query, ParserOptions::default());
+            let _ = parse_gql(tokens, &mut env);
+        }
+
+        let mut env = Environment::default();
+        let query = "SELECT CASE ELSE 1 END";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_ok());
+    }
+
+    #[test]
+    fn test_parse_chained_comparisons() {
+        let mut env = Environment::default();
+        let query = "SELECT * FROM diffs WHERE 1 < insertions < 10";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let diagnostic = parse_gql(tokens, &mut env).err().unwrap();
+        assert_eq!(
+            diagnostic.message(),
+            "Comparison operators can't be chained"
+        );
+    }
+
+    #[test]
+    fn test_fold_negative_number_literals() {
+        let mut context = ParserContext::default();
+        let mut env = Environment::default();
+        let cases = [
+            ("-9223372036854775808", Value::Integer(i64::MIN)),
+            ("-2.5e3", Value::Float(-2500.0)),
+            ("-1e-3", Value::Float(-0.001)),
+        ];
+
+        for (script, expected) in cases {
+            let tokens = crate::tokenizer::tokenize(script).ok().unwrap();
+            let mut position = 0;
+            let expression = parse_expression(&mut context, &mut env, &tokens, &mut position);
+            let expression = expression.ok().unwrap();
+            let number = expression.as_any().downcast_ref::<NumberExpression>();
+            assert!(number.unwrap().value.equals(&expected));
+        }
+
+        let tokens = crate::tokenizer::tokenize("1e400").ok().unwrap();
+        let mut position = 0;
+        let expression = parse_expression(&mut context, &mut env, &tokens, &mut position);
+        assert_eq!(expression.err().unwrap().message(), "Too big Float value");
+
+        let mut env = Environment::default();
+        let query = "SELECT * FROM commits LIMIT 5 OFFSET -1";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let diagnostic = parse_gql(tokens, &mut env).err().unwrap();
+        assert_eq!(diagnostic.message(), "`OFFSET` integer value is invalid");
+
+        let mut env = Environment::default();
+        let query = "SELECT * FROM commits LIMIT -1";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let diagnostic = parse_gql(tokens, &mut env).err().unwrap();
+        assert_eq!(diagnostic.message(), "`LIMIT` integer value is invalid");
+    }
+
+    #[test]
+    fn test_reuse_selected_expressions() {
+        let mut env = Environment::default();
+        let query = "SELECT LOWER(name) AS n, COUNT(name) FROM commits WHERE LOWER(name) != \"a\" GROUP BY LOWER(name) ORDER BY LOWER(name)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        let query = match parse_gql(tokens, &mut env).ok().unwrap() {
+            Query::Select(query) => query,
+            _ => panic!("Expect select query"),
+        };
+
+        let where_statement = query.statements["where"].as_any();
+        let where_statement = where_statement.downcast_ref::<WhereStatement>().unwrap();
+        let condition = where_statement.condition.as_any();
+        let condition = condition.downcast_ref::<ComparisonExpression>().unwrap();
+        assert!(condition.left.fingerprint() == Some("symbol(n)".to_string()));
+
+        let group_by_statement = query.statements["group"].as_any();
+        let group_by_statement = group_by_statement.downcast_ref::<GroupByStatement>();
+        assert_eq!(
+            group_by_statement.unwrap().field_names,
+            vec!["n".to_string()]
+        );
+
+        let order_by_statement = query.statements["order"].as_any();
+        let order_by_statement = order_by_statement.downcast_ref::<OrderByStatement>();
+        let argument = &order_by_statement.unwrap().arguments[0];
+        assert!(argument.fingerprint() == Some("symbol(n)".to_string()));
+
+        let mut env = Environment::default();
+        let query = "SELECT LOWER(name) FROM commits GROUP BY UPPER(name)";
+        let tokens = crate::tokenizer::tokenize(query).ok().unwrap();
+        assert!(parse_gql(tokens, &mut env).is_err());
     }
 }
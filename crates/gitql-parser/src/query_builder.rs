@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use gitql_ast::date_utils::time_stamp_to_date;
+use gitql_ast::date_utils::time_stamp_to_date_time;
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::expression::BooleanExpression;
+use gitql_ast::expression::ComparisonExpression;
+use gitql_ast::expression::ComparisonOperator;
+use gitql_ast::expression::Expression;
+use gitql_ast::expression::IsNullExpression;
+use gitql_ast::expression::LogicalExpression;
+use gitql_ast::expression::LogicalOperator;
+use gitql_ast::expression::NumberExpression;
+use gitql_ast::expression::StringExpression;
+use gitql_ast::expression::StringValueType;
+use gitql_ast::expression::SymbolExpression;
+use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::LimitCount;
+use gitql_ast::statement::LimitStatement;
+use gitql_ast::statement::OffsetStatement;
+use gitql_ast::statement::OrderByStatement;
+use gitql_ast::statement::Query;
+use gitql_ast::statement::SelectStatement;
+use gitql_ast::statement::SortingOrder;
+use gitql_ast::statement::Statement;
+use gitql_ast::statement::WhereStatement;
+use gitql_ast::types::TABLES_FIELDS_TYPES;
+use gitql_ast::value::Value;
+
+/// Build GitQL query directly as AST nodes without formatting and parsing query string,
+/// so values from user input are never interpreted as part of the query
+///
+/// ```ignore
+/// let query = QueryBuilder::select(["name", "email"])
+///     .from("commits")
+///     .where_eq("name", Value::Text(user_input))
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct QueryBuilder {
+    table_name: String,
+    fields_names: Vec<String>,
+    is_select_all: bool,
+    conditions: Vec<(String, Value)>,
+    order_by: Vec<(String, SortingOrder)>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+impl QueryBuilder {
+    /// Create a new builder that select the fields with the given names
+    pub fn select<I, S>(fields_names: I) -> QueryBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        QueryBuilder {
+            fields_names: fields_names
+                .into_iter()
+                .map(|name| name.as_ref().to_lowercase())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new builder that select all fields of the table
+    pub fn select_all() -> QueryBuilder {
+        QueryBuilder {
+            is_select_all: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn from(mut self, table_name: &str) -> QueryBuilder {
+        self.table_name = table_name.to_lowercase();
+        self
+    }
+
+    /// Keep only rows that has field equal to the value, multiple conditions are combined with `AND`
+    pub fn where_eq(mut self, field_name: &str, value: Value) -> QueryBuilder {
+        self.conditions.push((field_name.to_lowercase(), value));
+        self
+    }
+
+    pub fn order_by(mut self, field_name: &str, order: SortingOrder) -> QueryBuilder {
+        self.order_by.push((field_name.to_lowercase(), order));
+        self
+    }
+
+    pub fn limit(mut self, count: usize) -> QueryBuilder {
+        self.limit = Some(count);
+        self
+    }
+
+    pub fn offset(mut self, count: usize) -> QueryBuilder {
+        self.offset = Some(count);
+        self
+    }
+
+    /// Validate table, fields and values types then build the query
+    pub fn build(self) -> Result<Query, String> {
+        if !TABLES_FIELDS_NAMES.contains_key(self.table_name.as_str()) {
+            return Err(format!("Unresolved table name `{}`", self.table_name));
+        }
+
+        let table_fields = &TABLES_FIELDS_NAMES[self.table_name.as_str()];
+
+        let fields_names: Vec<String> = if self.is_select_all {
+            table_fields.iter().map(|field| field.to_string()).collect()
+        } else {
+            self.fields_names
+        };
+
+        if fields_names.is_empty() {
+            return Err("Query must select at least one field".to_string());
+        }
+
+        let mut hidden_selections: Vec<String> = vec![];
+        let used_fields = self
+            .conditions
+            .iter()
+            .map(|condition| &condition.0)
+            .chain(self.order_by.iter().map(|order| &order.0));
+
+        for field_name in fields_names.iter().chain(used_fields) {
+            if !table_fields.contains(&field_name.as_str()) {
+                return Err(format!(
+                    "Table `{}` has no field with name `{}`",
+                    self.table_name, field_name
+                ));
+            }
+
+            if !fields_names.contains(field_name) && !hidden_selections.contains(field_name) {
+                hidden_selections.push(field_name.to_string());
+            }
+        }
+
+        let mut statements: HashMap<&'static str, Box<dyn Statement>> = HashMap::new();
+
+        let fields_values: Vec<Box<dyn Expression>> = fields_names
+            .iter()
+            .map(|name| {
+                Box::new(SymbolExpression {
+                    value: name.to_string(),
+                }) as Box<dyn Expression>
+            })
+            .collect();
+
+        statements.insert(
+            "select",
+            Box::new(SelectStatement {
+                table_name: self.table_name.to_string(),
+                fields_names,
+                fields_values,
+                alias_table: HashMap::new(),
+                is_distinct: false,
+                values_table: None,
+            }),
+        );
+
+        let mut condition: Option<Box<dyn Expression>> = None;
+        for (field_name, value) in self.conditions {
+            let expected_type = &TABLES_FIELDS_TYPES[field_name.as_str()];
+            let value_type = value.data_type();
+            if !value_type.is_null() && value_type != *expected_type {
+                return Err(format!(
+                    "Field `{}` has type `{}` but compared with value of type `{}`",
+                    field_name, expected_type, value_type
+                ));
+            }
+
+            let equality = build_equality_expression(field_name, value);
+            let combined: Box<dyn Expression> = match condition {
+                Some(lhs) => Box::new(LogicalExpression {
+                    left: lhs,
+                    operator: LogicalOperator::And,
+                    right: equality,
+                }),
+                None => equality,
+            };
+            condition = Some(combined);
+        }
+
+        if let Some(condition) = condition {
+            statements.insert(
+                "where",
+                Box::new(WhereStatement {
+                    condition,
+                    condition_location: None,
+                }),
+            );
+        }
+
+        if !self.order_by.is_empty() {
+            let mut arguments: Vec<Box<dyn Expression>> = vec![];
+            let mut sorting_orders: Vec<SortingOrder> = vec![];
+            for (field_name, order) in self.order_by {
+                arguments.push(Box::new(SymbolExpression { value: field_name }));
+                sorting_orders.push(order);
+            }
+
+            statements.insert(
+                "order",
+                Box::new(OrderByStatement {
+                    arguments,
+                    sorting_orders,
+                }),
+            );
+        }
+
+        if let Some(count) = self.offset {
+            statements.insert("offset", Box::new(OffsetStatement { count }));
+        }
+
+        if let Some(count) = self.limit {
+            statements.insert(
+                "limit",
+                Box::new(LimitStatement {
+                    count: LimitCount::Absolute(count),
+                }),
+            );
+        }
+
+        Ok(Query::Select(GQLQuery {
+            statements,
+            has_aggregation_function: false,
+            has_group_by_statement: false,
+            has_non_deterministic_function: false,
+            hidden_selections,
+            subquery: None,
+            in_subqueries: vec![],
+        }))
+    }
+}
+
+fn build_equality_expression(field_name: String, value: Value) -> Box<dyn Expression> {
+    let symbol = Box::new(SymbolExpression { value: field_name });
+
+    let value_expression: Box<dyn Expression> = match value {
+        Value::Integer(_) | Value::Float(_) => Box::new(NumberExpression { value }),
+        Value::Text(text) => Box::new(StringExpression {
+            value: text,
+            value_type: StringValueType::Text,
+        }),
+        Value::Boolean(is_true) => Box::new(BooleanExpression { is_true }),
+        Value::DateTime(time_stamp) => Box::new(StringExpression {
+            value: time_stamp_to_date_time(time_stamp),
+            value_type: StringValueType::DateTime,
+        }),
+        Value::Date(time_stamp) => Box::new(StringExpression {
+            value: time_stamp_to_date(time_stamp),
+            value_type: StringValueType::Date,
+        }),
+        Value::Time(time) => Box::new(StringExpression {
+            value: time,
+            value_type: StringValueType::Time,
+        }),
+        // Comparing with null is never true, so check that the field is null instead
+        Value::Null => {
+            return Box::new(IsNullExpression {
+                argument: symbol,
+                has_not: false,
+            })
+        }
+    };
+
+    Box::new(ComparisonExpression {
+        left: symbol,
+        operator: ComparisonOperator::Equal,
+        right: value_expression,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_builder() {
+        let query = QueryBuilder::select(["name", "email"])
+            .from("commits")
+            .where_eq("title", Value::Text("\" OR 1 = 1".to_string()))
+            .order_by("datetime", SortingOrder::Descending)
+            .limit(10)
+            .build();
+
+        if let Ok(Query::Select(query)) = query {
+            assert!(query.statements.contains_key("select"));
+            assert!(query.statements.contains_key("where"));
+            assert!(query.statements.contains_key("order"));
+            assert!(query.statements.contains_key("limit"));
+            assert_eq!(query.hidden_selections, vec!["title", "datetime"]);
+        } else {
+            assert!(false);
+        }
+
+        let query = QueryBuilder::select_all().from("branches").build();
+        if let Ok(Query::Select(query)) = query {
+            let select = query.statements["select"]
+                .as_any()
+                .downcast_ref::<SelectStatement>()
+                .unwrap();
+            assert_eq!(
+                select.fields_names.len(),
+                TABLES_FIELDS_NAMES["branches"].len()
+            );
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_query_builder_validation() {
+        let query = QueryBuilder::select(["name"]).from("unknown").build();
+        assert!(query.is_err());
+
+        let query = QueryBuilder::select(["unknown"]).from("commits").build();
+        assert!(query.is_err());
+
+        let query = QueryBuilder::select(["name"])
+            .from("branches")
+            .where_eq("commit_count", Value::Text("1".to_string()))
+            .build();
+        assert!(query.is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::diagnostic::Diagnostic;
+
+/// Maximum number of suggested names attached to one diagnostic
+const MAX_SUGGESTIONS_COUNT: usize = 3;
+
+/// Add `Did you mean` help to the diagnostic with the candidates closest to the unknown name
+pub fn add_did_you_mean_help<'a, I>(diagnostic: Diagnostic, name: &str, candidates: I) -> Diagnostic
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let suggestions = closest_candidates(name, candidates);
+    if suggestions.is_empty() {
+        return diagnostic;
+    }
+
+    let suggestions: Vec<String> = suggestions
+        .iter()
+        .map(|suggestion| format!("`{}`", suggestion))
+        .collect();
+    diagnostic.add_help(&format!("Did you mean {}?", suggestions.join(" or ")))
+}
+
+/// Returns the candidates that are close enough to the name sorted by the distance,
+/// the allowed distance is one edit for each three characters in the name
+pub fn closest_candidates<'a, I>(name: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let name = name.to_lowercase();
+    let max_distance = usize::max(1, name.chars().count() / 3);
+
+    let mut matches: Vec<(usize, String)> = candidates
+        .into_iter()
+        .map(|candidate| candidate.to_lowercase())
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (levenshtein_distance(&name, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    matches.sort();
+    matches.dedup();
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS_COUNT)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Number of single character insertions, deletions or substitutions to change one text to the other
+pub fn levenshtein_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=second.len()).collect();
+    let mut current_row: Vec<usize> = vec![0; second.len() + 1];
+
+    for (i, first_char) in first.chars().enumerate() {
+        current_row[0] = i + 1;
+        for (j, second_char) in second.iter().enumerate() {
+            let substitution_cost = usize::from(first_char != *second_char);
+            current_row[j + 1] = (previous_row[j + 1] + 1)
+                .min(current_row[j] + 1)
+                .min(previous_row[j] + substitution_cost);
+        }
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[second.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("commits", "commits"), 0);
+        assert_eq!(levenshtein_distance("comits", "commits"), 1);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_closest_candidates() {
+        let candidates = ["commits", "branches", "refs", "tags"];
+        assert_eq!(closest_candidates("comits", candidates), vec!["commits"]);
+        assert_eq!(closest_candidates("tag", candidates), vec!["tags"]);
+        assert!(closest_candidates("unknown", candidates).is_empty());
+    }
+
+    #[test]
+    fn test_add_did_you_mean_help() {
+        let diagnostic = Diagnostic::error("Unresolved table name");
+        let diagnostic = add_did_you_mean_help(diagnostic, "brances", ["branches", "refs"]);
+        assert_eq!(
+            diagnostic.helps(),
+            &vec!["Did you mean `branches`?".to_string()]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::diagnostic::Diagnostic;
+use crate::tokenizer::Location;
+use crate::tokenizer::Token;
+use crate::tokenizer::TokenKind;
+
+/// Safe accessors over the tokens list used by the parser
+///
+/// All methods are bounds checked so reading past the end of the input never panics,
+/// missing tokens are reported as `None` or as a Diagnostic pointing to the last token
+pub trait TokenStream {
+    /// Return the token at the position if it exists
+    fn peek(&self, position: usize) -> Option<&Token<'_>>;
+
+    /// Return the kind of the token at the position if it exists
+    fn peek_kind(&self, position: usize) -> Option<&TokenKind> {
+        self.peek(position).map(|token| &token.kind)
+    }
+
+    /// Return the token before the position if it exists
+    fn previous(&self, position: usize) -> Option<&Token<'_>> {
+        position.checked_sub(1).and_then(|index| self.peek(index))
+    }
+
+    /// Return true if the token at the position exists and has this kind
+    fn is_kind(&self, position: usize, kind: TokenKind) -> bool {
+        self.peek_kind(position) == Some(&kind)
+    }
+
+    /// Return the token at the position if it exists and matches the predicate
+    fn peek_if(&self, position: usize, predicate: fn(&Token) -> bool) -> Option<&Token<'_>> {
+        self.peek(position).filter(|token| predicate(token))
+    }
+
+    /// Return true if the token at the position exists and matches the predicate
+    fn is_token(&self, position: usize, predicate: fn(&Token) -> bool) -> bool {
+        self.peek_if(position, predicate).is_some()
+    }
+
+    /// Return true if there are no more tokens starting from the position
+    fn is_at_end(&self, position: usize) -> bool {
+        self.peek(position).is_none()
+    }
+
+    /// Return the location of the token at the position, or of the last token
+    /// if the position is out of the bounds
+    fn location_at(&self, position: usize) -> Location;
+
+    /// Return the token at the position and move the position to the next one
+    fn advance(&self, position: &mut usize) -> Option<&Token<'_>> {
+        let token = self.peek(*position)?;
+        *position += 1;
+        Some(token)
+    }
+
+    /// Consume the token at the position if it has this kind,
+    /// otherwise report an error with the message at the current location
+    fn expect(
+        &self,
+        position: &mut usize,
+        kind: TokenKind,
+        message: &str,
+    ) -> Result<&Token<'_>, Box<Diagnostic>> {
+        match self.peek(*position) {
+            Some(token) if token.kind == kind => {
+                *position += 1;
+                Ok(token)
+            }
+            _ => Err(Diagnostic::error(message)
+                .with_location(self.location_at(*position))
+                .as_boxed()),
+        }
+    }
+}
+
+impl TokenStream for [Token<'_>] {
+    fn peek(&self, position: usize) -> Option<&Token<'_>> {
+        self.get(position)
+    }
+
+    fn location_at(&self, position: usize) -> Location {
+        match self.get(position).or(self.last()) {
+            Some(token) => token.location,
+            None => Location { start: 0, end: 0 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::tokenize;
+
+    #[test]
+    fn test_token_stream_accessors() {
+        let tokens = tokenize("SELECT name").ok().unwrap();
+        let tokens = tokens.as_slice();
+
+        assert!(tokens.is_kind(0, TokenKind::Select));
+        assert!(!tokens.is_kind(0, TokenKind::From));
+        assert!(!tokens.is_kind(10, TokenKind::Select));
+        assert!(tokens.peek(2).is_none());
+        assert!(tokens.peek_kind(2).is_none());
+        assert!(tokens.previous(0).is_none());
+        assert!(tokens
+            .previous(1)
+            .is_some_and(|t| t.kind == TokenKind::Select));
+        assert!(tokens.is_at_end(2));
+        assert_eq!(tokens.location_at(10).start, tokens.location_at(1).start);
+    }
+
+    #[test]
+    fn test_token_stream_advance_and_expect() {
+        let tokens = tokenize("SELECT name").ok().unwrap();
+        let tokens = tokens.as_slice();
+        let mut position = 0;
+
+        assert!(tokens.expect(&mut position, TokenKind::Select, "").is_ok());
+        assert_eq!(position, 1);
+
+        let result = tokens.expect(&mut position, TokenKind::From, "Expect `FROM`");
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap().message(), "Expect `FROM`");
+        assert_eq!(position, 1);
+
+        assert!(tokens.advance(&mut position).is_some());
+        assert!(tokens.advance(&mut position).is_none());
+        assert_eq!(position, 2);
+    }
+
+    #[test]
+    fn test_token_stream_empty_input() {
+        let tokens: Vec<Token> = vec![];
+        let tokens = tokens.as_slice();
+        let mut position = 0;
+
+        assert!(tokens.peek(0).is_none());
+        assert_eq!(tokens.location_at(0).start, 0);
+        assert!(tokens.expect(&mut position, TokenKind::Select, "").is_err());
+    }
+}
@@ -1,6 +1,10 @@
 #[derive(PartialEq)]
 pub enum TokenKind {
     Set,
+    Show,
+    Explain,
+    Create,
+    Insert,
     Select,
     Distinct,
     From,
@@ -8,14 +12,18 @@ pub enum TokenKind {
     Where,
     Having,
     Limit,
+    Percent,
+    Sample,
     Offset,
     Order,
     By,
+    All,
     In,
     Is,
     Not,
     Like,
     Glob,
+    Values,
 
     Case,
     When,
@@ -24,6 +32,7 @@ pub enum TokenKind {
     End,
 
     Between,
+    Symmetric,
     DotDot,
 
     Greater,
@@ -41,6 +50,7 @@ pub enum TokenKind {
     RightParen,
 
     LogicalOr,
+    Concat,
     LogicalAnd,
     LogicalXor,
 
@@ -81,555 +91,682 @@ pub struct Location {
     pub end: usize,
 }
 
-pub struct Token {
+/// Resolve the line and column of the character offset in the script, both are 1-based,
+/// locations store only character offsets and lines are resolved when they are reported
+pub fn line_and_column(script: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for character in script.chars().take(offset) {
+        if character == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// Token of the script, the literal is borrowed from the script unless it has to be
+/// transformed, for example numbers without `_` or escaped strings
+///
+/// Keywords and identifiers keep the original case of the script, use `identifier()`
+/// to get the name used to compare and lookup identifiers
+pub struct Token<'a> {
     pub location: Location,
     pub kind: TokenKind,
-    pub literal: String,
+    pub literal: Cow<'a, str>,
 }
 
-use crate::diagnostic::Diagnostic;
-
-pub fn tokenize(script: String) -> Result<Vec<Token>, Box<Diagnostic>> {
-    let mut tokens: Vec<Token> = Vec::new();
-
-    let mut position = 0;
-    let mut column_start;
-
-    let characters: Vec<char> = script.chars().collect();
-    let len = characters.len();
-
-    while position < len {
-        column_start = position;
-
-        let char = characters[position];
-
-        // Symbol
-        if char.is_alphabetic() {
-            tokens.push(consume_identifier(
-                &characters,
-                &mut position,
-                &mut column_start,
-            ));
-            continue;
+impl Token<'_> {
+    /// Returns the name of symbol or global variable, quoted identifiers are case-sensitive
+    /// and returned without quotes, otherwise the name is case-insensitive and returned in lowercase
+    pub fn identifier(&self) -> Cow<'_, str> {
+        let literal = self.literal.as_ref();
+        if let Some(name) = unquote(literal, '`') {
+            return Cow::Borrowed(name);
         }
 
-        // Global Variable Symbol
-        if char == '@' {
-            tokens.push(consume_global_variable_name(
-                &characters,
-                &mut position,
-                &mut column_start,
-            )?);
-            continue;
+        if let Some(name) = unquote(literal, '"') {
+            return if name.contains("\"\"") {
+                Cow::Owned(name.replace("\"\"", "\""))
+            } else {
+                Cow::Borrowed(name)
+            };
         }
 
-        // Number
-        if char.is_numeric() {
-            if char == '0' && position + 1 < len {
-                if characters[position + 1] == 'x' {
-                    position += 2;
-                    column_start += 2;
-                    tokens.push(consume_hex_number(
-                        &characters,
-                        &mut position,
-                        &mut column_start,
-                    )?);
-                    continue;
-                }
+        to_lowercase_literal(literal)
+    }
+}
 
-                if characters[position + 1] == 'b' {
-                    position += 2;
-                    column_start += 2;
-                    tokens.push(consume_binary_number(
-                        &characters,
-                        &mut position,
-                        &mut column_start,
-                    )?);
-                    continue;
-                }
+use std::borrow::Cow;
 
-                if characters[position + 1] == 'o' {
-                    position += 2;
-                    column_start += 2;
-                    tokens.push(consume_octal_number(
-                        &characters,
-                        &mut position,
-                        &mut column_start,
-                    )?);
-                    continue;
-                }
-            }
+use crate::context::ParserOptions;
+use crate::diagnostic::Diagnostic;
 
-            tokens.push(consume_number(
-                &characters,
-                &mut position,
-                &mut column_start,
-            )?);
-            continue;
-        }
+/// Characters of the script with the byte offset of each one, so the tokens literals
+/// can be sliced from the script using the characters positions
+struct Source<'a> {
+    script: &'a str,
+    chars: Vec<char>,
+    offsets: Vec<usize>,
+}
 
-        // String literal
-        if char == '"' {
-            tokens.push(consume_string(
-                &characters,
-                &mut position,
-                &mut column_start,
-            )?);
-            continue;
+impl<'a> Source<'a> {
+    fn new(script: &'a str) -> Self {
+        let mut chars = Vec::with_capacity(script.len());
+        let mut offsets = Vec::with_capacity(script.len() + 1);
+        for (offset, character) in script.char_indices() {
+            offsets.push(offset);
+            chars.push(character);
         }
-
-        // All chars between two backticks should be consumed as identifier
-        if char == '`' {
-            tokens.push(consume_backticks_identifier(
-                &characters,
-                &mut position,
-                &mut column_start,
-            )?);
-            continue;
+        offsets.push(script.len());
+        Source {
+            script,
+            chars,
+            offsets,
         }
+    }
 
-        // Plus
-        if char == '+' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    /// Returns the slice of the script between two characters positions
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.script[self.offsets[start]..self.offsets[end]]
+    }
+}
 
-            let token = Token {
-                location,
-                kind: TokenKind::Plus,
-                literal: "+".to_owned(),
-            };
+pub fn tokenize(script: &str) -> Result<Vec<Token<'_>>, Box<Diagnostic>> {
+    tokenize_with_options(script, ParserOptions::default())
+}
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+pub fn tokenize_with_options(
+    script: &str,
+    options: ParserOptions,
+) -> Result<Vec<Token<'_>>, Box<Diagnostic>> {
+    let (tokens, mut diagnostics) = tokenize_with_recovery(script, options);
+    if diagnostics.is_empty() {
+        Ok(tokens)
+    } else {
+        Err(diagnostics.remove(0))
+    }
+}
 
-        // Minus
-        if char == '-' {
-            // Ignore single line comment which from -- until the end of the current line
-            if position + 1 < characters.len() && characters[position + 1] == '-' {
-                ignore_single_line_comment(&characters, &mut position);
-                continue;
-            }
+/// Tokenize the script and continue after lexical errors, returns all the valid tokens
+/// with a diagnostic for each error, so editors and REPL can still work on broken input
+pub fn tokenize_with_recovery(
+    script: &str,
+    options: ParserOptions,
+) -> (Vec<Token<'_>>, Vec<Box<Diagnostic>>) {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut diagnostics: Vec<Box<Diagnostic>> = Vec::new();
 
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    let mut position = 0;
+    let source = Source::new(script);
+    let len = source.chars.len();
+
+    if len > options.max_query_length {
+        let diagnostic = Diagnostic::error("Query is too long")
+            .add_note(&format!(
+                "Query has {} characters but the maximum is {}",
+                len, options.max_query_length
+            ))
+            .add_help("Try to split the query or increase the maximum query length option")
+            .as_boxed();
+        return (tokens, vec![diagnostic]);
+    }
 
-            let token = Token {
-                location,
-                kind: TokenKind::Minus,
-                literal: "-".to_owned(),
-            };
+    while position < len {
+        let token_start = position;
+        if let Err(diagnostic) = consume_token(&source, &mut position, &options, &mut tokens) {
+            diagnostics.push(diagnostic);
 
-            tokens.push(token);
-            position += 1;
-            continue;
+            // Skip the invalid character if the error consumed nothing
+            if position == token_start {
+                position += 1;
+            }
         }
+    }
 
-        // Star
-        if char == '*' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
-
-            let token = Token {
-                location,
-                kind: TokenKind::Star,
-                literal: "*".to_owned(),
-            };
-
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+    (tokens, diagnostics)
+}
 
-        // Slash
-        if char == '/' {
-            // Ignore C style comment which from /* comment */
-            if position + 1 < characters.len() && characters[position + 1] == '*' {
-                ignore_c_style_comment(&characters, &mut position)?;
-                continue;
-            }
+fn consume_token<'a>(
+    source: &Source<'a>,
+    position: &mut usize,
+    options: &ParserOptions,
+    tokens: &mut Vec<Token<'a>>,
+) -> Result<(), Box<Diagnostic>> {
+    let characters = &source.chars;
+    let len = characters.len();
+    let mut column_start = *position;
 
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    let char = characters[*position];
 
-            let token = Token {
-                location,
-                kind: TokenKind::Slash,
-                literal: "/".to_owned(),
-            };
+    // Symbol
+    if char.is_alphabetic() {
+        let identifier = consume_identifier(source, position, &mut column_start);
+        tokens.push(check_identifier_length(identifier, options)?);
+        return Ok(());
+    }
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+    // Global Variable Symbol
+    if char == '@' {
+        let name = consume_global_variable_name(source, position, &mut column_start)?;
+        tokens.push(check_identifier_length(name, options)?);
+        return Ok(());
+    }
 
-        // Percentage
-        if char == '%' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    // Number
+    if char.is_ascii_digit() {
+        if char == '0' && *position + 1 < len {
+            if characters[*position + 1] == 'x' {
+                *position += 2;
+                column_start += 2;
+                tokens.push(consume_hex_number(source, position, &mut column_start)?);
+                return Ok(());
+            }
 
-            let token = Token {
-                location,
-                kind: TokenKind::Percentage,
-                literal: "%".to_owned(),
-            };
+            if characters[*position + 1] == 'b' {
+                *position += 2;
+                column_start += 2;
+                tokens.push(consume_binary_number(source, position, &mut column_start)?);
+                return Ok(());
+            }
 
-            tokens.push(token);
-            position += 1;
-            continue;
+            if characters[*position + 1] == 'o' {
+                *position += 2;
+                column_start += 2;
+                tokens.push(consume_octal_number(source, position, &mut column_start)?);
+                return Ok(());
+            }
         }
 
-        // Or
-        if char == '|' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+        tokens.push(consume_number(source, position, &mut column_start)?);
+        return Ok(());
+    }
 
-            position += 1;
+    // Double quoted identifier in ANSI mode
+    if char == '"' && options.ansi_mode {
+        let identifier = consume_double_quoted_identifier(source, position, &mut column_start)?;
+        tokens.push(check_identifier_length(identifier, options)?);
+        return Ok(());
+    }
 
-            let mut kind = TokenKind::BitwiseOr;
-            let literal = if position < len && characters[position] == '|' {
-                position += 1;
-                kind = TokenKind::LogicalOr;
-                "||"
-            } else {
-                "|"
-            };
+    // String literal
+    if char == '"' {
+        tokens.push(consume_string(source, position, &mut column_start)?);
+        return Ok(());
+    }
 
-            let token = Token {
-                location,
-                kind,
-                literal: literal.to_string(),
-            };
+    // Single quoted String literal
+    if char == '\'' {
+        tokens.push(consume_single_quoted_string(
+            source,
+            position,
+            &mut column_start,
+        )?);
+        return Ok(());
+    }
 
-            tokens.push(token);
-            continue;
-        }
+    // All chars between two backticks should be consumed as identifier
+    if char == '`' {
+        tokens.push(consume_backticks_identifier(
+            source,
+            position,
+            &mut column_start,
+        )?);
+        return Ok(());
+    }
 
-        // And
-        if char == '&' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    // Plus
+    if char == '+' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Plus,
+            literal: Cow::Borrowed("+"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            position += 1;
-            let mut kind = TokenKind::BitwiseAnd;
-            let literal = if position < len && characters[position] == '&' {
-                position += 1;
-                kind = TokenKind::LogicalAnd;
-                "&&"
-            } else {
-                "&"
-            };
+    // Minus
+    if char == '-' {
+        // Ignore single line comment which from -- until the end of the current line
+        if *position + 1 < characters.len() && characters[*position + 1] == '-' {
+            ignore_single_line_comment(characters, position);
+            return Ok(());
+        }
 
-            let token = Token {
-                location,
-                kind,
-                literal: literal.to_string(),
-            };
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
 
-            tokens.push(token);
-            continue;
-        }
+        let token = Token {
+            location,
+            kind: TokenKind::Minus,
+            literal: Cow::Borrowed("-"),
+        };
 
-        // xor
-        if char == '^' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            let token = Token {
-                location,
-                kind: TokenKind::LogicalXor,
-                literal: "^".to_owned(),
-            };
+    // Star
+    if char == '*' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Star,
+            literal: Cow::Borrowed("*"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            tokens.push(token);
-            position += 1;
-            continue;
+    // Slash
+    if char == '/' {
+        // Ignore C style comment which from /* comment */
+        if *position + 1 < characters.len() && characters[*position + 1] == '*' {
+            ignore_c_style_comment(characters, position)?;
+            return Ok(());
         }
 
-        // Comma
-        if char == ',' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
-
-            let token = Token {
-                location,
-                kind: TokenKind::Comma,
-                literal: ",".to_owned(),
-            };
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+        let token = Token {
+            location,
+            kind: TokenKind::Slash,
+            literal: Cow::Borrowed("/"),
+        };
 
-        // Dot or Range (DotDot)
-        if char == '.' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            position += 1;
+    // Percentage
+    if char == '%' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Percentage,
+            literal: Cow::Borrowed("%"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            let mut kind = TokenKind::Dot;
-            let literal = if position < len && characters[position] == '.' {
-                position += 1;
-                kind = TokenKind::DotDot;
-                ".."
+    // Or
+    if char == '|' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        *position += 1;
+
+        let mut kind = TokenKind::BitwiseOr;
+        let literal = if *position < len && characters[*position] == '|' {
+            *position += 1;
+            // In ANSI mode `||` is used to concatenate strings
+            kind = if options.ansi_mode {
+                TokenKind::Concat
             } else {
-                "."
+                TokenKind::LogicalOr
             };
+            "||"
+        } else {
+            "|"
+        };
 
-            let token = Token {
-                location,
-                kind,
-                literal: literal.to_string(),
-            };
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-            tokens.push(token);
-            continue;
-        }
+        tokens.push(token);
+        return Ok(());
+    }
 
-        // Greater or GreaterEqual
-        if char == '>' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    // And
+    if char == '&' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        *position += 1;
+        let mut kind = TokenKind::BitwiseAnd;
+        let literal = if *position < len && characters[*position] == '&' {
+            *position += 1;
+            kind = TokenKind::LogicalAnd;
+            "&&"
+        } else {
+            "&"
+        };
 
-            position += 1;
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-            let mut kind = TokenKind::Greater;
-            let literal = if position < len && characters[position] == '=' {
-                position += 1;
-                kind = TokenKind::GreaterEqual;
-                ">="
-            } else if position < len && characters[position] == '>' {
-                position += 1;
-                kind = TokenKind::BitwiseRightShift;
-                ">>"
-            } else {
-                ">"
-            };
+        tokens.push(token);
+        return Ok(());
+    }
 
-            let token = Token {
-                location,
-                kind,
-                literal: literal.to_string(),
-            };
+    // xor
+    if char == '^' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::LogicalXor,
+            literal: Cow::Borrowed("^"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            tokens.push(token);
-            continue;
-        }
+    // Comma
+    if char == ',' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Comma,
+            literal: Cow::Borrowed(","),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-        // Less, LessEqual or NULL-safe equal
-        if char == '<' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    // Dot or Range (DotDot)
+    if char == '.' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
 
-            position += 1;
+        *position += 1;
 
-            let mut kind = TokenKind::Less;
-            let literal = if position < len && characters[position] == '=' {
-                position += 1;
-                if position < len && characters[position] == '>' {
-                    position += 1;
-                    kind = TokenKind::NullSafeEqual;
-                    "<=>"
-                } else {
-                    kind = TokenKind::LessEqual;
-                    "<="
-                }
-            } else if position < len && characters[position] == '<' {
-                position += 1;
-                kind = TokenKind::BitwiseLeftShift;
-                "<<"
-            } else if position < len && characters[position] == '>' {
-                position += 1;
-                kind = TokenKind::BangEqual;
-                "<>"
-            } else {
-                "<"
-            };
+        let mut kind = TokenKind::Dot;
+        let literal = if *position < len && characters[*position] == '.' {
+            *position += 1;
+            kind = TokenKind::DotDot;
+            ".."
+        } else {
+            "."
+        };
 
-            let token = Token {
-                location,
-                kind,
-                literal: literal.to_owned(),
-            };
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-            tokens.push(token);
-            continue;
-        }
+        tokens.push(token);
+        return Ok(());
+    }
 
-        // Equal
-        if char == '=' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+    // Greater or GreaterEqual
+    if char == '>' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        *position += 1;
+
+        let mut kind = TokenKind::Greater;
+        let literal = if *position < len && characters[*position] == '=' {
+            *position += 1;
+            kind = TokenKind::GreaterEqual;
+            ">="
+        } else if *position < len && characters[*position] == '>' {
+            *position += 1;
+            kind = TokenKind::BitwiseRightShift;
+            ">>"
+        } else {
+            ">"
+        };
 
-            let token = Token {
-                location,
-                kind: TokenKind::Equal,
-                literal: "=".to_owned(),
-            };
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+        tokens.push(token);
+        return Ok(());
+    }
 
-        // Colon Equal
-        if char == ':' {
-            if position + 1 < len && characters[position + 1] == '=' {
-                let location = Location {
-                    start: column_start,
-                    end: position,
-                };
+    // Less, LessEqual or NULL-safe equal
+    if char == '<' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        *position += 1;
+
+        let mut kind = TokenKind::Less;
+        let literal = if *position < len && characters[*position] == '=' {
+            *position += 1;
+            if *position < len && characters[*position] == '>' {
+                *position += 1;
+                kind = TokenKind::NullSafeEqual;
+                "<=>"
+            } else {
+                kind = TokenKind::LessEqual;
+                "<="
+            }
+        } else if *position < len && characters[*position] == '<' {
+            *position += 1;
+            kind = TokenKind::BitwiseLeftShift;
+            "<<"
+        } else if *position < len && characters[*position] == '>' {
+            *position += 1;
+            kind = TokenKind::BangEqual;
+            "<>"
+        } else {
+            "<"
+        };
 
-                let token = Token {
-                    location,
-                    kind: TokenKind::ColonEqual,
-                    literal: ":=".to_owned(),
-                };
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-                tokens.push(token);
-                position += 2;
-                continue;
-            }
+        tokens.push(token);
+        return Ok(());
+    }
 
-            return Err(Diagnostic::error("Expect `=` after `:`")
-                .add_help("Only token that has `:` is `:=` so make sure you add `=` after `:`")
-                .with_location_span(column_start, position)
-                .as_boxed());
-        }
+    // Equal
+    if char == '=' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Equal,
+            literal: Cow::Borrowed("="),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-        // Bang or Bang Equal
-        if char == '!' {
+    // Colon Equal
+    if char == ':' {
+        if *position + 1 < len && characters[*position + 1] == '=' {
             let location = Location {
                 start: column_start,
-                end: position,
-            };
-
-            position += 1;
-
-            let mut kind = TokenKind::Bang;
-            let literal = if position < len && characters[position] == '=' {
-                position += 1;
-                kind = TokenKind::BangEqual;
-                "!="
-            } else {
-                "!"
+                end: *position,
             };
 
             let token = Token {
                 location,
-                kind,
-                literal: literal.to_owned(),
+                kind: TokenKind::ColonEqual,
+                literal: Cow::Borrowed(":="),
             };
 
             tokens.push(token);
-            continue;
+            *position += 2;
+            return Ok(());
         }
 
-        // Left Paren
-        if char == '(' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
-
-            let token = Token {
-                location,
-                kind: TokenKind::LeftParen,
-                literal: "(".to_owned(),
-            };
+        return Err(Diagnostic::error("Expect `=` after `:`")
+            .add_help("Only token that has `:` is `:=` so make sure you add `=` after `:`")
+            .with_location_span(column_start, *position)
+            .as_boxed());
+    }
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+    // Bang or Bang Equal
+    if char == '!' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
 
-        // Right Paren
-        if char == ')' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+        *position += 1;
 
-            let token = Token {
-                location,
-                kind: TokenKind::RightParen,
-                literal: ")".to_owned(),
-            };
+        let mut kind = TokenKind::Bang;
+        let literal = if *position < len && characters[*position] == '=' {
+            *position += 1;
+            kind = TokenKind::BangEqual;
+            "!="
+        } else {
+            "!"
+        };
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+        let token = Token {
+            location,
+            kind,
+            literal: Cow::Borrowed(literal),
+        };
 
-        // Semicolon
-        if char == ';' {
-            let location = Location {
-                start: column_start,
-                end: position,
-            };
+        tokens.push(token);
+        return Ok(());
+    }
 
-            let token = Token {
-                location,
-                kind: TokenKind::Semicolon,
-                literal: ";".to_owned(),
-            };
+    // Left Paren
+    if char == '(' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::LeftParen,
+            literal: Cow::Borrowed("("),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-            tokens.push(token);
-            position += 1;
-            continue;
-        }
+    // Right Paren
+    if char == ')' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::RightParen,
+            literal: Cow::Borrowed(")"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-        // Characters to ignoring
-        if char == ' ' || char == '\n' || char == '\t' {
-            position += 1;
-            continue;
-        }
+    // Semicolon
+    if char == ';' {
+        let location = Location {
+            start: column_start,
+            end: *position,
+        };
+
+        let token = Token {
+            location,
+            kind: TokenKind::Semicolon,
+            literal: Cow::Borrowed(";"),
+        };
+
+        tokens.push(token);
+        *position += 1;
+        return Ok(());
+    }
 
-        return Err(Diagnostic::error("Unexpected character")
-            .with_location_span(column_start, position)
-            .as_boxed());
+    // Characters to ignoring
+    if char == ' ' || char == '\n' || char == '\t' {
+        *position += 1;
+        return Ok(());
     }
 
-    Ok(tokens)
+    Err(Diagnostic::error("Unexpected character")
+        .with_location_span(column_start, *position)
+        .as_boxed())
 }
 
-fn consume_global_variable_name(
-    chars: &Vec<char>,
+fn consume_global_variable_name<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     // Consume `@`
     *pos += 1;
 
+    // Session variables names start with `@@`, for example `@@timezone`
+    if *pos < chars.len() && chars[*pos] == '@' {
+        *pos += 1;
+    }
+
     // Make sure first character is  alphabetic
     if *pos < chars.len() && !chars[*pos].is_alphabetic() {
         return Err(
@@ -644,11 +781,8 @@ fn consume_global_variable_name(
         *pos += 1;
     }
 
-    // Identifier is be case-insensitive by default, convert to lowercase to be easy to compare and lookup
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect())
-        .unwrap()
-        .to_lowercase();
+    // Keep the original case, identifier is compared case-insensitive using `Token::identifier()`
+    let literal = Cow::Borrowed(source.slice(*start, *pos));
 
     let location = Location {
         start: *start,
@@ -658,20 +792,36 @@ fn consume_global_variable_name(
     Ok(Token {
         location,
         kind: TokenKind::GlobalVariable,
-        literal: string,
+        literal,
     })
 }
 
-fn consume_identifier(chars: &Vec<char>, pos: &mut usize, start: &mut usize) -> Token {
+fn check_identifier_length<'a>(
+    token: Token<'a>,
+    options: &ParserOptions,
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let is_identifier = token.kind == TokenKind::Symbol || token.kind == TokenKind::GlobalVariable;
+    let length = token.identifier().chars().count();
+    if is_identifier && length > options.max_identifier_length {
+        return Err(Diagnostic::error("Identifier name is too long")
+            .add_note(&format!(
+                "Identifier has {} characters but the maximum is {}",
+                length, options.max_identifier_length
+            ))
+            .with_location(token.location)
+            .as_boxed());
+    }
+    Ok(token)
+}
+
+fn consume_identifier<'a>(source: &Source<'a>, pos: &mut usize, start: &mut usize) -> Token<'a> {
+    let chars = &source.chars;
     while *pos < chars.len() && (chars[*pos] == '_' || chars[*pos].is_alphanumeric()) {
         *pos += 1;
     }
 
-    // Identifier is be case-insensitive by default, convert to lowercase to be easy to compare and lookup
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect())
-        .unwrap()
-        .to_lowercase();
+    // Keep the original case, identifier is compared case-insensitive using `Token::identifier()`
+    let literal = Cow::Borrowed(source.slice(*start, *pos));
 
     let location = Location {
         start: *start,
@@ -680,19 +830,20 @@ fn consume_identifier(chars: &Vec<char>, pos: &mut usize, start: &mut usize) ->
 
     Token {
         location,
-        kind: resolve_symbol_kind(string.to_string()),
-        literal: string,
+        kind: resolve_symbol_kind(&literal),
+        literal,
     }
 }
 
-fn consume_number(
-    chars: &Vec<char>,
+fn consume_number<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     let mut kind = TokenKind::Integer;
 
-    while *pos < chars.len() && (chars[*pos].is_numeric() || chars[*pos] == '_') {
+    while *pos < chars.len() && (chars[*pos].is_ascii_digit() || chars[*pos] == '_') {
         *pos += 1;
     }
 
@@ -700,14 +851,29 @@ fn consume_number(
         *pos += 1;
 
         kind = TokenKind::Float;
-        while *pos < chars.len() && (chars[*pos].is_numeric() || chars[*pos] == '_') {
+        while *pos < chars.len() && (chars[*pos].is_ascii_digit() || chars[*pos] == '_') {
             *pos += 1;
         }
     }
 
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
-    let literal_num = string.replace('_', "");
+    // Scientific notation exponent with optional sign, for example `1e9` or `2.5E-3`
+    if *pos < chars.len() && (chars[*pos] == 'e' || chars[*pos] == 'E') {
+        let mut exponent_pos = *pos + 1;
+        if exponent_pos < chars.len() && (chars[exponent_pos] == '+' || chars[exponent_pos] == '-')
+        {
+            exponent_pos += 1;
+        }
+
+        if exponent_pos < chars.len() && chars[exponent_pos].is_ascii_digit() {
+            kind = TokenKind::Float;
+            *pos = exponent_pos;
+            while *pos < chars.len() && (chars[*pos].is_ascii_digit() || chars[*pos] == '_') {
+                *pos += 1;
+            }
+        }
+    }
+
+    let literal = without_digits_separator(source.slice(*start, *pos));
 
     let location = Location {
         start: *start,
@@ -717,15 +883,16 @@ fn consume_number(
     Ok(Token {
         location,
         kind,
-        literal: literal_num,
+        literal,
     })
 }
 
-fn consume_backticks_identifier(
-    chars: &Vec<char>,
+fn consume_backticks_identifier<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     *pos += 1;
 
     while *pos < chars.len() && chars[*pos] != '`' {
@@ -741,8 +908,8 @@ fn consume_backticks_identifier(
 
     *pos += 1;
 
-    let literal = &chars[*start + 1..*pos - 1];
-    let identifier = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
+    // Backticks are part of the literal to keep the identifier case-sensitive
+    let identifier = source.slice(*start, *pos);
 
     let location = Location {
         start: *start,
@@ -752,17 +919,18 @@ fn consume_backticks_identifier(
     let string_literal = Token {
         location,
         kind: TokenKind::Symbol,
-        literal: identifier,
+        literal: Cow::Borrowed(identifier),
     };
 
     Ok(string_literal)
 }
 
-fn consume_binary_number(
-    chars: &Vec<char>,
+fn consume_binary_number<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     let mut has_digit = false;
     while *pos < chars.len() && ((chars[*pos] == '0' || chars[*pos] == '1') || chars[*pos] == '_') {
         *pos += 1;
@@ -779,9 +947,7 @@ fn consume_binary_number(
         );
     }
 
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
-    let literal_num = string.replace('_', "");
+    let literal_num = without_digits_separator(source.slice(*start, *pos));
     let convert_result = i64::from_str_radix(&literal_num, 2);
 
     if convert_result.is_err() {
@@ -798,17 +964,18 @@ fn consume_binary_number(
     Ok(Token {
         location,
         kind: TokenKind::Integer,
-        literal: convert_result.ok().unwrap().to_string(),
+        literal: Cow::Owned(convert_result.ok().unwrap().to_string()),
     })
 }
 
-fn consume_octal_number(
-    chars: &Vec<char>,
+fn consume_octal_number<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     let mut has_digit = false;
-    while *pos < chars.len() && ((chars[*pos] >= '0' || chars[*pos] < '8') || chars[*pos] == '_') {
+    while *pos < chars.len() && (chars[*pos].is_ascii_digit() || chars[*pos] == '_') {
         *pos += 1;
         has_digit = true;
     }
@@ -823,9 +990,7 @@ fn consume_octal_number(
         );
     }
 
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
-    let literal_num = string.replace('_', "");
+    let literal_num = without_digits_separator(source.slice(*start, *pos));
     let convert_result = i64::from_str_radix(&literal_num, 8);
 
     if convert_result.is_err() {
@@ -842,15 +1007,16 @@ fn consume_octal_number(
     Ok(Token {
         location,
         kind: TokenKind::Integer,
-        literal: convert_result.ok().unwrap().to_string(),
+        literal: Cow::Owned(convert_result.ok().unwrap().to_string()),
     })
 }
 
-fn consume_hex_number(
-    chars: &Vec<char>,
+fn consume_hex_number<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     let mut has_digit = false;
     while *pos < chars.len() && (chars[*pos].is_ascii_hexdigit() || chars[*pos] == '_') {
         *pos += 1;
@@ -867,9 +1033,7 @@ fn consume_hex_number(
         );
     }
 
-    let literal = &chars[*start..*pos];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
-    let literal_num = string.replace('_', "");
+    let literal_num = without_digits_separator(source.slice(*start, *pos));
     let convert_result = i64::from_str_radix(&literal_num, 16);
 
     if convert_result.is_err() {
@@ -886,18 +1050,30 @@ fn consume_hex_number(
     Ok(Token {
         location,
         kind: TokenKind::Integer,
-        literal: convert_result.ok().unwrap().to_string(),
+        literal: Cow::Owned(convert_result.ok().unwrap().to_string()),
     })
 }
 
-fn consume_string(
-    chars: &Vec<char>,
+fn consume_string<'a>(
+    source: &Source<'a>,
     pos: &mut usize,
     start: &mut usize,
-) -> Result<Token, Box<Diagnostic>> {
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
     *pos += 1;
 
+    // The string is copied only if it has escape sequences, otherwise it's borrowed from the script
+    let mut escaped: Option<String> = None;
     while *pos < chars.len() && chars[*pos] != '"' {
+        if chars[*pos] == '\\' {
+            let string = escaped.get_or_insert_with(|| source.slice(*start + 1, *pos).to_string());
+            string.push(consume_escape_sequence(chars, pos)?);
+            continue;
+        }
+
+        if let Some(string) = escaped.as_mut() {
+            string.push(chars[*pos]);
+        }
         *pos += 1;
     }
 
@@ -910,9 +1086,6 @@ fn consume_string(
 
     *pos += 1;
 
-    let literal = &chars[*start + 1..*pos - 1];
-    let string = String::from_utf8(literal.iter().map(|&c| c as u8).collect()).unwrap();
-
     let location = Location {
         start: *start,
         end: *pos,
@@ -921,12 +1094,192 @@ fn consume_string(
     let string_literal = Token {
         location,
         kind: TokenKind::String,
-        literal: string,
+        literal: escaped_or_slice(source, escaped, *start + 1, *pos - 1),
     };
 
     Ok(string_literal)
 }
 
+fn consume_single_quoted_string<'a>(
+    source: &Source<'a>,
+    pos: &mut usize,
+    start: &mut usize,
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
+    *pos += 1;
+
+    let mut escaped: Option<String> = None;
+    loop {
+        if *pos >= chars.len() {
+            return Err(Diagnostic::error("Unterminated single quote string")
+                .add_help("Add ' at the end of the String literal")
+                .with_location_span(*start, *pos)
+                .as_boxed());
+        }
+
+        if chars[*pos] == '\'' {
+            // Two single quotes inside the string are escaped single quote
+            if *pos + 1 < chars.len() && chars[*pos + 1] == '\'' {
+                let string =
+                    escaped.get_or_insert_with(|| source.slice(*start + 1, *pos).to_string());
+                string.push('\'');
+                *pos += 2;
+                continue;
+            }
+            break;
+        }
+
+        if chars[*pos] == '\\' {
+            let string = escaped.get_or_insert_with(|| source.slice(*start + 1, *pos).to_string());
+            string.push(consume_escape_sequence(chars, pos)?);
+            continue;
+        }
+
+        if let Some(string) = escaped.as_mut() {
+            string.push(chars[*pos]);
+        }
+        *pos += 1;
+    }
+
+    *pos += 1;
+
+    let location = Location {
+        start: *start,
+        end: *pos,
+    };
+
+    Ok(Token {
+        location,
+        kind: TokenKind::String,
+        literal: escaped_or_slice(source, escaped, *start + 1, *pos - 1),
+    })
+}
+
+/// Consume escape sequence that start with backslash inside string literal and return the escaped char
+fn consume_escape_sequence(chars: &[char], pos: &mut usize) -> Result<char, Box<Diagnostic>> {
+    let escape_start = *pos;
+
+    // Consume `\` char
+    *pos += 1;
+
+    if *pos >= chars.len() {
+        return Err(Diagnostic::error("Unterminated escape sequence")
+            .add_help("Use `\\\\` to write a backslash inside the String literal")
+            .with_location_span(escape_start, *pos)
+            .as_boxed());
+    }
+
+    let escaped = chars[*pos];
+    *pos += 1;
+
+    let character = match escaped {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        '0' => '\0',
+        '\\' => '\\',
+        '"' => '"',
+        '\'' => '\'',
+        'u' => {
+            // Unicode escape can be `\u{1F600}` with 1 to 6 hex digits or `\u00E9` with 4 hex digits
+            let has_braces = *pos < chars.len() && chars[*pos] == '{';
+            if has_braces {
+                *pos += 1;
+            }
+
+            let digits_start = *pos;
+            let max_digits = if has_braces { 6 } else { 4 };
+            while *pos < chars.len()
+                && *pos - digits_start < max_digits
+                && chars[*pos].is_ascii_hexdigit()
+            {
+                *pos += 1;
+            }
+
+            let digits: String = chars[digits_start..*pos].iter().collect();
+            let is_closed = !has_braces || (*pos < chars.len() && chars[*pos] == '}');
+            if has_braces && is_closed {
+                *pos += 1;
+            }
+
+            let is_valid_length = if has_braces {
+                !digits.is_empty()
+            } else {
+                digits.len() == 4
+            };
+
+            let code_point = u32::from_str_radix(&digits, 16).ok();
+            match code_point.and_then(char::from_u32) {
+                Some(character) if is_closed && is_valid_length => character,
+                _ => {
+                    return Err(Diagnostic::error("Invalid unicode escape sequence")
+                        .add_help(
+                            "Use `\\uXXXX` with 4 hex digits or `\\u{X}` with 1 to 6 hex digits",
+                        )
+                        .add_note("Unicode escape must be a valid unicode scalar value")
+                        .with_location_span(escape_start, *pos)
+                        .as_boxed());
+                }
+            }
+        }
+        _ => {
+            return Err(Diagnostic::error(&format!(
+                "Unknown escape sequence `\\{}`",
+                escaped
+            ))
+            .add_help("Supported escape sequences are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\\"`, `\\'` and `\\u`")
+            .with_location_span(escape_start, *pos)
+            .as_boxed());
+        }
+    };
+
+    Ok(character)
+}
+
+fn consume_double_quoted_identifier<'a>(
+    source: &Source<'a>,
+    pos: &mut usize,
+    start: &mut usize,
+) -> Result<Token<'a>, Box<Diagnostic>> {
+    let chars = &source.chars;
+    *pos += 1;
+
+    loop {
+        if *pos >= chars.len() {
+            return Err(Diagnostic::error("Unterminated double quoted identifier")
+                .add_help("Add \" at the end of the identifier")
+                .add_note("In ANSI mode double quotes are used for identifiers and single quotes for strings")
+                .with_location_span(*start, *pos)
+                .as_boxed());
+        }
+
+        if chars[*pos] == '"' {
+            // Two double quotes inside the identifier are escaped double quote
+            if *pos + 1 < chars.len() && chars[*pos + 1] == '"' {
+                *pos += 2;
+                continue;
+            }
+            break;
+        }
+
+        *pos += 1;
+    }
+
+    *pos += 1;
+
+    let location = Location {
+        start: *start,
+        end: *pos,
+    };
+
+    Ok(Token {
+        location,
+        kind: TokenKind::Symbol,
+        // Double quotes are part of the literal to keep the identifier case-sensitive
+        literal: Cow::Borrowed(source.slice(*start, *pos)),
+    })
+}
+
 fn ignore_single_line_comment(chars: &Vec<char>, pos: &mut usize) {
     *pos += 2;
 
@@ -940,7 +1293,7 @@ fn ignore_single_line_comment(chars: &Vec<char>, pos: &mut usize) {
 fn ignore_c_style_comment(chars: &Vec<char>, pos: &mut usize) -> Result<(), Box<Diagnostic>> {
     *pos += 2;
 
-    while *pos + 1 < chars.len() && (chars[*pos] != '*' && chars[*pos + 1] != '/') {
+    while *pos + 1 < chars.len() && !(chars[*pos] == '*' && chars[*pos + 1] == '/') {
         *pos += 1;
     }
 
@@ -955,10 +1308,53 @@ fn ignore_c_style_comment(chars: &Vec<char>, pos: &mut usize) -> Result<(), Box<
     Ok(())
 }
 
-fn resolve_symbol_kind(literal: String) -> TokenKind {
-    match literal.to_lowercase().as_str() {
+/// Returns the escaped literal if it has escape sequences, otherwise the literal is sliced from the script
+fn escaped_or_slice<'a>(
+    source: &Source<'a>,
+    escaped: Option<String>,
+    start: usize,
+    end: usize,
+) -> Cow<'a, str> {
+    match escaped {
+        Some(string) => Cow::Owned(string),
+        None => Cow::Borrowed(source.slice(start, end)),
+    }
+}
+
+/// Returns the content of the literal between two quote characters if it's quoted
+fn unquote(literal: &str, quote: char) -> Option<&str> {
+    literal.strip_prefix(quote)?.strip_suffix(quote)
+}
+
+/// Returns the literal in lowercase, borrowed from the script if it's already lowercase
+fn to_lowercase_literal(literal: &str) -> Cow<'_, str> {
+    let is_lowercase = literal
+        .chars()
+        .all(|character| character.to_lowercase().eq([character]));
+    if is_lowercase {
+        Cow::Borrowed(literal)
+    } else {
+        Cow::Owned(literal.to_lowercase())
+    }
+}
+
+/// Returns the number literal without `_` digits separators, borrowed from the script if it has no separators
+fn without_digits_separator(literal: &str) -> Cow<'_, str> {
+    if literal.contains('_') {
+        Cow::Owned(literal.replace('_', ""))
+    } else {
+        Cow::Borrowed(literal)
+    }
+}
+
+fn resolve_symbol_kind(literal: &str) -> TokenKind {
+    match to_lowercase_literal(literal).as_ref() {
         // Reserved keywords
         "set" => TokenKind::Set,
+        "show" => TokenKind::Show,
+        "explain" => TokenKind::Explain,
+        "create" => TokenKind::Create,
+        "insert" => TokenKind::Insert,
         "select" => TokenKind::Select,
         "distinct" => TokenKind::Distinct,
         "from" => TokenKind::From,
@@ -966,20 +1362,25 @@ fn resolve_symbol_kind(literal: String) -> TokenKind {
         "where" => TokenKind::Where,
         "having" => TokenKind::Having,
         "limit" => TokenKind::Limit,
+        "percent" => TokenKind::Percent,
+        "sample" => TokenKind::Sample,
         "offset" => TokenKind::Offset,
         "order" => TokenKind::Order,
         "by" => TokenKind::By,
+        "all" => TokenKind::All,
         "case" => TokenKind::Case,
         "when" => TokenKind::When,
         "then" => TokenKind::Then,
         "else" => TokenKind::Else,
         "end" => TokenKind::End,
         "between" => TokenKind::Between,
+        "symmetric" => TokenKind::Symmetric,
         "in" => TokenKind::In,
         "is" => TokenKind::Is,
         "not" => TokenKind::Not,
         "like" => TokenKind::Like,
         "glob" => TokenKind::Glob,
+        "values" => TokenKind::Values,
 
         // Logical Operators
         "or" => TokenKind::LogicalOr,
@@ -1010,12 +1411,12 @@ mod tests {
     fn test_tokenize() {
         // Symbol: NAME
         let script = "NAME".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
             assert_eq!(4, tokens.as_ref().ok().unwrap()[0].location.end);
-            assert_eq!("name", tokens.as_ref().ok().unwrap()[0].literal);
+            assert_eq!("NAME", tokens.as_ref().ok().unwrap()[0].literal);
             if tokens.as_ref().ok().unwrap()[0].kind != TokenKind::Symbol {
                 assert!(false);
             }
@@ -1025,12 +1426,12 @@ mod tests {
 
         // GlobalVariable: @NAME
         let script = "@NAME".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
             assert_eq!(5, tokens.as_ref().ok().unwrap()[0].location.end);
-            assert_eq!("@name", tokens.as_ref().ok().unwrap()[0].literal);
+            assert_eq!("@NAME", tokens.as_ref().ok().unwrap()[0].literal);
             if tokens.as_ref().ok().unwrap()[0].kind != TokenKind::GlobalVariable {
                 assert!(false);
             }
@@ -1040,7 +1441,7 @@ mod tests {
 
         // Integer: 0x01
         let script = "0x01".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(2, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1055,7 +1456,7 @@ mod tests {
 
         // Integer: 0b01
         let script = "0b01".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(2, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1070,7 +1471,7 @@ mod tests {
 
         // Integer: 0o01
         let script = "0o01".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(2, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1085,7 +1486,7 @@ mod tests {
 
         // Integer: 1
         let script = "1".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1100,7 +1501,7 @@ mod tests {
 
         // Float: 0.1
         let script = "0.1".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1115,7 +1516,7 @@ mod tests {
 
         // String: "name"
         let script = "\"name\"".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1130,12 +1531,12 @@ mod tests {
 
         // Symbol: `name`
         let script = "`name`".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
             assert_eq!(6, tokens.as_ref().ok().unwrap()[0].location.end);
-            assert_eq!("name", tokens.as_ref().ok().unwrap()[0].literal);
+            assert_eq!("`name`", tokens.as_ref().ok().unwrap()[0].literal);
             if tokens.as_ref().ok().unwrap()[0].kind != TokenKind::Symbol {
                 assert!(false);
             }
@@ -1145,7 +1546,7 @@ mod tests {
 
         // Plus: +
         let script = "+".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1160,7 +1561,7 @@ mod tests {
 
         // Minus: -
         let script = "-".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1175,7 +1576,7 @@ mod tests {
 
         // Star: *
         let script = "*".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1190,7 +1591,7 @@ mod tests {
 
         // Slash: /
         let script = "/".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1205,7 +1606,7 @@ mod tests {
 
         // Percentage: %
         let script = "%".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1220,7 +1621,7 @@ mod tests {
 
         // BitwiseOr: |
         let script = "|".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1235,7 +1636,7 @@ mod tests {
 
         // LogicalOr: ||
         let script = "||".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1250,7 +1651,7 @@ mod tests {
 
         // BitwiseAnd: &
         let script = "&".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1265,7 +1666,7 @@ mod tests {
 
         // LogicalAnd: &&
         let script = "&&".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1280,7 +1681,7 @@ mod tests {
 
         // LogicalXor: ^
         let script = "^".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1295,7 +1696,7 @@ mod tests {
 
         // Comma: ,
         let script = ",".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1310,7 +1711,7 @@ mod tests {
 
         // Dot: .
         let script = ".".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1325,7 +1726,7 @@ mod tests {
 
         // DotDot: ..
         let script = "..".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1340,7 +1741,7 @@ mod tests {
 
         // Greater: >
         let script = ">".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1355,7 +1756,7 @@ mod tests {
 
         // GreaterEqual: >=
         let script = ">=".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1370,7 +1771,7 @@ mod tests {
 
         // BitwiseRightShift: >>
         let script = ">>".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1385,7 +1786,7 @@ mod tests {
 
         // Less: <
         let script = "<".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1400,7 +1801,7 @@ mod tests {
 
         // NulllSafeEqual: <=>
         let script = "<=>".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1415,7 +1816,7 @@ mod tests {
 
         // LessEqual: <=
         let script = "<=".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1430,7 +1831,7 @@ mod tests {
 
         // BitwiseLeftShift: <<
         let script = "<<".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1445,7 +1846,7 @@ mod tests {
 
         // BangEqual: <>
         let script = "<>".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1460,7 +1861,7 @@ mod tests {
 
         // Equal: =
         let script = "=".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1475,7 +1876,7 @@ mod tests {
 
         // ColonEqual: :
         let script = ":".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_err() {
             assert_eq!("Expect `=` after `:`", tokens.err().unwrap().message());
         } else {
@@ -1484,7 +1885,7 @@ mod tests {
 
         // ColonEqual: :=
         let script = ":=".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1499,7 +1900,7 @@ mod tests {
 
         // Bang: !
         let script = "!".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1514,7 +1915,7 @@ mod tests {
 
         // BangEqual: !=
         let script = "!=".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1529,7 +1930,7 @@ mod tests {
 
         // LeftParen: (
         let script = "(".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1544,7 +1945,7 @@ mod tests {
 
         // RightParen: )
         let script = ")".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1559,7 +1960,7 @@ mod tests {
 
         // Semicolon: ;
         let script = ";".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_ok() {
             assert_eq!(1, tokens.as_ref().ok().unwrap().len());
             assert_eq!(0, tokens.as_ref().ok().unwrap()[0].location.start);
@@ -1574,7 +1975,7 @@ mod tests {
 
         // Invalid: ?
         let script = "?".to_string();
-        let tokens = tokenize(script);
+        let tokens = tokenize(&script);
         if tokens.is_err() {
             assert_eq!("Unexpected character", tokens.err().unwrap().message());
         } else {
@@ -1585,10 +1986,10 @@ mod tests {
     #[test]
     fn test_consume_global_variable_name() {
         // Invalid: @_
-        let chars: Vec<char> = vec!['@', '_'];
+        let source = Source::new("@_");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_global_variable_name(&chars, &mut pos, &mut start);
+        let token = consume_global_variable_name(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!(
                 "Global variable name must start with alphabetic character",
@@ -1599,32 +2000,49 @@ mod tests {
         }
 
         // GlobalVariable: @N
-        let chars: Vec<char> = vec!['@', 'N'];
+        let source = Source::new("@N");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_global_variable_name(&chars, &mut pos, &mut start);
+        let token = consume_global_variable_name(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(2, token.as_ref().ok().unwrap().location.end);
-            assert_eq!("@n", token.as_ref().ok().unwrap().literal);
+            assert_eq!("@N", token.as_ref().ok().unwrap().literal);
             if token.as_ref().ok().unwrap().kind != TokenKind::GlobalVariable {
                 assert!(false);
             }
         } else {
             assert!(false);
         }
+
+        // Session variable: @@timezone
+        let source = Source::new("@@timezone");
+        let mut start = 0;
+        let mut pos = 0;
+        let token = consume_global_variable_name(&source, &mut pos, &mut start);
+        assert!(token.is_ok());
+        let token = token.ok().unwrap();
+        assert_eq!(10, token.location.end);
+        assert_eq!("@@timezone", token.literal);
+        assert!(token.kind == TokenKind::GlobalVariable);
+
+        // Invalid: @@@
+        let source = Source::new("@@@");
+        let mut start = 0;
+        let mut pos = 0;
+        assert!(consume_global_variable_name(&source, &mut pos, &mut start).is_err());
     }
 
     #[test]
     fn test_consume_identifier() {
         // Set: SET
-        let chars: Vec<char> = vec!['S', 'E', 'T'];
+        let source = Source::new("SET");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_identifier(&chars, &mut pos, &mut start);
+        let token = consume_identifier(&source, &mut pos, &mut start);
         assert_eq!(0, token.location.start);
         assert_eq!(3, token.location.end);
-        assert_eq!("set", token.literal);
+        assert_eq!("SET", token.literal);
         if token.kind != TokenKind::Set {
             assert!(false);
         }
@@ -1633,10 +2051,10 @@ mod tests {
     #[test]
     fn test_consume_number() {
         // Integer: 1
-        let chars: Vec<char> = vec!['1'];
+        let source = Source::new("1");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_number(&chars, &mut pos, &mut start);
+        let token = consume_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(1, token.as_ref().ok().unwrap().location.end);
@@ -1649,10 +2067,10 @@ mod tests {
         }
 
         // Integer: 1_0
-        let chars: Vec<char> = vec!['1', '_', '0'];
+        let source = Source::new("1_0");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_number(&chars, &mut pos, &mut start);
+        let token = consume_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
@@ -1665,10 +2083,10 @@ mod tests {
         }
 
         // Float: 1.0
-        let chars: Vec<char> = vec!['1', '.', '0'];
+        let source = Source::new("1.0");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_number(&chars, &mut pos, &mut start);
+        let token = consume_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
@@ -1681,10 +2099,10 @@ mod tests {
         }
 
         // Integer: 1_0.0
-        let chars: Vec<char> = vec!['1', '_', '0', '.', '0'];
+        let source = Source::new("1_0.0");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_number(&chars, &mut pos, &mut start);
+        let token = consume_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(5, token.as_ref().ok().unwrap().location.end);
@@ -1697,13 +2115,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_consume_number_with_exponent() {
+        let cases = [
+            ("1e9", "1e9", TokenKind::Float),
+            ("2.5E-3", "2.5E-3", TokenKind::Float),
+            ("1_0e+1_0", "10e+10", TokenKind::Float),
+            ("1e", "1", TokenKind::Integer),
+            ("2E-", "2", TokenKind::Integer),
+        ];
+
+        for (script, literal, kind) in cases {
+            let source = Source::new(script);
+            let mut start = 0;
+            let mut pos = 0;
+            let token = consume_number(&source, &mut pos, &mut start).ok().unwrap();
+            assert_eq!(literal, token.literal);
+            assert!(token.kind == kind);
+        }
+    }
+
     #[test]
     fn test_consume_backticks_identifier() {
         // Symbol: `N
-        let chars: Vec<char> = vec!['`', 'N'];
+        let source = Source::new("`N");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_backticks_identifier(&chars, &mut pos, &mut start);
+        let token = consume_backticks_identifier(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!("Unterminated backticks", token.err().unwrap().message());
         } else {
@@ -1711,14 +2149,14 @@ mod tests {
         }
 
         // Symbol: `N`
-        let chars: Vec<char> = vec!['`', 'N', '`'];
+        let source = Source::new("`N`");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_backticks_identifier(&chars, &mut pos, &mut start);
+        let token = consume_backticks_identifier(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
-            assert_eq!("N", token.as_ref().ok().unwrap().literal);
+            assert_eq!("`N`", token.as_ref().ok().unwrap().literal);
             if token.as_ref().ok().unwrap().kind != TokenKind::Symbol {
                 assert!(false);
             }
@@ -1730,10 +2168,10 @@ mod tests {
     #[test]
     fn test_consume_binary_number() {
         // Integer: 2
-        let chars: Vec<char> = vec!['2'];
+        let source = Source::new("2");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_binary_number(&chars, &mut pos, &mut start);
+        let token = consume_binary_number(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!(
                 "Missing digits after the integer base prefix",
@@ -1744,10 +2182,10 @@ mod tests {
         }
 
         // Integer: 010
-        let chars: Vec<char> = vec!['0', '1', '0'];
+        let source = Source::new("010");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_binary_number(&chars, &mut pos, &mut start);
+        let token = consume_binary_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
@@ -1763,10 +2201,10 @@ mod tests {
     #[test]
     fn test_consume_octal_number() {
         // Integer: 8
-        let chars: Vec<char> = vec!['8'];
+        let source = Source::new("8");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_octal_number(&chars, &mut pos, &mut start);
+        let token = consume_octal_number(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!("Invalid octal number", token.err().unwrap().message());
         } else {
@@ -1774,10 +2212,10 @@ mod tests {
         }
 
         // Integer: 0_7
-        let chars: Vec<char> = vec!['0', '_', '7'];
+        let source = Source::new("0_7");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_octal_number(&chars, &mut pos, &mut start);
+        let token = consume_octal_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
@@ -1793,10 +2231,10 @@ mod tests {
     #[test]
     fn test_consume_hex_number() {
         // Integer: G
-        let chars: Vec<char> = vec!['G'];
+        let source = Source::new("G");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_hex_number(&chars, &mut pos, &mut start);
+        let token = consume_hex_number(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!(
                 "Missing digits after the integer base prefix",
@@ -1807,10 +2245,10 @@ mod tests {
         }
 
         // Integer: 01EF
-        let chars: Vec<char> = vec!['0', '1', 'E', 'F'];
+        let source = Source::new("01EF");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_hex_number(&chars, &mut pos, &mut start);
+        let token = consume_hex_number(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(4, token.as_ref().ok().unwrap().location.end);
@@ -1826,10 +2264,10 @@ mod tests {
     #[test]
     fn test_consume_string() {
         // String: "N
-        let chars: Vec<char> = vec!['"', 'N'];
+        let source = Source::new("\"N");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_string(&chars, &mut pos, &mut start);
+        let token = consume_string(&source, &mut pos, &mut start);
         if token.is_err() {
             assert_eq!(
                 "Unterminated double quote string",
@@ -1840,10 +2278,10 @@ mod tests {
         }
 
         // String: "N"
-        let chars: Vec<char> = vec!['"', 'N', '"'];
+        let source = Source::new("\"N\"");
         let mut start = 0;
         let mut pos = 0;
-        let token = consume_string(&chars, &mut pos, &mut start);
+        let token = consume_string(&source, &mut pos, &mut start);
         if token.is_ok() {
             assert_eq!(0, token.as_ref().ok().unwrap().location.start);
             assert_eq!(3, token.as_ref().ok().unwrap().location.end);
@@ -1856,6 +2294,167 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_consume_string_with_escape_sequences() {
+        let cases = [
+            (r#""a\"b""#, "a\"b"),
+            (r#""a\nb\tc""#, "a\nb\tc"),
+            (r#""a\\b""#, "a\\b"),
+            (r#""it\'s""#, "it's"),
+            (r#""caf\u00E9""#, "café"),
+            (r#""\u{1F600}""#, "\u{1F600}"),
+            (r#""مرحبا""#, "مرحبا"),
+        ];
+
+        for (script, expected) in cases {
+            let source = Source::new(script);
+            let mut start = 0;
+            let mut pos = 0;
+            let token = consume_string(&source, &mut pos, &mut start);
+            assert_eq!(expected, token.ok().unwrap().literal);
+            assert_eq!(source.chars.len(), pos);
+        }
+
+        let source = Source::new(r#"'it\'s'"#);
+        let mut start = 0;
+        let mut pos = 0;
+        let token = consume_single_quoted_string(&source, &mut pos, &mut start);
+        assert_eq!("it's", token.ok().unwrap().literal);
+
+        let invalid_cases = [
+            (r#""\q""#, "Unknown escape sequence `\\q`"),
+            (r#""\u12""#, "Invalid unicode escape sequence"),
+            (r#""\u{D800}""#, "Invalid unicode escape sequence"),
+            (r#""\u{41""#, "Invalid unicode escape sequence"),
+            (r#""a\"#, "Unterminated escape sequence"),
+        ];
+
+        for (script, message) in invalid_cases {
+            let source = Source::new(script);
+            let mut start = 0;
+            let mut pos = 0;
+            let token = consume_string(&source, &mut pos, &mut start);
+            assert_eq!(message, token.err().unwrap().message());
+        }
+    }
+
+    #[test]
+    fn test_consume_single_quoted_string() {
+        // String: 'N
+        let source = Source::new("'N");
+        let mut start = 0;
+        let mut pos = 0;
+        let token = consume_single_quoted_string(&source, &mut pos, &mut start);
+        if token.is_err() {
+            assert_eq!(
+                "Unterminated single quote string",
+                token.err().unwrap().message()
+            );
+        } else {
+            assert!(false);
+        }
+
+        // String: 'N''s'
+        let source = Source::new("'N''s'");
+        let mut start = 0;
+        let mut pos = 0;
+        let token = consume_single_quoted_string(&source, &mut pos, &mut start);
+        if token.is_ok() {
+            assert_eq!(0, token.as_ref().ok().unwrap().location.start);
+            assert_eq!(6, token.as_ref().ok().unwrap().location.end);
+            assert_eq!("N's", token.as_ref().ok().unwrap().literal);
+            if token.as_ref().ok().unwrap().kind != TokenKind::String {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_with_recovery() {
+        // Invalid character and unterminated string: SELECT $ 1 "abc
+        let script = "SELECT $ 1 \"abc".to_string();
+        let (tokens, diagnostics) = tokenize_with_recovery(&script, ParserOptions::default());
+        assert_eq!(2, tokens.len());
+        assert_eq!(2, diagnostics.len());
+        if tokens[0].kind != TokenKind::Select || tokens[1].kind != TokenKind::Integer {
+            assert!(false);
+        }
+        assert_eq!("Unexpected character", diagnostics[0].message());
+
+        // Valid script has no diagnostics
+        let script = "SELECT 1".to_string();
+        let (tokens, diagnostics) = tokenize_with_recovery(&script, ParserOptions::default());
+        assert_eq!(2, tokens.len());
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_with_limits() {
+        let options = ParserOptions {
+            max_query_length: 16,
+            max_identifier_length: 4,
+            ..Default::default()
+        };
+
+        let tokens = tokenize_with_options("SELECT * FROM commits", options);
+        assert!(tokens.is_err());
+        assert_eq!(tokens.err().unwrap().message(), "Query is too long");
+
+        let tokens = tokenize_with_options("SELECT title", options);
+        assert!(tokens.is_err());
+        assert_eq!(
+            tokens.err().unwrap().message(),
+            "Identifier name is too long"
+        );
+
+        let tokens = tokenize_with_options("SELECT @one", options);
+        assert!(tokens.is_ok());
+    }
+
+    #[test]
+    fn test_tokenize_with_ansi_mode() {
+        let options = ParserOptions {
+            ansi_mode: true,
+            ..Default::default()
+        };
+
+        // Concat: 'a' || 'b'
+        let script = "'a' || 'b'".to_string();
+        let tokens = tokenize_with_options(&script, options);
+        if let Ok(tokens) = tokens {
+            assert_eq!(3, tokens.len());
+            if tokens[0].kind != TokenKind::String || tokens[1].kind != TokenKind::Concat {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+
+        // Double quoted identifier: "name"
+        let script = "\"name\"".to_string();
+        let tokens = tokenize_with_options(&script, options);
+        if let Ok(tokens) = tokens {
+            if tokens[0].kind != TokenKind::Symbol || tokens[0].identifier() != "name" {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+
+        // Single quoted string is allowed without ANSI mode too
+        let script = "'a'".to_string();
+        let tokens = tokenize(&script);
+        if let Ok(tokens) = tokens {
+            if tokens[0].kind != TokenKind::String || tokens[0].literal != "a" {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+    }
+
     #[test]
     fn test_ignore_single_line_comment() {
         // Comment: "-- N\n"
@@ -1891,20 +2490,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tokenize_non_ascii_characters() {
+        let tokens = tokenize("SELECT `naïve`, émoji").ok().unwrap();
+        assert_eq!(tokens[1].identifier(), "naïve");
+        assert_eq!(tokens[3].literal, "émoji");
+
+        // Non ASCII digits are not numbers
+        let (tokens, diagnostics) = tokenize_with_recovery("SELECT ٣", ParserOptions::default());
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(diagnostics[0].message(), "Unexpected character");
+
+        // C Style comment can contain `*` and `/`
+        let tokens = tokenize("SELECT /* a * b / c */ 1").ok().unwrap();
+        assert_eq!(tokens.len(), 2);
+    }
+
+    #[test]
+    fn test_tokens_borrow_literals_from_script() {
+        let script = "SELECT name, \"a\\tb\", 'c', 1_000, Title FROM commits";
+        let tokens = tokenize(script).ok().unwrap();
+
+        // Literals that are the same as the script are borrowed
+        assert!(matches!(tokens[1].literal, Cow::Borrowed("name")));
+        assert!(matches!(tokens[5].literal, Cow::Borrowed("c")));
+        assert!(matches!(tokens[9].literal, Cow::Borrowed("Title")));
+
+        // Escaped strings and numbers with separators are owned
+        assert!(matches!(tokens[3].literal, Cow::Owned(_)));
+        assert_eq!(tokens[3].literal, "a\tb");
+        assert!(matches!(tokens[7].literal, Cow::Owned(_)));
+        assert_eq!(tokens[7].literal, "1000");
+    }
+
+    #[test]
+    fn test_tokens_keep_original_case() {
+        let tokens = tokenize("Select Name, `Main`, @Var FROM Commits")
+            .ok()
+            .unwrap();
+        assert!(tokens[0].kind == TokenKind::Select);
+        assert_eq!(tokens[0].literal, "Select");
+        assert!(tokens[6].kind == TokenKind::From);
+        assert_eq!(tokens[6].literal, "FROM");
+
+        // Unquoted identifiers are case-insensitive, quoted identifiers are case-sensitive
+        assert_eq!(tokens[1].literal, "Name");
+        assert_eq!(tokens[1].identifier(), "name");
+        assert_eq!(tokens[3].literal, "`Main`");
+        assert_eq!(tokens[3].identifier(), "Main");
+        assert_eq!(tokens[5].literal, "@Var");
+        assert_eq!(tokens[5].identifier(), "@var");
+        assert_eq!(tokens[7].identifier(), "commits");
+
+        let options = ParserOptions {
+            ansi_mode: true,
+            ..Default::default()
+        };
+        let tokens = tokenize_with_options("SELECT \"Say \"\"Hi\"\"\"", options)
+            .ok()
+            .unwrap();
+        assert_eq!(tokens[1].literal, "\"Say \"\"Hi\"\"\"");
+        assert_eq!(tokens[1].identifier(), "Say \"Hi\"");
+    }
+
     #[test]
     fn test_resolve_symbol_kind() {
         // Set: SET
         let literal = "SET".to_string();
-        let kind = resolve_symbol_kind(literal);
+        let kind = resolve_symbol_kind(&literal);
         if kind != TokenKind::Set {
             assert!(false);
         }
 
+        // Show: Show
+        let literal = "Show".to_string();
+        let kind = resolve_symbol_kind(&literal);
+        if kind != TokenKind::Show {
+            assert!(false);
+        }
+
+        // Explain: Explain
+        let literal = "Explain".to_string();
+        let kind = resolve_symbol_kind(&literal);
+        if kind != TokenKind::Explain {
+            assert!(false);
+        }
+
+        // Create: create
+        let literal = "create".to_string();
+        let kind = resolve_symbol_kind(&literal);
+        if kind != TokenKind::Create {
+            assert!(false);
+        }
+
+        // Insert: INSERT
+        let literal = "INSERT".to_string();
+        let kind = resolve_symbol_kind(&literal);
+        if kind != TokenKind::Insert {
+            assert!(false);
+        }
+
         // Symbol: NAME
         let literal = "NAME".to_string();
-        let kind = resolve_symbol_kind(literal);
+        let kind = resolve_symbol_kind(&literal);
         if kind != TokenKind::Symbol {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_line_and_column() {
+        let script = "SELECT *\nFROM commits\nWHERE name = 1";
+        assert_eq!(line_and_column(script, 0), (1, 1));
+        assert_eq!(line_and_column(script, 7), (1, 8));
+        assert_eq!(line_and_column(script, 14), (2, 6));
+        assert_eq!(line_and_column(script, 22), (3, 1));
+    }
 }
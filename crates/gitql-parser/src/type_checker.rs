@@ -269,9 +269,37 @@ pub fn check_all_values_are_same_type(
     Some(data_type)
 }
 
+/// Checks if all non null values has the same type, because NULL can be compared with any type
+/// If they has the same type, return it or return None, if all values are NULL return the Null type
+pub fn check_all_values_are_same_type_or_null(
+    env: &mut Environment,
+    arguments: &Vec<Box<dyn Expression>>,
+) -> Option<DataType> {
+    if arguments.is_empty() {
+        return Some(DataType::Any);
+    }
+
+    let mut data_type: Option<DataType> = None;
+    for argument in arguments {
+        let expr_type = argument.expr_type(env);
+        if expr_type.is_null() {
+            continue;
+        }
+
+        match &data_type {
+            Some(values_type) if *values_type != expr_type => return None,
+            Some(_) => {}
+            None => data_type = Some(expr_type),
+        }
+    }
+
+    Some(data_type.unwrap_or(DataType::Null))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gitql_ast::expression::NullExpression;
     use gitql_ast::expression::NumberExpression;
     use gitql_ast::value::Value;
 
@@ -282,6 +310,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let expr: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -303,6 +341,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let expr: Box<dyn Expression> = Box::new(StringExpression {
             value: "12:36:31".to_string(),
@@ -327,6 +375,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let expr: Box<dyn Expression> = Box::new(StringExpression {
             value: "2024-01-10".to_string(),
@@ -351,6 +409,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let expr: Box<dyn Expression> = Box::new(StringExpression {
             value: "2024-01-10 12:36:31".to_string(),
@@ -375,6 +443,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let expr: Box<dyn Expression> = Box::new(StringExpression {
             value: "invalid".to_string(),
@@ -399,6 +477,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -423,6 +511,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -450,6 +548,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "12:36:31".to_string(),
@@ -477,6 +585,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -504,6 +622,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "2024-01-10".to_string(),
@@ -531,6 +659,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -558,6 +696,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(StringExpression {
             value: "2024-01-10 12:36:31".to_string(),
@@ -585,6 +733,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let lhs: Box<dyn Expression> = Box::new(NumberExpression {
             value: Value::Integer(1),
@@ -610,6 +768,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let arguments: Vec<Box<dyn Expression>> = vec![];
 
@@ -627,6 +795,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let arg1: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -649,6 +827,16 @@ mod tests {
             globals: Default::default(),
             globals_types: Default::default(),
             scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
         };
         let arg1: Box<dyn Expression> = Box::new(StringExpression {
             value: "name".to_string(),
@@ -667,4 +855,56 @@ mod tests {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_check_all_values_are_same_type_or_null() {
+        let mut env = Environment {
+            globals: Default::default(),
+            globals_types: Default::default(),
+            scopes: Default::default(),
+            tables: Default::default(),
+            providers: Default::default(),
+            tables_aliases: Default::default(),
+            subqueries_values: Default::default(),
+            temp_tables: Default::default(),
+            external_tables: Default::default(),
+            session: Default::default(),
+            aggregations: Default::default(),
+            spill: Default::default(),
+            rows_cache: Default::default(),
+        };
+
+        let arg1: Box<dyn Expression> = Box::new(StringExpression {
+            value: "name".to_string(),
+            value_type: StringValueType::Text,
+        });
+        let arg2: Box<dyn Expression> = Box::new(NullExpression {});
+        let arguments: Vec<Box<dyn Expression>> = vec![arg1, arg2];
+
+        let result = check_all_values_are_same_type_or_null(&mut env, &arguments);
+        if result.is_none() || !result.unwrap().is_text() {
+            assert!(false);
+        }
+
+        let arguments: Vec<Box<dyn Expression>> = vec![Box::new(NullExpression {})];
+        let result = check_all_values_are_same_type_or_null(&mut env, &arguments);
+        if result.is_none() || !result.unwrap().is_null() {
+            assert!(false);
+        }
+
+        let arg1: Box<dyn Expression> = Box::new(StringExpression {
+            value: "name".to_string(),
+            value_type: StringValueType::Text,
+        });
+        let arg2: Box<dyn Expression> = Box::new(NullExpression {});
+        let arg3: Box<dyn Expression> = Box::new(NumberExpression {
+            value: Value::Integer(1),
+        });
+        let arguments: Vec<Box<dyn Expression>> = vec![arg1, arg2, arg3];
+
+        let result = check_all_values_are_same_type_or_null(&mut env, &arguments);
+        if result.is_some() {
+            assert!(false);
+        }
+    }
 }
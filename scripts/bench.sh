@@ -0,0 +1,10 @@
+#!/bin/bash
+
+build=$(date +%FT%T%z)
+
+list="gitql-parser gitql-engine"
+for item in $list; do
+  pushd crates/"$item" || exit
+  build=$build cargo bench --all-features
+  popd || exit
+done
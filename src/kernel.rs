@@ -0,0 +1,496 @@
+use gitql_ast::environment::Environment;
+use gitql_ast::format::value_to_text;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::session::SessionOptions;
+use gitql_cli::arguments::Arguments;
+use gitql_cli::render;
+use gitql_engine::engine;
+use gitql_engine::engine::EvaluationResult;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+use hmac::Hmac;
+use hmac::Mac;
+use serde_json::json;
+use serde_json::Value;
+use sha2::Sha256;
+
+const PROTOCOL_VERSION: &str = "5.3";
+const MESSAGE_DELIMITER: &[u8] = b"<IDS|MSG>";
+const TEXT_RESULT_WIDTH: u16 = 120;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Jupyter message without the wire format details
+struct Message {
+    identities: Vec<Vec<u8>>,
+    header: Value,
+    content: Value,
+}
+
+/// Sockets and signing key of the kernel as described in the connection file
+struct Kernel {
+    shell: zmq::Socket,
+    control: zmq::Socket,
+    iopub: zmq::Socket,
+    key: Vec<u8>,
+    session: String,
+    messages_count: usize,
+    execution_count: usize,
+}
+
+/// Run GitQL as Jupyter kernel using the connection file passed by the notebook server,
+/// the kernel implements the subset of the messaging protocol needed to run queries and show their results
+pub fn launch_gitql_kernel(
+    connection_file: &str,
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    env: &mut Environment,
+) -> Result<(), String> {
+    let connection_content = std::fs::read_to_string(connection_file)
+        .map_err(|error| format!("Failed to read connection file: {}", error))?;
+    let connection: Value = serde_json::from_str(&connection_content)
+        .map_err(|error| format!("Invalid connection file: {}", error))?;
+
+    let signature_scheme = connection["signature_scheme"].as_str().unwrap_or("");
+    let key = connection["key"].as_str().unwrap_or("");
+    if !key.is_empty() && signature_scheme != "hmac-sha256" {
+        return Err(format!(
+            "Unsupported signature scheme `{}`",
+            signature_scheme
+        ));
+    }
+
+    let transport = connection["transport"].as_str().unwrap_or("tcp");
+    let ip = connection["ip"].as_str().unwrap_or("127.0.0.1");
+    let endpoint = |port_name: &str| -> Result<String, String> {
+        match connection[port_name].as_u64() {
+            Some(port) => Ok(format!("{}://{}:{}", transport, ip, port)),
+            None => Err(format!("Connection file has no `{}`", port_name)),
+        }
+    };
+
+    let context = zmq::Context::new();
+    let bind = |kind: zmq::SocketType, port_name: &str| -> Result<zmq::Socket, String> {
+        let socket = context.socket(kind).map_err(|error| error.to_string())?;
+        socket
+            .bind(&endpoint(port_name)?)
+            .map_err(|error| format!("Failed to bind `{}`: {}", port_name, error))?;
+        Ok(socket)
+    };
+
+    let mut kernel = Kernel {
+        shell: bind(zmq::ROUTER, "shell_port")?,
+        control: bind(zmq::ROUTER, "control_port")?,
+        iopub: bind(zmq::PUB, "iopub_port")?,
+        key: key.as_bytes().to_vec(),
+        session: format!("gitql-{}", std::process::id()),
+        messages_count: 0,
+        execution_count: 0,
+    };
+
+    // Queries don't read input, but the socket must be bound for the clients to connect
+    let _stdin = bind(zmq::ROUTER, "stdin_port")?;
+
+    // Heartbeat echoes back every message so the client knows that the kernel is alive
+    let heartbeat = bind(zmq::REP, "hb_port")?;
+    std::thread::spawn(move || loop {
+        match heartbeat.recv_bytes(0) {
+            Ok(message) => {
+                if heartbeat.send(message, 0).is_err() {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    });
+
+    loop {
+        let (is_shell_readable, is_control_readable) = {
+            let mut items = [
+                kernel.shell.as_poll_item(zmq::POLLIN),
+                kernel.control.as_poll_item(zmq::POLLIN),
+            ];
+            zmq::poll(&mut items, -1).map_err(|error| error.to_string())?;
+            (items[0].is_readable(), items[1].is_readable())
+        };
+
+        if is_control_readable && !kernel.handle_message(true, arguments, repos, env)? {
+            return Ok(());
+        }
+
+        if is_shell_readable && !kernel.handle_message(false, arguments, repos, env)? {
+            return Ok(());
+        }
+    }
+}
+
+impl Kernel {
+    /// Handle one request from the shell or control socket, returns false if the kernel should shutdown
+    fn handle_message(
+        &mut self,
+        is_control: bool,
+        arguments: &Arguments,
+        repos: &[gix::Repository],
+        env: &mut Environment,
+    ) -> Result<bool, String> {
+        let socket = if is_control {
+            &self.control
+        } else {
+            &self.shell
+        };
+        let parts = socket
+            .recv_multipart(0)
+            .map_err(|error| error.to_string())?;
+
+        // Messages with invalid signature are ignored as described in the protocol
+        let request = match self.decode(parts) {
+            Some(request) => request,
+            None => return Ok(true),
+        };
+
+        let msg_type = request.header["msg_type"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        self.publish(&request, "status", json!({ "execution_state": "busy" }))?;
+
+        let mut is_running = true;
+        match msg_type.as_str() {
+            "kernel_info_request" => {
+                let content = json!({
+                    "status": "ok",
+                    "protocol_version": PROTOCOL_VERSION,
+                    "implementation": "gitql",
+                    "implementation_version": env!("CARGO_PKG_VERSION"),
+                    "language_info": {
+                        "name": "gitql",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "mimetype": "text/x-sql",
+                        "file_extension": ".gql",
+                    },
+                    "banner": format!("GitQL version {}", env!("CARGO_PKG_VERSION")),
+                    "help_links": [],
+                });
+                self.reply(is_control, &request, "kernel_info_reply", content)?;
+            }
+            "execute_request" => {
+                let code = request.content["code"].as_str().unwrap_or("").to_string();
+                let content = self.execute(&request, &code, arguments, repos, env)?;
+                self.reply(is_control, &request, "execute_reply", content)?;
+            }
+            "is_complete_request" => {
+                let content = json!({ "status": "complete" });
+                self.reply(is_control, &request, "is_complete_reply", content)?;
+            }
+            "comm_info_request" => {
+                let content = json!({ "status": "ok", "comms": {} });
+                self.reply(is_control, &request, "comm_info_reply", content)?;
+            }
+            "shutdown_request" => {
+                let restart = request.content["restart"].as_bool().unwrap_or(false);
+                let content = json!({ "status": "ok", "restart": restart });
+                self.reply(is_control, &request, "shutdown_reply", content)?;
+                is_running = false;
+            }
+            _ => {}
+        }
+
+        self.publish(&request, "status", json!({ "execution_state": "idle" }))?;
+        Ok(is_running)
+    }
+
+    /// Execute each non empty line of the cell as a query and publish its result or diagnostic
+    fn execute(
+        &mut self,
+        request: &Message,
+        code: &str,
+        arguments: &Arguments,
+        repos: &[gix::Repository],
+        env: &mut Environment,
+    ) -> Result<Value, String> {
+        self.execution_count += 1;
+        let execution_count = self.execution_count;
+        self.publish(
+            request,
+            "execute_input",
+            json!({ "code": code, "execution_count": execution_count }),
+        )?;
+
+        let options = ParserOptions {
+            any_value_mode: arguments.any_value_mode,
+            ansi_mode: arguments.ansi_mode,
+            ..Default::default()
+        };
+
+        for query in code.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let result = tokenizer::tokenize_with_options(query, options)
+                .and_then(|tokens| {
+                    if tokens.is_empty() {
+                        return Err(Diagnostic::error("Expect a statement to run").as_boxed());
+                    }
+                    parser::parse_gql_with_options(tokens, env, options)
+                })
+                .and_then(|query_node| {
+                    engine::evaluate_with_threads(env, repos, query_node, arguments.threads)
+                });
+            env.clear_session();
+
+            match result {
+                Ok(EvaluationResult::SelectedGroups(mut groups, hidden_selections)) => {
+                    let data = result_data(&mut groups, &hidden_selections, &env.session);
+                    self.publish(
+                        request,
+                        "execute_result",
+                        json!({ "execution_count": execution_count, "data": data, "metadata": {} }),
+                    )?;
+                }
+                Ok(
+                    EvaluationResult::SetGlobalVariable
+                    | EvaluationResult::CreateTempTable
+                    | EvaluationResult::InsertedRows(_),
+                ) => {}
+                Err(diagnostic) => {
+                    let evalue = diagnostic.message().to_string();
+                    let mut traceback = vec![format!("[{}]: {}", diagnostic.label(), evalue)];
+                    traceback.extend(
+                        diagnostic
+                            .notes()
+                            .iter()
+                            .map(|note| format!("Note: {}", note)),
+                    );
+                    traceback.extend(
+                        diagnostic
+                            .helps()
+                            .iter()
+                            .map(|help| format!("Help: {}", help)),
+                    );
+
+                    let error = json!({
+                        "ename": diagnostic.label(),
+                        "evalue": evalue,
+                        "traceback": traceback,
+                    });
+                    self.publish(request, "error", error.clone())?;
+
+                    let mut content = error;
+                    content["status"] = json!("error");
+                    content["execution_count"] = json!(execution_count);
+                    return Ok(content);
+                }
+            }
+        }
+
+        Ok(json!({
+            "status": "ok",
+            "execution_count": execution_count,
+            "user_expressions": {},
+        }))
+    }
+
+    fn reply(
+        &mut self,
+        is_control: bool,
+        request: &Message,
+        msg_type: &str,
+        content: Value,
+    ) -> Result<(), String> {
+        let parts = self.encode(request, msg_type, content, request.identities.clone());
+        let socket = if is_control {
+            &self.control
+        } else {
+            &self.shell
+        };
+        socket
+            .send_multipart(parts, 0)
+            .map_err(|error| error.to_string())
+    }
+
+    fn publish(&mut self, request: &Message, msg_type: &str, content: Value) -> Result<(), String> {
+        let parts = self.encode(
+            request,
+            msg_type,
+            content,
+            vec![msg_type.as_bytes().to_vec()],
+        );
+        self.iopub
+            .send_multipart(parts, 0)
+            .map_err(|error| error.to_string())
+    }
+
+    /// Split the wire message parts and verify its signature
+    fn decode(&self, parts: Vec<Vec<u8>>) -> Option<Message> {
+        let delimiter = parts.iter().position(|part| part == MESSAGE_DELIMITER)?;
+        let frames = parts.get(delimiter + 1..delimiter + 6)?;
+        if !self.key.is_empty() {
+            let signature = std::str::from_utf8(&frames[0]).ok()?;
+            if signature != self.sign(&frames[1..5]) {
+                return None;
+            }
+        }
+
+        Some(Message {
+            identities: parts[..delimiter].to_vec(),
+            header: serde_json::from_slice(&frames[1]).ok()?,
+            content: serde_json::from_slice(&frames[4]).ok()?,
+        })
+    }
+
+    fn encode(
+        &mut self,
+        parent: &Message,
+        msg_type: &str,
+        content: Value,
+        identities: Vec<Vec<u8>>,
+    ) -> Vec<Vec<u8>> {
+        self.messages_count += 1;
+        let header = json!({
+            "msg_id": format!("{}-{}", self.session, self.messages_count),
+            "session": self.session,
+            "username": "gitql",
+            "date": chrono::Utc::now().to_rfc3339(),
+            "msg_type": msg_type,
+            "version": PROTOCOL_VERSION,
+        });
+
+        let frames: Vec<Vec<u8>> = vec![
+            header.to_string().into_bytes(),
+            parent.header.to_string().into_bytes(),
+            b"{}".to_vec(),
+            content.to_string().into_bytes(),
+        ];
+
+        let mut parts = identities;
+        parts.push(MESSAGE_DELIMITER.to_vec());
+        parts.push(self.sign(&frames).into_bytes());
+        parts.extend(frames);
+        parts
+    }
+
+    /// HMAC signature of the header, parent header, metadata and content frames as hex string
+    fn sign(&self, frames: &[Vec<u8>]) -> String {
+        if self.key.is_empty() {
+            return String::new();
+        }
+
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+        for frame in frames {
+            mac.update(frame);
+        }
+
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Rich representations of the query result, notebooks show the richest format they support
+fn result_data(
+    groups: &mut GitQLObject,
+    hidden_selections: &[String],
+    options: &SessionOptions,
+) -> Value {
+    let text =
+        render::render_objects_as_lines(groups, hidden_selections, TEXT_RESULT_WIDTH, options)
+            .join("\n");
+
+    // Rendering flatten the groups, so the hidden selections can be removed from the first group only
+    let visible_indexes: Vec<usize> = groups
+        .titles
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| !hidden_selections.contains(title))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut visible_object = GitQLObject {
+        titles: visible_indexes
+            .iter()
+            .map(|index| groups.titles[*index].to_string())
+            .collect(),
+        groups: vec![],
+    };
+
+    let mut html = String::from("<table><thead><tr>");
+    for title in &visible_object.titles {
+        html.push_str(&format!("<th>{}</th>", escape_html(title)));
+    }
+    html.push_str("</tr></thead><tbody>");
+
+    if let Some(group) = groups.groups.first() {
+        let mut rows = vec![];
+        for row in &group.rows {
+            html.push_str("<tr>");
+            let mut values = vec![];
+            for index in &visible_indexes {
+                let value = &row.values[*index];
+                html.push_str(&format!(
+                    "<td>{}</td>",
+                    escape_html(&value_to_text(value, options))
+                ));
+                values.push(value.clone());
+            }
+            html.push_str("</tr>");
+            rows.push(gitql_ast::object::Row { values });
+        }
+        visible_object
+            .groups
+            .push(gitql_ast::object::Group { rows });
+    }
+    html.push_str("</tbody></table>");
+
+    let json_rows = visible_object
+        .as_json(options)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        .unwrap_or(Value::Array(vec![]));
+
+    json!({
+        "text/plain": text,
+        "text/html": html,
+        "application/json": json_rows,
+    })
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use gitql_ast::object::Group;
+    use gitql_ast::object::Row;
+    use gitql_ast::value::Value as GitQLValue;
+
+    use super::*;
+
+    #[test]
+    fn test_result_data_hidden_selections() {
+        let mut object = GitQLObject {
+            titles: vec!["name".to_string(), "commit_count".to_string()],
+            groups: vec![Group {
+                rows: vec![Row {
+                    values: vec![
+                        GitQLValue::Text("<main>".to_string()),
+                        GitQLValue::Integer(3),
+                    ],
+                }],
+            }],
+        };
+
+        let options = SessionOptions::default();
+        let data = result_data(&mut object, &["commit_count".to_string()], &options);
+        let html = data["text/html"].as_str().unwrap();
+        assert_eq!(
+            html,
+            "<table><thead><tr><th>name</th></tr></thead><tbody><tr><td>&lt;main&gt;</td></tr></tbody></table>"
+        );
+        assert_eq!(data["application/json"], json!([{ "name": "<main>" }]));
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::Write;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style;
+use crossterm::terminal;
+use gitql_ast::environment::Environment;
+use gitql_cli::completer;
+
+/// Disable the terminal raw mode when the line is read, even if it's read with error
+struct RawModeGuard;
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Read one line from the terminal with basic editing keys and `Tab` completion,
+/// returns None if the input is closed by `Ctrl+D` on empty line
+pub fn read_line(prompt: &str, env: &Environment) -> std::io::Result<Option<String>> {
+    terminal::enable_raw_mode()?;
+    let _guard = RawModeGuard;
+
+    let mut line: Vec<char> = vec![];
+    let mut position = 0;
+    draw_line(prompt, &line, position)?;
+
+    loop {
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('c') => {
+                    line.clear();
+                    position = 0;
+                    print!("\r\n");
+                }
+                KeyCode::Char('d') if line.is_empty() => {
+                    print!("\r\n");
+                    return Ok(None);
+                }
+                _ => {}
+            }
+            draw_line(prompt, &line, position)?;
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Enter => {
+                print!("\r\n");
+                std::io::stdout().flush()?;
+                return Ok(Some(line.iter().collect()));
+            }
+            KeyCode::Tab => {
+                let input: String = line.iter().collect();
+                let completion = completer::complete(&input, position, env);
+                let word_length = position - completion.start;
+                let replacement = match completion.candidates.len() {
+                    0 => None,
+                    1 => Some(completion.candidates[0].to_string()),
+                    _ => {
+                        let shared = completer::common_prefix(&completion.candidates);
+                        if shared.chars().count() <= word_length {
+                            print!("\r\n{}\r\n", completion.candidates.join("  "));
+                        }
+                        Some(shared)
+                    }
+                };
+
+                if let Some(replacement) = replacement {
+                    line.splice(completion.start..position, replacement.chars());
+                    position = completion.start + replacement.chars().count();
+                }
+            }
+            KeyCode::Char(char) => {
+                line.insert(position, char);
+                position += 1;
+            }
+            KeyCode::Backspace if position > 0 => {
+                position -= 1;
+                line.remove(position);
+            }
+            KeyCode::Delete if position < line.len() => {
+                line.remove(position);
+            }
+            KeyCode::Left => {
+                position = position.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                position = (position + 1).min(line.len());
+            }
+            KeyCode::Home => {
+                position = 0;
+            }
+            KeyCode::End => {
+                position = line.len();
+            }
+            _ => {}
+        }
+
+        draw_line(prompt, &line, position)?;
+    }
+}
+
+fn draw_line(prompt: &str, line: &[char], position: usize) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    let content: String = line.iter().collect();
+    queue!(
+        stdout,
+        cursor::MoveToColumn(0),
+        terminal::Clear(terminal::ClearType::CurrentLine),
+        style::Print(prompt),
+        style::Print(content),
+        cursor::MoveToColumn((prompt.chars().count() + position) as u16),
+    )?;
+    stdout.flush()
+}
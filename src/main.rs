@@ -1,17 +1,56 @@
 use atty::Stream;
 use gitql_ast::environment::Environment;
+use gitql_ast::external::load_external_table;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::rows_cache::RowsCache;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::session::OUTPUT_FORMAT_VARIABLE;
+use gitql_ast::spill::SpillOptions;
+use gitql_ast::spill::DEFAULT_SPILL_THRESHOLD;
+use gitql_ast::statement::GQLQuery;
+use gitql_ast::statement::InsertStatement;
+use gitql_ast::statement::InsertTarget;
+use gitql_ast::statement::Query;
 use gitql_cli::arguments;
 use gitql_cli::arguments::Arguments;
 use gitql_cli::arguments::Command;
 use gitql_cli::arguments::OutputFormat;
+use gitql_cli::colored_stream;
+use gitql_cli::config;
+use gitql_cli::config::Config;
 use gitql_cli::diagnostic_reporter;
 use gitql_cli::diagnostic_reporter::DiagnosticReporter;
+use gitql_cli::pager;
 use gitql_cli::render;
+use gitql_cli::render::RenderOptions;
+use gitql_cli::script;
 use gitql_engine::engine;
 use gitql_engine::engine::EvaluationResult::SelectedGroups;
+use gitql_engine::engine_estimate;
+use gitql_engine::engine_memory;
+use gitql_engine::engine_progress;
+use gitql_engine::engine_stats::QueryStats;
+use gitql_parser::context::ParserOptions;
 use gitql_parser::diagnostic::Diagnostic;
 use gitql_parser::parser;
 use gitql_parser::tokenizer;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[cfg(feature = "jupyter")]
+mod kernel;
+mod line_editor;
+mod mysql;
+mod progress;
+mod self_update;
+mod server;
+mod tui;
+mod validator;
+
+const REPL_PROMPT: &str = "gql > ";
+const REPL_ROWS_CACHE_CAPACITY: usize = 16;
+const PAGER_RESERVED_LINES: usize = 6;
+const SQLITE_RESULT_TABLE: &str = "result";
 
 fn main() {
     if cfg!(debug_assertions) {
@@ -19,14 +58,49 @@ fn main() {
         std::env::set_var("RUST_LIB_BACKTRACE", "1");
     }
 
-    let args = std::env::args().collect();
-    let command = arguments::parse_arguments(&args);
+    let args: Vec<String> = std::env::args().collect();
+
+    // Default options are loaded from the config file and environment variables unless `--no-config` is passed
+    let config = if args.iter().any(|arg| arg == "--no-config") {
+        Config::default()
+    } else {
+        match config::load_config() {
+            Ok(config) => config,
+            Err(error) => {
+                let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let command = arguments::parse_arguments_with_config(&args, config);
+
+    // Memory limit, colors and tables style are applied to every query of the session
+    if let Command::ReplMode(arguments)
+    | Command::QueryMode(_, arguments)
+    | Command::ScriptMode(_, arguments)
+    | Command::TuiMode(arguments)
+    | Command::KernelMode(_, arguments)
+    | Command::ExportMode(_, arguments)
+    | Command::ServeMode(arguments)
+    | Command::MySQLMode(arguments) = &command
+    {
+        engine_memory::set_memory_limit(arguments.memory_limit);
+        colored_stream::set_colors_enabled(!arguments.no_color);
+        render::set_render_options(RenderOptions {
+            theme: arguments.theme,
+            max_column_width: arguments.max_column_width,
+        });
+    }
 
     match command {
         Command::ReplMode(arguments) => {
+            enable_terminal_progress();
             launch_gitql_repl(arguments);
         }
         Command::QueryMode(query, arguments) => {
+            enable_terminal_progress();
             let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
             let git_repos_result = validate_git_repositories(&arguments.repos);
             if git_repos_result.is_err() {
@@ -39,20 +113,116 @@ fn main() {
 
             let repos = git_repos_result.ok().unwrap();
             let mut env = Environment::default();
+            if let Err(error) = prepare_environment(&mut env, &arguments) {
+                reporter.report_diagnostic(&query, Diagnostic::error(&error));
+                return;
+            }
             execute_gitql_query(query, &arguments, &repos, &mut env, &mut reporter);
         }
+        Command::ScriptMode(path, arguments) => {
+            enable_terminal_progress();
+            if !execute_gitql_script(&path, &arguments) {
+                std::process::exit(1);
+            }
+        }
+        Command::TuiMode(arguments) => {
+            let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+            let git_repos_result = validate_git_repositories(&arguments.repos);
+            if git_repos_result.is_err() {
+                reporter.report_diagnostic(
+                    "",
+                    Diagnostic::error(git_repos_result.err().unwrap().as_str()),
+                );
+                return;
+            }
+
+            let repos = git_repos_result.ok().unwrap();
+            let mut env = Environment::default();
+            if let Err(error) = prepare_environment(&mut env, &arguments) {
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+                return;
+            }
+
+            env.rows_cache = Arc::new(Mutex::new(RowsCache::new(REPL_ROWS_CACHE_CAPACITY)));
+            if let Err(error) = tui::launch_gitql_tui(&arguments, &repos, &mut env) {
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+            }
+        }
+        Command::KernelMode(connection_file, arguments) => {
+            launch_gitql_kernel(connection_file, arguments);
+        }
+        Command::ValidateMode(path, arguments) => {
+            match validator::validate_queries(&path, &arguments) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(error) => {
+                    let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+                    reporter.report_diagnostic("", Diagnostic::error(&error));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::ExportMode(database_path, arguments) => {
+            enable_terminal_progress();
+            if !export_gitql_tables(&database_path, &arguments) {
+                std::process::exit(1);
+            }
+        }
+        Command::ServeMode(arguments) => {
+            let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+            let git_repos_result = validate_git_repositories(&arguments.repos);
+            if git_repos_result.is_err() {
+                reporter.report_diagnostic(
+                    "",
+                    Diagnostic::error(git_repos_result.err().unwrap().as_str()),
+                );
+                std::process::exit(1);
+            }
+
+            let repos = git_repos_result.ok().unwrap();
+            if let Err(error) = server::launch_gitql_server(&arguments, &repos) {
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+                std::process::exit(1);
+            }
+        }
+        Command::MySQLMode(arguments) => {
+            let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+            if let Err(error) = validate_git_repositories(&arguments.repos) {
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+                std::process::exit(1);
+            }
+
+            if let Err(error) = mysql::launch_mysql_server(arguments) {
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+                std::process::exit(1);
+            }
+        }
         Command::Help => {
             arguments::print_help_list();
         }
         Command::Version => {
             println!("GitQL version {}", env!("CARGO_PKG_VERSION"));
         }
+        Command::SelfUpdate => {
+            if let Err(error) = self_update::self_update() {
+                let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+                reporter.report_diagnostic("", Diagnostic::error(&error));
+            }
+        }
         Command::Error(error_message) => {
             println!("{}", error_message);
         }
     }
 }
 
+/// Render the progress of long running scans if stderr is a terminal
+fn enable_terminal_progress() {
+    if atty::is(Stream::Stderr) {
+        let reporter = progress::TerminalProgressReporter::default();
+        engine_progress::set_progress_reporter(Some(Box::new(reporter)));
+    }
+}
+
 fn launch_gitql_repl(arguments: Arguments) {
     let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
     let git_repos_result = validate_git_repositories(&arguments.repos);
@@ -65,25 +235,47 @@ fn launch_gitql_repl(arguments: Arguments) {
     }
 
     let mut global_env = Environment::default();
+    if let Err(error) = prepare_environment(&mut global_env, &arguments) {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+        return;
+    }
+
     let git_repositories = git_repos_result.ok().unwrap();
 
+    // Cache the collected rows so consecutive queries don't walk the repositories again
+    global_env.rows_cache = Arc::new(Mutex::new(RowsCache::new(REPL_ROWS_CACHE_CAPACITY)));
+
+    // Line editing and tab completion are available only if both input and output are terminal
+    let is_interactive = atty::is(Stream::Stdin) && atty::is(Stream::Stdout);
+
     let mut input = String::new();
 
     loop {
-        // Render Prompt only if input is received from terminal
-        if atty::is(Stream::Stdin) {
-            print!("gql > ");
-        }
-
-        std::io::Write::flush(&mut std::io::stdout()).expect("flush failed!");
-        match std::io::stdin().read_line(&mut input) {
-            Ok(buffer_length) => {
-                if buffer_length == 0 {
+        if is_interactive {
+            match line_editor::read_line(REPL_PROMPT, &global_env) {
+                Ok(Some(line)) => input = line,
+                Ok(None) => break,
+                Err(error) => {
+                    reporter.report_diagnostic(&input, Diagnostic::error(&format!("{}", error)));
                     break;
                 }
             }
-            Err(error) => {
-                reporter.report_diagnostic(&input, Diagnostic::error(&format!("{}", error)));
+        } else {
+            // Render Prompt only if input is received from terminal
+            if atty::is(Stream::Stdin) {
+                print!("{}", REPL_PROMPT);
+            }
+
+            std::io::Write::flush(&mut std::io::stdout()).expect("flush failed!");
+            match std::io::stdin().read_line(&mut input) {
+                Ok(buffer_length) => {
+                    if buffer_length == 0 {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    reporter.report_diagnostic(&input, Diagnostic::error(&format!("{}", error)));
+                }
             }
         }
 
@@ -97,6 +289,15 @@ fn launch_gitql_repl(arguments: Arguments) {
             break;
         }
 
+        if stdin_input == ":cache clear" {
+            if let Ok(mut cache) = global_env.rows_cache.lock() {
+                cache.clear();
+            }
+            println!("Cache cleared");
+            input.clear();
+            continue;
+        }
+
         execute_gitql_query(
             stdin_input.to_owned(),
             &arguments,
@@ -110,111 +311,357 @@ fn launch_gitql_repl(arguments: Arguments) {
     }
 }
 
+/// Execute the statements of the script file in order after defining the script arguments
+/// as global variables, stops at the first error and returns false if any error is reported
+fn execute_gitql_script(path: &str, arguments: &Arguments) -> bool {
+    let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            let message = format!("Failed to read script file `{}`: {}", path, error);
+            reporter.report_diagnostic("", Diagnostic::error(&message));
+            return false;
+        }
+    };
+
+    let parser_options = ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: arguments.ansi_mode,
+        ..Default::default()
+    };
+
+    let statements = match script::split_script_statements(&content, parser_options) {
+        Ok(statements) => statements,
+        Err(diagnostic) => {
+            reporter.report_diagnostic(&content, *diagnostic);
+            return false;
+        }
+    };
+
+    let git_repos_result = validate_git_repositories(&arguments.repos);
+    if git_repos_result.is_err() {
+        reporter.report_diagnostic(
+            "",
+            Diagnostic::error(git_repos_result.err().unwrap().as_str()),
+        );
+        return false;
+    }
+
+    let repos = git_repos_result.ok().unwrap();
+    let mut env = Environment::default();
+    if let Err(error) = script::define_script_arguments(&mut env, &arguments.script_arguments) {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+        return false;
+    }
+
+    if let Err(error) = prepare_environment(&mut env, arguments) {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+        return false;
+    }
+
+    for statement in statements {
+        if !execute_gitql_query(statement, arguments, &repos, &mut env, &mut reporter) {
+            return false;
+        }
+        env.clear_session();
+    }
+    true
+}
+
+#[cfg(feature = "jupyter")]
+fn launch_gitql_kernel(connection_file: String, arguments: Arguments) {
+    let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+    let git_repos_result = validate_git_repositories(&arguments.repos);
+    if git_repos_result.is_err() {
+        reporter.report_diagnostic(
+            "",
+            Diagnostic::error(git_repos_result.err().unwrap().as_str()),
+        );
+        return;
+    }
+
+    let repos = git_repos_result.ok().unwrap();
+    let mut env = Environment::default();
+    if let Err(error) = prepare_environment(&mut env, &arguments) {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+        return;
+    }
+
+    env.rows_cache = Arc::new(Mutex::new(RowsCache::new(REPL_ROWS_CACHE_CAPACITY)));
+    if let Err(error) = kernel::launch_gitql_kernel(&connection_file, &arguments, &repos, &mut env)
+    {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+    }
+}
+
+#[cfg(not(feature = "jupyter"))]
+fn launch_gitql_kernel(_connection_file: String, _arguments: Arguments) {
+    let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+    let diagnostic = Diagnostic::error("GitQL is built without Jupyter kernel support")
+        .add_help("Try to build GitQL with `--features jupyter`");
+    reporter.report_diagnostic("", diagnostic);
+}
+
+/// Export the selected tables of the repositories into the SQLite database, each table is
+/// written with its name and all of its columns, returns false if any error is reported
+fn export_gitql_tables(database_path: &str, arguments: &Arguments) -> bool {
+    let mut reporter = diagnostic_reporter::DiagnosticReporter::default();
+    let git_repos_result = validate_git_repositories(&arguments.repos);
+    if git_repos_result.is_err() {
+        reporter.report_diagnostic(
+            "",
+            Diagnostic::error(git_repos_result.err().unwrap().as_str()),
+        );
+        return false;
+    }
+
+    let repos = git_repos_result.ok().unwrap();
+    let mut env = Environment::default();
+    if let Err(error) = prepare_environment(&mut env, arguments) {
+        reporter.report_diagnostic("", Diagnostic::error(&error));
+        return false;
+    }
+
+    for table in &arguments.export_tables {
+        let query = format!("SELECT * FROM {}", table);
+        let tokens = match tokenizer::tokenize(&query) {
+            Ok(tokens) => tokens,
+            Err(diagnostic) => {
+                reporter.report_diagnostic(&query, *diagnostic);
+                return false;
+            }
+        };
+
+        let query_node = match parser::parse_gql(tokens, &mut env) {
+            Ok(query_node) => query_node,
+            Err(diagnostic) => {
+                reporter.report_diagnostic(&query, *diagnostic);
+                return false;
+            }
+        };
+
+        let evaluation_result =
+            engine::evaluate_with_threads(&mut env, &repos, query_node, arguments.threads);
+        match evaluation_result {
+            Ok(SelectedGroups(mut groups, hidden_selection)) => {
+                groups.remove_hidden_selections(&hidden_selection);
+                if let Err(error) = export_to_sqlite(database_path, table, &groups, &env.session) {
+                    reporter.report_diagnostic(&query, *error);
+                    return false;
+                }
+            }
+            Ok(_) => {}
+            Err(diagnostic) => {
+                reporter.report_diagnostic(&query, *diagnostic);
+                return false;
+            }
+        }
+        env.clear_session();
+    }
+    true
+}
+
+#[cfg(feature = "sqlite")]
+fn export_to_sqlite(
+    path: &str,
+    table_name: &str,
+    object: &GitQLObject,
+    options: &SessionOptions,
+) -> Result<(), Box<Diagnostic>> {
+    gitql_cli::sqlite::export_object_to_sqlite(path, table_name, object, options)
+        .map_err(|error| Diagnostic::error(&error).as_boxed())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn export_to_sqlite(
+    _path: &str,
+    _table_name: &str,
+    _object: &GitQLObject,
+    _options: &SessionOptions,
+) -> Result<(), Box<Diagnostic>> {
+    Err(
+        Diagnostic::error("GitQL is built without SQLite export support")
+            .add_help("Try to build GitQL with `--features sqlite`")
+            .as_boxed(),
+    )
+}
+
+/// Execute the query and render its result, returns false if any error is reported
 fn execute_gitql_query(
     query: String,
     arguments: &Arguments,
     repos: &[gix::Repository],
     env: &mut Environment,
     reporter: &mut DiagnosticReporter,
-) {
+) -> bool {
     let front_start = std::time::Instant::now();
-    let tokenizer_result = tokenizer::tokenize(query.clone());
+    let parser_options = ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: arguments.ansi_mode,
+        ..Default::default()
+    };
+
+    // Query terminated by `\G` is rendered vertically like MySQL
+    let (query, is_vertical_query) = render::split_vertical_terminator(&query);
+    let query = query.to_string();
+    let vertical = arguments.vertical || is_vertical_query;
+
+    let tokenizer_result = tokenizer::tokenize_with_options(&query, parser_options);
     if tokenizer_result.is_err() {
         let diagnostic = tokenizer_result.err().unwrap();
         reporter.report_diagnostic(&query, *diagnostic);
-        return;
+        return false;
     }
 
     let tokens = tokenizer_result.ok().unwrap();
     if tokens.is_empty() {
-        return;
+        return true;
     }
 
-    let parser_result = parser::parse_gql(tokens, env);
+    let parser_result = parser::parse_gql_with_options(tokens, env, parser_options);
     if parser_result.is_err() {
         let diagnostic = parser_result.err().unwrap();
         reporter.report_diagnostic(&query, *diagnostic);
-        return;
+        return false;
     }
 
     let query_node = parser_result.ok().unwrap();
     let front_duration = front_start.elapsed();
 
+    if let Query::Select(select_query) = &query_node {
+        print_scan_estimate_warning(env, repos, select_query);
+    }
+
     let engine_start = std::time::Instant::now();
-    let evaluation_result = engine::evaluate(env, repos, query_node);
+    let mut stats = QueryStats::default();
+    let evaluation_result =
+        engine::evaluate_with_stats(env, repos, query_node, arguments.threads, &mut stats);
 
     // Report Runtime exceptions if they exists
     if evaluation_result.is_err() {
-        reporter.report_diagnostic(
-            &query,
-            Diagnostic::exception(&evaluation_result.err().unwrap()),
-        );
-        return;
+        reporter.report_diagnostic(&query, *evaluation_result.err().unwrap());
+        return false;
     }
 
     // Render the result only if they are selected groups not any other statement
     let engine_result = evaluation_result.ok().unwrap();
     if let SelectedGroups(mut groups, hidden_selection) = engine_result {
-        match arguments.output_format {
-            OutputFormat::Render => {
-                render::render_objects(
+        // Output format selected by `@@output_format` overrides the `--output` argument
+        let output_format = match env.globals.get(OUTPUT_FORMAT_VARIABLE) {
+            Some(format) => {
+                arguments::parse_output_format(&format.as_text()).unwrap_or(arguments.output_format)
+            }
+            None => arguments.output_format,
+        };
+
+        if output_format == OutputFormat::Render && arguments.output_path.is_none() {
+            let options = &env.session;
+            render_objects_to_terminal(
+                &mut groups,
+                &hidden_selection,
+                arguments,
+                vertical,
+                options,
+            );
+        } else if output_format == OutputFormat::SQLite {
+            let Some(path) = &arguments.output_path else {
+                let diagnostic = Diagnostic::error("SQLite format requires database file path")
+                    .add_help("Pass the database file path using `--output`");
+                reporter.report_diagnostic(&query, diagnostic);
+                return false;
+            };
+
+            groups.remove_hidden_selections(&hidden_selection);
+            if let Err(diagnostic) =
+                export_to_sqlite(path, SQLITE_RESULT_TABLE, &groups, &env.session)
+            {
+                reporter.report_diagnostic(&query, *diagnostic);
+                return false;
+            }
+        } else {
+            let output = match output_format {
+                OutputFormat::Render if vertical => Ok(render::render_objects_vertically(
                     &mut groups,
                     &hidden_selection,
-                    arguments.pagination,
-                    arguments.page_size,
-                );
-            }
-            OutputFormat::JSON => {
-                let mut indexes = vec![];
-                for (index, title) in groups.titles.iter().enumerate() {
-                    if hidden_selection.contains(title) {
-                        indexes.insert(0, index);
-                    }
+                    &env.session,
+                )),
+                OutputFormat::Render => Ok(render::render_objects_as_text(
+                    &mut groups,
+                    &hidden_selection,
+                    &env.session,
+                )),
+                OutputFormat::JSON => {
+                    groups.remove_hidden_selections(&hidden_selection);
+                    groups
+                        .as_json(&env.session)
+                        .map(|json| format!("{}\n", json))
+                        .map_err(|error| error.to_string())
                 }
+                OutputFormat::CSV | OutputFormat::TSV => {
+                    groups.remove_hidden_selections(&hidden_selection);
+                    let delimiter = if output_format == OutputFormat::TSV {
+                        b'\t'
+                    } else {
+                        b','
+                    };
 
-                if groups.len() > 1 {
-                    groups.flat()
+                    groups
+                        .as_delimited(delimiter, !arguments.no_header, &env.session)
+                        .map_err(|error| error.to_string())
                 }
+                OutputFormat::SQLite => unreachable!("SQLite output is exported before"),
+            };
 
-                for index in indexes {
-                    groups.titles.remove(index);
-
-                    for row in &mut groups.groups[0].rows {
-                        row.values.remove(index);
-                    }
+            let write_result = output.and_then(|content| match &arguments.output_path {
+                Some(path) => render::write_output_file(path, &content, !arguments.no_overwrite),
+                None => {
+                    print!("{}", content);
+                    Ok(())
                 }
+            });
 
-                if let Ok(json) = groups.as_json() {
-                    println!("{}", json);
-                }
+            if let Err(error) = write_result {
+                reporter.report_diagnostic(&query, Diagnostic::error(&error));
+                return false;
             }
-            OutputFormat::CSV => {
-                let mut indexes = vec![];
-                for (index, title) in groups.titles.iter().enumerate() {
-                    if hidden_selection.contains(title) {
-                        indexes.insert(0, index);
-                    }
-                }
+        }
+    }
 
-                if groups.len() > 1 {
-                    groups.flat()
-                }
+    print_analysis(arguments, front_duration, engine_start);
+    print_stats(arguments, &stats);
+    true
+}
 
-                for index in indexes {
-                    groups.titles.remove(index);
+/// Print the query statistics to stderr so they don't mix with the exported result
+fn print_stats(arguments: &Arguments, stats: &QueryStats) {
+    if arguments.stats {
+        eprintln!();
+        eprintln!("Statistics:");
+        for line in stats.to_lines() {
+            eprintln!("{}", line);
+        }
+    }
+}
 
-                    for row in &mut groups.groups[0].rows {
-                        row.values.remove(index);
-                    }
-                }
+/// Warn on stderr before evaluating a query that scans an extremely large table
+fn print_scan_estimate_warning(env: &Environment, repos: &[gix::Repository], query: &GQLQuery) {
+    let Some(estimate) = engine_estimate::estimate_query_rows(env, repos, query) else {
+        return;
+    };
 
-                if let Ok(csv) = groups.as_csv() {
-                    println!("{}", csv);
-                }
-            }
-        }
+    if estimate.is_expensive() {
+        eprintln!(
+            "Warning: Query scans about {} rows from `{}`, it may take a long time",
+            estimate.rows_count, estimate.table_name
+        );
     }
+}
 
+fn print_analysis(
+    arguments: &Arguments,
+    front_duration: std::time::Duration,
+    engine_start: std::time::Instant,
+) {
     let engine_duration = engine_start.elapsed();
 
     if arguments.analysis {
@@ -227,10 +674,134 @@ fn execute_gitql_query(
     }
 }
 
+/// Render the result table, if stdout is a terminal and the table is taller than it the table
+/// is piped to the pager, or paginated internally if the pager can't be started
+fn render_objects_to_terminal(
+    groups: &mut GitQLObject,
+    hidden_selection: &[String],
+    arguments: &Arguments,
+    vertical: bool,
+    options: &SessionOptions,
+) {
+    if vertical {
+        let content = render::render_objects_vertically(groups, hidden_selection, options);
+        print_content_to_terminal(&content, arguments);
+        return;
+    }
+
+    if arguments.pagination || arguments.no_pager || !atty::is(Stream::Stdout) {
+        render::render_objects(
+            groups,
+            hidden_selection,
+            arguments.pagination,
+            arguments.page_size,
+            options,
+        );
+        return;
+    }
+
+    let terminal_height = match crossterm::terminal::size().map(|(_, height)| height as usize) {
+        Ok(height) if height > 0 => height,
+        _ => {
+            render::render_objects(
+                groups,
+                hidden_selection,
+                false,
+                arguments.page_size,
+                options,
+            );
+            return;
+        }
+    };
+
+    let content = render::render_objects_as_text(groups, hidden_selection, options);
+    if !pager::is_content_exceed_height(&content, terminal_height) {
+        render::render_objects(
+            groups,
+            hidden_selection,
+            false,
+            arguments.page_size,
+            options,
+        );
+        return;
+    }
+
+    if let Some(command) = pager::pager_command() {
+        if pager::page_content(&command, &content).is_ok() {
+            return;
+        }
+    } else {
+        // The pager is disabled explicitly so print the table as it is
+        print!("{}", content);
+        return;
+    }
+
+    // Fallback to the internal pager, keep space for the table borders and the page prompt
+    let page_size = terminal_height.saturating_sub(PAGER_RESERVED_LINES).max(1);
+    render::render_objects(groups, hidden_selection, true, page_size, options);
+}
+
+/// Print the content, or pipe it to the pager if stdout is a terminal and the content is taller than it
+fn print_content_to_terminal(content: &str, arguments: &Arguments) {
+    let terminal_height = crossterm::terminal::size().map_or(0, |(_, height)| height as usize);
+    if !arguments.no_pager
+        && atty::is(Stream::Stdout)
+        && terminal_height > 0
+        && pager::is_content_exceed_height(content, terminal_height)
+    {
+        if let Some(command) = pager::pager_command() {
+            if pager::page_content(&command, content).is_ok() {
+                return;
+            }
+        }
+    }
+
+    print!("{}", content);
+}
+
+/// Set the session variables defaults of the config and load the tables of `--table name=path`
+/// arguments into the environment as read-only tables
+fn prepare_environment(env: &mut Environment, arguments: &Arguments) -> Result<(), String> {
+    config::define_session_variables(env, &arguments.session_variables);
+    env.spill = arguments.spill_dir.as_ref().map(|directory| SpillOptions {
+        directory: directory.into(),
+        threshold: arguments.spill_threshold.unwrap_or(DEFAULT_SPILL_THRESHOLD),
+        max_size: arguments.spill_limit,
+    });
+    for (table_name, path) in &arguments.external_tables {
+        if env.has_table(table_name) {
+            return Err(format!("Table `{}` already exists", table_name));
+        }
+
+        let (fields, object) = load_external_table(path)
+            .map_err(|error| format!("Can't load external table `{}`: {}", path, error))?;
+        env.define_external_table(table_name, fields, object);
+    }
+    Ok(())
+}
+
+/// Queries received by the servers from other machines are not allowed to write files on the server
+fn check_server_query(query: &Query) -> Result<(), Box<Diagnostic>> {
+    if let Query::Insert(InsertStatement {
+        target: InsertTarget::File(path, _),
+        ..
+    }) = query
+    {
+        return Err(Diagnostic::error(&format!(
+            "Can't insert into file `{}` from server connection",
+            path
+        ))
+        .add_note("Writing files is disabled for the queries received by the server")
+        .as_boxed());
+    }
+    Ok(())
+}
+
 fn validate_git_repositories(repositories: &Vec<String>) -> Result<Vec<gix::Repository>, String> {
     let mut git_repositories: Vec<gix::Repository> = vec![];
     for repository in repositories {
-        let git_repository = gix::open(repository);
+        // Discover the repository so paths inside the work tree, bare repositories and linked worktrees are resolved
+        let git_repository = gix::discover(repository);
         if git_repository.is_err() {
             return Err(git_repository.err().unwrap().to_string());
         }
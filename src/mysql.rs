@@ -0,0 +1,1046 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::format::value_to_text;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+use gitql_ast::session::is_session_variable;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::types::DataType;
+use gitql_ast::value::Value;
+use gitql_cli::arguments::Arguments;
+use gitql_engine::engine;
+use gitql_engine::engine::EvaluationResult;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+use gitql_parser::tokenizer::TokenKind;
+use sha1::Digest;
+use sha1::Sha1;
+
+const SERVER_VERSION: &str = "8.0.0-GitQL";
+const DATABASE_NAME: &str = "gitql";
+const AUTH_PLUGIN_NAME: &str = "mysql_native_password";
+const SCRAMBLE_LENGTH: usize = 20;
+const MAX_PAYLOAD_LENGTH: usize = 0xff_ffff;
+
+/// Maximum number of connections served at the same time, the next connections are refused
+const MAX_CONNECTIONS: usize = 64;
+
+const CLIENT_LONG_PASSWORD: u32 = 0x1;
+const CLIENT_LONG_FLAG: u32 = 0x4;
+const CLIENT_CONNECT_WITH_DB: u32 = 0x8;
+const CLIENT_PROTOCOL_41: u32 = 0x200;
+const CLIENT_TRANSACTIONS: u32 = 0x2000;
+const CLIENT_SECURE_CONNECTION: u32 = 0x8000;
+const CLIENT_PLUGIN_AUTH: u32 = 0x80000;
+const CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 0x200000;
+const SERVER_CAPABILITIES: u32 = CLIENT_LONG_PASSWORD
+    | CLIENT_LONG_FLAG
+    | CLIENT_CONNECT_WITH_DB
+    | CLIENT_PROTOCOL_41
+    | CLIENT_TRANSACTIONS
+    | CLIENT_SECURE_CONNECTION
+    | CLIENT_PLUGIN_AUTH;
+
+const SERVER_STATUS_AUTOCOMMIT: u16 = 0x2;
+const UTF8_GENERAL_CI: u8 = 0x21;
+
+const COM_QUIT: u8 = 0x01;
+const COM_INIT_DB: u8 = 0x02;
+const COM_QUERY: u8 = 0x03;
+const COM_FIELD_LIST: u8 = 0x04;
+const COM_PING: u8 = 0x0e;
+
+const ER_CON_COUNT_ERROR: u16 = 1040;
+const ER_ACCESS_DENIED_ERROR: u16 = 1045;
+const ER_UNKNOWN_COM_ERROR: u16 = 1047;
+const ER_PARSE_ERROR: u16 = 1064;
+const ER_NET_PACKET_TOO_LARGE: u16 = 1153;
+
+const MYSQL_TYPE_TINY: u8 = 0x01;
+const MYSQL_TYPE_DOUBLE: u8 = 0x05;
+const MYSQL_TYPE_LONGLONG: u8 = 0x08;
+const MYSQL_TYPE_DATE: u8 = 0x0a;
+const MYSQL_TYPE_TIME: u8 = 0x0b;
+const MYSQL_TYPE_DATETIME: u8 = 0x0c;
+const MYSQL_TYPE_VAR_STRING: u8 = 0xfd;
+
+/// Values of the MySQL system variables that clients read after connecting, for example `@@version_comment`
+const MYSQL_SYSTEM_VARIABLES: [(&str, &str); 28] = [
+    ("auto_increment_increment", "1"),
+    ("autocommit", "1"),
+    ("character_set_client", "utf8mb4"),
+    ("character_set_connection", "utf8mb4"),
+    ("character_set_database", "utf8mb4"),
+    ("character_set_results", "utf8mb4"),
+    ("character_set_server", "utf8mb4"),
+    ("collation_connection", "utf8mb4_general_ci"),
+    ("collation_database", "utf8mb4_general_ci"),
+    ("collation_server", "utf8mb4_general_ci"),
+    ("init_connect", ""),
+    ("interactive_timeout", "28800"),
+    ("license", "MIT"),
+    ("lower_case_table_names", "0"),
+    ("max_allowed_packet", "67108864"),
+    ("net_buffer_length", "16384"),
+    ("net_write_timeout", "60"),
+    ("performance_schema", "0"),
+    ("query_cache_size", "0"),
+    ("sql_mode", ""),
+    ("system_time_zone", "UTC"),
+    ("time_zone", "SYSTEM"),
+    ("transaction_isolation", "REPEATABLE-READ"),
+    ("transaction_read_only", "0"),
+    ("tx_isolation", "REPEATABLE-READ"),
+    ("version", SERVER_VERSION),
+    ("version_comment", "GitQL"),
+    ("wait_timeout", "28800"),
+];
+
+/// Serve the queries over the MySQL client/server protocol so existing MySQL clients and BI tools
+/// can query the repositories, only the text protocol subset needed to run queries is supported,
+/// each connection is handled on its own thread with its own environment and opened repositories,
+/// up to `MAX_CONNECTIONS` connections at the same time
+pub fn launch_mysql_server(arguments: Arguments) -> Result<(), String> {
+    let address = format!("{}:{}", arguments.serve_host, arguments.serve_port);
+    let listener = TcpListener::bind(&address)
+        .map_err(|error| format!("Failed to listen on `{}`: {}", address, error))?;
+
+    println!("GitQL is serving MySQL protocol on {}", address);
+
+    let arguments = Arc::new(arguments);
+    let active_connections = Arc::new(AtomicUsize::new(0));
+    let mut connection_id: u32 = 0;
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        // Like MySQL, the error is sent instead of the handshake and the connection is closed
+        let Some(slot) = ConnectionSlot::acquire(&active_connections, MAX_CONNECTIONS) else {
+            if let Ok(mut connection) = MySQLConnection::new(stream) {
+                let _ = connection.refuse(ER_CON_COUNT_ERROR, "08004", "Too many connections");
+            }
+            continue;
+        };
+
+        connection_id = connection_id.wrapping_add(1);
+        let arguments = Arc::clone(&arguments);
+        std::thread::spawn(move || {
+            let _slot = slot;
+            // IO errors, for example client disconnect, only close the current connection
+            if let Ok(mut connection) = MySQLConnection::new(stream) {
+                let _ = connection.serve(connection_id, &arguments);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Slot of one served connection in the active connections count, released when it's dropped
+/// so the connection is counted until its thread ends even if it panics
+struct ConnectionSlot {
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl ConnectionSlot {
+    /// Returns the slot if there are less than `max_connections` active connections
+    fn acquire(active_connections: &Arc<AtomicUsize>, max_connections: usize) -> Option<Self> {
+        active_connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max_connections).then_some(count + 1)
+            })
+            .ok()?;
+
+        Some(ConnectionSlot {
+            active_connections: Arc::clone(active_connections),
+        })
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.active_connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Result of the MySQL statements that are answered without the GitQL engine
+enum MySQLStatement {
+    Ignored,
+    Rows(GitQLObject),
+    Query(String),
+}
+
+struct MySQLConnection {
+    reader: TcpStream,
+    writer: BufWriter<TcpStream>,
+    sequence_id: u8,
+}
+
+impl MySQLConnection {
+    fn new(stream: TcpStream) -> std::io::Result<Self> {
+        Ok(MySQLConnection {
+            reader: stream.try_clone()?,
+            writer: BufWriter::new(stream),
+            sequence_id: 0,
+        })
+    }
+
+    /// Send the error as the first packet then close the connection without serving it
+    fn refuse(&mut self, code: u16, sql_state: &str, message: &str) -> std::io::Result<()> {
+        self.write_error(code, sql_state, message)?;
+        self.writer.flush()
+    }
+
+    fn serve(&mut self, connection_id: u32, arguments: &Arguments) -> std::io::Result<()> {
+        let scramble = generate_scramble();
+        self.write_packet(&handshake_packet(connection_id, &scramble))?;
+        self.writer.flush()?;
+
+        if !self.authenticate(&scramble, arguments.serve_token.as_deref())? {
+            return self.writer.flush();
+        }
+
+        let mut repos = vec![];
+        for path in &arguments.repos {
+            match gix::discover(path) {
+                Ok(repo) => repos.push(repo),
+                Err(error) => {
+                    let message = format!("Can't open repository `{}`: {}", path, error);
+                    self.write_error(ER_ACCESS_DENIED_ERROR, "28000", &message)?;
+                    return self.writer.flush();
+                }
+            }
+        }
+
+        let mut env = Environment::default();
+        if let Err(error) = crate::prepare_environment(&mut env, arguments) {
+            self.write_error(ER_ACCESS_DENIED_ERROR, "28000", &error)?;
+            return self.writer.flush();
+        }
+
+        self.write_ok()?;
+        self.writer.flush()?;
+
+        loop {
+            let payload = self.read_packet()?;
+            let Some((&command, body)) = payload.split_first() else {
+                return Ok(());
+            };
+
+            match command {
+                COM_QUIT => return Ok(()),
+                COM_PING | COM_INIT_DB => self.write_ok()?,
+                COM_FIELD_LIST => self.write_eof()?,
+                COM_QUERY => {
+                    let query = String::from_utf8_lossy(body);
+                    match execute_mysql_query(&query, &mut env, &repos, arguments) {
+                        Ok(Some(object)) => self.write_result_set(&object, &env.session)?,
+                        Ok(None) => self.write_ok()?,
+                        Err(diagnostic) => {
+                            let message = diagnostic_message(&diagnostic);
+                            self.write_error(ER_PARSE_ERROR, "42000", &message)?
+                        }
+                    }
+                    env.clear_session();
+                }
+                _ => self.write_error(ER_UNKNOWN_COM_ERROR, "08S01", "Unsupported command")?,
+            }
+
+            self.writer.flush()?;
+        }
+    }
+
+    /// Read the handshake response and check the password if the token is set,
+    /// clients that use other authentication plugin are asked to switch to the native password plugin
+    fn authenticate(
+        &mut self,
+        scramble: &[u8; SCRAMBLE_LENGTH],
+        password: Option<&str>,
+    ) -> std::io::Result<bool> {
+        let payload = self.read_packet()?;
+        let Some((mut auth_response, auth_plugin)) = parse_handshake_response(&payload) else {
+            self.write_error(
+                ER_ACCESS_DENIED_ERROR,
+                "28000",
+                "Unsupported client protocol",
+            )?;
+            return Ok(false);
+        };
+
+        if auth_plugin.is_some_and(|plugin| plugin != AUTH_PLUGIN_NAME) {
+            let mut packet = vec![0xfe];
+            packet.extend_from_slice(AUTH_PLUGIN_NAME.as_bytes());
+            packet.push(0);
+            packet.extend_from_slice(scramble);
+            packet.push(0);
+            self.write_packet(&packet)?;
+            self.writer.flush()?;
+            auth_response = self.read_packet()?;
+        }
+
+        if let Some(password) = password {
+            if auth_response != native_password_response(password, scramble) {
+                self.write_error(ER_ACCESS_DENIED_ERROR, "28000", "Access denied")?;
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn write_result_set(
+        &mut self,
+        object: &GitQLObject,
+        options: &SessionOptions,
+    ) -> std::io::Result<()> {
+        let columns = object.columns();
+        let mut packet = vec![];
+        write_length_encoded_integer(&mut packet, columns.len() as u64);
+        self.write_packet(&packet)?;
+
+        for column in &columns {
+            self.write_packet(&column_definition_packet(&column.name, &column.data_type))?;
+        }
+        self.write_eof()?;
+
+        for row in object.groups.iter().flat_map(|group| group.rows.iter()) {
+            let mut packet = vec![];
+            for value in &row.values {
+                match value {
+                    Value::Null => packet.push(0xfb),
+                    Value::Boolean(boolean) => {
+                        write_length_encoded_string(&mut packet, if *boolean { b"1" } else { b"0" })
+                    }
+                    _ => write_length_encoded_string(
+                        &mut packet,
+                        value_to_text(value, options).as_bytes(),
+                    ),
+                }
+            }
+            self.write_packet(&packet)?;
+        }
+        self.write_eof()
+    }
+
+    fn write_ok(&mut self) -> std::io::Result<()> {
+        let mut packet = vec![0x00, 0x00, 0x00];
+        packet.extend_from_slice(&SERVER_STATUS_AUTOCOMMIT.to_le_bytes());
+        packet.extend_from_slice(&0u16.to_le_bytes());
+        self.write_packet(&packet)
+    }
+
+    fn write_eof(&mut self) -> std::io::Result<()> {
+        let mut packet = vec![0xfe];
+        packet.extend_from_slice(&0u16.to_le_bytes());
+        packet.extend_from_slice(&SERVER_STATUS_AUTOCOMMIT.to_le_bytes());
+        self.write_packet(&packet)
+    }
+
+    fn write_error(&mut self, code: u16, sql_state: &str, message: &str) -> std::io::Result<()> {
+        let mut packet = vec![0xff];
+        packet.extend_from_slice(&code.to_le_bytes());
+        packet.push(b'#');
+        packet.extend_from_slice(sql_state.as_bytes());
+        packet.extend_from_slice(message.as_bytes());
+        self.write_packet(&packet)
+    }
+
+    /// Read one payload, the connection is closed if the payload is longer than the command byte
+    /// and the longest query so clients can't make the server buffer many continuation packets
+    fn read_packet(&mut self) -> std::io::Result<Vec<u8>> {
+        let max_length = 1 + ParserOptions::default().max_query_length * 4;
+        match read_payload(&mut self.reader, max_length) {
+            Ok((payload, sequence_id)) => {
+                self.sequence_id = sequence_id.wrapping_add(1);
+                Ok(payload)
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::InvalidData => {
+                self.write_error(ER_NET_PACKET_TOO_LARGE, "08S01", &error.to_string())?;
+                self.writer.flush()?;
+                Err(error)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write the payload split into packets of the maximum length, if the last packet has
+    /// the maximum length an empty packet is sent to mark the end of the payload
+    fn write_packet(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        let mut remaining = payload;
+        loop {
+            let length = remaining.len().min(MAX_PAYLOAD_LENGTH);
+            let header = (length as u32).to_le_bytes();
+            self.writer
+                .write_all(&[header[0], header[1], header[2], self.sequence_id])?;
+            self.writer.write_all(&remaining[..length])?;
+            self.sequence_id = self.sequence_id.wrapping_add(1);
+
+            remaining = &remaining[length..];
+            if length < MAX_PAYLOAD_LENGTH {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Answer the statements that MySQL clients send to inspect the server, and translate the other
+/// queries to GitQL, returns `None` for the statements that don't select rows
+fn execute_mysql_query(
+    query: &str,
+    env: &mut Environment,
+    repos: &[gix::Repository],
+    arguments: &Arguments,
+) -> Result<Option<GitQLObject>, Box<Diagnostic>> {
+    let query = match translate_mysql_query(query, env) {
+        MySQLStatement::Ignored => return Ok(None),
+        MySQLStatement::Rows(object) => return Ok(Some(object)),
+        MySQLStatement::Query(query) => query,
+    };
+
+    // MySQL clients quote strings with single quotes so the ANSI mode is always enabled
+    let parser_options = ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: true,
+        allow_external_files: false,
+        ..Default::default()
+    };
+
+    let tokens = tokenizer::tokenize_with_options(&query, parser_options)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let query_node = parser::parse_gql_with_options(tokens, env, parser_options)?;
+    crate::check_server_query(&query_node)?;
+    match engine::evaluate_with_threads(env, repos, query_node, arguments.threads)? {
+        EvaluationResult::SelectedGroups(mut object, hidden_selection) => {
+            object.remove_hidden_selections(&hidden_selection);
+            Ok(Some(object))
+        }
+        EvaluationResult::SetGlobalVariable
+        | EvaluationResult::CreateTempTable
+        | EvaluationResult::InsertedRows(_) => Ok(None),
+    }
+}
+
+fn translate_mysql_query(query: &str, env: &Environment) -> MySQLStatement {
+    let query = strip_leading_comments(query)
+        .trim()
+        .trim_end_matches(';')
+        .trim_end();
+    let lowercase = query.to_ascii_lowercase();
+    let words: Vec<&str> = lowercase.split_whitespace().collect();
+
+    match words.as_slice() {
+        // MySQL client settings, for example `SET NAMES utf8mb4` or `SET @@sql_mode = ''`, are accepted
+        // and ignored but GitQL global and session variables like `SET @name = 1` or `SET @@timezone = 'UTC'`
+        // are still defined
+        ["set", variable, ..] if !is_gitql_variable(variable) => MySQLStatement::Ignored,
+        ["use", ..] | ["begin"] | ["commit"] | ["rollback"] | ["start", "transaction", ..] => {
+            MySQLStatement::Ignored
+        }
+        ["show", "databases" | "schemas", ..] => {
+            MySQLStatement::Rows(text_rows(&["Database"], vec![vec![DATABASE_NAME]]))
+        }
+        ["show", "tables", ..] | ["show", "full", "tables", ..] => {
+            let title = format!("Tables_in_{}", DATABASE_NAME);
+            let mut tables: Vec<&str> = TABLES_FIELDS_NAMES.keys().copied().collect();
+            tables.sort();
+            let rows = tables.into_iter().map(|table| vec![table]).collect();
+            MySQLStatement::Rows(text_rows(&[title.as_str()], rows))
+        }
+        ["show", "columns" | "fields", "from", table, ..]
+        | ["show", "full", "columns" | "fields", "from", table, ..]
+        | ["describe" | "desc", table, ..] => {
+            let table = translate_identifiers(table);
+            MySQLStatement::Rows(describe_table(env, &table))
+        }
+        ["show", "session" | "global", "variables", ..] | ["show", "variables", ..] => {
+            let pattern = lowercase
+                .split_once(" like ")
+                .map(|(_, pattern)| pattern.trim().trim_matches(|c| c == '\'' || c == '"'));
+            let rows = MYSQL_SYSTEM_VARIABLES
+                .iter()
+                .filter(|(name, _)| pattern.is_none_or(|pattern| like_matches(pattern, name)))
+                .map(|(name, value)| vec![*name, *value])
+                .collect();
+            MySQLStatement::Rows(text_rows(&["Variable_name", "Value"], rows))
+        }
+        ["show", "warnings" | "errors", ..] => {
+            MySQLStatement::Rows(text_rows(&["Level", "Code", "Message"], vec![]))
+        }
+        ["show", "status", ..] | ["show", "session" | "global", "status", ..] => {
+            MySQLStatement::Rows(text_rows(&["Variable_name", "Value"], vec![]))
+        }
+        ["select", ..] => match system_values_row(query) {
+            Some(object) => MySQLStatement::Rows(object),
+            None => MySQLStatement::Query(translate_identifiers(query)),
+        },
+        _ => MySQLStatement::Query(translate_identifiers(query)),
+    }
+}
+
+/// Returns true if the `SET` target, with the rest of the statement if it's not separated by spaces,
+/// is GitQL global variable or session variable, other variables are MySQL client settings
+fn is_gitql_variable(target: &str) -> bool {
+    let name_end = target
+        .find(|c: char| !c.is_alphanumeric() && c != '_' && c != '@')
+        .unwrap_or(target.len());
+    let name = &target[..name_end];
+    match name.strip_prefix("@@") {
+        Some(_) => is_session_variable(name),
+        None => name.starts_with('@'),
+    }
+}
+
+/// Returns one row for queries that select only system variables and functions, for example
+/// `SELECT @@version_comment LIMIT 1` or `SELECT DATABASE()`, or `None` for any other query
+fn system_values_row(query: &str) -> Option<GitQLObject> {
+    let lowercase = query.to_ascii_lowercase();
+    if lowercase.contains(" from ") {
+        return None;
+    }
+
+    let end = lowercase.find(" limit ").unwrap_or(query.len());
+    let items = query.get("select ".len()..end)?;
+
+    let mut titles = vec![];
+    let mut values = vec![];
+    for item in items.split(',') {
+        let item = item.trim();
+        let lowercase_item = item.to_ascii_lowercase();
+        let (expression, alias) = match lowercase_item.find(" as ") {
+            Some(index) => (&item[..index], Some(item[index + " as ".len()..].trim())),
+            None => (item, None),
+        };
+
+        values.push(system_value(&expression.trim().to_ascii_lowercase())?);
+        titles.push(unquote_identifier(alias.unwrap_or(expression.trim())));
+    }
+
+    Some(GitQLObject {
+        titles,
+        groups: vec![Group {
+            rows: vec![Row { values }],
+        }],
+    })
+}
+
+fn system_value(expression: &str) -> Option<Value> {
+    match expression {
+        "database()" | "schema()" => Some(Value::Text(DATABASE_NAME.to_string())),
+        "version()" => Some(Value::Text(SERVER_VERSION.to_string())),
+        "user()" | "current_user()" => Some(Value::Text(DATABASE_NAME.to_string())),
+        _ => {
+            let name = expression.strip_prefix("@@")?;
+            let name = ["session.", "global.", "local."]
+                .iter()
+                .find_map(|scope| name.strip_prefix(scope))
+                .unwrap_or(name);
+
+            let value = MYSQL_SYSTEM_VARIABLES
+                .iter()
+                .find(|(variable, _)| *variable == name)
+                .map_or(Value::Null, |(_, value)| match value.parse::<i64>() {
+                    Ok(integer) => Value::Integer(integer),
+                    Err(_) => Value::Text(value.to_string()),
+                });
+            Some(value)
+        }
+    }
+}
+
+/// Describe the fields of the table in the same columns as MySQL `DESCRIBE`
+fn describe_table(env: &Environment, table_name: &str) -> GitQLObject {
+    let rows = env
+        .table_fields(table_name)
+        .into_iter()
+        .map(|(name, data_type)| Row {
+            values: vec![
+                Value::Text(name),
+                Value::Text(mysql_type_name(&data_type).to_string()),
+                Value::Text("YES".to_string()),
+                Value::Text(String::new()),
+                Value::Null,
+                Value::Text(String::new()),
+            ],
+        })
+        .collect();
+
+    GitQLObject {
+        titles: ["Field", "Type", "Null", "Key", "Default", "Extra"]
+            .iter()
+            .map(|title| title.to_string())
+            .collect(),
+        groups: vec![Group { rows }],
+    }
+}
+
+fn text_rows(titles: &[&str], rows: Vec<Vec<&str>>) -> GitQLObject {
+    let rows = rows
+        .into_iter()
+        .map(|values| Row {
+            values: values
+                .into_iter()
+                .map(|value| Value::Text(value.to_string()))
+                .collect(),
+        })
+        .collect();
+
+    GitQLObject {
+        titles: titles.iter().map(|title| title.to_string()).collect(),
+        groups: vec![Group { rows }],
+    }
+}
+
+fn mysql_type_name(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Integer => "bigint",
+        DataType::Float => "double",
+        DataType::Boolean => "tinyint(1)",
+        DataType::Date => "date",
+        DataType::Time => "time",
+        DataType::DateTime => "datetime",
+        _ => "text",
+    }
+}
+
+fn mysql_column_type(data_type: &DataType) -> u8 {
+    match data_type {
+        DataType::Integer => MYSQL_TYPE_LONGLONG,
+        DataType::Float => MYSQL_TYPE_DOUBLE,
+        DataType::Boolean => MYSQL_TYPE_TINY,
+        DataType::Date => MYSQL_TYPE_DATE,
+        DataType::Time => MYSQL_TYPE_TIME,
+        DataType::DateTime => MYSQL_TYPE_DATETIME,
+        _ => MYSQL_TYPE_VAR_STRING,
+    }
+}
+
+fn column_definition_packet(name: &str, data_type: &DataType) -> Vec<u8> {
+    let column_type = mysql_column_type(data_type);
+    let mut packet = vec![];
+    write_length_encoded_string(&mut packet, b"def");
+    write_length_encoded_string(&mut packet, DATABASE_NAME.as_bytes());
+    write_length_encoded_string(&mut packet, b"");
+    write_length_encoded_string(&mut packet, b"");
+    write_length_encoded_string(&mut packet, name.as_bytes());
+    write_length_encoded_string(&mut packet, name.as_bytes());
+    packet.push(0x0c);
+    packet.extend_from_slice(&(UTF8_GENERAL_CI as u16).to_le_bytes());
+    packet.extend_from_slice(&1024u32.to_le_bytes());
+    packet.push(column_type);
+    packet.extend_from_slice(&0u16.to_le_bytes());
+    packet.push(if column_type == MYSQL_TYPE_DOUBLE {
+        31
+    } else {
+        0
+    });
+    packet.extend_from_slice(&[0, 0]);
+    packet
+}
+
+fn handshake_packet(connection_id: u32, scramble: &[u8; SCRAMBLE_LENGTH]) -> Vec<u8> {
+    let mut packet = vec![10];
+    packet.extend_from_slice(SERVER_VERSION.as_bytes());
+    packet.push(0);
+    packet.extend_from_slice(&connection_id.to_le_bytes());
+    packet.extend_from_slice(&scramble[..8]);
+    packet.push(0);
+    packet.extend_from_slice(&(SERVER_CAPABILITIES as u16).to_le_bytes());
+    packet.push(UTF8_GENERAL_CI);
+    packet.extend_from_slice(&SERVER_STATUS_AUTOCOMMIT.to_le_bytes());
+    packet.extend_from_slice(&((SERVER_CAPABILITIES >> 16) as u16).to_le_bytes());
+    packet.push(SCRAMBLE_LENGTH as u8 + 1);
+    packet.extend_from_slice(&[0; 10]);
+    packet.extend_from_slice(&scramble[8..]);
+    packet.push(0);
+    packet.extend_from_slice(AUTH_PLUGIN_NAME.as_bytes());
+    packet.push(0);
+    packet
+}
+
+/// Returns the authentication response and plugin name of the handshake response,
+/// or `None` if the client doesn't support the 4.1 protocol
+fn parse_handshake_response(payload: &[u8]) -> Option<(Vec<u8>, Option<&str>)> {
+    let capabilities = u32::from_le_bytes(payload.get(..4)?.try_into().ok()?);
+    if capabilities & CLIENT_PROTOCOL_41 == 0 {
+        return None;
+    }
+
+    // Skip max packet size, character set, reserved bytes and the null terminated user name
+    let mut position = 4 + 4 + 1 + 23;
+    position += payload
+        .get(position..)?
+        .iter()
+        .position(|byte| *byte == 0)?
+        + 1;
+
+    // Native password response is 20 bytes so its length encoded integer is always one byte
+    let auth_response_length =
+        if capabilities & (CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA | CLIENT_SECURE_CONNECTION) != 0 {
+            position += 1;
+            *payload.get(position - 1)? as usize
+        } else {
+            payload
+                .get(position..)?
+                .iter()
+                .position(|byte| *byte == 0)?
+        };
+
+    let auth_response = payload
+        .get(position..position + auth_response_length)?
+        .to_vec();
+    position += auth_response_length;
+
+    let mut fields = payload.get(position..)?.split(|byte| *byte == 0);
+    if capabilities & CLIENT_CONNECT_WITH_DB != 0 {
+        fields.next();
+    }
+
+    let auth_plugin = if capabilities & CLIENT_PLUGIN_AUTH != 0 {
+        fields
+            .next()
+            .and_then(|name| std::str::from_utf8(name).ok())
+    } else {
+        None
+    };
+
+    Some((auth_response, auth_plugin))
+}
+
+/// Expected response of `mysql_native_password`, `SHA1(password) XOR SHA1(scramble + SHA1(SHA1(password)))`
+fn native_password_response(password: &str, scramble: &[u8]) -> Vec<u8> {
+    if password.is_empty() {
+        return vec![];
+    }
+
+    let password_hash = Sha1::digest(password.as_bytes());
+    let double_hash = Sha1::digest(password_hash);
+    let mut hasher = Sha1::new();
+    hasher.update(scramble);
+    hasher.update(double_hash);
+    let scramble_hash = hasher.finalize();
+
+    password_hash
+        .iter()
+        .zip(scramble_hash.iter())
+        .map(|(left, right)| left ^ right)
+        .collect()
+}
+
+/// Generate random scramble, the keys of each `RandomState` are random so it's used as random source
+fn generate_scramble() -> [u8; SCRAMBLE_LENGTH] {
+    let state = RandomState::new();
+    let mut scramble = [0; SCRAMBLE_LENGTH];
+    for (index, byte) in scramble.iter_mut().enumerate() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(index);
+        // Keep the scramble printable because some clients read it as null terminated string
+        *byte = (hasher.finish() % 94) as u8 + 33;
+    }
+    scramble
+}
+
+fn write_length_encoded_integer(packet: &mut Vec<u8>, value: u64) {
+    if value < 251 {
+        packet.push(value as u8);
+    } else if value < 1 << 16 {
+        packet.push(0xfc);
+        packet.extend_from_slice(&(value as u16).to_le_bytes());
+    } else if value < 1 << 24 {
+        packet.push(0xfd);
+        packet.extend_from_slice(&(value as u32).to_le_bytes()[..3]);
+    } else {
+        packet.push(0xfe);
+        packet.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn write_length_encoded_string(packet: &mut Vec<u8>, value: &[u8]) {
+    write_length_encoded_integer(packet, value.len() as u64);
+    packet.extend_from_slice(value);
+}
+
+fn diagnostic_message(diagnostic: &Diagnostic) -> String {
+    let mut message = diagnostic.message().to_string();
+    for help in diagnostic.helps() {
+        message.push_str(&format!(", {}", help));
+    }
+    message
+}
+
+/// Remove the comments before the statement, for example the `/* mysql-connector-java */` prefix
+fn strip_leading_comments(query: &str) -> &str {
+    let mut query = query.trim_start();
+    while let Some(rest) = query.strip_prefix("/*") {
+        match rest.find("*/") {
+            Some(end) => query = rest[end + 2..].trim_start(),
+            None => break,
+        }
+    }
+    query
+}
+
+/// Read the packets of one payload, payloads larger than the maximum packet length are sent
+/// in many packets, returns the payload with the sequence id of the last packet
+fn read_payload(reader: &mut impl Read, max_length: usize) -> std::io::Result<(Vec<u8>, u8)> {
+    let mut payload = vec![];
+    loop {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let length = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+
+        let start = payload.len();
+        if start + length > max_length {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Packet is larger than the maximum of {} bytes", max_length),
+            ));
+        }
+
+        payload.resize(start + length, 0);
+        reader.read_exact(&mut payload[start..])?;
+        if length < MAX_PAYLOAD_LENGTH {
+            return Ok((payload, header[3]));
+        }
+    }
+}
+
+/// Rewrite the MySQL identifiers using the tokens so strings literals are kept as they are,
+/// backticks are removed from names that are not keywords and the database name is removed
+/// from qualified tables names, for example `` `gitql`.`commits` `` to `commits`
+fn translate_identifiers(query: &str) -> String {
+    let options = ParserOptions {
+        ansi_mode: true,
+        max_query_length: usize::MAX,
+        ..Default::default()
+    };
+
+    let (tokens, _) = tokenizer::tokenize_with_recovery(query, options);
+    let chars: Vec<char> = query.chars().collect();
+    let mut result = String::with_capacity(query.len());
+    let mut position = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Symbol || token.location.start < position {
+            continue;
+        }
+
+        let name = token.identifier();
+        let next = tokens.get(index + 1);
+        if name.eq_ignore_ascii_case(DATABASE_NAME)
+            && next.is_some_and(|next| next.kind == TokenKind::Dot)
+        {
+            result.extend(&chars[position..token.location.start]);
+            position = next.unwrap().location.start + 1;
+        } else if token.literal.starts_with('`') && is_plain_identifier(&name) {
+            result.extend(&chars[position..token.location.start]);
+            result.push_str(&name);
+            position = token.location.end;
+        }
+    }
+    result.extend(&chars[position..]);
+    result
+}
+
+/// Returns true if the name is still one symbol without the backticks
+fn is_plain_identifier(name: &str) -> bool {
+    match tokenizer::tokenize(name) {
+        Ok(tokens) => tokens.len() == 1 && tokens[0].kind == TokenKind::Symbol,
+        Err(_) => false,
+    }
+}
+
+fn unquote_identifier(name: &str) -> String {
+    name.trim_matches(|c| c == '`' || c == '\'' || c == '"')
+        .to_string()
+}
+
+/// Match the name with MySQL `LIKE` pattern where `%` matches any text and `_` matches one character
+fn like_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let mut matches = vec![vec![false; name.len() + 1]; pattern.len() + 1];
+    matches[0][0] = true;
+    for i in 1..=pattern.len() {
+        matches[i][0] = matches[i - 1][0] && pattern[i - 1] == '%';
+        for j in 1..=name.len() {
+            matches[i][j] = match pattern[i - 1] {
+                '%' => matches[i - 1][j] || matches[i][j - 1],
+                '_' => matches[i - 1][j - 1],
+                c => matches[i - 1][j - 1] && c == name[j - 1],
+            };
+        }
+    }
+    matches[pattern.len()][name.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gitql_cli::arguments::parse_arguments;
+    use gitql_cli::arguments::Command;
+
+    fn packet(payload: &[u8], sequence_id: u8) -> Vec<u8> {
+        let header = (payload.len() as u32).to_le_bytes();
+        let mut packet = vec![header[0], header[1], header[2], sequence_id];
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    #[test]
+    fn test_read_payload() {
+        let mut bytes = packet(b"\x03SELECT 1", 0);
+        bytes.extend(packet(b"\x01", 0));
+        let mut reader = std::io::Cursor::new(bytes);
+
+        let (payload, sequence_id) = read_payload(&mut reader, 1024).unwrap();
+        assert_eq!(payload, b"\x03SELECT 1");
+        assert_eq!(sequence_id, 0);
+
+        let (payload, _) = read_payload(&mut reader, 1024).unwrap();
+        assert_eq!(payload, b"\x01");
+    }
+
+    #[test]
+    fn test_read_payload_continuation_packets() {
+        let first = vec![b'a'; MAX_PAYLOAD_LENGTH];
+        let mut bytes = packet(&first, 0);
+        bytes.extend(packet(b"bc", 1));
+        let mut reader = std::io::Cursor::new(bytes);
+
+        let (payload, sequence_id) = read_payload(&mut reader, usize::MAX).unwrap();
+        assert_eq!(payload.len(), MAX_PAYLOAD_LENGTH + 2);
+        assert!(payload.ends_with(b"abc"));
+        assert_eq!(sequence_id, 1);
+    }
+
+    #[test]
+    fn test_read_payload_too_large() {
+        let mut reader = std::io::Cursor::new(packet(&[0; 32], 0));
+        let error = read_payload(&mut reader, 16).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        // Only the header of the continuation packet is read before the payload is rejected
+        let mut bytes = packet(&vec![0; MAX_PAYLOAD_LENGTH], 0);
+        bytes.extend([0xff, 0xff, 0xff, 1]);
+        let mut reader = std::io::Cursor::new(bytes);
+        let error = read_payload(&mut reader, MAX_PAYLOAD_LENGTH + 1)
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_translate_identifiers() {
+        assert_eq!(
+            translate_identifiers("SELECT `name` FROM `gitql`.`commits`"),
+            "SELECT name FROM commits"
+        );
+        assert_eq!(
+            translate_identifiers("SELECT gitql.commits.title FROM GITQL.commits"),
+            "SELECT commits.title FROM commits"
+        );
+        assert_eq!(
+            translate_identifiers("SELECT `order` FROM mygitql.commits"),
+            "SELECT `order` FROM mygitql.commits"
+        );
+    }
+
+    #[test]
+    fn test_translate_identifiers_keep_strings() {
+        assert_eq!(
+            translate_identifiers("SELECT * FROM commits WHERE title = 'fix `gitql.commits`'"),
+            "SELECT * FROM commits WHERE title = 'fix `gitql.commits`'"
+        );
+    }
+
+    #[test]
+    fn test_translate_mysql_query() {
+        let env = Environment::default();
+        match translate_mysql_query("/* client */ SELECT `title` FROM gitql.commits;", &env) {
+            MySQLStatement::Query(query) => assert_eq!(query, "SELECT title FROM commits"),
+            _ => panic!("Expect the query to be translated"),
+        }
+
+        assert!(matches!(
+            translate_mysql_query("SET NAMES utf8mb4", &env),
+            MySQLStatement::Ignored
+        ));
+    }
+
+    #[test]
+    fn test_translate_mysql_set_variables() {
+        let env = Environment::default();
+        for query in [
+            "SET @@sql_mode = ''",
+            "SET @@session.autocommit=1",
+            "SET autocommit = 1",
+            "SET SESSION transaction_isolation = 'READ-COMMITTED'",
+            "SET time_zone = '+00:00'",
+        ] {
+            assert!(matches!(
+                translate_mysql_query(query, &env),
+                MySQLStatement::Ignored
+            ));
+        }
+
+        for query in [
+            "SET @@timezone = '+02:00'",
+            "SET @@timezone='+02:00'",
+            "SET @limit = 1",
+        ] {
+            match translate_mysql_query(query, &env) {
+                MySQLStatement::Query(translated) => assert_eq!(translated, query),
+                _ => panic!("Expect `{}` to be forwarded", query),
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_mysql_set_session_variable() {
+        let mut env = Environment::default();
+        let args = vec!["gitql".to_string(), "mysql".to_string()];
+        let Command::MySQLMode(arguments) = parse_arguments(&args) else {
+            panic!("Expect MySQL mode arguments");
+        };
+
+        let result = execute_mysql_query("SET @@timezone = '+02:00'", &mut env, &[], &arguments);
+        assert!(matches!(result, Ok(None)));
+        assert!(env.globals["@@timezone"].equals(&Value::Text("+02:00".to_string())));
+
+        // Invalid GitQL session variable value is reported to the client
+        let result = execute_mysql_query("SET @@timezone = 'Mars'", &mut env, &[], &arguments);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_connection_slot() {
+        let active_connections = Arc::new(AtomicUsize::new(0));
+        let first = ConnectionSlot::acquire(&active_connections, 2);
+        let second = ConnectionSlot::acquire(&active_connections, 2);
+        assert!(first.is_some() && second.is_some());
+        assert!(ConnectionSlot::acquire(&active_connections, 2).is_none());
+
+        // Closed connection releases its slot for the next one
+        drop(first);
+        assert_eq!(active_connections.load(Ordering::SeqCst), 1);
+        assert!(ConnectionSlot::acquire(&active_connections, 2).is_some());
+    }
+}
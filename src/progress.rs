@@ -0,0 +1,57 @@
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crossterm::cursor;
+use crossterm::queue;
+use crossterm::terminal;
+use gitql_engine::engine_progress::ProgressReporter;
+use gitql_engine::engine_progress::ScanProgress;
+
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+/// Render the scan progress as one line on stderr, the line is cleared when the query is evaluated
+/// so it never mix with the printed result
+#[derive(Default)]
+pub struct TerminalProgressReporter {
+    is_drawn: AtomicBool,
+    frame: AtomicUsize,
+}
+
+impl ProgressReporter for TerminalProgressReporter {
+    fn report_progress(&self, progress: &ScanProgress) {
+        let frame = self.frame.fetch_add(1, Ordering::Relaxed) % SPINNER_FRAMES.len();
+        let mut stderr = std::io::stderr().lock();
+        let _ = queue!(
+            stderr,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        );
+        let _ = write!(
+            stderr,
+            "{} Collecting {}: {} rows from `{}` on {}",
+            SPINNER_FRAMES[frame],
+            progress.table_name,
+            progress.rows_count,
+            progress.repo_path,
+            progress.branch
+        );
+        let _ = stderr.flush();
+        self.is_drawn.store(true, Ordering::Relaxed);
+    }
+
+    fn finish(&self) {
+        if !self.is_drawn.swap(false, Ordering::Relaxed) {
+            return;
+        }
+
+        let mut stderr = std::io::stderr().lock();
+        let _ = queue!(
+            stderr,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        );
+        let _ = stderr.flush();
+    }
+}
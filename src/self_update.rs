@@ -0,0 +1,239 @@
+use std::io::Read;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+const LATEST_RELEASE_API: &str = "https://api.github.com/repos/AmrDeveloper/GQL/releases/latest";
+const RELEASE_DOWNLOAD_URL: &str = "https://github.com/AmrDeveloper/GQL/releases/download";
+const USER_AGENT: &str = concat!("gitql/", env!("CARGO_PKG_VERSION"));
+
+/// Download the latest release binary for the current platform, verify it with the release checksum
+/// then replace the current executable with it
+pub fn self_update() -> Result<(), String> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let latest_version = fetch_latest_version()?;
+    if !is_newer_version(&latest_version, current_version)? {
+        println!("GitQL is already up to date, version {}", current_version);
+        return Ok(());
+    }
+
+    let asset_name = release_asset_name()?;
+    let asset_url = format!("{}/{}/{}", RELEASE_DOWNLOAD_URL, latest_version, asset_name);
+
+    println!("Downloading GitQL version {}", latest_version);
+    let asset = download(&asset_url)?;
+    let checksum = String::from_utf8(download(&format!("{}.sha256", asset_url))?)
+        .map_err(|_| "Invalid release checksum file".to_string())?;
+
+    verify_checksum(&asset, &checksum, asset_name)?;
+
+    let binary = if asset_name.ends_with(".gz") {
+        let mut binary = vec![];
+        flate2::read::GzDecoder::new(asset.as_slice())
+            .read_to_end(&mut binary)
+            .map_err(|error| format!("Failed to decompress release binary: {}", error))?;
+        binary
+    } else {
+        asset
+    };
+
+    replace_current_executable(&binary)?;
+    println!(
+        "GitQL is updated from version {} to {}",
+        current_version, latest_version
+    );
+    Ok(())
+}
+
+/// Compare the SHA-256 digest of the downloaded asset with the published checksum
+fn verify_checksum(asset: &[u8], checksum: &str, asset_name: &str) -> Result<(), String> {
+    let actual_checksum = format!("{:x}", Sha256::digest(asset));
+    if actual_checksum != checksum.trim() {
+        return Err(format!(
+            "Checksum mismatch for `{}`, expected {} but got {}",
+            asset_name,
+            checksum.trim(),
+            actual_checksum
+        ));
+    }
+    Ok(())
+}
+
+/// Semantic version `major.minor.patch` with the optional pre-release identifiers
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    core: (u64, u64, u64),
+    // Derived ordering compares `false` first, so the release is newer than its pre-releases
+    is_release: bool,
+    pre_release: Vec<PreReleaseIdentifier>,
+}
+
+/// Numeric identifiers have lower precedence than the alphanumeric ones as described in SemVer
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreReleaseIdentifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+/// Parse the version with an optional `v` prefix, build metadata after `+` is ignored
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split('+').next()?;
+    let (core, pre_release) = match version.split_once('-') {
+        Some((core, pre_release)) => (core, Some(pre_release)),
+        None => (version, None),
+    };
+
+    let mut numbers = core.split('.').map(|number| number.parse::<u64>().ok());
+    let core = (numbers.next()??, numbers.next()??, numbers.next()??);
+    if numbers.next().is_some() {
+        return None;
+    }
+
+    let pre_release = match pre_release {
+        Some(pre_release) => pre_release
+            .split('.')
+            .map(|identifier| match identifier.parse::<u64>() {
+                Ok(number) => Some(PreReleaseIdentifier::Numeric(number)),
+                Err(_) if !identifier.is_empty() => {
+                    Some(PreReleaseIdentifier::Alphanumeric(identifier.to_string()))
+                }
+                Err(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?,
+        None => vec![],
+    };
+
+    Some(Version {
+        core,
+        is_release: pre_release.is_empty(),
+        pre_release,
+    })
+}
+
+/// Returns true only if the latest version is strictly newer than the current one,
+/// so the update never replaces the binary with an older release
+fn is_newer_version(latest_version: &str, current_version: &str) -> Result<bool, String> {
+    let latest = parse_version(latest_version)
+        .ok_or_else(|| format!("Invalid latest release version `{}`", latest_version))?;
+    let current = parse_version(current_version)
+        .ok_or_else(|| format!("Invalid current version `{}`", current_version))?;
+    Ok(latest > current)
+}
+
+fn fetch_latest_version() -> Result<String, String> {
+    let response = download(LATEST_RELEASE_API)?;
+    let release: serde_json::Value = serde_json::from_slice(&response)
+        .map_err(|error| format!("Invalid latest release response: {}", error))?;
+
+    match release["tag_name"].as_str() {
+        Some(tag_name) => Ok(tag_name.to_string()),
+        None => Err("Latest release has no version tag".to_string()),
+    }
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|error| format!("Failed to download `{}`: {}", url, error))?;
+
+    let mut bytes = vec![];
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(|error| format!("Failed to download `{}`: {}", url, error))?;
+    Ok(bytes)
+}
+
+/// Release assets names as published by the release workflow
+fn release_asset_name() -> Result<&'static str, String> {
+    if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Ok("gql-x86_64-linux.gz")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Ok("gql-aarch64-linux.gz")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Ok("gql-x86_64-macos.gz")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Ok("gql-aarch64-macos.gz")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        Ok("gql-x86_64-windows.exe")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86")) {
+        Ok("gql-x86-windows.exe")
+    } else {
+        Err("No release binary available for the current platform".to_string())
+    }
+}
+
+/// Write the new binary next to the current executable then swap them,
+/// the running executable is renamed first because Windows can't overwrite it
+fn replace_current_executable(binary: &[u8]) -> Result<(), String> {
+    let current_exe = std::env::current_exe()
+        .map_err(|error| format!("Failed to locate current executable: {}", error))?;
+    let new_exe = current_exe.with_extension("new");
+    let old_exe = current_exe.with_extension("old");
+
+    std::fs::write(&new_exe, binary)
+        .map_err(|error| format!("Failed to write new executable: {}", error))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new_exe, std::fs::Permissions::from_mode(0o755))
+            .map_err(|error| format!("Failed to set executable permissions: {}", error))?;
+    }
+
+    std::fs::rename(&current_exe, &old_exe)
+        .map_err(|error| format!("Failed to replace current executable: {}", error))?;
+
+    if let Err(error) = std::fs::rename(&new_exe, &current_exe) {
+        let _ = std::fs::rename(&old_exe, &current_exe);
+        return Err(format!("Failed to replace current executable: {}", error));
+    }
+
+    let _ = std::fs::remove_file(&old_exe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_checksum() {
+        let asset = b"gitql";
+        let checksum = format!("{:x}\n", Sha256::digest(asset));
+        assert!(verify_checksum(asset, &checksum, "gql-x86_64-linux.gz").is_ok());
+    }
+
+    #[test]
+    fn test_verify_checksum_mismatch() {
+        let checksum = format!("{:x}", Sha256::digest(b"gitql"));
+        let result = verify_checksum(b"other", &checksum, "gql-x86_64-linux.gz");
+        assert!(result.is_err());
+        assert!(result.err().unwrap().starts_with("Checksum mismatch"));
+
+        assert!(verify_checksum(b"gitql", "", "gql-x86_64-linux.gz").is_err());
+    }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("0.39.0", "0.38.0").unwrap());
+        assert!(is_newer_version("v0.38.1", "0.38.0").unwrap());
+        assert!(is_newer_version("1.0.0", "1.0.0-rc.1").unwrap());
+        assert!(is_newer_version("1.0.0-rc.2", "1.0.0-rc.1").unwrap());
+        assert!(is_newer_version("0.10.0", "0.9.0").unwrap());
+
+        assert!(!is_newer_version("0.38.0", "0.38.0").unwrap());
+        assert!(!is_newer_version("1.0.0-rc.1", "1.0.0").unwrap());
+        assert!(is_newer_version("latest", "0.38.0").is_err());
+    }
+
+    #[test]
+    fn test_is_newer_version_when_current_is_newer() {
+        assert!(!is_newer_version("0.38.0", "0.39.0").unwrap());
+        assert!(!is_newer_version("0.9.9", "0.10.0").unwrap());
+        assert!(!is_newer_version("v1.2.3", "1.2.4").unwrap());
+    }
+}
@@ -0,0 +1,563 @@
+use std::io::Read;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::object::value_to_json;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::statement::OrderByStatement;
+use gitql_ast::statement::Query;
+use gitql_cli::arguments::Arguments;
+use gitql_engine::engine::EvaluationResult;
+use gitql_engine::engine_incremental::IncrementalStates;
+use gitql_engine::engine_pagination::decode_cursor;
+use gitql_engine::engine_pagination::encode_cursor;
+use gitql_engine::engine_pagination::OrderKeys;
+use gitql_engine::engine_pagination::ResultsCache;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+use serde_json::json;
+use serde_json::Value;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Request;
+use tiny_http::Response;
+use tiny_http::Server;
+
+const QUERY_ENDPOINT: &str = "/query";
+const MAX_REQUEST_BODY_SIZE: u64 = 1024 * 1024;
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Selected rows of the query with their `ORDER BY` keys, and the session options of its environment
+/// used to format them, the keys are selected only for the paginated queries
+#[derive(Default)]
+struct QueryResult {
+    object: GitQLObject,
+    order_keys: OrderKeys,
+    options: SessionOptions,
+}
+
+/// Results of the paginated queries, so the next pages are served without evaluating the query again,
+/// and the aggregations states of the queries, so evaluating them again walks only the new commits
+#[derive(Default)]
+struct QueriesCache {
+    results: ResultsCache<QueryResult>,
+    incremental_states: IncrementalStates,
+}
+
+/// Serve the queries over HTTP, each `POST /query` request with JSON body like `{"query": "SELECT ..."}`
+/// is evaluated on the repositories and answered with the selected columns and rows as JSON,
+/// requests are handled one by one and each one has its own environment
+pub fn launch_gitql_server(arguments: &Arguments, repos: &[gix::Repository]) -> Result<(), String> {
+    let address = format!("{}:{}", arguments.serve_host, arguments.serve_port);
+    let server = Server::http(&address)
+        .map_err(|error| format!("Failed to listen on `{}`: {}", address, error))?;
+
+    println!(
+        "GitQL is serving queries on http://{}{}",
+        address, QUERY_ENDPOINT
+    );
+
+    let mut cache = QueriesCache::default();
+    for mut request in server.incoming_requests() {
+        let (status_code, body) = handle_request(&mut request, arguments, repos, &mut cache);
+        let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("Content type header must be valid");
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status_code)
+            .with_header(content_type);
+
+        // Client may disconnect before reading the response, that should not stop the server
+        let _ = request.respond(response);
+    }
+
+    Ok(())
+}
+
+/// Returns the status code and the JSON body of the response
+fn handle_request(
+    request: &mut Request,
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    cache: &mut QueriesCache,
+) -> (u16, Value) {
+    if let Some(token) = &arguments.serve_token {
+        if !is_authorized(request, token) {
+            let diagnostic = Diagnostic::error("Missing or invalid authorization token")
+                .add_help("Pass the token using `Authorization: Bearer <TOKEN>` header");
+            return (401, diagnostic_to_json(&diagnostic));
+        }
+    }
+
+    if request.url() != QUERY_ENDPOINT {
+        let message = format!("Unknown endpoint `{}`", request.url());
+        let diagnostic = Diagnostic::error(&message)
+            .add_help(&format!("Send the queries to `POST {}`", QUERY_ENDPOINT));
+        return (404, diagnostic_to_json(&diagnostic));
+    }
+
+    if *request.method() != Method::Post {
+        let diagnostic = Diagnostic::error("Queries must be sent using `POST` method");
+        return (405, diagnostic_to_json(&diagnostic));
+    }
+
+    let mut content = String::new();
+    let read_result = request
+        .as_reader()
+        .take(MAX_REQUEST_BODY_SIZE)
+        .read_to_string(&mut content);
+    if let Err(error) = read_result {
+        let diagnostic = Diagnostic::error(&format!("Failed to read request body: {}", error));
+        return (400, diagnostic_to_json(&diagnostic));
+    }
+
+    handle_query(&content, arguments, repos, cache)
+}
+
+/// Evaluate the query of the request body, returns the status code and the JSON body of the response
+fn handle_query(
+    content: &str,
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    cache: &mut QueriesCache,
+) -> (u16, Value) {
+    let body: Value = serde_json::from_str(content).unwrap_or(Value::Null);
+    let Some(query) = body["query"].as_str() else {
+        let diagnostic = Diagnostic::error("Request body must be JSON object with `query` field")
+            .add_help("For example `{\"query\": \"SELECT name FROM branches\"}`");
+        return (400, diagnostic_to_json(&diagnostic));
+    };
+
+    let cursor = body["cursor"].as_str();
+    let page_size = body["page_size"]
+        .as_u64()
+        .map(|page_size| usize::try_from(page_size).unwrap_or(usize::MAX));
+    if cursor.is_none() && page_size.is_none() {
+        return match execute_query(
+            query,
+            arguments,
+            repos,
+            &mut cache.incremental_states,
+            false,
+        ) {
+            Ok(result) => (
+                200,
+                object_to_json(&result.object, &result.options, 0, usize::MAX),
+            ),
+            Err(diagnostic) => (400, diagnostic_to_json(&diagnostic)),
+        };
+    }
+
+    let invalid_cursor = || {
+        let diagnostic = Diagnostic::error("Invalid cursor for this query")
+            .add_help("Send the same query with the `next_cursor` of the previous page");
+        (400, diagnostic_to_json(&diagnostic))
+    };
+
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let cursor = match cursor.map(decode_cursor) {
+        Some(Some(cursor)) => Some(cursor),
+        Some(None) => return invalid_cursor(),
+        None => None,
+    };
+
+    // The first page always evaluates the query, the next pages use the cached result if it still exists
+    let result = match cache.results.get(query) {
+        Some(result) if cursor.is_some() => result,
+        _ => match execute_query(query, arguments, repos, &mut cache.incremental_states, true) {
+            Ok(result) => cache.results.insert(query, result),
+            Err(diagnostic) => return (400, diagnostic_to_json(&diagnostic)),
+        },
+    };
+
+    let offset = match &cursor {
+        Some(cursor) => match result.order_keys.cursor_offset(cursor) {
+            Some(offset) => offset,
+            None => return invalid_cursor(),
+        },
+        None => 0,
+    };
+
+    let Some(end) = offset.checked_add(page_size) else {
+        let diagnostic = Diagnostic::error("Page size is too large")
+            .add_help("Try to use smaller `page_size` value");
+        return (400, diagnostic_to_json(&diagnostic));
+    };
+
+    let mut response = object_to_json(&result.object, &result.options, offset, page_size);
+    response["next_cursor"] = if end < result.order_keys.len() {
+        Value::String(encode_cursor(&result.order_keys.next_cursor(end)))
+    } else {
+        Value::Null
+    };
+    (200, response)
+}
+
+/// Compare the bearer token with the expected token in time that doesn't depend on the matching prefix
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let header = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"));
+
+    let Some(bearer) = header.and_then(|header| header.value.as_str().strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    bearer.len() == token.len()
+        && bearer
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+/// Evaluate the query, the `ORDER BY` keys of the rows are selected only if the result is paginated
+fn execute_query(
+    query: &str,
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    incremental_states: &mut IncrementalStates,
+    is_paginated: bool,
+) -> Result<QueryResult, Box<Diagnostic>> {
+    let parser_options = ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: arguments.ansi_mode,
+        allow_external_files: false,
+        ..Default::default()
+    };
+
+    let tokens = tokenizer::tokenize_with_options(query, parser_options)?;
+    if tokens.is_empty() {
+        return Ok(QueryResult::default());
+    }
+
+    let mut env = Environment::default();
+    crate::prepare_environment(&mut env, arguments)
+        .map_err(|error| Diagnostic::error(&error).as_boxed())?;
+
+    // The evaluation consumes the parsed query, so `ORDER BY` is taken from another parse of it
+    let order_by = if is_paginated {
+        let tokens = tokenizer::tokenize_with_options(query, parser_options)?;
+        match parser::parse_gql_with_options(tokens, &mut env.clone(), parser_options)? {
+            Query::Select(mut query) => query.statements.remove("order"),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    let query_node = parser::parse_gql_with_options(tokens, &mut env, parser_options)?;
+    crate::check_server_query(&query_node)?;
+    let evaluation_result =
+        incremental_states.evaluate(&mut env, repos, query, query_node, arguments.threads)?;
+    let object = match evaluation_result {
+        EvaluationResult::SelectedGroups(mut object, hidden_selection) => {
+            object.remove_hidden_selections(&hidden_selection);
+            object
+        }
+        EvaluationResult::SetGlobalVariable
+        | EvaluationResult::CreateTempTable
+        | EvaluationResult::InsertedRows(_) => GitQLObject::default(),
+    };
+
+    let order_keys = if is_paginated {
+        let order_by = order_by
+            .as_ref()
+            .and_then(|statement| statement.as_any().downcast_ref::<OrderByStatement>());
+        OrderKeys::select(&mut env, order_by, &object)?
+    } else {
+        OrderKeys::default()
+    };
+
+    Ok(QueryResult {
+        object,
+        order_keys,
+        options: env.session,
+    })
+}
+
+fn object_to_json(
+    object: &GitQLObject,
+    options: &SessionOptions,
+    offset: usize,
+    limit: usize,
+) -> Value {
+    let rows: Vec<Value> = object
+        .groups
+        .iter()
+        .flat_map(|group| group.rows.iter())
+        .skip(offset)
+        .take(limit)
+        .map(|row| {
+            let values = object
+                .titles
+                .iter()
+                .zip(row.values.iter())
+                .map(|(title, value)| (title.to_string(), value_to_json(value, options)))
+                .collect();
+            Value::Object(values)
+        })
+        .collect();
+
+    json!({ "columns": object.titles, "rows": rows })
+}
+
+fn diagnostic_to_json(diagnostic: &Diagnostic) -> Value {
+    let location = diagnostic
+        .location()
+        .map(|(start, end)| json!({ "start": start, "end": end }));
+
+    json!({
+        "error": {
+            "label": diagnostic.label(),
+            "message": diagnostic.message(),
+            "location": location,
+            "notes": diagnostic.notes(),
+            "helps": diagnostic.helps(),
+            "docs": diagnostic.docs(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use gitql_cli::arguments::parse_arguments;
+    use gitql_cli::arguments::Command;
+    use tiny_http::TestRequest;
+
+    use super::*;
+
+    fn serve_arguments(token: Option<&str>) -> Arguments {
+        let mut args = vec!["gitql".to_string(), "serve".to_string()];
+        if let Some(token) = token {
+            args.push("--token".to_string());
+            args.push(token.to_string());
+        }
+
+        match parse_arguments(&args) {
+            Command::ServeMode(arguments) => arguments,
+            _ => panic!("Expect serve mode arguments"),
+        }
+    }
+
+    fn query_request(body: &'static str) -> TestRequest {
+        TestRequest::new()
+            .with_method(Method::Post)
+            .with_path(QUERY_ENDPOINT)
+            .with_body(body)
+    }
+
+    fn authorization(value: &str) -> Header {
+        Header::from_bytes(&b"Authorization"[..], value.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_handle_query_request() {
+        let arguments = serve_arguments(None);
+        let mut request = query_request(r#"{"query": "SELECT 1 AS one"}"#).into();
+        let (status_code, body) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 200);
+        assert_eq!(body["columns"], json!(["one"]));
+        assert_eq!(body["rows"], json!([{ "one": 1 }]));
+    }
+
+    #[test]
+    fn test_handle_invalid_request() {
+        let arguments = serve_arguments(None);
+
+        let mut request = query_request(r#"{"sql": "SELECT 1"}"#).into();
+        let (status_code, body) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 400);
+        assert!(body["error"]["message"].is_string());
+
+        let mut request = query_request("SELECT 1").into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 400);
+
+        let mut request = query_request(r#"{"query": "SELECT FROM"}"#).into();
+        let (status_code, body) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 400);
+        assert!(body["error"]["location"].is_object());
+
+        let mut request = TestRequest::new().with_path(QUERY_ENDPOINT).into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 405);
+
+        let mut request = TestRequest::new()
+            .with_method(Method::Post)
+            .with_path("/queries")
+            .into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 404);
+    }
+
+    #[test]
+    fn test_handle_paginated_request() {
+        let arguments = serve_arguments(None);
+        let mut cache = QueriesCache::default();
+        let query = "SELECT n FROM (VALUES (1), (2), (3), (4), (5)) AS numbers(n)";
+
+        let body = format!(r#"{{"query": "{}", "page_size": 2}}"#, query);
+        let (status_code, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 200);
+        assert_eq!(body["rows"], json!([{ "n": 1 }, { "n": 2 }]));
+
+        let cursor = body["next_cursor"].as_str().unwrap();
+        let body = format!(
+            r#"{{"query": "{}", "cursor": "{}", "page_size": 2}}"#,
+            query, cursor
+        );
+        let (_, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(body["rows"], json!([{ "n": 3 }, { "n": 4 }]));
+
+        let cursor = body["next_cursor"].as_str().unwrap();
+        let body = format!(
+            r#"{{"query": "{}", "cursor": "{}", "page_size": 2}}"#,
+            query, cursor
+        );
+        let (_, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(body["rows"], json!([{ "n": 5 }]));
+        assert!(body["next_cursor"].is_null());
+    }
+
+    #[test]
+    fn test_handle_paginated_request_with_order_by() {
+        let arguments = serve_arguments(None);
+        let mut cache = QueriesCache::default();
+        let query = "SELECT n FROM (VALUES (3), (1), (2), (2), (4)) AS numbers(n) ORDER BY n DESC";
+
+        let body = format!(r#"{{"query": "{}", "page_size": 3}}"#, query);
+        let (_, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(body["rows"], json!([{ "n": 4 }, { "n": 3 }, { "n": 2 }]));
+
+        // Cursor has the keys of the last row, it's used after the result is removed from the cache
+        let cursor = body["next_cursor"].as_str().unwrap().to_string();
+        let body = format!(
+            r#"{{"query": "{}", "cursor": "{}", "page_size": 3}}"#,
+            query, cursor
+        );
+        let mut cache = QueriesCache::default();
+        let (status_code, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 200);
+        assert_eq!(body["rows"], json!([{ "n": 2 }, { "n": 1 }]));
+        assert!(body["next_cursor"].is_null());
+
+        // Ordering by unselected fields can't be paginated
+        let query = "SELECT n FROM (VALUES (1, 2)) AS numbers(n, m) ORDER BY m";
+        let body = format!(r#"{{"query": "{}", "page_size": 1}}"#, query);
+        let (status_code, _) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 400);
+    }
+
+    #[test]
+    fn test_handle_request_invalid_cursor() {
+        let arguments = serve_arguments(None);
+        let mut cache = QueriesCache::default();
+
+        let cursor = encode_cursor(&(vec![gitql_ast::value::Value::Integer(1)], 1));
+        let body = format!(r#"{{"query": "SELECT 1", "cursor": "{}"}}"#, cursor);
+        let (status_code, _) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 400);
+
+        let body = r#"{"query": "SELECT 1", "cursor": "page-2"}"#;
+        let (status_code, _) = handle_query(body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 400);
+
+        let cursor = encode_cursor(&(vec![], 1));
+        let body = format!(
+            r#"{{"query": "SELECT 1", "cursor": "{}", "page_size": {}}}"#,
+            cursor,
+            u64::MAX
+        );
+        let (status_code, body) = handle_query(&body, &arguments, &[], &mut cache);
+        assert_eq!(status_code, 400);
+        assert_eq!(body["error"]["message"], "Page size is too large");
+    }
+
+    /// Create bare repository with two commits of the same author
+    fn init_commits_repo(path: &std::path::Path) -> gix::Repository {
+        let mut repo = gix::init_bare(path).expect("failed to init bare");
+        let mut config = repo.config_snapshot_mut();
+        for section in ["author", "committer"] {
+            config
+                .set_raw_value(section, None, "name", "name")
+                .expect("failed to set name");
+            config
+                .set_raw_value(section, None, "email", "name@example.com")
+                .expect("failed to set email");
+        }
+        let repo = config
+            .commit_auto_rollback()
+            .expect("failed to commit auto rollback");
+
+        let tree = repo
+            .write_object(gix::objs::Tree::empty())
+            .expect("failed to write object")
+            .detach();
+        let first = repo
+            .commit("HEAD", "initial commit", tree, gix::commit::NO_PARENT_IDS)
+            .expect("failed to commit")
+            .detach();
+        repo.commit("HEAD", "next commit", tree, [first])
+            .expect("failed to commit");
+        gix::open(path).expect("failed to open repo")
+    }
+
+    #[test]
+    fn test_handle_rollup_request() {
+        let path = std::env::temp_dir().join(format!("gitql-serve-rollup-{}", std::process::id()));
+        let repos = [init_commits_repo(&path)];
+        let arguments = serve_arguments(None);
+        let mut cache = QueriesCache::default();
+
+        // Subtotal and grand total rows are returned like the CLI, also when the query is repeated
+        let body = r#"{"query": "SELECT name, email, COUNT(name) FROM commits GROUP BY ROLLUP(name, email)"}"#;
+        for _ in 0..2 {
+            let (status_code, body) = handle_query(body, &arguments, &repos, &mut cache);
+            assert_eq!(status_code, 200);
+            assert_eq!(body["rows"].as_array().unwrap().len(), 3);
+        }
+
+        std::fs::remove_dir_all(path).expect("failed to remove dir");
+    }
+
+    #[test]
+    fn test_handle_request_authorization() {
+        let arguments = serve_arguments(Some("secret"));
+
+        let mut request = query_request(r#"{"query": "SELECT 1"}"#).into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 401);
+
+        let mut request = query_request(r#"{"query": "SELECT 1"}"#)
+            .with_header(authorization("Bearer secrets"))
+            .into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 401);
+
+        let mut request = query_request(r#"{"query": "SELECT 1"}"#)
+            .with_header(authorization("secret"))
+            .into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 401);
+
+        let mut request = query_request(r#"{"query": "SELECT 1"}"#)
+            .with_header(authorization("Bearer secret"))
+            .into();
+        let (status_code, _) =
+            handle_request(&mut request, &arguments, &[], &mut QueriesCache::default());
+        assert_eq!(status_code, 200);
+    }
+}
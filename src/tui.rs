@@ -0,0 +1,459 @@
+use std::io::Write;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style;
+use crossterm::style::Color;
+use crossterm::terminal;
+use gitql_ast::environment::Environment;
+use gitql_ast::environment::TABLES_FIELDS_NAMES;
+use gitql_ast::types::TABLES_FIELDS_TYPES;
+use gitql_cli::arguments::Arguments;
+use gitql_cli::render;
+use gitql_engine::engine;
+use gitql_engine::engine::EvaluationResult;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+
+const QUERY_PROMPT: &str = "gql > ";
+const HELP_LINE: &str = "Enter: Run  Tab: Switch pane  Up/Down/PgUp/PgDn: Scroll  Esc: Quit";
+
+#[derive(PartialEq)]
+enum Focus {
+    Editor,
+    Schema,
+    Results,
+}
+
+struct TuiState {
+    query: Vec<char>,
+    cursor: usize,
+    focus: Focus,
+    schema_lines: Vec<String>,
+    schema_scroll: usize,
+    result_lines: Vec<String>,
+    result_scroll: usize,
+    diagnostic: Option<Box<Diagnostic>>,
+    status: String,
+}
+
+/// Restore the terminal when the UI is closed, even if it's closed by error
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Interactive terminal UI with a schema pane, a query editor that show diagnostics while typing
+/// and a scrollable results pane
+pub fn launch_gitql_tui(
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    env: &mut Environment,
+) -> Result<(), String> {
+    terminal::enable_raw_mode().map_err(|error| error.to_string())?;
+    let _guard = TerminalGuard;
+    crossterm::execute!(std::io::stdout(), terminal::EnterAlternateScreen)
+        .map_err(|error| error.to_string())?;
+
+    let mut state = TuiState {
+        query: vec![],
+        cursor: 0,
+        focus: Focus::Editor,
+        schema_lines: schema_lines(env),
+        schema_scroll: 0,
+        result_lines: vec![],
+        result_scroll: 0,
+        diagnostic: None,
+        status: String::new(),
+    };
+
+    loop {
+        draw(&state).map_err(|error| error.to_string())?;
+
+        let key = match event::read().map_err(|error| error.to_string())? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if is_quit_key(&key) {
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Tab => {
+                state.focus = match state.focus {
+                    Focus::Editor => Focus::Schema,
+                    Focus::Schema => Focus::Results,
+                    Focus::Results => Focus::Editor,
+                };
+            }
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => {
+                let (_, height) = terminal::size().map_err(|error| error.to_string())?;
+                let page = usize::from(height).saturating_sub(5).max(1);
+                let step = match key.code {
+                    KeyCode::Up => -1,
+                    KeyCode::Down => 1,
+                    KeyCode::PageUp => -(page as i64),
+                    _ => page as i64,
+                };
+
+                if state.focus == Focus::Schema {
+                    state.schema_scroll =
+                        scroll(state.schema_scroll, step, state.schema_lines.len());
+                } else {
+                    state.result_scroll =
+                        scroll(state.result_scroll, step, state.result_lines.len());
+                }
+            }
+            KeyCode::Enter => {
+                let query: String = state.query.iter().collect();
+                if !query.trim().is_empty() {
+                    let (width, _) = terminal::size().map_err(|error| error.to_string())?;
+                    let results_width = width.saturating_sub(schema_width(width) + 1);
+                    run_query(&mut state, query, arguments, repos, env, results_width);
+                }
+            }
+            _ if state.focus == Focus::Editor && edit_query(&mut state, &key) => {
+                let query: String = state.query.iter().collect();
+                state.diagnostic = analyze_query(&query, arguments, env);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_quit_key(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Esc
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// Apply the editing key on the query, returns true if the query is changed
+fn edit_query(state: &mut TuiState, key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char(char) => {
+            state.query.insert(state.cursor, char);
+            state.cursor += 1;
+            true
+        }
+        KeyCode::Backspace if state.cursor > 0 => {
+            state.cursor -= 1;
+            state.query.remove(state.cursor);
+            true
+        }
+        KeyCode::Delete if state.cursor < state.query.len() => {
+            state.query.remove(state.cursor);
+            true
+        }
+        KeyCode::Left => {
+            state.cursor = state.cursor.saturating_sub(1);
+            false
+        }
+        KeyCode::Right => {
+            state.cursor = (state.cursor + 1).min(state.query.len());
+            false
+        }
+        KeyCode::Home => {
+            state.cursor = 0;
+            false
+        }
+        KeyCode::End => {
+            state.cursor = state.query.len();
+            false
+        }
+        _ => false,
+    }
+}
+
+fn scroll(current: usize, step: i64, lines_count: usize) -> usize {
+    let max_scroll = lines_count.saturating_sub(1) as i64;
+    (current as i64 + step).clamp(0, max_scroll.max(0)) as usize
+}
+
+fn parser_options(arguments: &Arguments) -> ParserOptions {
+    ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: arguments.ansi_mode,
+        ..Default::default()
+    }
+}
+
+/// Check the query without executing it, returns the first diagnostic if exists
+fn analyze_query(query: &str, arguments: &Arguments, env: &Environment) -> Option<Box<Diagnostic>> {
+    if query.trim().is_empty() {
+        return None;
+    }
+
+    let options = parser_options(arguments);
+    let (tokens, mut diagnostics) = tokenizer::tokenize_with_recovery(query, options);
+    if !diagnostics.is_empty() {
+        return Some(diagnostics.remove(0));
+    }
+
+    if tokens.is_empty() {
+        return None;
+    }
+
+    // Parsing define the selected fields types, so it's checked on a copy of the environment
+    let mut analysis_env = env.clone();
+    parser::parse_gql_with_options(tokens, &mut analysis_env, options).err()
+}
+
+fn run_query(
+    state: &mut TuiState,
+    query: String,
+    arguments: &Arguments,
+    repos: &[gix::Repository],
+    env: &mut Environment,
+    width: u16,
+) {
+    let options = parser_options(arguments);
+    let result = tokenizer::tokenize_with_options(&query, options)
+        .and_then(|tokens| {
+            if tokens.is_empty() {
+                return Err(Diagnostic::error("Expect a statement to run").as_boxed());
+            }
+            parser::parse_gql_with_options(tokens, env, options)
+        })
+        .and_then(|query_node| {
+            engine::evaluate_with_threads(env, repos, query_node, arguments.threads)
+        });
+    env.clear_session();
+
+    match result {
+        Ok(EvaluationResult::SelectedGroups(mut groups, hidden_selections)) => {
+            let rows_count = groups.groups.iter().map(|group| group.len()).sum::<usize>();
+            state.result_lines = render::render_objects_as_lines(
+                &mut groups,
+                &hidden_selections,
+                width,
+                &env.session,
+            );
+            state.status = format!("{} rows", rows_count);
+            state.diagnostic = None;
+        }
+        Ok(EvaluationResult::SetGlobalVariable) => {
+            state.result_lines.clear();
+            state.status = "Global variable is set".to_string();
+            state.diagnostic = None;
+            state.schema_lines = schema_lines(env);
+        }
+        Ok(EvaluationResult::CreateTempTable) => {
+            state.result_lines.clear();
+            state.status = "Temporary table is created".to_string();
+            state.diagnostic = None;
+            state.schema_lines = schema_lines(env);
+        }
+        Ok(EvaluationResult::InsertedRows(rows_count)) => {
+            state.result_lines.clear();
+            state.status = format!("{} rows are inserted", rows_count);
+            state.diagnostic = None;
+        }
+        Err(diagnostic) => {
+            state.result_lines.clear();
+            state.status.clear();
+            state.diagnostic = Some(diagnostic);
+        }
+    }
+
+    state.result_scroll = 0;
+}
+
+/// Lines of the schema pane, each table name followed by its fields names and types
+fn schema_lines(env: &Environment) -> Vec<String> {
+    let mut lines = vec![];
+
+    let mut tables: Vec<&&str> = TABLES_FIELDS_NAMES.keys().collect();
+    tables.sort();
+    for table in tables {
+        lines.push(table.to_string());
+        for field in &TABLES_FIELDS_NAMES[*table] {
+            lines.push(format!("  {} {}", field, TABLES_FIELDS_TYPES[field]));
+        }
+    }
+
+    let mut custom_tables: Vec<&String> = env.tables.keys().collect();
+    custom_tables.sort();
+    for table in custom_tables {
+        lines.push(table.to_string());
+        for (field, data_type) in &env.tables[table] {
+            lines.push(format!("  {} {}", field, data_type));
+        }
+    }
+
+    let mut globals: Vec<&String> = env.globals_types.keys().collect();
+    globals.sort();
+    for global in globals {
+        lines.push(format!("{} {}", global, env.globals_types[global]));
+    }
+
+    lines
+}
+
+fn schema_width(width: u16) -> u16 {
+    (width / 4).clamp(16, 32).min(width / 2)
+}
+
+fn draw(state: &TuiState) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    let (width, height) = terminal::size()?;
+    let schema_width = schema_width(width);
+    let main_x = schema_width + 1;
+    let main_width = usize::from(width.saturating_sub(main_x));
+
+    queue!(
+        stdout,
+        cursor::Hide,
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+
+    // Schema pane
+    let title_color = pane_title_color(state, Focus::Schema);
+    draw_text(
+        &mut stdout,
+        0,
+        0,
+        "Schema",
+        usize::from(schema_width),
+        title_color,
+    )?;
+    for row in 1..height {
+        let line = state
+            .schema_lines
+            .get(state.schema_scroll + usize::from(row) - 1)
+            .map_or("", |line| line.as_str());
+        draw_text(
+            &mut stdout,
+            0,
+            row,
+            line,
+            usize::from(schema_width),
+            Color::Reset,
+        )?;
+        draw_text(&mut stdout, schema_width, row, "│", 1, Color::DarkGrey)?;
+    }
+
+    // Query editor and the diagnostic of the current query
+    let title_color = pane_title_color(state, Focus::Editor);
+    draw_text(&mut stdout, main_x, 0, "Query", main_width, title_color)?;
+    let query: String = state.query.iter().collect();
+    let editor_line = format!("{}{}", QUERY_PROMPT, query);
+    draw_text(
+        &mut stdout,
+        main_x,
+        1,
+        &editor_line,
+        main_width,
+        Color::Reset,
+    )?;
+
+    if let Some(diagnostic) = &state.diagnostic {
+        if let Some((start, end)) = diagnostic.location() {
+            let marker = format!(
+                "{}{}",
+                " ".repeat(QUERY_PROMPT.len() + start),
+                "^".repeat(usize::max(1, end.saturating_sub(start)))
+            );
+            draw_text(&mut stdout, main_x, 2, &marker, main_width, Color::Yellow)?;
+        }
+
+        let message = format!("[{}]: {}", diagnostic.label(), diagnostic.message());
+        draw_text(&mut stdout, main_x, 3, &message, main_width, Color::Red)?;
+    } else {
+        draw_text(
+            &mut stdout,
+            main_x,
+            3,
+            &state.status,
+            main_width,
+            Color::Green,
+        )?;
+    }
+
+    // Results pane
+    let title_color = pane_title_color(state, Focus::Results);
+    draw_text(&mut stdout, main_x, 4, "Results", main_width, title_color)?;
+    for row in 5..height.saturating_sub(1) {
+        let line = state
+            .result_lines
+            .get(state.result_scroll + usize::from(row) - 5)
+            .map_or("", |line| line.as_str());
+        draw_text(&mut stdout, main_x, row, line, main_width, Color::Reset)?;
+    }
+
+    draw_text(
+        &mut stdout,
+        main_x,
+        height.saturating_sub(1),
+        HELP_LINE,
+        main_width,
+        Color::DarkGrey,
+    )?;
+
+    // Keep the terminal cursor in the query editor
+    let cursor_column = main_x as usize + QUERY_PROMPT.len() + state.cursor;
+    if state.focus == Focus::Editor && cursor_column < usize::from(width) {
+        queue!(
+            stdout,
+            cursor::MoveTo(cursor_column as u16, 1),
+            cursor::Show
+        )?;
+    }
+
+    stdout.flush()
+}
+
+fn pane_title_color(state: &TuiState, pane: Focus) -> Color {
+    if state.focus == pane {
+        Color::Green
+    } else {
+        Color::Cyan
+    }
+}
+
+/// Draw text at the position, characters after the max width are cut
+fn draw_text(
+    stdout: &mut std::io::Stdout,
+    column: u16,
+    row: u16,
+    text: &str,
+    max_width: usize,
+    color: Color,
+) -> std::io::Result<()> {
+    let text: String = text.chars().take(max_width).collect();
+    queue!(
+        stdout,
+        cursor::MoveTo(column, row),
+        style::SetForegroundColor(color),
+        style::Print(text),
+        style::ResetColor
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll() {
+        assert_eq!(scroll(0, -1, 10), 0);
+        assert_eq!(scroll(2, 3, 10), 5);
+        assert_eq!(scroll(8, 5, 10), 9);
+        assert_eq!(scroll(0, 1, 0), 0);
+    }
+}
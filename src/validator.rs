@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use gitql_ast::environment::Environment;
+use gitql_ast::object::GitQLObject;
+use gitql_ast::object::Group;
+use gitql_ast::object::Row;
+use gitql_ast::session::SessionOptions;
+use gitql_ast::types::DataType;
+use gitql_ast::value::Value;
+use gitql_cli::arguments::Arguments;
+use gitql_cli::render;
+use gitql_parser::context::ParserOptions;
+use gitql_parser::diagnostic::Diagnostic;
+use gitql_parser::parser;
+use gitql_parser::tokenizer;
+use gitql_parser::tokenizer::Token;
+use gitql_parser::tokenizer::TokenKind;
+
+/// Extensions of the saved queries files
+const QUERIES_FILES_EXTENSIONS: [&str; 2] = ["gql", "sql"];
+
+/// Tables fields names and types loaded from schema file in the table order
+type Schema = HashMap<String, Vec<(String, DataType)>>;
+
+/// Parse and type check every saved query file in the path against the current schema, or the schema
+/// file of another version if it's passed, print a summary table and returns true if all queries are valid
+pub fn validate_queries(path: &str, arguments: &Arguments) -> Result<bool, String> {
+    let schema = match &arguments.schema_path {
+        Some(schema_path) => Some(load_schema(schema_path)?),
+        None => None,
+    };
+
+    let files = collect_queries_files(Path::new(path))?;
+    if files.is_empty() {
+        return Err(format!("No `.gql` or `.sql` files found in `{}`", path));
+    }
+
+    let parser_options = ParserOptions {
+        any_value_mode: arguments.any_value_mode,
+        ansi_mode: arguments.ansi_mode,
+        ..Default::default()
+    };
+
+    let mut rows: Vec<Row> = vec![];
+    let mut invalid_files_count = 0;
+    for file in &files {
+        let file_name = file.to_string_lossy().to_string();
+        let (queries_count, error) = match std::fs::read_to_string(file) {
+            Ok(content) => validate_queries_file(&content, schema.as_ref(), parser_options),
+            Err(error) => (0, Some(error.to_string())),
+        };
+
+        if error.is_some() {
+            invalid_files_count += 1;
+        }
+
+        let status = if error.is_some() { "Invalid" } else { "Valid" };
+        rows.push(Row {
+            values: vec![
+                Value::Text(file_name),
+                Value::Integer(queries_count as i64),
+                Value::Text(status.to_string()),
+                Value::Text(error.unwrap_or_default()),
+            ],
+        });
+    }
+
+    let mut summary = GitQLObject {
+        titles: vec![
+            "file".to_string(),
+            "queries".to_string(),
+            "status".to_string(),
+            "error".to_string(),
+        ],
+        groups: vec![Group { rows }],
+    };
+
+    let options = SessionOptions::default();
+    render::render_objects(&mut summary, &[], false, arguments.page_size, &options);
+    println!(
+        "{} files checked, {} valid, {} invalid",
+        files.len(),
+        files.len() - invalid_files_count,
+        invalid_files_count
+    );
+
+    Ok(invalid_files_count == 0)
+}
+
+/// Validate the `;` separated queries in the file content, returns the number of queries
+/// and the first error message with its line and column if exists
+fn validate_queries_file(
+    content: &str,
+    schema: Option<&Schema>,
+    options: ParserOptions,
+) -> (usize, Option<String>) {
+    let tokens = match tokenizer::tokenize_with_options(content, options) {
+        Ok(tokens) => tokens,
+        Err(diagnostic) => return (0, Some(format_diagnostic(content, &diagnostic))),
+    };
+
+    let mut env = Environment::default();
+    if let Some(schema) = schema {
+        for (table_name, fields) in schema {
+            env.override_table(table_name, fields.clone());
+        }
+    }
+
+    let mut queries: Vec<Vec<Token>> = vec![vec![]];
+    for token in tokens {
+        if token.kind == TokenKind::Semicolon {
+            queries.push(vec![]);
+        } else if let Some(query) = queries.last_mut() {
+            query.push(token);
+        }
+    }
+    queries.retain(|query| !query.is_empty());
+
+    let queries_count = queries.len();
+    for query in queries {
+        if let Some(schema) = schema {
+            if let Some(diagnostic) = check_table_in_schema(&query, schema) {
+                return (queries_count, Some(format_diagnostic(content, &diagnostic)));
+            }
+        }
+
+        if let Err(diagnostic) = parser::parse_gql_with_options(query, &mut env, options) {
+            return (queries_count, Some(format_diagnostic(content, &diagnostic)));
+        }
+
+        env.clear_session();
+    }
+
+    (queries_count, None)
+}
+
+/// Builtin tables are always known by the parser, so the tables that don't exist
+/// in the passed schema version must be reported before parsing
+fn check_table_in_schema(tokens: &[Token], schema: &Schema) -> Option<Diagnostic> {
+    let table_token = tokens
+        .windows(2)
+        .find(|pair| pair[0].kind == TokenKind::From && pair[1].kind == TokenKind::Symbol)
+        .map(|pair| &pair[1])?;
+
+    if schema.contains_key(table_token.identifier().as_ref()) {
+        return None;
+    }
+
+    Some(
+        Diagnostic::error(&format!(
+            "Table `{}` doesn't exist in the schema",
+            table_token.literal
+        ))
+        .with_location(table_token.location),
+    )
+}
+
+fn format_diagnostic(content: &str, diagnostic: &Diagnostic) -> String {
+    match diagnostic.location() {
+        Some((start, _)) => {
+            let (line, column) = tokenizer::line_and_column(content, start);
+            format!("{}:{}: {}", line, column, diagnostic.message())
+        }
+        None => diagnostic.message().to_string(),
+    }
+}
+
+/// Collect the saved queries files in the path recursively sorted by path,
+/// or the path itself if it's a file
+fn collect_queries_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let entries = std::fs::read_dir(path)
+        .map_err(|error| format!("Failed to read `{}`: {}", path.display(), error))?;
+
+    let mut files: Vec<PathBuf> = vec![];
+    for entry in entries {
+        let entry_path = entry.map_err(|error| error.to_string())?.path();
+        if entry_path.is_dir() {
+            files.extend(collect_queries_files(&entry_path)?);
+            continue;
+        }
+
+        let is_query_file = entry_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                QUERIES_FILES_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+            });
+
+        if is_query_file {
+            files.push(entry_path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Load schema file that map each table name to its fields in order, for example
+/// `{ "commits": [{ "name": "commit_id", "type": "Text" }] }`
+fn load_schema(path: &str) -> Result<Schema, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read schema `{}`: {}", path, error))?;
+
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|error| format!("Invalid schema `{}`: {}", path, error))?;
+
+    let tables = json
+        .as_object()
+        .ok_or_else(|| format!("Schema `{}` must be an object of tables", path))?;
+
+    let mut schema = Schema::new();
+    for (table_name, fields) in tables {
+        let fields = fields
+            .as_array()
+            .ok_or_else(|| format!("Table `{}` fields must be an array", table_name))?;
+
+        let mut table_fields: Vec<(String, DataType)> = vec![];
+        for field in fields {
+            let name = field["name"].as_str();
+            let data_type = field["type"].as_str().and_then(parse_data_type);
+            match (name, data_type) {
+                (Some(name), Some(data_type)) => {
+                    table_fields.push((name.to_lowercase(), data_type));
+                }
+                _ => {
+                    return Err(format!(
+                        "Table `{}` has field without valid `name` and `type`",
+                        table_name
+                    ))
+                }
+            }
+        }
+
+        schema.insert(table_name.to_lowercase(), table_fields);
+    }
+
+    Ok(schema)
+}
+
+fn parse_data_type(name: &str) -> Option<DataType> {
+    match name.to_lowercase().as_str() {
+        "any" => Some(DataType::Any),
+        "text" => Some(DataType::Text),
+        "integer" => Some(DataType::Integer),
+        "float" => Some(DataType::Float),
+        "boolean" => Some(DataType::Boolean),
+        "date" => Some(DataType::Date),
+        "time" => Some(DataType::Time),
+        "datetime" => Some(DataType::DateTime),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commits_schema() -> Schema {
+        let mut schema = Schema::new();
+        schema.insert(
+            "commits".to_string(),
+            vec![
+                ("commit_id".to_string(), DataType::Text),
+                ("title".to_string(), DataType::Text),
+            ],
+        );
+        schema
+    }
+
+    #[test]
+    fn test_validate_queries_file() {
+        let content = "SELECT title FROM commits;\nSELECT name FROM branches;\n";
+        let result = validate_queries_file(content, None, ParserOptions::default());
+        assert_eq!(result, (2, None));
+
+        let result = validate_queries_file("", None, ParserOptions::default());
+        assert_eq!(result, (0, None));
+    }
+
+    #[test]
+    fn test_validate_queries_file_with_error() {
+        let content = "SELECT title FROM commits;\nSELECT unknown FROM commits;";
+        let (queries_count, error) = validate_queries_file(content, None, ParserOptions::default());
+        assert_eq!(queries_count, 2);
+        assert_eq!(
+            error,
+            Some("2:21: No field with name `unknown`".to_string())
+        );
+
+        let (queries_count, error) =
+            validate_queries_file("SELECT 'title", None, ParserOptions::default());
+        assert_eq!(queries_count, 0);
+        assert!(error.is_some());
+    }
+
+    #[test]
+    fn test_validate_queries_file_with_schema() {
+        let schema = commits_schema();
+        let options = ParserOptions::default();
+
+        let result = validate_queries_file("SELECT title FROM commits", Some(&schema), options);
+        assert_eq!(result, (1, None));
+
+        let (_, error) = validate_queries_file("SELECT name FROM branches", Some(&schema), options);
+        assert_eq!(
+            error,
+            Some("1:18: Table `branches` doesn't exist in the schema".to_string())
+        );
+
+        let (_, error) =
+            validate_queries_file("SELECT unknown_field FROM commits", Some(&schema), options);
+        assert!(error.is_some());
+    }
+
+    #[test]
+    fn test_parse_data_type() {
+        assert!(matches!(parse_data_type("Text"), Some(DataType::Text)));
+        assert!(matches!(
+            parse_data_type("DATETIME"),
+            Some(DataType::DateTime)
+        ));
+        assert!(parse_data_type("varchar").is_none());
+    }
+}